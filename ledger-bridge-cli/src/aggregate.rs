@@ -0,0 +1,51 @@
+//! `aggregate` subcommand: export per-period and per-category totals of a
+//! statement without any individual transaction.
+
+use clap::Args;
+use ledger_parser::{AggregateReport, AnnotationStore, BucketPeriod, ParseError};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::input::InputArgs;
+
+/// Arguments of `ledger-bridge aggregate`
+#[derive(Args)]
+pub struct AggregateArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Bucket period of the totals: day or month
+    #[arg(long, value_name = "PERIOD", default_value_t = BucketPeriod::Day)]
+    period: BucketPeriod,
+
+    /// Annotation sidecar file (see `annotate`) supplying the categories
+    #[arg(long, value_name = "FILE")]
+    store: Option<PathBuf>,
+
+    /// Output file (default: stdout)
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<String>,
+}
+
+/// Run the `aggregate` subcommand, returning the number of aggregated transactions
+pub fn run(args: &AggregateArgs) -> Result<usize, ParseError> {
+    let statement = args.input.read()?;
+    let annotations = args
+        .store
+        .as_ref()
+        .map(AnnotationStore::from_path)
+        .transpose()?;
+
+    let report = AggregateReport::new(statement.as_view(), args.period, annotations.as_ref());
+    match &args.output {
+        Some(path) => report.write_to(&mut File::create(path)?)?,
+        None => {
+            let mut output = BufWriter::new(io::stdout().lock());
+            report.write_to(&mut output)?;
+            output.flush()?;
+        }
+    }
+
+    Ok(report.totals.count())
+}
@@ -0,0 +1,208 @@
+//! `annotate` subcommand: attach notes and categories to transactions and list
+//! them merged with the statement.
+
+use clap::{Args, ValueEnum};
+use ledger_parser::{fingerprints, AnnotationStore, ParseError, Transaction};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::input::InputArgs;
+use crate::locale::Locale;
+
+/// Arguments of `ledger-bridge annotate`
+#[derive(Args)]
+pub struct AnnotateArgs {
+    /// Annotation sidecar file (JSON); created on the first change
+    #[arg(long, value_name = "FILE")]
+    store: PathBuf,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Fingerprint of the transaction to annotate with --note and/or --category
+    #[arg(long, value_name = "FINGERPRINT", conflicts_with = "remove")]
+    set: Option<String>,
+
+    /// Note for the transaction given by --set
+    #[arg(long, value_name = "TEXT", requires = "set")]
+    note: Option<String>,
+
+    /// Category for the transaction given by --set
+    #[arg(long, value_name = "TEXT", requires = "set")]
+    category: Option<String>,
+
+    /// Fingerprint of the transaction whose annotation is deleted
+    #[arg(long, value_name = "FINGERPRINT")]
+    remove: Option<String>,
+
+    /// Output format for the annotated transactions
+    #[arg(long, value_enum, default_value_t = AnnotateOutput::Text)]
+    out: AnnotateOutput,
+
+    /// Number, date and currency conventions of text output: iso, en-US, en-GB, de-DE, fr-FR, ru-RU or sv-SE
+    #[arg(long, value_enum, ignore_case = true, default_value_t = Locale::Iso)]
+    locale: Locale,
+}
+
+/// Output formats supported by `annotate`
+#[derive(Clone, Copy, ValueEnum)]
+enum AnnotateOutput {
+    /// One line per transaction: fingerprint, date, direction, amount, description, category, note
+    Text,
+    /// JSON array of transactions with fingerprint, note and category
+    Json,
+    /// CSV table with fingerprint, transaction fields, note and category
+    Csv,
+}
+
+/// Transaction with its fingerprint and annotation, as written by the JSON output
+#[derive(Serialize)]
+struct AnnotatedTransaction<'a> {
+    fingerprint: &'a str,
+    #[serde(flatten)]
+    transaction: &'a Transaction,
+    note: Option<&'a str>,
+    category: Option<&'a str>,
+}
+
+/// Run the `annotate` subcommand, returning the number of annotated transactions
+pub fn run(args: &AnnotateArgs) -> Result<usize, ParseError> {
+    let statement = args.input.read()?;
+    let transactions = statement.as_view().transactions;
+    let keys = fingerprints(transactions);
+
+    let mut store = if args.store.exists() {
+        AnnotationStore::from_path(&args.store)?
+    } else {
+        AnnotationStore::new()
+    };
+
+    if let Some(fingerprint) = &args.set {
+        require_known(&keys, fingerprint)?;
+        let mut annotation = store.get(fingerprint).cloned().unwrap_or_default();
+        if let Some(note) = &args.note {
+            annotation.note = Some(note.clone()).filter(|note| !note.is_empty());
+        }
+        if let Some(category) = &args.category {
+            annotation.category = Some(category.clone()).filter(|category| !category.is_empty());
+        }
+        store.set(fingerprint, annotation);
+        store.write_to(&mut File::create(&args.store)?)?;
+    } else if let Some(fingerprint) = &args.remove {
+        if store.remove(fingerprint).is_some() {
+            store.write_to(&mut File::create(&args.store)?)?;
+        }
+    }
+
+    let annotated: Vec<AnnotatedTransaction<'_>> = transactions
+        .iter()
+        .zip(&keys)
+        .map(|(transaction, fingerprint)| {
+            let annotation = store.get(fingerprint);
+            AnnotatedTransaction {
+                fingerprint,
+                transaction,
+                note: annotation.and_then(|a| a.note.as_deref()),
+                category: annotation.and_then(|a| a.category.as_deref()),
+            }
+        })
+        .collect();
+
+    let mut output = BufWriter::new(io::stdout().lock());
+    match args.out {
+        AnnotateOutput::Text => {
+            for row in &annotated {
+                writeln!(output, "{}", format_line(row, args.locale))?;
+            }
+        }
+        AnnotateOutput::Json => {
+            serde_json::to_writer_pretty(&mut output, &annotated)
+                .map_err(|e| ParseError::InvalidFormat(format!("JSON output failed: {}", e)))?;
+            writeln!(output)?;
+        }
+        AnnotateOutput::Csv => write_csv(&mut output, &annotated)?,
+    }
+    output.flush()?;
+
+    Ok(annotated
+        .iter()
+        .filter(|row| row.note.is_some() || row.category.is_some())
+        .count())
+}
+
+/// Reject fingerprints that match no transaction of the statement (typos)
+fn require_known(keys: &[String], fingerprint: &str) -> Result<(), ParseError> {
+    if keys.iter().any(|key| key == fingerprint) {
+        Ok(())
+    } else {
+        Err(ParseError::InvalidFieldValue {
+            field: "fingerprint".into(),
+            value: fingerprint.into(),
+        })
+    }
+}
+
+fn format_line(row: &AnnotatedTransaction<'_>, locale: Locale) -> String {
+    let transaction = row.transaction;
+    format!(
+        "{}\t{}\t{:?}\t{}\t{}\t{}\t{}",
+        row.fingerprint,
+        locale.date(transaction.booking_date.date_naive()),
+        transaction.transaction_type,
+        locale.amount(transaction.amount),
+        transaction.description.replace('\n', " "),
+        row.category.unwrap_or_default(),
+        row.note.unwrap_or_default().replace('\n', " ")
+    )
+}
+
+fn write_csv<W: Write>(
+    writer: &mut W,
+    rows: &[AnnotatedTransaction<'_>],
+) -> Result<(), ParseError> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record([
+        "fingerprint",
+        "booking_date",
+        "direction",
+        "amount",
+        "description",
+        "reference",
+        "counterparty_name",
+        "category",
+        "note",
+    ])?;
+    for row in rows {
+        let transaction = row.transaction;
+        csv_writer.write_record([
+            row.fingerprint,
+            &transaction.booking_date.format("%Y-%m-%d").to_string(),
+            &format!("{:?}", transaction.transaction_type),
+            &format!("{:.2}", transaction.amount),
+            &transaction.description,
+            transaction.reference.as_deref().unwrap_or_default(),
+            transaction.counterparty_name.as_deref().unwrap_or_default(),
+            row.category.unwrap_or_default(),
+            row.note.unwrap_or_default(),
+        ])?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_known_fingerprint() {
+        let keys = vec!["4290dbcec6c55a51".to_string()];
+        assert!(require_known(&keys, "4290dbcec6c55a51").is_ok());
+        assert!(matches!(
+            require_known(&keys, "4290dbcec6c55a5"),
+            Err(ParseError::InvalidFieldValue { .. })
+        ));
+    }
+}
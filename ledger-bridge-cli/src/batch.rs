@@ -0,0 +1,277 @@
+//! `batch` subcommand: convert every statement file of a directory, reporting
+//! the files that fail instead of stopping at the first.
+
+use clap::Args;
+use glob::Pattern;
+use ledger_parser::ParseError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{run_job, ConvertArgs, Job, Outcome, DETECTABLE_EXTENSIONS};
+
+/// Arguments of `ledger-bridge batch`
+#[derive(Args)]
+pub struct BatchArgs {
+    /// Directory of the statement files to convert
+    #[arg(long, value_name = "DIR")]
+    input_dir: PathBuf,
+
+    /// Directory the converted files are written to, under their input names with the
+    /// output format as extension
+    #[arg(long, value_name = "DIR")]
+    output_dir: PathBuf,
+
+    /// Output format: csv, mt940, mt942, camt053, camt054, json or qif
+    #[arg(long, value_name = "FORMAT", required_unless_present = "preset")]
+    out_format: Option<String>,
+
+    /// Input format of every file: csv, mt940, mt942, camt053, camt052, camt054, bai2, json,
+    /// qif, xlsx, or auto
+    #[arg(long, value_name = "FORMAT", default_value = "auto")]
+    in_format: String,
+
+    /// Descend into subdirectories, mirroring them in the output directory
+    #[arg(long, short = 'r')]
+    recursive: bool,
+
+    /// Convert only files whose path below the input directory matches GLOB (e.g. `*.sta`),
+    /// instead of every file with a statement extension; may be repeated
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<Pattern>,
+
+    /// Leave out files whose path below the input directory matches GLOB; may be repeated
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<Pattern>,
+
+    #[command(flatten)]
+    convert: ConvertArgs,
+}
+
+/// Outcome of a batch run
+#[derive(Debug, Default, PartialEq)]
+pub struct BatchSummary {
+    /// Files converted
+    pub converted: usize,
+    /// Files left alone because every statement was converted before
+    pub duplicates: usize,
+    /// Files that failed to convert
+    pub failed: usize,
+    /// Transactions of the converted files
+    pub transactions: usize,
+}
+
+/// Run the `batch` subcommand: convert every file as the default conversion
+/// does, with its verification, filters, metrics and hooks, and print an error
+/// for every file that fails and the summary at the end, both to stderr
+pub fn run(args: &BatchArgs) -> Result<BatchSummary, ParseError> {
+    let out_format = args
+        .out_format
+        .as_deref()
+        .or(args.convert.preset.as_deref())
+        .unwrap_or_default();
+
+    let mut summary = BatchSummary::default();
+    // Input file of every output file written so far
+    let mut written: HashMap<PathBuf, PathBuf> = HashMap::new();
+    for relative in input_files(args)? {
+        let input = args.input_dir.join(&relative);
+        let output = args.output_dir.join(relative.with_extension(out_format));
+        let result = match written.get(&output) {
+            Some(earlier) => Err(ParseError::InvalidFormat(format!(
+                "{} is the output of {} as well",
+                output.display(),
+                earlier.display()
+            ))),
+            None => convert_file(&args.convert, &input, &output, &args.in_format, out_format),
+        };
+        match result {
+            Ok(Outcome::Converted(transactions)) => {
+                summary.converted += 1;
+                summary.transactions += transactions;
+                written.insert(output, input);
+            }
+            Ok(Outcome::Duplicate) => summary.duplicates += 1,
+            Err(error) => {
+                summary.failed += 1;
+                eprintln!("error: {}: {}", input.display(), error);
+            }
+        }
+    }
+
+    eprintln!(
+        "{} files converted ({} transactions), {} duplicates, {} failed",
+        summary.converted, summary.transactions, summary.duplicates, summary.failed
+    );
+    Ok(summary)
+}
+
+/// Convert `input` into `output`, creating the output's directory
+fn convert_file(
+    convert: &ConvertArgs,
+    input: &Path,
+    output: &Path,
+    in_format: &str,
+    out_format: &str,
+) -> Result<Outcome, ParseError> {
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let job = Job {
+        input: Some(input),
+        output: Some(output),
+        in_format,
+        out_format,
+        compress: None,
+    };
+    run_job(convert, &job)
+}
+
+/// Paths below the input directory of the files to convert, in name order
+fn input_files(args: &BatchArgs) -> Result<Vec<PathBuf>, ParseError> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(args.input_dir.join(&dir))? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let relative = dir.join(entry.file_name());
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() && args.recursive {
+                pending.push(relative);
+            } else if file_type.is_file() && selected(args, &relative) {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Whether the `--include` and `--exclude` globs select `relative`; without
+/// `--include`, files with a statement extension are selected
+fn selected(args: &BatchArgs, relative: &Path) -> bool {
+    let included = if args.include.is_empty() {
+        relative
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                DETECTABLE_EXTENSIONS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(extension))
+            })
+    } else {
+        args.include
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+    };
+    included
+        && !args
+            .exclude
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Command line of the subcommand alone
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: BatchArgs,
+    }
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+
+    #[test]
+    fn test_converts_selected_files_and_reports_failures() {
+        let root = std::env::temp_dir().join(format!("ledger-bridge-batch-{}", std::process::id()));
+        let input_dir = root.join("in");
+        fs::create_dir_all(input_dir.join("2025")).unwrap();
+        fs::write(input_dir.join("a.sta"), MT940).unwrap();
+        fs::write(input_dir.join("2025/b.sta"), MT940).unwrap();
+        fs::write(input_dir.join("2025/broken.sta"), "not a statement").unwrap();
+        fs::write(input_dir.join("notes.txt"), "ignored").unwrap();
+
+        let args = |extra: &[&str]| {
+            let mut argv = vec!["batch", "--out-format", "qif", "--input-dir"];
+            argv.push(input_dir.to_str().unwrap());
+            argv.push("--output-dir");
+            argv.push(root.to_str().unwrap());
+            argv.extend(extra);
+            Cli::parse_from(argv).args
+        };
+
+        let summary = run(&args(&[])).unwrap();
+        assert_eq!(summary.converted, 1);
+        assert!(root.join("a.qif").is_file());
+
+        let summary = run(&args(&["-r"])).unwrap();
+        let expected = BatchSummary {
+            converted: 2,
+            duplicates: 0,
+            failed: 1,
+            transactions: 2,
+        };
+        assert_eq!(summary, expected);
+        assert!(root.join("2025/b.qif").is_file());
+        // A failing file leaves no output behind, not even an empty one
+        assert!(!root.join("2025/broken.qif").exists());
+        assert!(!root.join("2025/broken.qif.tmp").exists());
+
+        let summary = run(&args(&[
+            "-r",
+            "--include",
+            "2025/*",
+            "--exclude",
+            "*broken*",
+        ]))
+        .unwrap();
+        assert_eq!((summary.converted, summary.failed), (1, 0));
+
+        // Filters and writer options of the conversion apply to every file
+        let summary = run(&args(&["--type", "credit", "--canonical-json"])).unwrap();
+        assert_eq!((summary.converted, summary.transactions), (1, 0));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_duplicates_leave_the_output_alone() {
+        let root =
+            std::env::temp_dir().join(format!("ledger-bridge-batch-dedup-{}", std::process::id()));
+        let input_dir = root.join("in");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("a.sta"), MT940).unwrap();
+        let cache = root.join("converted.json");
+        let argv = [
+            "batch",
+            "--out-format",
+            "qif",
+            "--input-dir",
+            input_dir.to_str().unwrap(),
+            "--output-dir",
+            root.to_str().unwrap(),
+            "--dedup-cache",
+            cache.to_str().unwrap(),
+        ];
+        let args = Cli::parse_from(argv).args;
+
+        assert_eq!(run(&args).unwrap().converted, 1);
+        fs::write(root.join("a.qif"), "kept").unwrap();
+        let summary = run(&args).unwrap();
+        assert_eq!((summary.converted, summary.duplicates), (0, 1));
+        assert_eq!(fs::read_to_string(root.join("a.qif")).unwrap(), "kept");
+
+        // Nor is it created when it is not there
+        fs::remove_file(root.join("a.qif")).unwrap();
+        assert_eq!(run(&args).unwrap().duplicates, 1);
+        assert!(!root.join("a.qif").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
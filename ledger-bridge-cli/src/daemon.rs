@@ -0,0 +1,560 @@
+//! `daemon` subcommand: serve conversions over a Unix socket so services doing
+//! many small conversions skip the process start-up of every CLI call.
+//!
+//! # Protocol
+//!
+//! Each message, in both directions, is a 4-byte big-endian length followed by
+//! that many bytes of UTF-8 JSON. A connection carries any number of requests,
+//! answered in order; clients may keep it open or reconnect per request.
+//!
+//! ```json
+//! {"in_format": "mt940", "out_format": "camt053", "input": ":20:REF\n..."}
+//! ```
+//!
+//! Optional request keys: `csv_profile`, `mt940_dialect`, `mt940_charset`,
+//! `camt053_profile`, `camt053_booking_time`, `canonical_json`,
+//! `no_sanitize_formulas` and `strict`, as on the command line; `daemon --strict`
+//! makes every request strict. Replies are
+//! `{"ok": true, "output": "...", "transactions": 3, "warnings": []}`, with the
+//! records a lenient parse skipped in `warnings`, or
+//! `{"ok": false, "error_code": "mt940_error", "error": "..."}`.
+
+use clap::Args;
+use ledger_parser::{
+    Camt053Profile, Camt053Statement, CsvDialect, Mt940Charset, Mt940Dialect, ParseError, Statement,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::metrics::ConversionMetrics;
+use crate::{csv_dialect, parse_options, verify, write_output, OutputOptions, STRICT_HELP};
+
+/// Default largest accepted request, so a corrupt length prefix cannot exhaust memory
+const DEFAULT_MAX_FRAME_BYTES: u32 = 4 * 1024 * 1024;
+
+/// Default number of connections served at once
+const DEFAULT_MAX_CONNECTIONS: NonZeroUsize = NonZeroUsize::new(16).unwrap();
+
+/// Permissions of the socket file: only the user running the daemon may connect
+#[cfg(unix)]
+const SOCKET_MODE: u32 = 0o600;
+
+/// Permissions of the directory the socket is bound in before it is moved into
+/// place, so it is never reachable with the permissions of the process umask
+#[cfg(unix)]
+const BIND_DIR_MODE: u32 = 0o700;
+
+/// Name of the socket inside that directory
+#[cfg(unix)]
+const BIND_NAME: &str = "socket";
+
+/// Arguments of `ledger-bridge daemon`
+#[derive(Args)]
+pub struct DaemonArgs {
+    /// Unix socket to listen on; a stale socket file at this path is replaced, but
+    /// not one a daemon still listens on
+    #[arg(long, value_name = "PATH")]
+    socket: PathBuf,
+
+    /// Parse every request strictly, as if it set `"strict": true`
+    #[arg(long, long_help = STRICT_HELP)]
+    strict: bool,
+
+    /// Add Prometheus metrics of every request to FILE (node_exporter textfile format)
+    #[arg(long, value_name = "FILE")]
+    metrics_file: Option<PathBuf>,
+
+    /// Largest request accepted, in bytes; a connection sending a larger one is closed
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_FRAME_BYTES)]
+    max_frame_bytes: u32,
+
+    /// Connections served at once; further clients wait until one closes
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_CONNECTIONS)]
+    max_connections: NonZeroUsize,
+}
+
+/// Settings shared by the connections of a daemon
+struct Service {
+    /// Parse every request strictly
+    strict: bool,
+    /// Largest request accepted, in bytes
+    max_frame_bytes: u32,
+    /// File the metrics of every request are added to
+    metrics_file: Option<PathBuf>,
+    /// Serializes the read-modify-write of the metrics file across connections
+    metrics_lock: Mutex<()>,
+}
+
+impl Default for Service {
+    fn default() -> Self {
+        Service {
+            strict: false,
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
+            metrics_file: None,
+            metrics_lock: Mutex::new(()),
+        }
+    }
+}
+
+/// One conversion request
+#[derive(Deserialize)]
+struct ConvertRequest {
+    in_format: String,
+    out_format: String,
+    input: String,
+    #[serde(default)]
+    csv_profile: Option<String>,
+    #[serde(default)]
+    mt940_dialect: Option<String>,
+    #[serde(default)]
+    mt940_charset: Option<String>,
+    #[serde(default)]
+    camt053_profile: Option<String>,
+    #[serde(default)]
+    camt053_booking_time: bool,
+    #[serde(default)]
+    canonical_json: bool,
+    #[serde(default)]
+    no_sanitize_formulas: bool,
+    #[serde(default)]
+    strict: bool,
+}
+
+/// Reply to a request
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum ConvertReply {
+    Converted {
+        ok: bool,
+        output: String,
+        transactions: usize,
+        /// Records the lenient parser skipped
+        warnings: Vec<String>,
+    },
+    Failed {
+        ok: bool,
+        error_code: &'static str,
+        error: String,
+    },
+}
+
+/// Run the `daemon` subcommand until the process is stopped
+#[cfg(unix)]
+pub fn run(args: &DaemonArgs) -> Result<(), ParseError> {
+    let listener = bind(&args.socket)?;
+    eprintln!(
+        "ledger-bridge daemon listening on {}",
+        args.socket.display()
+    );
+
+    let service = Arc::new(Service {
+        strict: args.strict,
+        max_frame_bytes: args.max_frame_bytes,
+        metrics_file: args.metrics_file.clone(),
+        metrics_lock: Mutex::new(()),
+    });
+    // One permit per connection served at once, handed back when it closes
+    let (release, permits) = std::sync::mpsc::sync_channel(args.max_connections.get());
+    for _ in 0..args.max_connections.get() {
+        let _ = release.send(());
+    }
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if permits.recv().is_err() {
+                    break;
+                }
+                let service = Arc::clone(&service);
+                let release = release.clone();
+                std::thread::spawn(move || {
+                    // The permit is returned however the connection ends
+                    let _permit = Permit(release);
+                    let (mut reader, mut writer) = match stream.try_clone() {
+                        Ok(reader) => (reader, stream),
+                        Err(e) => {
+                            eprintln!("warning: daemon connection failed: {}", e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = service.serve(&mut reader, &mut writer) {
+                        eprintln!("warning: daemon connection failed: {}", e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("warning: daemon failed to accept a connection: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Listen on `socket`, accessible only to the user running the daemon.
+///
+/// The socket is bound in a fresh directory only the user may enter, restricted
+/// to [`SOCKET_MODE`] and then moved to `socket`, replacing a socket left
+/// behind by a previous run; a socket some daemon still answers on is kept.
+#[cfg(unix)]
+fn bind(socket: &std::path::Path) -> Result<std::os::unix::net::UnixListener, ParseError> {
+    use std::fs;
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if let Ok(metadata) = fs::symlink_metadata(socket) {
+        if !metadata.file_type().is_socket() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", socket.display()),
+            )
+            .into());
+        }
+        if UnixStream::connect(socket).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a daemon is already listening on {}", socket.display()),
+            )
+            .into());
+        }
+    }
+
+    let parent = socket
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    let private = parent.join(format!(".ledger-bridge-{}", std::process::id()));
+    fs::DirBuilder::new().mode(BIND_DIR_MODE).create(&private)?;
+    let bound = private.join(BIND_NAME);
+    let listener = UnixListener::bind(&bound).and_then(|listener| {
+        fs::set_permissions(&bound, fs::Permissions::from_mode(SOCKET_MODE))?;
+        fs::rename(&bound, socket)?;
+        Ok(listener)
+    });
+    // Only the bound socket can be in the directory; both are gone once moved
+    fs::remove_file(&bound).ok();
+    let removed = fs::remove_dir(&private);
+    let listener = listener?;
+    removed?;
+    Ok(listener)
+}
+
+/// Connection slot of the daemon, handed back to the acceptor when dropped
+#[cfg(unix)]
+struct Permit(std::sync::mpsc::SyncSender<()>);
+
+#[cfg(unix)]
+impl Drop for Permit {
+    fn drop(&mut self) {
+        // The acceptor only stops listening when the process exits
+        let _ = self.0.send(());
+    }
+}
+
+/// Run the `daemon` subcommand; Unix sockets are not available on this platform
+#[cfg(not(unix))]
+pub fn run(_args: &DaemonArgs) -> Result<(), ParseError> {
+    Err(ParseError::InvalidFormat(
+        "daemon mode needs Unix domain sockets, which this platform lacks".into(),
+    ))
+}
+
+impl Service {
+    /// Answer requests from `reader` on `writer` until the client hangs up
+    fn serve<R: Read, W: Write>(&self, reader: &mut R, writer: &mut W) -> io::Result<()> {
+        while let Some(frame) = read_frame(reader, self.max_frame_bytes)? {
+            let reply = self.handle(&frame);
+            let payload = serde_json::to_vec(&reply).map_err(io::Error::other)?;
+            write_frame(writer, &payload)?;
+        }
+        Ok(())
+    }
+
+    /// Reply to one raw request, adding its outcome to the metrics file
+    fn handle(&self, frame: &[u8]) -> ConvertReply {
+        let started = Instant::now();
+        let result = serde_json::from_slice::<ConvertRequest>(frame)
+            .map_err(|e| ParseError::JsonError(format!("Invalid daemon request: {}", e)))
+            .and_then(|request| convert(&request, self.strict));
+        if let Some(path) = &self.metrics_file {
+            let mut metrics = ConversionMetrics::default();
+            let transactions = result.as_ref().map(|(_, transactions, _)| *transactions);
+            metrics.record(transactions, started.elapsed());
+            // A poisoned lock only means another connection panicked mid-write
+            let _guard = self.metrics_lock.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = metrics.add_to_file(path) {
+                eprintln!(
+                    "warning: failed to write metrics to {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+
+        match result {
+            Ok((output, transactions, warnings)) => ConvertReply::Converted {
+                ok: true,
+                output,
+                transactions,
+                warnings,
+            },
+            Err(error) => ConvertReply::Failed {
+                ok: false,
+                error_code: error.code(),
+                error: error.to_string(),
+            },
+        }
+    }
+}
+
+/// Next length-prefixed message of at most `max_bytes`, or `None` when the stream
+/// ends between messages
+fn read_frame<R: Read>(reader: &mut R, max_bytes: u32) -> io::Result<Option<Vec<u8>>> {
+    let mut prefix = [0u8; 4];
+    match reader.read_exact(&mut prefix) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let length = u32::from_be_bytes(prefix);
+    if length > max_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes exceeds {}", length, max_bytes),
+        ));
+    }
+    let mut frame = vec![0u8; length as usize];
+    reader.read_exact(&mut frame)?;
+    Ok(Some(frame))
+}
+
+/// Write a length-prefixed message; replies are only bounded by the 4-byte prefix,
+/// since converted output can be much larger than its request
+fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "reply too large"))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Convert the request's input, returning the output, its transaction count and
+/// the records skipped while parsing; strict requests also have their output
+/// verified
+fn convert(
+    request: &ConvertRequest,
+    strict: bool,
+) -> Result<(String, usize, Vec<String>), ParseError> {
+    let strict = strict || request.strict;
+    let parse = parse_options(strict);
+    if let Some(name) = &request.csv_profile {
+        if CsvDialect::by_name(name).is_none() {
+            return Err(ParseError::InvalidFieldValue {
+                field: "csv_profile".into(),
+                value: name.clone(),
+            });
+        }
+    }
+    let dialect = csv_dialect(&request.csv_profile, &parse);
+    let options = OutputOptions {
+        split_every: None,
+        mt940_dialect: parse_option::<Mt940Dialect>("mt940_dialect", &request.mt940_dialect)?,
+        mt940_charset: parse_option::<Mt940Charset>("mt940_charset", &request.mt940_charset)?,
+        camt053_profile: parse_option::<Camt053Profile>(
+            "camt053_profile",
+            &request.camt053_profile,
+        )?,
+        camt053_booking_time: request.camt053_booking_time,
+        sanitize_formulas: !request.no_sanitize_formulas,
+        canonical_json: request.canonical_json,
+    };
+
+    // Skipped records go back to the client, not to the daemon's stderr
+    let (statement, warnings) = Statement::from_read_with_options(
+        &mut request.input.as_bytes(),
+        &request.in_format,
+        Some(&dialect),
+        &parse,
+    )?;
+    let warnings = warnings.iter().map(ToString::to_string).collect();
+    let transactions = statement.transactions().len();
+    let original = strict.then(|| Camt053Statement::from(statement.clone()));
+    let mut output = Vec::new();
+    write_output(statement, &mut output, &request.out_format, &options)?;
+    if let Some(original) = original {
+        verify::verify(&original, &output, &request.out_format, &options)?;
+    }
+    let output = String::from_utf8(output)
+        .map_err(|e| ParseError::InvalidFormat(format!("Output is not UTF-8: {}", e)))?;
+    Ok((output, transactions, warnings))
+}
+
+/// Optional request value parsed like the matching command-line option
+fn parse_option<T>(field: &str, value: &Option<String>) -> Result<T, ParseError>
+where
+    T: std::str::FromStr + Default,
+{
+    match value {
+        Some(text) => text.parse().map_err(|_| ParseError::InvalidFieldValue {
+            field: field.into(),
+            value: text.clone(),
+        }),
+        None => Ok(T::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(payload: &str) -> Vec<u8> {
+        let mut bytes = (payload.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(payload.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_serve_answers_each_frame_in_order() {
+        let convert = serde_json::json!({
+            "in_format": "mt940",
+            "out_format": "json",
+            "input": ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                      :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n",
+        });
+        let mut input = frame(&convert.to_string());
+        input.extend(frame(
+            r#"{"in_format": "mt940", "out_format": "json", "input": "x"}"#,
+        ));
+
+        let mut output = Vec::new();
+        Service::default()
+            .serve(&mut input.as_slice(), &mut output)
+            .unwrap();
+
+        let mut replies = output.as_slice();
+        let first: serde_json::Value = serde_json::from_slice(
+            &read_frame(&mut replies, DEFAULT_MAX_FRAME_BYTES)
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first["ok"], true);
+        assert_eq!(first["transactions"], 1);
+        assert!(first["output"].as_str().unwrap().contains("\"Lunch\""));
+
+        let second: serde_json::Value = serde_json::from_slice(
+            &read_frame(&mut replies, DEFAULT_MAX_FRAME_BYTES)
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(second["ok"], false);
+        assert_eq!(second["error_code"], "mt940_error");
+        assert!(read_frame(&mut replies, DEFAULT_MAX_FRAME_BYTES)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_strict_request_fails_on_skipped_record() {
+        let request = |strict: bool| {
+            serde_json::json!({
+                "in_format": "mt940",
+                "out_format": "json",
+                "input": ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                          :61:200132C5,00NTRFNONREF\n:62F:C200102EUR100,00\n",
+                "strict": strict,
+            })
+            .to_string()
+        };
+
+        let lenient = Service::default().handle(request(false).as_bytes());
+        let ConvertReply::Converted {
+            transactions: 0,
+            warnings,
+            ..
+        } = lenient
+        else {
+            panic!("lenient request failed: {:?}", lenient);
+        };
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].starts_with("line 4, field 61"),
+            "{:?}",
+            warnings
+        );
+        for (request, strict) in [(request(true), false), (request(false), true)] {
+            let service = Service {
+                strict,
+                ..Default::default()
+            };
+            let ConvertReply::Failed { error, .. } = service.handle(request.as_bytes()) else {
+                panic!("strict request converted");
+            };
+            assert!(error.starts_with("line 4, field 61"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_csv_output_is_sanitized_unless_opted_out() {
+        let request = |no_sanitize_formulas: bool| {
+            serde_json::json!({
+                "in_format": "mt940",
+                "out_format": "csv",
+                "input": ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                          :61:200102D25,00NTRFNONREF\n:86:=1+2\n:62F:C200102EUR75,00\n",
+                "no_sanitize_formulas": no_sanitize_formulas,
+            })
+            .to_string()
+        };
+
+        for (no_sanitize_formulas, cell) in [(false, ",'=1+2"), (true, ",=1+2")] {
+            let reply = Service::default().handle(request(no_sanitize_formulas).as_bytes());
+            let ConvertReply::Converted { output, .. } = reply else {
+                panic!("csv request failed: {:?}", reply);
+            };
+            assert!(output.contains(cell), "{}", output);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bind_is_private_and_keeps_a_live_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root =
+            std::env::temp_dir().join(format!("ledger-bridge-daemon-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let socket = root.join("daemon.sock");
+
+        let listener = bind(&socket).unwrap();
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, SOCKET_MODE);
+        let error = bind(&socket).unwrap_err();
+        assert!(error.to_string().contains("already listening"), "{}", error);
+
+        // Once its daemon is gone, the socket is stale and replaced
+        drop(listener);
+        let _listener = bind(&socket).unwrap();
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 1);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_and_malformed_requests() {
+        let oversized = (DEFAULT_MAX_FRAME_BYTES + 1).to_be_bytes();
+        assert!(read_frame(&mut oversized.as_slice(), DEFAULT_MAX_FRAME_BYTES).is_err());
+        let small = frame("{}");
+        assert!(read_frame(&mut small.as_slice(), 1).is_err());
+
+        let reply = Service::default().handle(b"not json");
+        assert!(matches!(
+            reply,
+            ConvertReply::Failed {
+                error_code: "json_error",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! Duplicate statement detection across conversion runs.
+//!
+//! Banks re-deliver files: a retried SFTP upload, the same export fetched twice.
+//! With `--dedup-cache FILE`, every parsed statement is hashed over its
+//! canonical JSON, so re-encoded or re-wrapped copies of the same statement
+//! hash alike, and recorded in FILE, a small JSON database of the statements
+//! seen. A statement already recorded is skipped with a "duplicate of X"
+//! report on stderr instead of being converted again.
+
+use chrono::{SecondsFormat, Utc};
+use ledger_parser::{Camt053Statement, JsonStatement, ParseError, Statement};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Version of the cache file layout
+const CACHE_SCHEMA_VERSION: u32 = 1;
+/// Statements the cache remembers; the oldest are forgotten beyond this
+const MAX_CACHE_ENTRIES: usize = 10_000;
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A statement converted before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Hash of the statement's canonical JSON
+    pub hash: String,
+    /// Input file the statement came from, `stdin` for standard input
+    pub source: String,
+    /// When the statement was first converted (RFC 3339, UTC)
+    pub first_seen: String,
+}
+
+/// Layout of the cache file
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    schema_version: u32,
+    statements: Vec<CacheEntry>,
+}
+
+/// Statements converted by earlier runs, oldest first
+#[derive(Debug)]
+pub struct DedupCache {
+    path: PathBuf,
+    entries: Vec<CacheEntry>,
+}
+
+impl DedupCache {
+    /// Open the cache at `path`; a missing file is an empty cache
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let path = path.as_ref().to_path_buf();
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(DedupCache {
+                    path,
+                    entries: Vec::new(),
+                })
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let cache: CacheFile = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| ParseError::JsonError(format!("Invalid dedup cache: {}", e)))?;
+        if cache.schema_version > CACHE_SCHEMA_VERSION {
+            return Err(ParseError::JsonError(format!(
+                "Unsupported dedup cache schema_version {} (newest supported: {})",
+                cache.schema_version, CACHE_SCHEMA_VERSION
+            )));
+        }
+        Ok(DedupCache {
+            path,
+            entries: cache.statements,
+        })
+    }
+
+    /// Earlier conversion of the statement hashed to `hash`
+    pub fn get(&self, hash: &str) -> Option<&CacheEntry> {
+        self.entries.iter().find(|entry| entry.hash == hash)
+    }
+
+    /// Keep the statements not converted before, recording them as coming from
+    /// `source`, and report every duplicate on stderr
+    pub fn retain_new(
+        &mut self,
+        statements: Vec<Statement>,
+        source: &str,
+    ) -> Result<Vec<Statement>, ParseError> {
+        let mut new = Vec::with_capacity(statements.len());
+        for statement in statements {
+            let hash = statement_hash(&statement)?;
+            match self.get(&hash) {
+                Some(entry) => eprintln!(
+                    "skipped statement of {}: duplicate of {} (converted {})",
+                    source, entry.source, entry.first_seen
+                ),
+                None => {
+                    self.entries.push(CacheEntry {
+                        hash,
+                        source: source.to_string(),
+                        first_seen: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                    });
+                    new.push(statement);
+                }
+            }
+        }
+        Ok(new)
+    }
+
+    /// Write the cache back, forgetting the oldest statements beyond
+    /// [`MAX_CACHE_ENTRIES`]; the file is replaced in one step, so a crash
+    /// never leaves half a cache behind
+    pub fn save(mut self) -> Result<(), ParseError> {
+        let excess = self.entries.len().saturating_sub(MAX_CACHE_ENTRIES);
+        self.entries.drain(..excess);
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+        let mut writer = BufWriter::new(File::create(&temporary)?);
+        let cache = CacheFile {
+            schema_version: CACHE_SCHEMA_VERSION,
+            statements: self.entries,
+        };
+        serde_json::to_writer_pretty(&mut writer, &cache)
+            .map_err(|e| ParseError::JsonError(e.to_string()))?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+/// FNV-1a hash of the statement's canonical JSON, as 16 hex digits
+fn statement_hash(statement: &Statement) -> Result<String, ParseError> {
+    let mut canonical = Vec::new();
+    JsonStatement::from(Camt053Statement::from(statement.clone()))
+        .write_canonical(&mut canonical)?;
+    let hash = canonical.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    Ok(format!("{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use ledger_parser::{CsvDialect, ParseOptions};
+
+    fn statement(closing: &str) -> Statement {
+        let data = format!(
+            ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+             :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR{}\n",
+            closing
+        );
+        parse_input(
+            &mut data.as_bytes(),
+            "mt940",
+            &CsvDialect::default(),
+            &ParseOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_duplicates_are_skipped_across_runs() {
+        let dir = std::env::temp_dir().join(format!("ledger-bridge-dedup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.json");
+
+        let mut cache = DedupCache::open(&path).unwrap();
+        let kept = cache
+            .retain_new(vec![statement("75,00"), statement("75,00")], "a.mt940")
+            .unwrap();
+        assert_eq!(kept.len(), 1);
+        cache.save().unwrap();
+
+        let mut cache = DedupCache::open(&path).unwrap();
+        let kept = cache
+            .retain_new(vec![statement("75,00"), statement("76,00")], "b.mt940")
+            .unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].as_view().closing_balance, 76.0);
+        assert_eq!(cache.entries[0].source, "a.mt940");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+//! `explain` subcommand: print the normalized dump of a statement for bug
+//! reports, so the input and the output of a conversion can be diffed.
+
+use clap::Args;
+use ledger_parser::{debug, ParseError, Statement};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::input::InputArgs;
+
+/// Arguments of `ledger-bridge explain`
+#[derive(Args)]
+pub struct ExplainArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Output file (default: stdout)
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<String>,
+}
+
+/// Run the `explain` subcommand, returning the number of explained statements
+pub fn run(args: &ExplainArgs) -> Result<usize, ParseError> {
+    let statements = args.input.read_all()?;
+
+    match &args.output {
+        Some(path) => write_dumps(&mut BufWriter::new(File::create(path)?), &statements)?,
+        None => write_dumps(&mut BufWriter::new(io::stdout().lock()), &statements)?,
+    }
+    Ok(statements.len())
+}
+
+/// Dumps of `statements`, separated by an empty line
+fn write_dumps<W: Write>(writer: &mut W, statements: &[Statement]) -> Result<(), ParseError> {
+    for (index, statement) in statements.iter().enumerate() {
+        if index > 0 {
+            writeln!(writer)?;
+        }
+        writer.write_all(debug::explain(statement.as_view()).as_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_all_input;
+    use ledger_parser::{CsvDialect, ParseOptions};
+
+    #[test]
+    fn test_every_statement_is_dumped() {
+        let data = ":20:A\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n\
+                    :20:B\n:25:NL81ASNB9999999999\n:60F:C200102EUR75,00\n:62F:C200102EUR75,00\n";
+        let statements = parse_all_input(
+            &mut data.as_bytes(),
+            "mt940",
+            &CsvDialect::default(),
+            &ParseOptions::default(),
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        write_dumps(&mut output, &statements).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("account: ").count(), 2);
+        assert!(output.contains("transactions: 1\n[1] 2020-01-02 D 25.00 EUR\n"));
+        assert!(output.contains("\n\naccount: "));
+    }
+}
@@ -0,0 +1,196 @@
+//! Transaction filters of the conversion: keep only the entries of a date
+//! range, amount range, direction or description, applied after parsing and
+//! before writing.
+//!
+//! The date range narrows the statement's period: its opening balance moves
+//! forward over the entries booked before `--from-date`, so both balances stay
+//! the account's real balances. The other filters leave entries out of the
+//! period, so the closing balance they produce is synthetic: the opening
+//! balance plus the entries kept, which the account never held.
+
+use chrono::NaiveDate;
+use clap::{Args, ValueEnum};
+use ledger_parser::{Statement, Transaction, TransactionType};
+
+/// Direction of the `--type` filter
+#[derive(Clone, Copy, ValueEnum)]
+pub enum EntryType {
+    /// Incoming money
+    Credit,
+    /// Outgoing money
+    Debit,
+}
+
+impl EntryType {
+    fn matches(self, transaction_type: &TransactionType) -> bool {
+        matches!(
+            (self, transaction_type),
+            (EntryType::Credit, TransactionType::Credit)
+                | (EntryType::Debit, TransactionType::Debit)
+        )
+    }
+}
+
+/// Filter options of the conversion
+#[derive(Args)]
+pub struct FilterArgs {
+    /// Keep transactions booked on or after DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    from_date: Option<NaiveDate>,
+
+    /// Keep transactions booked on or before DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    to_date: Option<NaiveDate>,
+
+    /// Keep transactions of at least AMOUNT, credit or debit
+    #[arg(long, value_name = "AMOUNT")]
+    min_amount: Option<f64>,
+
+    /// Keep transactions of at most AMOUNT, credit or debit
+    #[arg(long, value_name = "AMOUNT")]
+    max_amount: Option<f64>,
+
+    /// Keep credit or debit transactions only
+    #[arg(long = "type", value_name = "TYPE", value_enum)]
+    entry_type: Option<EntryType>,
+
+    /// Keep transactions whose description contains TEXT (case-insensitive)
+    #[arg(long, value_name = "TEXT")]
+    description_contains: Option<String>,
+
+    /// Keep the balances of the input instead of moving them to the date range and
+    /// recomputing the closing balance from the transactions left (which makes it
+    /// synthetic with the amount, type and description filters)
+    #[arg(long)]
+    keep_balances: bool,
+}
+
+impl FilterArgs {
+    /// Whether a filter other than the date range is given
+    fn filters_entries(&self) -> bool {
+        self.min_amount.is_some()
+            || self.max_amount.is_some()
+            || self.entry_type.is_some()
+            || self.description_contains.is_some()
+    }
+
+    /// Leave out the transactions of `statement` the filters reject and, unless
+    /// `--keep-balances` is given, move its balances to the date range and
+    /// recompute the closing balance from the transactions left
+    pub fn apply(&self, statement: &mut Statement) {
+        if self.from_date.is_some() || self.to_date.is_some() {
+            if self.keep_balances {
+                let (from, to) = (self.from_date, self.to_date);
+                statement.transactions_mut().retain(|transaction| {
+                    let date = transaction.booking_date.date_naive();
+                    from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+                });
+            } else {
+                statement.retain_booked_between(self.from_date, self.to_date);
+            }
+        }
+        if !self.filters_entries() {
+            return;
+        }
+        let description = self.description_contains.as_deref().map(str::to_lowercase);
+        statement
+            .transactions_mut()
+            .retain(|transaction| self.keeps(transaction, description.as_deref()));
+        if !self.keep_balances {
+            statement.recompute_closing_balance();
+        }
+    }
+
+    /// Whether `transaction` passes the filters other than the date range;
+    /// `description` is the lowercase `--description-contains` text
+    fn keeps(&self, transaction: &Transaction, description: Option<&str>) -> bool {
+        self.min_amount.is_none_or(|min| transaction.amount >= min)
+            && self.max_amount.is_none_or(|max| transaction.amount <= max)
+            && self
+                .entry_type
+                .is_none_or(|entry_type| entry_type.matches(&transaction.transaction_type))
+            && description.is_none_or(|text| transaction.description.to_lowercase().contains(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use ledger_parser::EntryStatus;
+
+    /// Command line of the filter options alone
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: FilterArgs,
+    }
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:200102D25,00NTRFNONREF\n:86:Lunch\n\
+                         :61:200103C40,00NTRFNONREF\n:86:Refund lunch\n\
+                         :61:200110D5,00NTRFNONREF\n:86:Coffee\n:62F:C200110EUR110,00\n";
+
+    fn filtered(argv: &[&str]) -> Statement {
+        let mut statement = Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+        let args = Cli::parse_from([&["filter"], argv].concat()).args;
+        args.apply(&mut statement);
+        statement
+    }
+
+    #[test]
+    fn test_from_date_rolls_opening_balance_forward() {
+        let statement = filtered(&["--from-date", "2020-01-03"]);
+        let view = statement.as_view();
+        assert_eq!(view.transactions.len(), 2);
+        assert_eq!(view.opening_balance, 75.0);
+        assert_eq!(view.opening_date.date_naive().to_string(), "2020-01-02");
+        assert_eq!(view.closing_balance, 110.0);
+
+        let statement = filtered(&["--from-date", "2020-01-03", "--to-date", "2020-01-05"]);
+        let view = statement.as_view();
+        assert_eq!((view.opening_balance, view.closing_balance), (75.0, 115.0));
+        assert_eq!(view.closing_date.date_naive().to_string(), "2020-01-03");
+    }
+
+    #[test]
+    fn test_filters_recompute_closing_balance() {
+        let statement = filtered(&["--description-contains", "LUNCH", "--to-date", "2020-01-05"]);
+        assert_eq!(statement.transactions().len(), 2);
+        assert_eq!(statement.as_view().closing_balance, 115.0);
+
+        let statement = filtered(&["--type", "debit", "--min-amount", "10"]);
+        assert_eq!(statement.transactions().len(), 1);
+        assert_eq!(statement.as_view().closing_balance, 75.0);
+
+        let statement = filtered(&["--from-date", "2020-01-03", "--keep-balances"]);
+        assert_eq!(statement.transactions().len(), 2);
+        assert_eq!(statement.as_view().closing_balance, 110.0);
+    }
+
+    #[test]
+    fn test_pending_entry_leaves_balances_alone() {
+        let mut statement = Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+        let pending = Transaction {
+            amount: 50.0,
+            status: EntryStatus::Pending,
+            ..statement.transactions()[0].clone()
+        };
+        statement.transactions_mut().push(pending);
+        let args = Cli::parse_from(["filter", "--min-amount", "1"]).args;
+        args.apply(&mut statement);
+        assert_eq!(statement.transactions().len(), 4);
+        assert_eq!(statement.as_view().closing_balance, 110.0);
+
+        let args = Cli::parse_from(["filter", "--from-date", "2020-01-03"]).args;
+        args.apply(&mut statement);
+        assert_eq!(statement.as_view().opening_balance, 75.0);
+    }
+
+    #[test]
+    fn test_no_filter_keeps_statement() {
+        let statement = filtered(&["--keep-balances"]);
+        assert_eq!(statement.transactions().len(), 3);
+        assert_eq!(statement.as_view().closing_balance, 110.0);
+    }
+}
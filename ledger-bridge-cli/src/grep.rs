@@ -0,0 +1,149 @@
+//! `grep` subcommand: print the transactions of a statement matching a pattern.
+
+use clap::{Args, ValueEnum};
+use ledger_parser::{ParseError, Statement, StatementRef, Transaction};
+use std::io::{self, BufWriter, Write};
+
+use crate::input::InputArgs;
+use crate::locale::Locale;
+use crate::{write_output, OutputOptions};
+
+/// Arguments of `ledger-bridge grep`
+#[derive(Args)]
+pub struct GrepArgs {
+    /// Text to look for in description, reference and counterparty name
+    pattern: String,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Output format for the matching transactions
+    #[arg(long, value_enum, default_value_t = GrepOutput::Text)]
+    out: GrepOutput,
+
+    /// Match case exactly (matching is case-insensitive by default)
+    #[arg(long)]
+    case_sensitive: bool,
+
+    /// Rank matches by relevance (any word of PATTERN) instead of substring matching
+    #[arg(long)]
+    ranked: bool,
+
+    /// Number, date and currency conventions of text output: iso, en-US, en-GB, de-DE, fr-FR, ru-RU or sv-SE
+    #[arg(long, value_enum, ignore_case = true, default_value_t = Locale::Iso)]
+    locale: Locale,
+}
+
+/// Output formats supported by `grep`
+#[derive(Clone, Copy, ValueEnum)]
+enum GrepOutput {
+    /// One line per transaction: date, direction, amount, description
+    Text,
+    /// JSON array of transactions
+    Json,
+    /// CSV statement holding only the matches (balances are copied from the source)
+    Csv,
+    /// MT940 statement holding only the matches (balances are copied from the source)
+    Mt940,
+    /// CAMT.053 statement holding only the matches (balances are copied from the source)
+    Camt053,
+    /// QIF statement holding only the matches
+    Qif,
+}
+
+/// Run the `grep` subcommand
+pub fn run(args: &GrepArgs) -> Result<usize, ParseError> {
+    let statement = args.input.read()?;
+
+    let matches = matching_indices(statement.as_view(), args);
+    let count = matches.len();
+    let statement = retain_transactions(statement, &matches);
+
+    let mut output = BufWriter::new(io::stdout().lock());
+    match args.out {
+        GrepOutput::Text => {
+            for transaction in statement.as_view().transactions {
+                writeln!(output, "{}", format_line(transaction, args.locale))?;
+            }
+        }
+        GrepOutput::Json => {
+            serde_json::to_writer_pretty(&mut output, statement.as_view().transactions)
+                .map_err(|e| ParseError::InvalidFormat(format!("JSON output failed: {}", e)))?;
+            writeln!(output)?;
+        }
+        GrepOutput::Csv => write_output(statement, &mut output, "csv", &OutputOptions::default())?,
+        GrepOutput::Mt940 => {
+            write_output(statement, &mut output, "mt940", &OutputOptions::default())?
+        }
+        GrepOutput::Camt053 => {
+            write_output(statement, &mut output, "camt053", &OutputOptions::default())?
+        }
+        GrepOutput::Qif => write_output(statement, &mut output, "qif", &OutputOptions::default())?,
+    }
+    output.flush()?;
+
+    Ok(count)
+}
+
+/// Indices of matching transactions, in output order
+fn matching_indices(statement: StatementRef<'_>, args: &GrepArgs) -> Vec<usize> {
+    if args.ranked {
+        return statement
+            .search(&args.pattern)
+            .into_iter()
+            .map(|hit| hit.index)
+            .collect();
+    }
+
+    let pattern = if args.case_sensitive {
+        args.pattern.clone()
+    } else {
+        args.pattern.to_lowercase()
+    };
+    statement
+        .transactions
+        .iter()
+        .enumerate()
+        .filter(|(_, transaction)| {
+            searchable_fields(transaction).any(|field| {
+                if args.case_sensitive {
+                    field.contains(&pattern)
+                } else {
+                    field.to_lowercase().contains(&pattern)
+                }
+            })
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn searchable_fields(transaction: &Transaction) -> impl Iterator<Item = &str> {
+    [
+        Some(transaction.description.as_str()),
+        transaction.reference.as_deref(),
+        transaction.counterparty_name.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+fn format_line(transaction: &Transaction, locale: Locale) -> String {
+    format!(
+        "{}\t{:?}\t{}\t{}",
+        locale.date(transaction.booking_date.date_naive()),
+        transaction.transaction_type,
+        locale.amount(transaction.amount),
+        transaction.description.replace('\n', " ")
+    )
+}
+
+/// Keep only the transactions at `indices`, in that order
+fn retain_transactions(mut statement: Statement, indices: &[usize]) -> Statement {
+    let transactions = std::mem::take(statement.transactions_mut());
+    let mut slots: Vec<Option<Transaction>> = transactions.into_iter().map(Some).collect();
+    *statement.transactions_mut() = indices
+        .iter()
+        .filter_map(|&index| slots.get_mut(index).and_then(Option::take))
+        .collect();
+    statement
+}
@@ -0,0 +1,188 @@
+//! Notification hooks fired after a conversion finishes.
+//!
+//! Hooks let operators plug the CLI into existing alerting: a shell command can
+//! run on success or failure, and the run's stats can be POSTed as JSON to a
+//! webhook. A failing hook is reported on stderr but never changes the outcome
+//! of the conversion itself.
+
+use serde::Serialize;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Environment variable carrying the run status (`success`/`failure`) to hook commands
+const STATUS_ENV: &str = "LEDGER_BRIDGE_STATUS";
+
+/// Environment variable carrying the stats JSON to hook commands
+const STATS_ENV: &str = "LEDGER_BRIDGE_STATS";
+
+/// Longest a webhook POST may take, connecting included, before it is given up
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a conversion run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    /// Conversion completed and the output was written
+    Success,
+    /// Conversion failed; see `error_code` and `error`
+    Failure,
+}
+
+impl RunStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            RunStatus::Success => "success",
+            RunStatus::Failure => "failure",
+        }
+    }
+}
+
+/// Stats of one conversion, passed to hooks as JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct RunStats {
+    /// Whether the conversion succeeded
+    pub status: RunStatus,
+    /// Input file, `None` for stdin
+    pub input: Option<String>,
+    /// Output file, `None` for stdout
+    pub output: Option<String>,
+    /// Input format as given on the command line
+    pub in_format: String,
+    /// Output format as given on the command line
+    pub out_format: String,
+    /// Number of converted transactions (0 on failure)
+    pub transactions: usize,
+    /// Wall-clock duration of the conversion in milliseconds
+    pub duration_ms: u128,
+    /// Stable error code on failure (see `ParseError::code`)
+    pub error_code: Option<&'static str>,
+    /// Human-readable error message on failure
+    pub error: Option<String>,
+}
+
+/// Configured notification hooks
+#[derive(Debug, Default)]
+pub struct Hooks {
+    /// Shell command to run after a successful conversion
+    pub on_success: Option<String>,
+    /// Shell command to run after a failed conversion
+    pub on_failure: Option<String>,
+    /// URL receiving the stats JSON via HTTP POST after every conversion
+    pub webhook_url: Option<String>,
+}
+
+impl Hooks {
+    /// Fire the hooks matching `stats.status`, reporting hook failures on stderr
+    pub fn notify(&self, stats: &RunStats) {
+        let payload = match serde_json::to_string(stats) {
+            Ok(payload) => payload,
+            Err(e) => {
+                eprintln!("warning: failed to serialize hook payload: {}", e);
+                return;
+            }
+        };
+
+        let command = match stats.status {
+            RunStatus::Success => self.on_success.as_deref(),
+            RunStatus::Failure => self.on_failure.as_deref(),
+        };
+        if let Some(command) = command {
+            if let Err(e) = run_command(command, stats.status, &payload) {
+                eprintln!("warning: hook command '{}' failed: {}", command, e);
+            }
+        }
+
+        if let Some(url) = &self.webhook_url {
+            if let Err(e) = post_webhook(url, &payload, WEBHOOK_TIMEOUT) {
+                eprintln!("warning: webhook POST to {} failed: {}", url, e);
+            }
+        }
+    }
+}
+
+/// Run `command` through the platform shell with the stats JSON on stdin and in the environment.
+///
+/// Its output goes to stderr, since stdout may carry the converted statement.
+fn run_command(command: &str, status: RunStatus, payload: &str) -> io::Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let mut child = shell
+        .arg(command)
+        .env(STATUS_ENV, status.as_str())
+        .env(STATS_ENV, payload)
+        .stdin(Stdio::piped())
+        .stdout(io::stderr())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may close it early; that is not a hook failure
+        match stdin.write_all(payload.as_bytes()) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+            _ => {}
+        }
+    }
+
+    let exit = child.wait()?;
+    if exit.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("exited with {}", exit)))
+    }
+}
+
+/// POST the stats JSON to `url`, giving up after `timeout`
+fn post_webhook(url: &str, payload: &str, timeout: Duration) -> Result<(), ureq::Error> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .build()
+        .into();
+    agent
+        .post(url)
+        .header("Content-Type", "application/json")
+        .send(payload)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_json_shape() {
+        let stats = RunStats {
+            status: RunStatus::Failure,
+            input: Some("in.mt940".into()),
+            output: None,
+            in_format: "mt940".into(),
+            out_format: "camt053".into(),
+            transactions: 0,
+            duration_ms: 3,
+            error_code: Some("mt940_error"),
+            error: Some("MT940 error: bad".into()),
+        };
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains("\"status\":\"failure\""));
+        assert!(json.contains("\"error_code\":\"mt940_error\""));
+        assert!(json.contains("\"output\":null"));
+    }
+
+    #[test]
+    fn test_webhook_gives_up_on_a_silent_server() {
+        // The listener accepts connections but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let result = post_webhook(&url, "{}", Duration::from_millis(200));
+        assert!(matches!(result, Err(ureq::Error::Timeout(_))));
+    }
+}
@@ -0,0 +1,243 @@
+//! `info` subcommand: report what this binary supports, for humans or, with
+//! `--json`, for tooling that checks a deployment before running a pipeline.
+
+use clap::{Args, ValueEnum};
+use ledger_parser::{
+    enabled_features, Camt053Profile, Compression, Mt940Dialect, ParseError,
+    ANNOTATION_SCHEMA_VERSION, CSV_PROFILE_NAMES, JSON_SCHEMA_VERSION, PRESET_NAMES,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, BufWriter, Write};
+
+use crate::locale::Locale;
+
+/// Arguments of `ledger-bridge info`
+#[derive(Args)]
+pub struct InfoArgs {
+    /// Print the report as a JSON document
+    #[arg(long)]
+    json: bool,
+}
+
+/// What the CLI does with one format
+#[derive(Serialize)]
+struct FormatInfo {
+    name: &'static str,
+    read: bool,
+    write: bool,
+    capabilities: &'static [&'static str],
+}
+
+/// Formats of `--in-format` and `--out-format`, with their optional abilities:
+/// `detect` (recognised by `--in-format auto`), `multi-statement` (`--all-statements`),
+/// `split` (`--split-every`), `dialects`/`profiles` (layout options) and
+/// `canonical` (`--canonical-json`)
+const FORMATS: [FormatInfo; 10] = [
+    FormatInfo {
+        name: "csv",
+        read: true,
+        write: true,
+        capabilities: &["detect", "profiles", "sanitize-formulas"],
+    },
+    FormatInfo {
+        name: "mt940",
+        read: true,
+        write: true,
+        capabilities: &["detect", "multi-statement", "split", "dialects"],
+    },
+    FormatInfo {
+        name: "mt942",
+        read: true,
+        write: true,
+        capabilities: &["detect"],
+    },
+    FormatInfo {
+        name: "camt053",
+        read: true,
+        write: true,
+        capabilities: &["detect", "multi-statement", "split", "profiles"],
+    },
+    FormatInfo {
+        name: "camt052",
+        read: true,
+        write: false,
+        capabilities: &["detect"],
+    },
+    FormatInfo {
+        name: "camt054",
+        read: true,
+        write: true,
+        capabilities: &["detect"],
+    },
+    FormatInfo {
+        name: "bai2",
+        read: true,
+        write: false,
+        capabilities: &["detect"],
+    },
+    FormatInfo {
+        name: "json",
+        read: true,
+        write: true,
+        capabilities: &["detect", "canonical"],
+    },
+    FormatInfo {
+        name: "qif",
+        read: true,
+        write: true,
+        capabilities: &["detect"],
+    },
+    FormatInfo {
+        name: "xlsx",
+        read: true,
+        write: false,
+        capabilities: &[],
+    },
+];
+
+/// Capability and version report
+#[derive(Serialize)]
+struct Info {
+    version: &'static str,
+    library_version: &'static str,
+    features: Vec<&'static str>,
+    formats: &'static [FormatInfo],
+    presets: &'static [&'static str],
+    csv_profiles: &'static [&'static str],
+    mt940_dialects: Vec<String>,
+    camt053_profiles: Vec<String>,
+    compression: Vec<String>,
+    locales: Vec<String>,
+    schema_versions: BTreeMap<&'static str, u32>,
+}
+
+impl Info {
+    fn new() -> Self {
+        Info {
+            version: env!("CARGO_PKG_VERSION"),
+            library_version: ledger_parser::VERSION,
+            features: enabled_features(),
+            formats: &FORMATS,
+            presets: &PRESET_NAMES,
+            csv_profiles: &CSV_PROFILE_NAMES,
+            mt940_dialects: [
+                Mt940Dialect::Standard,
+                Mt940Dialect::German,
+                Mt940Dialect::Dutch,
+                Mt940Dialect::Abn,
+                Mt940Dialect::Swift,
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+            camt053_profiles: [Camt053Profile::Standard, Camt053Profile::Dk]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            compression: [Compression::Gzip, Compression::Zip]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            locales: Locale::value_variants()
+                .iter()
+                .filter_map(|locale| locale.to_possible_value())
+                .map(|value| value.get_name().to_string())
+                .collect(),
+            schema_versions: BTreeMap::from([
+                ("json", JSON_SCHEMA_VERSION),
+                ("annotations", ANNOTATION_SCHEMA_VERSION),
+            ]),
+        }
+    }
+}
+
+/// Run the `info` subcommand
+pub fn run(args: &InfoArgs) -> Result<(), ParseError> {
+    let info = Info::new();
+    let mut output = BufWriter::new(io::stdout().lock());
+    if args.json {
+        serde_json::to_writer_pretty(&mut output, &info)
+            .map_err(|e| ParseError::JsonError(format!("Failed to write info: {}", e)))?;
+        writeln!(output)?;
+    } else {
+        write_text(&mut output, &info)?;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+fn write_text<W: Write>(writer: &mut W, info: &Info) -> Result<(), ParseError> {
+    writeln!(writer, "ledger-bridge {}", info.version)?;
+    writeln!(writer, "ledger-parser {}", info.library_version)?;
+    writeln!(writer, "features\t{}", info.features.join(","))?;
+    for format in info.formats {
+        let access = match (format.read, format.write) {
+            (true, true) => "read,write",
+            (true, false) => "read",
+            (false, _) => "write",
+        };
+        writeln!(
+            writer,
+            "format\t{}\t{}\t{}",
+            format.name,
+            access,
+            format.capabilities.join(",")
+        )?;
+    }
+    writeln!(writer, "presets\t{}", info.presets.join(","))?;
+    writeln!(writer, "csv-profiles\t{}", info.csv_profiles.join(","))?;
+    writeln!(writer, "mt940-dialects\t{}", info.mt940_dialects.join(","))?;
+    writeln!(
+        writer,
+        "camt053-profiles\t{}",
+        info.camt053_profiles.join(",")
+    )?;
+    writeln!(writer, "compression\t{}", info.compression.join(","))?;
+    writeln!(writer, "locales\t{}", info.locales.join(","))?;
+    for (name, version) in &info.schema_versions {
+        writeln!(writer, "schema\t{}\t{}", name, version)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, write_output, OutputOptions};
+    use ledger_parser::{CsvDialect, QifStatement, Statement};
+
+    /// Whether the converter rejected the format name itself
+    fn unknown_format<T>(result: Result<T, ParseError>) -> bool {
+        matches!(result, Err(ParseError::InvalidFormat(message)) if message.starts_with("Unknown"))
+    }
+
+    #[test]
+    fn test_formats_match_the_converter() {
+        let qif = "!Type:Bank\nD01/05/2025\nT-10.00\nPLunch\n^\n";
+
+        for format in &FORMATS {
+            let read = parse_input(
+                &mut "".as_bytes(),
+                format.name,
+                &CsvDialect::default(),
+                &Default::default(),
+            );
+            assert_eq!(!unknown_format(read), format.read, "read {}", format.name);
+
+            let statement = Statement::Qif(QifStatement::from_read(&mut qif.as_bytes()).unwrap());
+            let written = write_output(
+                statement,
+                &mut Vec::new(),
+                format.name,
+                &OutputOptions::default(),
+            );
+            assert_eq!(
+                !unknown_format(written),
+                format.write,
+                "write {}",
+                format.name
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Input options shared by the conversion and the subcommands that read
+//! statements: which file, in which format, and how leniently to parse it.
+
+use clap::builder::PossibleValuesParser;
+use clap::Args;
+use ledger_parser::{MissingBalancePolicy, ParseError, ParseOptions, Statement, CSV_PROFILE_NAMES};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use crate::{csv_dialect, parse_all_input, parse_input, parse_options, STRICT_HELP};
+
+/// How statements are parsed, whichever file they come from
+#[derive(Args)]
+pub struct ReadArgs {
+    /// Read CSV input as the export of this bank instead of the Sberbank layout
+    #[arg(
+        long,
+        value_name = "PROFILE",
+        value_parser = PossibleValuesParser::new(CSV_PROFILE_NAMES)
+    )]
+    pub csv_profile: Option<String>,
+
+    /// When a Sberbank CSV or XLSX export lacks its opening or closing balance row:
+    /// error, derive (from the other balance and the transactions) or zero
+    #[arg(long, value_name = "POLICY", conflicts_with_all = ["csv_profile", "strict"])]
+    missing_balance: Option<MissingBalancePolicy>,
+
+    /// Character encoding of a Sberbank CSV export, e.g. windows-1251 or utf-8
+    /// (default: UTF-8, or Windows-1251 for input that is not valid UTF-8)
+    #[arg(long, value_name = "ENCODING", conflicts_with = "csv_profile")]
+    encoding: Option<String>,
+
+    /// Fail on malformed records and bank quirks instead of skipping them
+    #[arg(long, long_help = STRICT_HELP)]
+    pub strict: bool,
+}
+
+impl ReadArgs {
+    /// Parse options of `--strict`, `--missing-balance` and `--encoding`
+    pub fn parse_options(&self) -> ParseOptions {
+        let mut options = parse_options(self.strict);
+        if let Some(missing_balance) = self.missing_balance {
+            options.missing_balance = missing_balance;
+        }
+        options.encoding.clone_from(&self.encoding);
+        options
+    }
+
+    /// Parse the statement of `reader`, written in `format`
+    pub fn read<R: Read>(&self, reader: &mut R, format: &str) -> Result<Statement, ParseError> {
+        let options = self.parse_options();
+        let dialect = csv_dialect(&self.csv_profile, &options);
+        parse_input(reader, format, &dialect, &options)
+    }
+
+    /// Parse every statement of `reader`, written in `format`
+    pub fn read_all<R: Read>(
+        &self,
+        reader: &mut R,
+        format: &str,
+    ) -> Result<Vec<Statement>, ParseError> {
+        let options = self.parse_options();
+        let dialect = csv_dialect(&self.csv_profile, &options);
+        parse_all_input(reader, format, &dialect, &options)
+    }
+}
+
+/// Input file and format of a subcommand that reads one statement file
+#[derive(Args)]
+pub struct InputArgs {
+    /// Input file (default: stdin)
+    #[arg(long, short = 'i', value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// Input format: csv, mt940, mt942, camt053, camt052, camt054, bai2, json, qif, xlsx, or auto
+    #[arg(long, value_name = "FORMAT", default_value = "auto")]
+    pub in_format: String,
+
+    #[command(flatten)]
+    pub read: ReadArgs,
+}
+
+impl InputArgs {
+    /// Parse the statement of the input file or stdin
+    pub fn read(&self) -> Result<Statement, ParseError> {
+        match &self.input {
+            Some(path) => self.read.read(&mut File::open(path)?, &self.in_format),
+            None => self.read.read(&mut io::stdin().lock(), &self.in_format),
+        }
+    }
+
+    /// Parse every statement of the input file or stdin
+    pub fn read_all(&self) -> Result<Vec<Statement>, ParseError> {
+        match &self.input {
+            Some(path) => self.read.read_all(&mut File::open(path)?, &self.in_format),
+            None => self.read.read_all(&mut io::stdin().lock(), &self.in_format),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Command line of the input options alone
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: InputArgs,
+    }
+
+    #[test]
+    fn test_read_options_reach_the_parser() {
+        let args = Cli::parse_from([
+            "input",
+            "--encoding",
+            "windows-1251",
+            "--missing-balance",
+            "zero",
+        ])
+        .args;
+        let options = args.read.parse_options();
+        assert_eq!(options.encoding.as_deref(), Some("windows-1251"));
+        assert_eq!(options.missing_balance, MissingBalancePolicy::AssumeZero);
+        assert_eq!(args.in_format, "auto");
+
+        assert!(Cli::try_parse_from(["input", "--strict", "--missing-balance", "zero"]).is_err());
+        assert!(
+            Cli::try_parse_from(["input", "--csv-profile", "wise", "--encoding", "utf-8"]).is_err()
+        );
+    }
+}
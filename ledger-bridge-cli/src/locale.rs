@@ -0,0 +1,188 @@
+//! Locale-aware rendering of numbers, dates and money in text output.
+//!
+//! Only the human-readable `text` outputs of the subcommands are localized;
+//! machine formats (CSV, JSON, bank formats) never change with `--locale`.
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+
+/// Separator used by French and Swedish thousands groups (narrow no-break space)
+const NARROW_NO_BREAK_SPACE: &str = "\u{202f}";
+/// Separator used by Russian thousands groups and between amount and currency
+const NO_BREAK_SPACE: &str = "\u{a0}";
+
+/// Locale of human-readable output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Locale {
+    /// Locale-neutral: `2025-03-07`, `-1234.56 EUR` (the historical output)
+    #[default]
+    Iso,
+    /// United States: `03/07/2025`, `-$1,234.56`
+    #[value(name = "en-US")]
+    EnUs,
+    /// United Kingdom: `07/03/2025`, `-£1,234.56`
+    #[value(name = "en-GB")]
+    EnGb,
+    /// Germany: `07.03.2025`, `-1.234,56 €`
+    #[value(name = "de-DE")]
+    DeDe,
+    /// France: `07/03/2025`, `-1 234,56 €`
+    #[value(name = "fr-FR")]
+    FrFr,
+    /// Russia: `07.03.2025`, `-1 234,56 ₽`
+    #[value(name = "ru-RU")]
+    RuRu,
+    /// Sweden: `2025-03-07`, `-1 234,56 kr`
+    #[value(name = "sv-SE")]
+    SvSe,
+}
+
+/// Number, date and currency conventions of a locale
+struct Conventions {
+    thousands: &'static str,
+    decimal: char,
+    date: &'static str,
+    /// Whether the currency symbol precedes the amount
+    symbol_first: bool,
+    /// Whether currencies are written as symbols rather than ISO codes
+    symbols: bool,
+}
+
+const ISO: Conventions = Conventions {
+    thousands: "",
+    decimal: '.',
+    date: "%Y-%m-%d",
+    symbol_first: false,
+    symbols: false,
+};
+const EN_US: Conventions = Conventions {
+    thousands: ",",
+    decimal: '.',
+    date: "%m/%d/%Y",
+    symbol_first: true,
+    symbols: true,
+};
+const EN_GB: Conventions = Conventions {
+    date: "%d/%m/%Y",
+    ..EN_US
+};
+const DE_DE: Conventions = Conventions {
+    thousands: ".",
+    decimal: ',',
+    date: "%d.%m.%Y",
+    symbol_first: false,
+    symbols: true,
+};
+const FR_FR: Conventions = Conventions {
+    thousands: NARROW_NO_BREAK_SPACE,
+    date: "%d/%m/%Y",
+    ..DE_DE
+};
+const RU_RU: Conventions = Conventions {
+    thousands: NO_BREAK_SPACE,
+    ..DE_DE
+};
+const SV_SE: Conventions = Conventions {
+    thousands: NARROW_NO_BREAK_SPACE,
+    date: "%Y-%m-%d",
+    ..DE_DE
+};
+
+impl Locale {
+    fn conventions(self) -> &'static Conventions {
+        match self {
+            Locale::Iso => &ISO,
+            Locale::EnUs => &EN_US,
+            Locale::EnGb => &EN_GB,
+            Locale::DeDe => &DE_DE,
+            Locale::FrFr => &FR_FR,
+            Locale::RuRu => &RU_RU,
+            Locale::SvSe => &SV_SE,
+        }
+    }
+
+    /// Amount with two decimals and grouped thousands, e.g. `-1.234,56`
+    pub fn amount(self, amount: f64) -> String {
+        let conventions = self.conventions();
+        let fixed = format!("{:.2}", amount.abs());
+        let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+
+        let mut grouped = String::new();
+        for (position, digit) in integer.chars().enumerate() {
+            if position > 0 && (integer.len() - position).is_multiple_of(3) {
+                grouped.push_str(conventions.thousands);
+            }
+            grouped.push(digit);
+        }
+
+        // Rounding can turn tiny negative amounts into zero; never print "-0.00"
+        let sign = if amount < 0.0 && fixed.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            "-"
+        } else {
+            ""
+        };
+        format!("{}{}{}{}", sign, grouped, conventions.decimal, fraction)
+    }
+
+    /// Amount with currency symbol (or ISO code) placed as the locale does
+    pub fn money(self, amount: f64, currency: &str) -> String {
+        let conventions = self.conventions();
+        let symbol = currency_symbol(currency).filter(|_| conventions.symbols);
+        let number = self.amount(amount.abs());
+        let sign = if self.amount(amount).starts_with('-') {
+            "-"
+        } else {
+            ""
+        };
+
+        match symbol {
+            Some(symbol) if conventions.symbol_first => format!("{}{}{}", sign, symbol, number),
+            Some(symbol) => format!("{}{}{}{}", sign, number, NO_BREAK_SPACE, symbol),
+            None if conventions.symbol_first => format!("{}{} {}", sign, currency, number),
+            None => format!("{}{} {}", sign, number, currency),
+        }
+    }
+
+    /// Date in the locale's day/month order
+    pub fn date(self, date: NaiveDate) -> String {
+        date.format(self.conventions().date).to_string()
+    }
+}
+
+/// Symbol of the common currencies; others are written as their ISO code
+fn currency_symbol(currency: &str) -> Option<&'static str> {
+    match currency.to_ascii_uppercase().as_str() {
+        "EUR" => Some("€"),
+        "USD" => Some("$"),
+        "GBP" => Some("£"),
+        "JPY" => Some("¥"),
+        "RUB" => Some("₽"),
+        "SEK" | "DKK" | "NOK" => Some("kr"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_and_money_per_locale() {
+        assert_eq!(Locale::Iso.money(-1234.5, "EUR"), "-1234.50 EUR");
+        assert_eq!(Locale::EnUs.money(-1234.5, "USD"), "-$1,234.50");
+        assert_eq!(Locale::EnUs.money(1234567.0, "CHF"), "CHF 1,234,567.00");
+        assert_eq!(Locale::DeDe.money(-1234.5, "EUR"), "-1.234,50\u{a0}€");
+        assert_eq!(Locale::FrFr.amount(1234.5), "1\u{202f}234,50");
+        assert_eq!(Locale::DeDe.amount(-0.001), "0,00");
+        assert_eq!(Locale::RuRu.amount(999.0), "999,00");
+    }
+
+    #[test]
+    fn test_date_order() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        assert_eq!(Locale::Iso.date(date), "2025-03-07");
+        assert_eq!(Locale::EnUs.date(date), "03/07/2025");
+        assert_eq!(Locale::EnGb.date(date), "07/03/2025");
+        assert_eq!(Locale::DeDe.date(date), "07.03.2025");
+    }
+}
@@ -2,24 +2,87 @@
 //!
 //! Command-line interface for converting financial data between formats.
 
-use clap::Parser;
-use ledger_parser::{Camt053Statement, CsvStatement, Mt940Statement, ParseError};
-use std::fs::File;
-use std::io::{self, Read, Write};
+mod aggregate;
+mod annotate;
+mod batch;
+mod daemon;
+mod dedup;
+mod explain;
+mod filter;
+mod grep;
+mod hooks;
+mod info;
+mod input;
+mod locale;
+mod merge;
+mod metrics;
+mod split;
+mod summarize;
+mod verify;
+
+use clap::builder::PossibleValuesParser;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use ledger_parser::{
+    Camt053Profile, Camt053Statement, Camt053WriteOptions, Camt054Statement, CompressedWriter,
+    Compression, CounterpartyDirectory, CsvDialect, CsvStatement, CsvWriteOptions, Enricher,
+    ExportPreset, JsonStatement, Mt940Charset, Mt940Dialect, Mt940Statement, Mt940WriteOptions,
+    Mt942Statement, ParseError, ParseOptions, ParseWarning, QifStatement, Statement, PRESET_NAMES,
+};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use dedup::DedupCache;
+use hooks::{Hooks, RunStats, RunStatus};
+use input::ReadArgs;
+use metrics::ConversionMetrics;
+
+/// Source name of statements read from standard input
+const STDIN_SOURCE: &str = "stdin";
+
+/// Input format name that detects the format from the content
+const AUTO_FORMAT: &str = "auto";
+
+/// Extensions of `--input` files whose format is detected without `--in-format`
+const DETECTABLE_EXTENSIONS: &[&str] = &[
+    "csv", "mt940", "sta", "940", "mt942", "942", "xml", "camt", "camt052", "camt053", "camt054",
+    "bai", "bai2", "json", "qif", "xlsx",
+];
+
+/// `--help` text of `--strict`, shared by the conversion and the subcommands
+const STRICT_HELP: &str = "\
+Fail on input the default lenient mode reads anyway:
+  - a malformed transaction record (MT940 :61:, :64: and :65: fields, \
+Sberbank CSV and XLSX rows, CAMT.053 <Ntry> entries) fails the run with its \
+error instead of being skipped with a warning on stderr
+  - bank quirks detected from the input (e.g. the zero-amount rows of Sberbank \
+exports) are not worked around
+  - conversions read their output back as with --verify and fail if it lost \
+more than the output format is known to drop (not with --preset)
+Profile-mapped CSV (--csv-profile) and the other formats fail on malformed \
+records in both modes.";
 
 /// Convert financial data between CSV, MT940, and CAMT.053 formats
 #[derive(Parser)]
 #[command(name = "ledger-bridge")]
 #[command(version)]
 #[command(about = "Convert financial data between formats", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// Input format: csv, mt940, or camt053
-    #[arg(long, value_name = "FORMAT")]
-    in_format: String,
+    /// Subcommand; without one the CLI converts between formats
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Output format: csv, mt940, or camt053
+    /// Input format: csv, mt940, mt942, camt053, camt052, camt054, bai2, json, qif, xlsx, or auto
+    /// (the default for an --input file with a statement extension such as .sta, .xml or .csv)
     #[arg(long, value_name = "FORMAT")]
-    out_format: String,
+    in_format: Option<String>,
+
+    /// Output format: csv, mt940, mt942, camt053, camt054, json, or qif
+    #[arg(long, value_name = "FORMAT", required_unless_present = "preset")]
+    out_format: Option<String>,
 
     /// Input file (default: stdin)
     #[arg(long, short = 'i', value_name = "FILE")]
@@ -28,81 +91,673 @@ struct Cli {
     /// Output file (default: stdout)
     #[arg(long, short = 'o', value_name = "FILE")]
     output: Option<String>,
+
+    /// Compress the output: gz (gzip stream) or zip (archive with one entry)
+    #[arg(long, value_name = "FORMAT")]
+    compress: Option<Compression>,
+
+    #[command(flatten)]
+    convert: ConvertArgs,
 }
 
-/// Enum to hold any of the three format types
-enum Statement {
-    Csv(CsvStatement),
-    Mt940(Mt940Statement),
-    Camt053(Camt053Statement),
+/// Options of the conversion pipeline, shared by the default conversion and `batch`
+#[derive(Args)]
+struct ConvertArgs {
+    /// Convert every statement of a multi-message MT940 or multi-account CAMT.053 input
+    #[arg(long)]
+    all_statements: bool,
+
+    #[command(flatten)]
+    read: ReadArgs,
+
+    /// Write an import file for an accounting tool or legacy system instead of --out-format
+    #[arg(
+        long,
+        value_name = "PRESET",
+        conflicts_with_all = [
+            "out_format",
+            "split_every",
+            "canonical_json",
+            "no_sanitize_formulas",
+            "mt940_dialect",
+            "mt940_charset",
+            "camt053_profile",
+            "camt053_booking_time",
+        ],
+        value_parser = PossibleValuesParser::new(PRESET_NAMES)
+    )]
+    preset: Option<String>,
+
+    /// DATEV tax advisor number (Beraternummer) for `--preset datev`
+    #[arg(long, value_name = "NUMBER", requires = "preset")]
+    datev_advisor: Option<u32>,
+
+    /// DATEV client number (Mandantennummer) for `--preset datev`
+    #[arg(long, value_name = "NUMBER", requires = "preset")]
+    datev_client: Option<u32>,
+
+    /// BACS service user number (SUN) for `--preset bacs-standard18`
+    #[arg(long, value_name = "SUN", requires = "preset")]
+    bacs_sun: Option<String>,
+
+    /// BACS service user name for `--preset bacs-standard18`
+    #[arg(long, value_name = "NAME", requires = "preset")]
+    bacs_name: Option<String>,
+
+    /// Journal account of the bank statement for `--preset ledger` or `beancount` (default: Assets:Bank)
+    #[arg(long, value_name = "ACCOUNT", requires = "preset")]
+    bank_account: Option<String>,
+
+    /// Journal account for money received for `--preset ledger` or `beancount` (default: Income:Unknown)
+    #[arg(long, value_name = "ACCOUNT", requires = "preset")]
+    income_account: Option<String>,
+
+    /// Journal account for money paid out for `--preset ledger` or `beancount` (default: Expenses:Unknown)
+    #[arg(long, value_name = "ACCOUNT", requires = "preset")]
+    expense_account: Option<String>,
+
+    /// Split output into parts of at most N transactions (MT940 messages or CAMT.053 pages)
+    #[arg(long, value_name = "N")]
+    split_every: Option<usize>,
+
+    #[command(flatten)]
+    writer: WriterArgs,
+
+    /// CSV file of counterparty names by account (`account`/`iban` and `name` columns) to fill in
+    #[arg(long, value_name = "FILE")]
+    counterparty_directory: Option<PathBuf>,
+
+    /// Skip statements recorded in this cache file by earlier runs, and record the converted ones
+    #[arg(long, value_name = "FILE")]
+    dedup_cache: Option<PathBuf>,
+
+    /// Read the output back and fail if it lost more than the output format is known to drop
+    #[arg(long, conflicts_with = "preset")]
+    verify: bool,
+
+    /// Read the output back and warn on stderr about every field the output format dropped or truncated
+    #[arg(long, conflicts_with = "preset")]
+    warn_lossy: bool,
+
+    #[command(flatten)]
+    filter: filter::FilterArgs,
+
+    /// Add Prometheus metrics of this run to FILE (node_exporter textfile format)
+    #[arg(long, value_name = "FILE")]
+    metrics_file: Option<PathBuf>,
+
+    /// Shell command to run after a successful conversion (stats JSON on stdin)
+    #[arg(long, value_name = "COMMAND")]
+    on_success: Option<String>,
+
+    /// Shell command to run after a failed conversion (stats JSON on stdin)
+    #[arg(long, value_name = "COMMAND")]
+    on_failure: Option<String>,
+
+    /// POST the run's stats JSON to URL after every conversion
+    #[arg(long, value_name = "URL")]
+    webhook_url: Option<String>,
+}
+
+/// Writer options of the output formats, shared by the conversion, `merge` and `split`
+#[derive(Args)]
+struct WriterArgs {
+    /// Write JSON output in canonical form (compact, sorted keys, decimal-string amounts) for hashing
+    #[arg(long)]
+    canonical_json: bool,
+
+    /// Write CSV cells starting with =, +, - or @ as they are, without the ' that keeps
+    /// spreadsheets from running them as formulas
+    #[arg(long)]
+    no_sanitize_formulas: bool,
+
+    /// Layout of the MT940 `:86:` field: standard, german (?20 subfields), dutch or ing (/CNTP/
+    /// keywords), abn (ABN AMRO /TRTP/ keywords) or swift (generic /ORDP/ keywords)
+    #[arg(long, value_name = "DIALECT")]
+    mt940_dialect: Option<Mt940Dialect>,
+
+    /// Characters outside the SWIFT X set in MT940 output: keep, translit (Cyrillic and accented
+    /// letters to Latin), strip or error
+    #[arg(long, value_name = "POLICY")]
+    mt940_charset: Option<Mt940Charset>,
+
+    /// CAMT.053 compliance profile: standard or dk (German Deutsche Kreditwirtschaft rules)
+    #[arg(long, value_name = "PROFILE")]
+    camt053_profile: Option<Camt053Profile>,
+
+    /// Write CAMT.053 booking dates with time and offset (`<DtTm>`) instead of the date alone
+    #[arg(long)]
+    camt053_booking_time: bool,
+}
+
+/// Subcommands besides the default conversion
+#[derive(Subcommand)]
+enum Command {
+    /// Print transactions whose description, reference or counterparty matches PATTERN
+    Grep(grep::GrepArgs),
+    /// Attach notes and categories to transactions and print them with their annotations
+    Annotate(annotate::AnnotateArgs),
+    /// Export per-day/month and per-category totals without individual transactions
+    Aggregate(aggregate::AggregateArgs),
+    /// Summarize one statement or a directory of accounts: balances, transfers and totals
+    Summarize(summarize::SummarizeArgs),
+    /// Print a normalized dump of every statement of the input, for diffing in bug reports
+    Explain(explain::ExplainArgs),
+    /// Report the version, enabled features, formats, profiles and schema versions
+    Info(info::InfoArgs),
+    /// Serve conversions over a Unix socket (length-prefixed JSON messages)
+    Daemon(daemon::DaemonArgs),
+    /// Convert every statement file of a directory, reporting the files that fail
+    Batch(Box<batch::BatchArgs>),
+    /// Join consecutive statements of one account into one, checking the balances meet
+    Merge(merge::MergeArgs),
+    /// Write a statement as one file per calendar month or per N entries
+    Split(split::SplitArgs),
+}
+
+impl Cli {
+    /// Input format: `--in-format`, or `auto` when it is left out, which `main`
+    /// only allows for an `--input` file with a detectable extension
+    fn in_format(&self) -> &str {
+        self.in_format.as_deref().unwrap_or(AUTO_FORMAT)
+    }
+
+    /// Whether `--input` names a file whose format can be left to detection
+    fn input_detectable(&self) -> bool {
+        self.input
+            .as_deref()
+            .and_then(|input| Path::new(input).extension())
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                DETECTABLE_EXTENSIONS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(extension))
+            })
+    }
+
+    /// Output format, or the preset name when `--preset` is used
+    fn out_format(&self) -> &str {
+        self.out_format
+            .as_deref()
+            .or(self.convert.preset.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Files and formats of the conversion
+    fn job(&self) -> Job<'_> {
+        Job {
+            input: self.input.as_deref().map(Path::new),
+            output: self.output.as_deref().map(Path::new),
+            in_format: self.in_format(),
+            out_format: self.out_format(),
+            compress: self.compress,
+        }
+    }
+}
+
+impl ConvertArgs {
+    /// Writer settings taken from the command line
+    fn output_options(&self) -> OutputOptions {
+        OutputOptions {
+            split_every: self.split_every,
+            ..self.writer.output_options()
+        }
+    }
+}
+
+impl WriterArgs {
+    /// Writer settings taken from the command line, writing every statement whole
+    fn output_options(&self) -> OutputOptions {
+        OutputOptions {
+            split_every: None,
+            mt940_dialect: self.mt940_dialect.unwrap_or_default(),
+            mt940_charset: self.mt940_charset.unwrap_or_default(),
+            camt053_profile: self.camt053_profile.unwrap_or_default(),
+            camt053_booking_time: self.camt053_booking_time,
+            sanitize_formulas: !self.no_sanitize_formulas,
+            canonical_json: self.canonical_json,
+        }
+    }
+}
+
+/// Files and formats of one conversion; everything else comes from [`ConvertArgs`]
+struct Job<'a> {
+    /// Input file, `None` for stdin
+    input: Option<&'a Path>,
+    /// Output file, `None` for stdout
+    output: Option<&'a Path>,
+    /// Input format, `auto` to detect it
+    in_format: &'a str,
+    /// Output format, or the preset name when `--preset` is used
+    out_format: &'a str,
+    /// Compression of the output
+    compress: Option<Compression>,
+}
+
+/// What one conversion did
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    /// Statements were written, with this many transactions
+    Converted(usize),
+    /// Every statement was converted before, so nothing was written
+    Duplicate,
+}
+
+impl Outcome {
+    /// Transactions written, none for a duplicate
+    fn transactions(self) -> usize {
+        match self {
+            Outcome::Converted(transactions) => transactions,
+            Outcome::Duplicate => 0,
+        }
+    }
+}
+
+/// Format-specific writer settings shared by conversion and subcommands
+#[derive(Debug, Default)]
+struct OutputOptions {
+    /// Maximum transactions per MT940 message or CAMT.053 page
+    split_every: Option<usize>,
+    /// Layout of the MT940 `:86:` field
+    mt940_dialect: Mt940Dialect,
+    /// Handling of characters outside the SWIFT `X` set in MT940 output
+    mt940_charset: Mt940Charset,
+    /// Compliance profile of CAMT.053 output
+    camt053_profile: Camt053Profile,
+    /// Write CAMT.053 booking dates as `<DtTm>`
+    camt053_booking_time: bool,
+    /// Escape formula-like cells of CSV output
+    sanitize_formulas: bool,
+    /// Write JSON output in canonical form
+    canonical_json: bool,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command-line arguments
     let cli = Cli::parse();
 
-    // Execute conversion
-    run_conversion(cli)?;
+    match &cli.command {
+        Some(Command::Grep(args)) => {
+            grep::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Annotate(args)) => {
+            annotate::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Aggregate(args)) => {
+            aggregate::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Summarize(args)) => {
+            summarize::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Explain(args)) => {
+            explain::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Info(args)) => {
+            info::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Daemon(args)) => {
+            daemon::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Merge(args)) => {
+            merge::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Split(args)) => {
+            split::run(args)?;
+            return Ok(());
+        }
+        Some(Command::Batch(args)) => {
+            if batch::run(args)?.failed > 0 {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+    if cli.in_format.is_none() && !cli.input_detectable() {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--in-format is required unless --input has a statement file extension",
+            )
+            .exit();
+    }
 
+    run_job(&cli.convert, &cli.job())?;
     Ok(())
 }
 
-/// Main conversion logic
-fn run_conversion(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
-    // Handle input/output based on whether they are files or stdin/stdout
-    match (&cli.input, &cli.output) {
-        (Some(input_path), Some(output_path)) => {
-            let mut input = File::open(input_path)?;
-            let mut output = File::create(output_path)?;
-            convert(&mut input, &mut output, &cli.in_format, &cli.out_format)?;
+/// Run one conversion and report it to the metrics file and the hooks, returning
+/// its outcome
+fn run_job(args: &ConvertArgs, job: &Job) -> Result<Outcome, ParseError> {
+    // Execute conversion, timing it for the optional metrics file
+    let started = Instant::now();
+    let result = run_conversion(args, job);
+    let elapsed = started.elapsed();
+
+    if let Some(metrics_path) = &args.metrics_file {
+        let mut metrics = ConversionMetrics::default();
+        metrics.record(
+            result.as_ref().map(|outcome| outcome.transactions()),
+            elapsed,
+        );
+        metrics.add_to_file(metrics_path)?;
+    }
+
+    let hooks = Hooks {
+        on_success: args.on_success.clone(),
+        on_failure: args.on_failure.clone(),
+        webhook_url: args.webhook_url.clone(),
+    };
+    let path_name = |path: Option<&Path>| path.map(|path| path.display().to_string());
+    hooks.notify(&RunStats {
+        status: if result.is_ok() {
+            RunStatus::Success
+        } else {
+            RunStatus::Failure
+        },
+        input: path_name(job.input),
+        output: path_name(job.output),
+        in_format: job.in_format.to_string(),
+        out_format: job.out_format.to_string(),
+        transactions: result.as_ref().map_or(0, |outcome| outcome.transactions()),
+        duration_ms: elapsed.as_millis(),
+        error_code: result.as_ref().err().map(|e| e.code()),
+        error: result.as_ref().err().map(|e| e.to_string()),
+    });
+
+    result
+}
+
+/// Main conversion logic: read the input, then write what is new to the output
+fn run_conversion(args: &ConvertArgs, job: &Job) -> Result<Outcome, ParseError> {
+    let (statements, cache) = match job.input {
+        Some(input_path) => read_statements(&mut File::open(input_path)?, args, job)?,
+        None => read_statements(&mut io::stdin(), args, job)?,
+    };
+    // Nothing new to write: the output is not even opened
+    if statements.is_empty() && cache.is_some() {
+        return Ok(Outcome::Duplicate);
+    }
+
+    // Outputs are wrapped in BufWriter so format writers never hit an unbuffered sink
+    let transactions = match job.output {
+        Some(output_path) => convert_to_file(statements, output_path, args, job)?,
+        None => convert_into(statements, BufWriter::new(io::stdout().lock()), args, job)?,
+    };
+
+    // Only a successful run marks its statements as converted
+    if let Some(cache) = cache {
+        cache.save()?;
+    }
+    Ok(Outcome::Converted(transactions))
+}
+
+/// Convert into the file at `path` through `<path>.tmp`, which replaces it only
+/// once the conversion succeeded, so a failing one leaves no partial output
+fn convert_to_file(
+    statements: Vec<Statement>,
+    path: &Path,
+    args: &ConvertArgs,
+    job: &Job,
+) -> Result<usize, ParseError> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+    let output = BufWriter::new(File::create(&temporary)?);
+    match convert_into(statements, output, args, job) {
+        Ok(transactions) => {
+            fs::rename(&temporary, path)?;
+            Ok(transactions)
         }
-        (Some(input_path), None) => {
-            let mut input = File::open(input_path)?;
-            let mut output = io::stdout();
-            convert(&mut input, &mut output, &cli.in_format, &cli.out_format)?;
+        Err(error) => {
+            // The conversion error matters more than a leftover temporary file
+            fs::remove_file(&temporary).ok();
+            Err(error)
         }
-        (None, Some(output_path)) => {
-            let mut input = io::stdin();
-            let mut output = File::create(output_path)?;
-            convert(&mut input, &mut output, &cli.in_format, &cli.out_format)?;
+    }
+}
+
+/// Convert into `output`, compressed if requested, and flush it
+fn convert_into<W: Write>(
+    statements: Vec<Statement>,
+    mut output: W,
+    args: &ConvertArgs,
+    job: &Job,
+) -> Result<usize, ParseError> {
+    let transactions = match job.compress {
+        Some(compression) => {
+            let entry_name = archive_entry_name(job);
+            let mut compressed = CompressedWriter::new(&mut output, compression, &entry_name)?;
+            let transactions = convert(statements, &mut compressed, args, job)?;
+            compressed.finish()?;
+            transactions
         }
-        (None, None) => {
-            let mut input = io::stdin();
-            let mut output = io::stdout();
-            convert(&mut input, &mut output, &cli.in_format, &cli.out_format)?;
+        None => convert(statements, &mut output, args, job)?,
+    };
+    output.flush()?;
+    Ok(transactions)
+}
+
+/// Name of the converted file inside a zip archive: the output file name without
+/// its `.zip`/`.gz` suffix, or `statement.<format>` when writing to stdout
+fn archive_entry_name(job: &Job) -> String {
+    let Some(output) = job.output else {
+        return format!("statement.{}", job.out_format);
+    };
+    let compressed = output
+        .extension()
+        .is_some_and(|extension| extension == "zip" || extension == "gz");
+    let name = if compressed {
+        output.file_stem()
+    } else {
+        output.file_name()
+    };
+    let name = name.map(|name| name.to_string_lossy().into_owned());
+    match name {
+        Some(name) if Path::new(&name).extension().is_some() => name,
+        Some(name) => format!("{}.{}", name, job.out_format),
+        None => format!("statement.{}", job.out_format),
+    }
+}
+
+/// Parse the input and drop the statements converted before, returning the
+/// rest with the dedup cache that records them once the run succeeds
+fn read_statements<R: Read>(
+    reader: &mut R,
+    args: &ConvertArgs,
+    job: &Job,
+) -> Result<(Vec<Statement>, Option<DedupCache>), ParseError> {
+    // Parse based on input format
+    let mut statements = if args.all_statements {
+        args.read.read_all(reader, job.in_format)?
+    } else {
+        vec![args.read.read(reader, job.in_format)?]
+    };
+
+    // Re-delivered statements are dropped before anything else looks at them
+    let mut cache = args
+        .dedup_cache
+        .as_ref()
+        .map(DedupCache::open)
+        .transpose()?;
+    if let Some(cache) = &mut cache {
+        let source = job
+            .input
+            .map_or(STDIN_SOURCE.into(), |input| input.to_string_lossy());
+        statements = cache.retain_new(statements, &source)?;
+    }
+    Ok((statements, cache))
+}
+
+/// Perform the actual conversion: enrich, filter, write and verify
+fn convert<W: Write>(
+    mut statements: Vec<Statement>,
+    writer: &mut W,
+    args: &ConvertArgs,
+    job: &Job,
+) -> Result<usize, ParseError> {
+    if let Some(path) = &args.counterparty_directory {
+        let directory = CounterpartyDirectory::from_path(path)?;
+        for statement in &mut statements {
+            directory.enrich_all(statement.transactions_mut())?;
         }
     }
+    for statement in &mut statements {
+        args.filter.apply(statement);
+    }
 
-    Ok(())
+    // Several statements are written back to back into the same output; output
+    // that is read back is held until every statement has passed
+    let verify = args.verify || (args.read.strict && args.preset.is_none());
+    let mut transactions = 0;
+    let mut held = Vec::new();
+    for statement in statements {
+        transactions += statement.transactions().len();
+        if verify || args.warn_lossy {
+            let original = Camt053Statement::from(statement.clone());
+            let start = held.len();
+            write_statement(statement, &mut held, args, job.out_format)?;
+            if args.warn_lossy {
+                let report = verify::losses(&original, &held[start..], job.out_format)?;
+                for loss in report.losses {
+                    eprintln!("warning: {} output lost {}", job.out_format, loss);
+                }
+            }
+            if verify {
+                verify::verify(
+                    &original,
+                    &held[start..],
+                    job.out_format,
+                    &args.output_options(),
+                )?;
+            }
+        } else {
+            write_statement(statement, writer, args, job.out_format)?;
+        }
+    }
+    writer.write_all(&held)?;
+    Ok(transactions)
 }
 
-/// Perform the actual conversion
-fn convert<R: Read, W: Write>(
-    reader: &mut R,
+/// Convert and write one statement based on output format or accounting preset
+fn write_statement<W: Write>(
+    statement: Statement,
     writer: &mut W,
-    in_format: &str,
+    args: &ConvertArgs,
     out_format: &str,
 ) -> Result<(), ParseError> {
-    // Parse based on input format
-    let statement = parse_input(reader, in_format)?;
-
-    // Convert and write based on output format
-    write_output(statement, writer, out_format)?;
+    match &args.preset {
+        Some(preset) => {
+            let mut preset = ExportPreset::by_name(preset)
+                .ok_or_else(|| ParseError::InvalidFormat(format!("Unknown preset: {}", preset)))?;
+            match &mut preset {
+                ExportPreset::Datev(profile) => {
+                    profile.advisor_number = args.datev_advisor.unwrap_or(profile.advisor_number);
+                    profile.client_number = args.datev_client.unwrap_or(profile.client_number);
+                }
+                ExportPreset::Standard18(profile) => {
+                    if let Some(sun) = &args.bacs_sun {
+                        profile.service_user_number = sun.clone();
+                    }
+                    if let Some(name) = &args.bacs_name {
+                        profile.service_user_name = name.clone();
+                    }
+                }
+                ExportPreset::Ledger(profile) => {
+                    if let Some(account) = &args.bank_account {
+                        profile.bank_account = account.clone();
+                    }
+                    if let Some(account) = &args.income_account {
+                        profile.income_account = account.clone();
+                    }
+                    if let Some(account) = &args.expense_account {
+                        profile.expense_account = account.clone();
+                    }
+                }
+                ExportPreset::Beancount(profile) => {
+                    if let Some(account) = &args.bank_account {
+                        profile.bank_account = account.clone();
+                    }
+                    if let Some(account) = &args.income_account {
+                        profile.income_account = account.clone();
+                    }
+                    if let Some(account) = &args.expense_account {
+                        profile.expense_account = account.clone();
+                    }
+                }
+                ExportPreset::Csv(_) => {}
+            }
+            preset.write_to(statement.as_view(), writer)?;
+        }
+        None => write_output(statement, writer, out_format, &args.output_options())?,
+    }
 
     Ok(())
 }
 
-/// Parse input based on format type
-fn parse_input<R: Read>(reader: &mut R, format: &str) -> Result<Statement, ParseError> {
-    match format.to_lowercase().as_str() {
-        "csv" => Ok(Statement::Csv(CsvStatement::from_read(reader)?)),
-        "mt940" => Ok(Statement::Mt940(Mt940Statement::from_read(reader)?)),
-        "camt053" => Ok(Statement::Camt053(Camt053Statement::from_read(reader)?)),
-        _ => Err(ParseError::InvalidFormat(format!(
-            "Unknown input format: {}. Supported: csv, mt940, camt053",
-            format
-        ))),
+/// Parse every statement of the input; only MT940 and CAMT.053 files carry more than one
+fn parse_all_input<R: Read>(
+    reader: &mut R,
+    format: &str,
+    csv_dialect: &CsvDialect,
+    options: &ParseOptions,
+) -> Result<Vec<Statement>, ParseError> {
+    Ok(report_skipped(Statement::parse_all_with_options(
+        reader,
+        format,
+        Some(csv_dialect),
+        options,
+    )?))
+}
+
+/// Parse options of `--strict`: [`ParseOptions::strict`] with it, the lenient default without
+fn parse_options(strict: bool) -> ParseOptions {
+    if strict {
+        ParseOptions::strict()
+    } else {
+        ParseOptions::default()
+    }
+}
+
+/// CSV layout selected by `--csv-profile`; the Sberbank layout read with `options` without one
+fn csv_dialect(profile: &Option<String>, options: &ParseOptions) -> CsvDialect {
+    profile
+        .as_deref()
+        .and_then(CsvDialect::by_name)
+        .unwrap_or_else(|| CsvDialect::Sberbank(options.clone()))
+}
+
+/// Print the records a lenient parser skipped to stderr and keep what it parsed
+fn report_skipped<T>((parsed, warnings): (T, Vec<ParseWarning>)) -> T {
+    for warning in warnings {
+        eprintln!("warning: skipped {}", warning);
     }
+    parsed
+}
+
+/// Parse input based on format type; `auto` has the library sniff the format from the content.
+///
+/// `options` apply to the Sberbank CSV, XLSX, MT940 and CAMT.053 readers.
+fn parse_input<R: Read>(
+    reader: &mut R,
+    format: &str,
+    csv_dialect: &CsvDialect,
+    options: &ParseOptions,
+) -> Result<Statement, ParseError> {
+    Ok(report_skipped(Statement::from_read_with_options(
+        reader,
+        format,
+        Some(csv_dialect),
+        options,
+    )?))
 }
 
 /// Convert and write output based on format type
@@ -110,34 +765,84 @@ fn write_output<W: Write>(
     statement: Statement,
     writer: &mut W,
     format: &str,
+    options: &OutputOptions,
 ) -> Result<(), ParseError> {
+    let split_every = options.split_every;
     match format.to_lowercase().as_str() {
         "csv" => {
-            let csv = match statement {
-                Statement::Csv(s) => s,
-                Statement::Mt940(s) => s.into(),
-                Statement::Camt053(s) => s.into(),
+            if split_every.is_some() {
+                return Err(ParseError::InvalidFormat(
+                    "--split-every is only supported for mt940 and camt053 output".into(),
+                ));
+            }
+            let csv = CsvStatement::from(statement);
+            let options = CsvWriteOptions {
+                sanitize_formulas: options.sanitize_formulas,
             };
-            csv.write_to(writer)
+            csv.write_to_with(writer, &options)
         }
         "mt940" => {
-            let mt940 = match statement {
-                Statement::Mt940(s) => s,
-                Statement::Csv(s) => s.into(),
-                Statement::Camt053(s) => s.into(),
+            let mt940 = Mt940Statement::from(statement);
+            let options = Mt940WriteOptions {
+                max_transactions_per_message: split_every,
+                dialect: options.mt940_dialect,
+                charset: options.mt940_charset,
+                ..Default::default()
             };
-            mt940.write_to(writer)
+            mt940.write_to_with(writer, &options)
         }
         "camt053" => {
-            let camt053 = match statement {
-                Statement::Camt053(s) => s,
-                Statement::Mt940(s) => s.into(),
-                Statement::Csv(s) => s.into(),
+            let camt053 = Camt053Statement::from(statement);
+            let options = Camt053WriteOptions {
+                max_entries_per_page: split_every,
+                profile: options.camt053_profile,
+                booking_date_time: options.camt053_booking_time,
+                ..Default::default()
             };
-            camt053.write_to(writer)
+            camt053.write_to_with(writer, &options)
+        }
+        "qif" => {
+            if split_every.is_some() {
+                return Err(ParseError::InvalidFormat(
+                    "--split-every is only supported for mt940 and camt053 output".into(),
+                ));
+            }
+            let qif = QifStatement::from(statement);
+            qif.write_to(writer)
+        }
+        "camt054" => {
+            if split_every.is_some() {
+                return Err(ParseError::InvalidFormat(
+                    "--split-every is only supported for mt940 and camt053 output".into(),
+                ));
+            }
+            let camt054 = Camt054Statement::from(statement);
+            camt054.write_to(writer)
+        }
+        "mt942" => {
+            if split_every.is_some() {
+                return Err(ParseError::InvalidFormat(
+                    "--split-every is only supported for mt940 and camt053 output".into(),
+                ));
+            }
+            let mt942 = Mt942Statement::from(statement);
+            mt942.write_to(writer)
+        }
+        "json" => {
+            if split_every.is_some() {
+                return Err(ParseError::InvalidFormat(
+                    "--split-every is only supported for mt940 and camt053 output".into(),
+                ));
+            }
+            let json = JsonStatement::from(statement);
+            if options.canonical_json {
+                json.write_canonical(writer)
+            } else {
+                json.write_to(writer)
+            }
         }
         _ => Err(ParseError::InvalidFormat(format!(
-            "Unknown output format: {}. Supported: csv, mt940, camt053",
+            "Unknown output format: {}. Supported: csv, mt940, mt942, camt053, camt054, json, qif",
             format
         ))),
     }
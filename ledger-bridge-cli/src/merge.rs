@@ -0,0 +1,131 @@
+//! `merge` subcommand: join consecutive statements of one account, e.g. the
+//! daily files of a month, into a single statement.
+
+use clap::Args;
+use ledger_parser::{Camt053Statement, ParseError, Statement};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::input::ReadArgs;
+use crate::{verify, write_output, WriterArgs};
+
+/// Arguments of `ledger-bridge merge`
+#[derive(Args)]
+pub struct MergeArgs {
+    /// Statement files of one account, in any order; every statement of multi-message
+    /// MT940 and multi-account CAMT.053 files is merged
+    #[arg(value_name = "FILE", required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Output format: csv, mt940, mt942, camt053, camt054, json or qif
+    #[arg(long, value_name = "FORMAT")]
+    out_format: String,
+
+    /// Input format of every file: csv, mt940, mt942, camt053, camt052, camt054, bai2, json,
+    /// qif, xlsx, or auto
+    #[arg(long, value_name = "FORMAT", default_value = "auto")]
+    in_format: String,
+
+    #[command(flatten)]
+    read: ReadArgs,
+
+    /// Output file (default: stdout)
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<String>,
+
+    #[command(flatten)]
+    writer: WriterArgs,
+}
+
+/// Run the `merge` subcommand, returning the number of merged transactions.
+///
+/// The merged statement is written to memory first, and with `--strict` read
+/// back and verified, so a failing merge leaves the output untouched.
+pub fn run(args: &MergeArgs) -> Result<usize, ParseError> {
+    let mut statements = Vec::new();
+    for path in &args.inputs {
+        statements.extend(
+            args.read
+                .read_all(&mut File::open(path)?, &args.in_format)?,
+        );
+    }
+
+    let merged = Statement::merge(statements)?;
+    let transactions = merged.transactions().len();
+    let output_options = args.writer.output_options();
+    let original = args
+        .read
+        .strict
+        .then(|| Camt053Statement::from(merged.clone()));
+    let mut output = Vec::new();
+    write_output(merged, &mut output, &args.out_format, &output_options)?;
+    if let Some(original) = original {
+        verify::verify(&original, &output, &args.out_format, &output_options)?;
+    }
+
+    match &args.output {
+        Some(path) => fs::write(path, output)?,
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(&output)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Command line of the subcommand alone
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: MergeArgs,
+    }
+
+    const JANUARY: &str = ":20:REF1\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                           :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+    const FEBRUARY: &str = ":20:REF2\n:25:NL81ASNB9999999999\n:60F:C200102EUR75,00\n\
+                            :61:200203C40,00NTRFNONREF\n:86:Refund\n:62F:C200203EUR115,00\n";
+
+    #[test]
+    fn test_merges_with_writer_options_and_refuses_overlaps() {
+        let root = std::env::temp_dir().join(format!("ledger-bridge-merge-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let january = root.join("january.sta");
+        let february = root.join("february.sta");
+        fs::write(&january, JANUARY).unwrap();
+        fs::write(&february, FEBRUARY).unwrap();
+        let output = root.join("merged.json");
+
+        let args = |inputs: &[&PathBuf]| {
+            let mut argv = vec![
+                "merge",
+                "--out-format",
+                "json",
+                "--strict",
+                "--canonical-json",
+            ];
+            argv.extend(inputs.iter().map(|path| path.to_str().unwrap()));
+            argv.extend(["-o", output.to_str().unwrap()]);
+            Cli::parse_from(argv).args
+        };
+
+        assert_eq!(run(&args(&[&february, &january])).unwrap(), 2);
+        let merged = fs::read(&output).unwrap();
+        assert!(merged.starts_with(b"{\"account_number\":"));
+        let merged = Statement::from_read(&mut merged.as_slice(), "json").unwrap();
+        let view = merged.as_view();
+        assert_eq!((view.opening_balance, view.closing_balance), (100.0, 115.0));
+
+        let error = run(&args(&[&january, &february, &january])).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid opening date of statement 3"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,276 @@
+//! Prometheus metrics for conversion runs.
+//!
+//! The CLI has no long-running process to scrape, so metrics are written in the
+//! Prometheus text exposition format to a file, meant to be picked up by the
+//! node_exporter textfile collector. Every run adds its counts to the ones already
+//! in the file, so the `_total` counters and the histogram grow across runs as
+//! Prometheus counters do. Writing goes through a temporary file and a rename so
+//! the collector never reads a half-written file.
+
+use ledger_parser::ParseError;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the conversion latency histogram buckets
+const LATENCY_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// Metric name prefix shared by every exported series
+const METRIC_PREFIX: &str = "ledger_bridge";
+
+/// Counters and latency histogram collected over one CLI invocation.
+#[derive(Debug, Default, PartialEq)]
+pub struct ConversionMetrics {
+    files_processed: u64,
+    transactions_converted: u64,
+    failures: BTreeMap<String, u64>,
+    latency_bucket_counts: [u64; LATENCY_BUCKETS.len()],
+    latency_count: u64,
+    latency_sum: f64,
+}
+
+impl ConversionMetrics {
+    /// Record a successfully converted file
+    pub fn record_success(&mut self, transactions: usize, elapsed: Duration) {
+        self.files_processed += 1;
+        self.transactions_converted += transactions as u64;
+        self.observe_latency(elapsed);
+    }
+
+    /// Record a failed conversion, labelled by the error's stable code
+    pub fn record_failure(&mut self, error: &ParseError, elapsed: Duration) {
+        self.files_processed += 1;
+        *self.failures.entry(error.code().to_string()).or_insert(0) += 1;
+        self.observe_latency(elapsed);
+    }
+
+    /// Record the outcome of one conversion
+    pub fn record(&mut self, result: Result<usize, &ParseError>, elapsed: Duration) {
+        match result {
+            Ok(transactions) => self.record_success(transactions, elapsed),
+            Err(error) => self.record_failure(error, elapsed),
+        }
+    }
+
+    fn observe_latency(&mut self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        for (bucket, upper_bound) in self.latency_bucket_counts.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.latency_count += 1;
+        self.latency_sum += seconds;
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        // Writing into a String cannot fail, so the fmt::Results are ignored
+        let _ = writeln!(
+            out,
+            "# HELP {METRIC_PREFIX}_files_processed_total Statement files processed."
+        );
+        let _ = writeln!(out, "# TYPE {METRIC_PREFIX}_files_processed_total counter");
+        let _ = writeln!(
+            out,
+            "{METRIC_PREFIX}_files_processed_total {}",
+            self.files_processed
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP {METRIC_PREFIX}_failures_total Failed conversions by error code."
+        );
+        let _ = writeln!(out, "# TYPE {METRIC_PREFIX}_failures_total counter");
+        for (code, count) in &self.failures {
+            let _ = writeln!(
+                out,
+                "{METRIC_PREFIX}_failures_total{{code=\"{code}\"}} {count}"
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP {METRIC_PREFIX}_transactions_converted_total Transactions written to outputs."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE {METRIC_PREFIX}_transactions_converted_total counter"
+        );
+        let _ = writeln!(
+            out,
+            "{METRIC_PREFIX}_transactions_converted_total {}",
+            self.transactions_converted
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP {METRIC_PREFIX}_conversion_duration_seconds Time spent converting one file."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE {METRIC_PREFIX}_conversion_duration_seconds histogram"
+        );
+        for (count, upper_bound) in self.latency_bucket_counts.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(
+                out,
+                "{METRIC_PREFIX}_conversion_duration_seconds_bucket{{le=\"{upper_bound}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{METRIC_PREFIX}_conversion_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            self.latency_count
+        );
+        let _ = writeln!(
+            out,
+            "{METRIC_PREFIX}_conversion_duration_seconds_sum {}",
+            self.latency_sum
+        );
+        let _ = writeln!(
+            out,
+            "{METRIC_PREFIX}_conversion_duration_seconds_count {}",
+            self.latency_count
+        );
+
+        out
+    }
+
+    /// Add these metrics to the ones in `path`, written by earlier runs, and
+    /// atomically write the sums back
+    pub fn add_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut totals = match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e),
+        };
+        totals.add(self);
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        fs::write(&temp_path, totals.render())?;
+        fs::rename(&temp_path, path)
+    }
+
+    /// Add the counts of `other` to these
+    fn add(&mut self, other: &Self) {
+        self.files_processed += other.files_processed;
+        self.transactions_converted += other.transactions_converted;
+        for (code, count) in &other.failures {
+            *self.failures.entry(code.clone()).or_insert(0) += count;
+        }
+        for (bucket, count) in self
+            .latency_bucket_counts
+            .iter_mut()
+            .zip(other.latency_bucket_counts)
+        {
+            *bucket += count;
+        }
+        self.latency_count += other.latency_count;
+        self.latency_sum += other.latency_sum;
+    }
+
+    /// Read back metrics written by [`ConversionMetrics::render`]; comments and
+    /// series of other exporters are skipped
+    fn parse(text: &str) -> io::Result<Self> {
+        let mut metrics = Self::default();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let Some((series, value)) = line.rsplit_once(' ') else {
+                continue;
+            };
+            let Some(series) = series
+                .strip_prefix(METRIC_PREFIX)
+                .and_then(|series| series.strip_prefix('_'))
+            else {
+                continue;
+            };
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid metrics line: {}", line),
+                )
+            };
+            let count = || value.parse::<u64>().map_err(|_| invalid());
+            match series {
+                "files_processed_total" => metrics.files_processed = count()?,
+                "transactions_converted_total" => metrics.transactions_converted = count()?,
+                "conversion_duration_seconds_count" => metrics.latency_count = count()?,
+                "conversion_duration_seconds_sum" => {
+                    metrics.latency_sum = value.parse().map_err(|_| invalid())?
+                }
+                _ => {
+                    if let Some(code) = label(series, "failures_total", "code") {
+                        metrics.failures.insert(code.to_string(), count()?);
+                    } else if let Some(bound) =
+                        label(series, "conversion_duration_seconds_bucket", "le")
+                    {
+                        let index = LATENCY_BUCKETS
+                            .iter()
+                            .position(|upper_bound| upper_bound.to_string() == bound);
+                        if let Some(index) = index {
+                            metrics.latency_bucket_counts[index] = count()?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(metrics)
+    }
+}
+
+/// Value of the single label `key` of `series` when it is the metric `name`
+fn label<'a>(series: &'a str, name: &str, key: &str) -> Option<&'a str> {
+    series
+        .strip_prefix(name)?
+        .strip_prefix('{')?
+        .strip_prefix(key)?
+        .strip_prefix("=\"")?
+        .strip_suffix("\"}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_and_histogram() {
+        let mut metrics = ConversionMetrics::default();
+        metrics.record_success(12, Duration::from_millis(20));
+        metrics.record_failure(
+            &ParseError::Mt940Error("bad".into()),
+            Duration::from_secs(20),
+        );
+
+        let text = metrics.render();
+        assert!(text.contains("ledger_bridge_files_processed_total 2"));
+        assert!(text.contains("ledger_bridge_failures_total{code=\"mt940_error\"} 1"));
+        assert!(text.contains("ledger_bridge_transactions_converted_total 12"));
+        assert!(text.contains("ledger_bridge_conversion_duration_seconds_bucket{le=\"0.025\"} 1"));
+        assert!(text.contains("ledger_bridge_conversion_duration_seconds_bucket{le=\"+Inf\"} 2"));
+        assert!(text.contains("ledger_bridge_conversion_duration_seconds_count 2"));
+        assert_eq!(ConversionMetrics::parse(&text).unwrap(), metrics);
+    }
+
+    #[test]
+    fn test_runs_add_up_in_the_file() {
+        let path =
+            std::env::temp_dir().join(format!("ledger-bridge-metrics-{}.prom", std::process::id()));
+        let _ = fs::remove_file(&path);
+        for transactions in [3, 4] {
+            let mut metrics = ConversionMetrics::default();
+            metrics.record_success(transactions, Duration::from_millis(20));
+            metrics.add_to_file(&path).unwrap();
+        }
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("ledger_bridge_files_processed_total 2"));
+        assert!(text.contains("ledger_bridge_transactions_converted_total 7"));
+        assert!(text.contains("ledger_bridge_conversion_duration_seconds_bucket{le=\"0.025\"} 2"));
+        fs::remove_file(&path).unwrap();
+    }
+}
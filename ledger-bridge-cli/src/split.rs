@@ -0,0 +1,188 @@
+//! `split` subcommand: write a statement as one file per calendar month or per
+//! N entries, each a complete statement with its own opening and closing balance.
+
+use chrono::{DateTime, FixedOffset};
+use clap::Args;
+use ledger_parser::{Camt053Statement, ParseError, Statement};
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use crate::input::InputArgs;
+use crate::{verify, write_output, WriterArgs};
+
+/// Name of the output files when the statement comes from stdin
+const STDIN_STEM: &str = "statement";
+
+/// Arguments of `ledger-bridge split`
+#[derive(Args)]
+pub struct SplitArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Directory the parts are written to, as `<input name>-YYYY-MM.<format>` with
+    /// `--by-month` and `<input name>-<part>.<format>` with `--every`
+    #[arg(long, value_name = "DIR")]
+    output_dir: PathBuf,
+
+    /// Output format: csv, mt940, mt942, camt053, camt054, json or qif
+    #[arg(long, value_name = "FORMAT")]
+    out_format: String,
+
+    /// Write one part per calendar month of the booking dates
+    #[arg(long, required_unless_present = "every", conflicts_with = "every")]
+    by_month: bool,
+
+    /// Write parts of at most N entries
+    #[arg(long, value_name = "N")]
+    every: Option<NonZeroUsize>,
+
+    #[command(flatten)]
+    writer: WriterArgs,
+}
+
+/// Run the `split` subcommand, returning the number of files written.
+///
+/// Every part is written to memory first, and with `--strict` read back and
+/// verified, so a failing part leaves no files behind.
+pub fn run(args: &SplitArgs) -> Result<usize, ParseError> {
+    let statement = args.input.read()?;
+    let statement = Camt053Statement::from(statement);
+    let parts = match args.every {
+        Some(every) => statement.split_by_transaction_count(every.get()),
+        None => statement.split_by_month(),
+    };
+    let stem = args
+        .input
+        .input
+        .as_deref()
+        .and_then(Path::file_stem)
+        .map_or(STDIN_STEM.into(), |stem| stem.to_string_lossy());
+    let extension = args.out_format.to_lowercase();
+
+    let output_options = args.writer.output_options();
+    let mut files = Vec::with_capacity(parts.len());
+    for (index, part) in parts.into_iter().enumerate() {
+        let suffix = match args.every {
+            Some(_) => (index + 1).to_string(),
+            None => month_of(&part).format("%Y-%m").to_string(),
+        };
+        let path = args
+            .output_dir
+            .join(format!("{}-{}.{}", stem, suffix, extension));
+        let original = args.input.read.strict.then(|| part.clone());
+        let mut output = Vec::new();
+        write_output(
+            Statement::Camt053(part),
+            &mut output,
+            &args.out_format,
+            &output_options,
+        )?;
+        if let Some(original) = original {
+            verify::verify(&original, &output, &args.out_format, &output_options)?;
+        }
+        files.push((path, output));
+    }
+
+    fs::create_dir_all(&args.output_dir)?;
+    let count = files.len();
+    for (path, output) in files {
+        fs::write(path, output)?;
+    }
+    Ok(count)
+}
+
+/// Date within the month of a `--by-month` part: its first booking, or its
+/// closing date when the statement has no entries
+fn month_of(part: &Camt053Statement) -> DateTime<FixedOffset> {
+    part.transactions
+        .first()
+        .map_or(part.closing_date, |tx| tx.booking_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use ledger_parser::EntryStatus;
+
+    /// Command line of the subcommand alone
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: SplitArgs,
+    }
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:200131D25,00NTRFNONREF\n:86:Lunch\n\
+                         :61:200203C40,00NTRFNONREF\n:86:Refund\n\
+                         :61:200204D5,00NTRFNONREF\n:86:Coffee\n:62F:C200204EUR110,00\n";
+
+    #[test]
+    fn test_splits_by_month_and_by_count() {
+        let root = std::env::temp_dir().join(format!("ledger-bridge-split-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let input = root.join("bank.sta");
+        fs::write(&input, MT940).unwrap();
+
+        let args = |mode: &[&str]| {
+            let mut argv = vec!["split", "--out-format", "mt940", "-i"];
+            argv.push(input.to_str().unwrap());
+            argv.push("--output-dir");
+            argv.push(root.to_str().unwrap());
+            argv.extend(mode);
+            Cli::parse_from(argv).args
+        };
+
+        assert_eq!(run(&args(&["--by-month"])).unwrap(), 2);
+        let february = fs::read_to_string(root.join("bank-2020-02.mt940")).unwrap();
+        assert!(february.contains(":60F:C200131EUR75,00"));
+        assert!(february.contains(":62F:C200204EUR110,00"));
+
+        assert_eq!(run(&args(&["--every", "2"])).unwrap(), 2);
+        let first = fs::read_to_string(root.join("bank-1.mt940")).unwrap();
+        assert!(first.contains(":62F:C200203EUR115,00"));
+
+        assert!(
+            Cli::try_parse_from(["split", "--out-format", "qif", "--output-dir", "x"]).is_err()
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_pending_entry_and_writer_options() {
+        let root = std::env::temp_dir().join(format!(
+            "ledger-bridge-split-pending-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let input = root.join("held.json");
+        let mut statement = Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+        statement.transactions_mut()[0].status = EntryStatus::Pending;
+        statement.recompute_closing_balance();
+        let mut json = Vec::new();
+        statement.write_to(&mut json, "json").unwrap();
+        fs::write(&input, json).unwrap();
+
+        let argv = [
+            "split",
+            "--out-format",
+            "json",
+            "--every",
+            "1",
+            "--strict",
+            "--canonical-json",
+            "-i",
+            input.to_str().unwrap(),
+            "--output-dir",
+            root.to_str().unwrap(),
+        ];
+        assert_eq!(run(&Cli::parse_from(argv).args).unwrap(), 3);
+        let second = fs::read(root.join("held-2.json")).unwrap();
+        assert!(second.starts_with(b"{\"account_number\":"));
+        let second = Statement::from_read(&mut second.as_slice(), "json").unwrap();
+        let view = second.as_view();
+        assert_eq!((view.opening_balance, view.closing_balance), (100.0, 140.0));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! `summarize` subcommand: consolidated view over one statement or a directory
+//! of statements of several accounts.
+
+use clap::{Args, ValueEnum};
+use ledger_parser::{ParseError, Portfolio, Statement};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::input::InputArgs;
+use crate::locale::Locale;
+
+/// Arguments of `ledger-bridge summarize`
+#[derive(Args)]
+pub struct SummarizeArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Directory whose statement files (one or more accounts) are combined
+    #[arg(long, value_name = "DIR", conflicts_with = "input")]
+    portfolio: Option<PathBuf>,
+
+    /// Output format of the summary
+    #[arg(long, value_enum, default_value_t = SummarizeOutput::Text)]
+    out: SummarizeOutput,
+
+    /// Number, date and currency conventions of text output: iso, en-US, en-GB, de-DE, fr-FR, ru-RU or sv-SE
+    #[arg(long, value_enum, ignore_case = true, default_value_t = Locale::Iso)]
+    locale: Locale,
+}
+
+/// Output formats supported by `summarize`
+#[derive(Clone, Copy, ValueEnum)]
+enum SummarizeOutput {
+    /// Accounts, transfers and totals per currency, one per line
+    Text,
+    /// Portfolio as JSON, including the combined balance timeline
+    Json,
+}
+
+/// Run the `summarize` subcommand, returning the number of accounts
+pub fn run(args: &SummarizeArgs) -> Result<usize, ParseError> {
+    let statements = match &args.portfolio {
+        Some(dir) => {
+            let mut statements = Vec::new();
+            for path in statement_files(dir)? {
+                statements.extend(
+                    args.input
+                        .read
+                        .read_all(&mut File::open(&path)?, &args.input.in_format)?,
+                );
+            }
+            statements
+        }
+        None => vec![args.input.read()?],
+    };
+
+    let portfolio = Portfolio::from_statements(statements.iter().map(Statement::as_view));
+    let mut output = BufWriter::new(io::stdout().lock());
+    match args.out {
+        SummarizeOutput::Text => write_text(&mut output, &portfolio, args.locale)?,
+        SummarizeOutput::Json => portfolio.write_to(&mut output)?,
+    }
+    output.flush()?;
+
+    Ok(portfolio.accounts.len())
+}
+
+/// Regular, non-hidden files of `dir` in name order
+fn statement_files(dir: &Path) -> Result<Vec<PathBuf>, ParseError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if entry.file_type()?.is_file() && !hidden {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn write_text<W: Write>(
+    writer: &mut W,
+    portfolio: &Portfolio,
+    locale: Locale,
+) -> Result<(), ParseError> {
+    for account in &portfolio.accounts {
+        writeln!(
+            writer,
+            "account\t{}\t{}\t{}",
+            account.account_number,
+            locale.money(account.opening_balance, &account.currency),
+            locale.money(account.closing_balance, &account.currency)
+        )?;
+    }
+    for transfer in &portfolio.transfers {
+        writeln!(
+            writer,
+            "transfer\t{}\t{}\t{} -> {}\t{}{}",
+            locale.date(transfer.debit_date),
+            locale.date(transfer.credit_date),
+            transfer.from_account,
+            transfer.to_account,
+            locale.money(transfer.amount, &transfer.currency),
+            if transfer.confirmed {
+                "\tconfirmed"
+            } else {
+                ""
+            }
+        )?;
+    }
+    for (currency, totals) in &portfolio.totals {
+        let closing = portfolio
+            .timeline
+            .get(currency)
+            .and_then(|days| days.values().next_back())
+            .copied()
+            .unwrap_or_default();
+        writeln!(
+            writer,
+            "total\t{}\tin {}\tout {}\tnet {}\tbalance {}",
+            currency,
+            locale.money(totals.credit_total, currency),
+            locale.money(totals.debit_total, currency),
+            locale.money(totals.net(), currency),
+            locale.money(closing, currency)
+        )?;
+    }
+    Ok(())
+}
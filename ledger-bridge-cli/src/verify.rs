@@ -0,0 +1,523 @@
+//! `--verify`: read the converted output back and compare it with the parsed
+//! input, so a pipeline fails instead of delivering a conversion that lost
+//! more than the output format is known to drop. `--warn-lossy` reads it back
+//! the same way to list what the format did drop.
+
+use chrono::{DateTime, FixedOffset, NaiveTime, Offset, Utc};
+use ledger_parser::{
+    BalanceCode, BalanceType, Camt053Statement, ConversionReport, CsvDialect, EntryStatus,
+    Mt940Dialect, ParseError, ParseOptions, StatementInfo,
+};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::{parse_all_input, OutputOptions};
+
+/// Parts of a statement an output format keeps; the others are not compared
+#[derive(Debug, Clone, Copy)]
+struct Capabilities {
+    account_number: bool,
+    currency: bool,
+    balances: bool,
+    balance_dates: bool,
+    /// Kinds of additional balances, beside opening and closing
+    additional_balances: &'static [BalanceCode],
+    /// Booking dates keep their time of day, not only the date
+    booking_time: bool,
+    value_date: bool,
+    description: bool,
+    reference: bool,
+    counterparty_name: bool,
+    counterparty_account: bool,
+    counterparty_bic: bool,
+    counterparty_address: bool,
+    /// Entry currency, original amount and currency, and exchange rate
+    foreign_amounts: bool,
+    remittance: bool,
+    /// End-to-end and mandate ids
+    sepa_ids: bool,
+    /// Entry status and reversal indicator
+    entry_status: bool,
+    supplementary_data: bool,
+    /// Payments of batch entries
+    batch_details: bool,
+    /// Charges with their type codes and bearers
+    charges: bool,
+    /// [`StatementInfo`] and statement-level supplementary data
+    statement_info: bool,
+}
+
+/// Every part survives
+const ALL: Capabilities = Capabilities {
+    account_number: true,
+    currency: true,
+    balances: true,
+    balance_dates: true,
+    additional_balances: &[
+        BalanceCode::OpeningAvailable,
+        BalanceCode::ClosingAvailable,
+        BalanceCode::ForwardAvailable,
+        BalanceCode::InterimBooked,
+        BalanceCode::InterimAvailable,
+    ],
+    booking_time: true,
+    value_date: true,
+    description: true,
+    reference: true,
+    counterparty_name: true,
+    counterparty_account: true,
+    counterparty_bic: true,
+    counterparty_address: true,
+    foreign_amounts: true,
+    remittance: true,
+    sepa_ids: true,
+    entry_status: true,
+    supplementary_data: true,
+    batch_details: true,
+    charges: true,
+    statement_info: true,
+};
+
+/// Only account, balances, dates, amounts, descriptions and references survive
+const BASIC: Capabilities = Capabilities {
+    additional_balances: &[],
+    booking_time: false,
+    value_date: false,
+    counterparty_name: false,
+    counterparty_account: false,
+    counterparty_bic: false,
+    counterparty_address: false,
+    foreign_amounts: false,
+    remittance: false,
+    sepa_ids: false,
+    entry_status: false,
+    supplementary_data: false,
+    batch_details: false,
+    charges: false,
+    statement_info: false,
+    ..ALL
+};
+
+/// What `format` written with `options` keeps
+fn capabilities(format: &str, options: &OutputOptions) -> Capabilities {
+    match format.to_lowercase().as_str() {
+        "camt053" => Capabilities {
+            booking_time: options.camt053_booking_time,
+            ..ALL
+        },
+        // Notifications carry no balances, booking times or statement details
+        "camt054" => Capabilities {
+            balances: false,
+            balance_dates: false,
+            additional_balances: &[],
+            booking_time: false,
+            statement_info: false,
+            ..ALL
+        },
+        "json" => Capabilities {
+            additional_balances: &[],
+            statement_info: false,
+            ..ALL
+        },
+        "mt940" => mt940_capabilities(options.mt940_dialect),
+        // Interim reports carry no balances and always use the standard `:86:` layout
+        "mt942" => Capabilities {
+            balances: false,
+            balance_dates: false,
+            ..BASIC
+        },
+        // QIF has no account, currency or value date, and dates its opening balance
+        // at the first transaction
+        "qif" => Capabilities {
+            account_number: false,
+            currency: false,
+            balance_dates: false,
+            counterparty_name: true,
+            ..BASIC
+        },
+        _ => BASIC,
+    }
+}
+
+/// What MT940 keeps: `:61:` has a single date, `:64:`/`:65:` the available
+/// balances, and the `:86:` dialect decides the rest. The structured dialects
+/// cut descriptions to fit 6 lines, and their values cannot hold the `?` of
+/// German subfields or the `/` of keywords, so only the ids, agent and amounts
+/// recovered from them compare; `--warn-lossy` reports the cut descriptions.
+/// Dutch `/CHGS/` keeps the amounts of charges but not their codes, so charges
+/// do not compare.
+fn mt940_capabilities(dialect: Mt940Dialect) -> Capabilities {
+    let mt940 = Capabilities {
+        additional_balances: &[BalanceCode::ClosingAvailable, BalanceCode::ForwardAvailable],
+        ..BASIC
+    };
+    match dialect {
+        Mt940Dialect::Standard => mt940,
+        Mt940Dialect::German => Capabilities {
+            description: false,
+            counterparty_bic: true,
+            sepa_ids: true,
+            ..mt940
+        },
+        Mt940Dialect::Dutch => Capabilities {
+            description: false,
+            counterparty_bic: true,
+            foreign_amounts: true,
+            sepa_ids: true,
+            ..mt940
+        },
+        Mt940Dialect::Abn | Mt940Dialect::Swift => Capabilities {
+            description: false,
+            counterparty_bic: true,
+            sepa_ids: true,
+            ..mt940
+        },
+    }
+}
+
+/// Read `output` back as `format` and compare it with `original`.
+///
+/// # Errors
+///
+/// Returns `ParseError::VerificationFailed` naming the first statement part or
+/// transaction that differs, or why the output could not be read back.
+pub fn verify(
+    original: &Camt053Statement,
+    output: &[u8],
+    format: &str,
+    options: &OutputOptions,
+) -> Result<(), ParseError> {
+    let converted = read_back(output, format)?;
+    let caps = capabilities(format, options);
+    let mut expected = project(original, caps);
+    let mut found = project(&converted, caps);
+    drop_generated(&expected.info, &mut found.info);
+    if expected.transactions.len() != found.transactions.len() {
+        return Err(ParseError::VerificationFailed(format!(
+            "{} output has {} transactions instead of {}",
+            format,
+            found.transactions.len(),
+            expected.transactions.len()
+        )));
+    }
+    for (index, (expected, found)) in expected
+        .transactions
+        .iter()
+        .zip(&found.transactions)
+        .enumerate()
+    {
+        let fields = differing_fields(expected, found)?;
+        if !fields.is_empty() {
+            return Err(ParseError::VerificationFailed(format!(
+                "transaction {} changed in {} output: {}",
+                index + 1,
+                format,
+                fields.join(", ")
+            )));
+        }
+    }
+
+    expected.transactions.clear();
+    found.transactions.clear();
+    let fields = differing_fields(&expected, &found)?;
+    if !fields.is_empty() {
+        return Err(ParseError::VerificationFailed(format!(
+            "statement changed in {} output: {}",
+            format,
+            fields.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Fields of `original` that `output`, written as `format`, dropped or truncated.
+///
+/// # Errors
+///
+/// Returns `ParseError::VerificationFailed` if the output cannot be read back.
+pub fn losses(
+    original: &Camt053Statement,
+    output: &[u8],
+    format: &str,
+) -> Result<ConversionReport, ParseError> {
+    let converted = read_back(output, format)?;
+    Ok(ConversionReport::compare(
+        original.as_view(),
+        converted.as_view(),
+    ))
+}
+
+/// `output` read back as `format`, with the parts of split output joined
+fn read_back(output: &[u8], format: &str) -> Result<Camt053Statement, ParseError> {
+    // Split output reads back as several statements of the same account; a
+    // record the reader would skip is a failure, not a warning
+    let mut parts = parse_all_input(
+        &mut &output[..],
+        format,
+        &CsvDialect::Sberbank(ParseOptions::strict()),
+        &ParseOptions::strict(),
+    )
+    .map_err(|e| {
+        ParseError::VerificationFailed(format!("{} output cannot be read back: {}", format, e))
+    })?
+    .into_iter()
+    .map(Camt053Statement::from);
+    let mut converted = parts.next().ok_or_else(|| {
+        ParseError::VerificationFailed(format!("{} output holds no statement", format))
+    })?;
+    for part in parts {
+        converted.transactions.extend(part.transactions);
+        converted.closing_balance = part.closing_balance;
+        converted.closing_indicator = part.closing_indicator;
+        converted.closing_date = part.closing_date;
+    }
+    Ok(converted)
+}
+
+/// Clear the identification the CAMT writers generate for statements without
+/// one from `found`, as it was added rather than changed
+fn drop_generated(expected: &StatementInfo, found: &mut StatementInfo) {
+    if expected.statement_id.is_none() {
+        found.statement_id = None;
+    }
+    if expected.sequence_number.is_none() {
+        found.sequence_number = None;
+    }
+    if expected.created_at.is_none() {
+        found.created_at = None;
+    }
+}
+
+/// `statement` with the parts `caps` does not keep reset, amounts rounded to
+/// cents and dates cut to the day where times are not kept
+fn project(statement: &Camt053Statement, caps: Capabilities) -> Camt053Statement {
+    let mut projected = statement.clone();
+    if !caps.account_number {
+        projected.account_number.clear();
+    }
+    if !caps.currency {
+        projected.currency.clear();
+    }
+    projected.opening_balance = round_cents(projected.opening_balance);
+    projected.closing_balance = round_cents(projected.closing_balance);
+    if !caps.balances {
+        projected.opening_balance = 0.0;
+        projected.opening_indicator = BalanceType::Credit;
+        projected.closing_balance = 0.0;
+        projected.closing_indicator = BalanceType::Credit;
+    }
+    if caps.balance_dates {
+        projected.opening_date = day(projected.opening_date);
+        projected.closing_date = day(projected.closing_date);
+    } else {
+        projected.opening_date = DateTime::UNIX_EPOCH.fixed_offset();
+        projected.closing_date = DateTime::UNIX_EPOCH.fixed_offset();
+    }
+    projected
+        .balances
+        .retain(|balance| caps.additional_balances.contains(&balance.code));
+    for balance in &mut projected.balances {
+        balance.amount = round_cents(balance.amount);
+        balance.date = day(balance.date);
+    }
+    if !caps.statement_info {
+        projected.info = StatementInfo::default();
+        projected.supplementary_data.clear();
+    }
+
+    for tx in &mut projected.transactions {
+        tx.amount = round_cents(tx.amount);
+        // The MT940 writer fills in `:61:` codes the input does not carry
+        tx.swift_entry = None;
+        if !caps.booking_time {
+            tx.booking_date = day(tx.booking_date);
+        }
+        if !caps.value_date {
+            tx.value_date = None;
+        }
+        if !caps.description {
+            tx.description.clear();
+        }
+        if !caps.reference {
+            tx.reference = None;
+        }
+        if !caps.counterparty_name {
+            tx.counterparty_name = None;
+        }
+        if !caps.counterparty_account {
+            tx.counterparty_account = None;
+        }
+        if !caps.counterparty_bic {
+            tx.counterparty_bic = None;
+        }
+        if !caps.counterparty_address {
+            tx.counterparty_address = None;
+        }
+        if !caps.foreign_amounts {
+            tx.currency = None;
+            tx.original_amount = None;
+            tx.original_currency = None;
+            tx.exchange_rate = None;
+        }
+        if !caps.remittance {
+            tx.remittance = None;
+        }
+        if !caps.sepa_ids {
+            tx.end_to_end_id = None;
+            tx.mandate_id = None;
+        }
+        if !caps.entry_status {
+            tx.status = EntryStatus::Booked;
+            tx.is_reversal = false;
+        }
+        if !caps.supplementary_data {
+            tx.supplementary_data.clear();
+        }
+        if !caps.batch_details {
+            tx.details.clear();
+        }
+        if !caps.charges {
+            tx.charges.clear();
+        }
+    }
+    projected
+}
+
+/// Top-level fields whose serialized values differ between `expected` and `found`
+fn differing_fields<T: Serialize>(expected: &T, found: &T) -> Result<Vec<String>, ParseError> {
+    let to_value = |value: &T| {
+        serde_json::to_value(value)
+            .map_err(|e| ParseError::JsonError(format!("Failed to compare statements: {}", e)))
+    };
+    let (expected, found) = (to_value(expected)?, to_value(found)?);
+    let (Some(expected), Some(found)) = (expected.as_object(), found.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let fields: BTreeSet<&String> = expected
+        .keys()
+        .chain(found.keys())
+        .filter(|key| expected.get(*key) != found.get(*key))
+        .collect();
+    Ok(fields.into_iter().cloned().collect())
+}
+
+/// Midnight UTC of the day of `date`, as formats without times read it back
+fn day(date: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    date.date_naive()
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+        .with_timezone(&Utc.fix())
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, write_output};
+    use ledger_parser::Statement;
+
+    /// CAMT.053 statement using every part of the model
+    const RICH_CAMT053: &str = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+        <Id>STMT-42</Id><ElctrncSeqNb>42</ElctrncSeqNb>
+        <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy><Ownr><Nm>ACME GmbH</Nm></Ownr></Acct>
+        <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+        <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">125.50</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-21</Dt></Dt></Bal>
+        <Bal><Tp><CdOrPrtry><Cd>CLAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.50</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-21</Dt></Dt></Bal>
+        <Bal><Tp><CdOrPrtry><Cd>ITAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">90.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+        <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts>
+            <BookgDt><DtTm>2025-04-20T09:15:00+02:00</DtTm></BookgDt><ValDt><Dt>2025-04-21</Dt></ValDt>
+            <Chrgs><Amt Ccy="EUR">1.50</Amt><CdtDbtInd>DBIT</CdtDbtInd><Tp><Cd>COMM</Cd></Tp><Br>DEBT</Br></Chrgs>
+            <NtryDtls><TxDtls><Refs><EndToEndId>E2E-17</EndToEndId><TxId>INV17</TxId><MndtId>MANDATE-1</MndtId></Refs>
+                <AmtDtls><InstdAmt><Amt Ccy="USD">54.00</Amt><CcyXchg><SrcCcy>USD</SrcCcy><TrgtCcy>EUR</TrgtCcy><XchgRate>0.9259</XchgRate></CcyXchg></InstdAmt></AmtDtls>
+                <RltdPties><Dbtr><Nm>Customer Ltd</Nm><PstlAdr><TwnNm>London</TwnNm><Ctry>GB</Ctry></PstlAdr></Dbtr>
+                    <DbtrAcct><Id><IBAN>GB29NWBK60161331926819</IBAN></Id></DbtrAcct></RltdPties>
+                <RltdAgts><DbtrAgt><FinInstnId><BIC>NWBKGB2L</BIC></FinInstnId></DbtrAgt></RltdAgts>
+                <RmtInf><Ustrd>Invoice 17</Ustrd></RmtInf>
+            </TxDtls></NtryDtls></Ntry>
+        <Ntry><Amt Ccy="EUR">24.50</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts>
+            <BookgDt><Dt>2025-04-21</Dt></BookgDt><NtryDtls><TxDtls><RmtInf><Ustrd>Card payment</Ustrd></RmtInf></TxDtls></NtryDtls></Ntry>
+    </Stmt></BkToCstmrStmt></Document>"#;
+
+    fn rich_statement() -> Statement {
+        parse_input(
+            &mut RICH_CAMT053.as_bytes(),
+            "camt053",
+            &CsvDialect::default(),
+            &ParseOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_capabilities_match_the_writers() {
+        let dialects = [
+            Mt940Dialect::Standard,
+            Mt940Dialect::German,
+            Mt940Dialect::Dutch,
+            Mt940Dialect::Abn,
+            Mt940Dialect::Swift,
+        ];
+        for format in ["mt940", "mt942", "camt053", "camt054", "json", "qif"] {
+            for dialect in dialects {
+                let options = OutputOptions {
+                    mt940_dialect: dialect,
+                    ..OutputOptions::default()
+                };
+                let statement = rich_statement();
+                let original = Camt053Statement::from(statement.clone());
+                let mut output = Vec::new();
+                write_output(statement, &mut output, format, &options).unwrap();
+                if let Err(error) = verify(&original, &output, format, &options) {
+                    panic!("{} ({}): {}", format, dialect, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lost_data_fails_verification() {
+        // The Sberbank CSV layout only reads domestic account numbers back
+        let statement = rich_statement();
+        let original = Camt053Statement::from(statement.clone());
+        let mut output = Vec::new();
+        let options = OutputOptions::default();
+        write_output(statement, &mut output, "csv", &options).unwrap();
+        let error = verify(&original, &output, "csv", &options).unwrap_err();
+        assert_eq!(error.code(), "verification_failed");
+
+        let statement = rich_statement();
+        let mut output = Vec::new();
+        write_output(statement, &mut output, "json", &options).unwrap();
+
+        let lossy = String::from_utf8(output)
+            .unwrap()
+            .replace("MANDATE-1", "MANDATE-2");
+        let error = verify(&original, lossy.as_bytes(), "json", &options).unwrap_err();
+        assert_eq!(error.code(), "verification_failed");
+        assert_eq!(
+            error.to_string(),
+            "Verification failed: transaction 1 changed in json output: mandate_id"
+        );
+    }
+
+    #[test]
+    fn test_losses_of_the_output_format() {
+        let options = OutputOptions::default();
+        let original = Camt053Statement::from(rich_statement());
+        let mut output = Vec::new();
+        write_output(rich_statement(), &mut output, "camt053", &options).unwrap();
+        assert!(losses(&original, &output, "camt053").unwrap().is_lossless());
+
+        let mut output = Vec::new();
+        write_output(rich_statement(), &mut output, "qif", &options).unwrap();
+        let report = losses(&original, &output, "qif").unwrap();
+        let fields: Vec<_> = report.losses.iter().map(|loss| loss.field).collect();
+        assert!(fields.contains(&"currency"));
+        assert!(fields.contains(&"mandate_id"));
+        assert!(!fields.contains(&"description"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Writer benchmarks
+//!
+//! Measures `write_to` throughput for every format on a large synthetic statement,
+//! both into an in-memory buffer and into a file, so regressions in buffering or
+//! per-field allocation show up as a measurable slowdown.
+//!
+//! Run with `cargo bench -p ledger-parser --bench writers`.
+
+use chrono::{DateTime, Duration};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ledger_parser::{
+    BalanceType, Camt053Statement, CsvStatement, Mt940Statement, StatementInfo, Transaction,
+    TransactionType,
+};
+use std::fs::File;
+use std::hint::black_box;
+
+/// Number of transactions in the synthetic statement
+const TRANSACTION_COUNT: usize = 10_000;
+
+fn create_large_mt940(transaction_count: usize) -> Mt940Statement {
+    let start = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap();
+    let transactions = (0..transaction_count)
+        .map(|i| Transaction {
+            reference: Some(format!("REF{:08}", i)),
+            counterparty_name: Some("ACME Corporation".to_string()),
+            counterparty_account: Some("DE89370400440532013000".to_string()),
+            ..Transaction::new(
+                start + Duration::days((i % 365) as i64),
+                10.0 + i as f64 * 0.01,
+                if i % 2 == 0 {
+                    TransactionType::Credit
+                } else {
+                    TransactionType::Debit
+                },
+                format!("Invoice payment number {}", i),
+            )
+        })
+        .collect();
+
+    Mt940Statement {
+        account_number: "NL81ASNB9999999999".to_string(),
+        currency: "EUR".to_string(),
+        opening_balance: 1000.0,
+        opening_date: start,
+        opening_indicator: BalanceType::Credit,
+        closing_balance: 1000.0,
+        closing_date: start + Duration::days(364),
+        closing_indicator: BalanceType::Credit,
+        transactions,
+        info: StatementInfo::default(),
+        balances: Vec::new(),
+    }
+}
+
+fn bench_writers(c: &mut Criterion) {
+    let mt940 = create_large_mt940(TRANSACTION_COUNT);
+    let camt053: Camt053Statement = mt940.clone().into();
+    let csv: CsvStatement = mt940.clone().into();
+
+    let mut group = c.benchmark_group("write_to");
+    group.throughput(Throughput::Elements(TRANSACTION_COUNT as u64));
+
+    group.bench_function(BenchmarkId::new("mt940", "vec"), |b| {
+        b.iter(|| {
+            let mut output = Vec::new();
+            mt940.write_to(&mut output).unwrap();
+            black_box(output);
+        })
+    });
+    group.bench_function(BenchmarkId::new("camt053", "vec"), |b| {
+        b.iter(|| {
+            let mut output = Vec::new();
+            camt053.write_to(&mut output).unwrap();
+            black_box(output);
+        })
+    });
+    group.bench_function(BenchmarkId::new("csv", "vec"), |b| {
+        b.iter(|| {
+            let mut output = Vec::new();
+            csv.write_to(&mut output).unwrap();
+            black_box(output);
+        })
+    });
+
+    // Unbuffered File sinks are what the CLI hands to the writers
+    let dir = std::env::temp_dir();
+    group.bench_function(BenchmarkId::new("mt940", "file"), |b| {
+        b.iter(|| {
+            let mut file = File::create(dir.join("ledger_bench.mt940")).unwrap();
+            mt940.write_to(&mut file).unwrap();
+        })
+    });
+    group.bench_function(BenchmarkId::new("camt053", "file"), |b| {
+        b.iter(|| {
+            let mut file = File::create(dir.join("ledger_bench.xml")).unwrap();
+            camt053.write_to(&mut file).unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_writers);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+//! Export a statement with a CSV layout of your own.
+//!
+//! The first profile is built in code; the second is loaded from JSON, the way
+//! layouts kept in a configuration file are.
+//!
+//! ```text
+//! cargo run -p ledger-parser --example custom_csv_profile
+//! ```
+
+use ledger_parser::{CsvColumn, CsvProfile, Mt940Statement, ParseError};
+use std::io::{self, Write};
+
+const STATEMENT: &str = ":20:REF\n:25:DE89370400440532013000\n:28C:1/1\n:60F:C250101EUR1000,00\n\
+                         :61:250102D1234,50NTRFNONREF\n:86:Rent January\n\
+                         :61:250105C80,00NTRFINV-7\n:86:Invoice 7\n:62F:D250105EUR154,50\n";
+
+/// Layout as it could be stored next to the application's other settings
+const PROFILE_JSON: &str = r#"{
+    "name": "ledger-import",
+    "delimiter": 9,
+    "columns": [
+        ["date", "BookingDate"],
+        ["amount", "SignedAmount"],
+        ["memo", "Description"],
+        ["account", "AccountNumber"]
+    ],
+    "header": false,
+    "date_format": "%Y-%m-%d",
+    "decimal_separator": ".",
+    "sanitize_formulas": false
+}"#;
+
+fn main() -> Result<(), ParseError> {
+    let statement = Mt940Statement::from_read(&mut STATEMENT.as_bytes())?;
+    let mut output = io::stdout().lock();
+
+    // German spreadsheet layout: semicolons, decimal commas, separate
+    // debit and credit columns
+    let spreadsheet = CsvProfile {
+        name: "spreadsheet-de".into(),
+        delimiter: b';',
+        columns: vec![
+            ("Buchungstag".into(), CsvColumn::BookingDate),
+            ("Verwendungszweck".into(), CsvColumn::Description),
+            ("Soll".into(), CsvColumn::DebitAmount),
+            ("Haben".into(), CsvColumn::CreditAmount),
+            ("Währung".into(), CsvColumn::Currency),
+        ],
+        header: true,
+        date_format: "%d.%m.%Y".into(),
+        decimal_separator: ',',
+        sanitize_formulas: true,
+    };
+    spreadsheet.write_to(statement.as_view(), &mut output)?;
+    writeln!(output)?;
+
+    let configured: CsvProfile = serde_json::from_str(PROFILE_JSON)
+        .map_err(|e| ParseError::JsonError(format!("Invalid CSV profile: {}", e)))?;
+    configured.write_to(statement.as_view(), &mut output)?;
+    Ok(())
+}
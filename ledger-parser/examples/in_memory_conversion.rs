@@ -0,0 +1,35 @@
+//! Convert between formats entirely in memory, without files or threads.
+//!
+//! This is the shape to expose from sandboxed hosts such as WebAssembly: the
+//! library only needs `Read` and `Write`, so a byte slice in and a `Vec<u8>`
+//! out are enough. The crate has no bindings of its own; wrap [`convert`] with
+//! the host's glue (for example `wasm-bindgen`) and build without the `mmap`
+//! feature.
+//!
+//! ```text
+//! cargo run -p ledger-parser --example in_memory_conversion
+//! ```
+
+use ledger_parser::{Camt053Statement, Mt940Statement, ParseError};
+
+const STATEMENT: &str = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C250101EUR100,00\n\
+                         :61:250102D25,00NTRFNONREF\n:86:Lunch\n:62F:C250102EUR75,00\n";
+
+/// MT940 bytes in, CAMT.053 XML bytes out
+fn convert(input: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let statement = Mt940Statement::from_read(&mut &input[..])?;
+    let mut output = Vec::new();
+    Camt053Statement::from(statement).write_to(&mut output)?;
+    Ok(output)
+}
+
+fn main() -> Result<(), ParseError> {
+    let xml = convert(STATEMENT.as_bytes())?;
+    println!("{}", String::from_utf8_lossy(&xml));
+
+    // Errors carry a stable code a host can hand back to its caller
+    if let Err(error) = convert(b"not a statement") {
+        eprintln!("{}: {}", error.code(), error);
+    }
+    Ok(())
+}
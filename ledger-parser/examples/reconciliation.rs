@@ -0,0 +1,74 @@
+//! Reconcile the statements of several accounts.
+//!
+//! Each statement is checked on its own (opening balance plus bookings must
+//! give the closing balance), then all of them are combined into a
+//! [`Portfolio`] that pairs the transfers between the accounts.
+//!
+//! ```text
+//! cargo run -p ledger-parser --example reconciliation -- checking.mt940 savings.mt940
+//! ```
+
+use ledger_parser::{Money, Mt940Statement, ParseError, Portfolio, StatementRef};
+use std::fs::File;
+
+/// Statements used when no files are given on the command line
+const SAMPLES: [&str; 2] = [
+    ":20:A\n:25:CHECKING\n:60F:C250301EUR500,00\n\
+     :61:250303D200,00NTRFNONREF\n:86:To savings\n\
+     :61:250304D50,00NTRFNONREF\n:86:Groceries\n:62F:C250304EUR250,00\n",
+    ":20:B\n:25:SAVINGS\n:60F:C250301EUR1000,00\n\
+     :61:250304C200,00NTRFNONREF\n:86:From checking\n:62F:C250304EUR1200,00\n",
+];
+
+fn main() -> Result<(), ParseError> {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    let statements = if paths.is_empty() {
+        SAMPLES
+            .iter()
+            .map(|data| Mt940Statement::from_read(&mut data.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        paths
+            .iter()
+            .map(|path| Mt940Statement::from_read(&mut File::open(path)?))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    for statement in &statements {
+        let view = statement.as_view();
+        let difference = unexplained_difference(view)?;
+        let status = if difference.amount == 0.0 {
+            "balanced".to_string()
+        } else {
+            format!("off by {}", difference)
+        };
+        println!("account {}: {}", view.account_number, status);
+    }
+
+    let portfolio = Portfolio::from_statements(statements.iter().map(Mt940Statement::as_view));
+    for transfer in &portfolio.transfers {
+        println!(
+            "transfer {} -> {}: {:.2} {} ({} to {})",
+            transfer.from_account,
+            transfer.to_account,
+            transfer.amount,
+            transfer.currency,
+            transfer.debit_date,
+            transfer.credit_date
+        );
+    }
+    for (currency, totals) in &portfolio.totals {
+        println!("net {} without transfers: {:.2}", currency, totals.net());
+    }
+    Ok(())
+}
+
+/// Closing balance minus the opening balance and all bookings; zero when the
+/// statement is complete
+fn unexplained_difference(view: StatementRef<'_>) -> Result<Money, ParseError> {
+    let mut expected = view.opening_money();
+    for booking in view.transaction_money() {
+        expected = expected.checked_add(&booking)?;
+    }
+    view.closing_money().checked_sub(&expected)
+}
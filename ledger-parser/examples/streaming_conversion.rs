@@ -0,0 +1,47 @@
+//! Convert an MT940 file to MT940 entry lines without loading it into memory.
+//!
+//! Transactions are pulled from the input one entry at a time and written
+//! through a bounded buffer, so memory use does not grow with the file size.
+//!
+//! ```text
+//! cargo run -p ledger-parser --features async --example streaming_conversion -- statement.mt940
+//! ```
+
+use futures_util::io::{AllowStdIo, BufReader};
+use ledger_parser::{
+    convert_stream, mt940_entry_encoder, mt940_transactions, Mt940Dialect, ParseError,
+    DEFAULT_STREAM_BUFFER,
+};
+use std::fs::File;
+use std::io;
+
+/// Input used when no file is given on the command line
+const SAMPLE: &str = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C250101EUR100,00\n\
+                      :61:250102D25,00NTRFNONREF\n:86:Lunch\n\
+                      :61:250103C40,00NTRFNONREF\n:86:Refund\n:62F:C250103EUR115,00\n";
+
+fn main() -> Result<(), ParseError> {
+    let mut output = AllowStdIo::new(io::stdout().lock());
+    let encoder = mt940_entry_encoder(Mt940Dialect::Standard);
+
+    let converted = match std::env::args().nth(1) {
+        Some(path) => {
+            let input = BufReader::new(AllowStdIo::new(File::open(path)?));
+            futures_executor::block_on(convert_stream(
+                mt940_transactions(input),
+                &mut output,
+                DEFAULT_STREAM_BUFFER,
+                encoder,
+            ))?
+        }
+        None => futures_executor::block_on(convert_stream(
+            mt940_transactions(SAMPLE.as_bytes()),
+            &mut output,
+            DEFAULT_STREAM_BUFFER,
+            encoder,
+        ))?,
+    };
+
+    eprintln!("converted {} transactions", converted);
+    Ok(())
+}
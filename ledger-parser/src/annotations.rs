@@ -0,0 +1,305 @@
+//! Sidecar store of user annotations on transactions.
+//!
+//! Statements are re-converted from their source files over and over, so notes
+//! and categories cannot live in the converted output. An [`AnnotationStore`]
+//! keeps them in a separate JSON file keyed by transaction fingerprint: a hash
+//! of the transaction's content that comes out the same every time the same
+//! source file is parsed, whatever the output format.
+
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::{self, BTreeMap};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::error::ParseError;
+use crate::model::{Transaction, TransactionType};
+
+/// Schema version written to, and the newest accepted from, annotation files
+pub const ANNOTATION_SCHEMA_VERSION: u32 = 1;
+
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+/// Separator between hashed fields, so adjacent fields cannot run into each other
+const FIELD_SEPARATOR: u8 = 0x1f;
+
+/// Note and category a user attached to a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Free-text note
+    pub note: Option<String>,
+    /// Category, e.g. an expense account or budget line
+    pub category: Option<String>,
+}
+
+impl Annotation {
+    /// Whether neither a note nor a category is set
+    pub fn is_empty(&self) -> bool {
+        self.note.is_none() && self.category.is_none()
+    }
+}
+
+/// Annotations keyed by transaction fingerprint, stored as a JSON sidecar file.
+///
+/// # File format (version 1)
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "annotations": {
+///     "4290dbcec6c55a51": { "note": "Team lunch", "category": "Meals" }
+///   }
+/// }
+/// ```
+///
+/// Keys are written in sorted order, so the file diffs cleanly under version
+/// control. Files with a newer `schema_version` are rejected.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{fingerprints, Annotation, AnnotationStore, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// let keys = fingerprints(&statement.transactions);
+///
+/// let mut store = AnnotationStore::new();
+/// store.set(&keys[0], Annotation { note: None, category: Some("Meals".into()) });
+///
+/// // The same source file yields the same fingerprints on the next run
+/// let again = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// let annotation = store.get(&fingerprints(&again.transactions)[0]).unwrap();
+/// assert_eq!(annotation.category.as_deref(), Some("Meals"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationStore {
+    annotations: BTreeMap<String, Annotation>,
+}
+
+/// Wire representation of an annotation file
+#[derive(Serialize, Deserialize)]
+struct AnnotationFile {
+    schema_version: u32,
+    annotations: BTreeMap<String, Annotation>,
+}
+
+impl AnnotationStore {
+    /// Empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a store from any Read source.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::JsonError` if the input is not an annotation file
+    /// or has a `schema_version` newer than [`ANNOTATION_SCHEMA_VERSION`].
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let file: AnnotationFile = serde_json::from_reader(reader)
+            .map_err(|e| ParseError::JsonError(format!("Invalid annotation file: {}", e)))?;
+        if file.schema_version > ANNOTATION_SCHEMA_VERSION {
+            return Err(ParseError::JsonError(format!(
+                "Unsupported annotation schema_version {} (newest supported: {})",
+                file.schema_version, ANNOTATION_SCHEMA_VERSION
+            )));
+        }
+        Ok(AnnotationStore {
+            annotations: file.annotations,
+        })
+    }
+
+    /// Read a store from a file path.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened, otherwise the
+    /// same errors as [`AnnotationStore::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        Self::from_read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Write the store as a pretty-printed JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        let file = AnnotationFile {
+            schema_version: ANNOTATION_SCHEMA_VERSION,
+            annotations: self.annotations.clone(),
+        };
+        let mut writer = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, &file).map_err(|e| {
+            if e.is_io() {
+                ParseError::from(std::io::Error::from(e))
+            } else {
+                ParseError::JsonError(format!("Failed to write annotations: {}", e))
+            }
+        })?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Annotation stored for `fingerprint`
+    pub fn get(&self, fingerprint: &str) -> Option<&Annotation> {
+        self.annotations.get(fingerprint)
+    }
+
+    /// Store `annotation` for `fingerprint`; an empty annotation removes the entry
+    pub fn set(&mut self, fingerprint: &str, annotation: Annotation) {
+        if annotation.is_empty() {
+            self.annotations.remove(fingerprint);
+        } else {
+            self.annotations.insert(fingerprint.into(), annotation);
+        }
+    }
+
+    /// Remove and return the annotation of `fingerprint`
+    pub fn remove(&mut self, fingerprint: &str) -> Option<Annotation> {
+        self.annotations.remove(fingerprint)
+    }
+
+    /// Number of annotated transactions
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    /// Whether no transaction is annotated
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    /// Fingerprints and annotations in fingerprint order
+    pub fn iter(&self) -> btree_map::Iter<'_, String, Annotation> {
+        self.annotations.iter()
+    }
+}
+
+impl Transaction {
+    /// Content hash identifying the transaction across re-conversions, as 16 hex digits.
+    ///
+    /// Covers booking date, amount in cents, direction, reference, description
+    /// and counterparty account, hashed with FNV-1a so the value never depends
+    /// on the platform or Rust version. Identical transactions share a
+    /// fingerprint; [`fingerprints`] tells them apart within a statement.
+    pub fn fingerprint(&self) -> String {
+        let cents = (self.amount * 100.0).round() as i64;
+        let direction = match self.transaction_type {
+            TransactionType::Credit => "C",
+            TransactionType::Debit => "D",
+        };
+        let fields = [
+            self.booking_date.format("%Y-%m-%d").to_string(),
+            cents.to_string(),
+            direction.to_string(),
+            self.reference.clone().unwrap_or_default(),
+            self.description
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            self.counterparty_account.clone().unwrap_or_default(),
+        ];
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for field in &fields {
+            for &byte in field.as_bytes().iter().chain(&[FIELD_SEPARATOR]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+}
+
+/// Fingerprints of a statement's transactions, in order.
+///
+/// The n-th repetition of an identical transaction gets `-n` appended (`-2`,
+/// `-3`, …), so duplicates within one statement are annotated separately.
+pub fn fingerprints(transactions: &[Transaction]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    transactions
+        .iter()
+        .map(|transaction| {
+            let fingerprint = transaction.fingerprint();
+            let count = seen.entry(fingerprint.clone()).or_default();
+            *count += 1;
+            if *count == 1 {
+                fingerprint
+            } else {
+                format!("{}-{}", fingerprint, count)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn transaction(description: &str) -> Transaction {
+        Transaction {
+            reference: Some("INV-7".into()),
+            ..Transaction::new(
+                text::parse_date("2025-03-07").unwrap(),
+                25.5,
+                TransactionType::Debit,
+                description,
+            )
+        }
+    }
+
+    #[test]
+    fn test_fingerprints_are_stable_and_distinguish_duplicates() {
+        // Fixed value: changing the hash would orphan every stored annotation
+        assert_eq!(transaction("Lunch").fingerprint(), "4290dbcec6c55a51");
+        assert_eq!(
+            transaction("Lunch").fingerprint(),
+            transaction("Lunch\n ").fingerprint()
+        );
+        assert_ne!(
+            transaction("Lunch").fingerprint(),
+            transaction("Dinner").fingerprint()
+        );
+
+        let keys = fingerprints(&[transaction("Lunch"), transaction("Lunch")]);
+        assert_eq!(keys[1], format!("{}-2", keys[0]));
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let mut store = AnnotationStore::new();
+        store.set(
+            "b",
+            Annotation {
+                note: Some("Team lunch".into()),
+                category: None,
+            },
+        );
+        store.set(
+            "a",
+            Annotation {
+                note: None,
+                category: Some("Meals".into()),
+            },
+        );
+        store.set("b", Annotation::default());
+
+        let mut output = Vec::new();
+        store.write_to(&mut output).unwrap();
+        let loaded = AnnotationStore::from_read(&mut output.as_slice()).unwrap();
+        assert_eq!(loaded, store);
+        assert_eq!(loaded.len(), 1);
+
+        let newer = String::from_utf8(output)
+            .unwrap()
+            .replace("\"schema_version\": 1", "\"schema_version\": 2");
+        assert!(AnnotationStore::from_read(&mut newer.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Cooperative cancellation of long-running reads and writes.
+//!
+//! A [`CancellationToken`] is a cheap, clonable flag shared between the code
+//! running a conversion and the code that may abort it (a request handler, a UI
+//! thread). Wrapping the input or output with [`CancellationToken::reader`] or
+//! [`CancellationToken::writer`] makes every parser and writer check the flag
+//! on each IO call, so a slow or endless input no longer blocks until EOF: the
+//! operation fails with [`ParseError::Cancelled`] at the next read or write.
+//! Loops outside the library can call [`CancellationToken::check`] themselves.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::ParseError;
+
+/// Shared flag requesting that an operation stops early.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{CancellationToken, Mt940Statement, ParseError};
+///
+/// let token = CancellationToken::new();
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n:62F:C200101EUR100,00\n";
+///
+/// token.cancel();
+/// let result = Mt940Statement::from_read(&mut token.reader(data.as_bytes()));
+/// assert!(matches!(result, Err(ParseError::Cancelled)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// New token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation; every clone of the token observes it
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(ParseError::Cancelled)` once cancellation was requested.
+    ///
+    /// # Errors
+    /// Returns `ParseError::Cancelled` if the token is cancelled.
+    pub fn check(&self) -> Result<(), ParseError> {
+        if self.is_cancelled() {
+            Err(ParseError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wrap a reader so reads fail once the token is cancelled
+    pub fn reader<R: Read>(&self, inner: R) -> Cancellable<R> {
+        Cancellable {
+            inner,
+            token: self.clone(),
+        }
+    }
+
+    /// Wrap a writer so writes fail once the token is cancelled
+    pub fn writer<W: Write>(&self, inner: W) -> Cancellable<W> {
+        Cancellable {
+            inner,
+            token: self.clone(),
+        }
+    }
+
+    /// IO error carrying the cancellation marker, if cancelled
+    fn check_io(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            Err(io::Error::other(Cancelled))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Reader or writer that checks a [`CancellationToken`] before every call.
+///
+/// A cancelled call fails with an IO error that the library's parsers and
+/// writers report as [`ParseError::Cancelled`].
+#[derive(Debug)]
+pub struct Cancellable<T> {
+    inner: T,
+    token: CancellationToken,
+}
+
+impl<T> Cancellable<T> {
+    /// Unwrap the inner reader or writer
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Cancellable<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.token.check_io()?;
+        self.inner.read(buf)
+    }
+}
+
+impl<W: Write> Write for Cancellable<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.token.check_io()?;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.token.check_io()?;
+        self.inner.flush()
+    }
+}
+
+/// Marker payload of IO errors raised by [`Cancellable`].
+///
+/// The kind is deliberately not `Interrupted`: `read_to_string` and friends
+/// retry on that kind and would spin forever on a cancelled reader.
+#[derive(Debug)]
+struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// Whether `error` was raised by a cancelled [`Cancellable`]
+pub(crate) fn is_cancellation(error: &io::Error) -> bool {
+    error.get_ref().is_some_and(|inner| inner.is::<Cancelled>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Camt053Statement, Mt940Statement};
+
+    #[test]
+    fn test_cancel_mid_read() {
+        let token = CancellationToken::new();
+        // An endless input would block `from_read` forever without cancellation
+        let endless = ":20:REF\n".as_bytes().chain(io::repeat(b'x'));
+        let mut reader = token.reader(endless);
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf).unwrap();
+        token.cancel();
+
+        assert!(matches!(
+            Mt940Statement::from_read(&mut reader),
+            Err(ParseError::Cancelled)
+        ));
+        assert!(token.check().is_err());
+    }
+
+    #[test]
+    fn test_cancelled_writer_reports_cancelled() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102C50,00NTRFNONREF\n:86:Invoice\n:62F:C200102EUR150,00\n";
+        let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let mut writer = token.writer(Vec::new());
+        assert!(matches!(
+            statement.write_to(&mut writer),
+            Err(ParseError::Cancelled)
+        ));
+        let camt053: Camt053Statement = statement.into();
+        assert!(matches!(
+            camt053.write_to(&mut writer),
+            Err(ParseError::Cancelled)
+        ));
+        assert!(writer.into_inner().is_empty());
+    }
+}
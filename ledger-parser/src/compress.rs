@@ -0,0 +1,201 @@
+//! Compressed output (requires the `compression` cargo feature).
+//!
+//! Nightly batch conversions usually ship their results compressed. Instead of
+//! piping the output through a separate tool, every writable statement gets
+//! `write_to_gz` and `write_to_zip`, and [`CompressedWriter`] wraps any sink
+//! for writers without such methods (export profiles, custom encoders).
+
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Cursor, Write};
+use strum_macros::{Display, EnumString};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::ParseError;
+use crate::{
+    Camt053Statement, Camt054Statement, CsvStatement, JsonStatement, Mt940Statement,
+    Mt942Statement, QifStatement,
+};
+
+/// Container format of compressed output. Parses from and displays as `gz` or `zip`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum Compression {
+    /// Single gzip stream (`.gz`)
+    #[strum(to_string = "gz", serialize = "gzip")]
+    Gzip,
+    /// Zip archive holding the output as one deflated entry (`.zip`)
+    #[strum(to_string = "zip")]
+    Zip,
+}
+
+/// Writer compressing everything written to it into `inner`.
+///
+/// Gzip output is streamed. A zip archive ends with a directory that points
+/// back into the file, so its entry is assembled in memory and written to
+/// `inner` by [`CompressedWriter::finish`]; the sink does not need to be
+/// seekable. Dropping the writer without `finish` leaves the output incomplete.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{CompressedWriter, Compression};
+/// use std::io::Write;
+///
+/// let mut writer = CompressedWriter::new(Vec::new(), Compression::Gzip, "statement.sta").unwrap();
+/// writer.write_all(b":20:REF\n").unwrap();
+/// let output = writer.finish().unwrap();
+/// assert_eq!(output[..2], [0x1f, 0x8b]);
+/// ```
+pub struct CompressedWriter<W: Write> {
+    encoder: Encoder<W>,
+}
+
+/// Encoder state per container format
+enum Encoder<W: Write> {
+    Gzip(GzEncoder<W>),
+    Zip {
+        archive: Box<ZipWriter<Cursor<Vec<u8>>>>,
+        inner: W,
+    },
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Start compressed output into `inner`; `entry_name` names the file inside a zip archive
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if the archive entry cannot be started.
+    pub fn new(inner: W, compression: Compression, entry_name: &str) -> Result<Self, ParseError> {
+        let encoder = match compression {
+            Compression::Gzip => {
+                Encoder::Gzip(GzEncoder::new(inner, flate2::Compression::default()))
+            }
+            Compression::Zip => {
+                let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+                archive
+                    .start_file(entry_name, SimpleFileOptions::default())
+                    .map_err(io::Error::from)?;
+                Encoder::Zip {
+                    archive: Box::new(archive),
+                    inner,
+                }
+            }
+        };
+        Ok(CompressedWriter { encoder })
+    }
+
+    /// Complete the compressed stream and return the inner writer
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if writing the trailer or the archive fails.
+    pub fn finish(self) -> Result<W, ParseError> {
+        match self.encoder {
+            Encoder::Gzip(encoder) => Ok(encoder.finish()?),
+            Encoder::Zip { archive, mut inner } => {
+                let archive = archive.finish().map_err(io::Error::from)?;
+                inner.write_all(archive.get_ref())?;
+                inner.flush()?;
+                Ok(inner)
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.encoder {
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Zip { archive, .. } => archive.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Zip { archive, .. } => archive.flush(),
+        }
+    }
+}
+
+macro_rules! impl_compressed_writes {
+    ($statement:ty) => {
+        impl $statement {
+            /// Write the statement like `write_to`, gzip-compressed.
+            ///
+            /// # Errors
+            /// Same as `write_to`.
+            pub fn write_to_gz<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+                let mut compressed = CompressedWriter::new(writer, Compression::Gzip, "")?;
+                self.write_to(&mut compressed)?;
+                compressed.finish()?;
+                Ok(())
+            }
+
+            /// Write the statement like `write_to` as the single entry `entry_name` of a zip archive.
+            ///
+            /// # Errors
+            /// Same as `write_to`.
+            pub fn write_to_zip<W: Write>(
+                &self,
+                writer: &mut W,
+                entry_name: &str,
+            ) -> Result<(), ParseError> {
+                let mut compressed = CompressedWriter::new(writer, Compression::Zip, entry_name)?;
+                self.write_to(&mut compressed)?;
+                compressed.finish()?;
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_compressed_writes!(CsvStatement);
+impl_compressed_writes!(Mt940Statement);
+impl_compressed_writes!(Mt942Statement);
+impl_compressed_writes!(Camt053Statement);
+impl_compressed_writes!(Camt054Statement);
+impl_compressed_writes!(JsonStatement);
+impl_compressed_writes!(QifStatement);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use std::str::FromStr;
+
+    const DATA: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                        :61:200102C50,00NTRFNONREF\n:86:Invoice\n:62F:C200102EUR150,00\n";
+
+    #[test]
+    fn test_write_to_gz_round_trip() {
+        let statement = Mt940Statement::from_read(&mut DATA.as_bytes()).unwrap();
+        let mut plain = Vec::new();
+        statement.write_to(&mut plain).unwrap();
+        let mut compressed = Vec::new();
+        statement.write_to_gz(&mut compressed).unwrap();
+
+        let mut decompressed = Vec::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, plain);
+    }
+
+    #[test]
+    fn test_write_to_zip_single_entry() {
+        let statement = Mt940Statement::from_read(&mut DATA.as_bytes()).unwrap();
+        let mut archive = Vec::new();
+        statement
+            .write_to_zip(&mut archive, "statement.sta")
+            .unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut entry = archive.by_name("statement.sta").unwrap();
+        let mut content = String::new();
+        entry.read_to_string(&mut content).unwrap();
+        assert!(content.contains(":61:200102C50,00"));
+        assert_eq!(Compression::from_str("GZ").unwrap(), Compression::Gzip);
+    }
+}
@@ -0,0 +1,351 @@
+//! ISO 4217 currency codes.
+//!
+//! Statements keep their currencies as plain strings, in whatever case and
+//! spelling the bank used. [`Currency`] checks a code against the ISO 4217
+//! list, normalises it to the three upper-case letters and maps it to and
+//! from its numeric code, which Russian account numbers and some exports use
+//! instead of the letters.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::ParseError;
+
+/// Active ISO 4217 codes with their numeric codes, plus the no-currency `XXX`
+const ISO_4217: &[(&str, u16)] = &[
+    ("AED", 784),
+    ("AFN", 971),
+    ("ALL", 8),
+    ("AMD", 51),
+    ("ANG", 532),
+    ("AOA", 973),
+    ("ARS", 32),
+    ("AUD", 36),
+    ("AWG", 533),
+    ("AZN", 944),
+    ("BAM", 977),
+    ("BBD", 52),
+    ("BDT", 50),
+    ("BGN", 975),
+    ("BHD", 48),
+    ("BIF", 108),
+    ("BMD", 60),
+    ("BND", 96),
+    ("BOB", 68),
+    ("BRL", 986),
+    ("BSD", 44),
+    ("BTN", 64),
+    ("BWP", 72),
+    ("BYN", 933),
+    ("BZD", 84),
+    ("CAD", 124),
+    ("CDF", 976),
+    ("CHF", 756),
+    ("CLP", 152),
+    ("CNY", 156),
+    ("COP", 170),
+    ("CRC", 188),
+    ("CUP", 192),
+    ("CVE", 132),
+    ("CZK", 203),
+    ("DJF", 262),
+    ("DKK", 208),
+    ("DOP", 214),
+    ("DZD", 12),
+    ("EGP", 818),
+    ("ERN", 232),
+    ("ETB", 230),
+    ("EUR", 978),
+    ("FJD", 242),
+    ("FKP", 238),
+    ("GBP", 826),
+    ("GEL", 981),
+    ("GHS", 936),
+    ("GIP", 292),
+    ("GMD", 270),
+    ("GNF", 324),
+    ("GTQ", 320),
+    ("GYD", 328),
+    ("HKD", 344),
+    ("HNL", 340),
+    ("HTG", 332),
+    ("HUF", 348),
+    ("IDR", 360),
+    ("ILS", 376),
+    ("INR", 356),
+    ("IQD", 368),
+    ("IRR", 364),
+    ("ISK", 352),
+    ("JMD", 388),
+    ("JOD", 400),
+    ("JPY", 392),
+    ("KES", 404),
+    ("KGS", 417),
+    ("KHR", 116),
+    ("KMF", 174),
+    ("KPW", 408),
+    ("KRW", 410),
+    ("KWD", 414),
+    ("KYD", 136),
+    ("KZT", 398),
+    ("LAK", 418),
+    ("LBP", 422),
+    ("LKR", 144),
+    ("LRD", 430),
+    ("LSL", 426),
+    ("LYD", 434),
+    ("MAD", 504),
+    ("MDL", 498),
+    ("MGA", 969),
+    ("MKD", 807),
+    ("MMK", 104),
+    ("MNT", 496),
+    ("MOP", 446),
+    ("MRU", 929),
+    ("MUR", 480),
+    ("MVR", 462),
+    ("MWK", 454),
+    ("MXN", 484),
+    ("MYR", 458),
+    ("MZN", 943),
+    ("NAD", 516),
+    ("NGN", 566),
+    ("NIO", 558),
+    ("NOK", 578),
+    ("NPR", 524),
+    ("NZD", 554),
+    ("OMR", 512),
+    ("PAB", 590),
+    ("PEN", 604),
+    ("PGK", 598),
+    ("PHP", 608),
+    ("PKR", 586),
+    ("PLN", 985),
+    ("PYG", 600),
+    ("QAR", 634),
+    ("RON", 946),
+    ("RSD", 941),
+    ("RUB", 643),
+    ("RWF", 646),
+    ("SAR", 682),
+    ("SBD", 90),
+    ("SCR", 690),
+    ("SDG", 938),
+    ("SEK", 752),
+    ("SGD", 702),
+    ("SHP", 654),
+    ("SLE", 925),
+    ("SOS", 706),
+    ("SRD", 968),
+    ("SSP", 728),
+    ("STN", 930),
+    ("SVC", 222),
+    ("SYP", 760),
+    ("SZL", 748),
+    ("THB", 764),
+    ("TJS", 972),
+    ("TMT", 934),
+    ("TND", 788),
+    ("TOP", 776),
+    ("TRY", 949),
+    ("TTD", 780),
+    ("TWD", 901),
+    ("TZS", 834),
+    ("UAH", 980),
+    ("UGX", 800),
+    ("USD", 840),
+    ("UYU", 858),
+    ("UZS", 860),
+    ("VES", 928),
+    ("VND", 704),
+    ("VUV", 548),
+    ("WST", 882),
+    ("XAF", 950),
+    ("XAG", 961),
+    ("XAU", 959),
+    ("XCD", 951),
+    ("XDR", 960),
+    ("XOF", 952),
+    ("XPF", 953),
+    ("XXX", 999),
+    ("YER", 886),
+    ("ZAR", 710),
+    ("ZMW", 967),
+    ("ZWG", 924),
+];
+
+/// Withdrawn codes still found in bank files, with the code replacing them.
+///
+/// Russian banks kept the pre-1998 rouble code `RUR` (numeric 810) in account
+/// numbers and exports.
+const LEGACY_CODES: &[(&str, u16, &str)] = &[("RUR", 810, "RUB")];
+
+/// What a currency code must look like, for error messages
+const EXPECTED_CODE: &str = "an ISO 4217 currency code";
+
+/// A known ISO 4217 currency.
+///
+/// Parses from the three letters in any case, or from the three-digit numeric
+/// code; withdrawn codes banks still use (the rouble's `RUR`/`810`) parse as
+/// the code replacing them. Displays and serializes as the upper-case letters.
+///
+/// # Example
+/// ```
+/// use ledger_parser::Currency;
+///
+/// let euro: Currency = " eur ".parse().unwrap();
+/// assert_eq!(euro.code(), "EUR");
+/// assert_eq!(euro.numeric(), 978);
+/// assert_eq!(Currency::from_numeric(810).unwrap().code(), "RUB");
+/// assert!("EURO".parse::<Currency>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency {
+    code: &'static str,
+    numeric: u16,
+}
+
+impl Currency {
+    /// Currency of a letter or numeric code, ignoring case and surrounding
+    /// whitespace.
+    ///
+    /// # Errors
+    /// Returns `ParseError::UnexpectedValue` if `code` is no known ISO 4217 code.
+    pub fn parse(code: &str) -> Result<Self, ParseError> {
+        let trimmed = code.trim();
+        let currency = match trimmed.parse::<u16>() {
+            Ok(numeric) if trimmed.len() == 3 => Self::from_numeric(numeric),
+            Ok(_) => None,
+            Err(_) => Self::from_code(trimmed),
+        };
+        currency.ok_or_else(|| ParseError::UnexpectedValue {
+            field: "currency".into(),
+            expected: EXPECTED_CODE.into(),
+            found: code.into(),
+        })
+    }
+
+    /// Currency of the numeric ISO 4217 code, e.g. 978 for the euro
+    pub fn from_numeric(numeric: u16) -> Option<Self> {
+        ISO_4217
+            .iter()
+            .find(|(_, known)| *known == numeric)
+            .map(|(code, _)| *code)
+            .or_else(|| {
+                LEGACY_CODES
+                    .iter()
+                    .find(|(_, legacy, _)| *legacy == numeric)
+                    .map(|(_, _, code)| *code)
+            })
+            .and_then(Self::from_code)
+    }
+
+    /// Upper-case letter code, e.g. `EUR`
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Numeric ISO 4217 code, e.g. 978 for the euro
+    pub fn numeric(&self) -> u16 {
+        self.numeric
+    }
+
+    /// `code` as its ISO 4217 letters when it is a known code, otherwise
+    /// trimmed and upper-cased, so spellings of the same code compare equal
+    pub fn normalize(code: &str) -> String {
+        match Self::parse(code) {
+            Ok(currency) => currency.code.into(),
+            Err(_) => code.trim().to_ascii_uppercase(),
+        }
+    }
+
+    /// Currency of a letter code in any case
+    fn from_code(code: &str) -> Option<Self> {
+        let code = LEGACY_CODES
+            .iter()
+            .find(|(legacy, _, _)| legacy.eq_ignore_ascii_case(code))
+            .map_or(code, |(_, _, current)| current);
+        ISO_4217
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(code))
+            .map(|&(code, numeric)| Currency { code, numeric })
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code)
+    }
+}
+
+impl AsRef<str> for Currency {
+    fn as_ref(&self) -> &str {
+        self.code
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ParseError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::parse(code)
+    }
+}
+
+impl TryFrom<&str> for Currency {
+    type Error = ParseError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        Self::parse(code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.code.into()
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Self::parse(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_letters_numbers_and_legacy_codes() {
+        assert_eq!(Currency::parse("usd").unwrap().numeric(), 840);
+        assert_eq!(Currency::parse("008").unwrap().code(), "ALL");
+        assert_eq!(
+            Currency::parse("RUR").unwrap(),
+            Currency::parse("643").unwrap()
+        );
+        assert!(Currency::parse("8").is_err());
+        assert!(Currency::parse("ABC").is_err());
+        assert!(Currency::parse("").is_err());
+        assert_eq!(Currency::normalize("rur"), "RUB");
+        assert_eq!(Currency::normalize(" abc "), "ABC");
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&Currency::parse("chf").unwrap()).unwrap();
+        assert_eq!(json, "\"CHF\"");
+        let currency: Currency = serde_json::from_str(&json).unwrap();
+        assert_eq!(currency.code(), "CHF");
+        assert!(serde_json::from_str::<Currency>("\"CHX\"").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Plain-text dumps of statements for bug reports.
+//!
+//! When a conversion loses or changes something, the quickest way to show it
+//! is the statement before and after, side by side. [`explain`] renders the
+//! fields every format carries in one normalized layout, so the dumps of an
+//! MT940 file and of the CAMT.053 converted from it diff line by line:
+//!
+//! - transactions are sorted by booking date, direction, amount and text, so
+//!   formats that order entries differently still line up;
+//! - dates are written without time, amounts with two decimals and text with
+//!   runs of whitespace collapsed (MT940 wraps `:86:` narratives);
+//! - empty and absent fields are left out, and booked, non-reversal entries
+//!   carry no status line;
+//! - account numbers go through [`MaskedAccount`], so dumps can be pasted
+//!   into public issues as long as masking is on (the default).
+//!
+//! # Example
+//! ```
+//! use ledger_parser::{debug, Camt053Statement, Mt940Statement};
+//!
+//! let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+//!             :61:200102D25,00NTRFNONREF\n:86:Lunch at\n the office\n:62F:C200102EUR75,00\n";
+//! let mt940 = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+//! let dump = debug::explain(mt940.as_view());
+//! assert!(dump.contains("[1] 2020-01-02 D 25.00 EUR\n    description: Lunch at the office\n"));
+//!
+//! let camt053 = Camt053Statement::from(mt940.clone());
+//! assert_eq!(debug::explain(camt053.as_view()), dump);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::model::{BalanceType, EntryStatus, Transaction, TransactionType};
+use crate::redact::MaskedAccount;
+use crate::StatementRef;
+
+/// Layout of every date in a dump
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Indentation of the field lines below a transaction line
+const FIELD_INDENT: &str = "    ";
+
+/// Normalized, ordered text dump of `statement`, stable across formats.
+///
+/// The first lines hold the account, currency and balances, followed by one
+/// block per transaction: a `[n] date C|D amount currency` line and an
+/// indented `name: value` line per field present.
+pub fn explain(statement: StatementRef<'_>) -> String {
+    let mut dump = String::new();
+    // Writing to a String cannot fail
+    let _ = write_dump(&mut dump, statement);
+    dump
+}
+
+fn write_dump(dump: &mut String, statement: StatementRef<'_>) -> std::fmt::Result {
+    writeln!(
+        dump,
+        "account: {}",
+        MaskedAccount::new(statement.account_number)
+    )?;
+    writeln!(dump, "currency: {}", statement.currency)?;
+    writeln!(
+        dump,
+        "opening: {} {} {:.2}",
+        date(&statement.opening_date),
+        balance_sign(statement.opening_indicator),
+        statement.opening_balance
+    )?;
+    writeln!(
+        dump,
+        "closing: {} {} {:.2}",
+        date(&statement.closing_date),
+        balance_sign(statement.closing_indicator),
+        statement.closing_balance
+    )?;
+    writeln!(dump, "transactions: {}", statement.len())?;
+
+    let mut transactions: Vec<&Transaction> = statement.transactions.iter().collect();
+    transactions.sort_by(|a, b| compare(a, b));
+    for (index, transaction) in transactions.into_iter().enumerate() {
+        writeln!(
+            dump,
+            "[{}] {} {} {:.2} {}",
+            index + 1,
+            date(&transaction.booking_date),
+            transaction_sign(&transaction.transaction_type),
+            transaction.amount,
+            transaction
+                .currency
+                .as_deref()
+                .unwrap_or(statement.currency)
+        )?;
+        write_fields(dump, transaction)?;
+    }
+    Ok(())
+}
+
+/// Field lines of one transaction, in a fixed order
+fn write_fields(dump: &mut String, transaction: &Transaction) -> std::fmt::Result {
+    let mut field = |name: &str, value: Option<String>| match value {
+        Some(value) if !value.is_empty() => writeln!(dump, "{}{}: {}", FIELD_INDENT, name, value),
+        _ => Ok(()),
+    };
+    field(
+        "value date",
+        transaction
+            .value_date
+            .map(|value_date| value_date.format(DATE_FORMAT).to_string()),
+    )?;
+    field("description", Some(normalize(&transaction.description)))?;
+    field("reference", transaction.reference.as_deref().map(normalize))?;
+    field(
+        "end-to-end id",
+        transaction.end_to_end_id.as_deref().map(normalize),
+    )?;
+    field(
+        "mandate id",
+        transaction.mandate_id.as_deref().map(normalize),
+    )?;
+    field(
+        "counterparty",
+        transaction.counterparty_name.as_deref().map(normalize),
+    )?;
+    field(
+        "counterparty account",
+        transaction
+            .counterparty_account
+            .as_deref()
+            .map(|account| MaskedAccount::new(account).to_string()),
+    )?;
+    field("counterparty bic", transaction.counterparty_bic.clone())?;
+    field(
+        "original amount",
+        transaction.original_amount.map(|amount| {
+            let currency = transaction.original_currency.as_deref().unwrap_or_default();
+            format!("{:.2} {}", amount, currency).trim_end().to_string()
+        }),
+    )?;
+    field("status", status(transaction))?;
+    Ok(())
+}
+
+/// Order of transactions in a dump: booking date, credits before debits,
+/// amount, then the text fields
+fn compare(a: &Transaction, b: &Transaction) -> Ordering {
+    date(&a.booking_date)
+        .cmp(&date(&b.booking_date))
+        .then_with(|| {
+            transaction_sign(&a.transaction_type).cmp(transaction_sign(&b.transaction_type))
+        })
+        .then_with(|| a.amount.total_cmp(&b.amount))
+        .then_with(|| normalize(&a.description).cmp(&normalize(&b.description)))
+        .then_with(|| a.reference.cmp(&b.reference))
+}
+
+fn date(date: &DateTime<FixedOffset>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+fn balance_sign(indicator: &BalanceType) -> &'static str {
+    match indicator {
+        BalanceType::Credit => "C",
+        BalanceType::Debit => "D",
+    }
+}
+
+fn transaction_sign(transaction_type: &TransactionType) -> &'static str {
+    match transaction_type {
+        TransactionType::Credit => "C",
+        TransactionType::Debit => "D",
+    }
+}
+
+/// Entry status when it is other than booked, with the reversal flag
+fn status(transaction: &Transaction) -> Option<String> {
+    let status = match transaction.status {
+        EntryStatus::Booked => None,
+        EntryStatus::Pending => Some("pending"),
+        EntryStatus::Info => Some("info"),
+    };
+    match (status, transaction.is_reversal) {
+        (None, false) => None,
+        (None, true) => Some("reversal".into()),
+        (Some(status), false) => Some(status.into()),
+        (Some(status), true) => Some(format!("{}, reversal", status)),
+    }
+}
+
+/// Text with runs of whitespace collapsed to one space
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redact::MASKING_LOCK;
+    use crate::Mt940Statement;
+
+    #[test]
+    fn test_dump_is_sorted_and_masked() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200103D5,00NTRFNONREF\n:86:Coffee\n\
+                    :61:2001020102C25,00NTRFINV-17\n:86:Invoice  17\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200103EUR95,00\n";
+        let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        statement.transactions[1].counterparty_account = Some("DE89370400440532013000".into());
+        statement.transactions[1].status = EntryStatus::Pending;
+
+        let _guard = MASKING_LOCK.lock().unwrap();
+        let dump = explain(statement.as_view());
+        assert_eq!(
+            dump,
+            "account: **************9999\n\
+             currency: EUR\n\
+             opening: 2020-01-01 C 100.00\n\
+             closing: 2020-01-03 C 95.00\n\
+             transactions: 3\n\
+             [1] 2020-01-02 C 25.00 EUR\n    \
+             description: Invoice 17\n    \
+             reference: INV-17\n    \
+             counterparty account: ******************3000\n    \
+             status: pending\n\
+             [2] 2020-01-02 D 25.00 EUR\n    \
+             description: Lunch\n\
+             [3] 2020-01-03 D 5.00 EUR\n    \
+             description: Coffee\n"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Input format sniffing.
+//!
+//! Scripts converting whatever a bank sends should not have to branch on the
+//! file type. [`detect_format`] guesses the format from the first bytes of the
+//! content, and [`Statement::from_read`](crate::Statement::from_read) reads
+//! the format name `auto` with it.
+
+/// Number of leading bytes inspected when guessing the format
+const SNIFF_LENGTH: usize = 4096;
+
+/// Signature of the zip container of XLSX workbooks
+#[cfg(feature = "xlsx")]
+const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+/// Guess the statement format of `content`, returning the format name
+/// [`Statement::from_read`](crate::Statement::from_read) takes.
+///
+/// Detection is purely structural: a leading `!Type:`/`!Account` header means
+/// QIF, an XML prolog or `<Document` means CAMT.053 (CAMT.052 for a
+/// `BkToCstmrAcctRpt` report, CAMT.054 for a notification), a JSON object
+/// with `schema_version` means JSON, a leading `01,` file header means BAI2,
+/// SWIFT blocks or leading MT940 tags mean MT940 (MT942 for an interim
+/// report), a zip container means XLSX (with the `xlsx` feature) and
+/// delimiter-separated lines mean CSV. Returns `None` when nothing matches.
+///
+/// # Example
+/// ```
+/// use ledger_parser::detect_format;
+///
+/// assert_eq!(detect_format(b":20:REF\n:25:NL81ASNB9999999999\n"), Some("mt940"));
+/// assert_eq!(detect_format(b"<?xml version=\"1.0\"?><Document>"), Some("camt053"));
+/// assert_eq!(detect_format(b"plain text"), None);
+/// ```
+pub fn detect_format(content: &[u8]) -> Option<&'static str> {
+    #[cfg(feature = "xlsx")]
+    if content.starts_with(ZIP_SIGNATURE) {
+        return Some("xlsx");
+    }
+
+    // CSV exports need not be UTF-8; the markers looked for are ASCII
+    let head = String::from_utf8_lossy(&content[..content.len().min(SNIFF_LENGTH)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+
+    if head.starts_with("!Type:") || head.starts_with("!Account") || head.starts_with("!Option:") {
+        return Some("qif");
+    }
+
+    if head.starts_with('<') || head.contains("<Document") {
+        if head.contains("BkToCstmrAcctRpt") {
+            return Some("camt052");
+        }
+        if head.contains("BkToCstmrDbtCdtNtfctn") {
+            return Some("camt054");
+        }
+        return Some("camt053");
+    }
+
+    // Canonical JSON statements are objects carrying a schema version
+    if head.starts_with('{') && head.contains("\"schema_version\"") {
+        return Some("json");
+    }
+
+    // BAI2 files open with a `01` file header record
+    if head.starts_with("01,") {
+        return Some("bai2");
+    }
+
+    // MT942 shares the MT940 block/tag layout; its `:13D:` and `:34F:` tags are unique to it
+    let mt942_tag = head.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with(":13D:") || line.starts_with(":34F:")
+    });
+    if head.contains("{2:I942") || head.contains("{2:O942") || mt942_tag {
+        return Some("mt942");
+    }
+
+    let mt940_tag = head.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with(":20:") || line.starts_with(":25:") || line.starts_with(":60F:")
+    });
+    if head.starts_with("{1:") || head.contains("{4:") || mt940_tag {
+        return Some("mt940");
+    }
+
+    if head
+        .lines()
+        .any(|line| line.contains(',') || line.contains(';'))
+    {
+        return Some("csv");
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            detect_format("\u{feff}<?xml version=\"1.0\"?><Document>".as_bytes()),
+            Some("camt053")
+        );
+        assert_eq!(
+            detect_format(b"{1:F01BANK}{2:I940}{4:\n:20:REF"),
+            Some("mt940")
+        );
+        assert_eq!(detect_format(b":20:REF\n:25:ACC\n"), Some("mt940"));
+        assert_eq!(
+            detect_format(b"{1:F01BANK}{2:O942}{4:\n:20:REF"),
+            Some("mt942")
+        );
+        assert_eq!(detect_format(b":20:REF\n:34F:EUR0,\n"), Some("mt942"));
+        assert_eq!(
+            detect_format(b"<Document><BkToCstmrAcctRpt><GrpHdr>"),
+            Some("camt052")
+        );
+        assert_eq!(
+            detect_format(b"<Document><BkToCstmrDbtCdtNtfctn><GrpHdr>"),
+            Some("camt054")
+        );
+        assert_eq!(
+            detect_format(b"{\n  \"schema_version\": 1,\n"),
+            Some("json")
+        );
+        assert_eq!(
+            detect_format(b"01,BANK,ACME,250314,0800,1,,,2/\n"),
+            Some("bai2")
+        );
+        assert_eq!(detect_format(b"!Type:Bank\nD01/05/2025\n"), Some("qif"));
+        assert_eq!(
+            detect_format(",СберБизнес\n,ПАО СБЕРБАНК\n".as_bytes()),
+            Some("csv")
+        );
+        #[cfg(feature = "xlsx")]
+        assert_eq!(detect_format(b"PK\x03\x04\x14\x00"), Some("xlsx"));
+        assert_eq!(detect_format(b"plain text"), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Warnings about input the parsers skipped.
+//!
+//! Bank files contain the odd malformed transaction row. Parsing in lenient
+//! mode (the default, [`ParseOptions::strict`](crate::ParseOptions) off) skips
+//! such records and reports each one as a [`ParseWarning`] from the
+//! `from_read_with_options` parsers; strict mode fails on the first one with
+//! the error that would have become the warning. The `from_read_with_stats`
+//! parsers also count the records read, as [`ParseStats`], to tell whether a
+//! statement was ingested in full. A value the parser had to assume, such as
+//! the currency of a CSV export without one, is reported the same way.
+
+use std::fmt;
+
+use crate::error::{ErrorLocation, ParseError};
+
+/// A record skipped while parsing leniently, and why.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, ParseOptions};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200132C5,00NTRFNONREF\n:62F:C200102EUR100,00\n";
+/// let (statement, warnings) =
+///     Mt940Statement::from_read_with_options(&mut data.as_bytes(), &ParseOptions::default())
+///         .unwrap();
+/// assert!(statement.transactions.is_empty());
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(
+///     warnings[0].to_string(),
+///     "line 4, field 61: Invalid date: expected a YYMMDD date, found '200132'"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Where the skipped record is, when known
+    pub location: Option<ErrorLocation>,
+    /// Code of the error that caused the skip, as [`ParseError::code`] reports it
+    pub code: &'static str,
+    /// What was wrong with the record
+    pub message: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{}: {}", location, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl From<ParseError> for ParseWarning {
+    fn from(error: ParseError) -> Self {
+        ParseWarning {
+            location: error.location().cloned(),
+            code: error.code(),
+            message: error.without_location().to_string(),
+        }
+    }
+}
+
+/// How much of its input a lenient parse ingested.
+///
+/// Records are the transaction rows of CSV and XLSX exports, the `:61:`
+/// entries and `:64:`/`:65:` available balances of MT940 and the `<Ntry>`
+/// entries of CAMT.053. Every record seen was either parsed, skipped (with the
+/// reason in [`rows_skipped`](ParseStats::rows_skipped)) or left out on
+/// purpose by a bank quirk, such as the service rows of Sberbank exports.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, ParseOptions};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200101C5,00NTRFNONREF\n:61:200132C5,00NTRFNONREF\n\
+///             :62F:C200102EUR105,00\n";
+/// let (_, stats) =
+///     Mt940Statement::from_read_with_stats(&mut data.as_bytes(), &ParseOptions::default())
+///         .unwrap();
+/// assert_eq!((stats.rows_seen, stats.rows_parsed), (2, 1));
+/// assert_eq!(stats.rows_skipped[0].code, "unexpected_value");
+/// assert!(!stats.is_complete());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Records the parser came across
+    pub rows_seen: usize,
+    /// Records that made it into the statement
+    pub rows_parsed: usize,
+    /// Records skipped as malformed, with the reason for each, in input order
+    pub rows_skipped: Vec<ParseWarning>,
+    /// Values missing from the input that the parser assumed, such as the
+    /// currency of a CSV export; no record was skipped for them
+    pub assumptions: Vec<ParseWarning>,
+}
+
+impl ParseStats {
+    /// Whether every record was ingested, i.e. none was skipped as malformed
+    pub fn is_complete(&self) -> bool {
+        self.rows_skipped.is_empty()
+    }
+
+    /// Records a bank quirk left out on purpose
+    pub fn rows_dropped(&self) -> usize {
+        self.rows_seen - self.rows_parsed - self.rows_skipped.len()
+    }
+
+    /// Every warning: the assumptions, then the skipped records
+    pub fn into_warnings(self) -> Vec<ParseWarning> {
+        let mut warnings = self.assumptions;
+        warnings.extend(self.rows_skipped);
+        warnings
+    }
+}
+
+/// Collects the records a parser skips, or turns the first into an error in
+/// strict mode, and counts the records it reads
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics {
+    strict: bool,
+    stats: ParseStats,
+}
+
+impl Diagnostics {
+    /// Collector failing on skipped records when `strict` is set
+    pub(crate) fn new(strict: bool) -> Self {
+        Diagnostics {
+            strict,
+            stats: ParseStats::default(),
+        }
+    }
+
+    /// Count `count` records that made it into the statement
+    pub(crate) fn parsed(&mut self, count: usize) {
+        self.stats.rows_seen += count;
+        self.stats.rows_parsed += count;
+    }
+
+    /// Count a record a bank quirk leaves out on purpose
+    #[cfg_attr(not(feature = "sberbank"), allow(dead_code))]
+    pub(crate) fn dropped(&mut self) {
+        self.stats.rows_seen += 1;
+    }
+
+    /// Skip the record `error` describes, or fail with it in strict mode
+    pub(crate) fn skip(&mut self, error: ParseError) -> Result<(), ParseError> {
+        if self.strict {
+            return Err(error);
+        }
+        self.stats.rows_seen += 1;
+        self.stats.rows_skipped.push(error.into());
+        Ok(())
+    }
+
+    /// Assume a value the input lacks, with the warning `error` describes, or
+    /// fail with it in strict mode
+    #[cfg_attr(not(feature = "sberbank"), allow(dead_code))]
+    pub(crate) fn assume(&mut self, error: ParseError) -> Result<(), ParseError> {
+        if self.strict {
+            return Err(error);
+        }
+        self.stats.assumptions.push(error.into());
+        Ok(())
+    }
+
+    /// The warnings of the records skipped so far, in input order
+    pub(crate) fn warnings(&self) -> &[ParseWarning] {
+        &self.stats.rows_skipped
+    }
+
+    /// The warnings of every assumption and skipped record
+    pub(crate) fn into_warnings(self) -> Vec<ParseWarning> {
+        self.stats.into_warnings()
+    }
+
+    /// Counts of every record read
+    pub(crate) fn into_stats(self) -> ParseStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lenient_collects_and_strict_fails() {
+        let error = || ParseError::Mt940Error("Invalid amount".into()).at_line(7, Some("61"));
+
+        let mut lenient = Diagnostics::default();
+        lenient.parsed(3);
+        lenient.dropped();
+        lenient.skip(error()).unwrap();
+        let stats = lenient.into_stats();
+        assert_eq!(
+            (stats.rows_seen, stats.rows_parsed, stats.rows_dropped()),
+            (5, 3, 1)
+        );
+        let warnings = stats.rows_skipped;
+        assert_eq!(warnings[0].code, "mt940_error");
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 7, field 61: MT940 error: Invalid amount"
+        );
+
+        let mut strict = Diagnostics::new(true);
+        assert!(matches!(
+            strict.skip(error()),
+            Err(ParseError::Located { .. })
+        ));
+    }
+}
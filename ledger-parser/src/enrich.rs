@@ -0,0 +1,211 @@
+//! Enrichment of transactions from outside sources.
+//!
+//! Bank data often names a counterparty only by account, or by a truncated
+//! name. An [`Enricher`] fills such gaps per transaction, e.g. by looking the
+//! counterparty IBAN up in a company register or a local database, so
+//! converted statements carry the names accounting expects.
+//!
+//! [`CounterpartyDirectory`] is the bundled enricher, reading account to name
+//! mappings from a CSV file; any `Fn(&mut Transaction) -> Result<(), ParseError>`
+//! closure is an enricher as well.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::error::ParseError;
+use crate::model::Transaction;
+
+/// Header titles of the account column of a directory file
+const ACCOUNT_COLUMNS: [&str; 2] = ["account", "iban"];
+/// Header title of the name column of a directory file
+const NAME_COLUMN: &str = "name";
+
+/// Adds information from an outside source to transactions.
+///
+/// Enrichers are shared between threads, so stateful ones keep their state
+/// behind atomics or locks.
+pub trait Enricher: Send + Sync {
+    /// Add what the source knows about `transaction`; transactions it knows
+    /// nothing about are left as they are
+    ///
+    /// # Errors
+    /// Whatever the source fails with, e.g. an unreachable database.
+    fn enrich(&self, transaction: &mut Transaction) -> Result<(), ParseError>;
+
+    /// Enrich every transaction of a statement, in order, stopping at the
+    /// first error
+    ///
+    /// # Errors
+    /// The first error of [`Enricher::enrich`].
+    fn enrich_all(&self, transactions: &mut [Transaction]) -> Result<(), ParseError> {
+        transactions
+            .iter_mut()
+            .try_for_each(|transaction| self.enrich(transaction))
+    }
+}
+
+impl<F> Enricher for F
+where
+    F: Fn(&mut Transaction) -> Result<(), ParseError> + Send + Sync,
+{
+    fn enrich(&self, transaction: &mut Transaction) -> Result<(), ParseError> {
+        self(transaction)
+    }
+}
+
+/// Counterparty names by account number, read from a CSV directory file.
+///
+/// The file has a header row naming an `account` (or `iban`) and a `name`
+/// column, in any order and case; other columns are ignored. Accounts match
+/// regardless of spaces and case, so `DE89 3704 0044 0532 0130 00` finds
+/// `DE89370400440532013000`. A listed account gives the transaction the
+/// directory's name, replacing the one the bank sent.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{CounterpartyDirectory, Enricher, Mt940Statement};
+///
+/// let directory = "iban,name\nDE89370400440532013000,ACME GmbH\n";
+/// let directory = CounterpartyDirectory::from_read(&mut directory.as_bytes()).unwrap();
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102C25,00NTRFNONREF\n:86:Invoice 17\n:62F:C200102EUR125,00\n";
+/// let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// statement.transactions[0].counterparty_account = Some("DE89 3704 0044 0532 0130 00".into());
+///
+/// directory.enrich_all(&mut statement.transactions).unwrap();
+/// assert_eq!(statement.transactions[0].counterparty_name.as_deref(), Some("ACME GmbH"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CounterpartyDirectory {
+    names: HashMap<String, String>,
+}
+
+impl CounterpartyDirectory {
+    /// Read a directory from CSV.
+    ///
+    /// # Errors
+    /// Returns `ParseError::CsvError` if the CSV cannot be read and
+    /// `ParseError::MissingField` if the header lacks the account or name column.
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut csv_reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let headers = csv_reader
+            .headers()
+            .map_err(|e| ParseError::CsvError(e.to_string()))?
+            .clone();
+        let column = |titles: &[&str]| {
+            headers
+                .iter()
+                .position(|title| titles.iter().any(|t| title.trim().eq_ignore_ascii_case(t)))
+        };
+        let account = column(&ACCOUNT_COLUMNS)
+            .ok_or_else(|| ParseError::MissingField(ACCOUNT_COLUMNS[0].into()))?;
+        let name =
+            column(&[NAME_COLUMN]).ok_or_else(|| ParseError::MissingField(NAME_COLUMN.into()))?;
+
+        let mut names = HashMap::new();
+        for record in csv_reader.records() {
+            let record = record.map_err(|e| ParseError::CsvError(e.to_string()))?;
+            let (Some(account), Some(name)) = (record.get(account), record.get(name)) else {
+                continue;
+            };
+            let (account, name) = (normalize_account(account), name.trim());
+            if !account.is_empty() && !name.is_empty() {
+                names.insert(account, name.to_string());
+            }
+        }
+        Ok(CounterpartyDirectory { names })
+    }
+
+    /// Read a directory from a CSV file.
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if the file cannot be opened, otherwise
+    /// the same errors as [`CounterpartyDirectory::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        Self::from_read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Name listed for `account`, if any
+    pub fn name(&self, account: &str) -> Option<&str> {
+        self.names
+            .get(&normalize_account(account))
+            .map(String::as_str)
+    }
+
+    /// Number of listed accounts
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no account is listed
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl Enricher for CounterpartyDirectory {
+    fn enrich(&self, transaction: &mut Transaction) -> Result<(), ParseError> {
+        let name = transaction
+            .counterparty_account
+            .as_deref()
+            .and_then(|account| self.name(account));
+        if let Some(name) = name {
+            transaction.counterparty_name = Some(name.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Account number without spaces, upper case
+fn normalize_account(account: &str) -> String {
+    account
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_reads_any_column_order() {
+        let csv = "Name,x\nACME GmbH,1\n";
+        assert!(matches!(
+            CounterpartyDirectory::from_read(&mut csv.as_bytes()),
+            Err(ParseError::MissingField(field)) if field == "account"
+        ));
+
+        let csv = "note,NAME,Account\nsupplier,ACME GmbH,de89 3704 0044 0532 0130 00\n,,\n";
+        let directory = CounterpartyDirectory::from_read(&mut csv.as_bytes()).unwrap();
+        assert_eq!(directory.len(), 1);
+        assert_eq!(directory.name("DE89370400440532013000"), Some("ACME GmbH"));
+        assert_eq!(directory.name("NL81ASNB9999999999"), None);
+    }
+
+    #[test]
+    fn test_closure_enricher_stops_at_first_error() {
+        let enricher = |transaction: &mut Transaction| {
+            if transaction.counterparty_account.is_none() {
+                return Err(ParseError::MissingField("counterparty_account".into()));
+            }
+            transaction.counterparty_name = Some("Known".into());
+            Ok(())
+        };
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102C25,00NTRFNONREF\n:61:200102C25,00NTRFNONREF\n\
+                    :62F:C200102EUR150,00\n";
+        let mut statement = crate::Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        statement.transactions[0].counterparty_account = Some("DE89370400440532013000".into());
+
+        assert!(enricher.enrich_all(&mut statement.transactions).is_err());
+        assert_eq!(
+            statement.transactions[0].counterparty_name.as_deref(),
+            Some("Known")
+        );
+    }
+}
@@ -1,5 +1,58 @@
+use std::fmt;
 use thiserror::Error;
 
+use crate::cancel;
+
+/// Position in the input that a [`ParseError::Located`] refers to.
+///
+/// Lines and columns count from 1, byte offsets from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorLocation {
+    /// Line of a text format (CSV, MT940, MT942, JSON); for CSV the line on which the
+    /// record starts
+    Line {
+        /// Line number
+        line: usize,
+        /// Column, when known: the character column for JSON, the field number for CSV
+        column: Option<usize>,
+        /// Tag (MT940 `60F`) or column title (CSV) of the offending field, when known
+        field: Option<String>,
+    },
+    /// Element of an XML format (CAMT)
+    Xml {
+        /// Path of elements from the root, e.g. `Document/BkToCstmrStmt/Stmt/Bal/Amt`
+        path: String,
+        /// Byte offset of the reader when the error was found
+        offset: u64,
+    },
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorLocation::Line {
+                line,
+                column,
+                field,
+            } => {
+                write!(f, "line {}", line)?;
+                if let Some(column) = column {
+                    write!(f, ", column {}", column)?;
+                }
+                if let Some(field) = field {
+                    write!(f, ", field {}", field)?;
+                }
+                Ok(())
+            }
+            // Errors found outside any element have no path
+            ErrorLocation::Xml { path, offset } if path.is_empty() => {
+                write!(f, "byte {}", offset)
+            }
+            ErrorLocation::Xml { path, offset } => write!(f, "{} at byte {}", path, offset),
+        }
+    }
+}
+
 /// Error type for all parsing and formatting operations in the ledger-parser library.
 ///
 /// This unified error type covers all possible error conditions that can occur
@@ -7,8 +60,9 @@ use thiserror::Error;
 ///
 /// # Error Categories
 /// - **General errors**: Format validation, missing fields, invalid values
-/// - **Format-specific errors**: CSV, MT940, and CAMT.053 parsing errors
+/// - **Format-specific errors**: CSV, MT940, CAMT.053, CAMT.052, CAMT.054, QIF and XLSX parsing errors
 /// - **I/O errors**: File reading/writing failures
+/// - **Cancellation**: Operations aborted through a `CancellationToken`
 ///
 /// # Example
 /// ```
@@ -25,6 +79,26 @@ use thiserror::Error;
 ///     Err(e) => eprintln!("Other error: {}", e),
 /// }
 /// ```
+///
+/// Errors about a particular place of the input come wrapped in
+/// [`ParseError::Located`]; [`location`](ParseError::location) and
+/// [`without_location`](ParseError::without_location) take them apart:
+///
+/// ```
+/// use ledger_parser::{ErrorLocation, Mt940Statement, ParseError};
+///
+/// let input = ":20:REF\n:25:ACC\n:60F:C250101EUR1x0,00\n:62F:C250101EUR0,00\n";
+/// let error = Mt940Statement::from_read(&mut input.as_bytes()).unwrap_err();
+///
+/// assert!(matches!(
+///     error.location(),
+///     Some(ErrorLocation::Line { line: 3, field: Some(tag), .. }) if tag == "60F"
+/// ));
+/// assert!(matches!(
+///     error.without_location(),
+///     ParseError::UnexpectedValue { found, .. } if found == "1x0,00"
+/// ));
+/// ```
 #[derive(Error, Debug)]
 pub enum ParseError {
     /// Invalid or unsupported format specified
@@ -41,6 +115,25 @@ pub enum ParseError {
         /// The invalid value that was encountered
         value: String,
     },
+    /// Value of the input that does not have the form the format requires
+    #[error("Invalid {field}: expected {expected}, found '{found}'")]
+    UnexpectedValue {
+        /// What the value is, e.g. `amount` or `date`
+        field: String,
+        /// Form the value should have
+        expected: String,
+        /// The text that was found instead
+        found: String,
+    },
+    /// Error at a known position of the input
+    #[error("{location}: {source}")]
+    Located {
+        /// Where in the input the error occurred
+        location: ErrorLocation,
+        /// The error itself
+        #[source]
+        source: Box<ParseError>,
+    },
 
     /// CSV format parsing error
     #[error("CSV error: {0}")]
@@ -48,18 +141,171 @@ pub enum ParseError {
     /// MT940 format parsing error
     #[error("MT940 error: {0}")]
     Mt940Error(String),
+    /// MT942 (interim transaction report) format parsing error
+    #[error("MT942 error: {0}")]
+    Mt942Error(String),
     /// CAMT.053 XML format parsing error
     #[error("CAMT.053 error: {0}")]
     Camt053Error(String),
+    /// CAMT.052 (intra-day report) XML format parsing error
+    #[error("CAMT.052 error: {0}")]
+    Camt052Error(String),
+    /// CAMT.054 (debit/credit notification) XML format parsing error
+    #[error("CAMT.054 error: {0}")]
+    Camt054Error(String),
+    /// Canonical JSON statement parsing or serialization error
+    #[error("JSON error: {0}")]
+    JsonError(String),
+    /// BAI2 (cash management balance report) parsing error
+    #[error("BAI2 error: {0}")]
+    Bai2Error(String),
+    /// QIF (Quicken Interchange Format) parsing error
+    #[error("QIF error: {0}")]
+    QifError(String),
+    /// Excel (XLSX) workbook reading error
+    #[error("XLSX error: {0}")]
+    XlsxError(String),
+    /// Amounts in different currencies combined
+    #[error("Currency mismatch: expected {expected}, found {found}")]
+    CurrencyMismatch {
+        /// Currency of the left-hand amount
+        expected: String,
+        /// Currency of the right-hand amount
+        found: String,
+    },
     /// I/O operation error (file reading/writing)
     #[error("I/O error: {0}")]
-    IoError(#[from] std::io::Error),
+    IoError(#[source] std::io::Error),
+    /// The operation was aborted through a [`CancellationToken`](crate::CancellationToken)
+    #[error("Operation cancelled")]
+    Cancelled,
+    /// Re-reading converted output found data the conversion was not expected to lose
+    #[error("Verification failed: {0}")]
+    VerificationFailed(String),
+}
+
+impl ParseError {
+    /// Stable machine-readable code for the error category.
+    ///
+    /// Unlike the `Display` output, codes never change between releases, so they
+    /// are safe to use as metric labels or in alerting rules.
+    ///
+    /// A [`ParseError::Located`] has the code of the error it wraps.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::Located { source, .. } => source.code(),
+            ParseError::UnexpectedValue { .. } => "unexpected_value",
+            ParseError::InvalidFormat(_) => "invalid_format",
+            ParseError::MissingField(_) => "missing_field",
+            ParseError::InvalidFieldValue { .. } => "invalid_field_value",
+            ParseError::CsvError(_) => "csv_error",
+            ParseError::Mt940Error(_) => "mt940_error",
+            ParseError::Mt942Error(_) => "mt942_error",
+            ParseError::Camt053Error(_) => "camt053_error",
+            ParseError::Camt052Error(_) => "camt052_error",
+            ParseError::Camt054Error(_) => "camt054_error",
+            ParseError::JsonError(_) => "json_error",
+            ParseError::Bai2Error(_) => "bai2_error",
+            ParseError::QifError(_) => "qif_error",
+            ParseError::XlsxError(_) => "xlsx_error",
+            ParseError::CurrencyMismatch { .. } => "currency_mismatch",
+            ParseError::IoError(_) => "io_error",
+            ParseError::Cancelled => "cancelled",
+            ParseError::VerificationFailed(_) => "verification_failed",
+        }
+    }
+
+    /// Position in the input the error refers to, if known
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            ParseError::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// The error without its [`ParseError::Located`] wrapper
+    pub fn without_location(&self) -> &ParseError {
+        match self {
+            ParseError::Located { source, .. } => source.without_location(),
+            error => error,
+        }
+    }
+
+    /// Attach `location` to the error.
+    ///
+    /// Errors that already have a location keep the innermost one, and I/O errors
+    /// and cancellation are not about a place in the input, so they stay as they are.
+    pub(crate) fn at(self, location: ErrorLocation) -> Self {
+        match self {
+            ParseError::Located { .. } | ParseError::IoError(_) | ParseError::Cancelled => self,
+            error => ParseError::Located {
+                location,
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Apply `relabel` to the error inside any [`ParseError::Located`] wrapper,
+    /// keeping the location; used where one format reports errors of another
+    pub(crate) fn map_located(self, relabel: impl FnOnce(ParseError) -> ParseError) -> Self {
+        match self {
+            ParseError::Located { location, source } => ParseError::Located {
+                location,
+                source: Box::new(source.map_located(relabel)),
+            },
+            error => relabel(error),
+        }
+    }
+
+    /// Attach the line (and MT940 tag or CSV column title) of a text format
+    pub(crate) fn at_line(self, line: usize, field: Option<&str>) -> Self {
+        self.at(ErrorLocation::Line {
+            line,
+            column: None,
+            field: field.map(String::from),
+        })
+    }
+}
+
+/// Automatic conversion from I/O errors; reads and writes aborted by a
+/// cancelled token become `ParseError::Cancelled`
+impl From<std::io::Error> for ParseError {
+    fn from(error: std::io::Error) -> Self {
+        if cancel::is_cancellation(&error) {
+            ParseError::Cancelled
+        } else {
+            ParseError::IoError(error)
+        }
+    }
 }
 
 /// Automatic conversion from CSV errors to ParseError
 impl From<csv::Error> for ParseError {
     fn from(error: csv::Error) -> Self {
-        ParseError::CsvError(error.to_string())
+        match error.kind() {
+            csv::ErrorKind::Io(io_error) if cancel::is_cancellation(io_error) => {
+                ParseError::Cancelled
+            }
+            _ => {
+                let line = error.position().map(|position| position.line() as usize);
+                let error = ParseError::CsvError(error.to_string());
+                match line {
+                    Some(line) => error.at_line(line, None),
+                    None => error,
+                }
+            }
+        }
+    }
+}
+
+/// Automatic conversion from workbook errors; IO failures stay `ParseError::IoError`
+#[cfg(feature = "xlsx")]
+impl From<calamine::XlsxError> for ParseError {
+    fn from(error: calamine::XlsxError) -> Self {
+        match error {
+            calamine::XlsxError::Io(io_error) => io_error.into(),
+            _ => ParseError::XlsxError(error.to_string()),
+        }
     }
 }
 
@@ -111,6 +357,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_code() {
+        assert_eq!(ParseError::Mt940Error("x".into()).code(), "mt940_error");
+        let io_error = std::io::Error::other("boom");
+        assert_eq!(ParseError::from(io_error).code(), "io_error");
+    }
+
+    #[test]
+    fn test_located_error() {
+        let error = ParseError::UnexpectedValue {
+            field: "amount".into(),
+            expected: "a number".into(),
+            found: "12x".into(),
+        }
+        .at_line(7, Some("60F"));
+        assert_eq!(
+            error.to_string(),
+            "line 7, field 60F: Invalid amount: expected a number, found '12x'"
+        );
+        assert_eq!(error.code(), "unexpected_value");
+        assert!(matches!(
+            error.without_location(),
+            ParseError::UnexpectedValue { .. }
+        ));
+
+        // The innermost location wins; cancellation never gets one
+        let xml = ErrorLocation::Xml {
+            path: "Document/Stmt".into(),
+            offset: 120,
+        };
+        assert_eq!(
+            error.at(xml.clone()).location().unwrap().to_string(),
+            "line 7, field 60F"
+        );
+        assert!(ParseError::Cancelled.at(xml).location().is_none());
+    }
+
     #[test]
     fn test_error_debug() {
         let error = ParseError::Mt940Error("Test error".into());
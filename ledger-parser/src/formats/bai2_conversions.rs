@@ -0,0 +1,41 @@
+//! Type conversions from Bai2 to other formats
+//!
+//! Implements the `From` trait so BAI2 accounts can be written out in any
+//! writable format (CAMT.053, CAMT.054, CSV, JSON, MT940, MT942, QIF). BAI2 is
+//! read-only, so there are no conversions into BAI2.
+
+use crate::{
+    Bai2Statement, Camt053Statement, Camt054Statement, CsvStatement, JsonStatement, Mt940Statement,
+    QifStatement, StatementInfo,
+};
+
+/// Implements `From<Bai2Statement>` as a direct field-by-field conversion,
+/// since all structures share the same data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_from_bai2 {
+    ($target:ident $(, $field:ident: $value:expr)*) => {
+        impl From<Bai2Statement> for $target {
+            fn from(bai2: Bai2Statement) -> Self {
+                $target {
+                    account_number: bai2.account_number,
+                    currency: bai2.currency,
+                    opening_balance: bai2.opening_balance,
+                    opening_date: bai2.opening_date,
+                    opening_indicator: bai2.opening_indicator,
+                    closing_balance: bai2.closing_balance,
+                    closing_date: bai2.closing_date,
+                    closing_indicator: bai2.closing_indicator,
+                    transactions: bai2.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+    };
+}
+
+impl_from_bai2!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_from_bai2!(Camt054Statement);
+impl_from_bai2!(CsvStatement, info: StatementInfo::default());
+impl_from_bai2!(JsonStatement);
+impl_from_bai2!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_from_bai2!(QifStatement);
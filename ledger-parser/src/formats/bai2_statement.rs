@@ -0,0 +1,694 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::ParseError;
+use crate::formats::utils;
+use crate::model::{BalanceType, Transaction, TransactionType};
+
+/// Currency of groups and accounts that leave the currency code blank
+const DEFAULT_CURRENCY: &str = "USD";
+/// Summary type code of the opening ledger balance
+const OPENING_LEDGER_TYPE_CODE: &str = "010";
+/// Summary type code of the closing ledger balance
+const CLOSING_LEDGER_TYPE_CODE: &str = "015";
+/// Detail type codes of credit transactions
+const CREDIT_TYPE_CODES: std::ops::RangeInclusive<u16> = 100..=399;
+/// Detail type codes of debit transactions
+const DEBIT_TYPE_CODES: std::ops::RangeInclusive<u16> = 400..=699;
+/// BAI2 amounts are in cents without a decimal point
+const CENTS_PER_UNIT: f64 = 100.0;
+
+/// BAI2 cash management balance report (one account of a BAI2 file)
+///
+/// Parses the record structure used by US treasury systems:
+/// - `01`/`99` file header and trailer, `02`/`98` group header and trailer
+/// - `03` account identifier with summary amounts (`010` opening and `015`
+///   closing ledger balance), `49` account trailer
+/// - `16` transaction details (type codes 100–399 are credits, 400–699 debits)
+/// - `88` continuation records, joined to the record they continue
+///
+/// Control totals and record counts in the `49`/`98`/`99` trailers are checked.
+/// BAI2 dates transactions with the group's as-of date only; a missing opening
+/// or closing ledger balance is derived from the other one and the details.
+///
+/// BAI2 is read-only; convert into another format to write it out.
+/// Fields are identical to Mt940/Camt053/CsvStatement for seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bai2Statement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+impl Bai2Statement {
+    /// Parse a single-account BAI2 file from any Read source (file, stdin, buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Bai2Error` if the record structure is invalid, a
+    /// trailer's control total or count does not match, or the file does not
+    /// hold exactly one account (use [`Bai2Statement::from_read_all`] for
+    /// multi-account files).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::Bai2Statement;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("report.bai").unwrap();
+    /// let statement = Bai2Statement::from_read(&mut file).unwrap();
+    /// ```
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse every account of a BAI2 file, in file order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Bai2Error` if the record structure is invalid, a
+    /// trailer's control total or count does not match, or there are no accounts.
+    pub fn from_read_all<R: Read>(reader: &mut R) -> Result<Vec<Self>, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::parse_accounts(&content)
+    }
+
+    /// Parse a single-account BAI2 file from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Bai2Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse a single-account BAI2 file from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        let mut accounts = Self::parse_accounts(content)?;
+        if accounts.len() != 1 {
+            return Err(ParseError::Bai2Error(format!(
+                "File contains {} accounts; use Bai2Statement::from_read_all",
+                accounts.len()
+            )));
+        }
+        let statement = accounts.remove(0);
+        trace_statement!("parsed statement", "bai2", statement);
+
+        Ok(statement)
+    }
+
+    /// Parse all accounts from already loaded content
+    fn parse_accounts(content: &str) -> Result<Vec<Self>, ParseError> {
+        if content.trim().is_empty() {
+            return Err(ParseError::Bai2Error("Empty input".into()));
+        }
+
+        let mut parser = Bai2Parser::default();
+        for record in logical_records(content)? {
+            parser.record(&record)?;
+        }
+        parser.finish()
+    }
+}
+
+/// A logical record: a physical record plus its `88` continuations
+struct Record {
+    /// Two-digit record code
+    code: String,
+    /// Fields after the record code; continuation parts are separated by `\n`
+    body: String,
+    /// Number of physical records (lines) making up the logical record
+    physical_records: usize,
+}
+
+/// Group physical records into logical records, joining `88` continuations.
+///
+/// Every physical record loses its trailing `/` delimiter.
+fn logical_records(content: &str) -> Result<Vec<Record>, ParseError> {
+    let mut records: Vec<Record> = Vec::new();
+
+    for (index, line) in content.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let line = line.strip_suffix('/').unwrap_or(line);
+        let (code, body) = line.split_once(',').unwrap_or((line, ""));
+
+        if code == "88" {
+            let previous = records.last_mut().ok_or_else(|| {
+                ParseError::Bai2Error(format!(
+                    "Line {}: continuation record without a record to continue",
+                    index + 1
+                ))
+            })?;
+            previous.body.push('\n');
+            previous.body.push_str(body);
+            previous.physical_records += 1;
+        } else {
+            records.push(Record {
+                code: code.to_string(),
+                body: body.to_string(),
+                physical_records: 1,
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+/// Cursor over the comma-delimited fields of a record body
+struct Fields<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Fields<'a> {
+    fn new(body: &'a str) -> Self {
+        Fields { rest: Some(body) }
+    }
+
+    /// Next field; a continuation boundary also ends a field. Missing fields are empty.
+    fn next_field(&mut self) -> &'a str {
+        match self.rest {
+            Some(rest) => match rest.find([',', '\n']) {
+                Some(end) => {
+                    self.rest = Some(&rest[end + 1..]);
+                    rest[..end].trim()
+                }
+                None => {
+                    self.rest = None;
+                    rest.trim()
+                }
+            },
+            None => "",
+        }
+    }
+
+    /// Remaining text, including delimiters (the free-form `16` text field)
+    fn rest(&mut self) -> &'a str {
+        self.rest.take().unwrap_or_default()
+    }
+
+    /// Whether any field is left
+    fn is_empty(&self) -> bool {
+        self.rest.is_none_or(|rest| rest.trim().is_empty())
+    }
+}
+
+/// Account being read between its `03` and `49` records
+struct AccountScratch {
+    account_number: String,
+    currency: String,
+    opening: Option<i64>,
+    closing: Option<i64>,
+    transactions: Vec<Transaction>,
+    net: i64,
+    control_total: i64,
+    records: usize,
+}
+
+/// Group being read between its `02` and `98` records
+struct GroupScratch {
+    currency: String,
+    as_of_date: DateTime<FixedOffset>,
+    control_total: i64,
+    accounts: usize,
+    records: usize,
+}
+
+/// Record-by-record BAI2 state machine
+#[derive(Default)]
+struct Bai2Parser {
+    file_open: bool,
+    file_closed: bool,
+    group: Option<GroupScratch>,
+    account: Option<AccountScratch>,
+    statements: Vec<Bai2Statement>,
+    control_total: i64,
+    groups: usize,
+    records: usize,
+}
+
+impl Bai2Parser {
+    /// Consume one logical record
+    fn record(&mut self, record: &Record) -> Result<(), ParseError> {
+        if self.file_closed {
+            return Err(ParseError::Bai2Error(format!(
+                "Record {} after the 99 file trailer",
+                record.code
+            )));
+        }
+        self.records += record.physical_records;
+        if let Some(group) = &mut self.group {
+            group.records += record.physical_records;
+        }
+        if let Some(account) = &mut self.account {
+            account.records += record.physical_records;
+        }
+
+        let mut fields = Fields::new(&record.body);
+        match record.code.as_str() {
+            "01" => self.file_header(),
+            "02" => self.group_header(&mut fields),
+            "03" => self.account_identifier(&mut fields, record.physical_records),
+            "16" => self.transaction_detail(&mut fields),
+            "49" => self.account_trailer(&mut fields),
+            "98" => self.group_trailer(&mut fields),
+            "99" => self.file_trailer(&mut fields),
+            code => Err(ParseError::Bai2Error(format!(
+                "Unknown record code: {}",
+                code
+            ))),
+        }
+    }
+
+    /// `01` file header
+    fn file_header(&mut self) -> Result<(), ParseError> {
+        if self.file_open {
+            return Err(ParseError::Bai2Error("Duplicate 01 file header".into()));
+        }
+        self.file_open = true;
+        Ok(())
+    }
+
+    /// `02` group header: receiver, originator, status, as-of date/time, currency
+    fn group_header(&mut self, fields: &mut Fields<'_>) -> Result<(), ParseError> {
+        if !self.file_open || self.group.is_some() {
+            return Err(ParseError::Bai2Error(
+                "02 group header outside of a file or inside another group".into(),
+            ));
+        }
+        let _receiver = fields.next_field();
+        let _originator = fields.next_field();
+        let _status = fields.next_field();
+        let as_of_date = parse_date(fields.next_field())?;
+        let _as_of_time = fields.next_field();
+        let currency = currency_or(fields.next_field(), DEFAULT_CURRENCY);
+
+        self.group = Some(GroupScratch {
+            currency,
+            as_of_date,
+            control_total: 0,
+            accounts: 0,
+            // The 02 record itself counts towards the group's records
+            records: 1,
+        });
+        Ok(())
+    }
+
+    /// `03` account identifier: account, currency and summary amounts
+    fn account_identifier(
+        &mut self,
+        fields: &mut Fields<'_>,
+        physical_records: usize,
+    ) -> Result<(), ParseError> {
+        let group = match (&self.group, &self.account) {
+            (Some(group), None) => group,
+            _ => {
+                return Err(ParseError::Bai2Error(
+                    "03 account identifier outside of a group or inside another account".into(),
+                ))
+            }
+        };
+        let account_number = fields.next_field();
+        if account_number.is_empty() {
+            return Err(ParseError::MissingField("account_number".into()));
+        }
+        let mut account = AccountScratch {
+            account_number: account_number.to_string(),
+            currency: currency_or(fields.next_field(), &group.currency),
+            opening: None,
+            closing: None,
+            transactions: Vec::new(),
+            net: 0,
+            control_total: 0,
+            records: physical_records,
+        };
+
+        while !fields.is_empty() {
+            let type_code = fields.next_field();
+            let amount = fields.next_field();
+            let _item_count = fields.next_field();
+            skip_funds_type(fields)?;
+            if type_code.is_empty() || amount.is_empty() {
+                continue;
+            }
+
+            let amount = parse_cents(amount)?;
+            account.control_total += amount;
+            match type_code {
+                OPENING_LEDGER_TYPE_CODE => account.opening = Some(amount),
+                CLOSING_LEDGER_TYPE_CODE => account.closing = Some(amount),
+                _ => {}
+            }
+        }
+
+        self.account = Some(account);
+        Ok(())
+    }
+
+    /// `16` transaction detail: type code, amount, funds type, references, text
+    fn transaction_detail(&mut self, fields: &mut Fields<'_>) -> Result<(), ParseError> {
+        let (Some(group), Some(account)) = (&self.group, &mut self.account) else {
+            return Err(ParseError::Bai2Error(
+                "16 transaction detail outside of an account".into(),
+            ));
+        };
+
+        let type_code = fields.next_field();
+        let code: u16 = type_code.parse().map_err(|_| {
+            ParseError::Bai2Error(format!("Invalid detail type code: {}", type_code))
+        })?;
+        let transaction_type = if CREDIT_TYPE_CODES.contains(&code) {
+            TransactionType::Credit
+        } else if DEBIT_TYPE_CODES.contains(&code) {
+            TransactionType::Debit
+        } else {
+            return Err(ParseError::Bai2Error(format!(
+                "Unsupported detail type code: {}",
+                type_code
+            )));
+        };
+        let amount = parse_cents(fields.next_field())?;
+        let value_date = skip_funds_type(fields)?;
+        let bank_reference = fields.next_field();
+        let customer_reference = fields.next_field();
+        let description = fields
+            .rest()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let reference = [customer_reference, bank_reference]
+            .into_iter()
+            .find(|reference| !reference.is_empty())
+            .map(str::to_string);
+
+        account.control_total += amount;
+        account.net += match transaction_type {
+            TransactionType::Credit => amount,
+            TransactionType::Debit => -amount,
+        };
+        account.transactions.push(Transaction {
+            value_date,
+            reference,
+            ..Transaction::new(
+                group.as_of_date,
+                amount.abs() as f64 / CENTS_PER_UNIT,
+                transaction_type,
+                description,
+            )
+        });
+        Ok(())
+    }
+
+    /// `49` account trailer: account control total and record count
+    fn account_trailer(&mut self, fields: &mut Fields<'_>) -> Result<(), ParseError> {
+        let (Some(group), Some(account)) = (&mut self.group, self.account.take()) else {
+            return Err(ParseError::Bai2Error(
+                "49 account trailer without an account".into(),
+            ));
+        };
+        check_trailer("49", "control total", fields, account.control_total)?;
+        check_trailer("49", "record count", fields, account.records as i64)?;
+
+        group.control_total += account.control_total;
+        group.accounts += 1;
+
+        let (opening, closing) = match (account.opening, account.closing) {
+            (Some(opening), Some(closing)) => (opening, closing),
+            (Some(opening), None) => (opening, opening + account.net),
+            (None, Some(closing)) => (closing - account.net, closing),
+            (None, None) => (0, account.net),
+        };
+        let (opening_balance, opening_indicator) = unsigned_balance(opening);
+        let (closing_balance, closing_indicator) = unsigned_balance(closing);
+        self.statements.push(Bai2Statement {
+            account_number: account.account_number,
+            currency: account.currency,
+            opening_balance,
+            opening_date: group.as_of_date,
+            opening_indicator,
+            closing_balance,
+            closing_date: group.as_of_date,
+            closing_indicator,
+            transactions: account.transactions,
+        });
+        Ok(())
+    }
+
+    /// `98` group trailer: group control total, account count and record count
+    fn group_trailer(&mut self, fields: &mut Fields<'_>) -> Result<(), ParseError> {
+        if self.account.is_some() {
+            return Err(ParseError::Bai2Error(
+                "98 group trailer before the 49 account trailer".into(),
+            ));
+        }
+        let group = self
+            .group
+            .take()
+            .ok_or_else(|| ParseError::Bai2Error("98 group trailer without a group".into()))?;
+        check_trailer("98", "control total", fields, group.control_total)?;
+        check_trailer("98", "account count", fields, group.accounts as i64)?;
+        check_trailer("98", "record count", fields, group.records as i64)?;
+
+        self.control_total += group.control_total;
+        self.groups += 1;
+        Ok(())
+    }
+
+    /// `99` file trailer: file control total, group count and record count
+    fn file_trailer(&mut self, fields: &mut Fields<'_>) -> Result<(), ParseError> {
+        if !self.file_open || self.group.is_some() {
+            return Err(ParseError::Bai2Error(
+                "99 file trailer without a file header or before the 98 group trailer".into(),
+            ));
+        }
+        check_trailer("99", "control total", fields, self.control_total)?;
+        check_trailer("99", "group count", fields, self.groups as i64)?;
+        check_trailer("99", "record count", fields, self.records as i64)?;
+
+        self.file_closed = true;
+        Ok(())
+    }
+
+    /// Accounts of a complete file
+    fn finish(self) -> Result<Vec<Bai2Statement>, ParseError> {
+        if !self.file_closed {
+            return Err(ParseError::Bai2Error("Missing 99 file trailer".into()));
+        }
+        if self.statements.is_empty() {
+            return Err(ParseError::Bai2Error("File contains no accounts".into()));
+        }
+        Ok(self.statements)
+    }
+}
+
+/// Skip the funds type and its availability fields, returning a value date (`V`) if any
+fn skip_funds_type(fields: &mut Fields<'_>) -> Result<Option<NaiveDate>, ParseError> {
+    let funds_type = fields.next_field();
+    match funds_type {
+        "" | "0" | "1" | "2" | "Z" => Ok(None),
+        // Immediate, one-day and two-or-more-day availability amounts
+        "S" => {
+            for _ in 0..3 {
+                fields.next_field();
+            }
+            Ok(None)
+        }
+        // Value date and time
+        "V" => {
+            let date = NaiveDate::parse_from_str(fields.next_field(), "%y%m%d")
+                .map_err(|_| ParseError::Bai2Error("Invalid value date".into()))?;
+            fields.next_field();
+            Ok(Some(date))
+        }
+        // Distribution: count followed by (days, amount) pairs
+        "D" => {
+            let count = fields.next_field();
+            let count: usize = count.parse().map_err(|_| {
+                ParseError::Bai2Error(format!("Invalid distribution count: {}", count))
+            })?;
+            for _ in 0..count * 2 {
+                fields.next_field();
+            }
+            Ok(None)
+        }
+        other => Err(ParseError::Bai2Error(format!(
+            "Unknown funds type: {}",
+            other
+        ))),
+    }
+}
+
+/// Compare the next trailer field with the value computed while reading
+fn check_trailer(
+    code: &str,
+    name: &str,
+    fields: &mut Fields<'_>,
+    expected: i64,
+) -> Result<(), ParseError> {
+    let found = parse_cents(fields.next_field())?;
+    if found != expected {
+        return Err(ParseError::Bai2Error(format!(
+            "{} trailer {} is {}, computed {}",
+            code, name, found, expected
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a signed integer amount in cents (or a count)
+fn parse_cents(text: &str) -> Result<i64, ParseError> {
+    let digits = text.strip_prefix('+').unwrap_or(text);
+    if digits.is_empty()
+        || !digits
+            .trim_start_matches('-')
+            .chars()
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(ParseError::Bai2Error(format!("Invalid amount: {}", text)));
+    }
+    digits
+        .parse()
+        .map_err(|_| ParseError::Bai2Error(format!("Invalid amount: {}", text)))
+}
+
+/// Parse a YYMMDD date at midnight UTC
+fn parse_date(text: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    NaiveDate::parse_from_str(text, "%y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc.fix()))
+        .ok_or_else(|| ParseError::Bai2Error(format!("Invalid as-of date: {}", text)))
+}
+
+/// Currency code, or `fallback` when the field is blank
+fn currency_or(code: &str, fallback: &str) -> String {
+    if code.is_empty() {
+        fallback.to_string()
+    } else {
+        code.to_uppercase()
+    }
+}
+
+/// Absolute balance in units with its indicator
+fn unsigned_balance(cents: i64) -> (f64, BalanceType) {
+    let indicator = if cents < 0 {
+        BalanceType::Debit
+    } else {
+        BalanceType::Credit
+    };
+    (cents.abs() as f64 / CENTS_PER_UNIT, indicator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "01,BANKUS33,ACMECORP,250314,0800,1,,,2/
+02,ACMECORP,BANKUS33,1,250314,0800,USD,2/
+03,0975312468,USD,010,500000,,,015,412500,,/
+16,165,100000,0,BANKREF1,INV-42,CUSTOMER PAYMENT
+88,INVOICE 42
+16,475,187500,V,250315,0000,CHK1001,,CHECK PAID/
+49,1200000,5/
+98,1200000,1,7/
+99,1200000,1,9/
+";
+
+    #[test]
+    fn test_parse_single_account() {
+        let statement = Bai2Statement::from_read(&mut REPORT.as_bytes()).unwrap();
+
+        assert_eq!(statement.account_number, "0975312468");
+        assert_eq!(statement.currency, "USD");
+        assert_eq!(statement.opening_balance, 5000.0);
+        assert_eq!(statement.closing_balance, 4125.0);
+        assert_eq!(statement.closing_indicator, BalanceType::Credit);
+        assert_eq!(
+            statement.opening_date.format("%Y-%m-%d").to_string(),
+            "2025-03-14"
+        );
+
+        let [credit, debit] = statement.transactions.as_slice() else {
+            panic!("expected two transactions");
+        };
+        assert_eq!(credit.transaction_type, TransactionType::Credit);
+        assert_eq!(credit.amount, 1000.0);
+        assert_eq!(credit.reference.as_deref(), Some("INV-42"));
+        assert_eq!(credit.description, "CUSTOMER PAYMENT\nINVOICE 42");
+        assert_eq!(debit.transaction_type, TransactionType::Debit);
+        assert_eq!(debit.amount, 1875.0);
+        assert_eq!(debit.reference.as_deref(), Some("CHK1001"));
+        assert_eq!(debit.value_date, NaiveDate::from_ymd_opt(2025, 3, 15));
+        assert_eq!(debit.description, "CHECK PAID");
+    }
+
+    #[test]
+    fn test_control_total_mismatch_is_rejected() {
+        let tampered = REPORT.replace("49,1200000,5/", "49,1200001,5/");
+        let err = Bai2Statement::from_read(&mut tampered.as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::Bai2Error(msg) if msg.starts_with("49 trailer")));
+
+        let truncated = REPORT.replace("99,1200000,1,9/\n", "");
+        assert!(matches!(
+            Bai2Statement::from_read(&mut truncated.as_bytes()),
+            Err(ParseError::Bai2Error(msg)) if msg == "Missing 99 file trailer"
+        ));
+    }
+
+    #[test]
+    fn test_multi_account_file() {
+        let content = "01,BANK,ACME,250314,0800,1,,,2/
+02,ACME,BANK,1,250314,,,2/
+03,111,,015,-2500,,/
+49,-2500,2/
+03,222,EUR,010,1000,,/
+16,399,500,,,,MISC CREDIT/
+49,1500,3/
+98,-1000,2,7/
+99,-1000,1,9/
+";
+        assert!(Bai2Statement::from_read(&mut content.as_bytes()).is_err());
+
+        let accounts = Bai2Statement::from_read_all(&mut content.as_bytes()).unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].currency, DEFAULT_CURRENCY);
+        assert_eq!(accounts[0].closing_balance, 25.0);
+        assert_eq!(accounts[0].closing_indicator, BalanceType::Debit);
+        assert_eq!(accounts[0].opening_balance, 25.0);
+        assert_eq!(accounts[1].currency, "EUR");
+        assert_eq!(accounts[1].closing_balance, 15.0);
+        assert_eq!(accounts[1].transactions[0].reference, None);
+    }
+}
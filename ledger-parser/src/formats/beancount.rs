@@ -0,0 +1,300 @@
+use chrono::{DateTime, Duration, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+
+use crate::formats::paging::{
+    round_cents, signed_amount, signed_transaction_amount, unbooked_movement,
+};
+use crate::formats::presets::PRESET_BEANCOUNT;
+use crate::{ParseError, StatementRef, Transaction};
+
+/// Posting indentation used by `bean-format`
+const POSTING_INDENT: &str = "  ";
+/// Column the posting amounts are right-aligned to
+const AMOUNT_COLUMN: usize = 52;
+/// Minimum gap between account and amount
+const AMOUNT_GAP: usize = 2;
+/// Flag of a completed transaction
+const BOOKED_FLAG: char = '*';
+/// Flag of a transaction that needs attention, used for entries not booked yet
+const PENDING_FLAG: char = '!';
+
+/// Writer profile for Beancount ledgers.
+///
+/// The output opens the accounts, pads `bank_account` from
+/// `opening_balance_account` and asserts the opening balance, lists every
+/// transaction with the counterparty as payee and the description as
+/// narration, and ends with a `balance` assertion of the closing balance.
+/// Pending and informational entries carry the `!` flag; as Beancount counts
+/// flagged postings in balances, the closing assertion adds them to the
+/// statement's closing balance, which holds booked entries only.
+/// Beancount checks balances at the start of a day, so the opening assertion
+/// is dated on the opening date and the closing one on the day after the
+/// closing date; `open` and `pad` directives precede the opening date by a day.
+/// Account names must follow Beancount rules (`Assets:Bank`, no spaces).
+///
+/// # Example
+/// ```
+/// use ledger_parser::{BeancountProfile, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:DE89370400440532013000\n:28C:1/1\n:60F:C250101EUR100,00\n\
+///             :61:250107C1100,00NTRFINV7\n:86:Invoice 7\n:62F:C250107EUR1200,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// BeancountProfile::default()
+///     .write_to(statement.as_view(), &mut output)
+///     .unwrap();
+/// let ledger = String::from_utf8(output).unwrap();
+/// assert!(ledger.contains("2025-01-07 * \"Invoice 7\"\n"));
+/// assert!(ledger.ends_with("2025-01-08 balance Assets:Bank 1200.00 EUR\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeancountProfile {
+    /// Account of the bank statement, e.g. `Assets:Bank:Checking`
+    pub bank_account: String,
+    /// Counter account of money received
+    pub income_account: String,
+    /// Counter account of money paid out
+    pub expense_account: String,
+    /// Account the opening balance is padded from; `None` writes no `pad`
+    /// directive, for ledgers that already carry the account's history
+    pub opening_balance_account: Option<String>,
+    /// Write `open` directives; disable when appending to a ledger that already opens the accounts
+    pub open_accounts: bool,
+}
+
+impl Default for BeancountProfile {
+    fn default() -> Self {
+        BeancountProfile {
+            bank_account: "Assets:Bank".into(),
+            income_account: "Income:Unknown".into(),
+            expense_account: "Expenses:Unknown".into(),
+            opening_balance_account: Some("Equity:Opening-Balances".into()),
+            open_accounts: true,
+        }
+    }
+}
+
+impl BeancountProfile {
+    /// Profile name used by [`ExportPreset`](crate::ExportPreset)
+    pub fn name(&self) -> &'static str {
+        PRESET_BEANCOUNT
+    }
+
+    /// Write the statement as Beancount directives.
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        let mut writer = BufWriter::new(writer);
+        let currency = statement.currency;
+        let day_before_opening = date(statement.opening_date - Duration::days(1));
+
+        if self.open_accounts {
+            let mut accounts = vec![
+                &self.bank_account,
+                &self.income_account,
+                &self.expense_account,
+            ];
+            accounts.extend(&self.opening_balance_account);
+            for account in accounts {
+                writeln!(
+                    writer,
+                    "{} open {} {}",
+                    day_before_opening, account, currency
+                )?;
+            }
+            writeln!(writer)?;
+        }
+        if let Some(equity_account) = &self.opening_balance_account {
+            writeln!(
+                writer,
+                "{} pad {} {}",
+                day_before_opening, self.bank_account, equity_account
+            )?;
+        }
+        let opening = signed_amount(statement.opening_balance, statement.opening_indicator);
+        writeln!(
+            writer,
+            "{} balance {} {:.2} {}",
+            date(statement.opening_date),
+            self.bank_account,
+            round_cents(opening),
+            currency
+        )?;
+
+        for transaction in statement.transactions {
+            writeln!(writer)?;
+            self.write_transaction(&mut writer, transaction, currency)?;
+        }
+
+        let closing = signed_amount(statement.closing_balance, statement.closing_indicator)
+            + unbooked_movement(statement.transactions);
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{} balance {} {:.2} {}",
+            date(statement.closing_date + Duration::days(1)),
+            self.bank_account,
+            round_cents(closing),
+            currency
+        )?;
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// One transaction directive with its metadata and postings
+    fn write_transaction<W: Write>(
+        &self,
+        writer: &mut W,
+        transaction: &Transaction,
+        currency: &str,
+    ) -> Result<(), ParseError> {
+        let payee = transaction
+            .counterparty_name
+            .as_deref()
+            .map(|name| format!("{} ", quote(name)))
+            .unwrap_or_default();
+        let flag = if transaction.status.is_booked() {
+            BOOKED_FLAG
+        } else {
+            PENDING_FLAG
+        };
+        writeln!(
+            writer,
+            "{} {} {}{}",
+            date(transaction.booking_date),
+            flag,
+            payee,
+            quote(&transaction.description)
+        )?;
+        if let Some(reference) = &transaction.reference {
+            writeln!(writer, "{}reference: {}", POSTING_INDENT, quote(reference))?;
+        }
+        if let Some(account) = &transaction.counterparty_account {
+            writeln!(
+                writer,
+                "{}counterparty_account: {}",
+                POSTING_INDENT,
+                quote(account)
+            )?;
+        }
+
+        let amount = signed_transaction_amount(transaction);
+        let contra_account = if amount >= 0.0 {
+            &self.income_account
+        } else {
+            &self.expense_account
+        };
+        let amount = format!("{:.2} {}", round_cents(amount), currency);
+        let width = AMOUNT_COLUMN
+            .saturating_sub(POSTING_INDENT.len() + self.bank_account.chars().count())
+            .max(AMOUNT_GAP + amount.len());
+        writeln!(
+            writer,
+            "{}{}{:>width$}",
+            POSTING_INDENT, self.bank_account, amount
+        )?;
+        writeln!(writer, "{}{}", POSTING_INDENT, contra_account)?;
+        Ok(())
+    }
+}
+
+/// Beancount date (`YYYY-MM-DD`)
+fn date(date: DateTime<FixedOffset>) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Beancount string literal on a single line, with quotes and backslashes escaped
+fn quote(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::fixtures;
+    use crate::text;
+    use crate::{Camt053Statement, EntryStatus, TransactionType};
+
+    fn statement() -> Camt053Statement {
+        fixtures::statement(vec![fixtures::transaction(
+            25.5,
+            TransactionType::Debit,
+            "Rent \"March\"\nflat 2",
+        )])
+    }
+
+    #[test]
+    fn test_write_beancount_directives() {
+        let mut output = Vec::new();
+        BeancountProfile::default()
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        let ledger = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = ledger.lines().collect();
+
+        assert_eq!(lines[0], "2025-02-28 open Assets:Bank EUR");
+        assert_eq!(lines[3], "2025-02-28 open Equity:Opening-Balances EUR");
+        assert_eq!(
+            lines[5],
+            "2025-02-28 pad Assets:Bank Equity:Opening-Balances"
+        );
+        assert_eq!(lines[6], "2025-03-01 balance Assets:Bank 100.00 EUR");
+        assert_eq!(
+            lines[8],
+            "2025-03-07 * \"ACME GmbH\" \"Rent \\\"March\\\" flat 2\""
+        );
+        assert_eq!(lines[9], "  reference: \"INV-7\"");
+        assert_eq!(lines[10].len(), AMOUNT_COLUMN);
+        assert!(lines[10].ends_with("  -25.50 EUR"));
+        assert_eq!(lines[11], "  Expenses:Unknown");
+        assert_eq!(lines[13], "2025-04-01 balance Assets:Bank 74.50 EUR");
+    }
+
+    #[test]
+    fn test_write_without_open_and_pad() {
+        let profile = BeancountProfile {
+            opening_balance_account: None,
+            open_accounts: false,
+            ..BeancountProfile::default()
+        };
+        let mut output = Vec::new();
+        profile
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        let ledger = String::from_utf8(output).unwrap();
+
+        assert!(ledger.starts_with("2025-03-01 balance Assets:Bank 100.00 EUR\n"));
+        assert!(!ledger.contains(" open ") && !ledger.contains(" pad "));
+    }
+
+    #[test]
+    fn test_pending_entry_is_flagged_and_asserted() {
+        let mut statement = statement();
+        statement.transactions.push(Transaction {
+            status: EntryStatus::Pending,
+            ..Transaction::new(
+                text::parse_date("2025-03-20").unwrap(),
+                50.0,
+                TransactionType::Debit,
+                "Card",
+            )
+        });
+        let mut output = Vec::new();
+        BeancountProfile::default()
+            .write_to(statement.as_view(), &mut output)
+            .unwrap();
+        let ledger = String::from_utf8(output).unwrap();
+
+        assert!(ledger.contains("2025-03-20 ! \"Card\"\n"));
+        assert!(ledger.ends_with("2025-04-01 balance Assets:Bank 24.50 EUR\n"));
+    }
+}
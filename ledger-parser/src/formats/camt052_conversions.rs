@@ -0,0 +1,41 @@
+//! Type conversions from Camt052 to other formats
+//!
+//! Implements the `From` trait so intra-day reports can be written out in any
+//! writable format (CAMT.053, CAMT.054, CSV, JSON, MT940, QIF). Reports are read-only, so there
+//! are no conversions into CAMT.052.
+
+use crate::{
+    Camt052Statement, Camt053Statement, Camt054Statement, CsvStatement, JsonStatement,
+    Mt940Statement, QifStatement, StatementInfo,
+};
+
+/// Implements `From<Camt052Statement>` as a direct field-by-field conversion,
+/// since all structures share the same data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_from_camt052 {
+    ($target:ident $(, $field:ident: $value:expr)*) => {
+        impl From<Camt052Statement> for $target {
+            fn from(camt: Camt052Statement) -> Self {
+                $target {
+                    account_number: camt.account_number,
+                    currency: camt.currency,
+                    opening_balance: camt.opening_balance,
+                    opening_date: camt.opening_date,
+                    opening_indicator: camt.opening_indicator,
+                    closing_balance: camt.closing_balance,
+                    closing_date: camt.closing_date,
+                    closing_indicator: camt.closing_indicator,
+                    transactions: camt.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+    };
+}
+
+impl_from_camt052!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_from_camt052!(Camt054Statement);
+impl_from_camt052!(CsvStatement, info: StatementInfo::default());
+impl_from_camt052!(JsonStatement);
+impl_from_camt052!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_from_camt052!(QifStatement);
@@ -0,0 +1,172 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+
+use crate::diagnostics::Diagnostics;
+use crate::error::ParseError;
+use crate::formats::camt053_statement::{parse_document, CamtMessage};
+use crate::formats::utils;
+use crate::model::{BalanceType, Transaction};
+
+/// ISO 20022 CAMT.052 intra-day account report (`BkToCstmrAcctRpt`)
+///
+/// Parsed with the same CAMT infrastructure as [`Camt053Statement`](crate::Camt053Statement);
+/// reports differ mainly in their `<Rpt>` element and in often carrying only
+/// interim booked (`ITBD`) balances. The last `ITBD` is used when no `CLBD`
+/// is present, and a missing opening balance is derived from the closing
+/// balance and the reported entries.
+///
+/// Reports are read-only; convert into another format to write them out.
+/// Fields are identical to Mt940/Camt053/CsvStatement for seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Camt052Statement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+impl Camt052Statement {
+    /// Parse a CAMT.052 report from any Read source (file, stdin, buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Camt052Error` if the input is not well-formed XML or
+    /// is not a `BkToCstmrAcctRpt` document, and `ParseError::MissingField` if
+    /// the account, currency or balances cannot be determined.
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse a CAMT.052 report from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Camt052Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse CAMT.052 from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        let (message, camt) =
+            parse_document(content, &mut Diagnostics::default()).map_err(|err| {
+                err.map_located(|err| match err {
+                    ParseError::Camt053Error(msg) => ParseError::Camt052Error(msg),
+                    other => other,
+                })
+            })?;
+        if message != Some(CamtMessage::Report) {
+            return Err(ParseError::Camt052Error(
+                "Expected a BkToCstmrAcctRpt document".into(),
+            ));
+        }
+
+        let statement = Camt052Statement {
+            account_number: camt.account_number,
+            currency: camt.currency,
+            opening_balance: camt.opening_balance,
+            opening_date: camt.opening_date,
+            opening_indicator: camt.opening_indicator,
+            closing_balance: camt.closing_balance,
+            closing_date: camt.closing_date,
+            closing_indicator: camt.closing_indicator,
+            transactions: camt.transactions,
+        };
+        trace_statement!("parsed statement", "camt052", statement);
+
+        Ok(statement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TransactionType;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.052.001.02">
+    <BkToCstmrAcctRpt>
+        <GrpHdr><MsgId>RPT-1</MsgId><CreDtTm>2025-03-07T11:30:00</CreDtTm></GrpHdr>
+        <Rpt>
+            <Id>RPT-1-1</Id>
+            <Acct>
+                <Id><IBAN>DE89370400440532013000</IBAN></Id>
+                <Ccy>EUR</Ccy>
+            </Acct>
+            <Bal>
+                <Tp><CdOrPrtry><Cd>ITBD</Cd></CdOrPrtry></Tp>
+                <Amt Ccy="EUR">1150.00</Amt>
+                <CdtDbtInd>CRDT</CdtDbtInd>
+                <Dt><DtTm>2025-03-07T11:30:00</DtTm></Dt>
+            </Bal>
+            <Ntry>
+                <Amt Ccy="EUR">200.00</Amt>
+                <CdtDbtInd>CRDT</CdtDbtInd>
+                <Sts>BOOK</Sts>
+                <BookgDt><Dt>2025-03-07</Dt></BookgDt>
+                <NtryDtls><TxDtls>
+                    <Refs><TxId>TX-1</TxId></Refs>
+                    <RmtInf><Ustrd>Invoice 42</Ustrd></RmtInf>
+                </TxDtls></NtryDtls>
+            </Ntry>
+            <Ntry>
+                <Amt Ccy="EUR">50.00</Amt>
+                <CdtDbtInd>DBIT</CdtDbtInd>
+                <Sts>BOOK</Sts>
+                <BookgDt><Dt>2025-03-07</Dt></BookgDt>
+            </Ntry>
+        </Rpt>
+    </BkToCstmrAcctRpt>
+</Document>"#;
+
+    #[test]
+    fn test_parse_camt052_with_interim_balance() {
+        let statement = Camt052Statement::from_read(&mut REPORT.as_bytes()).unwrap();
+
+        assert_eq!(statement.account_number, "DE89370400440532013000");
+        assert_eq!(statement.currency, "EUR");
+        assert_eq!(statement.closing_balance, 1150.0);
+        assert_eq!(statement.closing_indicator, BalanceType::Credit);
+        // Opening derived from ITBD minus the reported entries
+        assert_eq!(statement.opening_balance, 1000.0);
+        assert_eq!(statement.opening_indicator, BalanceType::Credit);
+        assert_eq!(statement.transactions.len(), 2);
+        assert_eq!(statement.transactions[0].reference.as_deref(), Some("TX-1"));
+        assert_eq!(
+            statement.transactions[1].transaction_type,
+            TransactionType::Debit
+        );
+    }
+
+    #[test]
+    fn test_parse_camt052_rejects_camt053() {
+        let xml = REPORT
+            .replace("BkToCstmrAcctRpt", "BkToCstmrStmt")
+            .replace("Rpt>", "Stmt>");
+        let result = Camt052Statement::from_read(&mut xml.as_bytes());
+        assert!(matches!(result, Err(ParseError::Camt052Error(_))));
+    }
+}
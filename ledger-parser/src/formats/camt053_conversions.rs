@@ -1,9 +1,9 @@
 //! Type conversions from Camt053 to other formats
 //!
 //! Implements the `From` trait to enable idiomatic conversions between CAMT.053
-//! and other format structures (MT940, CSV).
+//! and other format structures (MT940, CSV, QIF).
 
-use crate::{Camt053Statement, CsvStatement, Mt940Statement};
+use crate::{Camt053Statement, CsvStatement, Mt940Statement, QifStatement};
 
 /// Convert CAMT.053 to MT940 format
 ///
@@ -28,6 +28,8 @@ impl From<Camt053Statement> for Mt940Statement {
             closing_date: camt.closing_date,
             closing_indicator: camt.closing_indicator,
             transactions: camt.transactions,
+            balances: camt.balances,
+            info: camt.info,
         }
     }
 }
@@ -55,6 +57,27 @@ impl From<Camt053Statement> for CsvStatement {
             closing_date: camt.closing_date,
             closing_indicator: camt.closing_indicator,
             transactions: camt.transactions,
+            info: camt.info,
+        }
+    }
+}
+
+/// Convert CAMT.053 to QIF format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<Camt053Statement> for QifStatement {
+    fn from(camt053: Camt053Statement) -> Self {
+        QifStatement {
+            account_number: camt053.account_number,
+            currency: camt053.currency,
+            opening_balance: camt053.opening_balance,
+            opening_date: camt053.opening_date,
+            opening_indicator: camt053.opening_indicator,
+            closing_balance: camt053.closing_balance,
+            closing_date: camt053.closing_date,
+            closing_indicator: camt053.closing_indicator,
+            transactions: camt053.transactions,
         }
     }
 }
@@ -2,17 +2,28 @@ mod camt053_const;
 mod camt053_utils;
 mod elements;
 mod parser;
+mod reader;
 mod scratch;
 mod writer;
 
+use elements::ElementName;
 use parser::CamtParser;
+pub use reader::Camt053Reader;
 
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use quick_xml::events::{BytesRef, Event};
 use serde::{Deserialize, Serialize};
-use std::io::{Read, Write};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use strum_macros::{Display, EnumString};
 
-use crate::error::ParseError;
-use crate::model::{BalanceType, Transaction};
+use crate::cancel::is_cancellation;
+use crate::diagnostics::{Diagnostics, ParseStats, ParseWarning};
+use crate::error::{ErrorLocation, ParseError};
+use crate::formats::{paging, utils};
+use crate::ids::SharedIdGenerator;
+use crate::model::{Balance, BalanceType, StatementHeader, StatementInfo, Transaction};
+use crate::quirks::ParseOptions;
 
 /// ISO 20022 CAMT.053 XML structure
 ///
@@ -38,6 +49,105 @@ pub struct Camt053Statement {
     pub closing_indicator: BalanceType,
     /// List of transactions in chronological order
     pub transactions: Vec<Transaction>,
+    /// Available, forward and interim balances reported beside opening and closing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub balances: Vec<Balance>,
+    /// Bank-proprietary XML of the document's `<SplmtryData>` blocks, kept verbatim
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supplementary_data: Vec<String>,
+    /// Statement identification, sequence numbers and period
+    #[serde(default, skip_serializing_if = "StatementInfo::is_empty")]
+    pub info: StatementInfo,
+}
+
+/// Options controlling how [`Camt053Statement::write_to_with`] renders output.
+///
+/// `Default` reproduces the output of [`Camt053Statement::write_to`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Camt053WriteOptions {
+    /// Maximum number of `<Ntry>` elements per `<Stmt>` page; `None` writes a single page
+    pub max_entries_per_page: Option<usize>,
+    /// Compliance profile the document is written for
+    pub profile: Camt053Profile,
+    /// Creation timestamp (`CreDtTm`) for profiles that require one; `None` uses the current time
+    pub created_at: Option<NaiveDateTime>,
+    /// Source of `MsgId` and `Stmt/Id` for profiles that write them; `None` derives them
+    /// from closing date and account
+    #[serde(skip)]
+    pub id_generator: Option<SharedIdGenerator>,
+    /// Write entry booking dates as `<DtTm>` with time and offset instead of `<Dt>`, keeping
+    /// the intraday order of camt.052-style content
+    pub booking_date_time: bool,
+    /// Write the document on a single line instead of indented by two spaces
+    #[serde(default)]
+    pub compact: bool,
+    /// Write the `<GrpHdr>` with `MsgId` and `CreDtTm`, and the statement `Id`,
+    /// `ElctrncSeqNb` and `CreDtTm`; `None` writes them, `Some(false)` keeps the
+    /// minimal document that only carries what the statement holds
+    #[serde(default)]
+    pub group_header: Option<bool>,
+    /// Message version the document is written in, which sets its namespace
+    #[serde(default)]
+    pub version: Camt053Version,
+    /// Write the `<Sts>` of every entry (`Some(true)`) or of none (`Some(false)`, so
+    /// pending entries read back as booked); `None` writes it where the profile
+    /// requires it and for entries that are not booked
+    #[serde(default)]
+    pub entry_status: Option<bool>,
+    /// Number every entry with `<NtryRef>` (`Some(true)`) or none (`Some(false)`);
+    /// `None` numbers the entries that have a reference. Without one, readers take the
+    /// number for the reference
+    #[serde(default)]
+    pub entry_refs: Option<bool>,
+}
+
+/// Version of the CAMT message schema the writer targets.
+///
+/// The version selects the document namespace and the few structures that
+/// differ between the versions written, such as the `<Rcrd>` blocks of
+/// charges. Parses from and displays as `001.02` or `001.04`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+pub enum Camt053Version {
+    /// `camt.053.001.02` (and `camt.052`/`camt.054.001.02`), the historical output
+    #[default]
+    #[strum(serialize = "001.02")]
+    V02,
+    /// `camt.053.001.04` (and `camt.052`/`camt.054.001.04`)
+    #[strum(serialize = "001.04")]
+    V04,
+}
+
+/// Compliance profile of the CAMT.053 writer.
+///
+/// Receiving software often validates against a national rulebook rather than
+/// just the ISO schema. Parses from and displays as `standard` or `dk`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Camt053Profile {
+    /// Schema-valid ISO 20022 document with group header and statement identification
+    #[default]
+    Standard,
+    /// Deutsche Kreditwirtschaft rules (DFÜ-Abkommen, Anlage 3) for German receivers:
+    /// group header with message id and pagination, statement id, electronic sequence
+    /// number and creation time, `PRCD` opening balance, booked status, mandatory value
+    /// date, `BkTxCd` with DK-issued GVC code, `EndToEndId` (`NOTPROVIDED` if unknown),
+    /// and names/remittance text cut to the DK field lengths.
+    Dk,
+}
+
+/// Root message of a CAMT bank-to-customer document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CamtMessage {
+    /// `BkToCstmrStmt` (CAMT.053 end-of-day statement)
+    Statement,
+    /// `BkToCstmrAcctRpt` (CAMT.052 intra-day report)
+    Report,
+    /// `BkToCstmrDbtCdtNtfctn` (CAMT.054 debit/credit notification)
+    Notification,
 }
 
 impl Camt053Statement {
@@ -47,7 +157,9 @@ impl Camt053Statement {
     /// balances (OPBD/CLBD types), and transaction entries from ISO 20022 XML.
     ///
     /// # Errors
-    /// Returns `ParseError::Camt053Error` if the XML structure is invalid.
+    /// Returns `ParseError::Camt053Error` if the XML structure is invalid or the
+    /// document holds several statements; read those with
+    /// [`Camt053Statement::parse_all`].
     ///
     /// # Example
     /// ```no_run
@@ -60,53 +172,143 @@ impl Camt053Statement {
         let mut content = String::new();
         reader.read_to_string(&mut content)?;
 
-        if content.trim().is_empty() {
-            return Err(ParseError::Camt053Error("Empty input".into()));
-        }
+        Self::from_content(&content)
+    }
 
-        // Fix non-breaking spaces in XML attributes (c2 a0 bytes)
-        let content = content.replace("\u{00a0}", " ");
-
-        let mut xml_reader = quick_xml::Reader::from_str(&content);
-        xml_reader.config_mut().trim_text(true);
-
-        let mut parser = CamtParser::default();
-        let mut buf = Vec::new();
-
-        loop {
-            match xml_reader.read_event_into(&mut buf) {
-                Ok(quick_xml::events::Event::Start(e)) => parser.handle_start(&e)?,
-                Ok(quick_xml::events::Event::End(e)) => parser.handle_end(&e)?,
-                Ok(quick_xml::events::Event::Text(e)) => {
-                    let bytes = e.as_ref();
-                    if !bytes.is_empty() {
-                        let decoded = String::from_utf8_lossy(bytes);
-                        let trimmed = decoded.trim();
-                        if !trimmed.is_empty() {
-                            parser.handle_text(trimmed)?;
-                        }
-                    }
-                }
-                Ok(quick_xml::events::Event::CData(e)) => {
-                    let text = String::from_utf8_lossy(e.as_ref());
-                    let trimmed = text.trim();
-                    if !trimmed.is_empty() {
-                        parser.handle_text(trimmed)?;
-                    }
-                }
-                Ok(quick_xml::events::Event::Eof) => break,
-                Err(e) => return Err(ParseError::Camt053Error(format!("XML parse error: {}", e))),
-                _ => {}
-            }
-            buf.clear();
-        }
+    /// Parse CAMT.053 with explicit [`ParseOptions`], returning the statement
+    /// with a [`ParseWarning`] for every entry skipped.
+    ///
+    /// An `<Ntry>` without a valid amount, credit/debit indicator or booking
+    /// date cannot become a transaction: leniently it is left out, with
+    /// `options.strict` it fails the parse. Bank quirks do not apply to CAMT.
+    ///
+    /// # Errors
+    /// Same as [`Camt053Statement::from_read`], and in strict mode the error of
+    /// the first such entry, located at its `<Ntry>`.
+    pub fn from_read_with_options<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        Self::from_read_with_stats(reader, options)
+            .map(|(statement, stats)| (statement, stats.into_warnings()))
+    }
+
+    /// Parse CAMT.053 with explicit [`ParseOptions`], as
+    /// [`Camt053Statement::from_read_with_options`], returning how many
+    /// `<Ntry>` entries were read, parsed and skipped.
+    ///
+    /// # Errors
+    /// Same as [`Camt053Statement::from_read_with_options`].
+    pub fn from_read_with_stats<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, ParseStats), ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut diagnostics = Diagnostics::new(options.strict);
+        let statement = Self::from_content_with(&content, &mut diagnostics)?;
+        Ok((statement, diagnostics.into_stats()))
+    }
+
+    /// Parse every statement of a CAMT.053 document, one per `<Stmt>` element.
+    ///
+    /// Banks put the statements of several accounts (or days) into one
+    /// `BkToCstmrStmt` message. Pages of a paginated statement (`<StmtPgntn>`
+    /// with `LastPgInd` false) are merged into a single statement, as in
+    /// [`Camt053Statement::from_read`].
+    ///
+    /// # Errors
+    /// Returns the first error of any statement, with the same variants as
+    /// [`Camt053Statement::from_read`].
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::Camt053Statement;
+    ///
+    /// let stmt = |iban: &str| format!(
+    ///     "<Stmt><Acct><Id><IBAN>{iban}</IBAN></Id><Ccy>EUR</Ccy></Acct>\
+    ///      <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy=\"EUR\">1.00</Amt>\
+    ///      <CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-01-01</Dt></Dt></Bal>\
+    ///      <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy=\"EUR\">1.00</Amt>\
+    ///      <CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-01-01</Dt></Dt></Bal></Stmt>");
+    /// let xml = format!(
+    ///     "<Document><BkToCstmrStmt>{}{}</BkToCstmrStmt></Document>",
+    ///     stmt("DE02120300000000202051"),
+    ///     stmt("DE89370400440532013000")
+    /// );
+    /// let statements = Camt053Statement::parse_all(&mut xml.as_bytes()).unwrap();
+    /// assert_eq!(statements.len(), 2);
+    /// assert_eq!(statements[1].account_number, "DE89370400440532013000");
+    /// ```
+    pub fn parse_all<R: Read>(reader: &mut R) -> Result<Vec<Self>, ParseError> {
+        Self::parse_all_with_options(reader, &ParseOptions::default())
+            .map(|(statements, _)| statements)
+    }
+
+    /// Parse every statement of a CAMT.053 document with explicit
+    /// [`ParseOptions`], as [`Camt053Statement::parse_all`], returning the
+    /// warnings of all of them as [`Camt053Statement::from_read_with_options`] does.
+    ///
+    /// # Errors
+    /// Same as [`Camt053Statement::parse_all`], and in strict mode the error of
+    /// the first entry that cannot become a transaction.
+    pub fn parse_all_with_options<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Vec<Self>, Vec<ParseWarning>), ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut diagnostics = Diagnostics::new(options.strict);
+        let (_, statements) = parse_document_all(&content, &mut diagnostics)?;
+        Ok((statements, diagnostics.into_warnings()))
+    }
+
+    /// Parse CAMT.053 from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory, which matters for very large statement files.
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Camt053Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
 
-        parser.build_statement()
+    /// Read only the account, currency and balances of a CAMT.053 document.
+    ///
+    /// Balances precede the entries in the schema, so reading stops at the first
+    /// `<Ntry>` without parsing or even reading the rest of the input. Unlike
+    /// [`Camt053Statement::from_read`], balances are not derived from entries:
+    /// documents without `OPBD`/`PRCD` and `CLBD` balances are rejected.
+    ///
+    /// # Errors
+    /// Returns `ParseError::MissingField` if the account or a balance is missing,
+    /// `ParseError::Camt053Error` for malformed XML before the first entry, and
+    /// `ParseError::IoError` if reading fails.
+    pub fn peek_header<R: Read>(reader: &mut R) -> Result<StatementHeader, ParseError> {
+        parse_header(reader)
+    }
+
+    /// Parse CAMT.053 from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        Self::from_content_with(content, &mut Diagnostics::default())
+    }
+
+    /// Parse CAMT.053 from already loaded content, reporting skipped entries to `diagnostics`
+    fn from_content_with(content: &str, diagnostics: &mut Diagnostics) -> Result<Self, ParseError> {
+        let (_, statement) = parse_document(content, diagnostics)?;
+        trace_statement!("parsed statement", "camt053", statement);
+
+        Ok(statement)
     }
 
     /// Write CAMT.053 to any destination implementing Write
     ///
-    /// Generates ISO 20022 CAMT.053 XML using `quick-xml` writer.
+    /// Generates ISO 20022 CAMT.053 XML using `quick-xml` writer. The XML writer
+    /// emits one small write per event, so the sink is buffered internally.
     ///
     /// # Errors
     /// Returns `ParseError::Camt053Error` if XML generation fails.
@@ -127,20 +329,342 @@ impl Camt053Statement {
     ///     closing_date: DateTime::parse_from_rfc3339("2025-01-31T00:00:00+00:00").unwrap(),
     ///     closing_indicator: BalanceType::Credit,
     ///     transactions: vec![],
+    ///     supplementary_data: vec![],
+    ///     balances: vec![],
+    ///     info: Default::default(),
     /// };
     /// let mut output = Vec::new();
     /// statement.write_to(&mut output).unwrap();
     /// ```
     pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
-        writer::CamtWriter::new(self, writer).write()
+        self.write_to_with(writer, &Camt053WriteOptions::default())
+    }
+
+    /// Write CAMT.053 using explicit writer options.
+    ///
+    /// When `max_entries_per_page` is set, the statement is emitted as several
+    /// `<Stmt>` pages inside one document. Intermediate balances use the `ITBD`
+    /// (interim booked) type; only the first page opens with `OPBD` and only the
+    /// last closes with `CLBD`. Version 04 pages carry `<StmtPgntn>`; version 02
+    /// has no such element, so its pages are numbered in the generated
+    /// `ElctrncSeqNb` and, instead of repeating the statement's own id, in an `Id`
+    /// suffix `-<page>`.
+    ///
+    /// `profile` selects the mandatory elements and orderings of a national
+    /// rulebook (see [`Camt053Profile`]).
+    ///
+    /// # Errors
+    /// Returns `ParseError::Camt053Error` if XML generation fails.
+    pub fn write_to_with<W: Write>(
+        &self,
+        writer: &mut W,
+        options: &Camt053WriteOptions,
+    ) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "camt053", self);
+        write_document(self, writer, options, CamtMessage::Statement)
+    }
+
+    /// Split the statement into standalone statements of at most `max_transactions` entries.
+    ///
+    /// Intermediate opening/closing balances are recomputed from the running total,
+    /// so every part is internally consistent and can be written to its own file.
+    /// Additional balances go with the last part.
+    pub fn split_by_transaction_count(&self, max_transactions: usize) -> Vec<Camt053Statement> {
+        self.part_statements(paging::split(
+            &self.balances(),
+            &self.transactions,
+            max_transactions,
+        ))
+    }
+
+    /// Split the statement into standalone statements, one per calendar month of the
+    /// booking dates, in month order.
+    ///
+    /// Balances are recomputed as in
+    /// [`split_by_transaction_count`](Self::split_by_transaction_count): each month
+    /// opens with the closing balance of the month before it, the first with the
+    /// statement's opening balance and the last closes with its closing balance.
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::{Camt053Statement, Mt940Statement};
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:200131D25,00NTRFNONREF\n:86:Lunch\n\
+    ///             :61:200203C40,00NTRFNONREF\n:86:Refund\n:62F:C200203EUR115,00\n";
+    /// let statement = Camt053Statement::from(Mt940Statement::from_read(&mut data.as_bytes()).unwrap());
+    ///
+    /// let months = statement.split_by_month();
+    /// assert_eq!(months.len(), 2);
+    /// assert_eq!((months[0].opening_balance, months[0].closing_balance), (100.0, 75.0));
+    /// assert_eq!((months[1].opening_balance, months[1].closing_balance), (75.0, 115.0));
+    /// ```
+    pub fn split_by_month(&self) -> Vec<Camt053Statement> {
+        self.part_statements(paging::split_by_month(&self.balances(), &self.transactions))
+    }
+
+    /// Statements of `parts` of this statement, as the paging helper splits them
+    fn part_statements(
+        &self,
+        parts: Vec<(paging::Balances, Vec<Transaction>)>,
+    ) -> Vec<Camt053Statement> {
+        let last = parts.len().saturating_sub(1);
+        parts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (balances, transactions))| Camt053Statement {
+                account_number: self.account_number.clone(),
+                currency: self.currency.clone(),
+                opening_balance: balances.opening_balance,
+                opening_date: balances.opening_date,
+                opening_indicator: balances.opening_indicator,
+                closing_balance: balances.closing_balance,
+                closing_date: balances.closing_date,
+                closing_indicator: balances.closing_indicator,
+                transactions,
+                supplementary_data: Vec::new(),
+                info: self.info.clone(),
+                // Available and forward balances describe the end of the statement
+                balances: if index == last {
+                    self.balances.clone()
+                } else {
+                    Vec::new()
+                },
+            })
+            .collect()
+    }
+
+    /// Statement balances in the shape used by the paging helper
+    fn balances(&self) -> paging::Balances {
+        paging::Balances {
+            opening_balance: self.opening_balance,
+            opening_indicator: self.opening_indicator.clone(),
+            opening_date: self.opening_date,
+            closing_balance: self.closing_balance,
+            closing_indicator: self.closing_indicator.clone(),
+            closing_date: self.closing_date,
+        }
     }
 }
 
+/// Parse a CAMT bank-to-customer document (`BkToCstmrStmt`, `BkToCstmrAcctRpt`
+/// or `BkToCstmrDbtCdtNtfctn`).
+///
+/// Statements, intra-day reports and notifications share their account, balance
+/// and entry structure, so all are read by the same parser. Returns the message type of
+/// the document root alongside the statement fields.
+///
+/// Documents with several statements are rejected rather than merged, since
+/// balances and entries of different accounts cannot be combined.
+pub(crate) fn parse_document(
+    content: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<(Option<CamtMessage>, Camt053Statement), ParseError> {
+    let (message, mut statements) = parse_document_all(content, diagnostics)?;
+    if statements.len() > 1 {
+        return Err(ParseError::Camt053Error(format!(
+            "Document contains {} statements; read them with parse_all",
+            statements.len()
+        )));
+    }
+    let statement = statements
+        .pop()
+        .ok_or_else(|| ParseError::Camt053Error("No statement in input".into()))?;
+    Ok((message, statement))
+}
+
+/// Parse a CAMT bank-to-customer document into one statement per `<Stmt>`,
+/// `<Rpt>` or `<Ntfctn>` block
+pub(crate) fn parse_document_all(
+    content: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<(Option<CamtMessage>, Vec<Camt053Statement>), ParseError> {
+    if content.trim().is_empty() {
+        return Err(ParseError::Camt053Error("Empty input".into()));
+    }
+
+    // Fix non-breaking spaces in XML attributes (c2 a0 bytes)
+    let content = content.replace("\u{00a0}", " ");
+
+    let mut xml_reader = quick_xml::Reader::from_str(&content);
+
+    let mut parser = CamtParser::default();
+    let mut buf = Vec::new();
+    // Start offset and nesting depth of the `<SplmtryData>` block being captured
+    let mut supplementary: Option<(usize, usize)> = None;
+    let is_supplementary = |name: &[u8]| {
+        matches!(
+            ElementName::from_name_bytes(name),
+            Ok(ElementName::SupplementaryData)
+        )
+    };
+
+    loop {
+        let position = xml_reader.buffer_position() as usize;
+        let event = xml_reader.read_event_into(&mut buf).map_err(|e| {
+            ParseError::Camt053Error(format!("XML parse error: {}", e))
+                .at(xml_location(&parser, xml_reader.buffer_position()))
+        })?;
+
+        // Bank-proprietary content is kept as written, and never seen by the parser
+        match (&event, supplementary.as_mut()) {
+            (Event::Start(e), None) if is_supplementary(e.name().as_ref()) => {
+                supplementary = Some((xml_reader.buffer_position() as usize, 0));
+            }
+            (Event::Start(e), Some((_, depth))) if is_supplementary(e.name().as_ref()) => {
+                *depth += 1;
+            }
+            (Event::End(e), Some((start, 0))) if is_supplementary(e.name().as_ref()) => {
+                let xml = content.get(*start..position).unwrap_or_default().trim();
+                parser.add_supplementary_data(xml.to_string());
+                supplementary = None;
+            }
+            (Event::End(e), Some((_, depth))) if is_supplementary(e.name().as_ref()) => {
+                *depth -= 1;
+            }
+            (Event::Eof, _) => break,
+            (_, Some(_)) => {}
+            (_, None) => {
+                let offset = xml_reader.buffer_position();
+                let more = feed_event(&mut parser, event)
+                    .map_err(|e| e.at(xml_location(&parser, offset)))?;
+                for (error, path) in parser.drain_skipped() {
+                    diagnostics.skip(error.at(ErrorLocation::Xml { path, offset }))?;
+                }
+                if !more {
+                    break;
+                }
+            }
+        }
+        buf.clear();
+    }
+
+    let message = parser.message();
+    let statements = parser.into_statements()?;
+    diagnostics.parsed(statements.iter().map(|s| s.transactions.len()).sum());
+    Ok((message, statements))
+}
+
+/// Read account and balances of a CAMT document, stopping at the first `<Ntry>`
+pub(crate) fn parse_header<R: Read>(reader: &mut R) -> Result<StatementHeader, ParseError> {
+    let mut xml_reader = quick_xml::Reader::from_reader(BufReader::new(reader));
+
+    let mut parser = CamtParser::default();
+    let mut buf = Vec::new();
+
+    while !parser.in_entries() {
+        let event = xml_reader
+            .read_event_into(&mut buf)
+            .map_err(|e| read_error(e, xml_location(&parser, xml_reader.buffer_position())))?;
+        let offset = xml_reader.buffer_position();
+        if !feed_event(&mut parser, event).map_err(|e| e.at(xml_location(&parser, offset)))? {
+            break;
+        }
+        buf.clear();
+    }
+
+    parser.header()
+}
+
+/// Error of reading XML from a stream: I/O failures (and cancellation) as
+/// such, malformed XML at `location`
+fn read_error(error: quick_xml::Error, location: ErrorLocation) -> ParseError {
+    match error {
+        quick_xml::Error::Io(io) if is_cancellation(&io) => ParseError::Cancelled,
+        quick_xml::Error::Io(io) => ParseError::IoError(io::Error::new(io.kind(), io)),
+        e => ParseError::Camt053Error(format!("XML parse error: {}", e)).at(location),
+    }
+}
+
+/// Where `parser` is in the document, for errors found at reader `offset`
+fn xml_location(parser: &CamtParser, offset: u64) -> ErrorLocation {
+    ErrorLocation::Xml {
+        path: parser.location_path(),
+        offset,
+    }
+}
+
+/// Pass one XML event to `parser`; returns `false` at the end of the document
+fn feed_event(parser: &mut CamtParser, event: Event<'_>) -> Result<bool, ParseError> {
+    match event {
+        Event::Start(e) => {
+            parser.flush_text()?;
+            parser.handle_start(&e)?
+        }
+        Event::End(e) => {
+            parser.flush_text()?;
+            parser.handle_end(&e)?
+        }
+        // An element's text arrives in pieces split at entity references
+        Event::Text(e) => parser.push_text(&String::from_utf8_lossy(e.as_ref())),
+        Event::CData(e) => parser.push_text(&String::from_utf8_lossy(e.as_ref())),
+        Event::GeneralRef(e) => parser.push_text(&resolve_reference(&e)),
+        Event::Eof => {
+            parser.flush_text()?;
+            return Ok(false);
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// Text of an entity or character reference; entities the document declares
+/// itself are kept as written
+fn resolve_reference(reference: &BytesRef<'_>) -> String {
+    if let Ok(Some(character)) = reference.resolve_char_ref() {
+        return character.to_string();
+    }
+    let name = String::from_utf8_lossy(reference.as_ref());
+    match quick_xml::escape::resolve_predefined_entity(&name) {
+        Some(text) => text.to_string(),
+        None => format!("&{};", name),
+    }
+}
+
+/// Write `statement` as a CAMT document of the given message type
+pub(crate) fn write_document<W: Write>(
+    statement: &Camt053Statement,
+    writer: &mut W,
+    options: &Camt053WriteOptions,
+    message: CamtMessage,
+) -> Result<(), ParseError> {
+    let mut buffered = BufWriter::new(writer);
+    let written = writer::CamtWriter::new(statement, &mut buffered, options, message).write();
+    // XML errors only keep the message of a failed write; flushing first lets a
+    // cancelled sink report `ParseError::Cancelled` instead of a CAMT error
+    buffered.flush()?;
+    written
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::formats::utils;
-    use crate::model::{Transaction, TransactionType};
+    use crate::model::{BalanceCode, EntryStatus, RemittanceInfo, Transaction, TransactionType};
+    use crate::text;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_peek_header_stops_before_entries() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Acct><Id><IBAN>DK8030000001234567</IBAN></Id><Ccy>DKK</Ccy></Acct>
+      <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="DKK">1000.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-01-01</Dt></Dt></Bal>
+      <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="DKK">250.00</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd><Dt><Dt>2025-01-31</Dt></Dt></Bal>
+      <Ntry><Amt Ccy="DKK">1.00</Broken>"#;
+
+        let header = Camt053Statement::peek_header(&mut xml.as_bytes()).unwrap();
+        assert_eq!(header.account_number, "DK8030000001234567");
+        assert_eq!(header.currency, "DKK");
+        assert_eq!(header.opening_balance, 1000.0);
+        assert_eq!(header.closing_balance, 250.0);
+        assert_eq!(header.closing_indicator, BalanceType::Debit);
+        // The malformed entry is never read
+        assert!(Camt053Statement::from_read(&mut xml.as_bytes()).is_err());
+    }
 
     #[test]
     fn test_camt053_structure() {
@@ -149,12 +673,15 @@ mod tests {
             account_number: "DK1234567890".into(),
             currency: "DKK".into(),
             opening_balance: 1000.0,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Credit,
             closing_balance: 1500.0,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         assert_eq!(statement.account_number, "DK1234567890");
@@ -170,12 +697,15 @@ mod tests {
             account_number: "DK8030000001234567".into(),
             currency: "DKK".into(),
             opening_balance: 1000.00,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Credit,
             closing_balance: 1500.00,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -205,33 +735,39 @@ mod tests {
             account_number: "DK8030000001234567".into(),
             currency: "DKK".into(),
             opening_balance: 1000.00,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Credit,
             closing_balance: 1591.15,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![
                 Transaction {
-                    booking_date: utils::parse_date("2025-01-15").unwrap(),
-                    value_date: Some("2025-01-15".into()),
-                    amount: 591.15,
-                    transaction_type: TransactionType::Credit,
-                    description: "Payment received".into(),
+                    value_date: NaiveDate::from_ymd_opt(2025, 1, 15),
                     reference: Some("TXN-123".into()),
                     counterparty_name: Some("John Doe".into()),
                     counterparty_account: Some("SE5180000810512345678901".into()),
+                    ..Transaction::new(
+                        text::parse_date("2025-01-15").unwrap(),
+                        591.15,
+                        TransactionType::Credit,
+                        "Payment received",
+                    )
                 },
                 Transaction {
-                    booking_date: utils::parse_date("2025-01-20").unwrap(),
-                    value_date: None,
-                    amount: 250.00,
-                    transaction_type: TransactionType::Debit,
-                    description: "Payment sent".into(),
                     reference: Some("TXN-456".into()),
                     counterparty_name: Some("Jane Smith".into()),
                     counterparty_account: Some("NO9386011117947".into()),
+                    ..Transaction::new(
+                        text::parse_date("2025-01-20").unwrap(),
+                        250.00,
+                        TransactionType::Debit,
+                        "Payment sent",
+                    )
                 },
             ],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -263,21 +799,26 @@ mod tests {
             account_number: "DK8030000001234567".into(),
             currency: "DKK".into(),
             opening_balance: 12345.67,
-            opening_date: utils::parse_date("2025-04-20").unwrap(),
+            opening_date: text::parse_date("2025-04-20").unwrap(),
             opening_indicator: BalanceType::Debit,
             closing_balance: 23456.78,
-            closing_date: utils::parse_date("2025-04-20").unwrap(),
+            closing_date: text::parse_date("2025-04-20").unwrap(),
             closing_indicator: BalanceType::Debit,
             transactions: vec![Transaction {
-                booking_date: utils::parse_date("2025-04-20").unwrap(),
-                value_date: Some("2025-04-20".into()),
-                amount: 591.15,
-                transaction_type: TransactionType::Credit,
-                description: "Payment description".into(),
+                value_date: NaiveDate::from_ymd_opt(2025, 4, 20),
                 reference: Some("3825-0123456789".into()),
                 counterparty_name: Some("Debtor Name".into()),
                 counterparty_account: Some("SE5180000810512345678901".into()),
+                ..Transaction::new(
+                    text::parse_date("2025-04-20").unwrap(),
+                    591.15,
+                    TransactionType::Credit,
+                    "Payment description",
+                )
             }],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         // Write to buffer
@@ -319,6 +860,614 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_round_trip_foreign_currency() {
+        let transaction = Transaction {
+            original_amount: Some(80.0),
+            original_currency: Some("USD".into()),
+            exchange_rate: Some(7.389375),
+            ..Transaction::new(
+                text::parse_date("2025-04-20").unwrap(),
+                591.15,
+                TransactionType::Debit,
+                "Card payment",
+            )
+        };
+        let statement = Camt053Statement {
+            account_number: "DK8030000001234567".into(),
+            currency: "DKK".into(),
+            opening_balance: 1000.0,
+            opening_date: text::parse_date("2025-04-20").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 408.85,
+            closing_date: text::parse_date("2025-04-20").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                transaction.clone(),
+                Transaction {
+                    currency: Some("EUR".into()),
+                    original_amount: None,
+                    original_currency: None,
+                    exchange_rate: None,
+                    ..transaction
+                },
+            ],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let xml = String::from_utf8(buffer).unwrap();
+        assert!(xml.contains("<Amt Ccy=\"USD\">80.00</Amt>"));
+        assert!(xml.contains("<XchgRate>7.389375</XchgRate>"));
+        assert!(xml.contains("<Amt Ccy=\"EUR\">591.15</Amt>"));
+
+        let parsed = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_round_trip_sepa_ids() {
+        let transaction = Transaction {
+            reference: Some("TX-1".into()),
+            end_to_end_id: Some("E2E-2025-0042".into()),
+            mandate_id: Some("MANDATE-7".into()),
+            ..Transaction::new(
+                text::parse_date("2025-04-20").unwrap(),
+                49.99,
+                TransactionType::Debit,
+                "Direct debit",
+            )
+        };
+        let statement = Camt053Statement {
+            account_number: "DE89370400440532013000".into(),
+            currency: "EUR".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-04-20").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 50.01,
+            closing_date: text::parse_date("2025-04-20").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![transaction],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let xml = String::from_utf8(buffer).unwrap();
+        assert!(xml.contains("<MndtId>MANDATE-7</MndtId>"));
+        let parsed = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+
+        let placeholder = xml.replace("E2E-2025-0042", "NOTPROVIDED");
+        let parsed = Camt053Statement::from_read(&mut placeholder.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions[0].end_to_end_id, None);
+    }
+
+    #[test]
+    fn test_entry_status_and_reversal() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+            <Ntry><Amt Ccy="EUR">20.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts><Cd>PDNG</Cd></Sts><BookgDt><Dt>2025-04-21</Dt></BookgDt></Ntry>
+            <Ntry><Amt Ccy="EUR">20.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><RvslInd>true</RvslInd><Sts>BOOK</Sts><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let flags: Vec<_> = statement
+            .transactions
+            .iter()
+            .map(|tx| (tx.status, tx.is_reversal))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                (EntryStatus::Booked, false),
+                (EntryStatus::Pending, false),
+                (EntryStatus::Booked, true),
+            ]
+        );
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_malformed_entry_warns_or_fails() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+            <Ntry><Amt Ccy="EUR">2O.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let (statement, warnings) =
+            Camt053Statement::from_read_with_options(&mut xml.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "unexpected_value");
+        assert!(matches!(
+            &warnings[0].location,
+            Some(ErrorLocation::Xml { path, .. }) if path == "Document/BkToCstmrStmt/Stmt/Ntry"
+        ));
+
+        let strict =
+            Camt053Statement::from_read_with_options(&mut xml.as_bytes(), &ParseOptions::strict());
+        assert!(matches!(
+            strict.map_err(|e| e.without_location().code()),
+            Err("unexpected_value")
+        ));
+    }
+
+    #[test]
+    fn test_booking_date_time_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">30.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><DtTm>2025-04-20T09:15:00</DtTm></BookgDt></Ntry>
+            <Ntry><Amt Ccy="EUR">20.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><DtTm>2025-04-20T16:45:30.5+02:00</DtTm></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let times: Vec<_> = statement
+            .transactions
+            .iter()
+            .map(|tx| tx.booking_date.to_rfc3339())
+            .collect();
+        assert_eq!(
+            times,
+            vec!["2025-04-20T09:15:00+00:00", "2025-04-20T16:45:30.500+02:00"]
+        );
+
+        // Dates only by default, as before
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let xml_output = String::from_utf8(buffer).unwrap();
+        assert!(xml_output.contains("<Dt>2025-04-20</Dt>"));
+        assert!(!xml_output.contains("<DtTm>"));
+
+        let options = Camt053WriteOptions {
+            booking_date_time: true,
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        statement.write_to_with(&mut buffer, &options).unwrap();
+        let xml_output = String::from_utf8(buffer).unwrap();
+        assert!(xml_output.contains("<DtTm>2025-04-20T09:15:00+00:00</DtTm>"));
+        assert!(xml_output.contains("<DtTm>2025-04-20T16:45:30.500+02:00</DtTm>"));
+
+        let parsed = Camt053Statement::from_read(&mut xml_output.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_entity_references_keep_their_text() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <AddtlNtryInf> Lunch &amp; &lt;stuff&gt; at Caf&#233; <![CDATA[<b>]]></AddtlNtryInf></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(
+            statement.transactions[0].description,
+            "Lunch & <stuff> at Café <b>"
+        );
+    }
+
+    #[test]
+    fn test_statement_info_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Id>STMT-2025-042</Id><StmtPgntn><PgNb>2</PgNb><LastPgInd>true</LastPgInd></StmtPgntn>
+            <ElctrncSeqNb>42</ElctrncSeqNb><LglSeqNb>7</LglSeqNb><CreDtTm>2025-04-21T06:00:00+02:00</CreDtTm>
+            <FrToDt><FrDtTm>2025-04-20T00:00:00+02:00</FrDtTm><ToDtTm>2025-04-20T23:59:59+02:00</ToDtTm></FrToDt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy><Ownr><Nm>ACME NV</Nm></Ownr>
+                <Svcr><FinInstnId><BICFI>GKCCBEBB</BICFI></FinInstnId></Svcr></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let info = &statement.info;
+        assert_eq!(info.statement_id.as_deref(), Some("STMT-2025-042"));
+        assert_eq!(
+            (
+                info.sequence_number,
+                info.legal_sequence_number,
+                info.page_number
+            ),
+            (Some(42), Some(7), Some(2))
+        );
+        assert_eq!(
+            info.created_at.map(|date| date.to_rfc3339()).as_deref(),
+            Some("2025-04-21T06:00:00+02:00")
+        );
+        assert!(info.from_date.is_some() && info.to_date.is_some());
+        assert_eq!(info.account_owner.as_deref(), Some("ACME NV"));
+        assert_eq!(info.servicer_bic.as_deref(), Some("GKCCBEBB"));
+
+        // The page number needs `<StmtPgntn>`, which version 02 lacks
+        let options = Camt053WriteOptions {
+            version: Camt053Version::V04,
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        statement.write_to_with(&mut buffer, &options).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.info, statement.info);
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.info.page_number, None);
+        assert_eq!(parsed.info.statement_id, statement.info.statement_id);
+
+        // MT940 keeps the id and `:28C:` numbers across the round trip
+        let mt940: crate::Mt940Statement = statement.into();
+        let mut buffer = Vec::new();
+        mt940.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains(":20:STMT-2025-042\n"));
+        assert!(text.contains(":28C:42/2\n"));
+        let back: Camt053Statement = crate::Mt940Statement::from_read(&mut text.as_bytes())
+            .unwrap()
+            .into();
+        assert_eq!(back.info.statement_id.as_deref(), Some("STMT-2025-042"));
+        assert_eq!(
+            (back.info.sequence_number, back.info.page_number),
+            (Some(42), Some(2))
+        );
+    }
+
+    #[test]
+    fn test_additional_balances_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">40.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>FWAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">15.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Dt><Dt>2025-04-22</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>ITAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">45.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let codes: Vec<BalanceCode> = statement.balances.iter().map(|b| b.code).collect();
+        assert_eq!(
+            codes,
+            [
+                BalanceCode::ClosingAvailable,
+                BalanceCode::ForwardAvailable,
+                BalanceCode::InterimAvailable
+            ]
+        );
+        assert_eq!(statement.balances[1].amount, 15.0);
+        assert_eq!(statement.balances[1].indicator, BalanceType::Debit);
+        assert_eq!(statement.closing_balance, 50.0);
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.balances, statement.balances);
+
+        // MT940 has tags for the available balances only
+        let mt940: crate::Mt940Statement = statement.clone().into();
+        let mut buffer = Vec::new();
+        mt940.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains(":62F:C250420EUR50,00\n:64:C250420EUR40,00\n:65:D250422EUR15,00\n"));
+        let back = crate::Mt940Statement::from_read(&mut text.as_bytes()).unwrap();
+        assert_eq!(back.balances, statement.balances[..2]);
+    }
+
+    #[test]
+    fn test_batch_entry_keeps_each_payment() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">70.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">30.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <NtryDtls><Btch><NbOfTxs>2</NbOfTxs></Btch>
+                    <TxDtls><Refs><EndToEndId>SAL-1</EndToEndId></Refs>
+                        <AmtDtls><TxAmt><Amt Ccy="EUR">10.00</Amt></TxAmt></AmtDtls>
+                        <RltdPties><Cdtr><Nm>Alice</Nm></Cdtr><CdtrAcct><Id><IBAN>DE89370400440532013000</IBAN></Id></CdtrAcct></RltdPties>
+                        <RmtInf><Ustrd>Salary April</Ustrd></RmtInf></TxDtls>
+                    <TxDtls><Refs><EndToEndId>SAL-2</EndToEndId></Refs>
+                        <AmtDtls><TxAmt><Amt Ccy="EUR">20.00</Amt></TxAmt></AmtDtls>
+                        <RltdPties><Cdtr><Nm>Bob</Nm></Cdtr></RltdPties>
+                        <RmtInf><Ustrd>Salary April</Ustrd></RmtInf></TxDtls>
+                </NtryDtls></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        let details = &statement.transactions[0].details;
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].amount, Some(10.0));
+        assert_eq!(details[0].end_to_end_id.as_deref(), Some("SAL-1"));
+        assert_eq!(details[0].counterparty_name.as_deref(), Some("Alice"));
+        assert_eq!(
+            details[0].counterparty_account.as_deref(),
+            Some("DE89370400440532013000")
+        );
+        assert_eq!(details[1].counterparty_name.as_deref(), Some("Bob"));
+        assert_eq!(details[1].description, "Salary April");
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("<NbOfTxs>2</NbOfTxs>"));
+        let parsed = Camt053Statement::from_read(&mut text.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+
+        // JSON keeps the payments in both of its forms
+        let json: crate::JsonStatement = statement.clone().into();
+        let mut buffer = Vec::new();
+        json.write_canonical(&mut buffer).unwrap();
+        let back = crate::JsonStatement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(back.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_charges_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.08"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">196.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">96.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <Chrgs><TtlChrgsAndTaxAmt Ccy="EUR">4.00</TtlChrgsAndTaxAmt>
+                    <Rcrd><Amt Ccy="EUR">3.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><ChrgInclInd>true</ChrgInclInd><Tp><Cd>COMM</Cd></Tp><Br>CRED</Br></Rcrd>
+                    <Rcrd><Amt Ccy="USD">1.10</Amt></Rcrd>
+                </Chrgs>
+                <NtryDtls><TxDtls>
+                    <AmtDtls><InstdAmt><Amt Ccy="EUR">100.00</Amt></InstdAmt></AmtDtls>
+                    <Chrgs><Rcrd><Amt Ccy="EUR">4.00</Amt></Rcrd></Chrgs>
+                    <RmtInf><Ustrd>Invoice 12</Ustrd></RmtInf>
+                </TxDtls></NtryDtls></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let transaction = &statement.transactions[0];
+        assert_eq!(transaction.description, "Invoice 12");
+        assert_eq!(transaction.original_amount, Some(100.0));
+        // The entry's own charges win over the total repeated in its details
+        assert_eq!(
+            transaction.charges,
+            vec![
+                crate::Charge {
+                    amount: 3.0,
+                    currency: Some("EUR".into()),
+                    charge_type: TransactionType::Debit,
+                    code: Some("COMM".into()),
+                    bearer: Some("CRED".into()),
+                },
+                crate::Charge {
+                    amount: 1.1,
+                    currency: Some("USD".into()),
+                    charge_type: TransactionType::Debit,
+                    code: None,
+                    bearer: None,
+                },
+            ]
+        );
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("<Chrgs>"));
+        let parsed = Camt053Statement::from_read(&mut text.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+
+        let json: crate::JsonStatement = statement.clone().into();
+        let mut buffer = Vec::new();
+        json.write_canonical(&mut buffer).unwrap();
+        let back = crate::JsonStatement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(back.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_non_iban_accounts_written_as_other_id() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt>
+            <Acct><Id><Othr><Id>40702810900000012345</Id></Othr></Id><Ccy>RUB</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="RUB">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="RUB">500.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="RUB">500.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <NtryDtls><TxDtls><RltdPties><DbtrAcct><Id><Othr><Id>40817810099910004312</Id></Othr></Id></DbtrAcct></RltdPties>
+                <RmtInf><Ustrd>Оплата</Ustrd></RmtInf></TxDtls></NtryDtls></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+        let mut statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(statement.account_number, "40702810900000012345");
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(!text.contains("<IBAN>"));
+        assert_eq!(text.matches("<Othr>").count(), 2);
+        let parsed = Camt053Statement::from_read(&mut text.as_bytes()).unwrap();
+        assert_eq!(parsed.account_number, statement.account_number);
+        assert_eq!(parsed.transactions, statement.transactions);
+
+        statement.transactions[0].counterparty_account = Some("DE89370400440532013000".into());
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("<IBAN>DE89370400440532013000</IBAN>"));
+    }
+
+    #[test]
+    fn test_write_options_version_and_layout() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">196.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">96.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <Chrgs><Amt Ccy="EUR">3.00</Amt><CdtDbtInd>DBIT</CdtDbtInd></Chrgs>
+                <Chrgs><Amt Ccy="EUR">1.00</Amt><CdtDbtInd>DBIT</CdtDbtInd></Chrgs>
+                <NtryDtls><TxDtls><RmtInf><Ustrd>Invoice 12</Ustrd></RmtInf></TxDtls></NtryDtls></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+
+        // Identification is generated by default, so the output is schema-valid
+        let options = Camt053WriteOptions {
+            created_at: chrono::NaiveDate::from_ymd_opt(2025, 4, 21)
+                .and_then(|date| date.and_hms_opt(6, 0, 0)),
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        statement.write_to_with(&mut buffer, &options).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("<MsgId>20250420BE68539007547034</MsgId>"));
+        assert!(text.contains("<Id>20250420BE68539007547034-1</Id>"));
+        assert!(text.contains("<ElctrncSeqNb>1</ElctrncSeqNb>"));
+        assert_eq!(
+            text.matches("<CreDtTm>2025-04-21T06:00:00</CreDtTm>")
+                .count(),
+            2
+        );
+
+        let options = Camt053WriteOptions {
+            compact: true,
+            group_header: Some(true),
+            version: Camt053Version::V04,
+            entry_status: Some(true),
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        statement.write_to_with(&mut buffer, &options).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(!text.contains('\n'));
+        assert!(text.contains("xmlns=\"urn:iso:std:iso:20022:tech:xsd:camt.053.001.04\""));
+        assert!(text.contains("<BkToCstmrStmt><GrpHdr><MsgId>"));
+        assert!(text.contains("<Sts>BOOK</Sts>"));
+        assert_eq!(text.matches("<Chrgs><Rcrd>").count(), 1);
+        assert_eq!(text.matches("<Rcrd>").count(), 2);
+        let parsed = Camt053Statement::from_read(&mut text.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+
+        let options = Camt053WriteOptions {
+            group_header: Some(false),
+            entry_status: Some(false),
+            entry_refs: Some(true),
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        statement.write_to_with(&mut buffer, &options).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert!(text.contains("<NtryRef>1</NtryRef>"));
+        assert!(!text.contains("<Sts>") && !text.contains("<GrpHdr>"));
+        assert_eq!("001.04".parse(), Ok(Camt053Version::V04));
+    }
+
+    #[test]
+    fn test_supplementary_data_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <NtryDtls><TxDtls><SplmtryData><Envlp><bank:Info xmlns:bank="urn:bank"><bank:Amt>1 &amp; 2</bank:Amt></bank:Info></Envlp></SplmtryData></TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt><SplmtryData><PlcAndNm>Document</PlcAndNm><Envlp><Ntry>ignored</Ntry></Envlp></SplmtryData></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(
+            statement.transactions[0].supplementary_data,
+            vec![
+                r#"<Envlp><bank:Info xmlns:bank="urn:bank"><bank:Amt>1 &amp; 2</bank:Amt></bank:Info></Envlp>"#
+            ]
+        );
+        assert_eq!(
+            statement.supplementary_data,
+            vec!["<PlcAndNm>Document</PlcAndNm><Envlp><Ntry>ignored</Ntry></Envlp>"]
+        );
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+        assert_eq!(parsed.supplementary_data, statement.supplementary_data);
+    }
+
+    #[test]
+    fn test_structured_remittance_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <NtryDtls><TxDtls><RmtInf><Strd>
+                    <CdtrRefInf><Tp><CdOrPrtry><Cd>SCOR</Cd></CdOrPrtry></Tp><Ref>RF18539007547034</Ref></CdtrRefInf>
+                    <AddtlRmtInf>Invoice 2025-17</AddtlRmtInf>
+                </Strd></RmtInf></TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let transaction = &statement.transactions[0];
+        assert_eq!(transaction.description, "RF18539007547034");
+        assert_eq!(
+            transaction.remittance,
+            Some(RemittanceInfo::Structured {
+                creditor_ref: Some("RF18539007547034".into()),
+                ref_type: Some("SCOR".into()),
+                additional: vec!["Invoice 2025-17".into()],
+            })
+        );
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let written = String::from_utf8(buffer).unwrap();
+        assert!(!written.contains("<Ustrd>"));
+        let parsed = Camt053Statement::from_read(&mut written.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+    }
+
+    #[test]
+    fn test_counterparty_bic_and_address_round_trip() {
+        let xml = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+            <Acct><Id><IBAN>BE68539007547034</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">0.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+            <Ntry><Amt Ccy="EUR">50.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-20</Dt></BookgDt>
+                <NtryDtls><TxDtls>
+                    <RltdPties><Dbtr><Nm>Acme Ltd</Nm><PstlAdr><StrtNm>High Street</StrtNm><BldgNb>12</BldgNb>
+                        <PstCd>SW1A 1AA</PstCd><TwnNm>London</TwnNm><Ctry>GB</Ctry></PstlAdr></Dbtr></RltdPties>
+                    <RltdAgts><DbtrAgt><FinInstnId><BIC>NWBKGB2L</BIC></FinInstnId></DbtrAgt></RltdAgts>
+                    <RmtInf><Ustrd>Invoice 17</Ustrd></RmtInf>
+                </TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let statement = Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let transaction = &statement.transactions[0];
+        assert_eq!(transaction.counterparty_name.as_deref(), Some("Acme Ltd"));
+        assert_eq!(transaction.counterparty_bic.as_deref(), Some("NWBKGB2L"));
+        let address = transaction.counterparty_address.as_ref().unwrap();
+        assert_eq!(address.street.as_deref(), Some("High Street"));
+        assert_eq!(address.town.as_deref(), Some("London"));
+        assert_eq!(address.country.as_deref(), Some("GB"));
+
+        let mut buffer = Vec::new();
+        statement.write_to(&mut buffer).unwrap();
+        let parsed = Camt053Statement::from_read(&mut buffer.as_slice()).unwrap();
+        assert_eq!(parsed.transactions, statement.transactions);
+    }
+
     #[test]
     fn test_write_to_buffer() {
         // Test writing to an in-memory buffer
@@ -326,12 +1475,15 @@ mod tests {
             account_number: "TEST123".into(),
             currency: "EUR".into(),
             opening_balance: 500.0,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Credit,
             closing_balance: 750.0,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -352,12 +1504,15 @@ mod tests {
             account_number: "DEBIT123".into(),
             currency: "USD".into(),
             opening_balance: 100.0,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Debit,
             closing_balance: 50.0,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Debit,
             transactions: vec![],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -377,21 +1532,20 @@ mod tests {
             account_number: "MINIMAL123".into(),
             currency: "GBP".into(),
             opening_balance: 1000.0,
-            opening_date: utils::parse_date("2025-01-01").unwrap(),
+            opening_date: text::parse_date("2025-01-01").unwrap(),
             opening_indicator: BalanceType::Credit,
             closing_balance: 1100.0,
-            closing_date: utils::parse_date("2025-01-31").unwrap(),
+            closing_date: text::parse_date("2025-01-31").unwrap(),
             closing_indicator: BalanceType::Credit,
-            transactions: vec![Transaction {
-                booking_date: utils::parse_date("2025-01-15").unwrap(),
-                value_date: None,
-                amount: 100.0,
-                transaction_type: TransactionType::Credit,
-                description: "Simple payment".into(),
-                reference: None,
-                counterparty_name: None,
-                counterparty_account: None,
-            }],
+            transactions: vec![Transaction::new(
+                text::parse_date("2025-01-15").unwrap(),
+                100.0,
+                TransactionType::Credit,
+                "Simple payment",
+            )],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -406,7 +1560,228 @@ mod tests {
         assert!(xml_output.contains("<Ustrd>Simple payment</Ustrd>"));
         // Should not contain optional elements when they're not present
         assert!(!xml_output.contains("<TxId>"));
+        assert!(!xml_output.contains("<NtryRef>"));
         assert!(!xml_output.contains("<Dbtr>"));
         assert!(!xml_output.contains("<DbtrAcct>"));
     }
+
+    #[test]
+    fn test_write_camt053_paginated() {
+        let tx = |date: &str, amount: f64| Transaction {
+            reference: Some(format!("REF-{}", date)),
+            ..Transaction::new(
+                text::parse_date(date).unwrap(),
+                amount,
+                TransactionType::Credit,
+                "Payment",
+            )
+        };
+        let statement = Camt053Statement {
+            account_number: "DK8030000001234567".into(),
+            currency: "DKK".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-01-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 160.0,
+            closing_date: text::parse_date("2025-01-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                tx("2025-01-02", 10.0),
+                tx("2025-01-03", 20.0),
+                tx("2025-01-04", 30.0),
+            ],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+
+        let options = Camt053WriteOptions {
+            max_entries_per_page: Some(2),
+            version: Camt053Version::V04,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let xml_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(xml_output.matches("<Stmt>").count(), 2);
+        assert!(xml_output.contains("<PgNb>1</PgNb>"));
+        assert!(xml_output.contains("<LastPgInd>false</LastPgInd>"));
+        assert!(xml_output.contains("<PgNb>2</PgNb>"));
+        assert!(xml_output.contains("<LastPgInd>true</LastPgInd>"));
+        assert_eq!(xml_output.matches("<Cd>ITBD</Cd>").count(), 2);
+        assert!(xml_output.contains("<Amt Ccy=\"DKK\">130.00</Amt>"));
+        assert!(xml_output.contains("<NtryRef>3</NtryRef>"));
+
+        // Version 02 has no `<StmtPgntn>`: the pages differ in `Id` and `ElctrncSeqNb`
+        // and are still read back as one statement
+        let mut statement = statement;
+        statement.info.statement_id = Some("STMT-1".into());
+        let options = Camt053WriteOptions {
+            max_entries_per_page: Some(2),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let xml_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(xml_output.matches("<Stmt>").count(), 2);
+        assert!(!xml_output.contains("<StmtPgntn>"));
+        assert!(xml_output.contains("<Id>STMT-1-1</Id>"));
+        assert!(xml_output.contains("<Id>STMT-1-2</Id>"));
+        assert!(xml_output.contains("<ElctrncSeqNb>2</ElctrncSeqNb>"));
+        let parsed = Camt053Statement::from_read(&mut xml_output.as_bytes()).unwrap();
+        assert_eq!(parsed.transactions.len(), 3);
+        assert_eq!(parsed.closing_balance, 160.0);
+    }
+
+    #[test]
+    fn test_parse_all_splits_statements_and_merges_pages() {
+        let statement = |account: &str, closing: f64| Camt053Statement {
+            account_number: account.into(),
+            currency: "DKK".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-01-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: closing,
+            closing_date: text::parse_date("2025-01-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: (1..=3)
+                .map(|day| Transaction {
+                    reference: Some(format!("REF-{}", day)),
+                    ..Transaction::new(
+                        text::parse_date(&format!("2025-01-0{}", day)).unwrap(),
+                        10.0,
+                        TransactionType::Credit,
+                        "Payment",
+                    )
+                })
+                .collect(),
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+        let first = statement("DK8030000001234567", 130.0);
+        // Without generated ids, so the statements read back unchanged
+        let options = Camt053WriteOptions {
+            max_entries_per_page: Some(2),
+            group_header: Some(false),
+            ..Default::default()
+        };
+        let mut paginated = Vec::new();
+        first.write_to_with(&mut paginated, &options).unwrap();
+        let paginated = String::from_utf8(paginated).unwrap();
+
+        // Two pages of one statement stay a single statement
+        let pages = Camt053Statement::parse_all(&mut paginated.as_bytes()).unwrap();
+        assert_eq!(pages, vec![first.clone()]);
+        assert_eq!(
+            Camt053Statement::from_read(&mut paginated.as_bytes()).unwrap(),
+            first
+        );
+
+        // A second account's <Stmt> becomes a statement of its own
+        let second = statement("SE5180000810512345678901", 130.0);
+        let mut other = Vec::new();
+        second.write_to_with(&mut other, &options).unwrap();
+        let other = String::from_utf8(other).unwrap();
+        let other_stmt =
+            &other[other.find("<Stmt>").unwrap()..other.find("</BkToCstmrStmt>").unwrap()];
+        let combined = paginated.replace(
+            "</BkToCstmrStmt>",
+            &format!("{}</BkToCstmrStmt>", other_stmt),
+        );
+
+        let statements = Camt053Statement::parse_all(&mut combined.as_bytes()).unwrap();
+        assert_eq!(statements, vec![first, second]);
+        assert!(matches!(
+            Camt053Statement::from_read(&mut combined.as_bytes()),
+            Err(ParseError::Camt053Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_camt053_dk_profile() {
+        let tx = |transaction_type: TransactionType, reference: Option<&str>| Transaction {
+            reference: reference.map(str::to_string),
+            counterparty_name: Some("M".repeat(80)),
+            ..Transaction::new(
+                text::parse_date("2025-03-07").unwrap(),
+                50.0,
+                transaction_type,
+                "Rechnung ".repeat(20),
+            )
+        };
+        let statement = Camt053Statement {
+            account_number: "DE89370400440532013000".into(),
+            currency: "EUR".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-03-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 100.0,
+            closing_date: text::parse_date("2025-03-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                tx(TransactionType::Credit, Some("E2E-1")),
+                tx(TransactionType::Debit, None),
+            ],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+
+        let options = Camt053WriteOptions {
+            profile: Camt053Profile::Dk,
+            created_at: chrono::NaiveDate::from_ymd_opt(2025, 4, 1)
+                .and_then(|date| date.and_hms_opt(8, 30, 0)),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let xml_output = String::from_utf8(output).unwrap();
+
+        assert!(xml_output.contains("<MsgId>20250331DE89370400440532013000</MsgId>"));
+        assert!(xml_output.contains("<Id>20250331DE89370400440532013000-1</Id>"));
+        assert!(xml_output.contains("<ElctrncSeqNb>1</ElctrncSeqNb>"));
+        assert_eq!(
+            xml_output
+                .matches("<CreDtTm>2025-04-01T08:30:00</CreDtTm>")
+                .count(),
+            2
+        );
+        assert!(xml_output.contains("<Cd>PRCD</Cd>"));
+        assert_eq!(xml_output.matches("<Sts>BOOK</Sts>").count(), 2);
+        assert_eq!(xml_output.matches("<ValDt>").count(), 2);
+        assert!(xml_output.contains("<Cd>NTRF+166</Cd>"));
+        assert!(xml_output.contains("<Cd>NTRF+116</Cd>"));
+        assert!(xml_output.contains("<EndToEndId>E2E-1</EndToEndId>"));
+        assert!(xml_output.contains("<EndToEndId>NOTPROVIDED</EndToEndId>"));
+        assert!(xml_output.contains(&format!("<Nm>{}</Nm>", "M".repeat(70))));
+        assert_eq!(xml_output.matches("<Ustrd>").count(), 4);
+
+        // Sts, ValDt and BkTxCd follow the schema order inside Ntry
+        let status = xml_output.find("<Sts>").unwrap();
+        let value_date = xml_output.find("<ValDt>").unwrap();
+        let code = xml_output.find("<BkTxCd>").unwrap();
+        assert!(status < value_date && value_date < code);
+
+        let options = Camt053WriteOptions {
+            id_generator: Some(SharedIdGenerator::new(crate::SequentialIds::new("MSG", 1))),
+            ..options
+        };
+        let mut generated = Vec::new();
+        statement.write_to_with(&mut generated, &options).unwrap();
+        let generated = String::from_utf8(generated).unwrap();
+        assert!(generated.contains("<MsgId>MSG000001</MsgId>"));
+        assert!(generated.contains("<Id>MSG000002</Id>"));
+
+        // DK output stays readable by the parser, PRCD included
+        let parsed = Camt053Statement::from_read(&mut xml_output.as_bytes()).unwrap();
+        assert_eq!(parsed.opening_balance, 100.0);
+        assert_eq!(parsed.transactions[0].reference.as_deref(), Some("E2E-1"));
+        assert_eq!(
+            parsed.transactions[1].description,
+            "Rechnung ".repeat(20).trim_end()
+        );
+    }
 }
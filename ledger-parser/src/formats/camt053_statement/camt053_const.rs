@@ -1,7 +1,44 @@
+// Document namespaces per message type
+pub(super) const CAMT053_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.053.001.02";
+pub(super) const CAMT052_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.052.001.02";
+pub(super) const CAMT054_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.054.001.02";
+pub(super) const CAMT053_V04_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.053.001.04";
+pub(super) const CAMT052_V04_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.052.001.04";
+pub(super) const CAMT054_V04_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:camt.054.001.04";
+
 // Balance type constants
 pub(super) const OPBD_BALANCE_TYPE: &str = "OPBD";
 pub(super) const CLBD_BALANCE_TYPE: &str = "CLBD";
+pub(super) const ITBD_BALANCE_TYPE: &str = "ITBD";
+/// Closing balance of the previous statement, used by DK as the opening balance
+pub(super) const PRCD_BALANCE_TYPE: &str = "PRCD";
 
 // Credit/Debit indicator constants
 pub(super) const CRDT_INDICATOR: &str = "CRDT";
 pub(super) const DBIT_INDICATOR: &str = "DBIT";
+
+// Entry status constants
+pub(super) const BOOKED_STATUS: &str = "BOOK";
+pub(super) const PENDING_STATUS: &str = "PDNG";
+/// Entry announced for a future date; read as pending
+pub(super) const FUTURE_STATUS: &str = "FUTR";
+pub(super) const INFO_STATUS: &str = "INFO";
+
+/// Maximum length of identifiers (`MsgId`, `Stmt/Id`, `EndToEndId`; ISO `Max35Text`)
+pub(super) const MAX_ID_LENGTH: usize = 35;
+
+// Deutsche Kreditwirtschaft (DK) profile constants
+pub(super) const DK_ISSUER: &str = "DK";
+/// Proprietary bank transaction code: SEPA credit transfer received (GVC 166)
+pub(super) const DK_TRANSACTION_CODE_CREDIT: &str = "NTRF+166";
+/// Proprietary bank transaction code: SEPA credit transfer sent (GVC 116)
+pub(super) const DK_TRANSACTION_CODE_DEBIT: &str = "NTRF+116";
+pub(super) const NOT_PROVIDED: &str = "NOTPROVIDED";
+/// Maximum length of party names (`Nm`)
+pub(super) const DK_MAX_NAME_LENGTH: usize = 70;
+/// Maximum length of one `Ustrd` remittance line
+pub(super) const DK_MAX_REMITTANCE_LENGTH: usize = 140;
+
+/// ISO 20022 codes of creditor reference types (`DocumentType3Code`); other types are proprietary
+pub(super) const CREDITOR_REFERENCE_TYPE_CODES: [&str; 6] =
+    ["RADM", "RPIN", "FXDR", "DISP", "PUOR", "SCOR"];
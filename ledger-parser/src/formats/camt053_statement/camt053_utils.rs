@@ -2,24 +2,25 @@ use chrono::{DateTime, FixedOffset};
 
 use crate::error::ParseError;
 use crate::formats::camt053_statement::camt053_const::*;
-use crate::formats::utils;
-use crate::model::{BalanceType, TransactionType};
+use crate::model::{BalanceType, EntryStatus, TransactionType};
+use crate::text;
 
 /// Parse amount from string (handles both dot and comma as decimal separator)
 pub(super) fn parse_amount(s: &str) -> Result<f64, ParseError> {
     let cleaned = s.trim().replace(',', ".");
     cleaned
         .parse::<f64>()
-        .map_err(|_| ParseError::InvalidFieldValue {
+        .map_err(|_| ParseError::UnexpectedValue {
             field: "amount".into(),
-            value: s.into(),
+            expected: "a decimal number".into(),
+            found: s.into(),
         })
 }
 
 /// Parse XML date/datetime to DateTime<FixedOffset>
 pub(super) fn parse_xml_date(s: &str) -> Result<DateTime<FixedOffset>, ParseError> {
     // Try parsing as datetime first (2023-04-20T23:24:31)
-    utils::parse_date(s.trim())
+    text::parse_date(s.trim())
 }
 
 /// Parse balance indicator (CRDT/DBIT) to BalanceType
@@ -27,9 +28,10 @@ pub(super) fn parse_balance_indicator(s: &str) -> Result<BalanceType, ParseError
     match s.trim().to_uppercase().as_str() {
         CRDT_INDICATOR => Ok(BalanceType::Credit),
         DBIT_INDICATOR => Ok(BalanceType::Debit),
-        _ => Err(ParseError::InvalidFieldValue {
-            field: "balance_indicator".into(),
-            value: s.to_string(),
+        _ => Err(ParseError::UnexpectedValue {
+            field: "balance indicator".into(),
+            expected: format!("{} or {}", CRDT_INDICATOR, DBIT_INDICATOR),
+            found: s.to_string(),
         }),
     }
 }
@@ -39,17 +41,27 @@ pub(super) fn parse_transaction_type(s: &str) -> Result<TransactionType, ParseEr
     match s.trim().to_uppercase().as_str() {
         CRDT_INDICATOR => Ok(TransactionType::Credit),
         DBIT_INDICATOR => Ok(TransactionType::Debit),
-        _ => Err(ParseError::InvalidFieldValue {
-            field: "transaction_type".into(),
-            value: s.to_string(),
+        _ => Err(ParseError::UnexpectedValue {
+            field: "credit/debit indicator".into(),
+            expected: format!("{} or {}", CRDT_INDICATOR, DBIT_INDICATOR),
+            found: s.to_string(),
         }),
     }
 }
 
+/// Parse entry status (BOOK/PDNG/FUTR/INFO) to EntryStatus; unknown codes are booked
+pub(super) fn parse_entry_status(s: &str) -> EntryStatus {
+    match s.trim().to_uppercase().as_str() {
+        PENDING_STATUS | FUTURE_STATUS => EntryStatus::Pending,
+        INFO_STATUS => EntryStatus::Info,
+        _ => EntryStatus::Booked,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{BalanceType, TransactionType};
+    use crate::model::{BalanceType, EntryStatus, TransactionType};
 
     #[test]
     fn test_parse_amount() {
@@ -85,6 +97,15 @@ mod tests {
         assert!(parse_transaction_type("INVALID").is_err());
     }
 
+    #[test]
+    fn test_parse_entry_status() {
+        assert_eq!(parse_entry_status("BOOK"), EntryStatus::Booked);
+        assert_eq!(parse_entry_status("pdng"), EntryStatus::Pending);
+        assert_eq!(parse_entry_status("FUTR"), EntryStatus::Pending);
+        assert_eq!(parse_entry_status("INFO"), EntryStatus::Info);
+        assert_eq!(parse_entry_status("XXXX"), EntryStatus::Booked);
+    }
+
     #[test]
     fn test_parse_xml_date() {
         // Test date only
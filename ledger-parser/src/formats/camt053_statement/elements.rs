@@ -11,14 +11,52 @@ pub(super) enum ElementName {
     Document,
     #[strum(serialize = "BkToCstmrStmt")]
     BkToCstmrStmt,
+    #[strum(serialize = "BkToCstmrAcctRpt")]
+    BkToCstmrAcctRpt,
+    #[strum(serialize = "BkToCstmrDbtCdtNtfctn")]
+    BkToCstmrDbtCdtNtfctn,
+    #[strum(serialize = "GrpHdr")]
+    GroupHeader,
+    #[strum(serialize = "MsgId")]
+    MessageId,
+    #[strum(serialize = "CreDtTm")]
+    CreationDateTime,
+    #[strum(serialize = "MsgPgntn")]
+    MessagePagination,
     #[strum(serialize = "Stmt")]
     Stmt,
+    #[strum(serialize = "Rpt")]
+    Report,
+    #[strum(serialize = "Ntfctn")]
+    Notification,
+    #[strum(serialize = "ElctrncSeqNb")]
+    ElectronicSequenceNumber,
+    #[strum(serialize = "LglSeqNb")]
+    LegalSequenceNumber,
+    #[strum(serialize = "FrToDt")]
+    FromToDate,
+    #[strum(serialize = "FrDtTm")]
+    FromDateTime,
+    #[strum(serialize = "ToDtTm")]
+    ToDateTime,
+    #[strum(serialize = "StmtPgntn")]
+    StatementPagination,
+    #[strum(serialize = "PgNb")]
+    PageNumber,
+    #[strum(serialize = "LastPgInd")]
+    LastPageIndicator,
     #[strum(serialize = "Acct")]
     Acct,
     #[strum(serialize = "Id")]
     Id,
     #[strum(serialize = "IBAN")]
     Iban,
+    #[strum(serialize = "Othr")]
+    OtherId,
+    #[strum(serialize = "Ownr")]
+    Owner,
+    #[strum(serialize = "Svcr")]
+    Servicer,
     #[strum(serialize = "Ccy")]
     Currency,
     #[strum(serialize = "Bal")]
@@ -35,6 +73,8 @@ pub(super) enum ElementName {
     CreditDebit,
     #[strum(serialize = "Dt")]
     Date,
+    #[strum(serialize = "DtTm")]
+    DateTime,
     #[strum(serialize = "Ntry")]
     Entry,
     #[strum(serialize = "NtryRef")]
@@ -43,14 +83,36 @@ pub(super) enum ElementName {
     BookingDate,
     #[strum(serialize = "ValDt")]
     ValueDate,
+    #[strum(serialize = "Sts")]
+    Status,
+    #[strum(serialize = "RvslInd")]
+    ReversalIndicator,
+    #[strum(serialize = "SplmtryData")]
+    SupplementaryData,
+    #[strum(serialize = "BkTxCd")]
+    BankTransactionCode,
+    #[strum(serialize = "Prtry")]
+    Proprietary,
+    #[strum(serialize = "Issr")]
+    Issuer,
     #[strum(serialize = "NtryDtls")]
     EntryDetails,
     #[strum(serialize = "TxDtls")]
     TransactionDetails,
+    #[strum(serialize = "Btch")]
+    Batch,
+    #[strum(serialize = "NbOfTxs")]
+    NumberOfTransactions,
+    #[strum(serialize = "TxAmt")]
+    TransactionAmount,
     #[strum(serialize = "Refs")]
     References,
+    #[strum(serialize = "EndToEndId")]
+    EndToEndId,
     #[strum(serialize = "TxId")]
     TransactionId,
+    #[strum(serialize = "MndtId")]
+    MandateId,
     #[strum(serialize = "RmtInf")]
     RemittanceInfo,
     #[strum(serialize = "Ustrd")]
@@ -75,6 +137,56 @@ pub(super) enum ElementName {
     Name,
     #[strum(serialize = "AddtlTxInf")]
     AdditionalInfo,
+    #[strum(serialize = "AddtlNtryInf")]
+    AdditionalEntryInfo,
+    #[strum(serialize = "Pty")]
+    Party,
+    #[strum(serialize = "AmtDtls")]
+    AmountDetails,
+    #[strum(serialize = "InstdAmt")]
+    InstructedAmount,
+    #[strum(serialize = "CcyXchg")]
+    CurrencyExchange,
+    #[strum(serialize = "XchgRate")]
+    ExchangeRate,
+    #[strum(serialize = "SrcCcy")]
+    SourceCurrency,
+    #[strum(serialize = "TrgtCcy")]
+    TargetCurrency,
+    #[strum(serialize = "Chrgs")]
+    Charges,
+    #[strum(serialize = "Rcrd")]
+    ChargesRecord,
+    #[strum(serialize = "Br")]
+    ChargeBearer,
+    #[strum(serialize = "AddtlRmtInf")]
+    AdditionalRemittanceInfo,
+    #[strum(serialize = "RltdAgts")]
+    RelatedAgents,
+    #[strum(serialize = "DbtrAgt")]
+    DebtorAgent,
+    #[strum(serialize = "CdtrAgt")]
+    CreditorAgent,
+    #[strum(serialize = "FinInstnId")]
+    FinancialInstitutionId,
+    #[strum(serialize = "BIC")]
+    Bic,
+    #[strum(serialize = "BICFI")]
+    BicFi,
+    #[strum(serialize = "PstlAdr")]
+    PostalAddress,
+    #[strum(serialize = "StrtNm")]
+    StreetName,
+    #[strum(serialize = "BldgNb")]
+    BuildingNumber,
+    #[strum(serialize = "PstCd")]
+    PostCode,
+    #[strum(serialize = "TwnNm")]
+    TownName,
+    #[strum(serialize = "Ctry")]
+    Country,
+    #[strum(serialize = "AdrLine")]
+    AddressLine,
     Other,
 }
 
@@ -3,12 +3,16 @@ use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart};
 
 use crate::error::ParseError;
-use crate::model::{BalanceType, Transaction};
+use crate::model::{
+    Balance, BalanceCode, BalanceType, StatementHeader, StatementInfo, Transaction,
+};
 
 use super::camt053_utils;
 use super::elements::ElementName;
-use super::scratch::{BalanceScratch, EntryScratch};
+use super::scratch::{BalanceScratch, ChargeScratch, DetailScratch, EntryScratch};
+use super::CamtMessage;
 use crate::formats::camt053_statement::camt053_const::*;
+use crate::formats::paging;
 
 #[derive(Default)]
 pub(super) struct CamtParser {
@@ -21,19 +25,66 @@ pub(super) struct CamtParser {
     closing_date: Option<DateTime<FixedOffset>>,
     closing_indicator: Option<BalanceType>,
     transactions: Vec<Transaction>,
+    message: Option<CamtMessage>,
+    last_interim_balance: Option<BalanceScratch>,
+    /// An opening booked (`OPBD`) balance was read, which a previous closing
+    /// (`PRCD`) one does not replace
+    opening_booked: bool,
     balance_scratch: BalanceScratch,
     entry_scratch: Option<EntryScratch>,
     path: Vec<ElementName>,
+    /// The current `<Stmt>` page announced that further pages follow (`LastPgInd` false)
+    continued: bool,
+    /// Several pages were merged into the current statement, which is then complete
+    merged: bool,
+    statements: Vec<super::Camt053Statement>,
+    /// Raw `<SplmtryData>` contents outside entries
+    supplementary_data: Vec<String>,
+    /// Identification and period of the current statement
+    info: StatementInfo,
+    /// Balances other than the opening and closing booked ones
+    balances: Vec<Balance>,
+    /// Entries that could not become transactions, with the path of their `<Ntry>`
+    skipped: Vec<(ParseError, String)>,
+    /// Text of the current element read so far, joined across entity references
+    text: String,
 }
 
 impl CamtParser {
     pub(super) fn handle_start(&mut self, event: &BytesStart) -> Result<(), ParseError> {
         let name = ElementName::from_name_bytes(event.name().as_ref())?;
+        if self.is_party_wrapper(name) {
+            return Ok(());
+        }
         self.path.push(name);
 
         match name {
+            ElementName::BkToCstmrStmt => self.message = Some(CamtMessage::Statement),
+            ElementName::BkToCstmrAcctRpt => self.message = Some(CamtMessage::Report),
+            ElementName::BkToCstmrDbtCdtNtfctn => self.message = Some(CamtMessage::Notification),
             ElementName::Balance => self.balance_scratch.clear(),
             ElementName::Entry => self.entry_scratch = Some(EntryScratch::default()),
+            ElementName::TransactionDetails => {
+                if let Some(entry) = self.entry_scratch.as_mut() {
+                    entry.details.push(DetailScratch::default());
+                }
+            }
+            ElementName::Charges | ElementName::ChargesRecord => {
+                let in_details = self.path.contains(&ElementName::TransactionDetails);
+                if let Some(entry) = self.entry_scratch.as_mut() {
+                    let charges = if in_details {
+                        &mut entry.detail_charges
+                    } else {
+                        &mut entry.charges
+                    };
+                    charges.push(ChargeScratch::default());
+                }
+            }
+            ElementName::StructuredRemittance => {
+                if let Some(entry) = self.entry_scratch.as_mut() {
+                    entry.structured = true;
+                }
+            }
             ElementName::Amount => self.capture_currency(event.attributes())?,
             _ => {}
         }
@@ -41,19 +92,88 @@ impl CamtParser {
         Ok(())
     }
 
-    pub(super) fn handle_end(&mut self, _event: &BytesEnd) -> Result<(), ParseError> {
+    pub(super) fn handle_end(&mut self, event: &BytesEnd) -> Result<(), ParseError> {
+        let name = ElementName::from_name_bytes(event.name().as_ref())?;
+        if self.is_party_wrapper(name) {
+            return Ok(());
+        }
         if let Some(ended) = self.path.pop() {
             match ended {
                 ElementName::Balance => self.finish_balance(),
                 ElementName::Entry => self.finish_entry(),
+                // Pages of a paginated statement are merged into one statement
+                ElementName::Stmt | ElementName::Report | ElementName::Notification
+                    if self.continued || self.is_unmarked_page() =>
+                {
+                    self.continued = false;
+                    self.merged = true;
+                }
+                ElementName::Stmt | ElementName::Report | ElementName::Notification => {
+                    self.finish_statement()?
+                }
                 _ => {}
             }
         }
         Ok(())
     }
 
-    pub(super) fn handle_text(&mut self, text: &str) -> Result<(), ParseError> {
-        if self.in_statement_account_id() {
+    /// Add a piece of the current element's text
+    pub(super) fn push_text(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    /// Hand the text gathered since the last tag, trimmed, to [`Self::handle_text`]
+    pub(super) fn flush_text(&mut self) -> Result<(), ParseError> {
+        let text = std::mem::take(&mut self.text);
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return Ok(());
+        }
+        self.handle_text(trimmed)
+    }
+
+    fn handle_text(&mut self, text: &str) -> Result<(), ParseError> {
+        if self.in_entries() {
+            self.capture_detail(text);
+            if self.path.contains(&ElementName::Charges) {
+                self.capture_charge(text);
+                return Ok(());
+            }
+        }
+        if self.path_ends_with(&[
+            ElementName::StatementPagination,
+            ElementName::LastPageIndicator,
+        ]) {
+            self.continued = text.eq_ignore_ascii_case("false");
+        } else if self
+            .in_statement_element(&[ElementName::StatementPagination, ElementName::PageNumber])
+        {
+            self.info.page_number = text.trim().parse().ok();
+        } else if self.in_statement_element(&[ElementName::Id]) {
+            // Merged pages keep the identification of the first
+            self.info
+                .statement_id
+                .get_or_insert_with(|| text.trim().to_string());
+        } else if self.in_statement_element(&[ElementName::ElectronicSequenceNumber]) {
+            self.info.sequence_number = self.info.sequence_number.or(text.trim().parse().ok());
+        } else if self.in_statement_element(&[ElementName::LegalSequenceNumber]) {
+            self.info.legal_sequence_number =
+                self.info.legal_sequence_number.or(text.trim().parse().ok());
+        } else if self.in_statement_element(&[ElementName::CreationDateTime]) {
+            self.info.created_at = camt053_utils::parse_xml_date(text).ok();
+        } else if self.in_statement_element(&[ElementName::FromToDate, ElementName::FromDateTime]) {
+            self.info.from_date = camt053_utils::parse_xml_date(text).ok();
+        } else if self.in_statement_element(&[ElementName::FromToDate, ElementName::ToDateTime]) {
+            self.info.to_date = camt053_utils::parse_xml_date(text).ok();
+        } else if self.in_statement_element(&[
+            ElementName::Acct,
+            ElementName::Owner,
+            ElementName::Name,
+        ]) {
+            self.info.account_owner = Some(text.trim().to_string());
+        } else if self.in_servicer_bic() {
+            self.info.servicer_bic = Some(text.trim().to_string());
+        } else if self.in_statement_account_id() {
             self.set_account_number(text);
         } else if self.path_ends_with(&[ElementName::Acct, ElementName::Currency]) {
             self.set_currency(text);
@@ -69,6 +189,11 @@ impl CamtParser {
         } else if self.path_ends_with(&[ElementName::Balance, ElementName::CreditDebit]) {
             self.balance_scratch.indicator = Some(text.to_string());
         } else if self.path_ends_with(&[ElementName::Balance, ElementName::Date, ElementName::Date])
+            || self.path_ends_with(&[
+                ElementName::Balance,
+                ElementName::Date,
+                ElementName::DateTime,
+            ])
         {
             self.balance_scratch.date = Some(text.to_string());
         } else if self.path_ends_with(&[ElementName::Entry, ElementName::Amount]) {
@@ -79,10 +204,25 @@ impl CamtParser {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.indicator = Some(text.to_string());
             }
+        } else if self.path_ends_with(&[ElementName::Entry, ElementName::Status])
+            || self.path_ends_with(&[ElementName::Entry, ElementName::Status, ElementName::Code])
+        {
+            // Newer message versions wrap the status code in <Cd>
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.status = Some(text.to_string());
+            }
+        } else if self.path_ends_with(&[ElementName::Entry, ElementName::ReversalIndicator]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.reversal = Some(text.to_string());
+            }
         } else if self.path_ends_with(&[
             ElementName::Entry,
             ElementName::BookingDate,
             ElementName::Date,
+        ]) || self.path_ends_with(&[
+            ElementName::Entry,
+            ElementName::BookingDate,
+            ElementName::DateTime,
         ]) {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.booking_date = Some(text.to_string());
@@ -109,6 +249,23 @@ impl CamtParser {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.tx_id = Some(text.to_string());
             }
+        } else if self.path_ends_with(&[
+            ElementName::TransactionDetails,
+            ElementName::References,
+            ElementName::EndToEndId,
+        ]) {
+            // NOTPROVIDED is the placeholder of payments initiated without an ID
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.end_to_end_id = Some(text.to_string()).filter(|id| id != NOT_PROVIDED);
+            }
+        } else if self.path_ends_with(&[
+            ElementName::TransactionDetails,
+            ElementName::References,
+            ElementName::MandateId,
+        ]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.mandate_id = Some(text.to_string());
+            }
         } else if self.path_ends_with(&[
             ElementName::Entry,
             ElementName::EntryDetails,
@@ -130,6 +287,30 @@ impl CamtParser {
         ]) {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.set_description_if_empty(text);
+                entry.creditor_ref = Some(text.to_string());
+            }
+        } else if self.path_ends_with(&[
+            ElementName::StructuredRemittance,
+            ElementName::CreditorReferenceInfo,
+            ElementName::BalanceType,
+            ElementName::CodeOrProprietary,
+            ElementName::Code,
+        ]) || self.path_ends_with(&[
+            ElementName::StructuredRemittance,
+            ElementName::CreditorReferenceInfo,
+            ElementName::BalanceType,
+            ElementName::CodeOrProprietary,
+            ElementName::Proprietary,
+        ]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.ref_type = Some(text.to_string());
+            }
+        } else if self.path_ends_with(&[
+            ElementName::StructuredRemittance,
+            ElementName::AdditionalRemittanceInfo,
+        ]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.additional_remittance.push(text.to_string());
             }
         } else if self.path_ends_with(&[
             ElementName::Entry,
@@ -155,6 +336,18 @@ impl CamtParser {
                     entry.counterparty_name = Some(text.to_string());
                 }
             }
+        } else if let Some((party, part)) = self.address_part() {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.set_address_part(party, part, text);
+            }
+        } else if self.in_agent_bic(ElementName::DebtorAgent) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.debtor_bic = Some(text.to_string());
+            }
+        } else if self.in_agent_bic(ElementName::CreditorAgent) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.creditor_bic = Some(text.to_string());
+            }
         } else if self.in_debtor_account_id() {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.counterparty_account = Some(text.to_string());
@@ -165,7 +358,21 @@ impl CamtParser {
                     entry.counterparty_account = Some(text.to_string());
                 }
             }
-        } else if self.path_ends_with(&[ElementName::Entry, ElementName::AdditionalInfo]) {
+        } else if self.path_ends_with(&[
+            ElementName::AmountDetails,
+            ElementName::InstructedAmount,
+            ElementName::Amount,
+        ]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.original_amount = Some(text.to_string());
+            }
+        } else if self.path_ends_with(&[ElementName::CurrencyExchange, ElementName::ExchangeRate]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.exchange_rate.get_or_insert_with(|| text.to_string());
+            }
+        } else if self.path_ends_with(&[ElementName::Entry, ElementName::AdditionalInfo])
+            || self.path_ends_with(&[ElementName::Entry, ElementName::AdditionalEntryInfo])
+        {
             if let Some(entry) = self.entry_scratch.as_mut() {
                 entry.push_description(text);
             }
@@ -174,15 +381,214 @@ impl CamtParser {
         Ok(())
     }
 
-    pub(super) fn build_statement(self) -> Result<super::Camt053Statement, ParseError> {
+    /// Record `text` on the current `<TxDtls>`, which stands apart in a batch entry
+    fn capture_detail(&mut self, text: &str) {
+        let debtor_account = self.in_debtor_account_id();
+        let creditor_account = self.in_creditor_account_id();
+        let ends = |suffix: &[ElementName]| self.path_ends_with(suffix);
+        let amount = ends(&[ElementName::TransactionDetails, ElementName::Amount])
+            || ends(&[
+                ElementName::AmountDetails,
+                ElementName::TransactionAmount,
+                ElementName::Amount,
+            ]);
+        let references =
+            |id: ElementName| ends(&[ElementName::TransactionDetails, ElementName::References, id]);
+        let (tx_id, end_to_end_id, mandate_id) = (
+            references(ElementName::TransactionId),
+            references(ElementName::EndToEndId),
+            references(ElementName::MandateId),
+        );
+        let name = |party: ElementName| {
+            ends(&[
+                ElementName::TransactionDetails,
+                ElementName::RelatedParties,
+                party,
+                ElementName::Name,
+            ])
+        };
+        let (debtor_name, creditor_name) = (name(ElementName::Debtor), name(ElementName::Creditor));
+        let description = ends(&[
+            ElementName::TransactionDetails,
+            ElementName::RemittanceInfo,
+            ElementName::UnstructuredRemittance,
+        ]);
+
+        let Some(detail) = self
+            .entry_scratch
+            .as_mut()
+            .and_then(|entry| entry.details.last_mut())
+        else {
+            return;
+        };
+        let value = Some(text.to_string());
+        if amount {
+            detail.amount = value;
+        } else if tx_id {
+            detail.tx_id = value;
+        } else if end_to_end_id {
+            detail.end_to_end_id = value;
+        } else if mandate_id {
+            detail.mandate_id = value;
+        } else if debtor_name {
+            detail.debtor_name = value;
+        } else if creditor_name {
+            detail.creditor_name = value;
+        } else if debtor_account {
+            detail.debtor_account = value;
+        } else if creditor_account {
+            detail.creditor_account = value;
+        } else if description {
+            detail.description.push(text.to_string());
+        }
+    }
+
+    /// Record `text` on the charge being read
+    fn capture_charge(&mut self, text: &str) {
+        let in_details = self.path.contains(&ElementName::TransactionDetails);
+        let Some(&element) = self.path.last() else {
+            return;
+        };
+        let parent = self.path.len().checked_sub(2).map(|index| self.path[index]);
+        let Some(charge) = self
+            .entry_scratch
+            .as_mut()
+            .and_then(|entry| entry.current_charge(in_details))
+        else {
+            return;
+        };
+        let value = Some(text.trim().to_string());
+        match (parent, element) {
+            (Some(ElementName::Charges | ElementName::ChargesRecord), ElementName::Amount) => {
+                charge.amount = value
+            }
+            (Some(ElementName::Charges | ElementName::ChargesRecord), ElementName::CreditDebit) => {
+                charge.indicator = value
+            }
+            (
+                Some(ElementName::Charges | ElementName::ChargesRecord),
+                ElementName::ChargeBearer,
+            ) => charge.bearer = value,
+            (Some(ElementName::BalanceType), ElementName::Code) => charge.code = value,
+            _ => {}
+        }
+    }
+
+    /// Message type of the document root, if one was seen
+    pub(super) fn message(&self) -> Option<CamtMessage> {
+        self.message
+    }
+
+    /// Elements open at the current event, e.g. `Document/BkToCstmrStmt/Stmt/Bal`;
+    /// elements the parser does not know show as `*`
+    pub(super) fn location_path(&self) -> String {
+        self.path
+            .iter()
+            .map(|element| match element {
+                ElementName::Other => "*".to_string(),
+                element => element.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Whether the parser is inside an `<Ntry>`; balances always precede the entries
+    pub(super) fn in_entries(&self) -> bool {
+        self.path.contains(&ElementName::Entry)
+    }
+
+    /// Statements of the document, one per `<Stmt>`, `<Rpt>` or `<Ntfctn>` block.
+    ///
+    /// Documents without such blocks yield the single statement read from their
+    /// account, balance and entry elements.
+    pub(super) fn into_statements(mut self) -> Result<Vec<super::Camt053Statement>, ParseError> {
+        // A last page announcing further pages still yields its statement
+        if self.statements.is_empty() || self.merged {
+            self.finish_statement()?;
+        }
+        // Message-level supplementary data follows the statements it belongs to
+        for statement in &mut self.statements {
+            statement
+                .supplementary_data
+                .extend(self.supplementary_data.iter().cloned());
+        }
+        Ok(self.statements)
+    }
+
+    /// Keep the raw contents of a `<SplmtryData>` block, on the current entry if any
+    pub(super) fn add_supplementary_data(&mut self, xml: String) {
+        match self.entry_scratch.as_mut() {
+            Some(entry) => entry.supplementary_data.push(xml),
+            None => self.supplementary_data.push(xml),
+        }
+    }
+
+    /// A version 02 `<Stmt>` has no `<StmtPgntn>`; a page other than the last one is
+    /// recognised by closing with an interim (`ITBD`) rather than a closing booked balance
+    fn is_unmarked_page(&self) -> bool {
+        self.message == Some(CamtMessage::Statement)
+            && self.info.page_number.is_none()
+            && self.closing_date.is_none()
+            && self.last_interim_balance.is_some()
+    }
+
+    /// Build the statement read so far and start over for the next block
+    fn finish_statement(&mut self) -> Result<(), ParseError> {
+        let mut block = std::mem::take(self);
+        self.message = block.message;
+        self.path = std::mem::take(&mut block.path);
+        self.statements = std::mem::take(&mut block.statements);
+
+        let statement = block.build_statement()?;
+        self.statements.push(statement);
+        Ok(())
+    }
+
+    fn build_statement(mut self) -> Result<super::Camt053Statement, ParseError> {
+        self.apply_balance_fallbacks();
+        let header = self.header()?;
+        if self.merged {
+            self.info.page_number = None;
+            // Interim balances of merged pages only mark the page boundaries
+            self.balances
+                .retain(|balance| balance.code != BalanceCode::InterimBooked);
+        }
+
+        // Entry currencies are only kept where they differ from the account's
+        for transaction in &mut self.transactions {
+            if transaction.currency.as_deref() == Some(header.currency.as_str()) {
+                transaction.currency = None;
+            }
+        }
+
+        Ok(super::Camt053Statement {
+            account_number: header.account_number,
+            currency: header.currency,
+            opening_balance: header.opening_balance,
+            opening_date: header.opening_date,
+            opening_indicator: header.opening_indicator,
+            closing_balance: header.closing_balance,
+            closing_date: header.closing_date,
+            closing_indicator: header.closing_indicator,
+            transactions: self.transactions,
+            supplementary_data: self.supplementary_data,
+            balances: self.balances,
+            info: self.info,
+        })
+    }
+
+    /// Account and balances seen so far; fallbacks derived from entries are not applied
+    pub(super) fn header(&self) -> Result<StatementHeader, ParseError> {
         let account_number = self
             .account_number
+            .clone()
             .ok_or_else(|| ParseError::MissingField("account_number".into()))?;
         let currency = self
             .currency
+            .clone()
             .ok_or_else(|| ParseError::MissingField("currency".into()))?;
 
-        Ok(super::Camt053Statement {
+        Ok(StatementHeader {
             account_number,
             currency,
             opening_balance: self.opening_balance.unwrap_or(0.0),
@@ -191,6 +597,7 @@ impl CamtParser {
                 .ok_or_else(|| ParseError::MissingField("opening_date".into()))?,
             opening_indicator: self
                 .opening_indicator
+                .clone()
                 .ok_or_else(|| ParseError::MissingField("opening_indicator".into()))?,
             closing_balance: self.closing_balance.unwrap_or(0.0),
             closing_date: self
@@ -198,22 +605,123 @@ impl CamtParser {
                 .ok_or_else(|| ParseError::MissingField("closing_date".into()))?,
             closing_indicator: self
                 .closing_indicator
+                .clone()
                 .ok_or_else(|| ParseError::MissingField("closing_indicator".into()))?,
-            transactions: self.transactions,
         })
     }
 
     fn finish_balance(&mut self) {
         if let Some(balance_type) = self.balance_scratch.balance_type.as_deref() {
             match balance_type.to_uppercase().as_str() {
-                OPBD_BALANCE_TYPE => self.apply_balance(BalanceKind::Opening),
+                OPBD_BALANCE_TYPE => {
+                    self.opening_booked = true;
+                    self.apply_balance(BalanceKind::Opening);
+                }
+                // German (DK) statements open with the previous closing balance
+                // (PRCD) instead, which only counts when there is no OPBD
+                PRCD_BALANCE_TYPE if !self.opening_booked => {
+                    self.apply_balance(BalanceKind::Opening)
+                }
+                PRCD_BALANCE_TYPE => {}
                 CLBD_BALANCE_TYPE => self.apply_balance(BalanceKind::Closing),
-                _ => {}
+                ITBD_BALANCE_TYPE => {
+                    self.last_interim_balance = Some(self.balance_scratch.clone());
+                    self.push_balance(BalanceCode::InterimBooked);
+                }
+                code => {
+                    if let Ok(code) = code.parse() {
+                        self.push_balance(code);
+                    }
+                }
             }
         }
         self.balance_scratch.clear();
     }
 
+    /// Keep the balance read so far as one of the statement's additional balances
+    fn push_balance(&mut self, code: BalanceCode) {
+        let scratch = &self.balance_scratch;
+        let (Some(amount), Some(indicator), Some(date)) = (
+            scratch
+                .amount
+                .as_deref()
+                .and_then(|text| camt053_utils::parse_amount(text).ok()),
+            scratch
+                .indicator
+                .as_deref()
+                .and_then(|text| camt053_utils::parse_balance_indicator(text).ok()),
+            scratch
+                .date
+                .as_deref()
+                .and_then(|text| camt053_utils::parse_xml_date(text).ok()),
+        ) else {
+            return;
+        };
+        self.balances.push(Balance {
+            code,
+            amount,
+            indicator,
+            date,
+        });
+    }
+
+    /// Intra-day reports often carry only interim booked (`ITBD`) balances: the
+    /// last one stands in for a missing `CLBD`, and a missing opening balance is
+    /// derived from it by reversing the reported entries.
+    ///
+    /// Debit/credit notifications carry no balances at all; they open at zero on
+    /// the first entry's booking date and close at the net of their entries.
+    fn apply_balance_fallbacks(&mut self) {
+        if self.closing_date.is_none() {
+            if let Some(interim) = self.last_interim_balance.take() {
+                self.balance_scratch = interim;
+                self.apply_balance(BalanceKind::Closing);
+            }
+        }
+
+        if self.message == Some(CamtMessage::Notification) && self.closing_date.is_none() {
+            self.apply_notification_balances();
+        }
+
+        if self.opening_date.is_some() {
+            return;
+        }
+        let (Some(closing), Some(indicator), Some(date)) = (
+            self.closing_balance,
+            self.closing_indicator.clone(),
+            self.closing_date,
+        ) else {
+            return;
+        };
+
+        let signed_closing = match indicator {
+            BalanceType::Credit => closing,
+            BalanceType::Debit => -closing,
+        };
+        let opening = round_cents(signed_closing - net_amount(&self.transactions));
+
+        self.opening_balance = Some(opening.abs());
+        self.opening_indicator = Some(indicator_for(opening));
+        self.opening_date = Some(date);
+    }
+
+    /// Zero opening balance on the first entry and the net of all entries as closing balance
+    fn apply_notification_balances(&mut self) {
+        let (Some(first), Some(last)) = (self.transactions.first(), self.transactions.last())
+        else {
+            return;
+        };
+        let (opening_date, closing_date) = (first.booking_date, last.booking_date);
+        let net = net_amount(&self.transactions);
+
+        self.opening_balance = Some(0.0);
+        self.opening_indicator = Some(BalanceType::Credit);
+        self.opening_date = Some(opening_date);
+        self.closing_balance = Some(net.abs());
+        self.closing_indicator = Some(indicator_for(net));
+        self.closing_date = Some(closing_date);
+    }
+
     fn apply_balance(&mut self, kind: BalanceKind) {
         if let Some(amount_text) = self.balance_scratch.amount.as_deref() {
             if let Ok(amount) = camt053_utils::parse_amount(amount_text) {
@@ -245,36 +753,68 @@ impl CamtParser {
 
     fn finish_entry(&mut self) {
         if let Some(entry) = self.entry_scratch.take() {
-            if let Ok(Some(tx)) = entry.finish() {
-                self.transactions.push(tx);
+            match entry.finish() {
+                Ok(tx) => self.transactions.push(tx),
+                Err(error) => {
+                    let path = format!("{}/{}", self.location_path(), ElementName::Entry);
+                    self.skipped.push((error, path));
+                }
             }
         }
     }
 
-    fn capture_currency(&mut self, attributes: Attributes<'_>) -> Result<(), ParseError> {
-        if self.currency.is_some() {
-            return Ok(());
-        }
+    /// Transactions completed since the last call, for readers that stream
+    /// entries instead of collecting them into statements
+    pub(super) fn take_transactions(&mut self) -> Vec<Transaction> {
+        std::mem::take(&mut self.transactions)
+    }
+
+    /// Number of statement blocks completed so far
+    pub(super) fn finished_statements(&self) -> usize {
+        self.statements.len()
+    }
 
-        for attr in attributes {
-            let attr = attr
-                .map_err(|err| ParseError::Camt053Error(format!("XML attribute error: {}", err)))?;
+    /// Entries that could not become transactions since the last call, with
+    /// the path of their `<Ntry>`
+    pub(super) fn drain_skipped(&mut self) -> std::vec::Drain<'_, (ParseError, String)> {
+        self.skipped.drain(..)
+    }
 
-            let key_str = std::str::from_utf8(attr.key.as_ref()).map_err(|err| {
-                ParseError::Camt053Error(format!("Invalid attribute key encoding: {}", err))
-            })?;
+    /// Record the `Ccy` attribute of an `<Amt>`: the entry currency, the
+    /// instructed currency, or the statement currency if none is known yet
+    fn capture_currency(&mut self, attributes: Attributes<'_>) -> Result<(), ParseError> {
+        let Some(currency) = currency_attribute(attributes)? else {
+            return Ok(());
+        };
 
-            if key_str == ElementName::Currency.to_string() {
-                let value = String::from_utf8(attr.value.as_ref().to_vec()).map_err(|err| {
-                    ParseError::Camt053Error(format!("Invalid currency encoding: {}", err))
-                })?;
-                if !value.trim().is_empty() {
-                    self.currency = Some(value);
-                }
-                break;
+        if self.path_ends_with(&[ElementName::Entry, ElementName::Amount]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.currency = Some(currency.clone());
+            }
+        } else if self.path.contains(&ElementName::Charges) {
+            let in_details = self.path.contains(&ElementName::TransactionDetails);
+            if let Some(charge) = self
+                .entry_scratch
+                .as_mut()
+                .and_then(|entry| entry.current_charge(in_details))
+            {
+                charge.currency = Some(currency);
+            }
+            return Ok(());
+        } else if self.path_ends_with(&[
+            ElementName::AmountDetails,
+            ElementName::InstructedAmount,
+            ElementName::Amount,
+        ]) {
+            if let Some(entry) = self.entry_scratch.as_mut() {
+                entry.original_currency = Some(currency);
             }
+            return Ok(());
         }
 
+        if self.currency.is_none() {
+            self.currency = Some(currency);
+        }
         Ok(())
     }
 
@@ -295,6 +835,16 @@ impl CamtParser {
         }
     }
 
+    /// Whether `name` is the `<Pty>` that wraps the party of a `<Dbtr>` or `<Cdtr>` since
+    /// camt.053.001.08; it stays out of the path so all versions match the same paths
+    fn is_party_wrapper(&self, name: ElementName) -> bool {
+        name == ElementName::Party
+            && matches!(
+                self.path.last(),
+                Some(ElementName::Debtor | ElementName::Creditor)
+            )
+    }
+
     fn path_ends_with(&self, suffix: &[ElementName]) -> bool {
         if self.path.len() < suffix.len() {
             return false;
@@ -303,16 +853,72 @@ impl CamtParser {
         self.path[offset..] == *suffix
     }
 
+    /// Whether `suffix` is nested directly in a `<Stmt>`, `<Rpt>` or `<Ntfctn>` block
+    fn in_statement_element(&self, suffix: &[ElementName]) -> bool {
+        let Some(offset) = self.path.len().checked_sub(suffix.len() + 1) else {
+            return false;
+        };
+        matches!(
+            self.path[offset],
+            ElementName::Stmt | ElementName::Report | ElementName::Notification
+        ) && self.path[offset + 1..] == *suffix
+    }
+
+    fn in_servicer_bic(&self) -> bool {
+        [ElementName::Bic, ElementName::BicFi]
+            .into_iter()
+            .any(|bic| {
+                self.in_statement_element(&[
+                    ElementName::Acct,
+                    ElementName::Servicer,
+                    ElementName::FinancialInstitutionId,
+                    bic,
+                ])
+            })
+    }
+
     fn in_statement_account_id(&self) -> bool {
         self.path_ends_with(&[ElementName::Acct, ElementName::Id, ElementName::Iban])
             || self.path_ends_with(&[
                 ElementName::Acct,
                 ElementName::Id,
-                ElementName::Other,
+                ElementName::OtherId,
                 ElementName::Id,
             ])
     }
 
+    /// Party (`Dbtr` or `Cdtr`) and element of the current `<PstlAdr>` child in
+    /// the related parties, with or without the `<Pty>` wrapper of newer versions
+    fn address_part(&self) -> Option<(ElementName, ElementName)> {
+        let (part, parents) = self.path.split_last()?;
+        let (address, parents) = parents.split_last()?;
+        if *address != ElementName::PostalAddress {
+            return None;
+        }
+        let mut parents = parents.iter().rev();
+        let party = parents
+            .by_ref()
+            .take(2)
+            .find(|element| matches!(element, ElementName::Debtor | ElementName::Creditor))?;
+        parents
+            .any(|element| *element == ElementName::RelatedParties)
+            .then_some((*party, *part))
+    }
+
+    fn in_agent_bic(&self, agent: ElementName) -> bool {
+        self.path_ends_with(&[
+            ElementName::RelatedAgents,
+            agent,
+            ElementName::FinancialInstitutionId,
+            ElementName::Bic,
+        ]) || self.path_ends_with(&[
+            ElementName::RelatedAgents,
+            agent,
+            ElementName::FinancialInstitutionId,
+            ElementName::BicFi,
+        ])
+    }
+
     fn in_debtor_account_id(&self) -> bool {
         self.path_ends_with(&[
             ElementName::Entry,
@@ -329,7 +935,7 @@ impl CamtParser {
             ElementName::RelatedParties,
             ElementName::DebtorAccount,
             ElementName::Id,
-            ElementName::Other,
+            ElementName::OtherId,
             ElementName::Id,
         ])
     }
@@ -350,7 +956,7 @@ impl CamtParser {
             ElementName::RelatedParties,
             ElementName::CreditorAccount,
             ElementName::Id,
-            ElementName::Other,
+            ElementName::OtherId,
             ElementName::Id,
         ])
     }
@@ -361,6 +967,44 @@ enum BalanceKind {
     Closing,
 }
 
+/// Sum of booked credits minus debits, rounded to cents
+fn net_amount(transactions: &[Transaction]) -> f64 {
+    round_cents(transactions.iter().map(paging::booked_amount).sum())
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Balance indicator for a signed amount
+fn indicator_for(amount: f64) -> BalanceType {
+    if amount < 0.0 {
+        BalanceType::Debit
+    } else {
+        BalanceType::Credit
+    }
+}
+
+/// Non-empty `Ccy` attribute of an element
+fn currency_attribute(attributes: Attributes<'_>) -> Result<Option<String>, ParseError> {
+    for attr in attributes {
+        let attr =
+            attr.map_err(|err| ParseError::Camt053Error(format!("XML attribute error: {}", err)))?;
+
+        let key_str = std::str::from_utf8(attr.key.as_ref()).map_err(|err| {
+            ParseError::Camt053Error(format!("Invalid attribute key encoding: {}", err))
+        })?;
+
+        if key_str == ElementName::Currency.to_string() {
+            let value = String::from_utf8(attr.value.as_ref().to_vec()).map_err(|err| {
+                ParseError::Camt053Error(format!("Invalid currency encoding: {}", err))
+            })?;
+            return Ok(Some(value).filter(|value| !value.trim().is_empty()));
+        }
+    }
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::model::{BalanceType, TransactionType};
@@ -526,4 +1170,46 @@ mod tests {
         assert_eq!(statement.opening_balance, 100.00);
         assert_eq!(statement.closing_balance, 200.00);
     }
+
+    #[test]
+    fn test_parse_camt053_prefers_opbd_over_prcd() {
+        let balance = |code: &str, amount: &str, date: &str| {
+            format!(
+                "<Bal><Tp><CdOrPrtry><Cd>{}</Cd></CdOrPrtry></Tp>\
+                 <Amt Ccy=\"DKK\">{}</Amt><CdtDbtInd>CRDT</CdtDbtInd>\
+                 <Dt><Dt>{}</Dt></Dt></Bal>",
+                code, amount, date
+            )
+        };
+        let document = |balances: &[String]| {
+            format!(
+                "<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:camt.053.001.02\">\
+                 <BkToCstmrStmt><Stmt><Acct><Id><IBAN>DK8030000001234567</IBAN></Id>\
+                 <Ccy>DKK</Ccy></Acct>{}</Stmt></BkToCstmrStmt></Document>",
+                balances.concat()
+            )
+        };
+        let prcd = balance("PRCD", "90.00", "2023-04-19");
+        let opbd = balance("OPBD", "100.00", "2023-04-20");
+        let clbd = balance("CLBD", "200.00", "2023-04-20");
+
+        // OPBD wins in either order
+        for balances in [
+            [opbd.clone(), prcd.clone(), clbd.clone()],
+            [prcd.clone(), opbd.clone(), clbd.clone()],
+        ] {
+            let xml = document(&balances);
+            let statement = super::super::Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+            assert_eq!(statement.opening_balance, 100.00);
+            assert_eq!(
+                statement.opening_date.date_naive().to_string(),
+                "2023-04-20"
+            );
+        }
+
+        // Without OPBD, PRCD opens the statement
+        let xml = document(&[prcd, clbd]);
+        let statement = super::super::Camt053Statement::from_read(&mut xml.as_bytes()).unwrap();
+        assert_eq!(statement.opening_balance, 90.00);
+    }
 }
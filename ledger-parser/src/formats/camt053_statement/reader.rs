@@ -0,0 +1,337 @@
+use std::collections::VecDeque;
+use std::io::{BufReader, Read};
+
+use quick_xml::events::Event;
+
+use crate::diagnostics::{Diagnostics, ParseWarning};
+use crate::error::{ErrorLocation, ParseError};
+use crate::model::{StatementHeader, Transaction};
+use crate::quirks::ParseOptions;
+
+use super::elements::ElementName;
+use super::parser::CamtParser;
+use super::{feed_event, read_error, xml_location};
+
+/// First byte of the UTF-8 encoding of U+00A0 (no-break space)
+const NBSP_LEAD: u8 = 0xc2;
+/// Second byte of the UTF-8 encoding of U+00A0 (no-break space)
+const NBSP_TRAIL: u8 = 0xa0;
+
+/// Streaming reader yielding the transactions of a CAMT.053 document one by
+/// one, in constant memory.
+///
+/// [`Camt053Statement::from_read`](crate::Camt053Statement::from_read) loads
+/// the whole document before parsing it; this reader parses the `quick-xml`
+/// event stream as it arrives and hands out every `<Ntry>` once it is
+/// complete, so statements of hundreds of megabytes can be processed entry by
+/// entry. Documents with several statements yield the entries of all of them
+/// in document order.
+///
+/// Account and balances precede the entries in the schema: [`header`] is
+/// available once the first transaction was returned, or after the last
+/// `None` for documents without entries. It holds the balances as written;
+/// unlike the buffered parser, the reader cannot derive missing ones from the
+/// entries. Raw `<SplmtryData>` blocks are not kept.
+///
+/// Malformed entries are skipped with a [`ParseWarning`] (see [`warnings`]),
+/// or fail the iteration with [`ParseOptions::strict`](crate::ParseOptions).
+/// After the first error the iterator is exhausted.
+///
+/// [`header`]: Camt053Reader::header
+/// [`warnings`]: Camt053Reader::warnings
+///
+/// # Example
+/// ```
+/// use ledger_parser::Camt053Reader;
+///
+/// let xml = r#"<Document><BkToCstmrStmt><Stmt>
+///     <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>
+///     <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+///     <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">75.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-21</Dt></Dt></Bal>
+///     <Ntry><Amt Ccy="EUR">25.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-04-21</Dt></BookgDt></Ntry>
+/// </Stmt></BkToCstmrStmt></Document>"#;
+///
+/// let mut reader = Camt053Reader::new(xml.as_bytes());
+/// let first = reader.next().unwrap().unwrap();
+/// assert_eq!(first.amount, 25.0);
+/// assert_eq!(reader.header().unwrap().closing_balance, 75.0);
+/// assert!(reader.next().is_none());
+/// ```
+pub struct Camt053Reader<R: Read> {
+    xml: quick_xml::Reader<BufReader<NoBreakSpaces<R>>>,
+    parser: CamtParser,
+    diagnostics: Diagnostics,
+    buf: Vec<u8>,
+    /// Transactions completed but not yet returned
+    ready: VecDeque<Transaction>,
+    /// Account and balances of the statement whose entries are being read
+    header: Option<StatementHeader>,
+    /// Statement blocks completed when `header` was taken
+    header_statement: Option<usize>,
+    /// Nesting depth of the `<SplmtryData>` block being skipped
+    supplementary_depth: Option<usize>,
+    /// End of the document or an error was reached
+    finished: bool,
+}
+
+impl<R: Read> Camt053Reader<R> {
+    /// Stream the transactions of `reader`, skipping malformed entries with a warning
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, &ParseOptions::default())
+    }
+
+    /// Stream the transactions of `reader`; `options.strict` fails on malformed entries
+    pub fn with_options(reader: R, options: &ParseOptions) -> Self {
+        let xml = quick_xml::Reader::from_reader(BufReader::new(NoBreakSpaces::new(reader)));
+        Camt053Reader {
+            xml,
+            parser: CamtParser::default(),
+            diagnostics: Diagnostics::new(options.strict),
+            buf: Vec::new(),
+            ready: VecDeque::new(),
+            header: None,
+            header_statement: None,
+            supplementary_depth: None,
+            finished: false,
+        }
+    }
+
+    /// Account and balances of the statement read so far, if the document has
+    /// them; see the type documentation for when they are known
+    pub fn header(&self) -> Option<&StatementHeader> {
+        self.header.as_ref()
+    }
+
+    /// Warnings of the entries skipped so far, in document order
+    pub fn warnings(&self) -> &[ParseWarning] {
+        self.diagnostics.warnings()
+    }
+
+    /// Read events until a transaction is complete or the document ends
+    fn advance(&mut self) -> Result<(), ParseError> {
+        while self.ready.is_empty() && !self.finished {
+            self.buf.clear();
+            let event = self.xml.read_event_into(&mut self.buf).map_err(|e| {
+                read_error(e, xml_location(&self.parser, self.xml.buffer_position()))
+            })?;
+            if skip_supplementary(&mut self.supplementary_depth, &event) {
+                continue;
+            }
+
+            let offset = self.xml.buffer_position();
+            let more = feed_event(&mut self.parser, event)
+                .map_err(|e| e.at(xml_location(&self.parser, offset)))?;
+            for (error, path) in self.parser.drain_skipped() {
+                self.diagnostics
+                    .skip(error.at(ErrorLocation::Xml { path, offset }))?;
+            }
+
+            if self.parser.in_entries() && self.header_statement.is_none() {
+                self.header = self.parser.header().ok();
+                self.header_statement = Some(self.parser.finished_statements());
+            }
+            if self.header_statement != Some(self.parser.finished_statements()) {
+                // The statement whose header was taken has ended
+                self.header_statement = None;
+            }
+
+            let currency = self.header.as_ref().map(|header| header.currency.as_str());
+            for mut transaction in self.parser.take_transactions() {
+                // Entry currencies are only kept where they differ from the account's
+                if transaction.currency.is_some() && transaction.currency.as_deref() == currency {
+                    transaction.currency = None;
+                }
+                self.ready.push_back(transaction);
+            }
+
+            if !more {
+                self.finish()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `event` belongs to a `<SplmtryData>` block, which is not parsed;
+/// `depth` tracks the nesting of the block being skipped
+fn skip_supplementary(depth: &mut Option<usize>, event: &Event<'_>) -> bool {
+    let is_supplementary = |name: &[u8]| {
+        matches!(
+            ElementName::from_name_bytes(name),
+            Ok(ElementName::SupplementaryData)
+        )
+    };
+    match (event, depth.as_mut()) {
+        (Event::Start(e), None) if is_supplementary(e.name().as_ref()) => {
+            *depth = Some(0);
+            true
+        }
+        (Event::Start(e), Some(nested)) if is_supplementary(e.name().as_ref()) => {
+            *nested += 1;
+            true
+        }
+        (Event::End(e), Some(0)) if is_supplementary(e.name().as_ref()) => {
+            *depth = None;
+            true
+        }
+        (Event::End(e), Some(nested)) if is_supplementary(e.name().as_ref()) => {
+            *nested -= 1;
+            true
+        }
+        (Event::Eof, _) => false,
+        (_, nested) => nested.is_some(),
+    }
+}
+
+impl<R: Read> Camt053Reader<R> {
+    /// Complete the document: the header of a document without entries comes
+    /// from its (last) statement
+    fn finish(&mut self) -> Result<(), ParseError> {
+        self.finished = true;
+        let statements = std::mem::take(&mut self.parser).into_statements()?;
+        if self.header.is_none() {
+            self.header = statements.last().map(|statement| StatementHeader {
+                account_number: statement.account_number.clone(),
+                currency: statement.currency.clone(),
+                opening_balance: statement.opening_balance,
+                opening_date: statement.opening_date,
+                opening_indicator: statement.opening_indicator.clone(),
+                closing_balance: statement.closing_balance,
+                closing_date: statement.closing_date,
+                closing_indicator: statement.closing_indicator.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Camt053Reader<R> {
+    type Item = Result<Transaction, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(error) = self.advance() {
+            self.finished = true;
+            self.ready.clear();
+            return Some(Err(error));
+        }
+        self.ready.pop_front().map(Ok)
+    }
+}
+
+/// Reader replacing no-break spaces with plain ones, as the buffered parser
+/// does, since some banks put them between XML attributes
+struct NoBreakSpaces<R> {
+    inner: R,
+    /// A no-break space lead byte ended the previous read
+    pending_lead: bool,
+}
+
+impl<R: Read> NoBreakSpaces<R> {
+    fn new(inner: R) -> Self {
+        NoBreakSpaces {
+            inner,
+            pending_lead: false,
+        }
+    }
+}
+
+impl<R: Read> Read for NoBreakSpaces<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // A held-back lead byte goes in front of the fresh input
+        let start = usize::from(self.pending_lead);
+        if self.pending_lead {
+            buf[0] = NBSP_LEAD;
+            self.pending_lead = false;
+        }
+        let read = self.inner.read(&mut buf[start..])?;
+        let mut len = start + read;
+        if read == 0 {
+            return Ok(len);
+        }
+        if buf[len - 1] == NBSP_LEAD && len > 1 {
+            // The trailing byte of a no-break space may come with the next read
+            self.pending_lead = true;
+            len -= 1;
+        }
+
+        let mut written = 0;
+        let mut index = 0;
+        while index < len {
+            if buf[index] == NBSP_LEAD && buf.get(index + 1) == Some(&NBSP_TRAIL) && index + 1 < len
+            {
+                buf[written] = b' ';
+                index += 2;
+            } else {
+                buf[written] = buf[index];
+                index += 1;
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Camt053Statement;
+
+    /// Statement of two entries, the second one malformed
+    const CAMT053: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"><BkToCstmrStmt><Stmt>
+    <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>
+    <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-20</Dt></Dt></Bal>
+    <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">125.50</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-04-21</Dt></Dt></Bal>
+    <Ntry><Amt Ccy="EUR">25.50</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-04-21</Dt></BookgDt>
+        <SplmtryData><Envlp><Amt Ccy="USD">1.00</Amt></Envlp></SplmtryData>
+        <NtryDtls><TxDtls><RmtInf><Ustrd>Invoice 17</Ustrd></RmtInf></TxDtls></NtryDtls></Ntry>
+    <Ntry><Amt Ccy="EUR">n/a</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-04-21</Dt></BookgDt></Ntry>
+</Stmt></BkToCstmrStmt></Document>"#;
+
+    /// Reader handing out its input a few bytes at a time
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_streams_the_entries_of_the_buffered_parser() {
+        let mut reader = Camt053Reader::new(Trickle(CAMT053.as_bytes()));
+        assert!(reader.header().is_none());
+        let transactions: Vec<Transaction> = reader.by_ref().map(Result::unwrap).collect();
+        assert_eq!(reader.warnings().len(), 1);
+        assert!(reader.warnings()[0].message.contains("n/a"));
+
+        let buffered = Camt053Statement::from_read(&mut CAMT053.as_bytes()).unwrap();
+        let mut expected = buffered.transactions.clone();
+        expected[0].supplementary_data.clear();
+        assert_eq!(transactions, expected);
+        let header = reader.header().unwrap();
+        assert_eq!(header.account_number, buffered.account_number);
+        assert_eq!(header.closing_balance, buffered.closing_balance);
+
+        let mut strict = Camt053Reader::with_options(CAMT053.as_bytes(), &ParseOptions::strict());
+        assert!(strict.next().unwrap().is_ok());
+        assert!(strict.next().unwrap().is_err());
+        assert!(strict.next().is_none());
+    }
+
+    #[test]
+    fn test_no_break_spaces_are_replaced_across_reads() {
+        let text = "a\u{a0}b\u{a0}\u{a0}c é";
+        let mut output = String::new();
+        NoBreakSpaces::new(Trickle(text.as_bytes()))
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "a b  c é");
+    }
+}
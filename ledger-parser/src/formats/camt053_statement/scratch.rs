@@ -1,9 +1,13 @@
 use crate::error::ParseError;
-use crate::model::Transaction;
+use crate::model::{
+    Charge, PostalAddress, RemittanceInfo, Transaction, TransactionDetail, TransactionType,
+};
 
+use super::camt053_const::NOT_PROVIDED;
 use super::camt053_utils;
+use super::elements::ElementName;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub(super) struct BalanceScratch {
     pub balance_type: Option<String>,
     pub amount: Option<String>,
@@ -24,16 +28,142 @@ impl BalanceScratch {
 pub(super) struct EntryScratch {
     pub amount: Option<String>,
     pub indicator: Option<String>,
+    pub status: Option<String>,
+    pub reversal: Option<String>,
+    pub supplementary_data: Vec<String>,
     pub booking_date: Option<String>,
     pub value_date: Option<String>,
     pub ntry_ref: Option<String>,
     pub tx_id: Option<String>,
+    pub end_to_end_id: Option<String>,
+    pub mandate_id: Option<String>,
     pub description: String,
     pub counterparty_name: Option<String>,
     pub counterparty_account: Option<String>,
+    pub currency: Option<String>,
+    pub original_amount: Option<String>,
+    pub original_currency: Option<String>,
+    pub exchange_rate: Option<String>,
+    /// Whether the entry has a `<Strd>` remittance block
+    pub structured: bool,
+    pub creditor_ref: Option<String>,
+    pub ref_type: Option<String>,
+    pub additional_remittance: Vec<String>,
+    pub debtor_bic: Option<String>,
+    pub creditor_bic: Option<String>,
+    pub debtor_address: PostalAddress,
+    pub creditor_address: PostalAddress,
+    /// One per `<TxDtls>`, kept apart when the entry is a batch of several
+    pub details: Vec<DetailScratch>,
+    /// One per `<Chrgs>` or `<Rcrd>` of the entry itself
+    pub charges: Vec<ChargeScratch>,
+    /// One per `<Chrgs>` or `<Rcrd>` of its `<TxDtls>`, used when the entry has none
+    pub detail_charges: Vec<ChargeScratch>,
+}
+
+/// Fields of one charge; a version 2 `<Chrgs>` holds them directly, later
+/// versions in `<Rcrd>` children, so a `<Chrgs>` without an amount is dropped
+#[derive(Default)]
+pub(super) struct ChargeScratch {
+    pub amount: Option<String>,
+    pub currency: Option<String>,
+    pub indicator: Option<String>,
+    pub code: Option<String>,
+    pub bearer: Option<String>,
+}
+
+impl ChargeScratch {
+    fn finish(self) -> Option<Charge> {
+        let amount = camt053_utils::parse_amount(self.amount.as_deref()?).ok()?;
+        // Banks leave out the indicator of the common case, a fee paid
+        let charge_type = self
+            .indicator
+            .as_deref()
+            .and_then(|value| camt053_utils::parse_transaction_type(value).ok())
+            .unwrap_or(TransactionType::Debit);
+        Some(Charge {
+            amount,
+            currency: self.currency,
+            charge_type,
+            code: self.code,
+            bearer: self.bearer,
+        })
+    }
+}
+
+/// Fields of one `<TxDtls>`; the party fields are resolved once the direction is known
+#[derive(Default)]
+pub(super) struct DetailScratch {
+    pub amount: Option<String>,
+    pub tx_id: Option<String>,
+    pub end_to_end_id: Option<String>,
+    pub mandate_id: Option<String>,
+    pub debtor_name: Option<String>,
+    pub creditor_name: Option<String>,
+    pub debtor_account: Option<String>,
+    pub creditor_account: Option<String>,
+    pub description: Vec<String>,
+}
+
+impl DetailScratch {
+    fn finish(self, transaction_type: &TransactionType) -> TransactionDetail {
+        let (name, account, other_name, other_account) = match transaction_type {
+            TransactionType::Credit => (
+                self.debtor_name,
+                self.debtor_account,
+                self.creditor_name,
+                self.creditor_account,
+            ),
+            TransactionType::Debit => (
+                self.creditor_name,
+                self.creditor_account,
+                self.debtor_name,
+                self.debtor_account,
+            ),
+        };
+        TransactionDetail {
+            amount: self
+                .amount
+                .as_deref()
+                .and_then(|value| camt053_utils::parse_amount(value).ok()),
+            reference: self.tx_id,
+            end_to_end_id: self.end_to_end_id.filter(|id| id != NOT_PROVIDED),
+            mandate_id: self.mandate_id,
+            counterparty_name: name.or(other_name),
+            counterparty_account: account.or(other_account),
+            description: self.description.join(" "),
+        }
+    }
 }
 
 impl EntryScratch {
+    /// Store `text` of the `<PstlAdr>` child `part` in the address of `party`
+    pub(super) fn set_address_part(&mut self, party: ElementName, part: ElementName, text: &str) {
+        let address = match party {
+            ElementName::Debtor => &mut self.debtor_address,
+            _ => &mut self.creditor_address,
+        };
+        let value = Some(text.to_string());
+        match part {
+            ElementName::StreetName => address.street = value,
+            ElementName::BuildingNumber => address.building_number = value,
+            ElementName::PostCode => address.postal_code = value,
+            ElementName::TownName => address.town = value,
+            ElementName::Country => address.country = value,
+            ElementName::AddressLine => address.lines.push(text.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Charge currently being read, nested in the entry or in its `<TxDtls>`
+    pub(super) fn current_charge(&mut self, in_details: bool) -> Option<&mut ChargeScratch> {
+        if in_details {
+            self.detail_charges.last_mut()
+        } else {
+            self.charges.last_mut()
+        }
+    }
+
     pub(super) fn push_description(&mut self, text: &str) {
         if !self.description.is_empty() {
             self.description.push(' ');
@@ -47,41 +177,82 @@ impl EntryScratch {
         }
     }
 
-    pub(super) fn finish(self) -> Result<Option<Transaction>, ParseError> {
-        let amount = match self
+    /// The entry as a transaction; an entry without a valid amount, indicator
+    /// or booking date cannot be one and is an error naming the element
+    pub(super) fn finish(self) -> Result<Transaction, ParseError> {
+        let missing = |element: &str| ParseError::MissingField(format!("Ntry/{}", element));
+        let amount = self
             .amount
             .as_deref()
-            .and_then(|value| camt053_utils::parse_amount(value).ok())
-        {
-            Some(value) => value,
-            None => return Ok(None),
-        };
-
-        let transaction_type = match self
+            .ok_or_else(|| missing("Amt"))
+            .and_then(camt053_utils::parse_amount)?;
+        let transaction_type = self
             .indicator
             .as_deref()
-            .and_then(|value| camt053_utils::parse_transaction_type(value).ok())
-        {
-            Some(value) => value,
-            None => return Ok(None),
-        };
-
-        let booking_date = match self
+            .ok_or_else(|| missing("CdtDbtInd"))
+            .and_then(camt053_utils::parse_transaction_type)?;
+        let booking_date = self
             .booking_date
             .as_deref()
-            .and_then(|value| camt053_utils::parse_xml_date(value).ok())
-        {
-            Some(value) => value,
-            None => return Ok(None),
-        };
+            .ok_or_else(|| missing("BookgDt"))
+            .and_then(camt053_utils::parse_xml_date)?;
 
-        let value_date = self.value_date.map(|value| value.to_string());
+        // The other party is the debtor of money received and the creditor of money paid
+        let (counterparty_bic, counterparty_address) = match transaction_type {
+            TransactionType::Credit => (
+                self.debtor_bic.or(self.creditor_bic),
+                Some(self.debtor_address).filter(|address| !address.is_empty()),
+            ),
+            TransactionType::Debit => (
+                self.creditor_bic.or(self.debtor_bic),
+                Some(self.creditor_address).filter(|address| !address.is_empty()),
+            ),
+        };
+        let value_date = self
+            .value_date
+            .as_deref()
+            .and_then(|value| camt053_utils::parse_xml_date(value).ok())
+            .map(|date| date.date_naive());
         let reference = self.tx_id.or(self.ntry_ref);
         let counterparty_name = self.counterparty_name;
         let counterparty_account = self.counterparty_account;
         let description = self.description.trim().to_string();
+        let remittance = self.structured.then_some(RemittanceInfo::Structured {
+            creditor_ref: self.creditor_ref,
+            ref_type: self.ref_type,
+            additional: self.additional_remittance,
+        });
+        // A single `<TxDtls>` is fully described by the transaction itself
+        let details = if self.details.len() > 1 {
+            self.details
+                .into_iter()
+                .map(|detail| detail.finish(&transaction_type))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut charges: Vec<Charge> = self
+            .charges
+            .into_iter()
+            .filter_map(ChargeScratch::finish)
+            .collect();
+        if charges.is_empty() {
+            charges = self
+                .detail_charges
+                .into_iter()
+                .filter_map(ChargeScratch::finish)
+                .collect();
+        }
+        let original_amount = self
+            .original_amount
+            .as_deref()
+            .and_then(|value| camt053_utils::parse_amount(value).ok());
+        let exchange_rate = self
+            .exchange_rate
+            .as_deref()
+            .and_then(|value| camt053_utils::parse_amount(value).ok());
 
-        Ok(Some(Transaction {
+        Ok(Transaction {
             booking_date,
             value_date,
             amount,
@@ -90,6 +261,27 @@ impl EntryScratch {
             reference,
             counterparty_name,
             counterparty_account,
-        }))
+            currency: self.currency,
+            original_amount,
+            original_currency: original_amount.and(self.original_currency),
+            exchange_rate,
+            remittance,
+            counterparty_bic,
+            counterparty_address,
+            end_to_end_id: self.end_to_end_id,
+            mandate_id: self.mandate_id,
+            status: self
+                .status
+                .as_deref()
+                .map(camt053_utils::parse_entry_status)
+                .unwrap_or_default(),
+            is_reversal: self
+                .reversal
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("true")),
+            supplementary_data: self.supplementary_data,
+            details,
+            charges,
+            swift_entry: None,
+        })
     }
 }
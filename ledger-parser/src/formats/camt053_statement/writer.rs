@@ -1,28 +1,55 @@
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use std::io::Write;
 
 use crate::formats::camt053_statement::camt053_const::*;
 use crate::formats::camt053_statement::elements::ElementName;
-use crate::model::{BalanceType, Transaction, TransactionType};
+use crate::model::{
+    BalanceType, Charge, EntryStatus, PostalAddress, RemittanceInfo, Transaction, TransactionType,
+};
 
-use super::{Camt053Statement, ParseError};
+use crate::formats::paging::{self, Page};
+use crate::formats::utils;
+use crate::ids::{IdContext, IdKind};
 
-/// Helper responsible for serialising `Camt053` statements into CAMT.053 XML.
+use super::{
+    Camt053Profile, Camt053Statement, Camt053Version, Camt053WriteOptions, CamtMessage, ParseError,
+};
+
+/// Helper responsible for serialising `Camt053` statements into CAMT XML.
 pub(super) struct CamtWriter<'a, W: Write> {
     statement: &'a Camt053Statement,
+    options: &'a Camt053WriteOptions,
+    message: CamtMessage,
+    /// `MsgId` of the document, generated once per write by profiles that emit it
+    message_id: String,
     writer: Writer<&'a mut W>,
 }
 
 impl<'a, W: Write> CamtWriter<'a, W> {
     /// Create a new XML writer around the provided `Write` sink.
-    pub(super) fn new(statement: &'a Camt053Statement, sink: &'a mut W) -> Self {
-        let writer = Writer::new_with_indent(sink, b' ', 2);
-        Self { statement, writer }
+    pub(super) fn new(
+        statement: &'a Camt053Statement,
+        sink: &'a mut W,
+        options: &'a Camt053WriteOptions,
+        message: CamtMessage,
+    ) -> Self {
+        let writer = if options.compact {
+            Writer::new(sink)
+        } else {
+            Writer::new_with_indent(sink, b' ', 2)
+        };
+        Self {
+            statement,
+            options,
+            message,
+            message_id: String::new(),
+            writer,
+        }
     }
 
-    /// Render the CAMT.053 document to the sink.
+    /// Render the CAMT document to the sink.
     pub(super) fn write(mut self) -> Result<(), ParseError> {
         self.write_document_start()?;
         self.write_statement()?;
@@ -37,7 +64,15 @@ impl<'a, W: Write> CamtWriter<'a, W> {
             })?;
 
         let mut document = BytesStart::new(ElementName::Document.to_string());
-        document.push_attribute(("xmlns", "urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"));
+        let namespace = match (self.message, self.options.version) {
+            (CamtMessage::Statement, Camt053Version::V02) => CAMT053_NAMESPACE,
+            (CamtMessage::Report, Camt053Version::V02) => CAMT052_NAMESPACE,
+            (CamtMessage::Notification, Camt053Version::V02) => CAMT054_NAMESPACE,
+            (CamtMessage::Statement, Camt053Version::V04) => CAMT053_V04_NAMESPACE,
+            (CamtMessage::Report, Camt053Version::V04) => CAMT052_V04_NAMESPACE,
+            (CamtMessage::Notification, Camt053Version::V04) => CAMT054_V04_NAMESPACE,
+        };
+        document.push_attribute(("xmlns", namespace));
         self.writer
             .write_event(Event::Start(document))
             .map_err(|e| {
@@ -54,63 +89,443 @@ impl<'a, W: Write> CamtWriter<'a, W> {
     }
 
     fn write_statement(&mut self) -> Result<(), ParseError> {
-        self.writer
-            .write_event(Event::Start(BytesStart::new(
-                ElementName::BkToCstmrStmt.to_string(),
-            )))
-            .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to write BkToCstmrStmt tag: {}", e))
-            })?;
+        let (root, _) = self.message_elements();
+        self.write_start(root)?;
 
-        self.writer
-            .write_event(Event::Start(BytesStart::new(ElementName::Stmt.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Stmt tag: {}", e)))?;
+        if self.is_identified() {
+            self.message_id = self.generate_message_id();
+            self.write_group_header()?;
+        }
 
+        // Notifications have no balances to carry between pages
+        let max_entries = match self.message {
+            CamtMessage::Notification => None,
+            _ => self.options.max_entries_per_page,
+        };
+        let pages = paging::paginate(
+            &self.statement.balances(),
+            &self.statement.transactions,
+            max_entries,
+        );
+        let mut entry_offset = 0;
+        for page in &pages {
+            self.write_page(page, entry_offset)?;
+            entry_offset += page.transactions.len();
+        }
+
+        self.write_supplementary_data(&self.statement.supplementary_data)?;
+        self.write_end(root)
+    }
+
+    /// Root message element and the element wrapping each statement page
+    fn message_elements(&self) -> (ElementName, ElementName) {
+        match self.message {
+            CamtMessage::Statement => (ElementName::BkToCstmrStmt, ElementName::Stmt),
+            CamtMessage::Report => (ElementName::BkToCstmrAcctRpt, ElementName::Report),
+            CamtMessage::Notification => (
+                ElementName::BkToCstmrDbtCdtNtfctn,
+                ElementName::Notification,
+            ),
+        }
+    }
+
+    /// Write one `<Stmt>` page; `entry_offset` keeps `NtryRef` numbering continuous across pages
+    fn write_page(&mut self, page: &Page<'_>, entry_offset: usize) -> Result<(), ParseError> {
+        let (_, page_element) = self.message_elements();
+        self.write_start(page_element)?;
+
+        let info = &self.statement.info;
+        if self.is_identified() {
+            let statement_id = self.page_id(self.statement_id(page.number), page);
+            self.write_text_element(ElementName::Id, &truncate(&statement_id, MAX_ID_LENGTH))?;
+        } else if let Some(statement_id) = &info.statement_id {
+            let statement_id = self.page_id(statement_id.clone(), page);
+            self.write_text_element(ElementName::Id, &statement_id)?;
+        }
+        // `<StmtPgntn>` only exists from version 04 on. A single-page statement keeps the
+        // historical output without pagination markers, unless it was received as a
+        // numbered page
+        if self.options.version != Camt053Version::V02 {
+            if page.total > 1 {
+                self.write_pagination(page.number, page.is_last())?;
+            } else if let Some(number) = info.page_number {
+                self.write_pagination(number as usize, true)?;
+            }
+        }
+        let sequence_number = match info.sequence_number {
+            Some(number) => Some(number as usize),
+            None if self.is_identified() => Some(page.number),
+            None => None,
+        };
+        if let Some(number) = sequence_number {
+            self.write_text_element(ElementName::ElectronicSequenceNumber, &number.to_string())?;
+        }
+        if let Some(number) = info.legal_sequence_number {
+            self.write_text_element(ElementName::LegalSequenceNumber, &number.to_string())?;
+        }
+        if self.is_identified() || info.created_at.is_some() {
+            let created_at = self.created_at();
+            self.write_text_element(ElementName::CreationDateTime, &created_at)?;
+        }
+        if let (Some(from), Some(to)) = (info.from_date, info.to_date) {
+            self.write_start(ElementName::FromToDate)?;
+            self.write_text_element(ElementName::FromDateTime, &format_date_time(&from))?;
+            self.write_text_element(ElementName::ToDateTime, &format_date_time(&to))?;
+            self.write_end(ElementName::FromToDate)?;
+        }
         self.write_account()?;
-        self.write_balances()?;
-        self.write_entries()?;
+        if self.message != CamtMessage::Notification {
+            self.write_balances(page)?;
+        }
+        self.write_entries(page.transactions, entry_offset)?;
 
-        self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::Stmt.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close Stmt tag: {}", e)))?;
+        self.write_end(page_element)
+    }
+
+    /// `Id` of a page: version 02 has no `<StmtPgntn>`, so pages of a paginated statement
+    /// that would all repeat the statement's own id are told apart by a `-<page>` suffix
+    fn page_id(&self, statement_id: String, page: &Page<'_>) -> String {
+        let repeated = self.options.id_generator.is_none()
+            && self.statement.info.statement_id.as_ref() == Some(&statement_id);
+        if self.options.version == Camt053Version::V02 && page.total > 1 && repeated {
+            format!("{}-{}", statement_id, page.number)
+        } else {
+            statement_id
+        }
+    }
+
+    fn write_pagination(&mut self, number: usize, is_last: bool) -> Result<(), ParseError> {
+        self.write_start(ElementName::StatementPagination)?;
+        self.write_text_element(ElementName::PageNumber, &number.to_string())?;
+        self.write_text_element(
+            ElementName::LastPageIndicator,
+            if is_last { "true" } else { "false" },
+        )?;
+        self.write_end(ElementName::StatementPagination)
+    }
+
+    fn is_dk(&self) -> bool {
+        self.options.profile == Camt053Profile::Dk
+    }
+
+    /// Whether the document carries the group header and the statement `Id`,
+    /// `CreDtTm` and `ElctrncSeqNb` the schema requires
+    fn is_identified(&self) -> bool {
+        self.options.group_header.unwrap_or(true)
+    }
+
+    /// `MsgId` from the generator, or derived from the statement
+    fn generate_message_id(&self) -> String {
+        let message_id = match &self.options.id_generator {
+            Some(generator) => generator.generate(&id_context(self.statement, IdKind::Message, 1)),
+            None => derived_message_id(self.statement),
+        };
+        truncate(&message_id, MAX_ID_LENGTH)
+    }
+
+    /// `Stmt/Id` of page `page`: from the generator, the statement's own id, or the
+    /// message id plus page number
+    fn statement_id(&self, page: usize) -> String {
+        match (
+            &self.options.id_generator,
+            &self.statement.info.statement_id,
+        ) {
+            (Some(generator), _) => {
+                generator.generate(&id_context(self.statement, IdKind::Statement, page))
+            }
+            (None, Some(statement_id)) => statement_id.clone(),
+            (None, None) => format!("{}-{}", self.message_id, page),
+        }
+    }
 
+    /// `CreDtTm` value from the options, the statement, or the current time
+    fn created_at(&self) -> String {
+        match (self.options.created_at, self.statement.info.created_at) {
+            (Some(created_at), _) => created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            (None, Some(created_at)) => format_date_time(&created_at),
+            (None, None) => Utc::now()
+                .naive_utc()
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string(),
+        }
+    }
+
+    /// Group header: message id, creation time and (single-message) pagination
+    fn write_group_header(&mut self) -> Result<(), ParseError> {
+        self.write_start(ElementName::GroupHeader)?;
+        let message_id = self.message_id.clone();
+        self.write_text_element(ElementName::MessageId, &message_id)?;
+        let created_at = self.created_at();
+        self.write_text_element(ElementName::CreationDateTime, &created_at)?;
+        self.write_start(ElementName::MessagePagination)?;
+        self.write_text_element(ElementName::PageNumber, "1")?;
+        self.write_text_element(ElementName::LastPageIndicator, "true")?;
+        self.write_end(ElementName::MessagePagination)?;
+        self.write_end(ElementName::GroupHeader)
+    }
+
+    /// DK bank transaction code: proprietary `NTRF+<GVC>` issued by `DK`
+    fn write_bank_transaction_code(&mut self, transaction: &Transaction) -> Result<(), ParseError> {
+        let code = match transaction.transaction_type {
+            TransactionType::Credit => DK_TRANSACTION_CODE_CREDIT,
+            TransactionType::Debit => DK_TRANSACTION_CODE_DEBIT,
+        };
+        self.write_start(ElementName::BankTransactionCode)?;
+        self.write_start(ElementName::Proprietary)?;
+        self.write_text_element(ElementName::Code, code)?;
+        self.write_text_element(ElementName::Issuer, DK_ISSUER)?;
+        self.write_end(ElementName::Proprietary)?;
+        self.write_end(ElementName::BankTransactionCode)
+    }
+
+    /// Write `<Name>` opening tag
+    fn write_start(&mut self, name: ElementName) -> Result<(), ParseError> {
         self.writer
-            .write_event(Event::End(BytesEnd::new(
-                ElementName::BkToCstmrStmt.to_string(),
-            )))
+            .write_event(Event::Start(BytesStart::new(name.to_string())))
             .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to close BkToCstmrStmt tag: {}", e))
+                ParseError::Camt053Error(format!("Failed to write {} tag: {}", name, e))
             })?;
-
         Ok(())
     }
 
-    fn write_account(&mut self) -> Result<(), ParseError> {
+    /// Write `</Name>` closing tag
+    fn write_end(&mut self, name: ElementName) -> Result<(), ParseError> {
         self.writer
-            .write_event(Event::Start(BytesStart::new(ElementName::Acct.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Acct tag: {}", e)))?;
+            .write_event(Event::End(BytesEnd::new(name.to_string())))
+            .map_err(|e| {
+                ParseError::Camt053Error(format!("Failed to close {} tag: {}", name, e))
+            })?;
+        Ok(())
+    }
 
-        self.writer
-            .write_event(Event::Start(BytesStart::new(ElementName::Id.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Id tag: {}", e)))?;
+    /// Write the instructed amount and exchange rate of a foreign-currency transaction
+    fn write_amount_details(
+        &mut self,
+        transaction: &Transaction,
+        currency: &str,
+    ) -> Result<(), ParseError> {
+        let (Some(amount), Some(original_currency)) = (
+            transaction.original_amount,
+            transaction.original_currency.as_deref(),
+        ) else {
+            return Ok(());
+        };
 
+        self.write_start(ElementName::AmountDetails)?;
+        self.write_start(ElementName::InstructedAmount)?;
+        let mut amt_tag = BytesStart::new(ElementName::Amount.to_string());
+        amt_tag.push_attribute(("Ccy", original_currency));
         self.writer
-            .write_event(Event::Start(BytesStart::new(ElementName::Iban.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write IBAN tag: {}", e)))?;
-
+            .write_event(Event::Start(amt_tag))
+            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Amt tag: {}", e)))?;
         self.writer
-            .write_event(Event::Text(BytesText::new(&self.statement.account_number)))
+            .write_event(Event::Text(BytesText::new(&format!("{:.2}", amount))))
             .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to write account number: {}", e))
+                ParseError::Camt053Error(format!("Failed to write instructed amount: {}", e))
             })?;
+        self.write_end(ElementName::Amount)?;
+
+        if let Some(rate) = transaction.exchange_rate {
+            self.write_start(ElementName::CurrencyExchange)?;
+            self.write_text_element(ElementName::SourceCurrency, original_currency)?;
+            self.write_text_element(ElementName::TargetCurrency, currency)?;
+            self.write_text_element(ElementName::ExchangeRate, &rate.to_string())?;
+            self.write_end(ElementName::CurrencyExchange)?;
+        }
 
+        self.write_end(ElementName::InstructedAmount)?;
+        self.write_end(ElementName::AmountDetails)
+    }
+
+    /// Write one version 2 `<Chrgs>` block per charge, or one `<Chrgs>` with a
+    /// `<Rcrd>` per charge in later versions
+    fn write_charges(&mut self, charges: &[Charge]) -> Result<(), ParseError> {
+        let records = self.options.version != Camt053Version::V02;
+        if records && !charges.is_empty() {
+            self.write_start(ElementName::Charges)?;
+        }
+        for charge in charges {
+            let element = if records {
+                ElementName::ChargesRecord
+            } else {
+                ElementName::Charges
+            };
+            self.write_start(element)?;
+            let mut amt_tag = BytesStart::new(ElementName::Amount.to_string());
+            if let Some(currency) = &charge.currency {
+                amt_tag.push_attribute(("Ccy", currency.as_str()));
+            }
+            self.writer
+                .write_event(Event::Start(amt_tag))
+                .map_err(|e| ParseError::Camt053Error(format!("Failed to write Amt tag: {}", e)))?;
+            self.writer
+                .write_event(Event::Text(BytesText::new(&format!(
+                    "{:.2}",
+                    charge.amount
+                ))))
+                .map_err(|e| {
+                    ParseError::Camt053Error(format!("Failed to write charge amount: {}", e))
+                })?;
+            self.write_end(ElementName::Amount)?;
+
+            let indicator = match charge.charge_type {
+                TransactionType::Credit => CRDT_INDICATOR,
+                TransactionType::Debit => DBIT_INDICATOR,
+            };
+            self.write_text_element(ElementName::CreditDebit, indicator)?;
+            if let Some(code) = &charge.code {
+                self.write_start(ElementName::BalanceType)?;
+                self.write_text_element(ElementName::Code, code)?;
+                self.write_end(ElementName::BalanceType)?;
+            }
+            if let Some(bearer) = &charge.bearer {
+                self.write_text_element(ElementName::ChargeBearer, bearer)?;
+            }
+            self.write_end(element)?;
+        }
+        if records && !charges.is_empty() {
+            self.write_end(ElementName::Charges)?;
+        }
+        Ok(())
+    }
+
+    /// Write a `<Strd>` block with the creditor reference and additional lines
+    fn write_structured_remittance(
+        &mut self,
+        remittance: &RemittanceInfo,
+    ) -> Result<(), ParseError> {
+        let RemittanceInfo::Structured {
+            creditor_ref,
+            ref_type,
+            additional,
+        } = remittance
+        else {
+            return Ok(());
+        };
+
+        self.write_start(ElementName::StructuredRemittance)?;
+        if creditor_ref.is_some() || ref_type.is_some() {
+            self.write_start(ElementName::CreditorReferenceInfo)?;
+            if let Some(ref_type) = ref_type {
+                let element = if CREDITOR_REFERENCE_TYPE_CODES.contains(&ref_type.as_str()) {
+                    ElementName::Code
+                } else {
+                    ElementName::Proprietary
+                };
+                self.write_start(ElementName::BalanceType)?;
+                self.write_start(ElementName::CodeOrProprietary)?;
+                self.write_text_element(element, ref_type)?;
+                self.write_end(ElementName::CodeOrProprietary)?;
+                self.write_end(ElementName::BalanceType)?;
+            }
+            if let Some(creditor_ref) = creditor_ref {
+                self.write_text_element(ElementName::ReferenceValue, creditor_ref)?;
+            }
+            self.write_end(ElementName::CreditorReferenceInfo)?;
+        }
+        for line in additional {
+            self.write_text_element(ElementName::AdditionalRemittanceInfo, line)?;
+        }
+        self.write_end(ElementName::StructuredRemittance)
+    }
+
+    /// Write the `<Refs>` block: end-to-end ID, the reference as `TxId` and the mandate ID
+    fn write_references(&mut self, transaction: &Transaction) -> Result<(), ParseError> {
+        let mut end_to_end_id = transaction.end_to_end_id.clone();
+        if self.is_dk() {
+            // DK requires the end-to-end reference; TxId keeps the reference readable by other tools
+            let id = end_to_end_id
+                .as_deref()
+                .or(transaction.reference.as_deref())
+                .map(|id| truncate(id, MAX_ID_LENGTH))
+                .unwrap_or_else(|| NOT_PROVIDED.to_string());
+            end_to_end_id = Some(id);
+        }
+        if end_to_end_id.is_none()
+            && transaction.reference.is_none()
+            && transaction.mandate_id.is_none()
+        {
+            return Ok(());
+        }
+
+        self.write_start(ElementName::References)?;
+        if let Some(end_to_end_id) = end_to_end_id.as_deref() {
+            self.write_text_element(ElementName::EndToEndId, end_to_end_id)?;
+        }
+        if let Some(reference) = transaction.reference.as_deref() {
+            self.write_text_element(ElementName::TransactionId, reference)?;
+        }
+        if let Some(mandate_id) = transaction.mandate_id.as_deref() {
+            self.write_text_element(ElementName::MandateId, mandate_id)?;
+        }
+        self.write_end(ElementName::References)
+    }
+
+    /// Write each raw XML block inside its own `<SplmtryData>`, unchanged
+    fn write_supplementary_data(&mut self, blocks: &[String]) -> Result<(), ParseError> {
+        for xml in blocks {
+            self.write_start(ElementName::SupplementaryData)?;
+            self.writer
+                .write_event(Event::Text(BytesText::from_escaped(xml.as_str())))
+                .map_err(|e| {
+                    ParseError::Camt053Error(format!("Failed to write SplmtryData: {}", e))
+                })?;
+            self.write_end(ElementName::SupplementaryData)?;
+        }
+        Ok(())
+    }
+
+    /// Write a `<PstlAdr>` block, its children in schema order
+    fn write_postal_address(&mut self, address: &PostalAddress) -> Result<(), ParseError> {
+        self.write_start(ElementName::PostalAddress)?;
+        let parts = [
+            (ElementName::StreetName, &address.street),
+            (ElementName::BuildingNumber, &address.building_number),
+            (ElementName::PostCode, &address.postal_code),
+            (ElementName::TownName, &address.town),
+            (ElementName::Country, &address.country),
+        ];
+        for (element, value) in parts {
+            if let Some(value) = value {
+                self.write_text_element(element, value)?;
+            }
+        }
+        for line in &address.lines {
+            self.write_text_element(ElementName::AddressLine, line)?;
+        }
+        self.write_end(ElementName::PostalAddress)
+    }
+
+    /// Write `<Name>text</Name>`
+    fn write_text_element(&mut self, name: ElementName, text: &str) -> Result<(), ParseError> {
+        self.write_start(name)?;
         self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::Iban.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close IBAN tag: {}", e)))?;
+            .write_event(Event::Text(BytesText::new(text)))
+            .map_err(|e| ParseError::Camt053Error(format!("Failed to write {}: {}", name, e)))?;
+        self.write_end(name)
+    }
 
+    /// Write the `<Id>` of an account: `<IBAN>` for an IBAN, `<Othr><Id>` for
+    /// other account numbers such as 20-digit Russian accounts
+    fn write_account_id(&mut self, account: &str) -> Result<(), ParseError> {
+        self.write_start(ElementName::Id)?;
+        if utils::is_iban(account) {
+            self.write_text_element(ElementName::Iban, account)?;
+        } else {
+            self.write_start(ElementName::OtherId)?;
+            self.write_text_element(ElementName::Id, account)?;
+            self.write_end(ElementName::OtherId)?;
+        }
+        self.write_end(ElementName::Id)
+    }
+
+    fn write_account(&mut self) -> Result<(), ParseError> {
         self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::Id.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close Id tag: {}", e)))?;
+            .write_event(Event::Start(BytesStart::new(ElementName::Acct.to_string())))
+            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Acct tag: {}", e)))?;
+
+        let statement = self.statement;
+        self.write_account_id(&statement.account_number)?;
 
         self.writer
             .write_event(Event::Start(BytesStart::new(
@@ -126,6 +541,20 @@ impl<'a, W: Write> CamtWriter<'a, W> {
             .write_event(Event::End(BytesEnd::new(ElementName::Currency.to_string())))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to close Ccy tag: {}", e)))?;
 
+        let info = &self.statement.info;
+        if let Some(owner) = &info.account_owner {
+            self.write_start(ElementName::Owner)?;
+            self.write_text_element(ElementName::Name, owner)?;
+            self.write_end(ElementName::Owner)?;
+        }
+        if let Some(bic) = &info.servicer_bic {
+            self.write_start(ElementName::Servicer)?;
+            self.write_start(ElementName::FinancialInstitutionId)?;
+            self.write_text_element(ElementName::Bic, bic)?;
+            self.write_end(ElementName::FinancialInstitutionId)?;
+            self.write_end(ElementName::Servicer)?;
+        }
+
         self.writer
             .write_event(Event::End(BytesEnd::new(ElementName::Acct.to_string())))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to close Acct tag: {}", e)))?;
@@ -133,21 +562,50 @@ impl<'a, W: Write> CamtWriter<'a, W> {
         Ok(())
     }
 
-    fn write_balances(&mut self) -> Result<(), ParseError> {
+    fn write_balances(&mut self, page: &Page<'_>) -> Result<(), ParseError> {
+        // Page boundaries inside the statement are interim balances, not opening/closing ones
+        let opening_type = if page.is_first() && self.is_dk() {
+            PRCD_BALANCE_TYPE
+        } else if page.is_first() {
+            OPBD_BALANCE_TYPE
+        } else {
+            ITBD_BALANCE_TYPE
+        };
+        let closing_type = if page.is_last() {
+            CLBD_BALANCE_TYPE
+        } else {
+            ITBD_BALANCE_TYPE
+        };
+
+        let (opening_balance, opening_indicator) = paging::unsigned_balance(page.opening_balance);
         self.write_balance(
-            OPBD_BALANCE_TYPE,
-            self.statement.opening_balance,
-            &self.statement.opening_indicator,
-            &self.statement.opening_date,
+            opening_type,
+            opening_balance,
+            &opening_indicator,
+            &page.opening_date,
         )?;
 
+        let (closing_balance, closing_indicator) = paging::unsigned_balance(page.closing_balance);
         self.write_balance(
-            CLBD_BALANCE_TYPE,
-            self.statement.closing_balance,
-            &self.statement.closing_indicator,
-            &self.statement.closing_date,
+            closing_type,
+            closing_balance,
+            &closing_indicator,
+            &page.closing_date,
         )?;
 
+        // Available, forward and interim balances follow the closing balance of the last page
+        if page.is_last() {
+            let statement = self.statement;
+            for balance in &statement.balances {
+                self.write_balance(
+                    &balance.code.to_string(),
+                    balance.amount,
+                    &balance.indicator,
+                    &balance.date,
+                )?;
+            }
+        }
+
         Ok(())
     }
 
@@ -277,9 +735,13 @@ impl<'a, W: Write> CamtWriter<'a, W> {
         Ok(())
     }
 
-    fn write_entries(&mut self) -> Result<(), ParseError> {
-        for (index, transaction) in self.statement.transactions.iter().enumerate() {
-            self.write_entry(transaction, index + 1)?;
+    fn write_entries(
+        &mut self,
+        transactions: &[Transaction],
+        entry_offset: usize,
+    ) -> Result<(), ParseError> {
+        for (index, transaction) in transactions.iter().enumerate() {
+            self.write_entry(transaction, entry_offset + index + 1)?;
         }
         Ok(())
     }
@@ -295,24 +757,41 @@ impl<'a, W: Write> CamtWriter<'a, W> {
             )))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to write Ntry tag: {}", e)))?;
 
-        self.writer
-            .write_event(Event::Start(BytesStart::new(
-                ElementName::EntryRef.to_string(),
-            )))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write NtryRef tag: {}", e)))?;
+        // The sequence number is only a reference when TxId carries the real one;
+        // on its own it would be read back as the transaction's reference
+        if self
+            .options
+            .entry_refs
+            .unwrap_or(transaction.reference.is_some())
+        {
+            self.writer
+                .write_event(Event::Start(BytesStart::new(
+                    ElementName::EntryRef.to_string(),
+                )))
+                .map_err(|e| {
+                    ParseError::Camt053Error(format!("Failed to write NtryRef tag: {}", e))
+                })?;
 
-        self.writer
-            .write_event(Event::Text(BytesText::new(&entry_ref.to_string())))
-            .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to write entry reference: {}", e))
-            })?;
+            self.writer
+                .write_event(Event::Text(BytesText::new(&entry_ref.to_string())))
+                .map_err(|e| {
+                    ParseError::Camt053Error(format!("Failed to write entry reference: {}", e))
+                })?;
 
-        self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::EntryRef.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close NtryRef tag: {}", e)))?;
+            self.writer
+                .write_event(Event::End(BytesEnd::new(ElementName::EntryRef.to_string())))
+                .map_err(|e| {
+                    ParseError::Camt053Error(format!("Failed to close NtryRef tag: {}", e))
+                })?;
+        }
 
+        let statement = self.statement;
+        let currency = transaction
+            .currency
+            .as_deref()
+            .unwrap_or(&statement.currency);
         let mut amt_tag = BytesStart::new(ElementName::Amount.to_string());
-        amt_tag.push_attribute(("Ccy", self.statement.currency.as_str()));
+        amt_tag.push_attribute(("Ccy", currency));
         self.writer
             .write_event(Event::Start(amt_tag))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to write Amt tag: {}", e)))?;
@@ -356,27 +835,42 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 ParseError::Camt053Error(format!("Failed to close CdtDbtInd tag: {}", e))
             })?;
 
+        if transaction.is_reversal {
+            self.write_text_element(ElementName::ReversalIndicator, "true")?;
+        }
+        // Booked entries only carry the status where the profile requires it
+        let status = match transaction.status {
+            EntryStatus::Booked => BOOKED_STATUS,
+            EntryStatus::Pending => PENDING_STATUS,
+            EntryStatus::Info => INFO_STATUS,
+        };
+        let write_status = self
+            .options
+            .entry_status
+            .unwrap_or(self.is_dk() || !transaction.status.is_booked());
+        if write_status {
+            self.write_text_element(ElementName::Status, status)?;
+        }
+
         self.writer
             .write_event(Event::Start(BytesStart::new(
                 ElementName::BookingDate.to_string(),
             )))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to write BookgDt tag: {}", e)))?;
 
-        self.writer
-            .write_event(Event::Start(BytesStart::new(ElementName::Date.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write Dt tag: {}", e)))?;
-
-        self.writer
-            .write_event(Event::Text(BytesText::new(
-                &transaction.booking_date.format("%Y-%m-%d").to_string(),
-            )))
-            .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to write booking date: {}", e))
-            })?;
-
-        self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::Date.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close Dt tag: {}", e)))?;
+        // <DtTm> keeps time and offset, so intraday sequencing survives the conversion
+        let (date_element, booking_date) = if self.options.booking_date_time {
+            (
+                ElementName::DateTime,
+                format_date_time(&transaction.booking_date),
+            )
+        } else {
+            (
+                ElementName::Date,
+                transaction.booking_date.format("%Y-%m-%d").to_string(),
+            )
+        };
+        self.write_text_element(date_element, &booking_date)?;
 
         self.writer
             .write_event(Event::End(BytesEnd::new(
@@ -384,7 +878,12 @@ impl<'a, W: Write> CamtWriter<'a, W> {
             )))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to close BookgDt tag: {}", e)))?;
 
-        if let Some(value_date) = transaction.value_date.as_ref() {
+        // DK makes the value date mandatory; the booking date is the closest known value
+        let value_date = match transaction.value_date {
+            None if self.is_dk() => Some(transaction.booking_date.date_naive()),
+            value_date => value_date,
+        };
+        if let Some(value_date) = value_date.map(|date| date.format("%Y-%m-%d").to_string()) {
             self.writer
                 .write_event(Event::Start(BytesStart::new(
                     ElementName::ValueDate.to_string(),
@@ -398,7 +897,7 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 .map_err(|e| ParseError::Camt053Error(format!("Failed to write Dt tag: {}", e)))?;
 
             self.writer
-                .write_event(Event::Text(BytesText::new(value_date)))
+                .write_event(Event::Text(BytesText::new(&value_date)))
                 .map_err(|e| {
                     ParseError::Camt053Error(format!("Failed to write value date: {}", e))
                 })?;
@@ -416,6 +915,11 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 })?;
         }
 
+        if self.is_dk() {
+            self.write_bank_transaction_code(transaction)?;
+        }
+        self.write_charges(&transaction.charges)?;
+
         self.writer
             .write_event(Event::Start(BytesStart::new(
                 ElementName::EntryDetails.to_string(),
@@ -424,55 +928,47 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 ParseError::Camt053Error(format!("Failed to write NtryDtls tag: {}", e))
             })?;
 
+        if transaction.details.is_empty() {
+            self.write_transaction_details(transaction, currency)?;
+        } else {
+            self.write_batch(transaction, currency)?;
+        }
+
         self.writer
-            .write_event(Event::Start(BytesStart::new(
-                ElementName::TransactionDetails.to_string(),
+            .write_event(Event::End(BytesEnd::new(
+                ElementName::EntryDetails.to_string(),
             )))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to write TxDtls tag: {}", e)))?;
+            .map_err(|e| {
+                ParseError::Camt053Error(format!("Failed to close NtryDtls tag: {}", e))
+            })?;
 
-        if transaction.reference.is_some() {
-            self.writer
-                .write_event(Event::Start(BytesStart::new(
-                    ElementName::References.to_string(),
-                )))
-                .map_err(|e| {
-                    ParseError::Camt053Error(format!("Failed to write Refs tag: {}", e))
-                })?;
+        self.writer
+            .write_event(Event::End(BytesEnd::new(ElementName::Entry.to_string())))
+            .map_err(|e| ParseError::Camt053Error(format!("Failed to close Ntry tag: {}", e)))?;
 
-            if let Some(reference) = transaction.reference.as_ref() {
-                self.writer
-                    .write_event(Event::Start(BytesStart::new(
-                        ElementName::TransactionId.to_string(),
-                    )))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to write TxId tag: {}", e))
-                    })?;
+        Ok(())
+    }
 
-                self.writer
-                    .write_event(Event::Text(BytesText::new(reference)))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to write reference: {}", e))
-                    })?;
+    /// Write the single `<TxDtls>` of an entry that is one payment
+    fn write_transaction_details(
+        &mut self,
+        transaction: &Transaction,
+        currency: &str,
+    ) -> Result<(), ParseError> {
+        self.writer
+            .write_event(Event::Start(BytesStart::new(
+                ElementName::TransactionDetails.to_string(),
+            )))
+            .map_err(|e| ParseError::Camt053Error(format!("Failed to write TxDtls tag: {}", e)))?;
 
-                self.writer
-                    .write_event(Event::End(BytesEnd::new(
-                        ElementName::TransactionId.to_string(),
-                    )))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to close TxId tag: {}", e))
-                    })?;
-            }
+        self.write_references(transaction)?;
 
-            self.writer
-                .write_event(Event::End(BytesEnd::new(
-                    ElementName::References.to_string(),
-                )))
-                .map_err(|e| {
-                    ParseError::Camt053Error(format!("Failed to close Refs tag: {}", e))
-                })?;
-        }
+        self.write_amount_details(transaction, currency)?;
 
-        if transaction.counterparty_name.is_some() || transaction.counterparty_account.is_some() {
+        if transaction.counterparty_name.is_some()
+            || transaction.counterparty_account.is_some()
+            || transaction.counterparty_address.is_some()
+        {
             self.writer
                 .write_event(Event::Start(BytesStart::new(
                     ElementName::RelatedParties.to_string(),
@@ -490,7 +986,11 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 TransactionType::Debit => ElementName::CreditorAccount.to_string(),
             };
 
-            if let Some(counterparty_name) = transaction.counterparty_name.as_ref() {
+            let counterparty_name = match transaction.counterparty_name.as_deref() {
+                Some(name) if self.is_dk() => Some(truncate(name, DK_MAX_NAME_LENGTH)),
+                name => name.map(str::to_string),
+            };
+            if counterparty_name.is_some() || transaction.counterparty_address.is_some() {
                 self.writer
                     .write_event(Event::Start(BytesStart::new(party_tag.clone())))
                     .map_err(|e| {
@@ -500,26 +1000,12 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                         ))
                     })?;
 
-                self.writer
-                    .write_event(Event::Start(BytesStart::new(ElementName::Name.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to write Nm tag: {}", e))
-                    })?;
-
-                self.writer
-                    .write_event(Event::Text(BytesText::new(counterparty_name)))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!(
-                            "Failed to write counterparty name: {}",
-                            e
-                        ))
-                    })?;
-
-                self.writer
-                    .write_event(Event::End(BytesEnd::new(ElementName::Name.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to close Nm tag: {}", e))
-                    })?;
+                if let Some(counterparty_name) = counterparty_name.as_deref() {
+                    self.write_text_element(ElementName::Name, counterparty_name)?;
+                }
+                if let Some(address) = transaction.counterparty_address.as_ref() {
+                    self.write_postal_address(address)?;
+                }
 
                 self.writer
                     .write_event(Event::End(BytesEnd::new(party_tag.clone())))
@@ -541,38 +1027,7 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                         ))
                     })?;
 
-                self.writer
-                    .write_event(Event::Start(BytesStart::new(ElementName::Id.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to write Id tag: {}", e))
-                    })?;
-
-                self.writer
-                    .write_event(Event::Start(BytesStart::new(ElementName::Iban.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to write IBAN tag: {}", e))
-                    })?;
-
-                self.writer
-                    .write_event(Event::Text(BytesText::new(counterparty_account)))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!(
-                            "Failed to write counterparty account: {}",
-                            e
-                        ))
-                    })?;
-
-                self.writer
-                    .write_event(Event::End(BytesEnd::new(ElementName::Iban.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to close IBAN tag: {}", e))
-                    })?;
-
-                self.writer
-                    .write_event(Event::End(BytesEnd::new(ElementName::Id.to_string())))
-                    .map_err(|e| {
-                        ParseError::Camt053Error(format!("Failed to close Id tag: {}", e))
-                    })?;
+                self.write_account_id(counterparty_account)?;
 
                 self.writer
                     .write_event(Event::End(BytesEnd::new(account_tag.clone())))
@@ -593,7 +1048,31 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 })?;
         }
 
-        if !transaction.description.is_empty() {
+        if let Some(bic) = transaction.counterparty_bic.as_deref() {
+            let agent = match transaction.transaction_type {
+                TransactionType::Credit => ElementName::DebtorAgent,
+                TransactionType::Debit => ElementName::CreditorAgent,
+            };
+            self.write_start(ElementName::RelatedAgents)?;
+            self.write_start(agent)?;
+            self.write_start(ElementName::FinancialInstitutionId)?;
+            self.write_text_element(ElementName::Bic, bic)?;
+            self.write_end(ElementName::FinancialInstitutionId)?;
+            self.write_end(agent)?;
+            self.write_end(ElementName::RelatedAgents)?;
+        }
+
+        let (unstructured, structured) = match &transaction.remittance {
+            Some(RemittanceInfo::Unstructured(text)) => (text.as_str(), None),
+            // A description that only repeats the creditor reference was read from <Strd>
+            Some(RemittanceInfo::Structured { creditor_ref, .. })
+                if creditor_ref.as_deref() == Some(transaction.description.as_str()) =>
+            {
+                ("", transaction.remittance.as_ref())
+            }
+            remittance => (transaction.description.as_str(), remittance.as_ref()),
+        };
+        if !unstructured.is_empty() || structured.is_some() {
             self.writer
                 .write_event(Event::Start(BytesStart::new(
                     ElementName::RemittanceInfo.to_string(),
@@ -602,27 +1081,20 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                     ParseError::Camt053Error(format!("Failed to write RmtInf tag: {}", e))
                 })?;
 
-            self.writer
-                .write_event(Event::Start(BytesStart::new(
-                    ElementName::UnstructuredRemittance.to_string(),
-                )))
-                .map_err(|e| {
-                    ParseError::Camt053Error(format!("Failed to write Ustrd tag: {}", e))
-                })?;
-
-            self.writer
-                .write_event(Event::Text(BytesText::new(&transaction.description)))
-                .map_err(|e| {
-                    ParseError::Camt053Error(format!("Failed to write description: {}", e))
-                })?;
-
-            self.writer
-                .write_event(Event::End(BytesEnd::new(
-                    ElementName::UnstructuredRemittance.to_string(),
-                )))
-                .map_err(|e| {
-                    ParseError::Camt053Error(format!("Failed to close Ustrd tag: {}", e))
-                })?;
+            // DK limits each Ustrd line to 140 characters; longer text spans several lines
+            let lines = if unstructured.is_empty() {
+                Vec::new()
+            } else if self.is_dk() {
+                wrap_words(unstructured, DK_MAX_REMITTANCE_LENGTH)
+            } else {
+                vec![unstructured.to_string()]
+            };
+            for line in &lines {
+                self.write_text_element(ElementName::UnstructuredRemittance, line)?;
+            }
+            if let Some(structured) = structured {
+                self.write_structured_remittance(structured)?;
+            }
 
             self.writer
                 .write_event(Event::End(BytesEnd::new(
@@ -633,24 +1105,158 @@ impl<'a, W: Write> CamtWriter<'a, W> {
                 })?;
         }
 
+        self.write_supplementary_data(&transaction.supplementary_data)?;
+
         self.writer
             .write_event(Event::End(BytesEnd::new(
                 ElementName::TransactionDetails.to_string(),
             )))
             .map_err(|e| ParseError::Camt053Error(format!("Failed to close TxDtls tag: {}", e)))?;
 
-        self.writer
-            .write_event(Event::End(BytesEnd::new(
-                ElementName::EntryDetails.to_string(),
-            )))
-            .map_err(|e| {
-                ParseError::Camt053Error(format!("Failed to close NtryDtls tag: {}", e))
-            })?;
+        Ok(())
+    }
 
-        self.writer
-            .write_event(Event::End(BytesEnd::new(ElementName::Entry.to_string())))
-            .map_err(|e| ParseError::Camt053Error(format!("Failed to close Ntry tag: {}", e)))?;
+    /// Write the `<Btch>` summary and one `<TxDtls>` per payment of a batch entry.
+    ///
+    /// The batch is written from its details alone; reading it back merges them
+    /// into the transaction fields again.
+    fn write_batch(&mut self, transaction: &Transaction, currency: &str) -> Result<(), ParseError> {
+        self.write_start(ElementName::Batch)?;
+        self.write_text_element(
+            ElementName::NumberOfTransactions,
+            &transaction.details.len().to_string(),
+        )?;
+        self.write_end(ElementName::Batch)?;
+
+        let (party, account) = match transaction.transaction_type {
+            TransactionType::Credit => (ElementName::Debtor, ElementName::DebtorAccount),
+            TransactionType::Debit => (ElementName::Creditor, ElementName::CreditorAccount),
+        };
+        let last = transaction.details.len().saturating_sub(1);
+        for (index, detail) in transaction.details.iter().enumerate() {
+            self.write_start(ElementName::TransactionDetails)?;
+
+            let references = [
+                (ElementName::EndToEndId, &detail.end_to_end_id),
+                (ElementName::TransactionId, &detail.reference),
+                (ElementName::MandateId, &detail.mandate_id),
+            ];
+            if references.iter().any(|(_, value)| value.is_some()) {
+                self.write_start(ElementName::References)?;
+                for (element, value) in references {
+                    if let Some(value) = value {
+                        self.write_text_element(element, value)?;
+                    }
+                }
+                self.write_end(ElementName::References)?;
+            }
+
+            if let Some(amount) = detail.amount {
+                self.write_start(ElementName::AmountDetails)?;
+                self.write_start(ElementName::TransactionAmount)?;
+                let mut amt_tag = BytesStart::new(ElementName::Amount.to_string());
+                amt_tag.push_attribute(("Ccy", currency));
+                self.writer
+                    .write_event(Event::Start(amt_tag))
+                    .map_err(|e| {
+                        ParseError::Camt053Error(format!("Failed to write Amt tag: {}", e))
+                    })?;
+                self.writer
+                    .write_event(Event::Text(BytesText::new(&format!("{:.2}", amount))))
+                    .map_err(|e| {
+                        ParseError::Camt053Error(format!("Failed to write batch amount: {}", e))
+                    })?;
+                self.write_end(ElementName::Amount)?;
+                self.write_end(ElementName::TransactionAmount)?;
+                self.write_end(ElementName::AmountDetails)?;
+            }
+
+            if detail.counterparty_name.is_some() || detail.counterparty_account.is_some() {
+                self.write_start(ElementName::RelatedParties)?;
+                if let Some(name) = detail.counterparty_name.as_deref() {
+                    self.write_start(party)?;
+                    self.write_text_element(ElementName::Name, name)?;
+                    self.write_end(party)?;
+                }
+                if let Some(iban) = detail.counterparty_account.as_deref() {
+                    self.write_start(account)?;
+                    self.write_start(ElementName::Id)?;
+                    self.write_text_element(ElementName::Iban, iban)?;
+                    self.write_end(ElementName::Id)?;
+                    self.write_end(account)?;
+                }
+                self.write_end(ElementName::RelatedParties)?;
+            }
 
+            if !detail.description.is_empty() {
+                self.write_start(ElementName::RemittanceInfo)?;
+                self.write_text_element(ElementName::UnstructuredRemittance, &detail.description)?;
+                self.write_end(ElementName::RemittanceInfo)?;
+            }
+
+            // Supplementary data of the entry stays with its last payment
+            if index == last {
+                self.write_supplementary_data(&transaction.supplementary_data)?;
+            }
+            self.write_end(ElementName::TransactionDetails)?;
+        }
         Ok(())
     }
 }
+
+/// Message id derived from the statement, stable across runs for the same input
+fn derived_message_id(statement: &Camt053Statement) -> String {
+    let id = format!(
+        "{}{}",
+        statement.closing_date.format("%Y%m%d"),
+        statement.account_number
+    );
+    // Leave room for the `-<page>` suffix of statement ids
+    truncate(&id, MAX_ID_LENGTH - 4)
+}
+
+/// Generator context for an identifier of `statement`
+fn id_context(statement: &Camt053Statement, kind: IdKind, page: usize) -> IdContext<'_> {
+    IdContext {
+        kind,
+        page,
+        account_number: &statement.account_number,
+        closing_date: statement.closing_date,
+    }
+}
+
+/// First `max` characters of `text`
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+/// ISO date-time with offset, e.g. `2025-04-20T09:15:00+02:00`
+fn format_date_time(date: &DateTime<FixedOffset>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+/// Split `text` into lines of at most `max` characters, breaking between words where possible
+fn wrap_words(text: &str, max: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() { 0 } else { 1 } + word.chars().count();
+        if !current.is_empty() && current.chars().count() + needed > max {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+        // A single word longer than a line is cut hard
+        while current.chars().count() > max {
+            let head: String = current.chars().take(max).collect();
+            current = current.chars().skip(max).collect();
+            lines.push(head);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
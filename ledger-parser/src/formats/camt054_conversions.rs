@@ -0,0 +1,54 @@
+//! Type conversions between Camt054 and other formats
+//!
+//! Implements the `From` trait in both directions between CAMT.054
+//! notifications and the other format structures (CAMT.053, CSV, MT940, QIF).
+
+use crate::{
+    Camt053Statement, Camt054Statement, CsvStatement, Mt940Statement, QifStatement, StatementInfo,
+};
+
+/// Implements `From` in both directions between `Camt054Statement` and a format
+/// struct as a direct field-by-field conversion, since all structures share the
+/// same data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_camt054_conversions {
+    ($other:ident $(, $field:ident: $value:expr)*) => {
+        impl From<Camt054Statement> for $other {
+            fn from(camt: Camt054Statement) -> Self {
+                $other {
+                    account_number: camt.account_number,
+                    currency: camt.currency,
+                    opening_balance: camt.opening_balance,
+                    opening_date: camt.opening_date,
+                    opening_indicator: camt.opening_indicator,
+                    closing_balance: camt.closing_balance,
+                    closing_date: camt.closing_date,
+                    closing_indicator: camt.closing_indicator,
+                    transactions: camt.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+
+        impl From<$other> for Camt054Statement {
+            fn from(statement: $other) -> Self {
+                Camt054Statement {
+                    account_number: statement.account_number,
+                    currency: statement.currency,
+                    opening_balance: statement.opening_balance,
+                    opening_date: statement.opening_date,
+                    opening_indicator: statement.opening_indicator,
+                    closing_balance: statement.closing_balance,
+                    closing_date: statement.closing_date,
+                    closing_indicator: statement.closing_indicator,
+                    transactions: statement.transactions,
+                }
+            }
+        }
+    };
+}
+
+impl_camt054_conversions!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_camt054_conversions!(CsvStatement, info: StatementInfo::default());
+impl_camt054_conversions!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_camt054_conversions!(QifStatement);
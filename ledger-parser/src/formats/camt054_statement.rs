@@ -0,0 +1,293 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::diagnostics::Diagnostics;
+use crate::error::ParseError;
+use crate::formats::camt053_statement::{parse_document, write_document, CamtMessage};
+use crate::formats::{paging, utils};
+use crate::model::{BalanceType, Transaction};
+use crate::{Camt053Statement, Camt053WriteOptions};
+
+/// ISO 20022 CAMT.054 debit/credit notification (`BkToCstmrDbtCdtNtfctn`)
+///
+/// Parsed and written with the same CAMT infrastructure as
+/// [`Camt053Statement`](crate::Camt053Statement). Notifications report single
+/// bookings and carry no balances: parsing opens at zero on the first entry's
+/// booking date and closes at the net of all entries, and writing emits only
+/// the account and entries of one `<Ntfctn>`.
+///
+/// Use [`Camt054Statement::aggregate`] to collect a day's notifications into
+/// one statement. Fields are identical to Mt940/Camt053/CsvStatement for
+/// seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Camt054Statement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+impl Camt054Statement {
+    /// Parse a CAMT.054 notification from any Read source (file, stdin, buffer).
+    ///
+    /// Entries of all `<Ntfctn>` blocks in the document are collected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Camt054Error` if the input is not well-formed XML, is
+    /// not a `BkToCstmrDbtCdtNtfctn` document or holds no entries, and
+    /// `ParseError::MissingField` if the account or currency is missing.
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse a CAMT.054 notification from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Camt054Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse CAMT.054 from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        let (message, camt) =
+            parse_document(content, &mut Diagnostics::default()).map_err(|err| {
+                err.map_located(|err| match err {
+                    ParseError::Camt053Error(msg) => ParseError::Camt054Error(msg),
+                    // Balances are only missing when the notification has no entries to derive them from
+                    ParseError::MissingField(field) if field.ends_with("_date") => {
+                        ParseError::Camt054Error("Notification contains no entries".into())
+                    }
+                    other => other,
+                })
+            })?;
+        if message != Some(CamtMessage::Notification) {
+            return Err(ParseError::Camt054Error(
+                "Expected a BkToCstmrDbtCdtNtfctn document".into(),
+            ));
+        }
+
+        let statement: Camt054Statement = camt.into();
+        trace_statement!("parsed statement", "camt054", statement);
+
+        Ok(statement)
+    }
+
+    /// Write CAMT.054 to any destination implementing Write.
+    ///
+    /// Emits one `<Ntfctn>` with the account and all entries; balances are not
+    /// part of a notification and are dropped.
+    ///
+    /// # Errors
+    /// Returns `ParseError::Camt054Error` if XML generation fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "camt054", self);
+        let camt: Camt053Statement = self.clone().into();
+        write_document(
+            &camt,
+            writer,
+            &Camt053WriteOptions::default(),
+            CamtMessage::Notification,
+        )
+        .map_err(|err| {
+            err.map_located(|err| match err {
+                ParseError::Camt053Error(msg) => ParseError::Camt054Error(msg),
+                other => other,
+            })
+        })
+    }
+
+    /// Merge notifications for one account into a single statement.
+    ///
+    /// Entries are ordered by booking date (keeping the input order for equal
+    /// dates). The result opens with the earliest notification's opening balance
+    /// and closes at that balance plus the net of all entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Camt054Error` if there are no notifications, and
+    /// `ParseError::InvalidFieldValue` if they disagree on account or currency.
+    pub fn aggregate<I>(notifications: I) -> Result<Camt054Statement, ParseError>
+    where
+        I: IntoIterator<Item = Camt054Statement>,
+    {
+        let mut notifications: Vec<_> = notifications.into_iter().collect();
+        notifications.sort_by_key(|notification| notification.opening_date);
+        let mut iter = notifications.into_iter();
+        let mut aggregated = iter
+            .next()
+            .ok_or_else(|| ParseError::Camt054Error("No notifications to aggregate".into()))?;
+
+        for notification in iter {
+            if notification.account_number != aggregated.account_number {
+                return Err(ParseError::InvalidFieldValue {
+                    field: "account_number".into(),
+                    value: notification.account_number,
+                });
+            }
+            if notification.currency != aggregated.currency {
+                return Err(ParseError::InvalidFieldValue {
+                    field: "currency".into(),
+                    value: notification.currency,
+                });
+            }
+            aggregated.transactions.extend(notification.transactions);
+        }
+        aggregated
+            .transactions
+            .sort_by_key(|transaction| transaction.booking_date);
+
+        let opening = match aggregated.opening_indicator {
+            BalanceType::Credit => aggregated.opening_balance,
+            BalanceType::Debit => -aggregated.opening_balance,
+        };
+        let net: f64 = aggregated
+            .transactions
+            .iter()
+            .map(paging::booked_amount)
+            .sum();
+        let closing = ((opening + net) * 100.0).round() / 100.0;
+        aggregated.closing_balance = closing.abs();
+        aggregated.closing_indicator = if closing < 0.0 {
+            BalanceType::Debit
+        } else {
+            BalanceType::Credit
+        };
+        if let Some(last) = aggregated.transactions.last() {
+            aggregated.closing_date = last.booking_date;
+        }
+
+        Ok(aggregated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLocation;
+
+    fn notification(date: &str, amount: &str, indicator: &str, reference: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.054.001.02">
+    <BkToCstmrDbtCdtNtfctn>
+        <GrpHdr><MsgId>NTF-{reference}</MsgId><CreDtTm>{date}T10:00:00</CreDtTm></GrpHdr>
+        <Ntfctn>
+            <Id>NTF-{reference}</Id>
+            <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id><Ccy>EUR</Ccy></Acct>
+            <Ntry>
+                <Amt Ccy="EUR">{amount}</Amt>
+                <CdtDbtInd>{indicator}</CdtDbtInd>
+                <Sts>BOOK</Sts>
+                <BookgDt><Dt>{date}</Dt></BookgDt>
+                <NtryDtls><TxDtls>
+                    <Refs><TxId>{reference}</TxId></Refs>
+                    <RmtInf><Ustrd>Payment {reference}</Ustrd></RmtInf>
+                </TxDtls></NtryDtls>
+            </Ntry>
+        </Ntfctn>
+    </BkToCstmrDbtCdtNtfctn>
+</Document>"#
+        )
+    }
+
+    #[test]
+    fn test_parse_camt054_notification() {
+        let xml = notification("2025-03-07", "250.00", "DBIT", "TX-1");
+        let statement = Camt054Statement::from_read(&mut xml.as_bytes()).unwrap();
+
+        assert_eq!(statement.account_number, "DE89370400440532013000");
+        assert_eq!(statement.currency, "EUR");
+        assert_eq!(statement.opening_balance, 0.0);
+        assert_eq!(statement.closing_balance, 250.0);
+        assert_eq!(statement.closing_indicator, BalanceType::Debit);
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(statement.transactions[0].reference.as_deref(), Some("TX-1"));
+        assert_eq!(statement.transactions[0].description, "Payment TX-1");
+    }
+
+    #[test]
+    fn test_parse_camt054_rejects_other_documents() {
+        let xml = notification("2025-03-07", "250.00", "DBIT", "TX-1")
+            .replace("BkToCstmrDbtCdtNtfctn", "BkToCstmrAcctRpt");
+        let error = Camt054Statement::from_read(&mut xml.as_bytes()).unwrap_err();
+        assert!(matches!(
+            error.without_location(),
+            ParseError::Camt054Error(_)
+        ));
+
+        let xml =
+            notification("2025-03-07", "250.00", "DBIT", "TX-1").replace("</Amt>", "</Amount>");
+        let error = Camt054Statement::from_read(&mut xml.as_bytes()).unwrap_err();
+        assert_eq!(error.code(), "camt054_error");
+        assert!(matches!(
+            error.location(),
+            Some(ErrorLocation::Xml { path, offset })
+                if path.ends_with("Ntfctn/Ntry/Amt") && *offset > 0
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_notifications() {
+        let parse = |xml: String| Camt054Statement::from_read(&mut xml.as_bytes()).unwrap();
+        let later = parse(notification("2025-03-08", "40.00", "DBIT", "TX-2"));
+        let earlier = parse(notification("2025-03-07", "100.00", "CRDT", "TX-1"));
+
+        let statement = Camt054Statement::aggregate([later, earlier]).unwrap();
+        assert_eq!(statement.transactions.len(), 2);
+        assert_eq!(statement.transactions[0].reference.as_deref(), Some("TX-1"));
+        assert_eq!(statement.closing_balance, 60.0);
+        assert_eq!(statement.closing_indicator, BalanceType::Credit);
+        assert_eq!(
+            statement.closing_date.format("%Y-%m-%d").to_string(),
+            "2025-03-08"
+        );
+
+        let mut other = parse(notification("2025-03-09", "1.00", "CRDT", "TX-3"));
+        other.currency = "USD".into();
+        assert!(Camt054Statement::aggregate([statement, other]).is_err());
+        assert!(Camt054Statement::aggregate(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_write_camt054_round_trip() {
+        let xml = notification("2025-03-07", "250.00", "CRDT", "TX-1");
+        let statement = Camt054Statement::from_read(&mut xml.as_bytes()).unwrap();
+
+        let mut output = Vec::new();
+        statement.write_to(&mut output).unwrap();
+        let written = String::from_utf8(output).unwrap();
+        assert!(written.contains("camt.054.001.02"));
+        assert!(written.contains("<BkToCstmrDbtCdtNtfctn>"));
+        assert!(written.contains("<Ntfctn>"));
+        assert!(!written.contains("<Bal>"));
+
+        let parsed = Camt054Statement::from_read(&mut written.as_bytes()).unwrap();
+        assert_eq!(parsed, statement);
+    }
+}
@@ -0,0 +1,268 @@
+//! Fallible conversions into the formats with hard field limits
+//!
+//! The `From` conversions move the data over as it is, and the writers then
+//! put out whatever they get: an `:86:` narrative longer than MT940 allows,
+//! an account CAMT.053 cannot identify, a currency no bank knows. The
+//! `TryFrom<&S>` implementations here check the statement against the target
+//! format first and fail with the first problem; `validate` lists all of
+//! them, for callers that only want to warn.
+
+use crate::diagnostics::ParseWarning;
+use crate::error::ParseError;
+use crate::formats::mt940_dialect::{self, MT940_NARRATIVE_LINES};
+use crate::view::StatementRef;
+use crate::{
+    Camt053Statement, Camt054Statement, CsvStatement, Currency, Mt940Statement, QifStatement,
+    Statement,
+};
+
+/// Maximum length of the `:25:` account identification
+const MT940_ACCOUNT_MAX_LENGTH: usize = 35;
+/// Maximum length of the customer reference of a `:61:` line
+const MT940_REFERENCE_MAX_LENGTH: usize = 16;
+/// Maximum length of a CAMT.053 `<IBAN>` or `<Othr><Id>` account identification
+const CAMT053_ACCOUNT_MAX_LENGTH: usize = 34;
+/// Length of the domestic account numbers the Sberbank layout reads
+#[cfg(feature = "sberbank")]
+const SBERBANK_ACCOUNT_LENGTH: usize = 20;
+
+impl Mt940Statement {
+    /// Problems that would make `statement` invalid MT940: a missing or too
+    /// long `:25:` account, a currency outside ISO 4217, `:61:` references
+    /// over 16 characters and descriptions that do not wrap into the 6 lines
+    /// of 65 characters of the standard `:86:` layout. Empty when it converts
+    /// cleanly.
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::{Camt053Statement, Mt940Statement};
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+    /// let mut camt = Camt053Statement::from(Mt940Statement::from_read(&mut data.as_bytes()).unwrap());
+    /// assert!(Mt940Statement::try_from(&camt).is_ok());
+    ///
+    /// camt.transactions[0].description = "x".repeat(400);
+    /// assert_eq!(
+    ///     Mt940Statement::validate(camt.as_view())[0].message,
+    ///     format!(
+    ///         "Invalid transaction 1 description: expected at most 6 lines of 65 characters, found '{}'",
+    ///         "x".repeat(400)
+    ///     )
+    /// );
+    /// assert!(Mt940Statement::try_from(&camt).is_err());
+    /// ```
+    pub fn validate(statement: StatementRef<'_>) -> Vec<ParseWarning> {
+        into_warnings(mt940_problems(statement))
+    }
+}
+
+impl Camt053Statement {
+    /// Problems that would make `statement` invalid CAMT.053: a missing
+    /// account or one over the 34 characters of `<IBAN>` and `<Othr><Id>`,
+    /// and statement or transaction currencies outside ISO 4217. Empty when
+    /// it converts cleanly.
+    pub fn validate(statement: StatementRef<'_>) -> Vec<ParseWarning> {
+        into_warnings(camt053_problems(statement))
+    }
+}
+
+#[cfg(feature = "sberbank")]
+impl CsvStatement {
+    /// Problems that would keep a Sberbank CSV export of `statement` from
+    /// being read back: an account other than the 20 digits of a domestic
+    /// account number, or a currency outside ISO 4217. Empty when it converts
+    /// cleanly.
+    pub fn validate(statement: StatementRef<'_>) -> Vec<ParseWarning> {
+        into_warnings(sberbank_problems(statement))
+    }
+}
+
+fn into_warnings(problems: Vec<ParseError>) -> Vec<ParseWarning> {
+    problems.into_iter().map(ParseWarning::from).collect()
+}
+
+fn mt940_problems(statement: StatementRef<'_>) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    let account_length = statement.account_number.chars().count();
+    if account_length == 0 || account_length > MT940_ACCOUNT_MAX_LENGTH {
+        problems.push(problem(
+            "account number",
+            "1 to 35 characters for :25:",
+            statement.account_number,
+        ));
+    }
+    problems.extend(currency_problem("currency", statement.currency));
+
+    for (index, tx) in statement.transactions.iter().enumerate() {
+        if let Some(reference) = &tx.reference {
+            if reference.chars().count() > MT940_REFERENCE_MAX_LENGTH {
+                problems.push(problem(
+                    &format!("transaction {} reference", index + 1),
+                    "at most 16 characters for :61:",
+                    reference,
+                ));
+            }
+        }
+        if mt940_dialect::wrap_narrative(&tx.description).len() > MT940_NARRATIVE_LINES {
+            problems.push(problem(
+                &format!("transaction {} description", index + 1),
+                "at most 6 lines of 65 characters",
+                &tx.description,
+            ));
+        }
+    }
+    problems
+}
+
+fn camt053_problems(statement: StatementRef<'_>) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    let account_length = statement.account_number.chars().count();
+    if account_length == 0 || account_length > CAMT053_ACCOUNT_MAX_LENGTH {
+        problems.push(problem(
+            "account number",
+            "1 to 34 characters for <IBAN> or <Othr><Id>",
+            statement.account_number,
+        ));
+    }
+    problems.extend(currency_problem("currency", statement.currency));
+
+    for (index, tx) in statement.transactions.iter().enumerate() {
+        let currencies = [
+            ("currency", &tx.currency),
+            ("original currency", &tx.original_currency),
+        ];
+        for (name, currency) in currencies {
+            if let Some(currency) = currency {
+                let field = format!("transaction {} {}", index + 1, name);
+                problems.extend(currency_problem(&field, currency));
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(feature = "sberbank")]
+fn sberbank_problems(statement: StatementRef<'_>) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    let account = statement.account_number;
+    if account.len() != SBERBANK_ACCOUNT_LENGTH || !account.chars().all(|c| c.is_ascii_digit()) {
+        problems.push(problem(
+            "account number",
+            "a 20-digit domestic account number",
+            account,
+        ));
+    }
+    problems.extend(currency_problem("currency", statement.currency));
+    problems
+}
+
+/// The error of `currency` when it is no ISO 4217 code
+fn currency_problem(field: &str, currency: &str) -> Option<ParseError> {
+    Currency::parse(currency)
+        .err()
+        .map(|_| problem(field, "an ISO 4217 currency code", currency))
+}
+
+fn problem(field: &str, expected: &str, found: &str) -> ParseError {
+    ParseError::UnexpectedValue {
+        field: field.into(),
+        expected: expected.into(),
+        found: found.into(),
+    }
+}
+
+/// `TryFrom<&source>` impls for `$target`, failing with the first of
+/// `$problems` and converting a clone otherwise
+macro_rules! impl_checked_conversions {
+    ($target:ty, $problems:ident: $($source:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&$source> for $target {
+                type Error = ParseError;
+
+                fn try_from(statement: &$source) -> Result<Self, Self::Error> {
+                    match $problems(statement.as_view()).into_iter().next() {
+                        Some(problem) => Err(problem),
+                        None => Ok(statement.clone().into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_conversions!(
+    Mt940Statement,
+    mt940_problems: Camt053Statement,
+    Camt054Statement,
+    CsvStatement,
+    QifStatement,
+    Statement,
+);
+impl_checked_conversions!(
+    Camt053Statement,
+    camt053_problems: Mt940Statement,
+    Camt054Statement,
+    CsvStatement,
+    QifStatement,
+    Statement,
+);
+#[cfg(feature = "sberbank")]
+impl_checked_conversions!(
+    CsvStatement,
+    sberbank_problems: Mt940Statement,
+    Camt053Statement,
+    Camt054Statement,
+    QifStatement,
+    Statement,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:200102D25,00NTRFINV-1\n:86:Lunch\n:62F:C200102EUR75,00\n";
+
+    fn camt053() -> Camt053Statement {
+        Mt940Statement::from_read(&mut MT940.as_bytes())
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_mt940_limits() {
+        let mut statement = camt053();
+        assert!(Mt940Statement::validate(statement.as_view()).is_empty());
+
+        statement.account_number = "A".repeat(36);
+        statement.currency = "eur".into();
+        statement.transactions[0].reference = Some("REFERENCE-LONGER-THAN-16".into());
+        statement.transactions[0].description = "line\n".repeat(7);
+        let warnings = Mt940Statement::validate(statement.as_view());
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[1].message.contains("transaction 1 reference"));
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.code == "unexpected_value"));
+
+        let error = Mt940Statement::try_from(&statement).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid account number"));
+    }
+
+    #[test]
+    fn test_camt053_and_csv_accounts_and_currencies() {
+        let mut statement = Mt940Statement::from_read(&mut MT940.as_bytes()).unwrap();
+        assert!(Camt053Statement::try_from(&statement).is_ok());
+        #[cfg(feature = "sberbank")]
+        assert!(CsvStatement::try_from(&statement).is_err());
+
+        statement.currency = "EURO".into();
+        statement.transactions[0].original_currency = Some("ZZZ".into());
+        let warnings = Camt053Statement::validate(statement.as_view());
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1]
+            .message
+            .starts_with("Invalid transaction 1 original currency"));
+    }
+}
@@ -1,9 +1,9 @@
 //! Type conversions from CsvStatement to other formats
 //!
 //! Implements the `From` trait to enable idiomatic conversions between CSV
-//! and other format structures (MT940, CAMT.053).
+//! and other format structures (MT940, CAMT.053, QIF).
 
-use crate::{Camt053Statement, CsvStatement, Mt940Statement};
+use crate::{Camt053Statement, CsvStatement, Mt940Statement, QifStatement};
 
 /// Convert CSV to MT940 format
 ///
@@ -28,6 +28,8 @@ impl From<CsvStatement> for Mt940Statement {
             closing_date: csv.closing_date,
             closing_indicator: csv.closing_indicator,
             transactions: csv.transactions,
+            info: csv.info,
+            balances: Vec::new(),
         }
     }
 }
@@ -55,6 +57,29 @@ impl From<CsvStatement> for Camt053Statement {
             closing_date: csv.closing_date,
             closing_indicator: csv.closing_indicator,
             transactions: csv.transactions,
+            supplementary_data: Vec::new(),
+            info: csv.info,
+            balances: Vec::new(),
+        }
+    }
+}
+
+/// Convert CSV to QIF format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<CsvStatement> for QifStatement {
+    fn from(csv: CsvStatement) -> Self {
+        QifStatement {
+            account_number: csv.account_number,
+            currency: csv.currency,
+            opening_balance: csv.opening_balance,
+            opening_date: csv.opening_date,
+            opening_indicator: csv.opening_indicator,
+            closing_balance: csv.closing_balance,
+            closing_date: csv.closing_date,
+            closing_indicator: csv.closing_indicator,
+            transactions: csv.transactions,
         }
     }
 }
@@ -0,0 +1,578 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::formats::paging::{booked_amount, round_cents};
+use crate::formats::qif_statement::QIF_UNKNOWN_CURRENCY;
+#[cfg(feature = "sberbank")]
+use crate::quirks::ParseOptions;
+use crate::{
+    text, BalanceType, CsvStatement, ErrorLocation, ParseError, StatementInfo, Transaction,
+    TransactionType,
+};
+
+/// Layout of a CSV statement read by [`CsvStatement::from_read_with`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CsvDialect {
+    /// The Sberbank (СберБизнес) export with header, transaction table and
+    /// balance footer, read with the given bank quirk options; what
+    /// [`CsvStatement::from_read`] expects (requires the `sberbank` cargo feature)
+    #[cfg(feature = "sberbank")]
+    Sberbank(ParseOptions),
+    /// A flat one-row-per-transaction table described by a column mapping
+    Mapped(Box<CsvMapping>),
+}
+
+#[cfg(feature = "sberbank")]
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect::Sberbank(ParseOptions::default())
+    }
+}
+
+#[cfg(feature = "sberbank")]
+impl From<ParseOptions> for CsvDialect {
+    fn from(options: ParseOptions) -> Self {
+        CsvDialect::Sberbank(options)
+    }
+}
+
+impl From<CsvMapping> for CsvDialect {
+    fn from(mapping: CsvMapping) -> Self {
+        CsvDialect::Mapped(Box::new(mapping))
+    }
+}
+
+/// Name of the Revolut account statement profile
+pub const CSV_PROFILE_REVOLUT: &str = "revolut";
+/// Name of the Wise (TransferWise) statement profile
+pub const CSV_PROFILE_WISE: &str = "wise";
+/// Name of the N26 transaction export profile
+pub const CSV_PROFILE_N26: &str = "n26";
+/// Name of the ING Germany (ING-DiBa) Umsatzanzeige profile
+pub const CSV_PROFILE_ING: &str = "ing";
+/// Name of the Nordea Sweden transaction export profile
+pub const CSV_PROFILE_NORDEA: &str = "nordea";
+/// Name of the Tinkoff (T-Bank) operations export profile
+pub const CSV_PROFILE_TINKOFF: &str = "tinkoff";
+
+/// Names of all built-in CSV profiles accepted by [`CsvDialect::by_name`].
+pub const CSV_PROFILE_NAMES: [&str; 6] = [
+    CSV_PROFILE_REVOLUT,
+    CSV_PROFILE_WISE,
+    CSV_PROFILE_N26,
+    CSV_PROFILE_ING,
+    CSV_PROFILE_NORDEA,
+    CSV_PROFILE_TINKOFF,
+];
+
+impl CsvDialect {
+    /// Look up a built-in bank profile by name (case-insensitive, see [`CSV_PROFILE_NAMES`]).
+    pub fn by_name(name: &str) -> Option<CsvDialect> {
+        match name.to_lowercase().as_str() {
+            CSV_PROFILE_REVOLUT => Some(CsvDialect::revolut()),
+            CSV_PROFILE_WISE => Some(CsvDialect::wise()),
+            CSV_PROFILE_N26 => Some(CsvDialect::n26()),
+            CSV_PROFILE_ING => Some(CsvDialect::ing()),
+            CSV_PROFILE_NORDEA => Some(CsvDialect::nordea()),
+            CSV_PROFILE_TINKOFF => Some(CsvDialect::tinkoff()),
+            _ => None,
+        }
+    }
+
+    /// Revolut account statement; rows are booked on their completion date.
+    pub fn revolut() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            date: header("Completed Date"),
+            date_format: "%Y-%m-%d %H:%M:%S".into(),
+            currency_column: Some(header("Currency")),
+            ..CsvMapping::default()
+        })
+    }
+
+    /// Wise (TransferWise) statement of one currency balance.
+    pub fn wise() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            date_format: "%d-%m-%Y".into(),
+            reference: Some(header("TransferWise ID")),
+            counterparty_name: Some(header("Payee Name")),
+            counterparty_account: Some(header("Payee Account Number")),
+            currency_column: Some(header("Currency")),
+            ..CsvMapping::default()
+        })
+    }
+
+    /// N26 transaction export; amounts are always in euro.
+    pub fn n26() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            amount: Some(header("Amount (EUR)")),
+            description: Some(header("Payment reference")),
+            counterparty_name: Some(header("Payee")),
+            counterparty_account: Some(header("Account number")),
+            currency: "EUR".into(),
+            ..CsvMapping::default()
+        })
+    }
+
+    /// ING Germany Umsatzanzeige; the account details preamble is skipped.
+    pub fn ing() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            delimiter: b';',
+            date: header("Buchung"),
+            date_format: "%d.%m.%Y".into(),
+            amount: Some(header("Betrag")),
+            description: Some(header("Verwendungszweck")),
+            counterparty_name: Some(header("Auftraggeber/Empfänger")),
+            decimal_separator: ',',
+            currency: "EUR".into(),
+            currency_column: Some(header("Währung")),
+            ..CsvMapping::default()
+        })
+    }
+
+    /// Nordea Sweden transaction export.
+    pub fn nordea() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            delimiter: b';',
+            date: header("Bokföringsdag"),
+            date_format: "%Y/%m/%d".into(),
+            amount: Some(header("Belopp")),
+            description: Some(header("Rubrik")),
+            counterparty_name: Some(header("Namn")),
+            decimal_separator: ',',
+            currency: "SEK".into(),
+            currency_column: Some(header("Valuta")),
+            ..CsvMapping::default()
+        })
+    }
+
+    /// Tinkoff (T-Bank) operations export.
+    pub fn tinkoff() -> CsvDialect {
+        CsvDialect::from(CsvMapping {
+            delimiter: b';',
+            date: header("Дата операции"),
+            date_format: "%d.%m.%Y %H:%M:%S".into(),
+            amount: Some(header("Сумма платежа")),
+            description: Some(header("Описание")),
+            decimal_separator: ',',
+            currency: "RUB".into(),
+            currency_column: Some(header("Валюта платежа")),
+            ..CsvMapping::default()
+        })
+    }
+}
+
+/// Column given by its header title
+fn header(title: &str) -> CsvField {
+    CsvField::Header(title.into())
+}
+
+/// Column of a mapped CSV file, by position or by header title.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvField {
+    /// 0-based column index
+    Index(usize),
+    /// Title in the header row, compared case-insensitively after trimming
+    Header(String),
+}
+
+/// Column mapping for bank CSV exports with one transaction per row.
+///
+/// Such files rarely carry account, currency or balances, so `account_number`
+/// and `currency` are fixed values (unless a `currency_column` is mapped), and the statement opens at zero on the
+/// first booking date and closes at the net of the rows on the last one (like
+/// [`Camt054Statement`](crate::Camt054Statement)). Amounts come either from a
+/// signed `amount` column (negative is a debit) or from separate `debit` and
+/// `credit` columns.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{CsvDialect, CsvField, CsvMapping, CsvStatement};
+///
+/// let data = "Buchungstag;Betrag;Verwendungszweck\n07.01.2025;-25,50;Rent\n08.01.2025;1.100,00;Invoice 7\n";
+/// let mapping = CsvMapping {
+///     delimiter: b';',
+///     date: CsvField::Header("Buchungstag".into()),
+///     date_format: "%d.%m.%Y".into(),
+///     amount: Some(CsvField::Header("Betrag".into())),
+///     description: Some(CsvField::Index(2)),
+///     decimal_separator: ',',
+///     currency: "EUR".into(),
+///     ..CsvMapping::default()
+/// };
+///
+/// let statement =
+///     CsvStatement::from_read_with(&mut data.as_bytes(), &CsvDialect::from(mapping)).unwrap();
+/// assert_eq!(statement.transactions.len(), 2);
+/// assert_eq!(statement.closing_balance, 1074.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvMapping {
+    /// Field delimiter
+    pub delimiter: u8,
+    /// Lines before the header row (or the first data row) to ignore
+    pub skip_rows: usize,
+    /// Whether a row of column titles precedes the data; with a `date` column
+    /// given by title, rows before the first one containing it are skipped
+    pub has_headers: bool,
+    /// Booking date column
+    pub date: CsvField,
+    /// `chrono` format string of the booking date (e.g. `%d.%m.%Y`)
+    pub date_format: String,
+    /// Signed amount column; takes precedence over `debit`/`credit`
+    pub amount: Option<CsvField>,
+    /// Column with the amount of money paid out
+    pub debit: Option<CsvField>,
+    /// Column with the amount of money received
+    pub credit: Option<CsvField>,
+    /// Description column
+    pub description: Option<CsvField>,
+    /// Reference column
+    pub reference: Option<CsvField>,
+    /// Counterparty name column
+    pub counterparty_name: Option<CsvField>,
+    /// Counterparty account column
+    pub counterparty_account: Option<CsvField>,
+    /// Decimal separator of amounts; the other of `.`/`,` and spaces are thousands separators
+    pub decimal_separator: char,
+    /// Account number of the statement
+    pub account_number: String,
+    /// Currency of the statement
+    pub currency: String,
+    /// Currency column; its value in the first row overrides `currency`
+    pub currency_column: Option<CsvField>,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        CsvMapping {
+            delimiter: b',',
+            skip_rows: 0,
+            has_headers: true,
+            date: CsvField::Header("Date".into()),
+            date_format: "%Y-%m-%d".into(),
+            amount: Some(CsvField::Header("Amount".into())),
+            debit: None,
+            credit: None,
+            description: Some(CsvField::Header("Description".into())),
+            reference: None,
+            counterparty_name: None,
+            counterparty_account: None,
+            decimal_separator: '.',
+            account_number: String::new(),
+            currency: QIF_UNKNOWN_CURRENCY.into(),
+            currency_column: None,
+        }
+    }
+}
+
+/// Column indices of a mapping, resolved against the header row
+struct Columns {
+    date: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    description: Option<usize>,
+    reference: Option<usize>,
+    counterparty_name: Option<usize>,
+    counterparty_account: Option<usize>,
+    currency: Option<usize>,
+}
+
+impl CsvMapping {
+    /// Parse `content` into a statement using this mapping
+    pub(crate) fn parse(&self, content: &str) -> Result<CsvStatement, ParseError> {
+        if self.amount.is_none() && self.debit.is_none() && self.credit.is_none() {
+            return Err(ParseError::MissingField(
+                "amount, debit or credit column".into(),
+            ));
+        }
+
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+        let mut records = csv_reader.records().skip(self.skip_rows);
+
+        let header = if self.has_headers {
+            Some(self.find_header(&mut records)?)
+        } else {
+            None
+        };
+        let columns = self.resolve(header.as_ref())?;
+
+        let mut currency = None;
+        let mut transactions = Vec::new();
+        for record in records {
+            let record = record?;
+            if record.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+            let transaction = self.parse_row(&record, &columns, header.as_ref())?;
+            if currency.is_none() {
+                currency = columns
+                    .currency
+                    .and_then(|index| record.get(index))
+                    .map(str::trim)
+                    .filter(|code| !code.is_empty())
+                    .map(String::from);
+            }
+            transactions.push(transaction);
+        }
+
+        let (Some(first), Some(last)) = (
+            transactions.iter().map(|t| t.booking_date).min(),
+            transactions.iter().map(|t| t.booking_date).max(),
+        ) else {
+            return Err(ParseError::CsvError("No transaction rows".into()));
+        };
+        let net = round_cents(transactions.iter().map(booked_amount).sum());
+
+        Ok(CsvStatement {
+            account_number: self.account_number.clone(),
+            currency: currency.unwrap_or_else(|| self.currency.clone()),
+            opening_balance: 0.0,
+            opening_date: first,
+            opening_indicator: BalanceType::Credit,
+            closing_balance: net.abs(),
+            closing_date: last,
+            closing_indicator: if net < 0.0 {
+                BalanceType::Debit
+            } else {
+                BalanceType::Credit
+            },
+            transactions,
+            info: StatementInfo::default(),
+        })
+    }
+
+    /// Header row: the first row after `skip_rows`, or, for a date column given
+    /// by title, the first row containing that title, so bank preambles are skipped
+    fn find_header(
+        &self,
+        records: &mut impl Iterator<Item = Result<csv::StringRecord, csv::Error>>,
+    ) -> Result<csv::StringRecord, ParseError> {
+        for record in records {
+            let record = record?;
+            match &self.date {
+                CsvField::Header(title) if !record.iter().any(|c| same_title(c, title)) => {}
+                _ => return Ok(record),
+            }
+        }
+        let title = match &self.date {
+            CsvField::Header(title) => title.as_str(),
+            CsvField::Index(_) => "header row",
+        };
+        Err(ParseError::MissingField(format!("CSV column '{}'", title)))
+    }
+
+    /// Column indices of every mapped field
+    fn resolve(&self, header: Option<&csv::StringRecord>) -> Result<Columns, ParseError> {
+        let resolve = |field: &CsvField| match field {
+            CsvField::Index(index) => Ok(*index),
+            CsvField::Header(title) => header
+                .and_then(|header| header.iter().position(|column| same_title(column, title)))
+                .ok_or_else(|| ParseError::MissingField(format!("CSV column '{}'", title))),
+        };
+        let optional = |field: &Option<CsvField>| field.as_ref().map(resolve).transpose();
+
+        Ok(Columns {
+            date: resolve(&self.date)?,
+            amount: optional(&self.amount)?,
+            debit: optional(&self.debit)?,
+            credit: optional(&self.credit)?,
+            description: optional(&self.description)?,
+            reference: optional(&self.reference)?,
+            counterparty_name: optional(&self.counterparty_name)?,
+            counterparty_account: optional(&self.counterparty_account)?,
+            currency: optional(&self.currency_column)?,
+        })
+    }
+
+    /// One transaction from a data row; errors carry the row's line and the
+    /// offending column
+    fn parse_row(
+        &self,
+        record: &csv::StringRecord,
+        columns: &Columns,
+        header: Option<&csv::StringRecord>,
+    ) -> Result<Transaction, ParseError> {
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .map(str::trim)
+                .unwrap_or_default()
+        };
+        let text = |index: Option<usize>| Some(field(index).to_string()).filter(|t| !t.is_empty());
+        let locate = |error: ParseError, index: usize| {
+            error.at(ErrorLocation::Line {
+                line: record
+                    .position()
+                    .map_or(0, |position| position.line() as usize),
+                column: Some(index + 1),
+                field: header
+                    .and_then(|header| header.get(index))
+                    .map(|title| title.trim_start_matches('\u{feff}').trim().to_string()),
+            })
+        };
+        // Unmapped debit or credit columns count as zero
+        let amount = |index: Option<usize>| match index {
+            Some(index) => self
+                .parse_amount(field(Some(index)))
+                .map_err(|e| locate(e, index)),
+            None => Ok(0.0),
+        };
+
+        let date = field(Some(columns.date));
+        let booking_date = NaiveDate::parse_from_str(date, &self.date_format)
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| DateTime::<FixedOffset>::from_naive_utc_and_offset(date, Utc.fix()))
+            .ok_or_else(|| {
+                let error = ParseError::UnexpectedValue {
+                    field: "date".into(),
+                    expected: format!("a date in the format {}", self.date_format),
+                    found: date.into(),
+                };
+                locate(error, columns.date)
+            })?;
+
+        let signed_amount = if columns.amount.is_some() {
+            amount(columns.amount)?
+        } else {
+            amount(columns.credit)?.abs() - amount(columns.debit)?.abs()
+        };
+        let transaction_type = if signed_amount < 0.0 {
+            TransactionType::Debit
+        } else {
+            TransactionType::Credit
+        };
+
+        Ok(Transaction {
+            reference: text(columns.reference),
+            counterparty_name: text(columns.counterparty_name),
+            counterparty_account: text(columns.counterparty_account),
+            ..Transaction::new(
+                booking_date,
+                round_cents(signed_amount.abs()),
+                transaction_type,
+                field(columns.description),
+            )
+        })
+    }
+
+    /// Amount with this mapping's decimal separator; empty cells are zero
+    fn parse_amount(&self, value: &str) -> Result<f64, ParseError> {
+        text::parse_amount_with(value, self.decimal_separator)
+    }
+}
+
+/// Whether a header cell carries `title` (case-insensitive, ignoring a byte order mark)
+fn same_title(cell: &str, title: &str) -> bool {
+    cell.trim_start_matches('\u{feff}')
+        .trim()
+        .eq_ignore_ascii_case(title.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_debit_credit_columns_by_index() {
+        let data = "Statement export\n\
+                    2025-03-01,Fees,12.50,,,\n\
+                    2025-03-02,\"Invoice 7, March\",,\"1,100.00\",INV-7,ACME GmbH\n";
+        let mapping = CsvMapping {
+            skip_rows: 1,
+            has_headers: false,
+            date: CsvField::Index(0),
+            amount: None,
+            debit: Some(CsvField::Index(2)),
+            credit: Some(CsvField::Index(3)),
+            description: Some(CsvField::Index(1)),
+            reference: Some(CsvField::Index(4)),
+            counterparty_name: Some(CsvField::Index(5)),
+            account_number: "12345678".into(),
+            currency: "USD".into(),
+            ..CsvMapping::default()
+        };
+
+        let statement = mapping.parse(data).unwrap();
+        assert_eq!(statement.account_number, "12345678");
+        assert_eq!(statement.transactions.len(), 2);
+        assert_eq!(
+            statement.transactions[0].transaction_type,
+            TransactionType::Debit
+        );
+        assert_eq!(statement.transactions[1].amount, 1100.0);
+        assert_eq!(statement.transactions[1].description, "Invoice 7, March");
+        assert_eq!(
+            statement.transactions[1].counterparty_name.as_deref(),
+            Some("ACME GmbH")
+        );
+        assert_eq!(statement.closing_balance, 1087.5);
+        assert_eq!(statement.closing_indicator, BalanceType::Credit);
+    }
+
+    #[test]
+    fn test_mapping_errors() {
+        let data = "Date,Amount\n2025-03-01,abc\n";
+        assert!(matches!(
+            CsvMapping::default().parse("Day,Amount\n2025-03-01,1.00\n"),
+            Err(ParseError::MissingField(_))
+        ));
+        let error = CsvMapping {
+            description: None,
+            ..CsvMapping::default()
+        }
+        .parse(data)
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2, field Amount: Invalid amount: expected a number such as \
+             1540,00 or -12.5, found 'abc'"
+        );
+        assert!(matches!(
+            error.location(),
+            Some(ErrorLocation::Line {
+                line: 2,
+                column: Some(2),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_builtin_profiles() {
+        let revolut = "Type,Product,Started Date,Completed Date,Description,Amount,Fee,Currency,State,Balance\n\
+                       CARD_PAYMENT,Current,2025-02-03 18:22:10,2025-02-04 09:01:55,Coffee,-3.40,0.00,GBP,COMPLETED,96.60\n";
+        let Some(CsvDialect::Mapped(mapping)) = CsvDialect::by_name("Revolut") else {
+            panic!("revolut profile missing");
+        };
+        let statement = mapping.parse(revolut).unwrap();
+        assert_eq!(statement.currency, "GBP");
+        assert_eq!(statement.transactions[0].amount, 3.4);
+        assert_eq!(
+            statement.transactions[0].booking_date.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 2, 4).unwrap()
+        );
+
+        // ING prepends account details before the transaction table
+        let ing = "\u{feff}Umsatzanzeige;Datei erstellt am: 10.01.2025 10:00\n\
+                   IBAN;DE12 5001 0517 0000 0000 00\n\n\
+                   Buchung;Valuta;Auftraggeber/Empfänger;Buchungstext;Verwendungszweck;Saldo;Währung;Betrag;Währung\n\
+                   08.01.2025;08.01.2025;ACME GmbH;Gutschrift;Invoice 7;1.200,00;EUR;1.100,00;EUR\n";
+        let Some(CsvDialect::Mapped(mapping)) = CsvDialect::by_name(CSV_PROFILE_ING) else {
+            panic!("ing profile missing");
+        };
+        let statement = mapping.parse(ing).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(statement.transactions[0].amount, 1100.0);
+        assert_eq!(
+            statement.transactions[0].counterparty_name.as_deref(),
+            Some("ACME GmbH")
+        );
+        assert!(CsvDialect::by_name("unknown").is_none());
+    }
+}
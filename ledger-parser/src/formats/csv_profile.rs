@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io::Write;
+
+use crate::formats::presets::{PRESET_QUICKBOOKS_CSV, PRESET_XERO};
+use crate::formats::utils::escape_formula;
+use crate::{ParseError, StatementRef, Transaction, TransactionType};
+
+/// Data written into one column of a profile-based CSV export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CsvColumn {
+    /// Booking date, rendered with the profile's `date_format`
+    BookingDate,
+    /// Value date as stored on the transaction (empty if missing)
+    ValueDate,
+    /// Signed amount: credits positive, debits negative
+    SignedAmount,
+    /// Absolute amount, paired with a [`CsvColumn::DebitCreditMark`] column
+    Amount,
+    /// Amount only on debit rows, empty otherwise
+    DebitAmount,
+    /// Amount only on credit rows, empty otherwise
+    CreditAmount,
+    /// Fixed marker for the direction of the transaction
+    DebitCreditMark {
+        /// Text written for debits (money out)
+        debit: String,
+        /// Text written for credits (money in)
+        credit: String,
+    },
+    /// Transaction description
+    Description,
+    /// Transaction reference (empty if missing)
+    Reference,
+    /// Counterparty name (empty if missing)
+    CounterpartyName,
+    /// Counterparty account (empty if missing)
+    CounterpartyAccount,
+    /// Statement currency
+    Currency,
+    /// Statement account number
+    AccountNumber,
+    /// The same text on every row
+    Constant(String),
+}
+
+/// A named CSV layout: delimiter, columns and date/amount conventions.
+///
+/// Profiles describe flat, one-row-per-transaction CSV files as expected by
+/// accounting tools, as opposed to the fixed Sberbank layout of [`CsvStatement`](crate::CsvStatement).
+/// Built-in layouts are available as constructors (and by name through
+/// [`ExportPreset`](crate::ExportPreset)); custom profiles can be constructed
+/// directly or deserialized with serde.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{CsvProfile, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C200101EUR100,00\n\
+///             :61:200102D25,50NTRFNONREF\n:86:Office supplies\n:62F:C200102EUR74,50\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let profile = CsvProfile::quickbooks();
+/// let mut output = Vec::new();
+/// profile.write_to(statement.as_view(), &mut output).unwrap();
+///
+/// let csv = String::from_utf8(output).unwrap();
+/// assert_eq!(csv, "Date,Description,Amount\n01/02/2020,Office supplies,-25.50\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvProfile {
+    /// Profile name, used in error messages and CLI listings
+    pub name: String,
+    /// Field delimiter
+    pub delimiter: u8,
+    /// Column headers and the data written under each of them
+    pub columns: Vec<(String, CsvColumn)>,
+    /// Whether to write a header row with the column titles
+    pub header: bool,
+    /// `chrono` format string for dates (e.g. `%d.%m.%Y`)
+    pub date_format: String,
+    /// Decimal separator for amounts
+    pub decimal_separator: char,
+    /// Prefix text cells starting with `=`, `+`, `-` or `@` with `'` so spreadsheet
+    /// applications do not evaluate them as formulas (CSV injection); amounts and
+    /// dates are never changed
+    pub sanitize_formulas: bool,
+}
+
+impl CsvProfile {
+    /// QuickBooks Online 3-column bank import: date, description, signed amount.
+    pub fn quickbooks() -> CsvProfile {
+        CsvProfile {
+            name: PRESET_QUICKBOOKS_CSV.into(),
+            delimiter: b',',
+            columns: vec![
+                ("Date".into(), CsvColumn::BookingDate),
+                ("Description".into(), CsvColumn::Description),
+                ("Amount".into(), CsvColumn::SignedAmount),
+            ],
+            header: true,
+            date_format: "%m/%d/%Y".into(),
+            decimal_separator: '.',
+            sanitize_formulas: true,
+        }
+    }
+
+    /// Xero precoded bank statement import (`*`-marked columns are mandatory in Xero).
+    pub fn xero() -> CsvProfile {
+        CsvProfile {
+            name: PRESET_XERO.into(),
+            delimiter: b',',
+            columns: vec![
+                ("*Date".into(), CsvColumn::BookingDate),
+                ("*Amount".into(), CsvColumn::SignedAmount),
+                ("Payee".into(), CsvColumn::CounterpartyName),
+                ("Description".into(), CsvColumn::Description),
+                ("Reference".into(), CsvColumn::Reference),
+            ],
+            header: true,
+            date_format: "%d/%m/%Y".into(),
+            decimal_separator: '.',
+            sanitize_formulas: true,
+        }
+    }
+
+    /// Write the statement's transactions using this profile.
+    ///
+    /// # Errors
+    /// Returns `ParseError::CsvError` if writing fails.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        let mut csv_writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
+
+        if self.header {
+            csv_writer.write_record(self.columns.iter().map(|(title, _)| title))?;
+        }
+
+        for transaction in statement.transactions {
+            let row = self
+                .columns
+                .iter()
+                .map(|(_, column)| self.render(column, statement, transaction))
+                .collect::<Result<Vec<_>, _>>()?;
+            csv_writer.write_record(&row)?;
+        }
+
+        csv_writer.flush()?;
+        Ok(())
+    }
+
+    /// Text of `column` for `transaction`
+    fn render(
+        &self,
+        column: &CsvColumn,
+        statement: StatementRef<'_>,
+        transaction: &Transaction,
+    ) -> Result<String, ParseError> {
+        let is_debit = transaction.transaction_type == TransactionType::Debit;
+        let text = match column {
+            CsvColumn::BookingDate => {
+                // An invalid user-supplied format makes chrono's Display fail; to_string() would panic
+                let mut date = String::new();
+                write!(
+                    date,
+                    "{}",
+                    transaction.booking_date.format(&self.date_format)
+                )
+                .map_err(|_| ParseError::InvalidFieldValue {
+                    field: "date_format".into(),
+                    value: self.date_format.clone(),
+                })?;
+                date
+            }
+            CsvColumn::ValueDate => {
+                let mut date = String::new();
+                if let Some(value_date) = transaction.value_date {
+                    write!(date, "{}", value_date.format(&self.date_format)).map_err(|_| {
+                        ParseError::InvalidFieldValue {
+                            field: "date_format".into(),
+                            value: self.date_format.clone(),
+                        }
+                    })?;
+                }
+                date
+            }
+            CsvColumn::SignedAmount if is_debit => self.format_amount(-transaction.amount),
+            CsvColumn::SignedAmount | CsvColumn::Amount => self.format_amount(transaction.amount),
+            CsvColumn::DebitAmount if is_debit => self.format_amount(transaction.amount),
+            CsvColumn::CreditAmount if !is_debit => self.format_amount(transaction.amount),
+            CsvColumn::DebitAmount | CsvColumn::CreditAmount => String::new(),
+            CsvColumn::DebitCreditMark { debit, credit } => {
+                if is_debit {
+                    debit.clone()
+                } else {
+                    credit.clone()
+                }
+            }
+            CsvColumn::Description => self.text(transaction.description.replace('\n', " ")),
+            CsvColumn::Reference => self.text(transaction.reference.clone().unwrap_or_default()),
+            CsvColumn::CounterpartyName => {
+                self.text(transaction.counterparty_name.clone().unwrap_or_default())
+            }
+            CsvColumn::CounterpartyAccount => {
+                self.text(transaction.counterparty_account.clone().unwrap_or_default())
+            }
+            CsvColumn::Currency => statement.currency.to_string(),
+            CsvColumn::AccountNumber => self.text(statement.account_number.to_string()),
+            CsvColumn::Constant(text) => text.clone(),
+        };
+        Ok(text)
+    }
+
+    /// Statement text as written into a cell, escaped if `sanitize_formulas` is set
+    fn text(&self, text: String) -> String {
+        if self.sanitize_formulas {
+            escape_formula(text)
+        } else {
+            text
+        }
+    }
+
+    /// Two-decimal amount with the profile's decimal separator
+    fn format_amount(&self, amount: f64) -> String {
+        let formatted = format!("{:.2}", amount);
+        if self.decimal_separator == '.' {
+            formatted
+        } else {
+            formatted.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::fixtures;
+    use crate::Camt053Statement;
+
+    fn statement() -> Camt053Statement {
+        fixtures::statement(vec![
+            fixtures::transaction(1100.0, TransactionType::Credit, "Invoice 7"),
+            fixtures::transaction(25.5, TransactionType::Debit, "Fees"),
+        ])
+    }
+
+    fn write(profile: CsvProfile) -> String {
+        let mut output = Vec::new();
+        profile
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_xero_profile() {
+        assert_eq!(
+            write(CsvProfile::xero()),
+            "*Date,*Amount,Payee,Description,Reference\n\
+             07/03/2025,1100.00,ACME GmbH,Invoice 7,INV-7\n\
+             07/03/2025,-25.50,ACME GmbH,Fees,INV-7\n"
+        );
+    }
+
+    #[test]
+    fn test_debit_credit_columns_with_comma_decimals() {
+        let profile = CsvProfile {
+            name: "custom".into(),
+            delimiter: b';',
+            columns: vec![
+                ("Soll".into(), CsvColumn::DebitAmount),
+                ("Haben".into(), CsvColumn::CreditAmount),
+                (
+                    "S/H".into(),
+                    CsvColumn::DebitCreditMark {
+                        debit: "H".into(),
+                        credit: "S".into(),
+                    },
+                ),
+            ],
+            header: false,
+            date_format: "%d.%m.%Y".into(),
+            decimal_separator: ',',
+            sanitize_formulas: false,
+        };
+        assert_eq!(write(profile), ";1100,00;S\n25,50;;H\n");
+    }
+
+    #[test]
+    fn test_invalid_date_format_is_an_error() {
+        let mut profile = CsvProfile::xero();
+        profile.date_format = "%Q".into();
+        let result = profile.write_to(statement().as_view(), &mut Vec::new());
+        assert!(matches!(result, Err(ParseError::InvalidFieldValue { .. })));
+    }
+
+    #[test]
+    fn test_formula_cells_are_escaped() {
+        let mut statement = statement();
+        statement.transactions[0].description = "=HYPERLINK(\"http://x\")".into();
+        statement.transactions[1].counterparty_name = Some("@SUM(A1)".into());
+        statement.transactions[1].reference = Some("-INV".into());
+
+        let mut output = Vec::new();
+        CsvProfile::xero()
+            .write_to(statement.as_view(), &mut output)
+            .unwrap();
+        let csv = String::from_utf8(output).unwrap();
+        assert!(csv.contains("1100.00,ACME GmbH,\"'=HYPERLINK(\"\"http://x\"\")\",INV-7\n"));
+        // Negative amounts stay numbers
+        assert!(csv.contains("-25.50,'@SUM(A1),Fees,'-INV\n"));
+
+        let mut output = Vec::new();
+        let profile = CsvProfile {
+            sanitize_formulas: false,
+            ..CsvProfile::xero()
+        };
+        profile.write_to(statement.as_view(), &mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains(",@SUM(A1),"));
+    }
+}
@@ -1,21 +1,43 @@
-use crate::formats::cvs_const::*;
-use crate::formats::formats_const::*;
-use crate::{formats::utils, BalanceType, ParseError, Transaction, TransactionType};
+use crate::formats::csv_mapping::CsvDialect;
+use crate::formats::paging;
+use crate::{BalanceType, ParseError, StatementInfo, Transaction};
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
-use std::io::{Read, Write};
+use std::io::Read;
+#[cfg(feature = "sberbank")]
+use {
+    crate::diagnostics::{Diagnostics, ParseStats, ParseWarning},
+    crate::formats::cvs_const::*,
+    crate::formats::formats_const::*,
+    crate::formats::utils::{self, escape_formula},
+    crate::quirks::{MissingBalancePolicy, ParseOptions, Quirk},
+    crate::{text, Currency, TransactionType},
+    chrono::NaiveDate,
+    std::io::Write,
+    std::path::Path,
+};
+
+/// Footer balance: absolute amount, date and side
+#[cfg(feature = "sberbank")]
+type FooterBalance = (f64, DateTime<FixedOffset>, BalanceType);
 
 /// CSV bank statement structure.
 ///
 /// Parses from and writes to CSV format using the `csv` crate.
 /// Fields are identical to Mt940/Camt053 for seamless conversions.
 ///
-/// This implementation handles the Russian Sberbank CSV format with:
+/// [`CsvStatement::from_read`] and [`CsvStatement::write_to`] handle the
+/// Russian Sberbank CSV format (with the `sberbank` cargo feature, on by
+/// default) with:
 /// - Multi-line header section (metadata)
 /// - Transaction rows with separate debit/credit columns
 /// - Multi-line cells (account information)
 /// - Footer section with balance information
 /// - Russian text and comma decimal separators
+///
+/// Other banks' flat exports are read through a [`CsvMapping`](crate::CsvMapping)
+/// with [`CsvStatement::from_read_with`] and written with a
+/// [`CsvProfile`](crate::CsvProfile), with or without the feature.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CsvStatement {
     /// Account number (IBAN or local format) from the bank statement
@@ -36,8 +58,91 @@ pub struct CsvStatement {
     pub closing_indicator: BalanceType,
     /// List of transactions in chronological order
     pub transactions: Vec<Transaction>,
+    /// Statement identification, sequence numbers and period
+    #[serde(default, skip_serializing_if = "StatementInfo::is_empty")]
+    pub info: StatementInfo,
+}
+
+/// Options for [`CsvStatement::write_to_with`].
+///
+/// `Default` reproduces the output of [`CsvStatement::write_to`], which sanitizes formulas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvWriteOptions {
+    /// Prefix descriptions and references starting with `=`, `+`, `-` or `@`
+    /// with `'` so spreadsheet applications do not evaluate them as formulas
+    /// (on by default; turn off for CSV read by programs rather than people)
+    pub sanitize_formulas: bool,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        Self {
+            sanitize_formulas: true,
+        }
+    }
+}
+
+impl CsvStatement {
+    /// Parse CSV in the given [`CsvDialect`]: the Sberbank layout with explicit
+    /// [`ParseOptions`](crate::ParseOptions) (e.g. to force or disable bank quirks),
+    /// or any bank's export through a [`CsvMapping`](crate::CsvMapping).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CsvStatement::from_read`]; mapped files also fail with
+    /// `ParseError::MissingField` for unknown header titles and
+    /// `ParseError::CsvError` naming the first row that cannot be parsed.
+    pub fn from_read_with<R: Read>(
+        reader: &mut R,
+        dialect: &CsvDialect,
+    ) -> Result<Self, ParseError> {
+        match dialect {
+            #[cfg(feature = "sberbank")]
+            CsvDialect::Sberbank(options) => {
+                Self::from_read_with_options(reader, options).map(|(statement, _)| statement)
+            }
+            CsvDialect::Mapped(mapping) => {
+                // Read entire content - needed because multi-line cells complicate streaming
+                let mut content = String::new();
+                reader.read_to_string(&mut content)?;
+                mapping.parse(&content)
+            }
+        }
+    }
+
+    /// Split the statement into standalone statements of at most `max_transactions` entries.
+    ///
+    /// Intermediate opening/closing balances are recomputed from the running total,
+    /// so every part is internally consistent and can be written to its own file.
+    pub fn split_by_transaction_count(&self, max_transactions: usize) -> Vec<CsvStatement> {
+        let balances = paging::Balances {
+            opening_balance: self.opening_balance,
+            opening_indicator: self.opening_indicator.clone(),
+            opening_date: self.opening_date,
+            closing_balance: self.closing_balance,
+            closing_indicator: self.closing_indicator.clone(),
+            closing_date: self.closing_date,
+        };
+
+        paging::split(&balances, &self.transactions, max_transactions)
+            .into_iter()
+            .map(|(balances, transactions)| CsvStatement {
+                account_number: self.account_number.clone(),
+                currency: self.currency.clone(),
+                opening_balance: balances.opening_balance,
+                opening_date: balances.opening_date,
+                opening_indicator: balances.opening_indicator,
+                closing_balance: balances.closing_balance,
+                closing_date: balances.closing_date,
+                closing_indicator: balances.closing_indicator,
+                transactions,
+                info: self.info.clone(),
+            })
+            .collect()
+    }
 }
 
+#[cfg(feature = "sberbank")]
 impl CsvStatement {
     /// Parse CSV from any Read source (file, stdin, buffer).
     ///
@@ -46,6 +151,10 @@ impl CsvStatement {
     /// - Transaction section (lines 13+): Transaction rows
     /// - Footer section: Balance information
     ///
+    /// Exports saved as Windows-1251 instead of UTF-8 are recognized and
+    /// transcoded; a byte order mark is honoured. Set
+    /// [`ParseOptions::encoding`] to name the encoding instead.
+    ///
     /// # Errors
     ///
     /// Returns `ParseError::CsvError` if:
@@ -63,10 +172,82 @@ impl CsvStatement {
     /// let statement = CsvStatement::from_read(&mut file).unwrap();
     /// ```
     pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        Self::from_read_with(reader, &CsvDialect::default())
+    }
+
+    /// Parse a Sberbank CSV export with explicit [`ParseOptions`], returning the
+    /// statement with a [`ParseWarning`] for every transaction row skipped.
+    ///
+    /// Leniently, a row of the transaction table whose date or amounts cannot be
+    /// parsed is left out; with `options.strict` it fails the parse. Empty rows
+    /// and, with the Sberbank quirk, service rows without an amount are not
+    /// transactions and never warned about.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CsvStatement::from_read`], and in strict mode the error of the
+    /// first malformed row, located at its line. An unknown `options.encoding`
+    /// fails with `ParseError::UnexpectedValue`, input malformed in it with
+    /// `ParseError::IoError`.
+    pub fn from_read_with_options<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        Self::from_read_with_stats(reader, options)
+            .map(|(statement, stats)| (statement, stats.into_warnings()))
+    }
+
+    /// Parse a Sberbank CSV export with explicit [`ParseOptions`], as
+    /// [`CsvStatement::from_read_with_options`], returning how many
+    /// transaction rows were read, parsed and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CsvStatement::from_read_with_options`].
+    pub fn from_read_with_stats<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, ParseStats), ParseError> {
         // Read entire content - needed because multi-line cells complicate streaming
-        let mut content = String::new();
-        reader.read_to_string(&mut content)?;
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+        let content = utils::decode(&content, options.encoding.as_deref(), LEGACY_ENCODING)?;
 
+        let mut diagnostics = Diagnostics::new(options.strict);
+        let statement = Self::from_content_with(&content, options, &mut diagnostics)?;
+        Ok((statement, diagnostics.into_stats()))
+    }
+
+    /// Parse CSV from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory, which matters for very large statement files.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened, otherwise the
+    /// same errors as [`CsvStatement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_bytes(path, |bytes| {
+            Self::from_content(&utils::decode(bytes, None, LEGACY_ENCODING)?)
+        })
+    }
+
+    /// Parse CSV from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        Self::from_content_with(
+            content,
+            &ParseOptions::default(),
+            &mut Diagnostics::default(),
+        )
+    }
+
+    /// Parse CSV from already loaded content with the quirks selected by `options`
+    fn from_content_with(
+        content: &str,
+        options: &ParseOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ParseError> {
         if content.is_empty() {
             return Err(ParseError::CsvError(ERROR_EMPTY_INPUT.into()));
         }
@@ -81,29 +262,58 @@ impl CsvStatement {
         let records: Vec<csv::StringRecord> =
             csv_reader.records().collect::<Result<Vec<_>, _>>()?;
 
+        let skip_zero_rows = options
+            .active_quirks(content)
+            .contains(&Quirk::SberbankZeroAmountRows);
+        Self::from_records(
+            &records,
+            skip_zero_rows,
+            options.missing_balance,
+            diagnostics,
+        )
+    }
+
+    /// Build the statement from the rows of a Sberbank export, read from CSV or a spreadsheet
+    pub(crate) fn from_records(
+        records: &[csv::StringRecord],
+        skip_zero_rows: bool,
+        missing_balance: MissingBalancePolicy,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ParseError> {
         if records.len() < MIN_CSV_LINES {
             return Err(ParseError::CsvError(ERROR_CSV_TOO_SHORT.into()));
         }
 
         // Extract account number from header (line 6, column 12)
-        let account_number = Self::extract_account_number(&records)?;
+        let account_number = Self::extract_account_number(records)?;
 
         // Extract currency from header (line 9, column 2)
-        let currency = Self::extract_currency(&records)?;
+        let currency = Self::extract_currency(records, &account_number, diagnostics)?;
 
         // Find transaction section and footer
-        let (transaction_start, footer_start) = Self::find_sections(&records)?;
+        let (transaction_start, footer_start) = Self::find_sections(records)?;
 
         // Parse transactions
-        let transactions = Self::parse_transactions(&records, transaction_start, footer_start)?;
+        let transactions = Self::parse_transactions(
+            records,
+            transaction_start,
+            footer_start,
+            skip_zero_rows,
+            diagnostics,
+        )?;
 
         // Extract balances from footer
-        let (opening_balance, opening_date, opening_indicator) =
-            Self::extract_opening_balance(&records, footer_start)?;
-        let (closing_balance, closing_date, closing_indicator) =
-            Self::extract_closing_balance(&records, footer_start)?;
+        let (
+            (opening_balance, opening_date, opening_indicator),
+            (closing_balance, closing_date, closing_indicator),
+        ) = Self::resolve_balances(
+            Self::extract_opening_balance(records, footer_start)?,
+            Self::extract_closing_balance(records, footer_start)?,
+            &transactions,
+            missing_balance,
+        )?;
 
-        Ok(CsvStatement {
+        let statement = CsvStatement {
             account_number,
             currency,
             opening_balance,
@@ -113,17 +323,50 @@ impl CsvStatement {
             closing_date,
             closing_indicator,
             transactions,
-        })
+            info: StatementInfo::default(),
+        };
+        trace_statement!("parsed statement", "csv", statement);
+
+        Ok(statement)
     }
 
     /// Write CSV to any Write destination (file, stdout, buffer).
     ///
-    /// Outputs in Russian Sberbank CSV format.
+    /// Outputs in Russian Sberbank CSV format, with formula-like cells escaped
+    /// (see [`CsvWriteOptions`]).
     ///
     /// # Errors
     ///
     /// Returns `ParseError::CsvError` if writing fails.
     pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        self.write_to_with(writer, &CsvWriteOptions::default())
+    }
+
+    /// Write CSV using explicit writer options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::CsvError` if writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::{CsvStatement, CsvWriteOptions};
+    /// # fn demo(statement: &CsvStatement) -> Result<(), ledger_parser::ParseError> {
+    /// let options = CsvWriteOptions {
+    ///     sanitize_formulas: false,
+    /// };
+    /// let mut output = Vec::new();
+    /// statement.write_to_with(&mut output, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_to_with<W: Write>(
+        &self,
+        writer: &mut W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "csv", self);
         let mut csv_writer = csv::WriterBuilder::new()
             .flexible(true) // Allow records with varying field counts
             .from_writer(writer);
@@ -132,7 +375,7 @@ impl CsvStatement {
         Self::write_header(&mut csv_writer, &self.account_number, &self.currency)?;
 
         // Write transaction section
-        Self::write_transactions(&mut csv_writer, &self.transactions)?;
+        Self::write_transactions(&mut csv_writer, &self.transactions, options)?;
 
         // Write footer section
         Self::write_footer(
@@ -172,8 +415,13 @@ impl CsvStatement {
         Err(ParseError::CsvError(ERROR_ACCOUNT_NOT_FOUND.into()))
     }
 
-    /// Extract currency from header section
-    fn extract_currency(records: &[csv::StringRecord]) -> Result<String, ParseError> {
+    /// Extract currency from header section, or from the currency digits of
+    /// `account_number`; assume RUB with a warning when neither has one
+    fn extract_currency(
+        records: &[csv::StringRecord],
+        account_number: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<String, ParseError> {
         let record = records
             .get(CURRENCY_LINE_INDEX)
             .ok_or_else(|| ParseError::CsvError(ERROR_MISSING_CURRENCY.into()))?;
@@ -192,7 +440,30 @@ impl CsvStatement {
             }
         }
 
-        // Default to RUB if not found
+        // Statements written by `write_to` carry a bare ISO code in the header
+        for record in &records[0..records.len().min(MAX_ACCOUNT_SEARCH_LINES)] {
+            for field in record.iter() {
+                let trimmed = field.trim();
+                if trimmed.len() == CURRENCY_CODE_LENGTH
+                    && trimmed.chars().all(|c| c.is_ascii_uppercase())
+                {
+                    if let Ok(currency) = Currency::parse(trimmed) {
+                        return Ok(currency.code().into());
+                    }
+                }
+            }
+        }
+
+        // Russian account numbers carry the numeric currency code
+        let account_currency = account_number
+            .get(ACCOUNT_CURRENCY_DIGITS)
+            .and_then(|digits| digits.parse().ok())
+            .and_then(Currency::from_numeric);
+        if let Some(currency) = account_currency {
+            return Ok(currency.code().into());
+        }
+
+        diagnostics.assume(ParseError::CsvError(WARNING_CURRENCY_ASSUMED.into()))?;
         Ok(CURRENCY_RUB.into())
     }
 
@@ -229,31 +500,44 @@ impl CsvStatement {
         Ok((transaction_start, footer_start))
     }
 
-    /// Parse transaction rows
+    /// Parse transaction rows; `skip_zero_rows` drops rows without any amount,
+    /// malformed rows go to `diagnostics`
     fn parse_transactions(
         records: &[csv::StringRecord],
         start: usize,
         end: usize,
+        skip_zero_rows: bool,
+        diagnostics: &mut Diagnostics,
     ) -> Result<Vec<Transaction>, ParseError> {
         let mut transactions = Vec::new();
 
-        for record in &records[start..end] {
+        for (row, record) in records.iter().enumerate().take(end).skip(start) {
             // Skip empty rows
             if record.iter().all(|f| f.trim().is_empty()) {
                 continue;
             }
 
-            // Try to parse as transaction
-            if let Ok(transaction) = Self::parse_transaction_record(record) {
-                transactions.push(transaction);
+            match Self::parse_transaction_record(record, skip_zero_rows) {
+                Ok(Some(transaction)) => {
+                    diagnostics.parsed(1);
+                    transactions.push(transaction)
+                }
+                Ok(None) => diagnostics.dropped(),
+                Err(error) => {
+                    diagnostics.skip(error.at_line(Self::record_line(record, row), None))?
+                }
             }
         }
 
         Ok(transactions)
     }
 
-    /// Parse a single transaction record
-    fn parse_transaction_record(record: &csv::StringRecord) -> Result<Transaction, ParseError> {
+    /// Parse a single transaction record; `None` for a service row without an
+    /// amount when `skip_zero_rows` is set
+    fn parse_transaction_record(
+        record: &csv::StringRecord,
+        skip_zero_rows: bool,
+    ) -> Result<Option<Transaction>, ParseError> {
         // Get field values by index
         let get_field =
             |idx: usize| -> String { record.get(idx).map(|s| s.trim().into()).unwrap_or_default() };
@@ -276,10 +560,10 @@ impl CsvStatement {
         // Determine transaction type and amount
         let (amount, transaction_type) = if debit_amount > 0.0 {
             (debit_amount, TransactionType::Debit)
-        } else if credit_amount > 0.0 {
+        } else if credit_amount > 0.0 || !skip_zero_rows {
             (credit_amount, TransactionType::Credit)
         } else {
-            return Err(ParseError::CsvError(ERROR_NO_TRANSACTION_AMOUNT.into()));
+            return Ok(None);
         };
 
         // Extract document number (around index 14)
@@ -300,37 +584,29 @@ impl CsvStatement {
             }
         }
 
-        Ok(Transaction {
-            booking_date,
-            value_date: None, // Not available in this format
-            amount,
-            transaction_type,
-            description,
+        Ok(Some(Transaction {
             reference,
-            counterparty_name: None,    // Could extract from account field
-            counterparty_account: None, // Could extract from account field
-        })
+            ..Transaction::new(booking_date, amount, transaction_type, description)
+        }))
     }
 
     /// Parse date format (comma as decimal separator)
     fn parse_date(date_str: &str) -> Result<DateTime<FixedOffset>, ParseError> {
-        utils::parse_date(date_str)
-            .map_err(|_| ParseError::CsvError(format!("Invalid date: {}", date_str)))
+        text::parse_date(date_str)
     }
 
     /// Parse amount format (comma as decimal separator)
     fn parse_amount(amount_str: &str) -> Result<f64, ParseError> {
-        utils::parse_amount(amount_str)
-            .map_err(|_| ParseError::CsvError(format!("Invalid amount: {}", amount_str)))
+        text::parse_amount(amount_str)
     }
 
-    /// Extract opening balance from footer section
+    /// Extract opening balance from footer section, `None` if there is no such row
     fn extract_opening_balance(
         records: &[csv::StringRecord],
         footer_start: usize,
-    ) -> Result<(f64, DateTime<FixedOffset>, BalanceType), ParseError> {
+    ) -> Result<Option<FooterBalance>, ParseError> {
         // Look for "Входящий остаток" in footer
-        for record in &records[footer_start..] {
+        for (row, record) in records.iter().enumerate().skip(footer_start) {
             for (i, field) in record.iter().enumerate() {
                 if field.to_lowercase().contains(OPENING_BALANCE_LABEL) {
                     // Amount is typically a few columns later - skip zeros
@@ -349,10 +625,11 @@ impl CsvStatement {
                                 };
 
                                 // Try to extract date (often at end of row)
-                                let date =
-                                    Self::parse_date(&Self::extract_date_from_record(record)?)?;
+                                let date = Self::extract_date_from_record(record)
+                                    .and_then(|date| Self::parse_date(&date))
+                                    .map_err(|e| e.at_line(Self::record_line(record, row), None))?;
 
-                                return Ok((amount.abs(), date, indicator));
+                                return Ok(Some((amount.abs(), date, indicator)));
                             }
                         }
                     }
@@ -360,16 +637,24 @@ impl CsvStatement {
             }
         }
 
-        Err(ParseError::CsvError(ERROR_OPENING_BALANCE_NOT_FOUND.into()))
+        Ok(None)
+    }
+
+    /// Line of `record` in the source file: its CSV position, or the sheet row
+    /// for records read from a workbook
+    fn record_line(record: &csv::StringRecord, index: usize) -> usize {
+        record
+            .position()
+            .map_or(index + 1, |position| position.line() as usize)
     }
 
-    /// Extract closing balance from footer section
+    /// Extract closing balance from footer section, `None` if there is no such row
     fn extract_closing_balance(
         records: &[csv::StringRecord],
         footer_start: usize,
-    ) -> Result<(f64, DateTime<FixedOffset>, BalanceType), ParseError> {
+    ) -> Result<Option<FooterBalance>, ParseError> {
         // Look for "Исходящий остаток" in footer
-        for record in &records[footer_start..] {
+        for (row, record) in records.iter().enumerate().skip(footer_start) {
             for (i, field) in record.iter().enumerate() {
                 if field.to_lowercase().contains(CLOSING_BALANCE_LABEL) {
                     // Amount is typically a few columns later - skip zeros
@@ -388,9 +673,11 @@ impl CsvStatement {
                                 };
 
                                 // Try to extract date (often at end of row)
-                                let date_str = Self::extract_date_from_record(record)?;
+                                let date = Self::extract_date_from_record(record)
+                                    .and_then(|date| Self::parse_date(&date))
+                                    .map_err(|e| e.at_line(Self::record_line(record, row), None))?;
 
-                                return Ok((amount.abs(), Self::parse_date(&date_str)?, indicator));
+                                return Ok(Some((amount.abs(), date, indicator)));
                             }
                         }
                     }
@@ -398,13 +685,91 @@ impl CsvStatement {
             }
         }
 
-        Err(ParseError::CsvError(ERROR_CLOSING_BALANCE_NOT_FOUND.into()))
+        Ok(None)
+    }
+
+    /// Fill in a missing opening or closing balance as `policy` asks.
+    ///
+    /// A filled-in opening balance is dated at the first transaction, a closing
+    /// balance at the last one; without transactions, at the other balance.
+    fn resolve_balances(
+        opening: Option<FooterBalance>,
+        closing: Option<FooterBalance>,
+        transactions: &[Transaction],
+        policy: MissingBalancePolicy,
+    ) -> Result<(FooterBalance, FooterBalance), ParseError> {
+        let opening_error = || ParseError::CsvError(ERROR_OPENING_BALANCE_NOT_FOUND.into());
+        let closing_error = || ParseError::CsvError(ERROR_CLOSING_BALANCE_NOT_FOUND.into());
+        let (opening, closing) = match (opening, closing) {
+            (Some(opening), Some(closing)) => return Ok((opening, closing)),
+            balances => balances,
+        };
+
+        let net = paging::round_cents(transactions.iter().map(paging::booked_amount).sum());
+        let signed = |(amount, _, indicator): &FooterBalance| match indicator {
+            BalanceType::Credit => *amount,
+            BalanceType::Debit => -amount,
+        };
+        let (opening_amount, closing_amount) = match policy {
+            MissingBalancePolicy::Error => {
+                return Err(if opening.is_none() {
+                    opening_error()
+                } else {
+                    closing_error()
+                })
+            }
+            MissingBalancePolicy::AssumeZero => (
+                opening.as_ref().map_or(ZERO_AMOUNT, signed),
+                closing.as_ref().map_or(ZERO_AMOUNT, signed),
+            ),
+            MissingBalancePolicy::DeriveFromTransactions => match (&opening, &closing) {
+                (Some(opening), _) => {
+                    let opening = signed(opening);
+                    (opening, paging::round_cents(opening + net))
+                }
+                (None, Some(closing)) => {
+                    let closing = signed(closing);
+                    (paging::round_cents(closing - net), closing)
+                }
+                (None, None) => (ZERO_AMOUNT, net),
+            },
+        };
+
+        let first_booking = transactions.first().map(|t| t.booking_date);
+        let last_booking = transactions.last().map(|t| t.booking_date);
+        let opening_date = opening
+            .as_ref()
+            .map(|(_, date, _)| *date)
+            .or(first_booking)
+            .or(closing.as_ref().map(|(_, date, _)| *date))
+            .ok_or_else(opening_error)?;
+        let closing_date = closing
+            .as_ref()
+            .map(|(_, date, _)| *date)
+            .or(last_booking)
+            .unwrap_or(opening_date);
+        let balance = |amount: f64, date| {
+            let indicator = if amount >= 0.0 {
+                BalanceType::Credit
+            } else {
+                BalanceType::Debit
+            };
+            (amount.abs(), date, indicator)
+        };
+        Ok((
+            balance(opening_amount, opening_date),
+            balance(closing_amount, closing_date),
+        ))
     }
 
     /// Extract date from a record (looks for date patterns)
     fn extract_date_from_record(record: &csv::StringRecord) -> Result<String, ParseError> {
         for field in record.iter().rev() {
             let trimmed = field.trim();
+            // Exact DD.MM.YYYY date, as written by `write_to`
+            if NaiveDate::parse_from_str(trimmed, BALANCE_DATE_FORMAT).is_ok() {
+                return Ok(trimmed.into());
+            }
             // Look for Russian date format like "01 января 2024 г."
             if trimmed.to_lowercase().contains(RUSSIAN_YEAR_SUFFIX)
                 && trimmed.len() > MIN_DATE_STRING_LENGTH
@@ -486,7 +851,15 @@ impl CsvStatement {
     fn write_transactions<W: Write>(
         csv_writer: &mut csv::Writer<W>,
         transactions: &[Transaction],
+        options: &CsvWriteOptions,
     ) -> Result<(), ParseError> {
+        let text = |text: &str| {
+            if options.sanitize_formulas {
+                escape_formula(text.to_string())
+            } else {
+                text.to_string()
+            }
+        };
         for tx in transactions {
             let mut row = vec![String::new(); OUTPUT_ROW_COLUMNS];
             let booking_date = tx.booking_date;
@@ -505,10 +878,10 @@ impl CsvStatement {
             }
 
             if let Some(ref reference) = tx.reference {
-                row[REFERENCE_COLUMN_INDEX] = reference.clone();
+                row[REFERENCE_COLUMN_INDEX] = text(reference);
             }
 
-            row[DESCRIPTION_COLUMN_INDEX] = tx.description.clone();
+            row[DESCRIPTION_COLUMN_INDEX] = text(&tx.description);
 
             csv_writer.write_record(&row)?;
         }
@@ -577,7 +950,7 @@ impl CsvStatement {
             "",
             "",
             "",
-            &opening_date.format("%d.%m.%Y").to_string(),
+            &opening_date.format(BALANCE_DATE_FORMAT).to_string(),
         ])?;
 
         let closing_sign = match closing_indicator {
@@ -604,16 +977,214 @@ impl CsvStatement {
             "",
             "",
             "",
-            &closing_date.format("%d.%m.%Y").to_string(),
+            &closing_date.format(BALANCE_DATE_FORMAT).to_string(),
         ])?;
 
         Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sberbank"))]
 mod tests {
     use super::*;
+    use crate::ErrorLocation;
+
+    #[test]
+    fn test_write_sanitizes_formulas_unless_disabled() {
+        let statement = CsvStatement {
+            account_number: "40702810000000000001".into(),
+            currency: "RUB".into(),
+            opening_balance: 0.0,
+            opening_date: text::parse_date("2025-03-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 10.0,
+            closing_date: text::parse_date("2025-03-01").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![Transaction {
+                reference: Some("+7".into()),
+                ..Transaction::new(
+                    text::parse_date("2025-03-01").unwrap(),
+                    10.0,
+                    TransactionType::Credit,
+                    "=1+2",
+                )
+            }],
+            info: StatementInfo::default(),
+        };
+
+        let mut sanitized = Vec::new();
+        statement.write_to(&mut sanitized).unwrap();
+        let sanitized = String::from_utf8(sanitized).unwrap();
+        assert!(sanitized.contains(",'=1+2"));
+        assert!(sanitized.contains(",'+7,"));
+
+        let mut plain = Vec::new();
+        let options = CsvWriteOptions {
+            sanitize_formulas: false,
+        };
+        statement.write_to_with(&mut plain, &options).unwrap();
+        assert!(String::from_utf8(plain).unwrap().contains(",=1+2"));
+    }
+
+    /// Sberbank export of a 100.00 opening, -30.00 and +5.00 bookings, without the `label` row
+    fn export_without(label: &str) -> String {
+        let transaction = |day: &str, amount, transaction_type| {
+            Transaction::new(
+                text::parse_date(day).unwrap(),
+                amount,
+                transaction_type,
+                "Payment",
+            )
+        };
+        let statement = CsvStatement {
+            account_number: "40702810000000000001".into(),
+            currency: CURRENCY_RUB.into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-03-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 75.0,
+            closing_date: text::parse_date("2025-03-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                transaction("2025-03-03", 30.0, TransactionType::Debit),
+                transaction("2025-03-05", 5.0, TransactionType::Credit),
+            ],
+            info: StatementInfo::default(),
+        };
+        let mut output = Vec::new();
+        statement.write_to(&mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.to_lowercase().contains(label))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn parse_with(
+        content: &str,
+        missing_balance: MissingBalancePolicy,
+    ) -> Result<CsvStatement, ParseError> {
+        let dialect = CsvDialect::Sberbank(ParseOptions {
+            missing_balance,
+            ..ParseOptions::default()
+        });
+        CsvStatement::from_read_with(&mut content.as_bytes(), &dialect)
+    }
+
+    #[test]
+    fn test_missing_opening_balance_policies() {
+        let content = export_without(OPENING_BALANCE_LABEL);
+        assert!(matches!(
+            parse_with(&content, MissingBalancePolicy::Error),
+            Err(ParseError::CsvError(message)) if message == ERROR_OPENING_BALANCE_NOT_FOUND
+        ));
+
+        let derived = parse_with(&content, MissingBalancePolicy::DeriveFromTransactions).unwrap();
+        assert_eq!(derived.opening_balance, 100.0);
+        assert_eq!(derived.opening_indicator, BalanceType::Credit);
+        assert_eq!(
+            derived.opening_date,
+            text::parse_date("2025-03-03").unwrap()
+        );
+        assert_eq!(derived.closing_balance, 75.0);
+
+        let zero = parse_with(&content, MissingBalancePolicy::AssumeZero).unwrap();
+        assert_eq!(zero.opening_balance, 0.0);
+        assert_eq!(zero.closing_balance, 75.0);
+    }
+
+    #[test]
+    fn test_windows_1251_export_is_transcoded() {
+        let content = export_without("\u{0}");
+        let (cp1251, _, _) = encoding_rs::WINDOWS_1251.encode(&content);
+        let expected = CsvStatement::from_read(&mut content.as_bytes()).unwrap();
+        assert_eq!(CsvStatement::from_read(&mut &cp1251[..]).unwrap(), expected);
+
+        let utf8 = ParseOptions {
+            encoding: Some("utf-8".into()),
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            CsvStatement::from_read_with_options(&mut &cp1251[..], &utf8),
+            Err(ParseError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_closing_and_both_balances() {
+        let content = export_without(CLOSING_BALANCE_LABEL);
+        let derived = parse_with(&content, MissingBalancePolicy::DeriveFromTransactions).unwrap();
+        assert_eq!(derived.closing_balance, 75.0);
+        assert_eq!(
+            derived.closing_date,
+            text::parse_date("2025-03-05").unwrap()
+        );
+
+        let content = export_without("остаток");
+        let derived = parse_with(&content, MissingBalancePolicy::DeriveFromTransactions).unwrap();
+        assert_eq!(derived.opening_balance, 0.0);
+        assert_eq!(derived.closing_balance, 25.0);
+        assert_eq!(derived.closing_indicator, BalanceType::Debit);
+        assert!(parse_with(&content, MissingBalancePolicy::Error).is_err());
+    }
+
+    #[test]
+    fn test_currency_from_account_or_assumed() {
+        let content = export_without("rub");
+        let usd = content.replace("40702810000000000001", "40702840000000000001");
+        assert_eq!(
+            CsvStatement::from_read(&mut usd.as_bytes())
+                .unwrap()
+                .currency,
+            "USD"
+        );
+
+        let unknown = content.replace("40702810000000000001", "40702000000000000001");
+        let (statement, warnings) =
+            CsvStatement::from_read_with_options(&mut unknown.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!(statement.currency, CURRENCY_RUB);
+        assert_eq!(
+            warnings[0].message,
+            format!("CSV error: {}", WARNING_CURRENCY_ASSUMED)
+        );
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        assert!(CsvStatement::from_read_with_options(&mut unknown.as_bytes(), &strict).is_err());
+    }
+
+    #[test]
+    fn test_malformed_rows_warn_or_fail() {
+        let content = export_without("no such row").replace("03.03.2025", "33.03.2025");
+        let (statement, warnings) =
+            CsvStatement::from_read_with_options(&mut content.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "unexpected_value");
+        assert!(warnings[0].message.contains("'33.03.2025'"));
+        assert!(matches!(
+            warnings[0].location,
+            Some(ErrorLocation::Line { .. })
+        ));
+        let (_, stats) =
+            CsvStatement::from_read_with_stats(&mut content.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!((stats.rows_seen, stats.rows_parsed), (2, 1));
+        assert_eq!(stats.rows_skipped, warnings);
+
+        let strict = CsvStatement::from_read_with_options(
+            &mut content.as_bytes(),
+            &ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+        );
+        assert!(matches!(strict, Err(ParseError::Located { .. })));
+    }
 
     #[test]
     fn test_parse_date() {
@@ -648,6 +1219,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_zero_amount_row_quirk() {
+        let mut fields = vec![""; DESCRIPTION_SEARCH_START_INDEX + 1];
+        fields[DATE_COLUMN_INDEX] = "20.02.2024";
+        fields[DESCRIPTION_SEARCH_START_INDEX] = "Service row";
+        let record = csv::StringRecord::from(fields);
+
+        assert!(CsvStatement::parse_transaction_record(&record, true)
+            .unwrap()
+            .is_none());
+        let transaction = CsvStatement::parse_transaction_record(&record, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.amount, 0.0);
+        assert_eq!(transaction.transaction_type, TransactionType::Credit);
+        assert_eq!(transaction.description, "Service row");
+    }
+
     #[test]
     fn test_parse_empty_csv() {
         let input = "";
@@ -668,6 +1257,7 @@ mod tests {
             closing_date: CsvStatement::parse_date("2024-12-31").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![],
+            info: StatementInfo::default(),
         };
 
         assert_eq!(statement.account_number, "40702810440000030888");
@@ -106,6 +106,13 @@ pub const MAX_VALID_YEAR: u32 = 2100;
 /// Currency information is in line 9 (index 8) of the header section.
 pub const CURRENCY_LINE_INDEX: usize = 8;
 
+/// Length of an ISO 4217 currency code
+pub const CURRENCY_CODE_LENGTH: usize = 3;
+
+/// Digits of a Russian account number holding the numeric currency code
+/// (`810` for roubles, `840` for US dollars)
+pub const ACCOUNT_CURRENCY_DIGITS: std::ops::Range<usize> = 5..8;
+
 /// ## Currency and Language Constants
 ///
 /// These constants define currency codes and their Russian language equivalents.
@@ -147,6 +154,9 @@ pub const OPENING_BALANCE_LABEL: &str = "входящий остаток";
 /// Russian text for "Closing Balance"
 pub const CLOSING_BALANCE_LABEL: &str = "исходящий остаток";
 
+/// Date format of the footer balance rows written by `write_to`
+pub const BALANCE_DATE_FORMAT: &str = "%d.%m.%Y";
+
 /// Russian date format suffix (year indicator)
 pub const RUSSIAN_YEAR_SUFFIX: &str = "г.";
 
@@ -202,6 +212,9 @@ pub const FOOTER_OPENING_BALANCE: &str = "Входящий остаток";
 /// Footer label for closing balance
 pub const FOOTER_CLOSING_BALANCE: &str = "Исходящий остаток";
 
+/// Code page of Sberbank exports not saved as UTF-8
+pub const LEGACY_ENCODING: &encoding_rs::Encoding = encoding_rs::WINDOWS_1251;
+
 /// ## Error Messages
 ///
 /// Standardized error messages for CSV parsing.
@@ -221,6 +234,9 @@ pub const ERROR_ACCOUNT_NOT_FOUND: &str = "Account number not found in header";
 /// Error message for missing currency
 pub const ERROR_MISSING_CURRENCY: &str = "Missing currency in header";
 
+/// Warning for a currency found neither in the header nor in the account number
+pub const WARNING_CURRENCY_ASSUMED: &str = "Currency not found in header, assuming RUB";
+
 /// Error message for transaction section not found
 pub const ERROR_TRANSACTION_SECTION_NOT_FOUND: &str =
     "Transaction section not found (missing 'Дата проводки')";
@@ -228,9 +244,6 @@ pub const ERROR_TRANSACTION_SECTION_NOT_FOUND: &str =
 /// Error message for empty date field
 pub const ERROR_EMPTY_DATE_FIELD: &str = "Empty date field";
 
-/// Error message for transaction with no amount
-pub const ERROR_NO_TRANSACTION_AMOUNT: &str = "Transaction has no amount";
-
 /// Error message for opening balance not found
 pub const ERROR_OPENING_BALANCE_NOT_FOUND: &str = "Opening balance not found";
 
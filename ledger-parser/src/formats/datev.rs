@@ -0,0 +1,372 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+use crate::formats::presets::PRESET_DATEV;
+use crate::{ParseError, StatementRef, Transaction, TransactionType};
+
+/// Marker of a DATEV file produced outside DATEV (`EXTF`)
+const DATEV_FORMAT_MARKER: &str = "EXTF";
+/// DATEV header version number
+const DATEV_HEADER_VERSION: u32 = 700;
+/// Data category of a booking batch (Buchungsstapel)
+const DATEV_CATEGORY_BOOKING_BATCH: u32 = 21;
+/// Format name matching [`DATEV_CATEGORY_BOOKING_BATCH`]
+const DATEV_FORMAT_NAME: &str = "Buchungsstapel";
+/// Format version of the booking batch layout
+const DATEV_FORMAT_VERSION: u32 = 13;
+/// Origin code (Herkunft) for data taken over from bank statements
+const DATEV_ORIGIN: &str = "RE";
+/// Booking type: financial accounting (Finanzbuchführung)
+const DATEV_BOOKING_TYPE_FINANCIAL: u32 = 1;
+/// Field separator mandated by the DATEV format
+const DATEV_DELIMITER: char = ';';
+/// Maximum length of the booking text (Buchungstext)
+const MAX_BOOKING_TEXT_LENGTH: usize = 60;
+/// Maximum length of the document field (Belegfeld 1)
+const MAX_DOCUMENT_FIELD_LENGTH: usize = 36;
+/// Substitute for characters that Windows-1252 cannot represent
+const CP1252_REPLACEMENT: u8 = b'?';
+
+/// Column headings of the booking batch, in the order DATEV requires.
+///
+/// Only the leading columns are written; DATEV accepts a shortened layout as
+/// long as the columns keep their positions.
+const DATEV_COLUMNS: [&str; 14] = [
+    "Umsatz (ohne Soll/Haben-Kz)",
+    "Soll/Haben-Kennzeichen",
+    "WKZ Umsatz",
+    "Kurs",
+    "Basis-Umsatz",
+    "WKZ Basis-Umsatz",
+    "Konto",
+    "Gegenkonto (ohne BU-Schlüssel)",
+    "BU-Schlüssel",
+    "Belegdatum",
+    "Belegfeld 1",
+    "Belegfeld 2",
+    "Skonto",
+    "Buchungstext",
+];
+
+/// Writer profile for DATEV booking batches (`EXTF` format, category 21).
+///
+/// German accountants import bank movements into DATEV as a booking batch: a
+/// semicolon-separated, Windows-1252 encoded file with a fixed header record,
+/// a column heading record and one booking per transaction. Each transaction is
+/// booked against `bank_account`; Soll/Haben is seen from that account, so
+/// money received is `S` and money paid out is `H`. The counter account cannot
+/// be derived from a bank statement and defaults to `contra_account`.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{DatevProfile, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:DE89370400440532013000\n:28C:1/1\n:60F:C250101EUR100,00\n\
+///             :61:250107C1100,00NTRFINV7\n:86:Invoice 7\n:62F:C250107EUR1200,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let profile = DatevProfile {
+///     advisor_number: 29098,
+///     client_number: 55003,
+///     ..DatevProfile::default()
+/// };
+/// let mut output = Vec::new();
+/// profile.write_to(statement.as_view(), &mut output).unwrap();
+/// assert!(output.starts_with(b"\"EXTF\";700;21;\"Buchungsstapel\";13;"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatevProfile {
+    /// Tax advisor number (Beraternummer)
+    pub advisor_number: u32,
+    /// Client number (Mandantennummer)
+    pub client_number: u32,
+    /// First day of the fiscal year; defaults to January 1st of the statement's opening year
+    pub fiscal_year_start: Option<NaiveDate>,
+    /// Length of general ledger account numbers (Sachkontenlänge)
+    pub account_length: u8,
+    /// Chart of accounts (Sachkontenrahmen), e.g. `03` for SKR03
+    pub chart_of_accounts: String,
+    /// Ledger account of the bank account (Konto), e.g. `1200` in SKR03
+    pub bank_account: String,
+    /// Counter account used for every booking (Gegenkonto), e.g. the `1590` suspense account
+    pub contra_account: String,
+    /// Batch description (Bezeichnung)
+    pub description: String,
+    /// Creation timestamp written to the header; `None` uses the current time
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl Default for DatevProfile {
+    fn default() -> Self {
+        DatevProfile {
+            advisor_number: 0,
+            client_number: 0,
+            fiscal_year_start: None,
+            account_length: 4,
+            chart_of_accounts: "03".into(),
+            bank_account: "1200".into(),
+            contra_account: "1590".into(),
+            description: "Kontoauszug".into(),
+            created_at: None,
+        }
+    }
+}
+
+impl DatevProfile {
+    /// Profile name used by [`ExportPreset`](crate::ExportPreset)
+    pub fn name(&self) -> &'static str {
+        PRESET_DATEV
+    }
+
+    /// Write the statement as a DATEV booking batch.
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        let mut content = String::new();
+
+        content.push_str(&self.header_record(statement));
+        content.push_str("\r\n");
+        content.push_str(&DATEV_COLUMNS.join(&DATEV_DELIMITER.to_string()));
+        content.push_str("\r\n");
+        for transaction in statement.transactions {
+            content.push_str(&self.booking_record(statement, transaction));
+            content.push_str("\r\n");
+        }
+
+        writer.write_all(&encode_windows_1252(&content))?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// The `EXTF` header record (31 fields)
+    fn header_record(&self, statement: StatementRef<'_>) -> String {
+        let created_at = self
+            .created_at
+            .unwrap_or_else(|| Utc::now().naive_utc())
+            .format("%Y%m%d%H%M%S%3f")
+            .to_string();
+        let fiscal_year_start = self.fiscal_year_start.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(statement.opening_date.year(), 1, 1)
+                .unwrap_or(statement.opening_date.date_naive())
+        });
+
+        let fields = [
+            quote(DATEV_FORMAT_MARKER),
+            DATEV_HEADER_VERSION.to_string(),
+            DATEV_CATEGORY_BOOKING_BATCH.to_string(),
+            quote(DATEV_FORMAT_NAME),
+            DATEV_FORMAT_VERSION.to_string(),
+            created_at,
+            String::new(), // imported (filled in by DATEV)
+            quote(DATEV_ORIGIN),
+            quote(""), // exported by
+            quote(""), // imported by
+            self.advisor_number.to_string(),
+            self.client_number.to_string(),
+            fiscal_year_start.format("%Y%m%d").to_string(),
+            self.account_length.to_string(),
+            statement.opening_date.format("%Y%m%d").to_string(),
+            statement.closing_date.format("%Y%m%d").to_string(),
+            quote(&self.description),
+            quote(""), // dictation code
+            DATEV_BOOKING_TYPE_FINANCIAL.to_string(),
+            "0".into(), // accounting purpose: independent of law
+            "0".into(), // not locked (Festschreibung)
+            quote(statement.currency),
+            String::new(), // reserved
+            quote(""),     // derivatives chart of accounts
+            String::new(), // reserved
+            String::new(), // reserved
+            quote(&self.chart_of_accounts),
+            String::new(), // industry solution ID
+            String::new(), // reserved
+            quote(""),     // reserved
+            quote(""),     // application information
+        ];
+        fields.join(&DATEV_DELIMITER.to_string())
+    }
+
+    /// One booking line for `transaction`
+    fn booking_record(&self, statement: StatementRef<'_>, transaction: &Transaction) -> String {
+        let debit_credit = match transaction.transaction_type {
+            TransactionType::Credit => "S",
+            TransactionType::Debit => "H",
+        };
+        let fields = [
+            format!("{:.2}", transaction.amount).replace('.', ","),
+            quote(debit_credit),
+            quote(statement.currency),
+            String::new(), // exchange rate
+            String::new(), // base amount
+            String::new(), // base currency
+            self.bank_account.clone(),
+            self.contra_account.clone(),
+            quote(""), // tax key (BU-Schlüssel)
+            transaction.booking_date.format("%d%m").to_string(),
+            quote(&document_field(
+                transaction.reference.as_deref().unwrap_or(""),
+            )),
+            quote(""),     // Belegfeld 2
+            String::new(), // cash discount
+            quote(&truncate(
+                &transaction.description.replace(['\r', '\n'], " "),
+                MAX_BOOKING_TEXT_LENGTH,
+            )),
+        ];
+        fields.join(&DATEV_DELIMITER.to_string())
+    }
+}
+
+/// DATEV text field: wrapped in quotes, inner quotes doubled
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// First `max` characters of `text`
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+/// Belegfeld 1 only allows `A-Z 0-9 $ & % * + - /`; other characters are dropped
+fn document_field(reference: &str) -> String {
+    let allowed: String = reference
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || "$&%*+-/".contains(*c))
+        .collect();
+    truncate(&allowed, MAX_DOCUMENT_FIELD_LENGTH)
+}
+
+/// Encode `text` as Windows-1252, replacing unmappable characters with `?`
+fn encode_windows_1252(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x00..=0x7F | 0xA0..=0xFF => c as u8,
+            _ => cp1252_high_byte(c).unwrap_or(CP1252_REPLACEMENT),
+        })
+        .collect()
+}
+
+/// Windows-1252 byte for characters mapped into the 0x80-0x9F range
+fn cp1252_high_byte(c: char) -> Option<u8> {
+    let byte = match c {
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    };
+    Some(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::fixtures;
+    use crate::Camt053Statement;
+
+    fn statement() -> Camt053Statement {
+        let tx = |amount, transaction_type, description| Transaction {
+            reference: Some("inv_7/2025".into()),
+            counterparty_name: None,
+            ..fixtures::transaction(amount, transaction_type, description)
+        };
+        fixtures::statement(vec![
+            tx(1100.0, TransactionType::Credit, "Zahlung Müller \"Büro\""),
+            tx(25.5, TransactionType::Debit, "Gebühren €"),
+        ])
+    }
+
+    fn write() -> Vec<u8> {
+        let profile = DatevProfile {
+            advisor_number: 29098,
+            client_number: 55003,
+            created_at: NaiveDate::from_ymd_opt(2025, 4, 1)
+                .and_then(|date| date.and_hms_milli_opt(14, 4, 40, 439)),
+            ..DatevProfile::default()
+        };
+        let mut output = Vec::new();
+        profile
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        output
+    }
+
+    #[test]
+    fn test_header_record_matches_spec_layout() {
+        let output = write();
+        let text: String = output.iter().map(|&b| b as char).collect();
+        let header = text.lines().next().unwrap();
+        let fields: Vec<&str> = header.split(';').collect();
+
+        assert_eq!(fields.len(), 31);
+        assert_eq!(
+            &fields[..6],
+            &[
+                "\"EXTF\"",
+                "700",
+                "21",
+                "\"Buchungsstapel\"",
+                "13",
+                "20250401140440439"
+            ]
+        );
+        assert_eq!(fields[7], "\"RE\"");
+        assert_eq!(
+            &fields[10..16],
+            &["29098", "55003", "20250101", "4", "20250301", "20250331"]
+        );
+        assert_eq!(fields[21], "\"EUR\"");
+        assert_eq!(fields[26], "\"03\"");
+    }
+
+    #[test]
+    fn test_booking_records() {
+        let output = write();
+        let lines: Vec<&[u8]> = output.split(|&b| b == b'\n').collect();
+
+        assert!(lines[1].starts_with(b"Umsatz (ohne Soll/Haben-Kz);Soll/Haben-Kennzeichen;"));
+        assert_eq!(
+            lines[2],
+            b"1100,00;\"S\";\"EUR\";;;;1200;1590;\"\";0703;\"INV7/2025\";\"\";;\"Zahlung M\xFCller \"\"B\xFCro\"\"\"\r"
+        );
+        assert_eq!(
+            lines[3],
+            b"25,50;\"H\";\"EUR\";;;;1200;1590;\"\";0703;\"INV7/2025\";\"\";;\"Geb\xFChren \x80\"\r"
+        );
+    }
+
+    #[test]
+    fn test_encode_windows_1252_replaces_unmappable() {
+        assert_eq!(encode_windows_1252("ä€Ж"), vec![0xE4, 0x80, b'?']);
+    }
+}
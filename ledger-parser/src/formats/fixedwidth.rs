@@ -0,0 +1,340 @@
+//! Toolkit for fixed-width record formats (Standard 18, Norma43, CODA, ...).
+//!
+//! A format is described declaratively as [`RecordLayout`]s made of
+//! [`FieldSpec`]s; rendering pads/validates each value and parsing slices a
+//! record back into its fields. Amount and date encodings shared by these
+//! formats live here as well, so format modules only hold definitions and the
+//! mapping to and from [`Transaction`](crate::Transaction)s.
+//!
+//! The reading half (record iterator, field slicing, decoders) has no reader
+//! on top of it yet, hence the `dead_code` allowances below.
+
+use chrono::NaiveDate;
+
+use crate::ParseError;
+
+/// How a field is padded and validated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FieldKind {
+    /// Left-aligned text, space-padded and cut to the field width
+    Alpha,
+    /// Right-aligned digits, zero-padded; longer values are an error
+    Numeric,
+}
+
+/// One field of a fixed-width record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FieldSpec {
+    /// Field name used in error messages
+    pub name: &'static str,
+    /// Width in characters
+    pub width: usize,
+    /// Padding and validation rule
+    pub kind: FieldKind,
+}
+
+impl FieldSpec {
+    /// Text field
+    pub(crate) const fn alpha(name: &'static str, width: usize) -> FieldSpec {
+        FieldSpec {
+            name,
+            width,
+            kind: FieldKind::Alpha,
+        }
+    }
+
+    /// Digits-only field
+    pub(crate) const fn numeric(name: &'static str, width: usize) -> FieldSpec {
+        FieldSpec {
+            name,
+            width,
+            kind: FieldKind::Numeric,
+        }
+    }
+
+    /// Pad or validate `value` to exactly the field width
+    pub(crate) fn render(&self, value: &str) -> Result<String, ParseError> {
+        match self.kind {
+            FieldKind::Alpha => Ok(alpha(value, self.width)),
+            FieldKind::Numeric => {
+                let value = value.trim();
+                if value.chars().count() > self.width || !value.chars().all(|c| c.is_ascii_digit())
+                {
+                    return Err(ParseError::InvalidFieldValue {
+                        field: self.name.into(),
+                        value: value.to_string(),
+                    });
+                }
+                Ok(format!("{:0>width$}", value, width = self.width))
+            }
+        }
+    }
+}
+
+/// Ordered fields making up one record type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RecordLayout {
+    /// Record type name used in error messages
+    pub name: &'static str,
+    /// Fields in record order
+    pub fields: &'static [FieldSpec],
+}
+
+impl RecordLayout {
+    /// Total record length in characters
+    pub(crate) fn length(&self) -> usize {
+        self.fields.iter().map(|field| field.width).sum()
+    }
+
+    /// Render one value per field into a record of exactly [`RecordLayout::length`] characters.
+    ///
+    /// # Errors
+    /// Returns `ParseError::InvalidFormat` if the number of values does not match
+    /// the layout, or `ParseError::InvalidFieldValue` for invalid numeric values.
+    pub(crate) fn render(&self, values: &[&str]) -> Result<String, ParseError> {
+        if values.len() != self.fields.len() {
+            return Err(ParseError::InvalidFormat(format!(
+                "{} record expects {} fields, got {}",
+                self.name,
+                self.fields.len(),
+                values.len()
+            )));
+        }
+        let mut record = String::with_capacity(self.length());
+        for (field, value) in self.fields.iter().zip(values) {
+            record.push_str(&field.render(value)?);
+        }
+        Ok(record)
+    }
+
+    /// Slice `record` into one value per field.
+    ///
+    /// Editors often strip trailing blanks, so a short record is read as if it
+    /// were space-padded; a record longer than the layout is an error.
+    ///
+    /// # Errors
+    /// Returns `ParseError::InvalidFormat` if the record is too long.
+    #[allow(dead_code)]
+    pub(crate) fn parse<'a>(&self, record: &'a str) -> Result<Vec<&'a str>, ParseError> {
+        let length = record.chars().count();
+        if length > self.length() {
+            return Err(ParseError::InvalidFormat(format!(
+                "{} record has {} characters, expected {}",
+                self.name,
+                length,
+                self.length()
+            )));
+        }
+
+        let mut values = Vec::with_capacity(self.fields.len());
+        let mut rest = record;
+        for field in self.fields {
+            let end = rest
+                .char_indices()
+                .nth(field.width)
+                .map_or(rest.len(), |(index, _)| index);
+            let (value, tail) = rest.split_at(end);
+            values.push(value);
+            rest = tail;
+        }
+        Ok(values)
+    }
+}
+
+/// Iterator over the records of a fixed-width file, see [`records`]
+#[allow(dead_code)]
+pub(crate) struct Records<'a> {
+    rest: &'a str,
+    length: usize,
+    line_based: bool,
+    number: usize,
+}
+
+impl<'a> Iterator for Records<'a> {
+    /// 1-based record number and record text (without line terminator)
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (record, tail) = if self.line_based {
+                match self.rest.split_once('\n') {
+                    Some((line, tail)) => (line.trim_end_matches('\r'), tail),
+                    None => (self.rest.trim_end_matches('\r'), ""),
+                }
+            } else {
+                let end = self
+                    .rest
+                    .char_indices()
+                    .nth(self.length)
+                    .map_or(self.rest.len(), |(index, _)| index);
+                self.rest.split_at(end)
+            };
+            self.rest = tail;
+            if record.trim().is_empty() {
+                continue;
+            }
+            self.number += 1;
+            return Some((self.number, record));
+        }
+    }
+}
+
+/// Iterate over the records of `content`.
+///
+/// Records are usually one per line (LF or CRLF); content without any line
+/// break is cut into chunks of `length` characters, as produced by mainframe
+/// transfers. Blank lines are skipped.
+#[allow(dead_code)]
+pub(crate) fn records(content: &str, length: usize) -> Records<'_> {
+    let content = content.trim_start_matches('\u{feff}');
+    Records {
+        rest: content,
+        length: length.max(1),
+        line_based: content.contains('\n'),
+        number: 0,
+    }
+}
+
+/// Left-aligned text, space-padded or cut to `width` characters
+pub(crate) fn alpha(text: &str, width: usize) -> String {
+    format!("{:<width$}", text.chars().take(width).collect::<String>())
+}
+
+/// Right-aligned zero-padded number
+pub(crate) fn numeric(value: u64, width: usize) -> String {
+    format!("{:0>width$}", value)
+}
+
+/// Absolute `amount` as digits with `decimals` implied decimal places, zero-padded to `width`.
+///
+/// # Errors
+/// Returns `ParseError::InvalidFieldValue` if the amount does not fit the field.
+#[allow(dead_code)]
+pub(crate) fn encode_amount(
+    amount: f64,
+    width: usize,
+    decimals: u32,
+) -> Result<String, ParseError> {
+    let scaled = (amount.abs() * 10f64.powi(decimals as i32)).round();
+    if !scaled.is_finite() || scaled >= 10f64.powi(width as i32) {
+        return Err(ParseError::InvalidFieldValue {
+            field: "amount".into(),
+            value: amount.to_string(),
+        });
+    }
+    Ok(numeric(scaled as u64, width))
+}
+
+/// Read digits with `decimals` implied decimal places (e.g. `00000012550` → `125.50`).
+///
+/// # Errors
+/// Returns `ParseError::InvalidFieldValue` if the field is not all digits.
+#[allow(dead_code)]
+pub(crate) fn decode_amount(text: &str, decimals: u32) -> Result<f64, ParseError> {
+    let digits = text.trim();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError::InvalidFieldValue {
+            field: "amount".into(),
+            value: text.to_string(),
+        });
+    }
+    let value: f64 = digits.parse().map_err(|_| ParseError::InvalidFieldValue {
+        field: "amount".into(),
+        value: text.to_string(),
+    })?;
+    Ok(value / 10f64.powi(decimals as i32))
+}
+
+/// Read a date field with a `chrono` format (e.g. `%y%m%d`).
+///
+/// # Errors
+/// Returns `ParseError::InvalidFieldValue` if the date does not match the format.
+#[allow(dead_code)]
+pub(crate) fn decode_date(text: &str, format: &str) -> Result<NaiveDate, ParseError> {
+    NaiveDate::parse_from_str(text.trim(), format).map_err(|_| ParseError::InvalidFieldValue {
+        field: "date".into(),
+        value: text.to_string(),
+    })
+}
+
+/// Upper-case `text` and replace characters outside `A-Z`, `0-9` and `allowed` with spaces
+pub(crate) fn restrict_charset(text: &str, allowed: &str) -> String {
+    text.to_uppercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || allowed.contains(c) {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: RecordLayout = RecordLayout {
+        name: "test",
+        fields: &[
+            FieldSpec::alpha("code", 2),
+            FieldSpec::numeric("amount", 6),
+            FieldSpec::alpha("text", 5),
+        ],
+    };
+
+    #[test]
+    fn test_render_and_parse_record() {
+        let record = LAYOUT.render(&["11", "1250", "Invoice"]).unwrap();
+        assert_eq!(record, "11001250Invoi");
+        assert_eq!(LAYOUT.length(), 13);
+        assert_eq!(
+            LAYOUT.parse(&record).unwrap(),
+            vec!["11", "001250", "Invoi"]
+        );
+
+        // Trailing blanks stripped by an editor read as padding
+        assert_eq!(
+            LAYOUT.parse("11001250ab").unwrap(),
+            vec!["11", "001250", "ab"]
+        );
+        assert!(LAYOUT.parse("11001250abcdefg").is_err());
+    }
+
+    #[test]
+    fn test_render_rejects_invalid_numeric() {
+        assert!(matches!(
+            LAYOUT.render(&["11", "12.50", ""]),
+            Err(ParseError::InvalidFieldValue { field, .. }) if field == "amount"
+        ));
+        assert!(LAYOUT.render(&["11", "1234567", ""]).is_err());
+        assert!(LAYOUT.render(&["11"]).is_err());
+    }
+
+    #[test]
+    fn test_records_by_line_and_by_length() {
+        let lines: Vec<_> = records("abc\r\n\r\ndef\nghi", 3).collect();
+        assert_eq!(lines, vec![(1, "abc"), (2, "def"), (3, "ghi")]);
+
+        let chunks: Vec<_> = records("abcdefgh", 3).collect();
+        assert_eq!(chunks, vec![(1, "abc"), (2, "def"), (3, "gh")]);
+    }
+
+    #[test]
+    fn test_amount_and_date_encodings() {
+        assert_eq!(encode_amount(-125.5, 11, 2).unwrap(), "00000012550");
+        assert_eq!(encode_amount(1.2345, 8, 3).unwrap(), "00001235");
+        assert!(encode_amount(1e12, 11, 2).is_err());
+        assert_eq!(decode_amount("00000012550", 2).unwrap(), 125.5);
+        assert!(decode_amount("12A", 2).is_err());
+        assert_eq!(
+            decode_date("250307", "%y%m%d").unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 7).unwrap()
+        );
+        assert_eq!(restrict_charset("Café #1/a", "/"), "CAF   1/A");
+    }
+}
@@ -0,0 +1,51 @@
+//! Statement shared by the tests of the export formats
+
+use crate::text;
+use crate::{BalanceType, Camt053Statement, StatementInfo, Transaction, TransactionType};
+
+/// Opening balance of the fixture statement
+const OPENING_BALANCE: f64 = 100.0;
+
+/// Booking of `amount` on 2025-03-07 with reference `INV-7` from or to ACME GmbH
+pub(crate) fn transaction(
+    amount: f64,
+    transaction_type: TransactionType,
+    description: &str,
+) -> Transaction {
+    Transaction {
+        reference: Some("INV-7".into()),
+        counterparty_name: Some("ACME GmbH".into()),
+        ..Transaction::new(
+            text::parse_date("2025-03-07").unwrap(),
+            amount,
+            transaction_type,
+            description,
+        )
+    }
+}
+
+/// March 2025 statement of the EUR account DE89370400440532013000, opening with
+/// 100.00 in credit and closing with the balance after `transactions`
+pub(crate) fn statement(transactions: Vec<Transaction>) -> Camt053Statement {
+    let closing_balance =
+        transactions
+            .iter()
+            .fold(OPENING_BALANCE, |balance, tx| match tx.transaction_type {
+                TransactionType::Credit => balance + tx.amount,
+                TransactionType::Debit => balance - tx.amount,
+            });
+    Camt053Statement {
+        account_number: "DE89370400440532013000".into(),
+        currency: "EUR".into(),
+        opening_balance: OPENING_BALANCE,
+        opening_date: text::parse_date("2025-03-01").unwrap(),
+        opening_indicator: BalanceType::Credit,
+        closing_balance,
+        closing_date: text::parse_date("2025-03-31").unwrap(),
+        closing_indicator: BalanceType::Credit,
+        transactions,
+        supplementary_data: Vec::new(),
+        info: StatementInfo::default(),
+        balances: Vec::new(),
+    }
+}
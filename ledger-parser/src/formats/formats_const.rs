@@ -13,7 +13,21 @@ pub const DECIMAL_SEPARATOR_COMMA: &str = ",";
 pub const DECIMAL_SEPARATOR_DOT: &str = ".";
 
 /// Negative sign for amounts
+#[cfg(feature = "sberbank")]
 pub const NEGATIVE_SIGN: &str = "-";
 
 /// Empty string (for positive amounts)
+#[cfg(feature = "sberbank")]
 pub const POSITIVE_SIGN: &str = "";
+
+/// Leading characters that make spreadsheet applications evaluate a cell as a formula
+pub const FORMULA_TRIGGERS: [char; 4] = ['=', '+', '-', '@'];
+
+/// Prefix written before formula-like cell text so spreadsheets show it as text
+pub const FORMULA_ESCAPE: char = '\'';
+
+/// Length of the shortest identifier the ISO 20022 IBAN pattern accepts
+pub const IBAN_MIN_LENGTH: usize = 5;
+
+/// Length of the longest IBAN allowed by ISO 13616
+pub const IBAN_MAX_LENGTH: usize = 34;
@@ -0,0 +1,56 @@
+//! Type conversions between Json and other formats
+//!
+//! Implements the `From` trait in both directions between the canonical JSON
+//! format and the other format structures (CAMT.053, CAMT.054, CSV, MT940, QIF).
+
+use crate::{
+    Camt053Statement, Camt054Statement, CsvStatement, JsonStatement, Mt940Statement, QifStatement,
+    StatementInfo,
+};
+
+/// Implements `From` in both directions between `JsonStatement` and a format
+/// struct as a direct field-by-field conversion, since all structures share the
+/// same data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_json_conversions {
+    ($other:ident $(, $field:ident: $value:expr)*) => {
+        impl From<JsonStatement> for $other {
+            fn from(camt: JsonStatement) -> Self {
+                $other {
+                    account_number: camt.account_number,
+                    currency: camt.currency,
+                    opening_balance: camt.opening_balance,
+                    opening_date: camt.opening_date,
+                    opening_indicator: camt.opening_indicator,
+                    closing_balance: camt.closing_balance,
+                    closing_date: camt.closing_date,
+                    closing_indicator: camt.closing_indicator,
+                    transactions: camt.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+
+        impl From<$other> for JsonStatement {
+            fn from(statement: $other) -> Self {
+                JsonStatement {
+                    account_number: statement.account_number,
+                    currency: statement.currency,
+                    opening_balance: statement.opening_balance,
+                    opening_date: statement.opening_date,
+                    opening_indicator: statement.opening_indicator,
+                    closing_balance: statement.closing_balance,
+                    closing_date: statement.closing_date,
+                    closing_indicator: statement.closing_indicator,
+                    transactions: statement.transactions,
+                }
+            }
+        }
+    };
+}
+
+impl_json_conversions!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_json_conversions!(Camt054Statement);
+impl_json_conversions!(CsvStatement, info: StatementInfo::default());
+impl_json_conversions!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_json_conversions!(QifStatement);
@@ -0,0 +1,910 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::formats::utils;
+use crate::{
+    BalanceType, Charge, EntryStatus, ErrorLocation, ParseError, PostalAddress, RemittanceInfo,
+    Transaction, TransactionDetail, TransactionType,
+};
+
+/// Schema version written to, and the newest accepted from, JSON documents
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+/// Decimal places of amounts in canonical JSON
+const CANONICAL_AMOUNT_DECIMALS: usize = 2;
+
+/// Statement in the canonical JSON format, a stable serialisation of the unified
+/// model for downstream pipelines.
+///
+/// # Schema (version 1)
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "account_number": "DE89370400440532013000",
+///   "currency": "EUR",
+///   "opening_balance": { "amount": 100.0, "indicator": "credit", "date": "2025-03-01T00:00:00Z" },
+///   "closing_balance": { "amount": 150.0, "indicator": "credit", "date": "2025-03-31T00:00:00Z" },
+///   "transactions": [
+///     {
+///       "booking_date": "2025-03-07T00:00:00Z",
+///       "value_date": "2025-03-07",
+///       "amount": 50.0,
+///       "direction": "credit",
+///       "description": "Invoice 42",
+///       "reference": "INV-42",
+///       "counterparty_name": "ACME GmbH",
+///       "counterparty_account": "DE02120300000000202051"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Key names are part of the schema and independent of the Rust field names.
+/// Amounts are non-negative with the sign carried by `indicator`/`direction`
+/// (`"credit"` or `"debit"`); dates are RFC 3339 with offset. Optional
+/// transaction fields are `null` when unknown. Foreign-currency transactions add
+/// `currency` (when it differs from the statement's), `original_amount`,
+/// `original_currency` and `exchange_rate`, and structured remittance adds
+/// `remittance` (`{"type": "structured", "creditor_ref": …, "ref_type": …,
+/// "additional": […]}` or `{"type": "unstructured", "text": …}`). The
+/// counterparty's bank adds `counterparty_bic` and its address
+/// `counterparty_address` (`street`, `building_number`, `postal_code`, `town`,
+/// `country`, `lines`, each only when known), and SEPA payments `end_to_end_id`
+/// and `mandate_id`. Entries that are not booked add `status` (`"pending"` or
+/// `"info"`), reversals `"reversal": true` and CAMT `<SplmtryData>` blocks
+/// `supplementary_data` (an array of raw XML strings). Batch entries add
+/// `details`, one object per payment with `amount`, `reference`,
+/// `end_to_end_id`, `mandate_id`, `counterparty_name`, `counterparty_account`
+/// and `description`, each only when known. Bank fees add `charges`, one
+/// object per charge with `amount`, `direction` and, when known, `currency`,
+/// `code` and `bearer`. These keys are left out otherwise. A change that breaks existing
+/// readers increments `schema_version`; documents with a newer version than
+/// [`JSON_SCHEMA_VERSION`] are rejected. Unknown keys are ignored, so optional
+/// keys can be added without a version change.
+///
+/// For hashing or signing, [`JsonStatement::write_canonical`] writes the same
+/// schema in a canonical form; readers accept both forms.
+///
+/// Fields are identical to Mt940/Camt053/CsvStatement for seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonStatement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+/// Wire representation of a document; the schema's key names live here
+#[derive(Serialize, Deserialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    account_number: Cow<'a, str>,
+    currency: Cow<'a, str>,
+    opening_balance: JsonBalance,
+    closing_balance: JsonBalance,
+    transactions: Vec<JsonTransaction<'a>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonBalance {
+    #[serde(deserialize_with = "amount_from_number_or_text")]
+    amount: f64,
+    indicator: JsonDirection,
+    date: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonTransaction<'a> {
+    booking_date: DateTime<FixedOffset>,
+    value_date: Option<NaiveDate>,
+    #[serde(deserialize_with = "amount_from_number_or_text")]
+    amount: f64,
+    direction: JsonDirection,
+    description: Cow<'a, str>,
+    reference: Option<Cow<'a, str>>,
+    counterparty_name: Option<Cow<'a, str>>,
+    counterparty_account: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    currency: Option<Cow<'a, str>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "optional_amount_from_number_or_text"
+    )]
+    original_amount: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_currency: Option<Cow<'a, str>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "optional_amount_from_number_or_text"
+    )]
+    exchange_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remittance: Option<JsonRemittance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    counterparty_bic: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    counterparty_address: Option<JsonAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_to_end_id: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mandate_id: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<JsonStatus>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    reversal: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    supplementary_data: Vec<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    details: Vec<JsonDetail>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    charges: Vec<JsonCharge>,
+}
+
+/// Fee or tax charged on a transaction; unknown parts are left out
+#[derive(Serialize, Deserialize)]
+struct JsonCharge {
+    #[serde(deserialize_with = "amount_from_number_or_text")]
+    amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    currency: Option<String>,
+    direction: JsonDirection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bearer: Option<String>,
+}
+
+/// One payment of a batch entry; unknown parts are left out
+#[derive(Serialize, Deserialize)]
+struct JsonDetail {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "optional_amount_from_number_or_text"
+    )]
+    amount: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_to_end_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mandate_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    counterparty_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    counterparty_account: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+}
+
+/// Postal address; unknown parts are left out
+#[derive(Serialize, Deserialize)]
+struct JsonAddress {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    street: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    building_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    town: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    country: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    lines: Vec<String>,
+}
+
+/// Remittance information, tagged by `type`
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonRemittance {
+    Unstructured {
+        text: String,
+    },
+    Structured {
+        creditor_ref: Option<String>,
+        ref_type: Option<String>,
+        #[serde(default)]
+        additional: Vec<String>,
+    },
+}
+
+/// Sign of a balance or transaction amount
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonDirection {
+    Credit,
+    Debit,
+}
+
+/// Booking status of a transaction; absent means booked
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonStatus {
+    Booked,
+    Pending,
+    Info,
+}
+
+impl JsonStatement {
+    /// Parse a JSON statement from any Read source (file, stdin, buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::JsonError` if the input is not valid JSON, does not
+    /// follow the schema, or has a `schema_version` newer than [`JSON_SCHEMA_VERSION`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::JsonStatement;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("statement.json").unwrap();
+    /// let statement = JsonStatement::from_read(&mut file).unwrap();
+    /// ```
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse a JSON statement from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`JsonStatement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse JSON from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        let document: JsonDocument<'_> = serde_json::from_str(content).map_err(invalid_json)?;
+        if document.schema_version > JSON_SCHEMA_VERSION {
+            return Err(ParseError::JsonError(format!(
+                "Unsupported schema_version {} (newest supported: {})",
+                document.schema_version, JSON_SCHEMA_VERSION
+            )));
+        }
+
+        let statement = JsonStatement {
+            account_number: document.account_number.into_owned(),
+            currency: document.currency.into_owned(),
+            opening_balance: document.opening_balance.amount,
+            opening_date: document.opening_balance.date,
+            opening_indicator: document.opening_balance.indicator.into(),
+            closing_balance: document.closing_balance.amount,
+            closing_date: document.closing_balance.date,
+            closing_indicator: document.closing_balance.indicator.into(),
+            transactions: document
+                .transactions
+                .into_iter()
+                .map(|tx| Transaction {
+                    value_date: tx.value_date,
+                    reference: tx.reference.map(Cow::into_owned),
+                    counterparty_name: tx.counterparty_name.map(Cow::into_owned),
+                    counterparty_account: tx.counterparty_account.map(Cow::into_owned),
+                    currency: tx.currency.map(Cow::into_owned),
+                    original_amount: tx.original_amount,
+                    original_currency: tx.original_currency.map(Cow::into_owned),
+                    exchange_rate: tx.exchange_rate,
+                    remittance: tx.remittance.map(Into::into),
+                    counterparty_bic: tx.counterparty_bic.map(Cow::into_owned),
+                    counterparty_address: tx.counterparty_address.map(Into::into),
+                    end_to_end_id: tx.end_to_end_id.map(Cow::into_owned),
+                    mandate_id: tx.mandate_id.map(Cow::into_owned),
+                    status: tx.status.map(Into::into).unwrap_or_default(),
+                    is_reversal: tx.reversal,
+                    supplementary_data: tx
+                        .supplementary_data
+                        .into_iter()
+                        .map(Cow::into_owned)
+                        .collect(),
+                    details: tx.details.into_iter().map(Into::into).collect(),
+                    charges: tx.charges.into_iter().map(Into::into).collect(),
+                    ..Transaction::new(
+                        tx.booking_date,
+                        tx.amount,
+                        tx.direction.into(),
+                        tx.description.into_owned(),
+                    )
+                })
+                .collect(),
+        };
+        trace_statement!("parsed statement", "json", statement);
+
+        Ok(statement)
+    }
+
+    /// Write the statement as a pretty-printed JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::JsonError` if serialization fails and
+    /// `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "json", self);
+        let document = JsonDocument {
+            schema_version: JSON_SCHEMA_VERSION,
+            account_number: Cow::Borrowed(&self.account_number),
+            currency: Cow::Borrowed(&self.currency),
+            opening_balance: JsonBalance {
+                amount: self.opening_balance,
+                indicator: (&self.opening_indicator).into(),
+                date: self.opening_date,
+            },
+            closing_balance: JsonBalance {
+                amount: self.closing_balance,
+                indicator: (&self.closing_indicator).into(),
+                date: self.closing_date,
+            },
+            transactions: self
+                .transactions
+                .iter()
+                .map(|tx| JsonTransaction {
+                    booking_date: tx.booking_date,
+                    value_date: tx.value_date,
+                    amount: tx.amount,
+                    direction: (&tx.transaction_type).into(),
+                    description: Cow::Borrowed(&tx.description),
+                    reference: tx.reference.as_deref().map(Cow::Borrowed),
+                    counterparty_name: tx.counterparty_name.as_deref().map(Cow::Borrowed),
+                    counterparty_account: tx.counterparty_account.as_deref().map(Cow::Borrowed),
+                    currency: tx.currency.as_deref().map(Cow::Borrowed),
+                    original_amount: tx.original_amount,
+                    original_currency: tx.original_currency.as_deref().map(Cow::Borrowed),
+                    exchange_rate: tx.exchange_rate,
+                    remittance: tx.remittance.clone().map(Into::into),
+                    counterparty_bic: tx.counterparty_bic.as_deref().map(Cow::Borrowed),
+                    counterparty_address: tx.counterparty_address.clone().map(Into::into),
+                    end_to_end_id: tx.end_to_end_id.as_deref().map(Cow::Borrowed),
+                    mandate_id: tx.mandate_id.as_deref().map(Cow::Borrowed),
+                    status: (!tx.status.is_booked()).then(|| tx.status.into()),
+                    reversal: tx.is_reversal,
+                    supplementary_data: tx
+                        .supplementary_data
+                        .iter()
+                        .map(|xml| Cow::Borrowed(xml.as_str()))
+                        .collect(),
+                    details: tx.details.iter().cloned().map(Into::into).collect(),
+                    charges: tx.charges.iter().cloned().map(Into::into).collect(),
+                })
+                .collect(),
+        };
+
+        let mut writer = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, &document).map_err(|e| {
+            if e.is_io() {
+                ParseError::from(std::io::Error::from(e))
+            } else {
+                ParseError::JsonError(format!("Failed to write JSON: {}", e))
+            }
+        })?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the statement as canonical JSON, byte-for-byte reproducible across
+    /// platforms and crate versions, for hashing or signing.
+    ///
+    /// The canonical form is the regular schema written compactly (no
+    /// whitespace, no trailing newline) with object keys sorted by name,
+    /// amounts as decimal strings with two places (`"150.00"`) and dates as
+    /// RFC 3339 with a numeric offset (`"2025-03-01T00:00:00+00:00"`).
+    /// Transactions keep the statement order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ledger_parser::{JsonStatement, Mt940Statement};
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n:62F:C200101EUR100,00\n";
+    /// let statement: JsonStatement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap().into();
+    ///
+    /// let mut output = Vec::new();
+    /// statement.write_canonical(&mut output).unwrap();
+    /// let json = String::from_utf8(output).unwrap();
+    /// assert!(json.starts_with(r#"{"account_number":"NL81ASNB9999999999","closing_balance":{"amount":"100.00""#));
+    /// ```
+    pub fn write_canonical<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "json", self);
+        let balance = |amount: f64, indicator: &BalanceType, date: &DateTime<FixedOffset>| {
+            json!({
+                "amount": canonical_amount(amount),
+                "date": canonical_date(date),
+                "indicator": JsonDirection::from(indicator),
+            })
+        };
+        let transactions: Vec<Value> = self
+            .transactions
+            .iter()
+            .map(|tx| {
+                let mut transaction = json!({
+                    "amount": canonical_amount(tx.amount),
+                    "booking_date": canonical_date(&tx.booking_date),
+                    "counterparty_account": tx.counterparty_account,
+                    "counterparty_name": tx.counterparty_name,
+                    "description": tx.description,
+                    "direction": JsonDirection::from(&tx.transaction_type),
+                    "reference": tx.reference,
+                    "value_date": tx.value_date,
+                });
+                // Optional keys only appear when set, so existing hashes stay valid
+                let foreign = [
+                    ("currency", tx.currency.clone().map(Value::from)),
+                    (
+                        "original_amount",
+                        tx.original_amount.map(|a| canonical_amount(a).into()),
+                    ),
+                    (
+                        "original_currency",
+                        tx.original_currency.clone().map(Value::from),
+                    ),
+                    (
+                        "exchange_rate",
+                        tx.exchange_rate.map(|rate| rate.to_string().into()),
+                    ),
+                    (
+                        "remittance",
+                        tx.remittance
+                            .clone()
+                            .map(|remittance| json!(JsonRemittance::from(remittance))),
+                    ),
+                    (
+                        "counterparty_bic",
+                        tx.counterparty_bic.clone().map(Value::from),
+                    ),
+                    (
+                        "counterparty_address",
+                        tx.counterparty_address
+                            .clone()
+                            .map(|address| json!(JsonAddress::from(address))),
+                    ),
+                    ("end_to_end_id", tx.end_to_end_id.clone().map(Value::from)),
+                    ("mandate_id", tx.mandate_id.clone().map(Value::from)),
+                    (
+                        "status",
+                        (!tx.status.is_booked()).then(|| json!(JsonStatus::from(tx.status))),
+                    ),
+                    ("reversal", tx.is_reversal.then_some(Value::Bool(true))),
+                    (
+                        "supplementary_data",
+                        (!tx.supplementary_data.is_empty()).then(|| json!(tx.supplementary_data)),
+                    ),
+                    (
+                        "details",
+                        (!tx.details.is_empty()).then(|| {
+                            tx.details
+                                .iter()
+                                .map(|detail| {
+                                    let mut value = json!(JsonDetail::from(detail.clone()));
+                                    if let Some(amount) = detail.amount {
+                                        value["amount"] = canonical_amount(amount).into();
+                                    }
+                                    value
+                                })
+                                .collect()
+                        }),
+                    ),
+                    (
+                        "charges",
+                        (!tx.charges.is_empty()).then(|| {
+                            tx.charges
+                                .iter()
+                                .map(|charge| {
+                                    let mut value = json!(JsonCharge::from(charge.clone()));
+                                    value["amount"] = canonical_amount(charge.amount).into();
+                                    value
+                                })
+                                .collect()
+                        }),
+                    ),
+                ];
+                if let Value::Object(keys) = &mut transaction {
+                    for (key, value) in foreign {
+                        if let Some(value) = value {
+                            keys.insert(key.to_string(), value);
+                        }
+                    }
+                }
+                transaction
+            })
+            .collect();
+        let document = json!({
+            "account_number": self.account_number,
+            "closing_balance": balance(self.closing_balance, &self.closing_indicator, &self.closing_date),
+            "currency": self.currency,
+            "opening_balance": balance(self.opening_balance, &self.opening_indicator, &self.opening_date),
+            "schema_version": JSON_SCHEMA_VERSION,
+            "transactions": transactions,
+        });
+
+        // `serde_json::Map` keeps keys sorted, and compact output has no layout choices
+        writer.write_all(document.to_string().as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Error for a document serde_json rejected, located at its line and column
+fn invalid_json(error: serde_json::Error) -> ParseError {
+    let (line, column) = (error.line(), error.column());
+    let message = error.to_string();
+    // serde_json appends the position to its message; the location carries it
+    let suffix = format!(" at line {} column {}", line, column);
+    let message = message.strip_suffix(&suffix).unwrap_or(&message);
+    let error = ParseError::JsonError(format!("Invalid JSON statement: {}", message));
+    if line == 0 {
+        return error;
+    }
+    error.at(ErrorLocation::Line {
+        line,
+        column: Some(column),
+        field: None,
+    })
+}
+
+/// Amount as a fixed-point decimal string, the canonical number representation
+fn canonical_amount(amount: f64) -> String {
+    format!("{:.*}", CANONICAL_AMOUNT_DECIMALS, amount)
+}
+
+/// RFC 3339 date with a numeric offset (never `Z`)
+fn canonical_date(date: &DateTime<FixedOffset>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+/// Amount given as a JSON number or, in canonical documents, as a decimal string
+fn amount_from_number_or_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount<'a> {
+        Number(f64),
+        Text(Cow<'a, str>),
+    }
+
+    match Amount::deserialize(deserializer)? {
+        Amount::Number(amount) => Ok(amount),
+        Amount::Text(text) => text
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid amount '{}'", text))),
+    }
+}
+
+/// Optional amount given as a JSON number or a decimal string
+fn optional_amount_from_number_or_text<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    amount_from_number_or_text(deserializer).map(Some)
+}
+
+impl From<PostalAddress> for JsonAddress {
+    fn from(address: PostalAddress) -> Self {
+        JsonAddress {
+            street: address.street,
+            building_number: address.building_number,
+            postal_code: address.postal_code,
+            town: address.town,
+            country: address.country,
+            lines: address.lines,
+        }
+    }
+}
+
+impl From<TransactionDetail> for JsonDetail {
+    fn from(detail: TransactionDetail) -> Self {
+        JsonDetail {
+            amount: detail.amount,
+            reference: detail.reference,
+            end_to_end_id: detail.end_to_end_id,
+            mandate_id: detail.mandate_id,
+            counterparty_name: detail.counterparty_name,
+            counterparty_account: detail.counterparty_account,
+            description: detail.description,
+        }
+    }
+}
+
+impl From<JsonDetail> for TransactionDetail {
+    fn from(detail: JsonDetail) -> Self {
+        TransactionDetail {
+            amount: detail.amount,
+            reference: detail.reference,
+            end_to_end_id: detail.end_to_end_id,
+            mandate_id: detail.mandate_id,
+            counterparty_name: detail.counterparty_name,
+            counterparty_account: detail.counterparty_account,
+            description: detail.description,
+        }
+    }
+}
+
+impl From<Charge> for JsonCharge {
+    fn from(charge: Charge) -> Self {
+        JsonCharge {
+            amount: charge.amount,
+            currency: charge.currency,
+            direction: (&charge.charge_type).into(),
+            code: charge.code,
+            bearer: charge.bearer,
+        }
+    }
+}
+
+impl From<JsonCharge> for Charge {
+    fn from(charge: JsonCharge) -> Self {
+        Charge {
+            amount: charge.amount,
+            currency: charge.currency,
+            charge_type: charge.direction.into(),
+            code: charge.code,
+            bearer: charge.bearer,
+        }
+    }
+}
+
+impl From<JsonAddress> for PostalAddress {
+    fn from(address: JsonAddress) -> Self {
+        PostalAddress {
+            street: address.street,
+            building_number: address.building_number,
+            postal_code: address.postal_code,
+            town: address.town,
+            country: address.country,
+            lines: address.lines,
+        }
+    }
+}
+
+impl From<RemittanceInfo> for JsonRemittance {
+    fn from(remittance: RemittanceInfo) -> Self {
+        match remittance {
+            RemittanceInfo::Unstructured(text) => JsonRemittance::Unstructured { text },
+            RemittanceInfo::Structured {
+                creditor_ref,
+                ref_type,
+                additional,
+            } => JsonRemittance::Structured {
+                creditor_ref,
+                ref_type,
+                additional,
+            },
+        }
+    }
+}
+
+impl From<JsonRemittance> for RemittanceInfo {
+    fn from(remittance: JsonRemittance) -> Self {
+        match remittance {
+            JsonRemittance::Unstructured { text } => RemittanceInfo::Unstructured(text),
+            JsonRemittance::Structured {
+                creditor_ref,
+                ref_type,
+                additional,
+            } => RemittanceInfo::Structured {
+                creditor_ref,
+                ref_type,
+                additional,
+            },
+        }
+    }
+}
+
+impl From<EntryStatus> for JsonStatus {
+    fn from(status: EntryStatus) -> Self {
+        match status {
+            EntryStatus::Booked => JsonStatus::Booked,
+            EntryStatus::Pending => JsonStatus::Pending,
+            EntryStatus::Info => JsonStatus::Info,
+        }
+    }
+}
+
+impl From<JsonStatus> for EntryStatus {
+    fn from(status: JsonStatus) -> Self {
+        match status {
+            JsonStatus::Booked => EntryStatus::Booked,
+            JsonStatus::Pending => EntryStatus::Pending,
+            JsonStatus::Info => EntryStatus::Info,
+        }
+    }
+}
+
+impl From<JsonDirection> for BalanceType {
+    fn from(direction: JsonDirection) -> Self {
+        match direction {
+            JsonDirection::Credit => BalanceType::Credit,
+            JsonDirection::Debit => BalanceType::Debit,
+        }
+    }
+}
+
+impl From<JsonDirection> for TransactionType {
+    fn from(direction: JsonDirection) -> Self {
+        match direction {
+            JsonDirection::Credit => TransactionType::Credit,
+            JsonDirection::Debit => TransactionType::Debit,
+        }
+    }
+}
+
+impl From<&BalanceType> for JsonDirection {
+    fn from(indicator: &BalanceType) -> Self {
+        match indicator {
+            BalanceType::Credit => JsonDirection::Credit,
+            BalanceType::Debit => JsonDirection::Debit,
+        }
+    }
+}
+
+impl From<&TransactionType> for JsonDirection {
+    fn from(transaction_type: &TransactionType) -> Self {
+        match transaction_type {
+            TransactionType::Credit => JsonDirection::Credit,
+            TransactionType::Debit => JsonDirection::Debit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn statement() -> JsonStatement {
+        JsonStatement {
+            account_number: "DE89370400440532013000".into(),
+            currency: "EUR".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-03-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 50.0,
+            closing_date: text::parse_date("2025-03-31").unwrap(),
+            closing_indicator: BalanceType::Debit,
+            transactions: vec![Transaction {
+                value_date: NaiveDate::from_ymd_opt(2025, 3, 7),
+                reference: Some("INV-42".into()),
+                ..Transaction::new(
+                    text::parse_date("2025-03-07").unwrap(),
+                    150.0,
+                    TransactionType::Debit,
+                    "Rent \"March\"",
+                )
+            }],
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip_and_schema_keys() {
+        let mut output = Vec::new();
+        statement().write_to(&mut output).unwrap();
+        let text = String::from_utf8(output.clone()).unwrap();
+
+        assert!(text.contains("\"schema_version\": 1"));
+        assert!(text.contains("\"indicator\": \"debit\""));
+        assert!(text.contains("\"direction\": \"debit\""));
+        assert!(text.contains("\"date\": \"2025-03-01T00:00:00Z\""));
+        assert!(text.contains("\"counterparty_name\": null"));
+        assert_eq!(
+            JsonStatement::from_read(&mut output.as_slice()).unwrap(),
+            statement()
+        );
+    }
+
+    #[test]
+    fn test_canonical_json_is_sorted_and_fixed_point() {
+        let mut output = Vec::new();
+        statement().write_canonical(&mut output).unwrap();
+        let text = String::from_utf8(output.clone()).unwrap();
+
+        assert_eq!(
+            text,
+            r#"{"account_number":"DE89370400440532013000","closing_balance":{"amount":"50.00","date":"2025-03-31T00:00:00+00:00","indicator":"debit"},"currency":"EUR","opening_balance":{"amount":"100.00","date":"2025-03-01T00:00:00+00:00","indicator":"credit"},"schema_version":1,"transactions":[{"amount":"150.00","booking_date":"2025-03-07T00:00:00+00:00","counterparty_account":null,"counterparty_name":null,"description":"Rent \"March\"","direction":"debit","reference":"INV-42","value_date":"2025-03-07"}]}"#
+        );
+        assert_eq!(
+            JsonStatement::from_read(&mut output.as_slice()).unwrap(),
+            statement()
+        );
+    }
+
+    #[test]
+    fn test_optional_keys_round_trip() {
+        let mut foreign = statement();
+        foreign.transactions[0].original_amount = Some(162.3);
+        foreign.transactions[0].original_currency = Some("USD".into());
+        foreign.transactions[0].exchange_rate = Some(0.9242);
+        foreign.transactions[0].remittance = Some(RemittanceInfo::Structured {
+            creditor_ref: Some("RF18539007547034".into()),
+            ref_type: Some("SCOR".into()),
+            additional: vec!["Invoice 2025-17".into()],
+        });
+        foreign.transactions[0].counterparty_bic = Some("NWBKGB2L".into());
+        foreign.transactions[0].counterparty_address = Some(PostalAddress {
+            town: Some("London".into()),
+            country: Some("GB".into()),
+            ..PostalAddress::default()
+        });
+        foreign.transactions[0].end_to_end_id = Some("E2E-2025-0042".into());
+        foreign.transactions[0].mandate_id = Some("MANDATE-7".into());
+        foreign.transactions[0].status = EntryStatus::Pending;
+        foreign.transactions[0].is_reversal = true;
+        foreign.transactions[0].supplementary_data = vec!["<Envlp><Id>7</Id></Envlp>".into()];
+
+        let mut pretty = Vec::new();
+        foreign.write_to(&mut pretty).unwrap();
+        let mut canonical = Vec::new();
+        foreign.write_canonical(&mut canonical).unwrap();
+        let text = String::from_utf8(canonical.clone()).unwrap();
+        assert!(text.contains(
+            r#""counterparty_account":null,"counterparty_address":{"country":"GB","town":"London"},"counterparty_bic":"NWBKGB2L","#
+        ));
+        assert!(text.contains(
+            r#""end_to_end_id":"E2E-2025-0042","exchange_rate":"0.9242","mandate_id":"MANDATE-7","original_amount":"162.30","original_currency":"USD","reference":"INV-42","remittance":{"additional":["Invoice 2025-17"],"creditor_ref":"RF18539007547034","ref_type":"SCOR","type":"structured"},"reversal":true,"status":"pending","supplementary_data":["<Envlp><Id>7</Id></Envlp>"]"#
+        ));
+
+        for output in [pretty, canonical] {
+            assert_eq!(
+                JsonStatement::from_read(&mut output.as_slice()).unwrap(),
+                foreign
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_rejects_newer_schema_and_missing_keys() {
+        let mut output = Vec::new();
+        statement().write_to(&mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+
+        let newer = text.replace("\"schema_version\": 1", "\"schema_version\": 2");
+        assert!(matches!(
+            JsonStatement::from_read(&mut newer.as_bytes()),
+            Err(ParseError::JsonError(msg)) if msg.starts_with("Unsupported schema_version")
+        ));
+
+        let renamed = text.replace("\"currency\"", "\"ccy\"");
+        let error = JsonStatement::from_read(&mut renamed.as_bytes()).unwrap_err();
+        assert!(matches!(
+            error.location(),
+            Some(ErrorLocation::Line {
+                column: Some(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            error.without_location(),
+            ParseError::JsonError(msg) if msg == "Invalid JSON statement: missing field `currency`"
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+
+use crate::formats::paging::{
+    round_cents, signed_amount, signed_transaction_amount, unbooked_movement,
+};
+use crate::formats::presets::PRESET_LEDGER;
+use crate::{ParseError, StatementRef, Transaction};
+
+/// Posting indentation; ledger requires postings to be indented
+const POSTING_INDENT: &str = "    ";
+/// Minimum gap between account and amount (ledger needs at least two spaces)
+const AMOUNT_GAP: usize = 2;
+/// Column the amounts are right-aligned to, for readable journals
+const AMOUNT_COLUMN: usize = 52;
+/// Payee of the opening balance entry
+const OPENING_BALANCE_PAYEE: &str = "Opening balance";
+/// Status mark of a cleared entry
+const CLEARED_MARK: char = '*';
+/// Status mark of a pending entry
+const PENDING_MARK: char = '!';
+
+/// Writer profile for plain-text double-entry journals read by ledger-cli and hledger.
+///
+/// Every transaction becomes a cleared journal entry with two postings: the
+/// signed amount on `bank_account` and the balancing amount (elided) on
+/// `income_account` for money received or `expense_account` for money paid
+/// out. The reference becomes the entry code and the counterparty a
+/// `counterparty:` tag. When `opening_balance_account` is set the journal
+/// starts with an opening balance entry and the last bank posting asserts the
+/// statement's closing balance, so `hledger check` catches missing entries.
+/// Pending and informational entries are marked pending (`!`) instead of
+/// cleared, and the assertion adds them to the closing balance, which holds
+/// booked entries only.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{LedgerProfile, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:DE89370400440532013000\n:28C:1/1\n:60F:C250101EUR100,00\n\
+///             :61:250107C1100,00NTRFINV7\n:86:Invoice 7\n:62F:C250107EUR1200,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// LedgerProfile::default()
+///     .write_to(statement.as_view(), &mut output)
+///     .unwrap();
+/// let journal = String::from_utf8(output).unwrap();
+/// assert!(journal.contains("2025-01-07 * (INV7) Invoice 7\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerProfile {
+    /// Account of the bank statement, e.g. `Assets:Bank:Checking`
+    pub bank_account: String,
+    /// Counter account of money received
+    pub income_account: String,
+    /// Counter account of money paid out
+    pub expense_account: String,
+    /// Counter account of the opening balance entry; `None` writes neither the
+    /// opening entry nor the closing balance assertion
+    pub opening_balance_account: Option<String>,
+}
+
+impl Default for LedgerProfile {
+    fn default() -> Self {
+        LedgerProfile {
+            bank_account: "Assets:Bank".into(),
+            income_account: "Income:Unknown".into(),
+            expense_account: "Expenses:Unknown".into(),
+            opening_balance_account: Some("Equity:Opening Balances".into()),
+        }
+    }
+}
+
+impl LedgerProfile {
+    /// Profile name used by [`ExportPreset`](crate::ExportPreset)
+    pub fn name(&self) -> &'static str {
+        PRESET_LEDGER
+    }
+
+    /// Write the statement as a ledger/hledger journal.
+    ///
+    /// # Errors
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        let mut writer = BufWriter::new(writer);
+        let currency = statement.currency;
+        let closing = signed_amount(statement.closing_balance, statement.closing_indicator)
+            + unbooked_movement(statement.transactions);
+        let assert_closing = self.opening_balance_account.is_some();
+
+        if let Some(equity_account) = &self.opening_balance_account {
+            let opening = signed_amount(statement.opening_balance, statement.opening_indicator);
+            let assertion =
+                (assert_closing && statement.transactions.is_empty()).then_some(closing);
+            writeln!(
+                writer,
+                "{} {} {}",
+                statement.opening_date.format("%Y-%m-%d"),
+                CLEARED_MARK,
+                OPENING_BALANCE_PAYEE
+            )?;
+            writeln!(
+                writer,
+                "{}",
+                posting(&self.bank_account, opening, currency, assertion)
+            )?;
+            writeln!(writer, "{}{}", POSTING_INDENT, equity_account)?;
+        }
+
+        let last = statement.transactions.len().saturating_sub(1);
+        for (index, transaction) in statement.transactions.iter().enumerate() {
+            if index > 0 || self.opening_balance_account.is_some() {
+                writeln!(writer)?;
+            }
+            let assertion = (assert_closing && index == last).then_some(closing);
+            self.write_entry(&mut writer, transaction, currency, assertion)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// One journal entry for `transaction`
+    fn write_entry<W: Write>(
+        &self,
+        writer: &mut W,
+        transaction: &Transaction,
+        currency: &str,
+        assertion: Option<f64>,
+    ) -> Result<(), ParseError> {
+        let code = transaction
+            .reference
+            .as_deref()
+            .map(|reference| format!("({}) ", single_line(reference)))
+            .unwrap_or_default();
+        let mark = if transaction.status.is_booked() {
+            CLEARED_MARK
+        } else {
+            PENDING_MARK
+        };
+        writeln!(
+            writer,
+            "{} {} {}{}",
+            transaction.booking_date.format("%Y-%m-%d"),
+            mark,
+            code,
+            single_line(&transaction.description)
+        )?;
+        if let Some(name) = &transaction.counterparty_name {
+            writeln!(
+                writer,
+                "{}; counterparty: {}",
+                POSTING_INDENT,
+                single_line(name)
+            )?;
+        }
+
+        let amount = signed_transaction_amount(transaction);
+        let contra_account = if amount >= 0.0 {
+            &self.income_account
+        } else {
+            &self.expense_account
+        };
+        writeln!(
+            writer,
+            "{}",
+            posting(&self.bank_account, amount, currency, assertion)
+        )?;
+        writeln!(writer, "{}{}", POSTING_INDENT, contra_account)?;
+        Ok(())
+    }
+}
+
+/// Posting line with the amount right-aligned and an optional balance assertion
+fn posting(account: &str, amount: f64, currency: &str, assertion: Option<f64>) -> String {
+    let amount = format!("{:.2} {}", round_cents(amount), currency);
+    let width = AMOUNT_COLUMN
+        .saturating_sub(POSTING_INDENT.len() + account.chars().count())
+        .max(AMOUNT_GAP + amount.len());
+    let mut line = format!("{}{}{:>width$}", POSTING_INDENT, account, amount);
+    if let Some(balance) = assertion {
+        line.push_str(&format!(" = {:.2} {}", round_cents(balance), currency));
+    }
+    line
+}
+
+/// Journal headers and tags end at the line break; fold multi-line text into one line
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::fixtures;
+    use crate::{Camt053Statement, EntryStatus, TransactionType};
+
+    fn statement() -> Camt053Statement {
+        fixtures::statement(vec![
+            fixtures::transaction(1100.0, TransactionType::Credit, "Invoice 7\nMarch"),
+            Transaction {
+                reference: None,
+                counterparty_name: None,
+                ..fixtures::transaction(25.5, TransactionType::Debit, "Fees")
+            },
+        ])
+    }
+
+    #[test]
+    fn test_write_journal_with_balances() {
+        let mut output = Vec::new();
+        LedgerProfile::default()
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        let journal = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = journal.lines().collect();
+
+        assert_eq!(lines[0], "2025-03-01 * Opening balance");
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["Assets:Bank", "100.00", "EUR"]
+        );
+        assert_eq!(lines[2], "    Equity:Opening Balances");
+        assert_eq!(lines[4], "2025-03-07 * (INV-7) Invoice 7 March");
+        assert_eq!(lines[5], "    ; counterparty: ACME GmbH");
+        assert_eq!(lines[6].len(), AMOUNT_COLUMN);
+        assert!(lines[6].ends_with("  1100.00 EUR"));
+        assert_eq!(lines[7], "    Income:Unknown");
+        assert!(lines[10].ends_with("  -25.50 EUR = 1174.50 EUR"));
+        assert_eq!(lines[11], "    Expenses:Unknown");
+    }
+
+    #[test]
+    fn test_write_journal_without_opening_entry() {
+        let profile = LedgerProfile {
+            bank_account: "Assets:Checking".into(),
+            opening_balance_account: None,
+            ..LedgerProfile::default()
+        };
+        let mut output = Vec::new();
+        profile
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        let journal = String::from_utf8(output).unwrap();
+
+        assert!(journal.starts_with("2025-03-07 * (INV-7)"));
+        assert!(!journal.contains(" = "));
+        assert!(journal.contains("    Assets:Checking"));
+    }
+
+    #[test]
+    fn test_pending_entry_is_marked_and_asserted() {
+        let mut statement = statement();
+        statement.transactions[1].status = EntryStatus::Pending;
+        statement.closing_balance = 1200.0;
+        let mut output = Vec::new();
+        LedgerProfile::default()
+            .write_to(statement.as_view(), &mut output)
+            .unwrap();
+        let journal = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = journal.lines().collect();
+
+        assert_eq!(lines[4], "2025-03-07 * (INV-7) Invoice 7 March");
+        assert_eq!(lines[9], "2025-03-07 ! Fees");
+        assert!(lines[10].ends_with("  -25.50 EUR = 1174.50 EUR"));
+    }
+}
@@ -1,9 +1,9 @@
 //! Type conversions from Mt940 to other formats
 //!
 //! Implements the `From` trait to enable idiomatic conversions between MT940
-//! and other format structures (CAMT.053, CSV).
+//! and other format structures (CAMT.053, CSV, QIF).
 
-use crate::{Camt053Statement, CsvStatement, Mt940Statement};
+use crate::{Camt053Statement, CsvStatement, Mt940Statement, QifStatement};
 
 /// Convert MT940 to CAMT.053 format
 ///
@@ -28,6 +28,9 @@ impl From<Mt940Statement> for Camt053Statement {
             closing_date: mt940.closing_date,
             closing_indicator: mt940.closing_indicator,
             transactions: mt940.transactions,
+            supplementary_data: Vec::new(),
+            balances: mt940.balances,
+            info: mt940.info,
         }
     }
 }
@@ -55,6 +58,27 @@ impl From<Mt940Statement> for CsvStatement {
             closing_date: mt940.closing_date,
             closing_indicator: mt940.closing_indicator,
             transactions: mt940.transactions,
+            info: mt940.info,
+        }
+    }
+}
+
+/// Convert MT940 to QIF format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<Mt940Statement> for QifStatement {
+    fn from(mt940: Mt940Statement) -> Self {
+        QifStatement {
+            account_number: mt940.account_number,
+            currency: mt940.currency,
+            opening_balance: mt940.opening_balance,
+            opening_date: mt940.opening_date,
+            opening_indicator: mt940.opening_indicator,
+            closing_balance: mt940.closing_balance,
+            closing_date: mt940.closing_date,
+            closing_indicator: mt940.closing_indicator,
+            transactions: mt940.transactions,
         }
     }
 }
@@ -0,0 +1,1174 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use crate::formats::utils;
+use crate::text;
+use crate::{Charge, PostalAddress, Transaction, TransactionType};
+
+/// Maximum length of a `?20`-`?29`/`?60`-`?63` purpose subfield and of `?32`/`?33` names
+const GERMAN_SUBFIELD_LENGTH: usize = 27;
+/// Purpose subfields available in the German layout (`?20`-`?29`, then `?60`-`?63`)
+const GERMAN_PURPOSE_SUBFIELDS: [u8; 14] = [20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 60, 61, 62, 63];
+/// Maximum length of the `?30` bank code or BIC
+const GERMAN_BANK_LENGTH: usize = 11;
+/// Maximum length of the `?31` account number or IBAN
+const GERMAN_ACCOUNT_LENGTH: usize = 34;
+/// German SEPA keyword of the remittance text in the purpose subfields
+const GERMAN_REMITTANCE: &str = "SVWZ+";
+/// Business transaction code (GVC) for a SEPA credit transfer received
+const GERMAN_GVC_CREDIT: &str = "166";
+/// Business transaction code (GVC) for a SEPA credit transfer sent
+const GERMAN_GVC_DEBIT: &str = "116";
+/// Posting text (`?00`) for received transfers
+const GERMAN_POSTING_TEXT_CREDIT: &str = "GUTSCHRIFT";
+/// Posting text (`?00`) for sent transfers
+const GERMAN_POSTING_TEXT_DEBIT: &str = "UEBERWEISUNG";
+/// Maximum length of one `:86:` line
+pub(crate) const MT940_LINE_LENGTH: usize = 65;
+/// Maximum number of lines of an `:86:` field
+pub(crate) const MT940_NARRATIVE_LINES: usize = 6;
+/// Dutch keyword of the original (instructed) currency and amount, e.g. `/OCMT/USD12,50/`
+const DUTCH_ORIGINAL_AMOUNT: &str = "OCMT";
+/// Dutch keyword of the exchange rate, e.g. `/EXCH/1,0876/`
+const DUTCH_EXCHANGE_RATE: &str = "EXCH";
+/// Dutch keyword of a charge deducted by the bank, e.g. `/CHGS/EUR1,50/`
+const DUTCH_CHARGES: &str = "CHGS";
+/// Dutch keyword of the counterparty: `/CNTP/account/BIC/name/city/`
+const DUTCH_COUNTERPARTY: &str = "CNTP";
+/// Dutch keyword of a counterparty BIC given on its own, e.g. `/BIC/RABONL2U/`
+const DUTCH_BIC: &str = "BIC";
+/// German subfield of the counterparty bank code or BIC
+const GERMAN_BANK_SUBFIELD: &str = "?30";
+/// Dutch keyword of the SEPA end-to-end ID, e.g. `/EREF/E2E-42/`
+const DUTCH_END_TO_END_ID: &str = "EREF";
+/// Dutch keyword of the SEPA direct debit mandate reference, e.g. `/MARF/MANDATE-7/`
+const DUTCH_MANDATE_ID: &str = "MARF";
+/// German SEPA keyword of the end-to-end ID in the purpose subfields
+const GERMAN_END_TO_END_ID: &str = "EREF+";
+/// German SEPA keyword of the mandate reference in the purpose subfields
+const GERMAN_MANDATE_ID: &str = "MREF+";
+/// German SEPA keywords that can follow a value in the purpose subfields
+const GERMAN_SEPA_KEYWORDS: [&str; 9] = [
+    "EREF+", "KREF+", "MREF+", "CRED+", "DEBT+", "SVWZ+", "ABWA+", "ABWE+", "IBAN+",
+];
+/// Placeholder end-to-end ID of payments initiated without one
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+/// Keyword of the counterparty IBAN in the ABN AMRO and SWIFT layouts
+const IBAN_KEYWORD: &str = "IBAN";
+/// Keyword of the counterparty name in the ABN AMRO and SWIFT layouts
+const NAME_KEYWORD: &str = "NAME";
+/// SWIFT keyword of the ordering party, the counterparty of a credit
+const SWIFT_ORDERING_PARTY: &str = "ORDP";
+/// SWIFT keyword of the beneficiary, the counterparty of a debit
+const SWIFT_BENEFICIARY: &str = "BENM";
+/// ABN AMRO keyword of the transaction type, e.g. `/TRTP/SEPA OVERBOEKING/`
+const ABN_TRANSACTION_TYPE: &str = "TRTP";
+/// ABN AMRO transaction type of SEPA credit transfers
+const ABN_CREDIT_TRANSFER: &str = "SEPA OVERBOEKING";
+/// ABN AMRO transaction type of SEPA direct debits
+const ABN_DIRECT_DEBIT: &str = "SEPA INCASSO ALGEMEEN DOORLOPEND";
+/// Keyword of the remittance text in the Dutch, ABN AMRO and SWIFT layouts
+const REMITTANCE_KEYWORD: &str = "REMI";
+/// Prefix of unstructured remittance text in the Dutch layout, `/REMI/USTD//text/`
+const UNSTRUCTURED_REMITTANCE: &str = "USTD//";
+/// Keywords of the Dutch, ABN AMRO and SWIFT layouts; a field starting with one
+/// of them is read as structured
+const STRUCTURED_KEYWORDS: [&str; 17] = [
+    "TRTP", "EREF", "MARF", "CSID", "ORDP", "BENM", "CNTP", "IBAN", "BIC", "NAME", "ADDR", "REMI",
+    "OCMT", "EXCH", "CHGS", "PREF", "RTRN",
+];
+/// Length of the keywords banks add to the known ones, e.g. `/OPRP/` or `/DACT/`
+const BANK_KEYWORD_LENGTH: usize = 4;
+/// Keywords whose values are read into fields of the transaction; the others
+/// make up the description of a field without `/REMI/`
+const READ_KEYWORDS: [&str; 12] = [
+    "EREF", "MARF", "ORDP", "BENM", "CNTP", "IBAN", "BIC", "NAME", "REMI", "OCMT", "EXCH", "CHGS",
+];
+
+/// Layout of the `:86:` information field produced by the MT940 writer.
+///
+/// MT940 leaves `:86:` free-form, but ERPs that import statements expect the
+/// structure their banks use. Parsing already accepts all of these; this
+/// selects what [`Mt940Statement::write_to_with`](crate::Mt940Statement::write_to_with) emits.
+/// Parses from and displays as its lowercase name (`standard`, `german`, `dutch`, `abn`,
+/// `swift`); `ing` parses as `dutch` and `generic` as `swift`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Mt940Dialect {
+    /// Description as stored on the transaction, wrapped at word boundaries
+    /// into the 6 lines of 65 characters `:86:` allows
+    #[default]
+    Standard,
+    /// German structured layout (DFÜ-Abkommen) with `?`-numbered subfields:
+    /// `GVC?00posting text?20`-`?29`/`?60`-`?63` purpose (with `EREF+`, `MREF+` and
+    /// `SVWZ+` SEPA keywords), `?30` BIC, `?31` account, `?32`/`?33` name
+    German,
+    /// Dutch keyword layout as written by Rabobank and ING:
+    /// `/EREF/end-to-end ID/CNTP/account/BIC/name/city/MARF/mandate/REMI/USTD//text/`
+    #[strum(to_string = "dutch", serialize = "ing")]
+    Dutch,
+    /// ABN AMRO SEPA layout:
+    /// `/TRTP/type/IBAN/account/BIC/bic/NAME/name/MARF/mandate/REMI/text/EREF/end-to-end ID`
+    Abn,
+    /// Generic SWIFT keyword layout:
+    /// `/EREF/end-to-end ID/ORDP//NAME/name/IBAN/account/BIC/bic/MARF/mandate/REMI/text/`,
+    /// with `BENM` instead of `ORDP` for debits
+    #[strum(to_string = "swift", serialize = "generic")]
+    Swift,
+}
+
+impl Mt940Dialect {
+    /// Lines of the `:86:` field for `transaction` (the first goes after the tag)
+    pub(crate) fn field86_lines(&self, transaction: &Transaction) -> Vec<String> {
+        match self {
+            Mt940Dialect::Standard => narrative_lines(&transaction.description),
+            Mt940Dialect::German => german_lines(transaction),
+            Mt940Dialect::Dutch => dutch_lines(transaction),
+            Mt940Dialect::Abn => abn_lines(transaction),
+            Mt940Dialect::Swift => swift_lines(transaction),
+        }
+    }
+}
+
+/// German layout: whole subfields packed into the 6 lines of 65 characters, so
+/// no subfield is split by a line break. Purpose text that does not fit is cut
+/// from the end; the counterparty subfields always stay.
+fn german_lines(transaction: &Transaction) -> Vec<String> {
+    let (gvc, posting_text) = match transaction.transaction_type {
+        TransactionType::Credit => (GERMAN_GVC_CREDIT, GERMAN_POSTING_TEXT_CREDIT),
+        TransactionType::Debit => (GERMAN_GVC_DEBIT, GERMAN_POSTING_TEXT_DEBIT),
+    };
+    let header = format!("{}?00{}", gvc, posting_text);
+
+    // SEPA keywords keep the end-to-end reference separate from the remittance text
+    let mut purpose = String::new();
+    if let Some(end_to_end_id) = end_to_end_id(transaction) {
+        purpose.push_str(&format!(
+            "{}{} ",
+            GERMAN_END_TO_END_ID,
+            german_text(end_to_end_id)
+        ));
+    }
+    if let Some(mandate_id) = &transaction.mandate_id {
+        purpose.push_str(&format!(
+            "{}{} ",
+            GERMAN_MANDATE_ID,
+            german_text(mandate_id)
+        ));
+    }
+    purpose.push_str(GERMAN_REMITTANCE);
+    purpose.push_str(&german_text(&transaction.description));
+    let purpose: Vec<String> = GERMAN_PURPOSE_SUBFIELDS
+        .iter()
+        .zip(chunk_chars(&purpose, GERMAN_SUBFIELD_LENGTH))
+        .map(|(subfield, chunk)| format!("?{}{}", subfield, chunk))
+        .collect();
+
+    let mut counterparty = Vec::new();
+    if let Some(bic) = &transaction.counterparty_bic {
+        let bic = truncate_chars(&german_text(bic), GERMAN_BANK_LENGTH);
+        counterparty.push(format!("{}{}", GERMAN_BANK_SUBFIELD, bic));
+    }
+    if let Some(account) = &transaction.counterparty_account {
+        let account = truncate_chars(&german_text(account), GERMAN_ACCOUNT_LENGTH);
+        counterparty.push(format!("?31{}", account));
+    }
+    if let Some(name) = &transaction.counterparty_name {
+        for (subfield, chunk) in [32, 33]
+            .iter()
+            .zip(chunk_chars(&german_text(name), GERMAN_SUBFIELD_LENGTH))
+        {
+            counterparty.push(format!("?{}{}", subfield, chunk));
+        }
+    }
+
+    let mut kept = purpose.len();
+    loop {
+        let lines = pack_subfields(&header, purpose[..kept].iter().chain(&counterparty));
+        if lines.len() <= MT940_NARRATIVE_LINES || kept == 0 {
+            return lines;
+        }
+        kept -= 1;
+    }
+}
+
+/// Lines of `first` followed by `subfields`, each line holding as many whole
+/// subfields as fit in the MT940 line length
+fn pack_subfields<'a>(first: &str, subfields: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut lines = vec![first.to_string()];
+    for subfield in subfields {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + subfield.chars().count() <= MT940_LINE_LENGTH => {
+                line.push_str(subfield)
+            }
+            _ => lines.push(subfield.clone()),
+        }
+    }
+    lines
+}
+
+/// Dutch layout: `/KEYWORD/value` pairs, wrapped at the MT940 line length
+fn dutch_lines(transaction: &Transaction) -> Vec<String> {
+    fitted_lines(&transaction.description, |description| {
+        dutch_field(transaction, description)
+    })
+}
+
+/// Dutch layout of `transaction` with `description` as remittance text
+fn dutch_field(transaction: &Transaction, description: &str) -> String {
+    let mut field = String::new();
+    if let Some(end_to_end_id) = end_to_end_id(transaction) {
+        field.push_str(&format!(
+            "/{}/{}",
+            DUTCH_END_TO_END_ID,
+            dutch_text(end_to_end_id)
+        ));
+    }
+    let town = transaction
+        .counterparty_address
+        .as_ref()
+        .and_then(|address| address.town.as_deref());
+    if transaction.counterparty_account.is_some()
+        || transaction.counterparty_bic.is_some()
+        || transaction.counterparty_name.is_some()
+        || town.is_some()
+    {
+        // The city is the last value; the next keyword's leading `/` closes it
+        field.push_str(&format!(
+            "/{}/{}/{}/{}/{}",
+            DUTCH_COUNTERPARTY,
+            dutch_text(transaction.counterparty_account.as_deref().unwrap_or("")),
+            dutch_text(transaction.counterparty_bic.as_deref().unwrap_or("")),
+            dutch_text(transaction.counterparty_name.as_deref().unwrap_or("")),
+            dutch_text(town.unwrap_or(""))
+        ));
+    }
+    if let Some(mandate_id) = &transaction.mandate_id {
+        field.push_str(&format!(
+            "/{}/{}/",
+            DUTCH_MANDATE_ID,
+            dutch_text(mandate_id)
+        ));
+    }
+    if let (Some(amount), Some(currency)) =
+        (transaction.original_amount, &transaction.original_currency)
+    {
+        let amount = format!("{:.2}", amount).replace('.', ",");
+        field.push_str(&format!(
+            "/{}/{}{}/",
+            DUTCH_ORIGINAL_AMOUNT,
+            dutch_text(currency),
+            amount
+        ));
+        if let Some(rate) = transaction.exchange_rate {
+            field.push_str(&format!(
+                "/{}/{}/",
+                DUTCH_EXCHANGE_RATE,
+                rate.to_string().replace('.', ",")
+            ));
+        }
+    }
+    for charge in &transaction.charges {
+        let amount = format!("{:.2}", charge.amount).replace('.', ",");
+        field.push_str(&format!(
+            "/{}/{}{}/",
+            DUTCH_CHARGES,
+            dutch_text(charge.currency.as_deref().unwrap_or("")),
+            amount
+        ));
+    }
+    field.push_str(&format!("/REMI/USTD//{}/", dutch_text(description)));
+    field
+}
+
+/// ABN AMRO layout: the transaction type, counterparty, remittance text and,
+/// last, the end-to-end ID (`NOTPROVIDED` when there is none)
+fn abn_lines(transaction: &Transaction) -> Vec<String> {
+    fitted_lines(&transaction.description, |description| {
+        abn_field(transaction, description)
+    })
+}
+
+/// ABN AMRO layout of `transaction` with `description` as remittance text
+fn abn_field(transaction: &Transaction, description: &str) -> String {
+    let transaction_type = match transaction.mandate_id {
+        Some(_) => ABN_DIRECT_DEBIT,
+        None => ABN_CREDIT_TRANSFER,
+    };
+    let mut field = format!("/{}/{}", ABN_TRANSACTION_TYPE, transaction_type);
+    push_counterparty(&mut field, transaction);
+    push_keyword(
+        &mut field,
+        DUTCH_MANDATE_ID,
+        transaction.mandate_id.as_deref(),
+    );
+    push_keyword(&mut field, REMITTANCE_KEYWORD, Some(description));
+    push_keyword(
+        &mut field,
+        DUTCH_END_TO_END_ID,
+        Some(end_to_end_id(transaction).unwrap_or(NOT_PROVIDED)),
+    );
+    field
+}
+
+/// SWIFT layout: `/KEYWORD/value/` pairs with the counterparty under the
+/// ordering party or beneficiary keyword, wrapped at the MT940 line length
+fn swift_lines(transaction: &Transaction) -> Vec<String> {
+    fitted_lines(&transaction.description, |description| {
+        swift_field(transaction, description)
+    })
+}
+
+/// SWIFT layout of `transaction` with `description` as remittance text
+fn swift_field(transaction: &Transaction, description: &str) -> String {
+    let mut field = String::new();
+    push_keyword(&mut field, DUTCH_END_TO_END_ID, end_to_end_id(transaction));
+    if transaction.counterparty_name.is_some() || transaction.counterparty_account.is_some() {
+        let party = match transaction.transaction_type {
+            TransactionType::Credit => SWIFT_ORDERING_PARTY,
+            TransactionType::Debit => SWIFT_BENEFICIARY,
+        };
+        field.push_str(&format!("/{}/", party));
+    }
+    push_counterparty(&mut field, transaction);
+    push_keyword(
+        &mut field,
+        DUTCH_MANDATE_ID,
+        transaction.mandate_id.as_deref(),
+    );
+    push_keyword(&mut field, REMITTANCE_KEYWORD, Some(description));
+    field.push('/');
+    field
+}
+
+/// Lines of the keyword layout `field` builds around a description, wrapped at
+/// the MT940 line length. When the field does not fit in 6 lines, the
+/// description is cut from the end to make room (the layouts write it one
+/// character for one), and whatever still does not fit is left out.
+fn fitted_lines(description: &str, field: impl Fn(&str) -> String) -> Vec<String> {
+    let capacity = MT940_NARRATIVE_LINES * MT940_LINE_LENGTH;
+    let excess = field(description).chars().count().saturating_sub(capacity);
+    let mut kept = description.chars().count().saturating_sub(excess);
+    loop {
+        // Lines ended early may need a few characters more
+        let mut lines = keyword_lines(&field(&truncate_chars(description, kept)));
+        if lines.len() <= MT940_NARRATIVE_LINES || kept == 0 {
+            lines.truncate(MT940_NARRATIVE_LINES);
+            return lines;
+        }
+        kept -= 1;
+    }
+}
+
+/// Keyword layout `text` split at the MT940 line length, a line ending early
+/// where the next one would open a field (see [`opens_field`]); the layouts are
+/// read back by joining the lines without separator
+fn keyword_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > MT940_LINE_LENGTH {
+        let limit = rest
+            .char_indices()
+            .nth(MT940_LINE_LENGTH)
+            .map_or(rest.len(), |(index, _)| index);
+        let end = word_break(rest, limit);
+        lines.push(rest[..end].to_string());
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        lines.push(rest.to_string());
+    }
+    lines
+}
+
+/// Append the `/IBAN/`, `/BIC/` and `/NAME/` keywords of the counterparty the
+/// transaction has
+fn push_counterparty(field: &mut String, transaction: &Transaction) {
+    push_keyword(
+        field,
+        IBAN_KEYWORD,
+        transaction.counterparty_account.as_deref(),
+    );
+    push_keyword(field, DUTCH_BIC, transaction.counterparty_bic.as_deref());
+    push_keyword(
+        field,
+        NAME_KEYWORD,
+        transaction.counterparty_name.as_deref(),
+    );
+}
+
+/// Append `/KEYWORD/value` when there is a value; the next keyword's leading
+/// `/` closes it
+fn push_keyword(field: &mut String, keyword: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        field.push_str(&format!("/{}/{}", keyword, dutch_text(value)));
+    }
+}
+
+/// Original currency, amount and exchange rate from the Dutch `/OCMT/` and
+/// `/EXCH/` keywords of a `:86:` field, whichever layout the rest of it uses
+pub(crate) fn foreign_amount(field86: &str) -> (Option<String>, Option<f64>, Option<f64>) {
+    // Wrapped lines split values at arbitrary points
+    let field: String = field86.lines().collect();
+
+    let original = keyword_value(&field, DUTCH_ORIGINAL_AMOUNT).and_then(|value| {
+        let split = value.char_indices().nth(3)?.0;
+        let (currency, amount) = value.split_at(split);
+        let amount = text::parse_amount(amount).ok()?;
+        currency
+            .chars()
+            .all(|c| c.is_ascii_alphabetic())
+            .then(|| (currency.to_ascii_uppercase(), amount))
+    });
+    let Some((currency, amount)) = original else {
+        return (None, None, None);
+    };
+    let rate =
+        keyword_value(&field, DUTCH_EXCHANGE_RATE).and_then(|value| text::parse_amount(value).ok());
+
+    (Some(currency), Some(amount), rate)
+}
+
+/// Charges of the Dutch `/CHGS/` keywords of a `:86:` field, e.g. `/CHGS/EUR1,50/`;
+/// the currency is optional
+pub(crate) fn charges(field86: &str) -> Vec<Charge> {
+    let field: String = field86.lines().collect();
+    let keyword = format!("/{}/", DUTCH_CHARGES);
+
+    field
+        .match_indices(&keyword)
+        .filter_map(|(start, _)| {
+            let value = &field[start + keyword.len()..];
+            let value = value.split('/').next().unwrap_or(value).trim();
+            let split = value
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(value.len());
+            let (currency, amount) = value.split_at(split);
+            if amount.is_empty() {
+                return None;
+            }
+            let amount = text::parse_amount(amount).ok()?;
+            Some(Charge {
+                amount,
+                currency: (currency.len() == 3).then(|| currency.to_ascii_uppercase()),
+                charge_type: TransactionType::Debit,
+                code: None,
+                bearer: None,
+            })
+        })
+        .collect()
+}
+
+/// Lines of `description` wrapped at word boundaries to the MT940 line length,
+/// however many there are.
+///
+/// The continuation of a wrapped line starts with the space it was broken at,
+/// and a word too long for a line fills it completely, so [`join_narrative`]
+/// can tell continuations from the description's own line breaks. No line
+/// after the first may open a field (see [`opens_field`]): the breaks are
+/// chosen to avoid it, and where no choice does, a line ends early instead
+/// (read back with a line break in it); a line of the description starting
+/// with such a word is joined to the line before it with a space.
+pub(crate) fn wrap_narrative(description: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in narrative_paragraphs(description) {
+        let plans = line_plans(&paragraph);
+        let mut start = 0;
+        while let Some((end, _)) = plans[start] {
+            lines.push(paragraph[start..end].to_string());
+            start = end;
+        }
+        lines.push(paragraph[start..].to_string());
+    }
+    lines
+}
+
+/// For every offset of `paragraph` a line may start at, the end of that line
+/// and whether the rest wraps without ending a line early; `None` where the
+/// rest fits in one line. Lines break at the last space that fits, or else fill
+/// the line, or else at an earlier space, whichever keeps the later lines from
+/// opening a field.
+fn line_plans(paragraph: &str) -> Vec<Option<(usize, bool)>> {
+    let mut plans: Vec<Option<(usize, bool)>> = vec![None; paragraph.len() + 1];
+    for (offset, _) in paragraph.char_indices().rev() {
+        let rest = &paragraph[offset..];
+        let Some((limit, _)) = rest.char_indices().nth(MT940_LINE_LENGTH) else {
+            continue;
+        };
+        // The leading space of a continuation is no place to break
+        let mut breaks: Vec<usize> = rest[..limit]
+            .match_indices(' ')
+            .map(|(index, _)| index)
+            .filter(|index| *index > 0)
+            .collect();
+        if rest[limit..].starts_with(' ') {
+            breaks.push(limit);
+        }
+        let mut candidates: Vec<usize> = breaks.last().copied().into_iter().collect();
+        if !rest[limit..].starts_with(' ') {
+            candidates.push(limit);
+        }
+        candidates.extend(breaks.iter().rev().skip(1));
+        candidates.retain(|end| !opens_field(&rest[*end..]));
+
+        let clean = |end: &usize| plans[offset + end].is_none_or(|(_, clean)| clean);
+        let plan = match candidates.iter().find(|end| clean(end)) {
+            Some(end) => (offset + end, true),
+            None => {
+                let end = candidates
+                    .first()
+                    .copied()
+                    .unwrap_or_else(|| word_break(rest, limit));
+                (offset + end, false)
+            }
+        };
+        plans[offset] = Some(plan);
+    }
+    plans
+}
+
+/// Lines of `description`, without leading spaces (which would read as a
+/// continuation), and with a line that would open a field joined to the one
+/// before it; blank lines before such a line are dropped
+fn narrative_paragraphs(description: &str) -> Vec<String> {
+    let mut paragraphs: Vec<String> = Vec::new();
+    for line in description.lines() {
+        let line = line.trim_start();
+        if opens_field(line) {
+            while paragraphs.last().is_some_and(String::is_empty) {
+                paragraphs.pop();
+            }
+            if let Some(last) = paragraphs.last_mut() {
+                last.push(' ');
+                last.push_str(line);
+                continue;
+            }
+        }
+        paragraphs.push(line.to_string());
+    }
+    paragraphs
+}
+
+/// Where to break a word running past `limit`: at `limit`, or before the
+/// characters there that would open a field on the next line
+fn word_break(rest: &str, limit: usize) -> usize {
+    let mut end = limit;
+    while opens_field(&rest[end..]) {
+        match rest[..end].char_indices().next_back() {
+            Some((index, _)) if index > 0 => end = index,
+            _ => return limit,
+        }
+    }
+    end
+}
+
+/// Whether a line starting with `text` would be read as a tag (`:`) or the end
+/// of the message (`-`) instead of continuing the `:86:` field
+fn opens_field(text: &str) -> bool {
+    text.trim_start().starts_with([':', '-'])
+}
+
+/// The first 6 lines of `description` wrapped, the free-text `:86:` field
+pub(crate) fn narrative_lines(description: &str) -> Vec<String> {
+    let mut lines = wrap_narrative(description);
+    lines.truncate(MT940_NARRATIVE_LINES);
+    lines
+}
+
+/// Free text of an `:86:` field with wrapped lines joined back: lines that
+/// start with a space continue the previous one, as do lines after one filled
+/// to the MT940 line length; other line breaks are kept
+pub(crate) fn join_narrative(field86: &str) -> String {
+    let mut text = String::with_capacity(field86.len());
+    let mut previous_full = false;
+    for (index, line) in field86.lines().enumerate() {
+        if index > 0 && !previous_full && !line.starts_with(' ') {
+            text.push('\n');
+        }
+        text.push_str(line);
+        previous_full = line.chars().count() >= MT940_LINE_LENGTH;
+    }
+    text
+}
+
+/// End-to-end ID to write: the SEPA one, or the transaction reference of
+/// transactions that have none
+fn end_to_end_id(transaction: &Transaction) -> Option<&str> {
+    transaction
+        .end_to_end_id
+        .as_deref()
+        .or(transaction.reference.as_deref())
+}
+
+/// SEPA end-to-end ID and mandate reference of a `:86:` field: the Dutch
+/// `/EREF/` and `/MARF/` keywords or the German `EREF+` and `MREF+` keywords
+pub(crate) fn sepa_ids(field86: &str) -> (Option<String>, Option<String>) {
+    let field: String = field86.lines().collect();
+    let purpose = german_purpose(&field);
+
+    let end_to_end_id = keyword_value(&field, DUTCH_END_TO_END_ID)
+        .or_else(|| german_keyword_value(&purpose, GERMAN_END_TO_END_ID))
+        .filter(|id| *id != NOT_PROVIDED)
+        .map(str::to_string);
+    let mandate_id = keyword_value(&field, DUTCH_MANDATE_ID)
+        .or_else(|| german_keyword_value(&purpose, GERMAN_MANDATE_ID))
+        .map(str::to_string);
+    (end_to_end_id, mandate_id)
+}
+
+/// Purpose subfields (`?20`-`?29`, `?60`-`?63`) of a German field, joined
+fn german_purpose(field: &str) -> String {
+    field
+        .split('?')
+        .skip(1)
+        .filter_map(|subfield| {
+            let number: u8 = subfield.get(..2)?.parse().ok()?;
+            GERMAN_PURPOSE_SUBFIELDS
+                .contains(&number)
+                .then(|| &subfield[2..])
+        })
+        .collect()
+}
+
+/// Value following a German SEPA `keyword` up to the next keyword
+fn german_keyword_value<'a>(purpose: &'a str, keyword: &str) -> Option<&'a str> {
+    let value = &purpose[purpose.find(keyword)? + keyword.len()..];
+    let end = GERMAN_SEPA_KEYWORDS
+        .iter()
+        .filter_map(|next| value.find(next))
+        .min()
+        .unwrap_or(value.len());
+    Some(value[..end].trim()).filter(|value| !value.is_empty())
+}
+
+/// Counterparty BIC and city of a `:86:` field: the Dutch `/BIC/` keyword or
+/// `/CNTP/` values, or a BIC in the German `?30` subfield
+pub(crate) fn counterparty_agent(field86: &str) -> (Option<String>, Option<PostalAddress>) {
+    let field: String = field86.lines().collect();
+
+    let counterparty: Vec<&str> = field
+        .find(&format!("/{}/", DUTCH_COUNTERPARTY))
+        .map(|start| {
+            field[start + DUTCH_COUNTERPARTY.len() + 2..]
+                .splitn(5, '/')
+                .take(4)
+                .map(str::trim)
+                .collect()
+        })
+        .unwrap_or_default();
+    let german_bank = field.find(GERMAN_BANK_SUBFIELD).map(|start| {
+        let value = &field[start + GERMAN_BANK_SUBFIELD.len()..];
+        value.split('?').next().unwrap_or(value).trim()
+    });
+
+    let bic = keyword_value(&field, DUTCH_BIC)
+        .or(counterparty.get(1).copied())
+        .or(german_bank)
+        .filter(|bic| utils::is_bic(bic))
+        .map(str::to_string);
+    let address = counterparty
+        .get(3)
+        .filter(|town| !town.is_empty())
+        .map(|town| PostalAddress {
+            town: Some(town.to_string()),
+            ..PostalAddress::default()
+        });
+    (bic, address)
+}
+
+/// Counterparty name and account of a structured `:86:` field: the `/NAME/` and
+/// `/IBAN/` keywords (also within `/ORDP/` and `/BENM/`), the Dutch `/CNTP/` values,
+/// or the German `?32`/`?33` name and `?31` account subfields
+pub(crate) fn counterparty(field86: &str) -> (Option<String>, Option<String>) {
+    let field: String = field86.lines().collect();
+    let keywords = structured_keywords(&field);
+    let value = |keyword: &str| {
+        keywords
+            .iter()
+            .find(|(name, value)| *name == keyword && !value.is_empty())
+            .map(|(_, value)| value.to_string())
+    };
+    let counterparty: Vec<&str> = keywords
+        .iter()
+        .find(|(name, _)| *name == DUTCH_COUNTERPARTY)
+        .map(|(_, value)| value.split('/').map(str::trim).collect())
+        .unwrap_or_default();
+    let part = |index: usize| {
+        counterparty
+            .get(index)
+            .filter(|part| !part.is_empty())
+            .map(|part| part.to_string())
+    };
+
+    let name = value(NAME_KEYWORD).or_else(|| part(2)).or_else(|| {
+        let name: String = [32, 33]
+            .iter()
+            .filter_map(|number| german_subfield(&field, *number))
+            .collect();
+        Some(name.trim().to_string()).filter(|name| !name.is_empty())
+    });
+    let account = value(IBAN_KEYWORD)
+        .or_else(|| part(0))
+        .or_else(|| german_subfield(&field, 31).map(|account| account.trim().to_string()))
+        .filter(|account| !account.is_empty());
+    (name, account)
+}
+
+/// Value of the `?number` subfield of a German field; only fields that have
+/// the `?00` posting text are read as German
+fn german_subfield(field: &str, number: u8) -> Option<&str> {
+    if !field.contains("?00") {
+        return None;
+    }
+    let number = format!("{:02}", number);
+    field
+        .split('?')
+        .skip(1)
+        .find_map(|subfield| subfield.strip_prefix(&number))
+}
+
+/// Remittance text of a structured `:86:` field: the `/REMI/` keyword without
+/// the Dutch `USTD//` prefix, or the German purpose subfields from `SVWZ+` on
+pub(crate) fn remittance_text(field86: &str) -> Option<String> {
+    let field: String = field86.lines().collect();
+    let keyword = structured_keywords(&field)
+        .into_iter()
+        .find(|(name, _)| *name == REMITTANCE_KEYWORD)
+        .map(|(_, value)| {
+            value
+                .strip_prefix(UNSTRUCTURED_REMITTANCE)
+                .unwrap_or(value)
+                .trim_end_matches('/')
+                .trim()
+                .to_string()
+        });
+    let german = || {
+        let purpose = german_purpose(&field);
+        german_keyword_value(&purpose, GERMAN_REMITTANCE).map(str::to_string)
+    };
+    keyword.or_else(german).filter(|text| !text.is_empty())
+}
+
+/// Description of a structured `:86:` field without remittance text: the
+/// keywords no field of the transaction takes, as `/KEYWORD/value`, so none is
+/// lost; `None` for a field that is not structured
+pub(crate) fn unread_keywords(field86: &str) -> Option<String> {
+    let field: String = field86.lines().collect();
+    let keywords = structured_keywords(&field);
+    if keywords.is_empty() {
+        return None;
+    }
+    Some(
+        keywords
+            .into_iter()
+            .filter(|(keyword, _)| !READ_KEYWORDS.contains(keyword))
+            .map(|(keyword, value)| format!("/{}/{}", keyword, value))
+            .collect(),
+    )
+}
+
+/// `(keyword, value)` pairs of a field in one of the keyword layouts, in field
+/// order; a field not starting with a known keyword has none. Values run up to
+/// the next keyword, known or any other of 4 capital letters such as a bank's
+/// own `/OPRP/`, so they keep any other `/` of their own. The 4 values of the
+/// Dutch `/CNTP/` and the `USTD//` prefix of its `/REMI/` hold no keywords.
+fn structured_keywords(field: &str) -> Vec<(&str, &str)> {
+    let field = field.trim();
+    let mut next = keyword_at(field, 0).filter(|keyword| STRUCTURED_KEYWORDS.contains(keyword));
+    let mut keywords = Vec::new();
+    let mut start = 0;
+    while let Some(keyword) = next {
+        let value_start = start + keyword.len() + 2;
+        let value = &field[value_start..];
+        let skipped = match keyword {
+            DUTCH_COUNTERPARTY => value
+                .match_indices('/')
+                .nth(2)
+                .map_or(value.len(), |(index, _)| index + 1),
+            REMITTANCE_KEYWORD if value.starts_with(UNSTRUCTURED_REMITTANCE) => {
+                UNSTRUCTURED_REMITTANCE.len()
+            }
+            _ => 0,
+        };
+        let boundary = value[skipped..]
+            .match_indices('/')
+            .map(|(index, _)| value_start + skipped + index)
+            .find_map(|index| keyword_at(field, index).map(|keyword| (index, keyword)));
+        let value_end = boundary.map_or(field.len(), |(index, _)| index);
+        let value = field[value_start..value_end].trim();
+        keywords.push((keyword, value.strip_suffix('/').unwrap_or(value).trim()));
+        (start, next) = match boundary {
+            Some((index, keyword)) => (index, Some(keyword)),
+            None => (field.len(), None),
+        };
+    }
+    keywords
+}
+
+/// Keyword of the `/KEYWORD/` at `index` of `field`: a known one, or any other
+/// of 4 capital letters
+fn keyword_at(field: &str, index: usize) -> Option<&str> {
+    let rest = field[index..].strip_prefix('/')?;
+    let end = rest.find('/')?;
+    let keyword = &rest[..end];
+    let other = keyword.len() == BANK_KEYWORD_LENGTH
+        && keyword.bytes().all(|byte| byte.is_ascii_uppercase());
+    (other || STRUCTURED_KEYWORDS.contains(&keyword)).then_some(keyword)
+}
+
+/// Value following `/KEYWORD/` up to the next `/`
+fn keyword_value<'a>(field: &'a str, keyword: &str) -> Option<&'a str> {
+    let start = field.find(&format!("/{}/", keyword))? + keyword.len() + 2;
+    let value = &field[start..];
+    Some(value.split('/').next().unwrap_or(value).trim()).filter(|value| !value.is_empty())
+}
+
+/// `?` starts a subfield in the German layout, so it cannot appear in values
+fn german_text(text: &str) -> String {
+    text.replace(['\r', '\n'], " ").replace('?', ".")
+}
+
+/// `/` delimits keywords in the Dutch layout, so it cannot appear in values
+fn dutch_text(text: &str) -> String {
+    text.replace(['\r', '\n'], " ").replace('/', "-")
+}
+
+/// The first `size` characters of `text`
+fn truncate_chars(text: &str, size: usize) -> String {
+    text.chars().take(size).collect()
+}
+
+/// Split `text` into pieces of at most `size` characters
+fn chunk_chars(text: &str, size: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(size.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction() -> Transaction {
+        Transaction {
+            reference: Some("E2E-42".into()),
+            counterparty_name: Some("Muster Handels GmbH und Partner KG".into()),
+            counterparty_account: Some("DE89370400440532013000".into()),
+            ..Transaction::new(
+                text::parse_date("2025-03-07").unwrap(),
+                10.0,
+                TransactionType::Credit,
+                "Rechnung 2025/17 vom 01.03. Danke?",
+            )
+        }
+    }
+
+    #[test]
+    fn test_german_subfields() {
+        let lines = Mt940Dialect::German.field86_lines(&transaction());
+
+        assert_eq!(
+            lines,
+            vec![
+                "166?00GUTSCHRIFT?20EREF+E2E-42 SVWZ+Rechnung 2",
+                "?21025/17 vom 01.03. Danke.?31DE89370400440532013000",
+                "?32Muster Handels GmbH und Par?33tner KG",
+            ]
+        );
+        assert_eq!(
+            remittance_text(&lines.join("\n")).as_deref(),
+            Some("Rechnung 2025/17 vom 01.03. Danke.")
+        );
+    }
+
+    #[test]
+    fn test_german_long_description_is_cut_to_six_lines() {
+        let mut tx = transaction();
+        tx.description = "Rechnung 2025/17 Wartung der Anlage ".repeat(14);
+
+        let lines = Mt940Dialect::German.field86_lines(&tx);
+        assert_eq!(lines.len(), MT940_NARRATIVE_LINES);
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() <= MT940_LINE_LENGTH));
+        assert!(lines
+            .concat()
+            .ends_with("?32Muster Handels GmbH und Par?33tner KG"));
+
+        let read_back = remittance_text(&lines.join("\n")).unwrap();
+        assert!(read_back.len() > 100);
+        assert!(tx.description.starts_with(&read_back));
+    }
+
+    #[test]
+    fn test_keyword_layouts_cut_long_descriptions_to_six_lines() {
+        let mut tx = transaction();
+        tx.description = "Factuur 2025-17 onderhoud installatie ".repeat(14);
+
+        for dialect in [Mt940Dialect::Dutch, Mt940Dialect::Abn, Mt940Dialect::Swift] {
+            let lines = dialect.field86_lines(&tx);
+            assert_eq!(lines.len(), MT940_NARRATIVE_LINES, "{}", dialect);
+            assert!(lines
+                .iter()
+                .all(|line| line.chars().count() <= MT940_LINE_LENGTH));
+
+            let field = lines.join("\n");
+            let read_back = remittance_text(&field).unwrap();
+            assert!(tx.description.starts_with(&read_back), "{}", dialect);
+            assert_eq!(sepa_ids(&field).0.as_deref(), Some("E2E-42"));
+        }
+    }
+
+    #[test]
+    fn test_german_counterparty_round_trip() {
+        let field = Mt940Dialect::German
+            .field86_lines(&transaction())
+            .join("\n");
+        assert_eq!(
+            counterparty(&field),
+            (
+                Some("Muster Handels GmbH und Partner KG".into()),
+                Some("DE89370400440532013000".into())
+            )
+        );
+        // Without the posting text a `?` is no subfield
+        assert_eq!(counterparty("Paid?31 days late"), (None, None));
+    }
+
+    #[test]
+    fn test_dutch_keywords() {
+        let lines = Mt940Dialect::Dutch.field86_lines(&transaction());
+        let field = lines.concat();
+
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() <= MT940_LINE_LENGTH));
+        assert_eq!(
+            field,
+            "/EREF/E2E-42/CNTP/DE89370400440532013000//Muster Handels GmbH und Partner KG/\
+             /REMI/USTD//Rechnung 2025-17 vom 01.03. Danke?/"
+        );
+    }
+
+    #[test]
+    fn test_dutch_foreign_amount_round_trip() {
+        let mut tx = transaction();
+        tx.original_amount = Some(12.5);
+        tx.original_currency = Some("USD".into());
+        tx.exchange_rate = Some(1.0876);
+
+        let lines = Mt940Dialect::Dutch.field86_lines(&tx);
+        assert!(lines
+            .concat()
+            .contains("/OCMT/USD12,50//EXCH/1,0876//REMI/"));
+        assert_eq!(
+            foreign_amount(&lines.join("\n")),
+            (Some("USD".into()), Some(12.5), Some(1.0876))
+        );
+        assert_eq!(foreign_amount("/OCMT/12,50/"), (None, None, None));
+    }
+
+    #[test]
+    fn test_dutch_charges_round_trip() {
+        let mut tx = transaction();
+        tx.charges = vec![Charge {
+            amount: 1.5,
+            currency: Some("EUR".into()),
+            charge_type: TransactionType::Debit,
+            code: None,
+            bearer: None,
+        }];
+
+        let lines = Mt940Dialect::Dutch.field86_lines(&tx);
+        assert!(lines.concat().contains("/CHGS/EUR1,50//REMI/"));
+        assert_eq!(charges(&lines.join("\n")), tx.charges);
+        assert_eq!(charges("/CHGS/2,00/")[0].currency, None);
+        assert!(charges("/CHGS/EUR/").is_empty());
+    }
+
+    #[test]
+    fn test_counterparty_bic_and_city() {
+        let mut tx = transaction();
+        tx.counterparty_bic = Some("COBADEFFXXX".into());
+        tx.counterparty_address = Some(PostalAddress {
+            town: Some("Frankfurt".into()),
+            ..PostalAddress::default()
+        });
+
+        let dutch = Mt940Dialect::Dutch.field86_lines(&tx).join("\n");
+        assert!(dutch
+            .lines()
+            .collect::<String>()
+            .contains("/COBADEFFXXX/Muster Handels GmbH und Partner KG/Frankfurt/REMI/"));
+        assert_eq!(
+            counterparty_agent(&dutch),
+            (tx.counterparty_bic.clone(), tx.counterparty_address.clone())
+        );
+
+        let german = Mt940Dialect::German.field86_lines(&tx).join("\n");
+        assert_eq!(
+            counterparty_agent(&german).0.as_deref(),
+            Some("COBADEFFXXX")
+        );
+        // A German bank code is not a BIC
+        assert_eq!(
+            counterparty_agent("166?00GUTSCHRIFT?3037040044"),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn test_sepa_ids_round_trip() {
+        let mut tx = transaction();
+        tx.end_to_end_id = Some("E2E-2025-0042".into());
+        tx.mandate_id = Some("MANDATE-7".into());
+
+        for dialect in [Mt940Dialect::German, Mt940Dialect::Dutch] {
+            let field = dialect.field86_lines(&tx).join("\n");
+            assert_eq!(
+                sepa_ids(&field),
+                (tx.end_to_end_id.clone(), tx.mandate_id.clone()),
+                "{}",
+                dialect
+            );
+        }
+        assert_eq!(sepa_ids("/EREF/NOTPROVIDED/REMI/USTD//Rent/"), (None, None));
+    }
+
+    #[test]
+    fn test_abn_and_swift_keywords() {
+        let mut tx = transaction();
+        tx.counterparty_bic = Some("COBADEFFXXX".into());
+        tx.mandate_id = Some("MANDATE-7".into());
+
+        let abn = Mt940Dialect::Abn.field86_lines(&tx);
+        assert!(abn
+            .iter()
+            .all(|line| line.chars().count() <= MT940_LINE_LENGTH));
+        assert_eq!(
+            abn.concat(),
+            "/TRTP/SEPA INCASSO ALGEMEEN DOORLOPEND/IBAN/DE89370400440532013000\
+             /BIC/COBADEFFXXX/NAME/Muster Handels GmbH und Partner KG/MARF/MANDATE-7\
+             /REMI/Rechnung 2025-17 vom 01.03. Danke?/EREF/E2E-42"
+        );
+
+        tx.transaction_type = TransactionType::Debit;
+        let swift = Mt940Dialect::Swift.field86_lines(&tx);
+        assert_eq!(
+            swift.concat(),
+            "/EREF/E2E-42/BENM//IBAN/DE89370400440532013000/BIC/COBADEFFXXX\
+             /NAME/Muster Handels GmbH und Partner KG/MARF/MANDATE-7\
+             /REMI/Rechnung 2025-17 vom 01.03. Danke?/"
+        );
+
+        for lines in [abn, swift] {
+            let field = lines.join("\n");
+            assert_eq!(
+                sepa_ids(&field),
+                (Some("E2E-42".into()), Some("MANDATE-7".into()))
+            );
+            assert_eq!(counterparty_agent(&field).0.as_deref(), Some("COBADEFFXXX"));
+        }
+    }
+
+    #[test]
+    fn test_narrative_wraps_at_words_and_joins_back() {
+        let description = format!(
+            "{} end\nSecond line\n{}",
+            "Payment for order 4711 ".repeat(4),
+            "x".repeat(70)
+        );
+        let lines = wrap_narrative(&description);
+        assert_eq!(lines.len(), 5);
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() <= MT940_LINE_LENGTH));
+        assert_eq!(lines[1], " 4711 Payment for order 4711  end");
+        assert_eq!(lines[3].len(), MT940_LINE_LENGTH);
+        assert_eq!(join_narrative(&lines.join("\n")), description);
+
+        assert_eq!(
+            join_narrative("Invoice 1\n  ACME\nThanks"),
+            "Invoice 1  ACME\nThanks"
+        );
+        assert_eq!(
+            narrative_lines(&"word ".repeat(100)).len(),
+            MT940_NARRATIVE_LINES
+        );
+    }
+
+    #[test]
+    fn test_structured_counterparty_and_remittance() {
+        let abn = "/TRTP/SEPA OVERBOEKING/IBAN/NL46ABNA0499998748/BIC/ABNANL2A\n\
+                   /NAME/J. Jansen/REMI/Factuur 2025/17/EREF/NOTPROVIDED";
+        assert_eq!(
+            counterparty(abn),
+            (Some("J. Jansen".into()), Some("NL46ABNA0499998748".into()))
+        );
+        assert_eq!(remittance_text(abn).as_deref(), Some("Factuur 2025/17"));
+
+        let swift = "/EREF/E2E-9/ORDP//NAME/ACME Corp/IBAN/DE89370400440532013000/REMI/Rent May/";
+        assert_eq!(
+            counterparty(swift),
+            (
+                Some("ACME Corp".into()),
+                Some("DE89370400440532013000".into())
+            )
+        );
+        assert_eq!(remittance_text(swift).as_deref(), Some("Rent May"));
+
+        let field = Mt940Dialect::Dutch.field86_lines(&transaction()).join("\n");
+        assert_eq!(
+            counterparty(&field),
+            (
+                Some("Muster Handels GmbH und Partner KG".into()),
+                Some("DE89370400440532013000".into())
+            )
+        );
+        assert_eq!(
+            remittance_text(&field).as_deref(),
+            Some("Rechnung 2025-17 vom 01.03. Danke?")
+        );
+
+        // Free text mentioning a keyword is not structured
+        assert_eq!(counterparty("Paid to /NAME/ Bob"), (None, None));
+        assert_eq!(remittance_text("Invoice /REMI/ 5"), None);
+        assert_eq!(unread_keywords("Invoice /REMI/ 5"), None);
+
+        // Values end at keywords of the bank's own, which stay in the description
+        let bank = "/EREF/GS-1\n/CRNM/ACME/REMI/Payment to Vendor\n/OPRP/Tag Payment";
+        assert_eq!(remittance_text(bank).as_deref(), Some("Payment to Vendor"));
+        assert_eq!(
+            unread_keywords("/EREF/GS-2/DACT/8348577826/OAMT/11-25/").as_deref(),
+            Some("/DACT/8348577826/OAMT/11-25")
+        );
+        // but not within the Dutch counterparty values or unstructured text
+        let dutch = "/CNTP/NL46ABNA0499998748/ABNANL2A/ACME/ROME/REMI/USTD//RENT/";
+        assert_eq!(counterparty(dutch).0.as_deref(), Some("ACME"));
+        assert_eq!(remittance_text(dutch).as_deref(), Some("RENT"));
+    }
+
+    #[test]
+    fn test_parse_dialect_name() {
+        assert_eq!("German".parse(), Ok(Mt940Dialect::German));
+        assert_eq!("ing".parse(), Ok(Mt940Dialect::Dutch));
+        assert_eq!("generic".parse(), Ok(Mt940Dialect::Swift));
+        assert_eq!(Mt940Dialect::Abn.to_string(), "abn");
+        assert_eq!(Mt940Dialect::Dutch.to_string(), "dutch");
+        assert!("swiss".parse::<Mt940Dialect>().is_err());
+    }
+
+    #[test]
+    fn test_standard_keeps_description() {
+        let mut tx = transaction();
+        tx.description = "line one\nline two".into();
+        assert_eq!(
+            Mt940Dialect::Standard.field86_lines(&tx),
+            vec!["line one", "line two"]
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::diagnostics::{Diagnostics, ParseWarning};
+use crate::error::ParseError;
+use crate::formats::mt940_statement::{Mt940Field, Mt940Statement};
+use crate::model::{BalanceType, StatementHeader, Transaction};
+use crate::quirks::{ParseOptions, Quirk};
+
+/// Start of the SWIFT text block holding the tags
+const TEXT_BLOCK_START: &str = "{4:";
+/// End of the SWIFT text block
+const TEXT_BLOCK_END: &str = "-}";
+
+/// Amount, date, indicator and currency of a `:60F:`/`:62F:` balance
+type BalanceLine = (f64, DateTime<FixedOffset>, BalanceType, String);
+
+/// Streaming reader yielding the `:61:` entries of an MT940 file one by one,
+/// in constant memory.
+///
+/// [`Mt940Statement::from_read`] reads the whole file into a string and
+/// tokenizes it before parsing; this reader takes the input one line at a
+/// time and hands out every `:61:` entry, with the description of a directly
+/// following `:86:`, as soon as the next tag shows the entry is complete.
+/// Memory stays bounded by the longest entry, so year-end files with hundreds
+/// of thousands of entries need not fit in RAM. Entries of every message and
+/// `:20:` sequence are yielded in file order.
+///
+/// Each statement is checked as the buffered parser checks it: a malformed or
+/// missing account or booked balance fails the iteration once the statement
+/// ends. [`header`] holds the account and balances of the last statement whose
+/// closing balance was read. Bank quirks are detected from the SWIFT header
+/// blocks of each message; files without blocks only get the quirks `options`
+/// name explicitly. Available balances (`:64:`/`:65:`) are not read.
+///
+/// Malformed entries are skipped with a [`ParseWarning`] (see [`warnings`]),
+/// or fail the iteration with [`ParseOptions::strict`]. After the first error
+/// the iterator is exhausted.
+///
+/// [`header`]: Mt940Reader::header
+/// [`warnings`]: Mt940Reader::warnings
+///
+/// # Example
+/// ```
+/// use ledger_parser::Mt940Reader;
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102C50,00NTRFNONREF\n:86:Invoice 123\n\
+///             :61:200102D25,00NTRFNONREF\n:62F:C200102EUR125,00\n";
+/// let mut reader = Mt940Reader::new(data.as_bytes());
+/// assert_eq!(reader.next().unwrap().unwrap().description, "Invoice 123");
+/// assert_eq!(reader.next().unwrap().unwrap().amount, 25.0);
+/// assert!(reader.next().is_none());
+/// assert_eq!(reader.header().unwrap().closing_balance, 125.0);
+/// ```
+pub struct Mt940Reader<R: BufRead> {
+    reader: R,
+    options: ParseOptions,
+    diagnostics: Diagnostics,
+    line: String,
+    /// Lines read so far
+    line_number: usize,
+    /// Tokens read but not yet consumed
+    tokens: VecDeque<Token>,
+    /// Tag whose value may still continue on the next line
+    current: Option<Mt940Field>,
+    /// Text before the `{4:` block of the next message, for bank detection
+    message_header: String,
+    /// A message (or a file without blocks) is being read
+    in_message: bool,
+    /// Quirks of the message being read
+    quirks: Vec<Quirk>,
+    /// `:61:` entry waiting for a `:86:` description
+    entry: Option<Mt940Field>,
+    /// Header tags of the `:20:` sequence being read
+    sequence: Sequence,
+    /// Account and balances of the last statement closed
+    header: Option<StatementHeader>,
+    /// End of the input or an error was reached
+    finished: bool,
+}
+
+/// Structure of the input, as the line reader sees it
+enum Token {
+    /// A message starts, with the quirks of its bank
+    MessageStart(Vec<Quirk>),
+    /// A complete `:TAG:value` field
+    Field(Mt940Field),
+    /// The message (or the input) ends
+    MessageEnd,
+}
+
+/// Header tags of one `:20:`…`:62F:` sequence
+#[derive(Default)]
+struct Sequence {
+    /// Any tag of the sequence was read
+    started: bool,
+    account_number: Option<String>,
+    opening: Option<BalanceLine>,
+    /// The closing balance was read
+    closed: bool,
+}
+
+impl<R: BufRead> Mt940Reader<R> {
+    /// Stream the entries of `reader`, skipping malformed ones with a warning
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, &ParseOptions::default())
+    }
+
+    /// Stream the entries of `reader` with the quirks selected by `options`;
+    /// `options.strict` fails on malformed entries
+    pub fn with_options(reader: R, options: &ParseOptions) -> Self {
+        Mt940Reader {
+            reader,
+            options: options.clone(),
+            diagnostics: Diagnostics::new(options.strict),
+            line: String::new(),
+            line_number: 0,
+            tokens: VecDeque::new(),
+            current: None,
+            message_header: String::new(),
+            in_message: false,
+            quirks: Vec::new(),
+            entry: None,
+            sequence: Sequence::default(),
+            header: None,
+            finished: false,
+        }
+    }
+
+    /// Account and balances of the last statement whose closing balance was
+    /// read, `None` before the first `:62F:`/`:62M:`
+    pub fn header(&self) -> Option<&StatementHeader> {
+        self.header.as_ref()
+    }
+
+    /// Warnings of the entries skipped so far, in file order
+    pub fn warnings(&self) -> &[ParseWarning] {
+        self.diagnostics.warnings()
+    }
+
+    /// Read tokens until an entry is complete or the input ends
+    fn advance(&mut self) -> Result<Option<Transaction>, ParseError> {
+        loop {
+            let Some(token) = self.next_token()? else {
+                return Ok(None);
+            };
+            let (transaction, field) = match token {
+                Token::MessageStart(quirks) => {
+                    self.quirks = quirks;
+                    continue;
+                }
+                Token::MessageEnd => {
+                    let transaction = self.take_entry(None)?;
+                    self.end_sequence()?;
+                    (transaction, None)
+                }
+                Token::Field(field) => (self.take_entry(Some(&field))?, Some(field)),
+            };
+            if let Some(field) = field.filter(|field| field.tag != "86") {
+                self.read_field(field)?;
+            }
+            if transaction.is_some() {
+                return Ok(transaction);
+            }
+        }
+    }
+
+    /// Parse the pending `:61:` entry, with `next` as its description when it
+    /// is an `:86:`; a malformed entry goes to the diagnostics
+    fn take_entry(&mut self, next: Option<&Mt940Field>) -> Result<Option<Transaction>, ParseError> {
+        let Some(entry) = self.entry.take() else {
+            return Ok(None);
+        };
+        let description = match next {
+            Some(field) if field.tag == "86" => field.value.trim(),
+            _ => "",
+        };
+        match entry
+            .parse(|line| Mt940Statement::parse_transaction_line(line, description, &self.quirks))
+        {
+            Ok(transaction) => {
+                self.diagnostics.parsed(1);
+                Ok(Some(transaction))
+            }
+            Err(error) => {
+                self.diagnostics.skip(error)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Track the header tags of the sequence and hold back `:61:` entries
+    fn read_field(&mut self, field: Mt940Field) -> Result<(), ParseError> {
+        if field.tag == "20" && self.sequence.started {
+            self.end_sequence()?;
+        }
+        self.sequence.started = true;
+
+        match field.tag.as_str() {
+            "25" if self.sequence.account_number.is_none() => {
+                self.sequence.account_number = Some(field.value.trim().into());
+            }
+            "60F" | "60M" if self.sequence.opening.is_none() => {
+                self.sequence.opening = Some(field.parse(Mt940Statement::parse_balance_line)?);
+            }
+            "62F" | "62M" if !self.sequence.closed => {
+                let closing = field.parse(Mt940Statement::parse_balance_line)?;
+                self.header = Some(self.sequence.header(closing)?);
+                self.sequence.closed = true;
+            }
+            "61" => self.entry = Some(field),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Check the sequence read so far has its closing balance, and start a new one
+    fn end_sequence(&mut self) -> Result<(), ParseError> {
+        let sequence = std::mem::take(&mut self.sequence);
+        if sequence.started && !sequence.closed {
+            return Err(ParseError::Mt940Error("Missing :62F: or :62M: tag".into()));
+        }
+        Ok(())
+    }
+
+    /// Next token of the input, reading lines as needed
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        while self.tokens.is_empty() && !self.finished {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                self.end_message();
+                self.finished = true;
+            } else {
+                self.line_number += 1;
+                let line = std::mem::take(&mut self.line);
+                self.read_line(line.trim_end_matches(['\r', '\n']));
+                self.line = line;
+            }
+        }
+        Ok(self.tokens.pop_front())
+    }
+
+    /// Split one line into message boundaries and tags
+    fn read_line(&mut self, line: &str) {
+        let mut text = line.trim();
+        let mut continuation = Some(line);
+        if self.in_message {
+            if let Some(rest) = text.strip_prefix(TEXT_BLOCK_END) {
+                self.end_message();
+                text = rest.trim_start();
+            } else if text.starts_with('{') || text.starts_with('}') {
+                self.end_message();
+            }
+        }
+
+        if !self.in_message {
+            match text.find(TEXT_BLOCK_START) {
+                Some(start) => {
+                    self.message_header.push_str(&text[..start]);
+                    text = text[start + TEXT_BLOCK_START.len()..].trim_start();
+                    continuation = None;
+                    self.start_message();
+                }
+                None if split_tag(text).is_some() => self.start_message(),
+                None => {
+                    self.message_header.push_str(text);
+                    self.message_header.push('\n');
+                    return;
+                }
+            }
+        }
+
+        if let Some((tag, value)) = split_tag(text) {
+            self.end_field();
+            self.current = Some(Mt940Field {
+                tag: tag.into(),
+                value: value.into(),
+                line: self.line_number,
+            });
+        } else if text.starts_with(':') {
+            // Not a tag, but not a continuation either
+            self.end_field();
+        } else if let (Some(field), Some(line)) = (&mut self.current, continuation) {
+            field.value.push('\n');
+            field.value.push_str(line);
+        }
+    }
+
+    /// A message starts: detect the quirks of its bank from the text before it
+    fn start_message(&mut self) {
+        let quirks = self.options.active_quirks(&self.message_header);
+        self.message_header.clear();
+        self.in_message = true;
+        self.tokens.push_back(Token::MessageStart(quirks));
+    }
+
+    /// The message ends with the field being read
+    fn end_message(&mut self) {
+        if self.in_message {
+            self.end_field();
+            self.tokens.push_back(Token::MessageEnd);
+            self.in_message = false;
+        }
+    }
+
+    /// The field being read is complete
+    fn end_field(&mut self) {
+        if let Some(field) = self.current.take() {
+            self.tokens.push_back(Token::Field(field));
+        }
+    }
+}
+
+impl Sequence {
+    /// Header of the sequence closing with `closing`
+    fn header(&self, closing: BalanceLine) -> Result<StatementHeader, ParseError> {
+        let account_number = self
+            .account_number
+            .clone()
+            .ok_or_else(|| ParseError::Mt940Error("Missing :25: account tag".into()))?;
+        let (opening_balance, opening_date, opening_indicator, currency) = self
+            .opening
+            .clone()
+            .ok_or_else(|| ParseError::Mt940Error("Missing :60F: or :60M: tag".into()))?;
+        let (closing_balance, closing_date, closing_indicator, _) = closing;
+        Ok(StatementHeader {
+            account_number,
+            currency,
+            opening_balance,
+            opening_date,
+            opening_indicator,
+            closing_balance,
+            closing_date,
+            closing_indicator,
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for Mt940Reader<R> {
+    type Item = Result<Transaction, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.advance() {
+            Ok(transaction) => transaction.map(Ok),
+            Err(error) => {
+                self.finished = true;
+                self.tokens.clear();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Tag and first-line value of a `:TAG:value` line
+fn split_tag(text: &str) -> Option<(&str, &str)> {
+    text.strip_prefix(':')?.split_once(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_parse_all_across_messages() {
+        let data = "{1:F01GSCRUS30XXXX3614000002}{2:I940GSCRUS30XXXXN}{4:\r\n:20:A\r\n\
+                    :25:NL81ASNB9999999999\r\n:60F:C191230EUR100,00\r\n\
+                    :61:1912310102C50,00NTRFINV-1\r\n:86:Invoice 1\r\n  ACME\r\n\
+                    :61:191231D10,00NTRFNONREF\r\n:62F:C200102EUR140,00\r\n\
+                    -}{1:F01BANK}{2:I940BANK}{4:\r\n:20:B\r\n:25:NL81ASNB9999999999\r\n\
+                    :60F:C200102EUR140,00\r\n:61:200104D5,00NTRFNONREF\r\n:86:Fee\r\n\
+                    :62F:C200104EUR135,00\r\n-}\r\n";
+        let statements = Mt940Statement::parse_all(&mut data.as_bytes()).unwrap();
+        let expected: Vec<_> = statements
+            .iter()
+            .flat_map(|statement| statement.transactions.clone())
+            .collect();
+
+        let mut reader = Mt940Reader::new(data.as_bytes());
+        let streamed: Vec<_> = reader.by_ref().map(Result::unwrap).collect();
+        assert_eq!(streamed, expected);
+        // The Goldman Sachs entry date applies to the first message only
+        assert_eq!(
+            streamed[0].booking_date.date_naive().to_string(),
+            "2020-01-02"
+        );
+        assert_eq!(reader.header().unwrap().closing_balance, 135.0);
+    }
+
+    #[test]
+    fn test_malformed_entries_warn_or_fail() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200132C5,00NTRFNONREF\n:86:Broken\n:61:200102C5,00NTRFNONREF\n\
+                    :62F:C200102EUR105,00\n";
+        let mut lenient = Mt940Reader::new(data.as_bytes());
+        assert_eq!(lenient.by_ref().count(), 1);
+        assert!(lenient.warnings()[0]
+            .to_string()
+            .starts_with("line 4, field 61"));
+
+        let strict = ParseOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let mut strict = Mt940Reader::with_options(data.as_bytes(), &strict);
+        assert!(strict.next().unwrap().is_err());
+        assert!(strict.next().is_none());
+
+        let truncated = Mt940Reader::new(&data.as_bytes()[..data.len() - 22]);
+        let last = truncated.last().unwrap();
+        assert!(matches!(last, Err(ParseError::Mt940Error(message)) if message.contains(":62F:")));
+    }
+}
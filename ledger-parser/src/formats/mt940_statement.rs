@@ -1,7 +1,55 @@
-use crate::{formats::utils, BalanceType, ParseError, Transaction, TransactionType};
-use chrono::{DateTime, FixedOffset, NaiveDate, Offset, Utc};
+use crate::diagnostics::{Diagnostics, ParseStats, ParseWarning};
+use crate::formats::mt940_dialect::{self, Mt940Dialect};
+use crate::formats::mt940_tags::Mt940Tags;
+use crate::formats::paging::{self, Page};
+use crate::formats::swift_charset::Mt940Charset;
+use crate::ids::{IdContext, IdKind, SharedIdGenerator};
+use crate::quirks::{ParseOptions, Quirk};
+use crate::{
+    formats::utils, text, Balance, BalanceCode, BalanceType, ParseError, StatementHeader,
+    StatementInfo, SwiftEntry, Transaction, TransactionType,
+};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Offset, Utc};
 use serde::{Deserialize, Serialize};
-use std::io::{Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// `:61:` reference for the account owner meaning "no reference"
+const NO_REFERENCE: &str = "NONREF";
+/// `:61:` transaction type code written for entries that carry none
+const DEFAULT_TRANSACTION_CODE: &str = "NTRF";
+/// Separator of the bank reference in `:61:`
+const BANK_REFERENCE_SEPARATOR: &str = "//";
+/// `:20:` transaction reference written when no ID generator is configured
+const DEFAULT_TRANSACTION_REFERENCE: &str = "STATEMENT";
+/// Maximum length of the `:20:` transaction reference (SWIFT `16x`)
+const TRANSACTION_REFERENCE_MAX_LENGTH: usize = 16;
+/// Closing available balance tag
+const CLOSING_AVAILABLE_TAG: &str = "64";
+/// Forward available balance tag, repeated per value date
+const FORWARD_AVAILABLE_TAG: &str = "65";
+/// Opening balance tag of a page continuing the previous message
+const INTERMEDIATE_OPENING_TAG: &str = "60M";
+/// Closing balance tag of a page continued in the next message
+const INTERMEDIATE_CLOSING_TAG: &str = "62M";
+
+/// One `:TAG:value` field of a message and the line of the input it starts on
+#[derive(Debug, Clone)]
+pub(crate) struct Mt940Field {
+    pub(crate) tag: String,
+    pub(crate) value: String,
+    pub(crate) line: usize,
+}
+
+impl Mt940Field {
+    /// Run `parse` on the value, locating its errors at this field
+    pub(crate) fn parse<T>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        parse(&self.value).map_err(|error| error.at_line(self.line, Some(&self.tag)))
+    }
+}
 
 /// MT940 SWIFT message structure.
 ///
@@ -10,7 +58,7 @@ use std::io::{Read, Write};
 ///
 /// This implementation handles the SWIFT MT940 format with:
 /// - Block structure (`{1:...}{2:...}{4:...}`)
-/// - Tag-based fields (`:20:`, `:25:`, `:60F:`, `:61:`, `:86:`, `:62F:`)
+/// - Tag-based fields (`:20:`, `:25:`, `:60F:`, `:61:`, `:86:`, `:62F:`, `:64:`, `:65:`)
 /// - YYMMDD date format with century inference
 /// - Multi-line `:86:` fields
 /// - Both comma and dot as decimal separators
@@ -34,6 +82,29 @@ pub struct Mt940Statement {
     pub closing_indicator: BalanceType,
     /// List of transactions in chronological order
     pub transactions: Vec<Transaction>,
+    /// Available, forward and interim balances reported beside opening and closing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub balances: Vec<Balance>,
+    /// Statement identification, sequence numbers and period
+    #[serde(default, skip_serializing_if = "StatementInfo::is_empty")]
+    pub info: StatementInfo,
+}
+
+/// Options controlling how [`Mt940Statement::write_to_with`] renders output.
+///
+/// `Default` reproduces the output of [`Mt940Statement::write_to`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mt940WriteOptions {
+    /// Maximum number of `:61:` entries per SWIFT message; `None` writes a single message
+    pub max_transactions_per_message: Option<usize>,
+    /// Layout of the `:86:` information field
+    pub dialect: Mt940Dialect,
+    /// Handling of characters outside the SWIFT `X` set
+    #[serde(default)]
+    pub charset: Mt940Charset,
+    /// Source of the `:20:` transaction reference (one per SWIFT message); `None` writes `STATEMENT`
+    #[serde(skip)]
+    pub id_generator: Option<SharedIdGenerator>,
 }
 
 impl Mt940Statement {
@@ -58,29 +129,299 @@ impl Mt940Statement {
     /// let statement = Mt940Statement::from_read(&mut file).unwrap();
     /// ```
     pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        Self::from_read_with(reader, &ParseOptions::default())
+    }
+
+    /// Parse MT940 with explicit [`ParseOptions`], e.g. to force or disable bank quirks.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Mt940Statement::from_read_with_options`].
+    pub fn from_read_with<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        Self::from_read_with_options(reader, options).map(|(statement, _)| statement)
+    }
+
+    /// Parse MT940 with explicit [`ParseOptions`], returning the statement with
+    /// a [`ParseWarning`] for every record skipped.
+    ///
+    /// Leniently, a malformed `:61:` entry is left out of the transactions and
+    /// a malformed `:64:`/`:65:` available balance out of the balances; with
+    /// `options.strict` either fails the parse.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Mt940Statement::from_read`], and in strict mode the error of
+    /// the first malformed record, located at its line.
+    pub fn from_read_with_options<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        Self::from_read_with_stats(reader, options)
+            .map(|(statement, stats)| (statement, stats.into_warnings()))
+    }
+
+    /// Parse MT940 with explicit [`ParseOptions`], as
+    /// [`Mt940Statement::from_read_with_options`], returning how many `:61:`
+    /// entries and `:64:`/`:65:` balances were read, parsed and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Mt940Statement::from_read_with_options`].
+    pub fn from_read_with_stats<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Self, ParseStats), ParseError> {
         // Read entire content
         let mut content = String::new();
         reader.read_to_string(&mut content)?;
 
+        let mut diagnostics = Diagnostics::new(options.strict);
+        let statement = Self::from_content_with(&content, options, &mut diagnostics)?;
+        Ok((statement, diagnostics.into_stats()))
+    }
+
+    /// Parse every statement of an MT940 file, in file order.
+    ///
+    /// Bank files often bundle several SWIFT messages (`{4:` blocks), and a
+    /// message or a bare tag file may hold several `:20:`…`:62F:` sequences;
+    /// each `:20:` starts a new statement. A statement split over several
+    /// messages, each page closing with `:62M:` and the next opening with
+    /// `:60M:`, is joined back into one. Bank quirks are detected per message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Mt940Error` if the input holds no statement or any
+    /// statement is invalid (see [`Mt940Statement::from_read`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ledger_parser::Mt940Statement;
+    ///
+    /// let data = "{1:F01BANK}{2:I940BANK}{4:\n:20:A\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n:62F:C200101EUR100,00\n-}\
+    ///             {1:F01BANK}{2:I940BANK}{4:\n:20:B\n:25:NL81ASNB9999999999\n:60F:C200102EUR100,00\n\
+    ///             :61:200102C50,00NTRFNONREF\n:62F:C200102EUR150,00\n-}";
+    /// let statements = Mt940Statement::parse_all(&mut data.as_bytes()).unwrap();
+    /// assert_eq!(statements.len(), 2);
+    /// assert_eq!(statements[1].closing_balance, 150.0);
+    /// ```
+    pub fn parse_all<R: Read>(reader: &mut R) -> Result<Vec<Self>, ParseError> {
+        Self::parse_all_with_options(reader, &ParseOptions::default())
+            .map(|(statements, _)| statements)
+    }
+
+    /// Parse every statement of an MT940 file with explicit [`ParseOptions`],
+    /// as [`Mt940Statement::parse_all`], returning the warnings of all of them
+    /// as [`Mt940Statement::from_read_with_options`] does.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Mt940Statement::parse_all`], and in strict mode the error of
+    /// the first malformed record.
+    pub fn parse_all_with_options<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<(Vec<Self>, Vec<ParseWarning>), ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut diagnostics = Diagnostics::new(options.strict);
+        let statements = Self::parse_statements(&content, options, &mut diagnostics, false)?;
+        Ok((statements, diagnostics.into_warnings()))
+    }
+
+    /// Statements of `content` with their pages joined, only the first one
+    /// when `first_only` is set
+    fn parse_statements(
+        content: &str,
+        options: &ParseOptions,
+        diagnostics: &mut Diagnostics,
+        first_only: bool,
+    ) -> Result<Vec<Self>, ParseError> {
+        let mut statements: Vec<Self> = Vec::new();
+        let mut continued = false;
+        for (first_line, message) in Self::split_messages(content) {
+            let quirks = options.active_quirks(message);
+            Self::extract_block4(message)?;
+            let tags = Self::parse_tags(message, first_line);
+            for sequence in Self::split_sequences(&tags) {
+                let is_page = continued && Self::has_tag(sequence, INTERMEDIATE_OPENING_TAG);
+                if first_only && !is_page && !statements.is_empty() {
+                    return Ok(statements);
+                }
+                let statement = Self::from_tags(sequence, &quirks, diagnostics)?;
+                continued = Self::has_tag(sequence, INTERMEDIATE_CLOSING_TAG);
+                match statements.last_mut() {
+                    Some(last) if is_page && last.account_number == statement.account_number => {
+                        last.append_page(statement)
+                    }
+                    _ => statements.push(statement),
+                }
+            }
+        }
+
+        if statements.is_empty() {
+            return Err(ParseError::Mt940Error("No statement in input".into()));
+        }
+        Ok(statements)
+    }
+
+    /// Whether a sequence holds a field with `tag`
+    fn has_tag(tags: &[Mt940Field], tag: &str) -> bool {
+        tags.iter().any(|field| field.tag == tag)
+    }
+
+    /// Append the next page of a statement split over several messages; the
+    /// joined statement has no single page number
+    fn append_page(&mut self, page: Self) {
+        self.transactions.extend(page.transactions);
+        self.closing_balance = page.closing_balance;
+        self.closing_date = page.closing_date;
+        self.closing_indicator = page.closing_indicator;
+        self.balances.extend(page.balances);
+        self.info.page_number = None;
+    }
+
+    /// Parse MT940 from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory, which matters for very large statement files.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Mt940Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Read only the account, currency and balances of an MT940 statement.
+    ///
+    /// Lines are scanned one at a time and the reader is not consumed past the
+    /// closing balance (`:62F:`/`:62M:`) of the first message; entries on the
+    /// way are skipped without being parsed, which makes balance monitoring of
+    /// large files much cheaper than [`Mt940Statement::from_read`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Mt940Error` if the account or a balance tag is
+    /// missing or malformed, and `ParseError::IoError` if reading fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ledger_parser::Mt940Statement;
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:200102C50,00NTRFNONREF\n:86:Invoice\n:62F:C200102EUR150,00\n";
+    /// let header = Mt940Statement::peek_header(&mut data.as_bytes()).unwrap();
+    /// assert_eq!(header.closing_balance, 150.0);
+    /// ```
+    pub fn peek_header<R: Read>(reader: &mut R) -> Result<StatementHeader, ParseError> {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut account_number = None;
+        let mut opening = None;
+        let mut closing = None;
+
+        while closing.is_none() {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let mut text = line.trim();
+            if let Some(start) = text.find("{4:") {
+                text = text[start + 3..].trim_start();
+            }
+            if text.starts_with("-}") {
+                break;
+            }
+
+            let Some((tag, value)) = text.strip_prefix(':').and_then(|t| t.split_once(':')) else {
+                continue;
+            };
+            match tag {
+                "25" if account_number.is_none() => account_number = Some(value.trim().into()),
+                "60F" | "60M" if opening.is_none() => {
+                    opening = Some(Self::parse_balance_line(value)?);
+                }
+                "62F" | "62M" => closing = Some(Self::parse_balance_line(value)?),
+                _ => {}
+            }
+        }
+
+        let account_number = account_number
+            .ok_or_else(|| ParseError::Mt940Error("Missing :25: account tag".into()))?;
+        let (opening_balance, opening_date, opening_indicator, currency) =
+            opening.ok_or_else(|| ParseError::Mt940Error("Missing :60F: or :60M: tag".into()))?;
+        let (closing_balance, closing_date, closing_indicator, _) =
+            closing.ok_or_else(|| ParseError::Mt940Error("Missing :62F: or :62M: tag".into()))?;
+
+        Ok(StatementHeader {
+            account_number,
+            currency,
+            opening_balance,
+            opening_date,
+            opening_indicator,
+            closing_balance,
+            closing_date,
+            closing_indicator,
+        })
+    }
+
+    /// Parse MT940 from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        Self::from_content_with(
+            content,
+            &ParseOptions::default(),
+            &mut Diagnostics::default(),
+        )
+    }
+
+    /// Parse MT940 from already loaded content with the quirks selected by `options`
+    fn from_content_with(
+        content: &str,
+        options: &ParseOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ParseError> {
         if content.trim().is_empty() {
             return Err(ParseError::Mt940Error("Empty input".into()));
         }
 
-        // Extract Block 4 (contains actual data)
-        let block4 = Self::extract_block4(&content)?;
+        // Check Block 4 (contains actual data) is closed, then parse its tags
+        Self::extract_block4(content)?;
+        let tags = Self::parse_tags(content, 1);
 
-        // Parse tags from Block 4
-        let tags = Self::parse_tags(&block4)?;
+        // A statement continued in the next messages is read with all its pages
+        if Self::has_tag(&tags, INTERMEDIATE_CLOSING_TAG) {
+            let statements = Self::parse_statements(content, options, diagnostics, true)?;
+            if let Some(statement) = statements.into_iter().next() {
+                return Ok(statement);
+            }
+        }
+        Self::from_tags(&tags, &options.active_quirks(content), diagnostics)
+    }
 
+    /// Build a statement from the tags of one `:20:`…`:62F:` sequence
+    fn from_tags(
+        tags: &[Mt940Field],
+        quirks: &[Quirk],
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Self, ParseError> {
         // Extract required fields
-        let account_number = Self::extract_account_number(&tags)?;
+        let account_number = Self::extract_account_number(tags)?;
         let (opening_balance, opening_date, opening_indicator, currency) =
-            Self::extract_opening_balance(&tags)?;
+            Self::extract_opening_balance(tags)?;
         let (closing_balance, closing_date, closing_indicator) =
-            Self::extract_closing_balance(&tags, &currency)?;
-        let transactions = Self::extract_transactions(&tags, &currency)?;
+            Self::extract_closing_balance(tags, &currency)?;
+        let transactions = Self::extract_transactions(tags, quirks, diagnostics)?;
+        let info = Self::extract_statement_info(tags);
+        let balances = Self::extract_available_balances(tags, diagnostics)?;
 
-        Ok(Mt940Statement {
+        let statement = Mt940Statement {
             account_number,
             currency,
             opening_balance,
@@ -90,79 +431,326 @@ impl Mt940Statement {
             closing_date,
             closing_indicator,
             transactions,
-        })
+            balances,
+            info,
+        };
+        trace_statement!("parsed statement", "mt940", statement);
+
+        Ok(statement)
     }
 
     /// Write MT940 to any Write destination (file, stdout, buffer).
     ///
+    /// Output is buffered internally because every tag is emitted with its own
+    /// `writeln!`; without buffering an unbuffered `File` or stdout would receive
+    /// one system call per field.
+    ///
     /// # Errors
     ///
     /// Returns `ParseError::Mt940Error` if writing fails.
     pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        self.write_to_with(writer, &Mt940WriteOptions::default())
+    }
+
+    /// Write MT940 using explicit writer options.
+    ///
+    /// When `max_transactions_per_message` is set, the statement is emitted as a
+    /// sequence of SWIFT messages. Each message carries `:28C:<statement>/<sequence>` and
+    /// intermediate balances are written as `:60M:`/`:62M:`; only the first
+    /// message opens with `:60F:` and only the last closes with `:62F:`.
+    ///
+    /// `dialect` selects the layout of the `:86:` field (see [`Mt940Dialect`]), and
+    /// `id_generator` the `:20:` reference of each message, cut to 16 characters.
+    /// Without a generator `:20:` is the statement id of [`StatementInfo`], and the
+    /// statement number of `:28C:` comes from its (legal) sequence number.
+    /// `charset` makes the text fit the SWIFT `X` character set (see
+    /// [`Mt940Charset`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails, and with
+    /// [`Mt940Charset::Error`] `ParseError::UnexpectedValue` for text outside
+    /// the `X` set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::{Mt940Statement, Mt940WriteOptions};
+    /// # fn demo(statement: &Mt940Statement) -> Result<(), ledger_parser::ParseError> {
+    /// let options = Mt940WriteOptions {
+    ///     max_transactions_per_message: Some(500),
+    ///     ..Default::default()
+    /// };
+    /// let mut output = Vec::new();
+    /// statement.write_to_with(&mut output, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_to_with<W: Write>(
+        &self,
+        writer: &mut W,
+        options: &Mt940WriteOptions,
+    ) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "mt940", self);
+        let mut writer = BufWriter::new(writer);
+
+        let pages = paging::paginate(
+            &self.balances(),
+            &self.transactions,
+            options.max_transactions_per_message,
+        );
+        for page in &pages {
+            self.write_message(&mut writer, page, options)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Split the statement into standalone statements of at most `max_transactions` entries.
+    ///
+    /// Intermediate opening/closing balances are recomputed from the running total,
+    /// so every part is internally consistent and can be written to its own file.
+    /// Additional balances go with the last part.
+    pub fn split_by_transaction_count(&self, max_transactions: usize) -> Vec<Mt940Statement> {
+        let parts = paging::split(&self.balances(), &self.transactions, max_transactions);
+        let last = parts.len().saturating_sub(1);
+        parts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (balances, transactions))| Mt940Statement {
+                account_number: self.account_number.clone(),
+                currency: self.currency.clone(),
+                opening_balance: balances.opening_balance,
+                opening_date: balances.opening_date,
+                opening_indicator: balances.opening_indicator,
+                closing_balance: balances.closing_balance,
+                closing_date: balances.closing_date,
+                closing_indicator: balances.closing_indicator,
+                transactions,
+                info: self.info.clone(),
+                balances: if index == last {
+                    self.balances.clone()
+                } else {
+                    Vec::new()
+                },
+            })
+            .collect()
+    }
+
+    /// Statement balances in the shape used by the paging helper
+    fn balances(&self) -> paging::Balances {
+        paging::Balances {
+            opening_balance: self.opening_balance,
+            opening_indicator: self.opening_indicator.clone(),
+            opening_date: self.opening_date,
+            closing_balance: self.closing_balance,
+            closing_indicator: self.closing_indicator.clone(),
+            closing_date: self.closing_date,
+        }
+    }
+
+    /// Write one SWIFT message (one page of the statement)
+    fn write_message<W: Write>(
+        &self,
+        writer: &mut W,
+        page: &Page<'_>,
+        options: &Mt940WriteOptions,
+    ) -> Result<(), ParseError> {
         // Write simplified MT940 format (Block 4 only with proper envelope)
         writeln!(
             writer,
             "{{1:F01BANKXXXXXX0000000000}}{{2:I940BANKXXXXXXN}}{{4:"
         )?;
-        writeln!(writer, ":20:STATEMENT")?;
-        writeln!(writer, ":25:{}", self.account_number)?;
-        writeln!(writer, ":28C:1/1")?;
-
-        // Opening balance
-        let opening_indicator_char = match self.opening_indicator {
-            BalanceType::Credit => 'C',
-            BalanceType::Debit => 'D',
+        let reference = match &options.id_generator {
+            Some(generator) => generator.generate(&IdContext {
+                kind: IdKind::Message,
+                page: page.number,
+                account_number: &self.account_number,
+                closing_date: self.closing_date,
+            }),
+            None => self
+                .info
+                .statement_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TRANSACTION_REFERENCE.to_string()),
         };
+        let reference: String = reference
+            .chars()
+            .take(TRANSACTION_REFERENCE_MAX_LENGTH)
+            .collect();
+        writeln!(writer, ":20:{}", options.charset.apply(&reference, "20")?)?;
+        writeln!(
+            writer,
+            ":25:{}",
+            options.charset.apply(&self.account_number, "25")?
+        )?;
+        // A statement written as one message keeps the page number it was received with
+        let info = &self.info;
+        let sequence_number = info
+            .sequence_number
+            .or(info.legal_sequence_number)
+            .unwrap_or(1);
+        let page_number = match info.page_number {
+            Some(number) if page.total == 1 => number as usize,
+            _ => page.number,
+        };
+        writeln!(writer, ":28C:{}/{}", sequence_number, page_number)?;
+
+        // Opening balance (:60M: marks an intermediate balance carried over from the previous page)
+        let (opening_balance, opening_indicator) = paging::unsigned_balance(page.opening_balance);
+        let opening_tag = if page.is_first() { "60F" } else { "60M" };
+        writeln!(
+            writer,
+            ":{}:{}{}{}{}",
+            opening_tag,
+            Self::format_indicator(&opening_indicator),
+            Self::format_yymmdd(&page.opening_date),
+            self.currency,
+            Self::format_amount(opening_balance)
+        )?;
+
+        Self::write_entries(writer, page.transactions, options.dialect, options.charset)?;
+
+        // Closing balance (:62M: marks an intermediate balance continued on the next page)
+        let (closing_balance, closing_indicator) = paging::unsigned_balance(page.closing_balance);
+        let closing_tag = if page.is_last() { "62F" } else { "62M" };
         writeln!(
             writer,
-            ":60F:{}{}{}{}",
-            opening_indicator_char,
-            Self::format_yymmdd(&self.opening_date),
+            ":{}:{}{}{}{}",
+            closing_tag,
+            Self::format_indicator(&closing_indicator),
+            Self::format_yymmdd(&page.closing_date),
             self.currency,
-            Self::format_amount(self.opening_balance)
+            Self::format_amount(closing_balance)
         )?;
 
-        // Transactions
-        for tx in &self.transactions {
-            let tx_indicator = match tx.transaction_type {
-                TransactionType::Credit => 'C',
-                TransactionType::Debit => 'D',
+        // Available balances describe the end of the statement; other kinds have no tag
+        if page.is_last() {
+            for balance in &self.balances {
+                let tag = match balance.code {
+                    BalanceCode::ClosingAvailable => CLOSING_AVAILABLE_TAG,
+                    BalanceCode::ForwardAvailable => FORWARD_AVAILABLE_TAG,
+                    _ => continue,
+                };
+                writeln!(
+                    writer,
+                    ":{}:{}{}{}{}",
+                    tag,
+                    Self::format_indicator(&balance.indicator),
+                    Self::format_yymmdd(&balance.date),
+                    self.currency,
+                    Self::format_amount(balance.amount)
+                )?;
+            }
+        }
+
+        writeln!(writer, "-}}")?;
+
+        Ok(())
+    }
+
+    /// Write `:61:`/`:86:` pairs for `transactions`, shared with MT942
+    pub(crate) fn write_entries<W: Write>(
+        writer: &mut W,
+        transactions: &[Transaction],
+        dialect: Mt940Dialect,
+        charset: Mt940Charset,
+    ) -> Result<(), ParseError> {
+        for tx in transactions {
+            // A reversal is marked with the direction of the entry it reverses
+            let tx_indicator = match (&tx.transaction_type, tx.is_reversal) {
+                (TransactionType::Credit, false) => "C",
+                (TransactionType::Debit, false) => "D",
+                (TransactionType::Credit, true) => "RD",
+                (TransactionType::Debit, true) => "RC",
             };
+            let swift_entry = tx.swift_entry.clone().unwrap_or_default();
 
-            writeln!(
+            write!(
                 writer,
-                ":61:{}{}{}NTRF{}",
+                ":61:{}{}{}{}{}{}",
                 Self::format_yymmdd(&tx.booking_date),
                 tx_indicator,
+                swift_entry.funds_code.map(String::from).unwrap_or_default(),
                 Self::format_amount(tx.amount),
-                tx.reference.as_ref().unwrap_or(&String::new())
+                swift_entry
+                    .transaction_code
+                    .as_deref()
+                    .unwrap_or(DEFAULT_TRANSACTION_CODE),
+                charset.apply(tx.reference.as_deref().unwrap_or_default(), "61")?
             )?;
+            if let Some(bank_reference) = &swift_entry.bank_reference {
+                write!(
+                    writer,
+                    "{}{}",
+                    BANK_REFERENCE_SEPARATOR,
+                    charset.apply(bank_reference, "61")?
+                )?;
+            }
+            writeln!(writer)?;
+            if let Some(details) = &swift_entry.supplementary_details {
+                writeln!(writer, "{}", charset.apply(details, "61")?)?;
+            }
 
-            // Description in :86: field
-            writeln!(writer, ":86:{}", tx.description)?;
+            // Description in :86: field, laid out per the selected dialect;
+            // the text is made to fit the charset first, as transliteration
+            // lengthens it and the layouts wrap it to the line length
+            let lines = dialect.field86_lines(&*charset.apply_to_transaction(tx)?);
+            let mut lines = lines.into_iter();
+            let first = lines.next().unwrap_or_default();
+            writeln!(writer, ":86:{}", first)?;
+            for line in lines {
+                writeln!(writer, "{}", line)?;
+            }
         }
 
-        // Closing balance
-        let closing_indicator_char = match self.closing_indicator {
-            BalanceType::Credit => 'C',
-            BalanceType::Debit => 'D',
-        };
-        writeln!(
-            writer,
-            ":62F:{}{}{}{}",
-            closing_indicator_char,
-            Self::format_yymmdd(&self.closing_date),
-            self.currency,
-            Self::format_amount(self.closing_balance)
-        )?;
+        Ok(())
+    }
 
-        writeln!(writer, "-}}")?;
+    /// Split content into its SWIFT messages, each ending with its block 4;
+    /// content without blocks is a single message
+    fn split_messages(content: &str) -> Vec<(usize, &str)> {
+        let mut messages = Vec::new();
+        let mut rest = content;
+        let mut line = 1;
+        while let Some(start) = rest.find("{4:") {
+            let body = start + 3;
+            // The message keeps its end marker, which `extract_block4` looks for
+            let end = match rest[body..].find("-}") {
+                Some(end) => body + end + 2,
+                None => rest[body..]
+                    .find('}')
+                    .map_or(rest.len(), |end| body + end + 1),
+            };
+            messages.push((line, &rest[..end]));
+            line += rest[..end].matches('\n').count();
+            rest = &rest[end..];
+        }
+        if messages.is_empty() && !content.trim().is_empty() {
+            messages.push((1, content));
+        }
+        messages
+    }
 
-        Ok(())
+    /// Split the tags of a message into statements, each starting at a `:20:` tag
+    fn split_sequences(tags: &[Mt940Field]) -> Vec<&[Mt940Field]> {
+        let mut sequences = Vec::new();
+        let mut start = 0;
+        for (index, field) in tags.iter().enumerate() {
+            if field.tag == "20" && index > start {
+                sequences.push(&tags[start..index]);
+                start = index;
+            }
+        }
+        if start < tags.len() {
+            sequences.push(&tags[start..]);
+        }
+        sequences
     }
 
     /// Extract Block 4 from MT940 content
-    fn extract_block4(content: &str) -> Result<String, ParseError> {
+    pub(crate) fn extract_block4(content: &str) -> Result<String, ParseError> {
         // Look for {4: ... -} or {4: ... }
         if let Some(start) = content.find("{4:") {
             let after_start = &content[start + 3..];
@@ -180,89 +768,118 @@ impl Mt940Statement {
         Ok(content.into())
     }
 
-    /// Parse tags from Block 4 content
-    fn parse_tags(block4: &str) -> Result<Vec<(String, String)>, ParseError> {
-        let mut tags = Vec::new();
-        let lines: Vec<&str> = block4.lines().collect();
-        let mut i = 0;
+    /// Parse the tags of the Block 4 of `message`, which starts on line `first_line`
+    ///
+    /// Uses the public [`Mt940Tags`] tokenizer and normalizes continuation line
+    /// breaks to `\n` so downstream extraction does not depend on the input's line endings.
+    pub(crate) fn parse_tags(message: &str, first_line: usize) -> Vec<Mt940Field> {
+        let mut tokens = Mt940Tags::parse(message);
+        let mut fields = Vec::new();
+        while let Some((tag, value)) = tokens.next() {
+            fields.push(Mt940Field {
+                tag: tag.as_str().into(),
+                value: value.lines().collect::<Vec<_>>().join("\n"),
+                line: first_line + tokens.line() - 1,
+            });
+        }
+        fields
+    }
 
-        while i < lines.len() {
-            let line = lines[i].trim();
+    /// Extract account number from :25: tag
+    pub(crate) fn extract_account_number(tags: &[Mt940Field]) -> Result<String, ParseError> {
+        tags.iter()
+            .find(|field| field.tag == "25")
+            .map(|field| field.value.trim().into())
+            .ok_or_else(|| ParseError::Mt940Error("Missing :25: account tag".into()))
+    }
 
-            // Skip empty lines
-            if line.is_empty() {
-                i += 1;
-                continue;
+    /// Statement id from `:20:` and statement/sequence number from `:28C:` (`5n[/5n]`)
+    fn extract_statement_info(tags: &[Mt940Field]) -> StatementInfo {
+        let value = |name: &str| {
+            tags.iter()
+                .find(|field| field.tag == name)
+                .map(|field| field.value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let (sequence_number, page_number) = match value("28C") {
+            Some(number) => {
+                let (statement, page) = match number.split_once('/') {
+                    Some((statement, page)) => (statement, Some(page)),
+                    None => (number, None),
+                };
+                (
+                    statement.parse().ok(),
+                    page.and_then(|page| page.parse().ok()),
+                )
             }
+            None => (None, None),
+        };
 
-            // Check if line starts with tag (colon followed by digits/letters and colon)
-            if let Some(stripped) = line.strip_prefix(':') {
-                if let Some(second_colon) = stripped.find(':') {
-                    let tag = &stripped[..second_colon];
-                    let value = &stripped[second_colon + 1..];
-
-                    // Collect multi-line values (lines without leading colon are continuations)
-                    let mut full_value: String = value.into();
-                    i += 1;
-
-                    while i < lines.len() {
-                        let next_line = lines[i];
-                        if next_line.trim().starts_with(':') {
-                            break;
-                        }
-                        full_value.push('\n');
-                        full_value.push_str(next_line);
-                        i += 1;
-                    }
+        StatementInfo {
+            statement_id: value("20").map(String::from),
+            sequence_number,
+            page_number,
+            ..Default::default()
+        }
+    }
 
-                    tags.push((tag.into(), full_value));
-                    continue;
+    /// Extract closing available (`:64:`) and forward available (`:65:`) balances
+    fn extract_available_balances(
+        tags: &[Mt940Field],
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<Balance>, ParseError> {
+        let mut balances = Vec::new();
+        for field in tags {
+            let code = match field.tag.as_str() {
+                CLOSING_AVAILABLE_TAG => BalanceCode::ClosingAvailable,
+                FORWARD_AVAILABLE_TAG => BalanceCode::ForwardAvailable,
+                _ => continue,
+            };
+            match field.parse(Self::parse_balance_line) {
+                Ok((amount, date, indicator, _)) => {
+                    diagnostics.parsed(1);
+                    balances.push(Balance {
+                        code,
+                        amount,
+                        indicator,
+                        date,
+                    })
                 }
+                Err(error) => diagnostics.skip(error)?,
             }
-
-            i += 1;
         }
-
-        Ok(tags)
-    }
-
-    /// Extract account number from :25: tag
-    fn extract_account_number(tags: &[(String, String)]) -> Result<String, ParseError> {
-        tags.iter()
-            .find(|(tag, _)| tag == "25")
-            .map(|(_, value)| value.trim().into())
-            .ok_or_else(|| ParseError::Mt940Error("Missing :25: account tag".into()))
+        Ok(balances)
     }
 
     /// Extract opening balance from :60F: or :60M: tag
     fn extract_opening_balance(
-        tags: &[(String, String)],
+        tags: &[Mt940Field],
     ) -> Result<(f64, DateTime<FixedOffset>, BalanceType, String), ParseError> {
         let balance_tag = tags
             .iter()
-            .find(|(tag, _)| tag == "60F" || tag == "60M")
+            .find(|field| field.tag == "60F" || field.tag == "60M")
             .ok_or_else(|| ParseError::Mt940Error("Missing :60F: or :60M: tag".into()))?;
 
-        Self::parse_balance_line(&balance_tag.1)
+        balance_tag.parse(Self::parse_balance_line)
     }
 
     /// Extract closing balance from :62F: or :62M: tag
     fn extract_closing_balance(
-        tags: &[(String, String)],
+        tags: &[Mt940Field],
         _currency: &str,
     ) -> Result<(f64, DateTime<FixedOffset>, BalanceType), ParseError> {
         let balance_tag = tags
             .iter()
-            .find(|(tag, _)| tag == "62F" || tag == "62M")
+            .find(|field| field.tag == "62F" || field.tag == "62M")
             .ok_or_else(|| ParseError::Mt940Error("Missing :62F: or :62M: tag".into()))?;
 
-        let (amount, date, indicator, _) = Self::parse_balance_line(&balance_tag.1)?;
+        let (amount, date, indicator, _) = balance_tag.parse(Self::parse_balance_line)?;
         Ok((amount, date, indicator))
     }
 
     /// Parse balance line format: C/D + YYMMDD + CCY + amount
     /// Example: C200101EUR444,29
-    fn parse_balance_line(
+    pub(crate) fn parse_balance_line(
         line: &str,
     ) -> Result<(f64, DateTime<FixedOffset>, BalanceType, String), ParseError> {
         let line = line.trim();
@@ -303,27 +920,32 @@ impl Mt940Statement {
         Ok((amount, date, indicator, currency))
     }
 
-    /// Extract transactions from :61: and :86: tag pairs
-    fn extract_transactions(
-        tags: &[(String, String)],
-        _currency: &str,
+    /// Extract transactions from :61: and :86: tag pairs; malformed `:61:`
+    /// entries go to `diagnostics`
+    pub(crate) fn extract_transactions(
+        tags: &[Mt940Field],
+        quirks: &[Quirk],
+        diagnostics: &mut Diagnostics,
     ) -> Result<Vec<Transaction>, ParseError> {
         let mut transactions = Vec::new();
         let mut i = 0;
 
         while i < tags.len() {
-            if tags[i].0 == "61" {
-                let transaction_line = &tags[i].1;
-
+            if tags[i].tag == "61" {
                 // Look for following :86: tag (description)
-                let description = if i + 1 < tags.len() && tags[i + 1].0 == "86" {
-                    tags[i + 1].1.trim().into()
+                let description = if i + 1 < tags.len() && tags[i + 1].tag == "86" {
+                    tags[i + 1].value.trim().into()
                 } else {
                     String::new()
                 };
 
-                if let Ok(tx) = Self::parse_transaction_line(transaction_line, &description) {
-                    transactions.push(tx);
+                match tags[i].parse(|line| Self::parse_transaction_line(line, &description, quirks))
+                {
+                    Ok(tx) => {
+                        diagnostics.parsed(1);
+                        transactions.push(tx)
+                    }
+                    Err(error) => diagnostics.skip(error)?,
                 }
             }
             i += 1;
@@ -333,9 +955,14 @@ impl Mt940Statement {
     }
 
     /// Parse transaction line (:61:)
-    /// Format: YYMMDD[MMDD]C/D[amount][type][reference]
+    /// Format: YYMMDD[MMDD]C|D|RC|RD[funds code][amount][type][reference][//bank reference]
+    /// with supplementary details on a second line
     /// Example: 2001010101D65,00NOVBNL47INGB9999999999
-    fn parse_transaction_line(line: &str, description: &str) -> Result<Transaction, ParseError> {
+    pub(crate) fn parse_transaction_line(
+        line: &str,
+        description: &str,
+        quirks: &[Quirk],
+    ) -> Result<Transaction, ParseError> {
         let line = line.trim();
 
         if line.is_empty() {
@@ -348,25 +975,34 @@ impl Mt940Statement {
         }
 
         let date_str = &line[..6];
-        let booking_date = Self::parse_yymmdd_date(date_str)?;
+        let mut booking_date = Self::parse_yymmdd_date(date_str)?;
+        let mut value_date = None;
 
         let mut rest = &line[6..];
 
-        // Optional booking date (MMDD) - skip if present
+        // Optional entry date (MMDD) - only used by banks that post on it
         if rest.len() >= 4 && rest[..4].chars().all(|c| c.is_ascii_digit()) {
+            if quirks.contains(&Quirk::GoldmanSachsEntryDate) {
+                value_date = Some(booking_date.date_naive());
+                booking_date = Self::parse_entry_date(&rest[..4], booking_date)?;
+            }
             rest = &rest[4..];
         }
 
-        // Next char is C or D
+        // Next char is C or D, or RC/RD for the reversal of a credit/debit
         if rest.is_empty() {
             return Err(ParseError::Mt940Error(
                 "Missing transaction indicator".into(),
             ));
         }
 
-        let transaction_type = match rest.chars().next() {
-            Some('C') => TransactionType::Credit,
-            Some('D') => TransactionType::Debit,
+        let is_reversal = rest.starts_with("RC") || rest.starts_with("RD");
+        if is_reversal {
+            rest = &rest[1..];
+        }
+        let transaction_type = match (rest.chars().next(), is_reversal) {
+            (Some('C'), false) | (Some('D'), true) => TransactionType::Credit,
+            (Some('D'), false) | (Some('C'), true) => TransactionType::Debit,
             _ => {
                 return Err(ParseError::Mt940Error(
                     "Invalid transaction indicator".into(),
@@ -376,6 +1012,12 @@ impl Mt940Statement {
 
         rest = &rest[1..];
 
+        // Optional funds code, the third letter of the currency code
+        let funds_code = rest.chars().next().filter(char::is_ascii_alphabetic);
+        if funds_code.is_some() {
+            rest = &rest[1..];
+        }
+
         // Parse amount (find first non-digit, non-comma, non-dot char)
         let amount_end = rest
             .find(|c: char| !c.is_ascii_digit() && c != ',' && c != '.')
@@ -390,102 +1032,157 @@ impl Mt940Statement {
         let amount_str = &rest[..amount_end];
         let amount = Self::parse_amount(amount_str)?;
 
-        // Rest is transaction type code and reference (variable format)
-        let reference = if amount_end < rest.len() {
-            Some(rest[amount_end..].trim().into())
-        } else {
-            None
-        };
+        // Rest is the transaction type code and the account owner's reference
+        let reference = Self::parse_owner_reference(&rest[amount_end..]);
+        let swift_entry = Self::parse_swift_entry(funds_code, &rest[amount_end..]);
+        let (counterparty_name, counterparty_account) = mt940_dialect::counterparty(description);
+        let (original_currency, original_amount, exchange_rate) =
+            mt940_dialect::foreign_amount(description);
+        let (counterparty_bic, counterparty_address) =
+            mt940_dialect::counterparty_agent(description);
+        let (end_to_end_id, mandate_id) = mt940_dialect::sepa_ids(description);
+        let charges = mt940_dialect::charges(description);
+        // Structured fields keep the remittance text apart from the keywords
+        let description = mt940_dialect::remittance_text(description)
+            .or_else(|| mt940_dialect::unread_keywords(description))
+            .unwrap_or_else(|| mt940_dialect::join_narrative(description));
+        // The end-to-end ID stands in for a missing owner's reference
+        let reference = reference.or_else(|| end_to_end_id.clone());
 
         Ok(Transaction {
-            booking_date,
-            value_date: None,
-            amount,
-            transaction_type,
-            description: description.into(),
+            value_date,
             reference,
-            counterparty_name: None,
-            counterparty_account: None,
+            counterparty_name,
+            counterparty_account,
+            original_amount,
+            original_currency,
+            exchange_rate,
+            counterparty_bic,
+            counterparty_address,
+            end_to_end_id,
+            mandate_id,
+            is_reversal,
+            charges,
+            swift_entry,
+            ..Transaction::new(booking_date, amount, transaction_type, description)
         })
     }
 
-    /// Parse YYMMDD date with century inference
-    /// 00-49 → 2000-2049, 50-99 → 1950-1999
-    fn parse_yymmdd_date(date_str: &str) -> Result<DateTime<FixedOffset>, ParseError> {
-        if date_str.len() != 6 || !date_str.chars().all(|c| c.is_ascii_digit()) {
-            return Err(ParseError::Mt940Error(format!(
-                "Expected YYMMDD date, found '{}'",
-                date_str
-            )));
-        }
-
-        let year_part = &date_str[..2];
-        let month_part = &date_str[2..4];
-        let day_part = &date_str[4..];
-
-        let yy: i32 = year_part.parse().map_err(|_| {
-            ParseError::Mt940Error(format!(
-                "Invalid year component in '{}': {}",
-                date_str, year_part
-            ))
-        })?;
-        let mm: u32 = month_part.parse().map_err(|_| {
-            ParseError::Mt940Error(format!(
-                "Invalid month component in '{}': {}",
-                date_str, month_part
-            ))
-        })?;
-        let dd: u32 = day_part.parse().map_err(|_| {
-            ParseError::Mt940Error(format!(
-                "Invalid day component in '{}': {}",
-                date_str, day_part
-            ))
-        })?;
-
-        let year = match yy {
-            0..=49 => 2000 + yy,
-            50..=99 => 1900 + yy,
-            _ => {
-                return Err(ParseError::Mt940Error(format!(
-                    "Year component must be two digits in '{}': {}",
-                    date_str, year_part
-                )))
-            }
+    /// Reference for the account owner from the tail of a `:61:` line.
+    ///
+    /// Strips the transaction type identification code (`N`/`F`/`S` plus three
+    /// characters, e.g. `NTRF`) and the `//` bank reference; `NONREF` means none.
+    fn parse_owner_reference(tail: &str) -> Option<String> {
+        let (_, references) = Self::split_type_code(tail);
+        let reference = references
+            .split(BANK_REFERENCE_SEPARATOR)
+            .next()
+            .unwrap_or_default()
+            .trim();
+        if reference.is_empty() || reference == NO_REFERENCE {
+            None
+        } else {
+            Some(reference.to_string())
+        }
+    }
+
+    /// Codes and bank references of a `:61:` line from its `funds_code` and the
+    /// tail after the amount; `None` when the line has none of them
+    fn parse_swift_entry(funds_code: Option<char>, tail: &str) -> Option<SwiftEntry> {
+        let (transaction_code, references) = Self::split_type_code(tail);
+        let bank_reference = references
+            .split_once(BANK_REFERENCE_SEPARATOR)
+            .map(|(_, bank_reference)| bank_reference.trim())
+            .filter(|bank_reference| !bank_reference.is_empty())
+            .map(str::to_string);
+        let supplementary_details = tail
+            .trim()
+            .lines()
+            .nth(1)
+            .map(str::trim)
+            .filter(|details| !details.is_empty())
+            .map(str::to_string);
+
+        let entry = SwiftEntry {
+            funds_code,
+            transaction_code: transaction_code.map(str::to_string),
+            bank_reference,
+            supplementary_details,
         };
+        (entry != SwiftEntry::default()).then_some(entry)
+    }
 
-        let date = NaiveDate::from_ymd_opt(year, mm, dd).ok_or_else(|| {
-            ParseError::Mt940Error(format!(
-                "Invalid calendar date derived from '{}': {:04}-{:02}-{:02}",
-                date_str, year, mm, dd
-            ))
-        })?;
+    /// Transaction type identification code (`N`/`F`/`S` plus three characters,
+    /// e.g. `NTRF`) of the tail of a `:61:` line, and the references on the rest
+    /// of its first line
+    fn split_type_code(tail: &str) -> (Option<&str>, &str) {
+        let first_line = tail.trim().lines().next().unwrap_or_default().trim();
+        let mut chars = first_line.chars();
+        let has_type_code = matches!(chars.next(), Some('N' | 'F' | 'S'))
+            && chars.take(3).filter(|c| c.is_ascii_alphanumeric()).count() == 3;
+        if has_type_code {
+            (Some(&first_line[..4]), &first_line[4..])
+        } else {
+            (None, first_line)
+        }
+    }
 
-        let datetime = date.and_hms_opt(0, 0, 0).ok_or_else(|| {
-            ParseError::Mt940Error(format!(
-                "Invalid time component derived from '{}': midnight",
-                date_str
-            ))
-        })?;
+    /// Parse YYMMDD date with century inference
+    /// 00-49 → 2000-2049, 50-99 → 1950-1999
+    pub(crate) fn parse_yymmdd_date(date_str: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+        text::parse_yymmdd(date_str)
+    }
+
+    /// Parse the `MMDD` entry date of a `:61:` line in the year of `value_date`.
+    ///
+    /// Entries around New Year may be posted in the neighbouring year, so a
+    /// December entry for a January value date (and vice versa) moves the year.
+    fn parse_entry_date(
+        entry_date: &str,
+        value_date: DateTime<FixedOffset>,
+    ) -> Result<DateTime<FixedOffset>, ParseError> {
+        let invalid = || ParseError::UnexpectedValue {
+            field: "entry date".into(),
+            expected: "an MMDD date".into(),
+            found: entry_date.into(),
+        };
+        let month: u32 = entry_date[..2].parse().map_err(|_| invalid())?;
+        let day: u32 = entry_date[2..].parse().map_err(|_| invalid())?;
+        let year = match (value_date.month(), month) {
+            (1, 12) => value_date.year() - 1,
+            (12, 1) => value_date.year() + 1,
+            _ => value_date.year(),
+        };
 
+        let date = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(invalid)?;
         Ok(DateTime::<FixedOffset>::from_naive_utc_and_offset(
-            datetime,
+            date,
             Utc.fix(),
         ))
     }
 
     /// Parse amount (handle both comma and dot as decimal separator)
-    fn parse_amount(amount_str: &str) -> Result<f64, ParseError> {
-        utils::parse_amount(amount_str)
-            .map_err(|_| ParseError::Mt940Error(format!("Invalid amount: {}", amount_str)))
+    pub(crate) fn parse_amount(amount_str: &str) -> Result<f64, ParseError> {
+        text::parse_amount(amount_str)
+    }
+
+    /// Format balance indicator as C/D
+    fn format_indicator(indicator: &BalanceType) -> char {
+        match indicator {
+            BalanceType::Credit => 'C',
+            BalanceType::Debit => 'D',
+        }
     }
 
     /// Format date as YYMMDD
-    fn format_yymmdd(date: &DateTime<FixedOffset>) -> String {
+    pub(crate) fn format_yymmdd(date: &DateTime<FixedOffset>) -> String {
         date.format("%y%m%d").to_string()
     }
 
     /// Format amount with comma as decimal separator
-    fn format_amount(amount: f64) -> String {
+    pub(crate) fn format_amount(amount: f64) -> String {
         format!("{:.2}", amount).replace('.', ",")
     }
 }
@@ -493,6 +1190,7 @@ impl Mt940Statement {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ErrorLocation;
 
     #[test]
     fn test_parse_yymmdd_date() {
@@ -516,10 +1214,43 @@ mod tests {
         assert_eq!(result.format("%Y-%m-%d").to_string(), "2023-01-01");
     }
 
+    #[test]
+    fn test_malformed_entries_warn_or_fail() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102C50,00NTRFNONREF\n:61:200132D10,00NTRFNONREF\n\
+                    :62F:C200102EUR150,00\n:64:C200102EURx\n";
+
+        let (statement, warnings) =
+            Mt940Statement::from_read_with_options(&mut data.as_bytes(), &ParseOptions::default())
+                .unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert!(statement.balances.is_empty());
+        let located: Vec<_> = warnings
+            .iter()
+            .map(|warning| match &warning.location {
+                Some(ErrorLocation::Line { line, field, .. }) => (*line, field.clone()),
+                other => panic!("unexpected location {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            located,
+            vec![(5, Some("61".to_string())), (7, Some("64".to_string()))]
+        );
+
+        let strict =
+            Mt940Statement::from_read_with_options(&mut data.as_bytes(), &ParseOptions::strict());
+        assert!(matches!(
+            strict.unwrap_err().location(),
+            Some(ErrorLocation::Line { line: 5, .. })
+        ));
+    }
+
     #[test]
     fn test_parse_yymmdd_date_invalid_input() {
         let result = Mt940Statement::parse_yymmdd_date("2A0101");
-        assert!(matches!(result, Err(ParseError::Mt940Error(_))));
+        assert!(
+            matches!(result, Err(ParseError::UnexpectedValue { found, .. }) if found == "2A0101")
+        );
     }
 
     #[test]
@@ -570,6 +1301,7 @@ mod tests {
         let result = Mt940Statement::parse_transaction_line(
             "2001010101D65,00NOVBNL47INGB9999999999",
             "Betaling sieraden",
+            &[],
         );
         assert!(result.is_ok());
         let tx = result.unwrap();
@@ -579,6 +1311,110 @@ mod tests {
         assert_eq!(tx.booking_date.format("%Y-%m-%d").to_string(), "2020-01-01");
     }
 
+    #[test]
+    fn test_goldman_sachs_entry_date_quirk() {
+        let line = "2412310102D12,01NTRFGSLNVSHSUTKWDR//GI2504900007841";
+        let quirks = [Quirk::GoldmanSachsEntryDate];
+
+        let tx = Mt940Statement::parse_transaction_line(line, "", &quirks).unwrap();
+        assert_eq!(tx.booking_date.format("%Y-%m-%d").to_string(), "2025-01-02");
+        assert_eq!(tx.value_date, NaiveDate::from_ymd_opt(2024, 12, 31));
+
+        let tx = Mt940Statement::parse_transaction_line(line, "", &[]).unwrap();
+        assert_eq!(tx.booking_date.format("%Y-%m-%d").to_string(), "2024-12-31");
+        assert_eq!(tx.value_date, None);
+    }
+
+    #[test]
+    fn test_parse_all_messages_and_sequences() {
+        use std::path::PathBuf;
+
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../example_files/MT940 github 1.mt940");
+        let content = std::fs::read_to_string(&path).unwrap();
+        let statements = Mt940Statement::parse_all(&mut content.as_bytes()).unwrap();
+        assert_eq!(statements.len(), 31);
+        assert_eq!(
+            statements[0],
+            Mt940Statement::from_read(&mut content.as_bytes()).unwrap()
+        );
+
+        // Bare tag files separate statements by :20: only
+        let bare = ":20:A\n:25:ACC\n:60F:C200101EUR1,00\n:62F:C200101EUR1,00\n\
+                    :20:B\n:25:ACC\n:60F:C200102EUR1,00\n:61:200102D1,00NTRFNONREF\n:62F:C200102EUR0,00\n";
+        let statements = Mt940Statement::parse_all(&mut bare.as_bytes()).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1].transactions.len(), 1);
+        assert!(Mt940Statement::parse_all(&mut "".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_peek_header_matches_full_parse() {
+        use std::path::PathBuf;
+
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../example_files/mt 940 gs.mt940");
+        let statement = Mt940Statement::from_path(&path).unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        let header = Mt940Statement::peek_header(&mut file).unwrap();
+
+        assert_eq!(header.account_number, statement.account_number);
+        assert_eq!(header.currency, statement.currency);
+        assert_eq!(header.opening_balance, statement.opening_balance);
+        assert_eq!(header.closing_balance, statement.closing_balance);
+        assert_eq!(header.closing_date, statement.closing_date);
+        assert_eq!(header.closing_indicator, statement.closing_indicator);
+    }
+
+    #[test]
+    fn test_swift_entry_round_trip() {
+        let line = "2501150115RDR1250,00NCHK1234567890//BNK-77\nCHEQUE 000123";
+        let tx = Mt940Statement::parse_transaction_line(line, "Cheque", &[]).unwrap();
+        assert_eq!(tx.transaction_type, TransactionType::Credit);
+        assert!(tx.is_reversal);
+        assert_eq!(tx.amount, 1250.0);
+        assert_eq!(tx.reference.as_deref(), Some("1234567890"));
+        assert_eq!(
+            tx.swift_entry,
+            Some(SwiftEntry {
+                funds_code: Some('R'),
+                transaction_code: Some("NCHK".into()),
+                bank_reference: Some("BNK-77".into()),
+                supplementary_details: Some("CHEQUE 000123".into()),
+            })
+        );
+
+        let mut output = Vec::new();
+        Mt940Statement::write_entries(
+            &mut output,
+            &[tx],
+            Mt940Dialect::Standard,
+            Mt940Charset::Keep,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ":61:250115RDR1250,00NCHK1234567890//BNK-77\nCHEQUE 000123\n:86:Cheque\n"
+        );
+
+        let tx = Mt940Statement::parse_transaction_line("250115C5,00", "", &[]).unwrap();
+        assert_eq!(tx.swift_entry, None);
+    }
+
+    #[test]
+    fn test_parse_owner_reference() {
+        assert_eq!(
+            Mt940Statement::parse_owner_reference("NOVBNL47INGB9999999999").as_deref(),
+            Some("NL47INGB9999999999")
+        );
+        assert_eq!(
+            Mt940Statement::parse_owner_reference("NTRFINV-17//BANKREF1").as_deref(),
+            Some("INV-17")
+        );
+        assert_eq!(Mt940Statement::parse_owner_reference("NTRFNONREF"), None);
+        assert_eq!(Mt940Statement::parse_owner_reference("NTRF"), None);
+    }
+
     #[test]
     fn test_parse_empty_mt940() {
         let input = "";
@@ -626,6 +1462,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_real_mt940_gs_keywords_end_at_bank_keywords() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../example_files/mt 940 gs.mt940");
+        let Ok(statement) = Mt940Statement::from_path(&path) else {
+            return;
+        };
+
+        let descriptions: Vec<&str> = statement
+            .transactions
+            .iter()
+            .map(|tx| tx.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "USD Payment to Vendor",
+                "The maximum length of the block is 65 characters",
+                "USD Payment from USD account",
+                "/DACT/8348577826/DBIC/CITIUS30XXX/OAMT/11-25/DCID/CPQYTB74",
+            ]
+        );
+        assert_eq!(
+            statement.transactions[3].end_to_end_id.as_deref(),
+            Some("GS0DUTB31IOUHRS")
+        );
+    }
+
     #[test]
     fn test_parse_real_mt940_asn() {
         use std::fs::File;
@@ -654,6 +1518,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_path_matches_from_read() {
+        use std::fs::File;
+        use std::path::PathBuf;
+
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../example_files/mt 940 gs.mt940");
+
+        if let Ok(mut file) = File::open(&path) {
+            let from_read = Mt940Statement::from_read(&mut file).unwrap();
+            let from_path = Mt940Statement::from_path(&path).unwrap();
+            assert_eq!(from_read, from_path);
+        }
+    }
+
+    #[test]
+    fn test_from_path_missing_file() {
+        let result = Mt940Statement::from_path("does/not/exist.mt940");
+        assert!(matches!(result, Err(ParseError::IoError(_))));
+    }
+
+    #[test]
+    fn test_mt940_write_paginated() {
+        let tx = |day: &str, amount: f64, transaction_type: TransactionType| {
+            Transaction::new(
+                Mt940Statement::parse_yymmdd_date(day).unwrap(),
+                amount,
+                transaction_type,
+                "Payment",
+            )
+        };
+        let statement = Mt940Statement {
+            account_number: "NL81ASNB9999999999".into(),
+            currency: "EUR".into(),
+            opening_balance: 100.0,
+            opening_date: Mt940Statement::parse_yymmdd_date("200101").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 50.0,
+            closing_date: Mt940Statement::parse_yymmdd_date("200105").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                tx("200102", 20.0, TransactionType::Credit),
+                tx("200103", 150.0, TransactionType::Debit),
+                tx("200104", 80.0, TransactionType::Credit),
+            ],
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        };
+
+        let options = Mt940WriteOptions {
+            max_transactions_per_message: Some(2),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let output_str = String::from_utf8(output).unwrap();
+
+        assert_eq!(output_str.matches("{4:").count(), 2);
+        assert!(output_str.contains(":28C:1/1"));
+        assert!(output_str.contains(":28C:1/2"));
+        assert!(output_str.contains(":60F:C200101EUR100,00"));
+        assert!(output_str.contains(":62M:D200103EUR30,00"));
+        assert!(output_str.contains(":60M:D200103EUR30,00"));
+        assert!(output_str.contains(":62F:C200105EUR50,00"));
+        assert_eq!(output_str.matches(":20:STATEMENT").count(), 2);
+
+        // The pages read back as the statement they were split from
+        for parsed in [
+            Mt940Statement::from_read(&mut output_str.as_bytes()).unwrap(),
+            Mt940Statement::parse_all(&mut output_str.as_bytes())
+                .unwrap()
+                .remove(0),
+        ] {
+            assert_eq!(parsed.transactions.len(), 3);
+            assert_eq!(parsed.opening_balance, statement.opening_balance);
+            assert_eq!(
+                (parsed.closing_balance, parsed.closing_date),
+                (statement.closing_balance, statement.closing_date)
+            );
+            assert_eq!(parsed.info.page_number, None);
+        }
+        assert_eq!(
+            Mt940Statement::parse_all(&mut output_str.as_bytes())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Every message gets its own reference from the generator
+        let options = Mt940WriteOptions {
+            id_generator: Some(SharedIdGenerator::new(crate::SequentialIds::new(
+                "ACME-STMT-",
+                7,
+            ))),
+            ..options
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains(":20:ACME-STMT-000007\n"));
+        assert!(output_str.contains(":20:ACME-STMT-000008\n"));
+
+        let parts = statement.split_by_transaction_count(2);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].closing_balance, 30.0);
+        assert_eq!(parts[0].closing_indicator, BalanceType::Debit);
+        assert_eq!(parts[1].opening_balance, 30.0);
+        assert_eq!(parts[1].transactions.len(), 1);
+    }
+
     #[test]
     fn test_mt940_write() {
         let statement = Mt940Statement {
@@ -666,6 +1640,8 @@ mod tests {
             closing_date: Mt940Statement::parse_yymmdd_date("200101").unwrap(),
             closing_indicator: BalanceType::Credit,
             transactions: vec![],
+            info: StatementInfo::default(),
+            balances: Vec::new(),
         };
 
         let mut output = Vec::new();
@@ -677,4 +1653,160 @@ mod tests {
         assert!(output_str.contains(":60F:C200101EUR444,29"));
         assert!(output_str.contains(":62F:C200101EUR379,29"));
     }
+
+    #[test]
+    fn test_mt940_write_wraps_long_descriptions() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+        let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        let description = "Оплата по счёту 17 за обслуживание офиса и кофемашины в марте 2025 года";
+        statement.transactions[0].description = description.into();
+
+        let mut output = Vec::new();
+        statement.write_to(&mut output).unwrap();
+        let read_back = Mt940Statement::from_read(&mut output.as_slice()).unwrap();
+        assert_eq!(read_back.transactions[0].description, description);
+
+        let options = Mt940WriteOptions {
+            charset: Mt940Charset::Transliterate,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        statement.write_to_with(&mut output, &options).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.lines().all(|line| line.chars().count() <= 65 + 4));
+        assert!(text.contains("v marte\n 2025 goda\n"));
+    }
+
+    #[test]
+    fn test_long_field86_is_reported_truncated_in_every_dialect() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+        let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        let tx = &mut statement.transactions[0];
+        tx.description = "Invoice 2025-17 maintenance of the plant ".repeat(12);
+        tx.counterparty_name = Some("ACME Corp".into());
+        tx.counterparty_account = Some("DE89370400440532013000".into());
+
+        for dialect in [
+            Mt940Dialect::Standard,
+            Mt940Dialect::German,
+            Mt940Dialect::Dutch,
+            Mt940Dialect::Abn,
+            Mt940Dialect::Swift,
+        ] {
+            let options = Mt940WriteOptions {
+                dialect,
+                ..Default::default()
+            };
+            let mut output = Vec::new();
+            statement.write_to_with(&mut output, &options).unwrap();
+            let text = String::from_utf8(output.clone()).unwrap();
+            let field86 = &text[text.find(":86:").unwrap() + 4..text.find(":62F:").unwrap()];
+            assert!(field86.lines().count() <= mt940_dialect::MT940_NARRATIVE_LINES);
+
+            let read_back = Mt940Statement::from_read(&mut output.as_slice()).unwrap();
+            let report = crate::ConversionReport::compare(statement.as_view(), read_back.as_view());
+            let losses: Vec<_> = report.losses.iter().map(ToString::to_string).collect();
+            assert!(
+                losses.contains(&"description truncated from 1 transaction".to_string()),
+                "{}: {:?}",
+                dialect,
+                losses
+            );
+            if dialect != Mt940Dialect::Standard {
+                assert!(
+                    !losses.iter().any(|loss| loss.starts_with("counterparty")),
+                    "{}: {:?}",
+                    dialect,
+                    losses
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_field86_lines_never_open_a_field_in_every_dialect() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+        let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+
+        // Every padding puts a line boundary before `:62F:` or `-5,00` in some layout
+        for padding in 0..mt940_dialect::MT940_LINE_LENGTH {
+            let description = format!(
+                "Refund {} :62F:C200101EUR5,00 -5,00 correction",
+                "x".repeat(padding)
+            );
+            for dialect in [
+                Mt940Dialect::Standard,
+                Mt940Dialect::Dutch,
+                Mt940Dialect::Abn,
+                Mt940Dialect::Swift,
+            ] {
+                statement.transactions[0].description = description.clone();
+                let options = Mt940WriteOptions {
+                    dialect,
+                    ..Default::default()
+                };
+                let mut output = Vec::new();
+                statement.write_to_with(&mut output, &options).unwrap();
+
+                let read_back = Mt940Statement::from_read(&mut output.as_slice()).unwrap();
+                assert_eq!(
+                    read_back.transactions[0].description, description,
+                    "{} with {} padding",
+                    dialect, padding
+                );
+                assert_eq!(read_back.closing_balance, 75.0);
+            }
+        }
+
+        // A line of the description that would open a field joins the one before
+        statement.transactions[0].description = "Refund\n\n:62F:C200101EUR5,00".into();
+        let mut output = Vec::new();
+        statement.write_to(&mut output).unwrap();
+        let read_back = Mt940Statement::from_read(&mut output.as_slice()).unwrap();
+        assert_eq!(
+            read_back.transactions[0].description,
+            "Refund :62F:C200101EUR5,00"
+        );
+    }
+
+    #[test]
+    fn test_transliterated_field86_fits_line_length_in_every_dialect() {
+        let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                    :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+        let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+        let tx = &mut statement.transactions[0];
+        tx.description = "Überweisung für Müller & Söhne, Größenänderung Straßenschäden".into();
+        tx.counterparty_name = Some("Jürgen Weiß Straßenbau und Gärtnerei".into());
+        tx.counterparty_account = Some("DE89370400440532013000".into());
+
+        for dialect in [
+            Mt940Dialect::Standard,
+            Mt940Dialect::German,
+            Mt940Dialect::Dutch,
+            Mt940Dialect::Abn,
+            Mt940Dialect::Swift,
+        ] {
+            let options = Mt940WriteOptions {
+                dialect,
+                charset: Mt940Charset::Transliterate,
+                ..Default::default()
+            };
+            let mut output = Vec::new();
+            statement.write_to_with(&mut output, &options).unwrap();
+            let text = String::from_utf8(output).unwrap();
+            let field86 = &text[text.find(":86:").unwrap() + 4..text.find(":62F:").unwrap()];
+            assert!(field86.is_ascii(), "{}", dialect);
+            assert!(
+                field86
+                    .lines()
+                    .all(|line| line.chars().count() <= mt940_dialect::MT940_LINE_LENGTH),
+                "{}: {}",
+                dialect,
+                field86
+            );
+        }
+    }
 }
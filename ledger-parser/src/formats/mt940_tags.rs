@@ -0,0 +1,255 @@
+use std::fmt;
+
+/// A single MT940 field tag such as `20`, `25`, `60F` or `61`.
+///
+/// The tag borrows from the input it was tokenized from. It is kept as a thin
+/// wrapper around the raw text (instead of an enum of known tags) so that bank
+/// specific or future MT94x tags survive tokenization untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mt940Tag<'a>(&'a str);
+
+impl<'a> Mt940Tag<'a> {
+    /// Raw tag text without the surrounding colons (e.g. `"60F"`).
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Numeric part of the tag (e.g. `"60"` for `:60F:`).
+    pub fn number(&self) -> &'a str {
+        let end = self
+            .0
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.0.len());
+        &self.0[..end]
+    }
+
+    /// Letter option of the tag, if any (e.g. `Some("F")` for `:60F:`).
+    pub fn option(&self) -> Option<&'a str> {
+        let number_len = self.number().len();
+        if number_len < self.0.len() {
+            Some(&self.0[number_len..])
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq<str> for Mt940Tag<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Mt940Tag<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for Mt940Tag<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ":{}:", self.0)
+    }
+}
+
+/// Low-level tokenizer over the tags of an MT940 message.
+///
+/// This is the same tokenizer `Mt940Statement::from_read` uses internally, exposed
+/// for callers with unusual MT94x variants who want to build their own extraction
+/// on top of our block handling. Each item is a `(tag, value)` pair where the
+/// value is borrowed from the input and contains continuation lines with their
+/// original line breaks; no allocation happens while iterating.
+///
+/// Block handling: if the input contains a SWIFT `{4:` block, only its content
+/// (up to `-}` or `}`) is tokenized. Otherwise the whole input is treated as
+/// block 4 data. Lines before the first tag are ignored.
+///
+/// # Example
+///
+/// ```
+/// use ledger_parser::Mt940Tags;
+///
+/// let data = "{1:F01BANK}{4:\n:20:REF\n:86:first line\nsecond line\n-}";
+/// let tags: Vec<_> = Mt940Tags::parse(data).collect();
+///
+/// assert_eq!(tags.len(), 2);
+/// assert_eq!(tags[0].0.as_str(), "20");
+/// assert_eq!(tags[1].1, "first line\nsecond line");
+/// ```
+///
+/// [`line`](Mt940Tags::line) tells on which line of the input the tag last
+/// returned starts, for error messages.
+#[derive(Debug, Clone)]
+pub struct Mt940Tags<'a> {
+    block: &'a str,
+    position: usize,
+    /// Line of the input on which `block` starts
+    first_line: usize,
+    /// Lines of `block` consumed so far
+    lines_read: usize,
+    /// Line of the tag last returned
+    tag_line: usize,
+}
+
+impl<'a> Mt940Tags<'a> {
+    /// Tokenize MT940 content into `(tag, value)` pairs.
+    pub fn parse(input: &'a str) -> Self {
+        let (block_start, block) = Self::block4(input);
+        Mt940Tags {
+            block,
+            position: 0,
+            first_line: 1 + input[..block_start].matches('\n').count(),
+            lines_read: 0,
+            tag_line: 0,
+        }
+    }
+
+    /// Line (counted from 1) of the input on which the tag last returned by
+    /// `next` starts; 0 before the first tag.
+    ///
+    /// ```
+    /// use ledger_parser::Mt940Tags;
+    ///
+    /// let mut tags = Mt940Tags::parse("{4:\n:20:REF\n:86:one\ntwo\n:62F:C\n-}");
+    /// tags.next();
+    /// assert_eq!(tags.line(), 2);
+    /// tags.next();
+    /// tags.next();
+    /// assert_eq!(tags.line(), 5);
+    /// ```
+    pub fn line(&self) -> usize {
+        self.tag_line
+    }
+
+    /// Locate the content of block 4 and its byte offset, falling back to the whole input.
+    fn block4(input: &'a str) -> (usize, &'a str) {
+        match input.find("{4:") {
+            Some(start) => {
+                let after_start = &input[start + 3..];
+                let end = after_start
+                    .find("-}")
+                    .or_else(|| after_start.find('}'))
+                    .unwrap_or(after_start.len());
+                (start + 3, &after_start[..end])
+            }
+            None => (0, input),
+        }
+    }
+
+    /// Byte range `(start, end)` of the next physical line without its terminator.
+    fn next_line(&mut self) -> Option<(usize, usize)> {
+        if self.position >= self.block.len() {
+            return None;
+        }
+        let start = self.position;
+        let (mut end, next) = match self.block[start..].find('\n') {
+            Some(offset) => (start + offset, start + offset + 1),
+            None => (self.block.len(), self.block.len()),
+        };
+        if self.block[start..end].ends_with('\r') {
+            end -= 1;
+        }
+        self.position = next;
+        self.lines_read += 1;
+        Some((start, end))
+    }
+
+    /// Whether the next line starts a new tag (continuation lines do not).
+    fn next_line_starts_tag(&self) -> bool {
+        self.block[self.position..]
+            .lines()
+            .next()
+            .map(|line| line.trim().starts_with(':'))
+            .unwrap_or(true)
+    }
+}
+
+impl<'a> Iterator for Mt940Tags<'a> {
+    type Item = (Mt940Tag<'a>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let block = self.block;
+
+        while let Some((line_start, line_end)) = self.next_line() {
+            let line = &block[line_start..line_end];
+            let trimmed = line.trim();
+
+            // A tag line looks like `:TAG:value`; anything else before a tag is noise
+            let Some(stripped) = trimmed.strip_prefix(':') else {
+                continue;
+            };
+            let Some(second_colon) = stripped.find(':') else {
+                continue;
+            };
+
+            self.tag_line = self.first_line + self.lines_read - 1;
+            let leading_whitespace = line.len() - line.trim_start().len();
+            let tag_start = line_start + leading_whitespace + 1;
+            let tag = Mt940Tag(&block[tag_start..tag_start + second_colon]);
+
+            // Value starts after the second colon; first line trailing whitespace is dropped
+            let value_start = tag_start + second_colon + 1;
+            let mut value_end = line_start + leading_whitespace + trimmed.len();
+
+            // Extend the value over continuation lines (lines not starting with ':')
+            while self.position < block.len() && !self.next_line_starts_tag() {
+                if let Some((_, continuation_end)) = self.next_line() {
+                    value_end = continuation_end;
+                }
+            }
+
+            return Some((tag, &block[value_start..value_end]));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags_with_block_structure() {
+        let input = "{1:F01TEST}{2:I940}{4:\n:20:REF\n:25:ACC123\n-}";
+        let tags: Vec<_> = Mt940Tags::parse(input).collect();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].0, "20");
+        assert_eq!(tags[0].1, "REF");
+        assert_eq!(tags[1].0, "25");
+        assert_eq!(tags[1].1, "ACC123");
+    }
+
+    #[test]
+    fn test_parse_tags_multiline_value() {
+        let input = ":61:2001010101D65,00NOVB\r\n:86:line one\r\nline two\r\n:62F:C200101EUR1,00";
+        let tags: Vec<_> = Mt940Tags::parse(input).collect();
+
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[1].0, "86");
+        assert_eq!(tags[1].1, "line one\r\nline two");
+        assert_eq!(tags[2].1, "C200101EUR1,00");
+    }
+
+    #[test]
+    fn test_parse_tags_skips_noise_before_first_tag() {
+        let input = "header noise\n:20:REF\n";
+        let tags: Vec<_> = Mt940Tags::parse(input).collect();
+
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].0.as_str(), "20");
+    }
+
+    #[test]
+    fn test_tag_number_and_option() {
+        let tag = Mt940Tag("60F");
+        assert_eq!(tag.number(), "60");
+        assert_eq!(tag.option(), Some("F"));
+        assert_eq!(tag.to_string(), ":60F:");
+
+        let tag = Mt940Tag("86");
+        assert_eq!(tag.number(), "86");
+        assert_eq!(tag.option(), None);
+    }
+}
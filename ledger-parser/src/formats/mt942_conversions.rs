@@ -0,0 +1,70 @@
+//! Type conversions between Mt942 and other formats
+//!
+//! Implements the `From` trait in both directions between MT942 interim reports
+//! and the other format structures (CAMT.053, CAMT.054, CSV, JSON, MT940, QIF), plus
+//! the one-way conversions from read-only BAI2, CAMT.052 and XLSX statements. The floor limits have
+//! no counterpart elsewhere: they are dropped when leaving MT942 and default to
+//! zero (report every entry) when entering it.
+
+#[cfg(feature = "xlsx")]
+use crate::XlsxStatement;
+use crate::{
+    Bai2Statement, Camt052Statement, Camt053Statement, Camt054Statement, CsvStatement,
+    JsonStatement, Mt940Statement, Mt942Statement, QifStatement, StatementInfo,
+};
+
+/// Implements `From<$other>` for `Mt942Statement` (and the reverse direction
+/// unless `@into` is given) as a field-by-field conversion of the shared data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_mt942_conversions {
+    (@into $other:ident) => {
+        impl From<$other> for Mt942Statement {
+            fn from(statement: $other) -> Self {
+                Mt942Statement {
+                    account_number: statement.account_number,
+                    currency: statement.currency,
+                    opening_balance: statement.opening_balance,
+                    opening_date: statement.opening_date,
+                    opening_indicator: statement.opening_indicator,
+                    closing_balance: statement.closing_balance,
+                    closing_date: statement.closing_date,
+                    closing_indicator: statement.closing_indicator,
+                    transactions: statement.transactions,
+                    debit_floor_limit: 0.0,
+                    credit_floor_limit: 0.0,
+                }
+            }
+        }
+    };
+    ($other:ident $(, $field:ident: $value:expr)*) => {
+        impl_mt942_conversions!(@into $other);
+
+        impl From<Mt942Statement> for $other {
+            fn from(mt942: Mt942Statement) -> Self {
+                $other {
+                    account_number: mt942.account_number,
+                    currency: mt942.currency,
+                    opening_balance: mt942.opening_balance,
+                    opening_date: mt942.opening_date,
+                    opening_indicator: mt942.opening_indicator,
+                    closing_balance: mt942.closing_balance,
+                    closing_date: mt942.closing_date,
+                    closing_indicator: mt942.closing_indicator,
+                    transactions: mt942.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+    };
+}
+
+impl_mt942_conversions!(@into Bai2Statement);
+impl_mt942_conversions!(@into Camt052Statement);
+impl_mt942_conversions!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_mt942_conversions!(Camt054Statement);
+impl_mt942_conversions!(CsvStatement, info: StatementInfo::default());
+impl_mt942_conversions!(JsonStatement);
+impl_mt942_conversions!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_mt942_conversions!(QifStatement);
+#[cfg(feature = "xlsx")]
+impl_mt942_conversions!(@into XlsxStatement);
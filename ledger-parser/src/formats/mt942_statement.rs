@@ -0,0 +1,416 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::diagnostics::Diagnostics;
+use crate::formats::mt940_dialect::Mt940Dialect;
+use crate::formats::mt940_statement::Mt940Field;
+use crate::formats::paging;
+use crate::formats::swift_charset::Mt940Charset;
+use crate::formats::utils;
+use crate::quirks::ParseOptions;
+use crate::{BalanceType, Mt940Statement, ParseError, Transaction, TransactionType};
+
+/// Length of the `YYMMDDhhmm` part of a `:13D:` date/time indication
+const DATE_TIME_LENGTH: usize = 10;
+/// Length of the `±hhmm` UTC offset closing a `:13D:` date/time indication
+const OFFSET_LENGTH: usize = 5;
+/// Length of an ISO 4217 currency code
+const CURRENCY_LENGTH: usize = 3;
+
+/// SWIFT MT942 interim transaction report.
+///
+/// Shares tag handling with [`Mt940Statement`]; the differences are:
+/// - `:34F:` floor limits instead of balances (one tag for both directions,
+///   or a `D` and a `C` tag)
+/// - `:13D:` date/time indication with UTC offset, used as the closing date
+/// - `:90D:`/`:90C:` number and sum of debit/credit entries, checked against
+///   the parsed `:61:` entries
+///
+/// MT942 carries no balances: parsing opens at zero on the first entry's
+/// booking date and closes at the net of all entries, and writing drops them.
+/// The common fields are identical to Mt940/Camt053/CsvStatement for
+/// seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mt942Statement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance (the `:13D:` indication)
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+    /// Debit floor limit from `:34F:`; smaller debits are not reported
+    pub debit_floor_limit: f64,
+    /// Credit floor limit from `:34F:`; smaller credits are not reported
+    pub credit_floor_limit: f64,
+}
+
+impl Mt942Statement {
+    /// Parse MT942 from any Read source (file, stdin, buffer).
+    ///
+    /// Handles both full SWIFT format with blocks and simplified tag-only format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::Mt942Error` if:
+    /// - The message structure is invalid
+    /// - `:25:`, `:34F:` or `:13D:` is missing or malformed
+    /// - `:90D:`/`:90C:` disagree with the `:61:` entries
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::Mt942Statement;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("interim.mt942").unwrap();
+    /// let report = Mt942Statement::from_read(&mut file).unwrap();
+    /// ```
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse MT942 from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`Mt942Statement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse MT942 from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        if content.trim().is_empty() {
+            return Err(ParseError::Mt942Error("Empty input".into()));
+        }
+
+        Mt940Statement::extract_block4(content).map_err(Self::from_mt940_error)?;
+        let tags = Mt940Statement::parse_tags(content, 1);
+
+        let account_number =
+            Mt940Statement::extract_account_number(&tags).map_err(Self::from_mt940_error)?;
+        let (currency, debit_floor_limit, credit_floor_limit) = Self::extract_floor_limits(&tags)?;
+        let closing_date = Self::extract_date_time(&tags)?;
+        let quirks = ParseOptions::default().active_quirks(content);
+        // Skipped entries show up as a mismatch with the :90D:/:90C: summaries
+        let transactions =
+            Mt940Statement::extract_transactions(&tags, &quirks, &mut Diagnostics::default())
+                .map_err(Self::from_mt940_error)?;
+        Self::check_summaries(&tags, &transactions)?;
+
+        let net = paging::round_cents(transactions.iter().map(paging::booked_amount).sum());
+        let (closing_balance, closing_indicator) = paging::unsigned_balance(net);
+        let opening_date = transactions
+            .first()
+            .map_or(closing_date, |tx| tx.booking_date);
+
+        let statement = Mt942Statement {
+            account_number,
+            currency,
+            opening_balance: 0.0,
+            opening_date,
+            opening_indicator: BalanceType::Credit,
+            closing_balance,
+            closing_date,
+            closing_indicator,
+            transactions,
+            debit_floor_limit,
+            credit_floor_limit,
+        };
+        trace_statement!("parsed statement", "mt942", statement);
+
+        Ok(statement)
+    }
+
+    /// Write MT942 to any Write destination (file, stdout, buffer).
+    ///
+    /// Emits one SWIFT message with `:34F:` (a single tag when both floor limits
+    /// are equal), `:13D:` from the closing date, the `:61:`/`:86:` entries and
+    /// `:90D:`/`:90C:` summaries. Balances are not part of MT942 and are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "mt942", self);
+        let mut writer = BufWriter::new(writer);
+
+        writeln!(
+            writer,
+            "{{1:F01BANKXXXXXX0000000000}}{{2:I942BANKXXXXXXN}}{{4:"
+        )?;
+        writeln!(writer, ":20:REPORT")?;
+        writeln!(writer, ":25:{}", self.account_number)?;
+        writeln!(writer, ":28C:1/1")?;
+        if self.debit_floor_limit == self.credit_floor_limit {
+            writeln!(
+                writer,
+                ":34F:{}{}",
+                self.currency,
+                Mt940Statement::format_amount(self.debit_floor_limit)
+            )?;
+        } else {
+            writeln!(
+                writer,
+                ":34F:{}D{}",
+                self.currency,
+                Mt940Statement::format_amount(self.debit_floor_limit)
+            )?;
+            writeln!(
+                writer,
+                ":34F:{}C{}",
+                self.currency,
+                Mt940Statement::format_amount(self.credit_floor_limit)
+            )?;
+        }
+        writeln!(writer, ":13D:{}", self.closing_date.format("%y%m%d%H%M%z"))?;
+
+        Mt940Statement::write_entries(
+            &mut writer,
+            &self.transactions,
+            Mt940Dialect::default(),
+            Mt940Charset::default(),
+        )?;
+
+        for (tag, transaction_type) in [
+            ("90D", TransactionType::Debit),
+            ("90C", TransactionType::Credit),
+        ] {
+            let (count, sum) = Self::summary(&self.transactions, &transaction_type);
+            writeln!(
+                writer,
+                ":{}:{}{}{}",
+                tag,
+                count,
+                self.currency,
+                Mt940Statement::format_amount(sum)
+            )?;
+        }
+
+        writeln!(writer, "-}}")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Re-label errors of the shared MT940 helpers as MT942 errors
+    fn from_mt940_error(err: ParseError) -> ParseError {
+        err.map_located(|err| match err {
+            ParseError::Mt940Error(msg) => ParseError::Mt942Error(msg),
+            other => other,
+        })
+    }
+
+    /// Currency and (debit, credit) floor limits from the `:34F:` tags.
+    ///
+    /// A tag without `D`/`C` sets both limits; otherwise the `D` and `C` tags set
+    /// their own direction.
+    fn extract_floor_limits(tags: &[Mt940Field]) -> Result<(String, f64, f64), ParseError> {
+        let mut floor_limits = tags.iter().filter(|field| field.tag == "34F").peekable();
+        if floor_limits.peek().is_none() {
+            return Err(ParseError::Mt942Error(
+                "Missing :34F: floor limit tag".into(),
+            ));
+        }
+
+        let mut currency = String::new();
+        let (mut debit, mut credit) = (0.0, 0.0);
+        for field in floor_limits {
+            let (code, indicator, amount) = field.parse(|value| {
+                let value = value.trim();
+                let code = value.get(..CURRENCY_LENGTH).ok_or_else(|| {
+                    ParseError::Mt942Error(format!("Invalid :34F: floor limit: {}", value))
+                })?;
+                let rest = &value[CURRENCY_LENGTH..];
+                let (indicator, amount) = match rest.chars().next() {
+                    Some(indicator @ ('D' | 'C')) => (Some(indicator), &rest[1..]),
+                    _ => (None, rest),
+                };
+                Ok((
+                    code.to_string(),
+                    indicator,
+                    Mt940Statement::parse_amount(amount)?,
+                ))
+            })?;
+            match indicator {
+                Some('D') => debit = amount,
+                Some(_) => credit = amount,
+                None => (debit, credit) = (amount, amount),
+            }
+            currency = code;
+        }
+
+        Ok((currency, debit, credit))
+    }
+
+    /// Date and time of the report from `:13D:` (`YYMMDDhhmm±hhmm`)
+    fn extract_date_time(tags: &[Mt940Field]) -> Result<DateTime<FixedOffset>, ParseError> {
+        let field = tags
+            .iter()
+            .find(|field| field.tag == "13D")
+            .ok_or_else(|| ParseError::Mt942Error("Missing :13D: date/time indication".into()))?;
+        field.parse(|value| {
+            let value = value.trim();
+            let invalid = || ParseError::UnexpectedValue {
+                field: "date/time".into(),
+                expected: "YYMMDDhhmm followed by a ±hhmm offset".into(),
+                found: value.into(),
+            };
+            if value.len() != DATE_TIME_LENGTH + OFFSET_LENGTH {
+                return Err(invalid());
+            }
+
+            // Reuse the century inference of MT940 dates for the YYMMDD part
+            let date = Mt940Statement::parse_yymmdd_date(&value[..6])?.date_naive();
+            DateTime::parse_from_str(
+                &format!("{}{}", date.format("%Y%m%d"), &value[6..]),
+                "%Y%m%d%H%M%z",
+            )
+            .map_err(|_| invalid())
+        })
+    }
+
+    /// Check `:90D:`/`:90C:` (`<count><currency><amount>`) against the parsed entries
+    fn check_summaries(
+        tags: &[Mt940Field],
+        transactions: &[Transaction],
+    ) -> Result<(), ParseError> {
+        for (tag, transaction_type) in [
+            ("90D", TransactionType::Debit),
+            ("90C", TransactionType::Credit),
+        ] {
+            let Some(field) = tags.iter().find(|field| field.tag == tag) else {
+                continue;
+            };
+            field.parse(|value| {
+                let value = value.trim();
+                let count_end = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                let count: usize = value[..count_end].parse().map_err(|_| {
+                    ParseError::Mt942Error(format!("Invalid :{}: entry count: {}", tag, value))
+                })?;
+                let amount = value
+                    .get(count_end + CURRENCY_LENGTH..)
+                    .ok_or_else(|| {
+                        ParseError::Mt942Error(format!("Invalid :{}: sum: {}", tag, value))
+                    })
+                    .and_then(Mt940Statement::parse_amount)?;
+
+                let (parsed_count, parsed_sum) = Self::summary(transactions, &transaction_type);
+                if count != parsed_count || paging::round_cents(amount) != parsed_sum {
+                    return Err(ParseError::Mt942Error(format!(
+                        ":{}: reports {} entries totalling {:.2}, found {} totalling {:.2}",
+                        tag, count, amount, parsed_count, parsed_sum
+                    )));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Number and rounded sum of the entries of one direction
+    fn summary(transactions: &[Transaction], transaction_type: &TransactionType) -> (usize, f64) {
+        let (count, sum) = transactions
+            .iter()
+            .filter(|tx| &tx.transaction_type == transaction_type)
+            .fold((0, 0.0), |(count, sum), tx| (count + 1, sum + tx.amount));
+        (count, paging::round_cents(sum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLocation;
+
+    const REPORT: &str = "{1:F01BANKDEFFXXXX0000000000}{2:O942BANKDEFFXXXXN}{4:
+:20:INTRADAY1
+:25:DE89370400440532013000
+:28C:7/1
+:34F:EURD50,00
+:34F:EURC0,
+:13D:2503141530+0100
+:61:2503140314D120,50NTRFINV-42
+:86:Office supplies
+:61:2503140314C1000,00NTRFNONREF
+:86:Customer payment
+:90D:1EUR120,50
+:90C:1EUR1000,00
+-}";
+
+    #[test]
+    fn test_parse_interim_report() {
+        let report = Mt942Statement::from_read(&mut REPORT.as_bytes()).unwrap();
+
+        assert_eq!(report.account_number, "DE89370400440532013000");
+        assert_eq!(report.currency, "EUR");
+        assert_eq!(report.debit_floor_limit, 50.0);
+        assert_eq!(report.credit_floor_limit, 0.0);
+        assert_eq!(
+            report.closing_date.to_rfc3339(),
+            "2025-03-14T15:30:00+01:00"
+        );
+        assert_eq!(report.transactions.len(), 2);
+        assert_eq!(report.transactions[0].reference.as_deref(), Some("INV-42"));
+        assert_eq!(report.opening_balance, 0.0);
+        assert_eq!(report.closing_balance, 879.5);
+        assert_eq!(report.closing_indicator, BalanceType::Credit);
+    }
+
+    #[test]
+    fn test_summary_mismatch_is_rejected() {
+        let tampered = REPORT.replace(":90D:1EUR120,50", ":90D:2EUR120,50");
+        let err = Mt942Statement::from_read(&mut tampered.as_bytes()).unwrap_err();
+        assert!(
+            matches!(err.location(), Some(ErrorLocation::Line { field: Some(tag), .. }) if tag == "90D")
+        );
+        assert!(
+            matches!(err.without_location(), ParseError::Mt942Error(msg) if msg.starts_with(":90D:"))
+        );
+
+        let missing = REPORT.replace(":13D:2503141530+0100\n", "");
+        assert!(matches!(
+            Mt942Statement::from_read(&mut missing.as_bytes()),
+            Err(ParseError::Mt942Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let report = Mt942Statement::from_read(&mut REPORT.as_bytes()).unwrap();
+        let mut output = Vec::new();
+        report.write_to(&mut output).unwrap();
+        let text = String::from_utf8(output.clone()).unwrap();
+
+        assert!(text.contains(":34F:EURD50,00\n:34F:EURC0,00\n"));
+        assert!(text.contains(":13D:2503141530+0100\n"));
+        assert!(text.contains(":90C:1EUR1000,00\n"));
+        assert_eq!(
+            Mt942Statement::from_read(&mut output.as_slice()).unwrap(),
+            report
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Statement pagination shared by the writers, `split_by_transaction_count` and
+//! `split_by_month`.
+//!
+//! Receiving systems often cap the number of entries per message or file. A page
+//! carries a slice of the statement's transactions plus the balances that bracket
+//! it, so each format can render intermediate balances (`:60M:`/`:62M:` in MT940,
+//! `ITBD` in CAMT.053) instead of pretending every part is a complete statement.
+
+use chrono::{DateTime, Datelike, FixedOffset};
+
+use crate::model::{BalanceType, Transaction, TransactionType};
+
+/// One part of a paginated statement.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Page<'a> {
+    /// 1-based page number
+    pub number: usize,
+    /// Total number of pages in the statement
+    pub total: usize,
+    /// Opening balance of this page (signed: credit positive, debit negative)
+    pub opening_balance: f64,
+    /// Date of this page's opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Closing balance of this page (signed: credit positive, debit negative)
+    pub closing_balance: f64,
+    /// Date of this page's closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Transactions belonging to this page
+    pub transactions: &'a [Transaction],
+}
+
+impl Page<'_> {
+    /// Whether this page starts the statement (its opening balance is final, not intermediate)
+    pub fn is_first(&self) -> bool {
+        self.number == 1
+    }
+
+    /// Whether this page ends the statement (its closing balance is final, not intermediate)
+    pub fn is_last(&self) -> bool {
+        self.number == self.total
+    }
+}
+
+/// Statement balances in the form stored on the format structs.
+pub(crate) struct Balances {
+    pub opening_balance: f64,
+    pub opening_indicator: BalanceType,
+    pub opening_date: DateTime<FixedOffset>,
+    pub closing_balance: f64,
+    pub closing_indicator: BalanceType,
+    pub closing_date: DateTime<FixedOffset>,
+}
+
+/// Split transactions into pages of at most `max_transactions` entries.
+///
+/// `None` or `Some(0)` produce a single page. Intermediate balances are derived
+/// from the opening balance plus the running sum of the booked transactions; the
+/// last page always closes with the statement's own closing balance so the file
+/// total never drifts from what the bank reported.
+pub(crate) fn paginate<'a>(
+    balances: &Balances,
+    transactions: &'a [Transaction],
+    max_transactions: Option<usize>,
+) -> Vec<Page<'a>> {
+    let chunks = match max_transactions {
+        Some(size) if size > 0 && transactions.len() > size => transactions.chunks(size).collect(),
+        _ => vec![transactions],
+    };
+    pages(balances, chunks)
+}
+
+/// Pages of `chunks`, consecutive parts of a statement's transactions, with
+/// intermediate balances derived as in [`paginate`]
+fn pages<'a>(balances: &Balances, chunks: Vec<&'a [Transaction]>) -> Vec<Page<'a>> {
+    let opening = signed_amount(balances.opening_balance, &balances.opening_indicator);
+    let closing = signed_amount(balances.closing_balance, &balances.closing_indicator);
+    let total = chunks.len();
+    let mut pages = Vec::with_capacity(total);
+    let mut running_balance = opening;
+    let mut running_date = balances.opening_date;
+
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let page_opening = running_balance;
+        let page_opening_date = running_date;
+
+        let is_last = index + 1 == total;
+        let (page_closing, page_closing_date) = if is_last {
+            (closing, balances.closing_date)
+        } else {
+            let movement: f64 = chunk.iter().map(booked_amount).sum();
+            let last_booking = chunk
+                .last()
+                .map(|tx| tx.booking_date)
+                .unwrap_or(page_opening_date);
+            (round_cents(page_opening + movement), last_booking)
+        };
+
+        pages.push(Page {
+            number: index + 1,
+            total,
+            opening_balance: page_opening,
+            opening_date: page_opening_date,
+            closing_balance: page_closing,
+            closing_date: page_closing_date,
+            transactions: chunk,
+        });
+
+        running_balance = page_closing;
+        running_date = page_closing_date;
+    }
+
+    pages
+}
+
+/// Split a statement into standalone parts of at most `max_transactions` entries.
+///
+/// Each part gets its own balances (recomputed for intermediate boundaries) and an
+/// owned copy of its transactions, ready to be turned back into a format struct.
+pub(crate) fn split(
+    balances: &Balances,
+    transactions: &[Transaction],
+    max_transactions: usize,
+) -> Vec<(Balances, Vec<Transaction>)> {
+    into_parts(paginate(balances, transactions, Some(max_transactions)))
+}
+
+/// Split a statement into standalone parts, one per calendar month of the
+/// booking dates in month order, balanced as in [`split`]. Transactions keep
+/// their order within a month; a statement without any is a single part.
+pub(crate) fn split_by_month(
+    balances: &Balances,
+    transactions: &[Transaction],
+) -> Vec<(Balances, Vec<Transaction>)> {
+    let month = |tx: &Transaction| (tx.booking_date.year(), tx.booking_date.month());
+    let mut sorted = transactions.to_vec();
+    sorted.sort_by_key(month);
+    let mut chunks: Vec<&[Transaction]> = sorted.chunk_by(|a, b| month(a) == month(b)).collect();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+    into_parts(pages(balances, chunks))
+}
+
+/// Standalone parts of `pages`, each with its balances and a copy of its transactions
+fn into_parts(pages: Vec<Page<'_>>) -> Vec<(Balances, Vec<Transaction>)> {
+    pages
+        .into_iter()
+        .map(|page| {
+            let (opening_balance, opening_indicator) = unsigned_balance(page.opening_balance);
+            let (closing_balance, closing_indicator) = unsigned_balance(page.closing_balance);
+            let part_balances = Balances {
+                opening_balance,
+                opening_indicator,
+                opening_date: page.opening_date,
+                closing_balance,
+                closing_indicator,
+                closing_date: page.closing_date,
+            };
+            (part_balances, page.transactions.to_vec())
+        })
+        .collect()
+}
+
+/// Convert an unsigned balance and its indicator to a signed amount
+pub(crate) fn signed_amount(amount: f64, indicator: &BalanceType) -> f64 {
+    match indicator {
+        BalanceType::Credit => amount,
+        BalanceType::Debit => -amount,
+    }
+}
+
+/// Split a signed amount back into the unsigned amount and indicator stored on statements
+pub(crate) fn unsigned_balance(amount: f64) -> (f64, BalanceType) {
+    if amount < 0.0 {
+        (-amount, BalanceType::Debit)
+    } else {
+        (amount, BalanceType::Credit)
+    }
+}
+
+/// Signed amount of a transaction (debits negative), booked or not
+pub(crate) fn signed_transaction_amount(transaction: &Transaction) -> f64 {
+    match transaction.transaction_type {
+        TransactionType::Credit => transaction.amount,
+        TransactionType::Debit => -transaction.amount,
+    }
+}
+
+/// Signed effect of a transaction on the account balance: pending and
+/// informational entries have not moved it
+pub(crate) fn booked_amount(transaction: &Transaction) -> f64 {
+    if transaction.status.is_booked() {
+        signed_transaction_amount(transaction)
+    } else {
+        0.0
+    }
+}
+
+/// Signed sum of the pending and informational entries of `transactions`,
+/// which journals post flagged on top of the booked balance
+pub(crate) fn unbooked_movement(transactions: &[Transaction]) -> f64 {
+    transactions
+        .iter()
+        .map(|transaction| signed_transaction_amount(transaction) - booked_amount(transaction))
+        .sum()
+}
+
+/// Round to whole cents so repeated f64 additions don't leak noise into output
+pub(crate) fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{text, EntryStatus};
+
+    fn transaction(amount: f64, transaction_type: TransactionType, date: &str) -> Transaction {
+        Transaction::new(
+            text::parse_date(date).unwrap(),
+            amount,
+            transaction_type,
+            "",
+        )
+    }
+
+    fn balances(opening: f64, closing: f64) -> Balances {
+        Balances {
+            opening_balance: opening,
+            opening_indicator: BalanceType::Credit,
+            opening_date: text::parse_date("2025-01-01").unwrap(),
+            closing_balance: closing,
+            closing_indicator: BalanceType::Credit,
+            closing_date: text::parse_date("2025-01-31").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_paginate_single_page_without_limit() {
+        let transactions = vec![transaction(10.0, TransactionType::Credit, "2025-01-02")];
+        let pages = paginate(&balances(100.0, 110.0), &transactions, None);
+
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].is_first() && pages[0].is_last());
+        assert_eq!(pages[0].opening_balance, 100.0);
+        assert_eq!(pages[0].closing_balance, 110.0);
+    }
+
+    #[test]
+    fn test_paginate_intermediate_balances() {
+        let transactions = vec![
+            transaction(10.0, TransactionType::Credit, "2025-01-02"),
+            transaction(30.0, TransactionType::Debit, "2025-01-03"),
+            transaction(5.5, TransactionType::Credit, "2025-01-04"),
+        ];
+        let pages = paginate(&balances(100.0, 85.5), &transactions, Some(2));
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].transactions.len(), 2);
+        assert_eq!(pages[0].closing_balance, 80.0);
+        assert_eq!(
+            pages[0].closing_date,
+            text::parse_date("2025-01-03").unwrap()
+        );
+        assert_eq!(pages[1].opening_balance, 80.0);
+        assert_eq!(pages[1].opening_date, pages[0].closing_date);
+        assert_eq!(pages[1].closing_balance, 85.5);
+        assert!(pages[1].is_last());
+    }
+
+    #[test]
+    fn test_paginate_skips_pending_entries_in_balances() {
+        let transactions = vec![
+            Transaction {
+                status: EntryStatus::Pending,
+                ..transaction(50.0, TransactionType::Debit, "2025-01-02")
+            },
+            transaction(10.0, TransactionType::Debit, "2025-01-03"),
+        ];
+        let pages = paginate(&balances(100.0, 90.0), &transactions, Some(1));
+
+        assert_eq!(pages[0].closing_balance, 100.0);
+        assert_eq!(pages[1].opening_balance, 100.0);
+        assert_eq!(pages[1].closing_balance, 90.0);
+    }
+
+    #[test]
+    fn test_split_by_month_groups_out_of_order_entries() {
+        let transactions = vec![
+            transaction(10.0, TransactionType::Credit, "2025-01-30"),
+            transaction(30.0, TransactionType::Debit, "2025-02-02"),
+            transaction(5.0, TransactionType::Credit, "2025-01-31"),
+        ];
+        let parts = split_by_month(&balances(100.0, 85.0), &transactions);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].1.len(), 2);
+        assert_eq!(parts[0].0.closing_balance, 115.0);
+        assert_eq!(parts[1].0.opening_balance, 115.0);
+        assert_eq!(parts[1].0.closing_balance, 85.0);
+        assert_eq!(split_by_month(&balances(100.0, 100.0), &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_unsigned_balance_round_trip() {
+        assert_eq!(unsigned_balance(-12.5), (12.5, BalanceType::Debit));
+        assert_eq!(unsigned_balance(3.0), (3.0, BalanceType::Credit));
+        assert_eq!(signed_amount(12.5, &BalanceType::Debit), -12.5);
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+
+use crate::formats::paging::round_cents;
+use crate::{ParseError, StatementRef, Transaction, TransactionType};
+
+/// Document namespace of customer credit transfer initiation version 3 (SEPA baseline)
+const PAIN001_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pain.001.001.03";
+/// Prefix of message ids derived from the creation time
+const MESSAGE_ID_PREFIX: &str = "PAIN001-";
+/// Payment method: credit transfer
+const PAYMENT_METHOD_TRANSFER: &str = "TRF";
+/// Placeholder for identifiers and names the source transaction does not carry
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+/// Maximum length of identifiers (`MsgId`, `PmtInfId`, `EndToEndId`)
+const MAX_ID_LENGTH: usize = 35;
+/// Maximum length of party names (`Nm`)
+const MAX_NAME_LENGTH: usize = 70;
+/// Maximum length of the unstructured remittance information (`Ustrd`)
+const MAX_REMITTANCE_LENGTH: usize = 140;
+
+/// ISO 20022 pain.001 customer credit transfer initiation.
+///
+/// One payment instruction per transaction, all debited from `debtor_account`
+/// in `currency` on `execution_date`. Creditor account and name come from the
+/// transaction's counterparty, the reference becomes the `EndToEndId` and the
+/// description the remittance text. Build it from the debit transactions of a
+/// statement with [`Pain001Document::from_debits`] to re-submit payouts, or
+/// from any list of transactions with [`Pain001Document::from_transactions`].
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, Pain001Document};
+///
+/// let data = ":20:REF\n:25:DE89370400440532013000\n:60F:C250101EUR100,00\n\
+///             :61:250107D25,50NTRFINV7\n:86:Invoice 7\n:62F:C250107EUR74,50\n";
+/// let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// statement.transactions[0].counterparty_account = Some("DE02120300000000202051".into());
+///
+/// let mut output = Vec::new();
+/// Pain001Document::from_debits(statement.as_view())
+///     .write_to(&mut output)
+///     .unwrap();
+/// let xml = String::from_utf8(output).unwrap();
+/// assert!(xml.contains("<InstdAmt Ccy=\"EUR\">25.50</InstdAmt>"));
+/// assert!(xml.contains("<IBAN>DE02120300000000202051</IBAN>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pain001Document {
+    /// Message identifier (`GrpHdr/MsgId`), also used as payment information id
+    pub message_id: String,
+    /// Creation timestamp (`CreDtTm`)
+    pub created_at: NaiveDateTime,
+    /// Requested execution date (`ReqdExctnDt`)
+    pub execution_date: NaiveDate,
+    /// Name of the debtor and initiating party; `None` writes `NOTPROVIDED`
+    pub debtor_name: Option<String>,
+    /// IBAN of the account the payments are debited from
+    pub debtor_account: String,
+    /// BIC of the debtor's bank; `None` writes `NOTPROVIDED` (IBAN-only SEPA payments)
+    pub debtor_bic: Option<String>,
+    /// Three-letter ISO 4217 currency code of all payments
+    pub currency: String,
+    /// Payments to initiate; amounts must be positive and counterparty accounts set
+    pub payments: Vec<Transaction>,
+}
+
+impl Pain001Document {
+    /// Document paying out `transactions` from `debtor_account`, created and executed now
+    pub fn from_transactions<I>(
+        debtor_account: impl Into<String>,
+        currency: impl Into<String>,
+        transactions: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Transaction>,
+    {
+        let created_at = Utc::now().naive_utc();
+        Pain001Document {
+            message_id: format!("{}{}", MESSAGE_ID_PREFIX, created_at.format("%Y%m%d%H%M%S")),
+            created_at,
+            execution_date: created_at.date(),
+            debtor_name: None,
+            debtor_account: debtor_account.into(),
+            debtor_bic: None,
+            currency: currency.into(),
+            payments: transactions.into_iter().collect(),
+        }
+    }
+
+    /// Document repeating the debit transactions of `statement` from its account
+    pub fn from_debits(statement: StatementRef<'_>) -> Self {
+        let debits = statement
+            .transactions
+            .iter()
+            .filter(|transaction| transaction.transaction_type == TransactionType::Debit)
+            .cloned();
+        Self::from_transactions(statement.account_number, statement.currency, debits)
+    }
+
+    /// Sum of all payment amounts (`CtrlSum`)
+    pub fn control_sum(&self) -> f64 {
+        round_cents(self.payments.iter().map(|payment| payment.amount).sum())
+    }
+
+    /// Write the document as pain.001.001.03 XML.
+    ///
+    /// # Errors
+    /// Returns `ParseError::MissingField` if there are no payments or a payment
+    /// has no counterparty account, `ParseError::InvalidFieldValue` for a
+    /// non-positive amount, and `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        self.validate()?;
+
+        let mut buffered = BufWriter::new(writer);
+        let mut xml = Writer::new_with_indent(&mut buffered, b' ', 2);
+        xml.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        let mut document = BytesStart::new("Document");
+        document.push_attribute(("xmlns", PAIN001_NAMESPACE));
+        xml.write_event(Event::Start(document))?;
+        start(&mut xml, "CstmrCdtTrfInitn")?;
+
+        let message_id = truncate(&self.message_id, MAX_ID_LENGTH);
+        let payment_count = self.payments.len().to_string();
+        let control_sum = format!("{:.2}", self.control_sum());
+        let debtor_name = truncate(
+            self.debtor_name.as_deref().unwrap_or(NOT_PROVIDED),
+            MAX_NAME_LENGTH,
+        );
+
+        start(&mut xml, "GrpHdr")?;
+        text(&mut xml, "MsgId", &message_id)?;
+        let created_at = self.created_at.format("%Y-%m-%dT%H:%M:%S").to_string();
+        text(&mut xml, "CreDtTm", &created_at)?;
+        text(&mut xml, "NbOfTxs", &payment_count)?;
+        text(&mut xml, "CtrlSum", &control_sum)?;
+        start(&mut xml, "InitgPty")?;
+        text(&mut xml, "Nm", &debtor_name)?;
+        end(&mut xml, "InitgPty")?;
+        end(&mut xml, "GrpHdr")?;
+
+        start(&mut xml, "PmtInf")?;
+        text(&mut xml, "PmtInfId", &message_id)?;
+        text(&mut xml, "PmtMtd", PAYMENT_METHOD_TRANSFER)?;
+        text(&mut xml, "NbOfTxs", &payment_count)?;
+        text(&mut xml, "CtrlSum", &control_sum)?;
+        let execution_date = self.execution_date.format("%Y-%m-%d").to_string();
+        text(&mut xml, "ReqdExctnDt", &execution_date)?;
+        start(&mut xml, "Dbtr")?;
+        text(&mut xml, "Nm", &debtor_name)?;
+        end(&mut xml, "Dbtr")?;
+        account(&mut xml, "DbtrAcct", &self.debtor_account)?;
+        start(&mut xml, "DbtrAgt")?;
+        start(&mut xml, "FinInstnId")?;
+        match &self.debtor_bic {
+            Some(bic) => text(&mut xml, "BIC", bic)?,
+            None => {
+                start(&mut xml, "Othr")?;
+                text(&mut xml, "Id", NOT_PROVIDED)?;
+                end(&mut xml, "Othr")?;
+            }
+        }
+        end(&mut xml, "FinInstnId")?;
+        end(&mut xml, "DbtrAgt")?;
+
+        for payment in &self.payments {
+            self.write_payment(&mut xml, payment)?;
+        }
+
+        end(&mut xml, "PmtInf")?;
+        end(&mut xml, "CstmrCdtTrfInitn")?;
+        end(&mut xml, "Document")?;
+        buffered.flush()?;
+        Ok(())
+    }
+
+    /// One `CdtTrfTxInf` credit transfer transaction
+    fn write_payment<W: Write>(
+        &self,
+        xml: &mut Writer<W>,
+        payment: &Transaction,
+    ) -> Result<(), ParseError> {
+        start(xml, "CdtTrfTxInf")?;
+        start(xml, "PmtId")?;
+        let end_to_end_id = payment.reference.as_deref().unwrap_or(NOT_PROVIDED);
+        text(xml, "EndToEndId", &truncate(end_to_end_id, MAX_ID_LENGTH))?;
+        end(xml, "PmtId")?;
+
+        start(xml, "Amt")?;
+        let mut amount = BytesStart::new("InstdAmt");
+        amount.push_attribute(("Ccy", self.currency.as_str()));
+        xml.write_event(Event::Start(amount))?;
+        let formatted = format!("{:.2}", round_cents(payment.amount));
+        xml.write_event(Event::Text(BytesText::new(&formatted)))?;
+        end(xml, "InstdAmt")?;
+        end(xml, "Amt")?;
+
+        start(xml, "Cdtr")?;
+        let creditor = payment.counterparty_name.as_deref().unwrap_or(NOT_PROVIDED);
+        text(xml, "Nm", &truncate(creditor, MAX_NAME_LENGTH))?;
+        end(xml, "Cdtr")?;
+        if let Some(creditor_account) = &payment.counterparty_account {
+            account(xml, "CdtrAcct", creditor_account)?;
+        }
+
+        let remittance = payment.description.split_whitespace().collect::<Vec<_>>();
+        if !remittance.is_empty() {
+            start(xml, "RmtInf")?;
+            text(
+                xml,
+                "Ustrd",
+                &truncate(&remittance.join(" "), MAX_REMITTANCE_LENGTH),
+            )?;
+            end(xml, "RmtInf")?;
+        }
+        end(xml, "CdtTrfTxInf")?;
+        Ok(())
+    }
+
+    /// Reject documents the bank would refuse before writing anything
+    fn validate(&self) -> Result<(), ParseError> {
+        if self.payments.is_empty() {
+            return Err(ParseError::MissingField("CdtTrfTxInf".into()));
+        }
+        for (index, payment) in self.payments.iter().enumerate() {
+            if payment.counterparty_account.is_none() {
+                return Err(ParseError::MissingField(format!(
+                    "CdtrAcct of payment {}",
+                    index + 1
+                )));
+            }
+            if payment.amount.is_nan() || payment.amount <= 0.0 {
+                return Err(ParseError::InvalidFieldValue {
+                    field: "InstdAmt".into(),
+                    value: payment.amount.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Opening tag of `name`
+fn start<W: Write>(xml: &mut Writer<W>, name: &str) -> Result<(), ParseError> {
+    xml.write_event(Event::Start(BytesStart::new(name)))?;
+    Ok(())
+}
+
+/// Closing tag of `name`
+fn end<W: Write>(xml: &mut Writer<W>, name: &str) -> Result<(), ParseError> {
+    xml.write_event(Event::End(BytesEnd::new(name)))?;
+    Ok(())
+}
+
+/// Element `name` containing escaped `value`
+fn text<W: Write>(xml: &mut Writer<W>, name: &str, value: &str) -> Result<(), ParseError> {
+    start(xml, name)?;
+    xml.write_event(Event::Text(BytesText::new(value)))?;
+    end(xml, name)
+}
+
+/// Account element `name` identified by IBAN
+fn account<W: Write>(xml: &mut Writer<W>, name: &str, iban: &str) -> Result<(), ParseError> {
+    start(xml, name)?;
+    start(xml, "Id")?;
+    text(xml, "IBAN", &iban.replace(' ', ""))?;
+    end(xml, "Id")?;
+    end(xml, name)
+}
+
+/// First `max` characters of `text`
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn payment(amount: f64, account: Option<&str>) -> Transaction {
+        Transaction {
+            reference: Some("INV-7".into()),
+            counterparty_name: Some("ACME GmbH".into()),
+            counterparty_account: account.map(Into::into),
+            ..Transaction::new(
+                text::parse_date("2025-03-07").unwrap(),
+                amount,
+                TransactionType::Debit,
+                "Refund\ninvoice 7",
+            )
+        }
+    }
+
+    #[test]
+    fn test_write_pain001_document() {
+        let mut document = Pain001Document::from_transactions(
+            "DE89 3704 0044 0532 0130 00",
+            "EUR",
+            vec![
+                payment(25.5, Some("DE02120300000000202051")),
+                payment(10.0, Some("DE02120300000000202051")),
+            ],
+        );
+        document.message_id = "MSG-1".into();
+        document.debtor_bic = Some("COBADEFFXXX".into());
+
+        let mut output = Vec::new();
+        document.write_to(&mut output).unwrap();
+        let xml = String::from_utf8(output).unwrap();
+
+        assert!(xml.contains(PAIN001_NAMESPACE));
+        assert!(xml.contains("<MsgId>MSG-1</MsgId>"));
+        assert_eq!(xml.matches("<NbOfTxs>2</NbOfTxs>").count(), 2);
+        assert_eq!(xml.matches("<CtrlSum>35.50</CtrlSum>").count(), 2);
+        assert!(xml.contains("<IBAN>DE89370400440532013000</IBAN>"));
+        assert!(xml.contains("<BIC>COBADEFFXXX</BIC>"));
+        assert!(xml.contains("<EndToEndId>INV-7</EndToEndId>"));
+        assert!(xml.contains("<Ustrd>Refund invoice 7</Ustrd>"));
+        assert!(xml.contains("<Nm>NOTPROVIDED</Nm>"));
+    }
+
+    #[test]
+    fn test_reject_unpayable_transactions() {
+        let write = |payments| {
+            Pain001Document::from_transactions("DE89370400440532013000", "EUR", payments)
+                .write_to(&mut Vec::new())
+        };
+
+        assert!(matches!(write(vec![]), Err(ParseError::MissingField(_))));
+        assert!(matches!(
+            write(vec![payment(25.5, None)]),
+            Err(ParseError::MissingField(_))
+        ));
+        assert!(matches!(
+            write(vec![payment(0.0, Some("DE02120300000000202051"))]),
+            Err(ParseError::InvalidFieldValue { .. })
+        ));
+    }
+}
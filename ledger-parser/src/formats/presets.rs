@@ -0,0 +1,114 @@
+use std::io::Write;
+
+use crate::formats::beancount::BeancountProfile;
+use crate::formats::csv_profile::CsvProfile;
+use crate::formats::datev::DatevProfile;
+use crate::formats::ledger_journal::LedgerProfile;
+use crate::formats::standard18::Standard18Profile;
+use crate::{ParseError, StatementRef};
+
+/// Name of the QuickBooks Online 3-column bank import preset
+pub const PRESET_QUICKBOOKS_CSV: &str = "quickbooks-csv";
+/// Name of the Xero precoded bank statement import preset
+pub const PRESET_XERO: &str = "xero";
+/// Name of the DATEV booking batch preset
+pub const PRESET_DATEV: &str = "datev";
+/// Name of the UK BACS Standard 18 payment file preset
+pub const PRESET_STANDARD18: &str = "bacs-standard18";
+/// Name of the ledger-cli/hledger plain-text journal preset
+pub const PRESET_LEDGER: &str = "ledger";
+/// Name of the Beancount ledger preset
+pub const PRESET_BEANCOUNT: &str = "beancount";
+
+/// Names of all built-in presets accepted by [`ExportPreset::by_name`].
+pub const PRESET_NAMES: [&str; 6] = [
+    PRESET_QUICKBOOKS_CSV,
+    PRESET_XERO,
+    PRESET_DATEV,
+    PRESET_STANDARD18,
+    PRESET_LEDGER,
+    PRESET_BEANCOUNT,
+];
+
+/// Named export for an accounting tool, bundling layout and conventions.
+///
+/// Simple tools take a flat [`CsvProfile`]; DATEV needs its dedicated
+/// [`DatevProfile`] writer with header block and Windows-1252 encoding, and
+/// legacy UK systems the fixed-width [`Standard18Profile`] file. Plain-text
+/// accounting tools read the double-entry journal of [`LedgerProfile`] or the
+/// [`BeancountProfile`] directives.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportPreset {
+    /// Flat CSV import file
+    Csv(CsvProfile),
+    /// DATEV booking batch
+    Datev(DatevProfile),
+    /// UK BACS Standard 18 payment file
+    Standard18(Standard18Profile),
+    /// ledger-cli/hledger plain-text journal
+    Ledger(LedgerProfile),
+    /// Beancount ledger
+    Beancount(BeancountProfile),
+}
+
+impl ExportPreset {
+    /// Look up a built-in preset by name (case-insensitive, see [`PRESET_NAMES`]).
+    pub fn by_name(name: &str) -> Option<ExportPreset> {
+        match name.to_lowercase().as_str() {
+            PRESET_QUICKBOOKS_CSV => Some(ExportPreset::Csv(CsvProfile::quickbooks())),
+            PRESET_XERO => Some(ExportPreset::Csv(CsvProfile::xero())),
+            PRESET_DATEV => Some(ExportPreset::Datev(DatevProfile::default())),
+            PRESET_STANDARD18 => Some(ExportPreset::Standard18(Standard18Profile::default())),
+            PRESET_LEDGER => Some(ExportPreset::Ledger(LedgerProfile::default())),
+            PRESET_BEANCOUNT => Some(ExportPreset::Beancount(BeancountProfile::default())),
+            _ => None,
+        }
+    }
+
+    /// Preset name
+    pub fn name(&self) -> &str {
+        match self {
+            ExportPreset::Csv(profile) => &profile.name,
+            ExportPreset::Datev(profile) => profile.name(),
+            ExportPreset::Standard18(profile) => profile.name(),
+            ExportPreset::Ledger(profile) => profile.name(),
+            ExportPreset::Beancount(profile) => profile.name(),
+        }
+    }
+
+    /// Write the statement using this preset.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying profile writer.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        match self {
+            ExportPreset::Csv(profile) => profile.write_to(statement, writer),
+            ExportPreset::Datev(profile) => profile.write_to(statement, writer),
+            ExportPreset::Standard18(profile) => profile.write_to(statement, writer),
+            ExportPreset::Ledger(profile) => profile.write_to(statement, writer),
+            ExportPreset::Beancount(profile) => profile.write_to(statement, writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_name() {
+        for name in PRESET_NAMES {
+            let preset = ExportPreset::by_name(name).unwrap();
+            assert_eq!(preset.name(), name);
+        }
+        assert!(matches!(
+            ExportPreset::by_name("DATEV"),
+            Some(ExportPreset::Datev(_))
+        ));
+        assert!(ExportPreset::by_name("lotus-123").is_none());
+    }
+}
@@ -0,0 +1,72 @@
+//! Type conversions from QIF to other formats
+//!
+//! Implements the `From` trait to enable idiomatic conversions between QIF
+//! and other format structures (CAMT.053, CSV, MT940).
+
+use crate::{Camt053Statement, CsvStatement, Mt940Statement, QifStatement, StatementInfo};
+
+/// Convert QIF to CAMT.053 format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<QifStatement> for Camt053Statement {
+    fn from(qif: QifStatement) -> Self {
+        Camt053Statement {
+            account_number: qif.account_number,
+            currency: qif.currency,
+            opening_balance: qif.opening_balance,
+            opening_date: qif.opening_date,
+            opening_indicator: qif.opening_indicator,
+            closing_balance: qif.closing_balance,
+            closing_date: qif.closing_date,
+            closing_indicator: qif.closing_indicator,
+            transactions: qif.transactions,
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        }
+    }
+}
+
+/// Convert QIF to CSV format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<QifStatement> for CsvStatement {
+    fn from(qif: QifStatement) -> Self {
+        CsvStatement {
+            account_number: qif.account_number,
+            currency: qif.currency,
+            opening_balance: qif.opening_balance,
+            opening_date: qif.opening_date,
+            opening_indicator: qif.opening_indicator,
+            closing_balance: qif.closing_balance,
+            closing_date: qif.closing_date,
+            closing_indicator: qif.closing_indicator,
+            transactions: qif.transactions,
+            info: StatementInfo::default(),
+        }
+    }
+}
+
+/// Convert QIF to MT940 format
+///
+/// Performs a direct field-by-field conversion since both structures
+/// share the same data model.
+impl From<QifStatement> for Mt940Statement {
+    fn from(qif: QifStatement) -> Self {
+        Mt940Statement {
+            account_number: qif.account_number,
+            currency: qif.currency,
+            opening_balance: qif.opening_balance,
+            opening_date: qif.opening_date,
+            opening_indicator: qif.opening_indicator,
+            closing_balance: qif.closing_balance,
+            closing_date: qif.closing_date,
+            closing_indicator: qif.closing_indicator,
+            transactions: qif.transactions,
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use crate::formats::paging;
+use crate::{formats::utils, BalanceType, ParseError, Transaction, TransactionType};
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+/// Header line of a bank account section
+const QIF_TYPE_BANK: &str = "!Type:Bank";
+/// Prefix of every section header (`!Type:Bank`, `!Type:Cat`, ...)
+const QIF_TYPE_PREFIX: &str = "!Type:";
+/// Section types holding account transactions; category, class and investment lists are skipped
+const QIF_TRANSACTION_TYPES: [&str; 5] = ["Bank", "Cash", "CCard", "Oth A", "Oth L"];
+/// Header of the optional account description block
+const QIF_ACCOUNT_HEADER: &str = "!Account";
+/// Record terminator
+const QIF_END_OF_RECORD: &str = "^";
+/// Payee Quicken uses for the record carrying the account's starting balance
+const QIF_OPENING_BALANCE_PAYEE: &str = "Opening Balance";
+/// QIF carries no currency; ISO 4217 `XXX` means "no currency"
+pub const QIF_UNKNOWN_CURRENCY: &str = "XXX";
+/// Date format written to QIF files (US month/day/year, as Quicken expects)
+const QIF_DATE_FORMAT: &str = "%m/%d/%Y";
+/// Date formats accepted when parsing, after `'` year separators are normalised
+/// (two-digit years first: `%Y` would read `25` as the year 25)
+const QIF_DATE_FORMATS: [&str; 5] = ["%m/%d/%y", "%m/%d/%Y", "%d.%m.%Y", "%Y-%m-%d", "%m-%d-%Y"];
+
+/// Quicken Interchange Format (QIF) bank statement.
+///
+/// Parses from and writes to the `!Type:Bank` section of QIF exports.
+/// Fields are identical to Mt940/Camt053/CsvStatement for seamless conversions.
+///
+/// QIF has no balances, account number or currency, so they are derived:
+/// - the account number comes from an optional `!Account` block (`N` line)
+/// - the opening balance comes from Quicken's `Opening Balance` record (zero if absent)
+/// - the closing balance is the opening balance plus all transactions
+/// - the currency is [`QIF_UNKNOWN_CURRENCY`]; set it after parsing if known
+///
+/// Records map as `D` → booking date, `T`/`U` → amount (negative = debit),
+/// `P` → counterparty name, `M` → description (payee if no memo) and `N` → reference.
+/// Categories, splits, addresses and cleared flags are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QifStatement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+/// Fields of one `^`-terminated QIF record
+#[derive(Default)]
+struct QifRecord {
+    date: Option<String>,
+    amount: Option<String>,
+    payee: Option<String>,
+    memo: Option<String>,
+    number: Option<String>,
+}
+
+impl QifStatement {
+    /// Parse QIF from any Read source (file, stdin, buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::QifError` if the `!Type:` header is missing, a record
+    /// lacks its date or amount, or a date/amount cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ledger_parser::QifStatement;
+    ///
+    /// let data = "!Type:Bank\nD03/03/2025\nT-379.00\nPCITY OF SPRINGFIELD\nMWater bill\nN1005\n^\n";
+    /// let statement = QifStatement::from_read(&mut data.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(statement.transactions.len(), 1);
+    /// assert_eq!(statement.closing_balance, 379.0);
+    /// ```
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        Self::from_content(&content)
+    }
+
+    /// Parse QIF from a file path.
+    ///
+    /// With the `mmap` cargo feature enabled the file is memory-mapped instead of
+    /// being copied into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened or is not UTF-8,
+    /// otherwise the same errors as [`QifStatement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        utils::with_file_contents(path, Self::from_content)
+    }
+
+    /// Parse QIF from already loaded content
+    fn from_content(content: &str) -> Result<Self, ParseError> {
+        let mut account_number = String::new();
+        let mut in_account_block = false;
+        let mut in_transactions = false;
+        let mut seen_header = false;
+        let mut record = QifRecord::default();
+        let mut opening: Option<(f64, DateTime<FixedOffset>)> = None;
+        let mut transactions = Vec::new();
+
+        for line in content.trim_start_matches('\u{feff}').lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('!') {
+                in_account_block = line.eq_ignore_ascii_case(QIF_ACCOUNT_HEADER);
+                in_transactions = line.strip_prefix(QIF_TYPE_PREFIX).is_some_and(|kind| {
+                    QIF_TRANSACTION_TYPES
+                        .iter()
+                        .any(|known| kind.trim().eq_ignore_ascii_case(known))
+                });
+                seen_header |= in_transactions;
+                record = QifRecord::default();
+                continue;
+            }
+
+            if in_account_block {
+                if let Some(name) = line.strip_prefix('N') {
+                    account_number = name.trim().to_string();
+                }
+                continue;
+            }
+            if !in_transactions {
+                continue;
+            }
+
+            if line == QIF_END_OF_RECORD {
+                let finished = std::mem::take(&mut record);
+                let is_opening = finished.payee.as_deref() == Some(QIF_OPENING_BALANCE_PAYEE)
+                    && opening.is_none()
+                    && transactions.is_empty();
+                let transaction = Self::build_transaction(finished)?;
+                if is_opening {
+                    opening = Some((
+                        paging::signed_transaction_amount(&transaction),
+                        transaction.booking_date,
+                    ));
+                } else {
+                    transactions.push(transaction);
+                }
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let code = chars.next();
+            let value = chars.as_str().trim().to_string();
+            match code {
+                Some('D') => record.date = Some(value),
+                Some('T') | Some('U') => record.amount = Some(value),
+                Some('P') => record.payee = Some(value),
+                Some('M') => record.memo = Some(value),
+                Some('N') => record.number = Some(value),
+                _ => {} // categories, splits, addresses, cleared status
+            }
+        }
+
+        if !seen_header {
+            return Err(ParseError::QifError(
+                "Missing !Type: header for a bank, cash or card account (e.g. !Type:Bank)".into(),
+            ));
+        }
+
+        let first_date = transactions.iter().map(|tx| tx.booking_date).min();
+        let last_date = transactions.iter().map(|tx| tx.booking_date).max();
+        let (opening_signed, opening_date) = match opening {
+            Some(opening) => opening,
+            None => (
+                0.0,
+                first_date.ok_or_else(|| {
+                    ParseError::QifError("Statement contains no transactions".into())
+                })?,
+            ),
+        };
+        let closing_signed = paging::round_cents(
+            opening_signed + transactions.iter().map(paging::booked_amount).sum::<f64>(),
+        );
+        let (opening_balance, opening_indicator) = paging::unsigned_balance(opening_signed);
+        let (closing_balance, closing_indicator) = paging::unsigned_balance(closing_signed);
+
+        let statement = QifStatement {
+            account_number,
+            currency: QIF_UNKNOWN_CURRENCY.to_string(),
+            opening_balance,
+            opening_date,
+            opening_indicator,
+            closing_balance,
+            closing_date: last_date.unwrap_or(opening_date),
+            closing_indicator,
+            transactions,
+        };
+        trace_statement!("parsed statement", "qif", statement);
+
+        Ok(statement)
+    }
+
+    /// Turn a finished record into a transaction
+    fn build_transaction(record: QifRecord) -> Result<Transaction, ParseError> {
+        let date = record
+            .date
+            .ok_or_else(|| ParseError::QifError("Record without D (date) line".into()))?;
+        let amount = record
+            .amount
+            .ok_or_else(|| ParseError::QifError("Record without T (amount) line".into()))?;
+        let amount = Self::parse_qif_amount(&amount)?;
+
+        let transaction_type = if amount < 0.0 {
+            TransactionType::Debit
+        } else {
+            TransactionType::Credit
+        };
+        let description = record
+            .memo
+            .clone()
+            .or_else(|| record.payee.clone())
+            .unwrap_or_default();
+
+        Ok(Transaction {
+            reference: record.number,
+            counterparty_name: record.payee,
+            ..Transaction::new(
+                Self::parse_qif_date(&date)?,
+                amount.abs(),
+                transaction_type,
+                description,
+            )
+        })
+    }
+
+    /// Parse QIF dates such as `03/07/2025`, `3/ 7'25` (Quicken) or `07.03.2025`
+    fn parse_qif_date(date: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+        let normalized = date.replace(' ', "").replace('\'', "/");
+        QIF_DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&normalized, format).ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|naive| DateTime::<FixedOffset>::from_naive_utc_and_offset(naive, Utc.fix()))
+            .ok_or_else(|| ParseError::QifError(format!("Invalid date: {}", date)))
+    }
+
+    /// Parse amounts with `,` or `.` thousands separators; the last separator is the decimal one
+    fn parse_qif_amount(amount: &str) -> Result<f64, ParseError> {
+        let trimmed = amount.trim();
+        let normalized = match (trimmed.rfind(','), trimmed.rfind('.')) {
+            (Some(comma), Some(dot)) if comma > dot => trimmed.replace('.', "").replace(',', "."),
+            (Some(_), Some(_)) => trimmed.replace(',', ""),
+            // A lone comma is a decimal separator only with exactly two digits after it
+            (Some(comma), None) if trimmed.len() - comma == 3 => trimmed.replace(',', "."),
+            (Some(_), None) => trimmed.replace(',', ""),
+            _ => trimmed.to_string(),
+        };
+        normalized
+            .parse::<f64>()
+            .map_err(|_| ParseError::QifError(format!("Invalid amount: {}", amount)))
+    }
+
+    /// Write QIF to any Write destination (file, stdout, buffer).
+    ///
+    /// Writes an `!Account` block when the account number is known, then a
+    /// `!Type:Bank` section. A non-zero opening balance becomes Quicken's
+    /// `Opening Balance` record so that re-parsing yields the same balances.
+    /// Counterparty accounts and the currency have no QIF field and are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        trace_statement!("writing statement", "qif", self);
+        let mut writer = BufWriter::new(writer);
+
+        if !self.account_number.is_empty() {
+            writeln!(writer, "{}", QIF_ACCOUNT_HEADER)?;
+            writeln!(writer, "N{}", single_line(&self.account_number))?;
+            writeln!(writer, "TBank")?;
+            writeln!(writer, "{}", QIF_END_OF_RECORD)?;
+        }
+        writeln!(writer, "{}", QIF_TYPE_BANK)?;
+
+        let opening_signed = paging::signed_amount(self.opening_balance, &self.opening_indicator);
+        if opening_signed != 0.0 {
+            writeln!(writer, "D{}", self.opening_date.format(QIF_DATE_FORMAT))?;
+            writeln!(writer, "T{:.2}", opening_signed)?;
+            writeln!(writer, "P{}", QIF_OPENING_BALANCE_PAYEE)?;
+            writeln!(writer, "{}", QIF_END_OF_RECORD)?;
+        }
+
+        for tx in &self.transactions {
+            writeln!(writer, "D{}", tx.booking_date.format(QIF_DATE_FORMAT))?;
+            writeln!(writer, "T{:.2}", paging::signed_transaction_amount(tx))?;
+            if let Some(payee) = &tx.counterparty_name {
+                writeln!(writer, "P{}", single_line(payee))?;
+            }
+            if !tx.description.is_empty() {
+                writeln!(writer, "M{}", single_line(&tx.description))?;
+            }
+            if let Some(reference) = &tx.reference {
+                writeln!(writer, "N{}", single_line(reference))?;
+            }
+            writeln!(writer, "{}", QIF_END_OF_RECORD)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// QIF fields are single lines
+fn single_line(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    const SAMPLE: &str = "!Account\nNChecking 1234\nTBank\n^\n!Type:Bank\n\
+                          D01/01/2025\nT1,000.00\nPOpening Balance\nL[Checking 1234]\n^\n\
+                          D1/ 5'25\nT-379.00\nCX\nPCITY OF SPRINGFIELD\nMWater bill\nN1005\nLUtilities\n^\n\
+                          D01/10/2025\nU250.50\nT250.50\nPACME Corp\n^\n";
+
+    #[test]
+    fn test_parse_qif() {
+        let statement = QifStatement::from_read(&mut SAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(statement.account_number, "Checking 1234");
+        assert_eq!(statement.currency, QIF_UNKNOWN_CURRENCY);
+        assert_eq!(statement.opening_balance, 1000.0);
+        assert_eq!(statement.opening_indicator, BalanceType::Credit);
+        assert_eq!(statement.closing_balance, 871.5);
+        assert_eq!(
+            statement.closing_date,
+            text::parse_date("2025-01-10").unwrap()
+        );
+        assert_eq!(statement.transactions.len(), 2);
+
+        let water = &statement.transactions[0];
+        assert_eq!(water.booking_date, text::parse_date("2025-01-05").unwrap());
+        assert_eq!(water.amount, 379.0);
+        assert_eq!(water.transaction_type, TransactionType::Debit);
+        assert_eq!(water.description, "Water bill");
+        assert_eq!(water.reference.as_deref(), Some("1005"));
+        assert_eq!(
+            water.counterparty_name.as_deref(),
+            Some("CITY OF SPRINGFIELD")
+        );
+
+        // Without a memo the payee doubles as description
+        assert_eq!(statement.transactions[1].description, "ACME Corp");
+    }
+
+    #[test]
+    fn test_qif_round_trip() {
+        let statement = QifStatement::from_read(&mut SAMPLE.as_bytes()).unwrap();
+        let mut output = Vec::new();
+        statement.write_to(&mut output).unwrap();
+        let written = String::from_utf8(output).unwrap();
+
+        assert!(written.starts_with("!Account\nNChecking 1234\nTBank\n^\n!Type:Bank\n"));
+        assert!(written
+            .contains("D01/05/2025\nT-379.00\nPCITY OF SPRINGFIELD\nMWater bill\nN1005\n^\n"));
+
+        let reparsed = QifStatement::from_read(&mut written.as_bytes()).unwrap();
+        assert_eq!(reparsed, statement);
+    }
+
+    #[test]
+    fn test_parse_qif_amounts() {
+        assert_eq!(
+            QifStatement::parse_qif_amount("-1,234.56").unwrap(),
+            -1234.56
+        );
+        assert_eq!(QifStatement::parse_qif_amount("1.234,56").unwrap(), 1234.56);
+        assert_eq!(QifStatement::parse_qif_amount("12,50").unwrap(), 12.5);
+        assert_eq!(QifStatement::parse_qif_amount("1,000").unwrap(), 1000.0);
+        assert!(QifStatement::parse_qif_amount("abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_qif_errors() {
+        let missing_header = QifStatement::from_read(&mut "D01/01/2025\nT1.00\n^\n".as_bytes());
+        assert!(matches!(missing_header, Err(ParseError::QifError(_))));
+
+        let missing_amount =
+            QifStatement::from_read(&mut "!Type:Bank\nD01/01/2025\n^\n".as_bytes());
+        assert!(matches!(missing_amount, Err(ParseError::QifError(_))));
+
+        let bad_date = QifStatement::from_read(&mut "!Type:Bank\nD31/31/2025\nT1\n^\n".as_bytes());
+        assert!(matches!(bad_date, Err(ParseError::QifError(_))));
+    }
+}
@@ -0,0 +1,517 @@
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+use crate::formats::fixedwidth::{alpha, numeric, restrict_charset, FieldSpec, RecordLayout};
+use crate::formats::presets::PRESET_STANDARD18;
+use crate::{ParseError, StatementRef, Transaction, TransactionType};
+
+/// Transaction code of a bank giro credit (money paid to the destination account)
+const CODE_CREDIT: &str = "99";
+/// Transaction code of a direct debit (money collected from the destination account)
+const CODE_DEBIT: &str = "17";
+/// Destination account type written on every record
+const ACCOUNT_TYPE: &str = "0";
+/// Reference written on contra records
+const CONTRA_REFERENCE: &str = "CONTRA";
+/// Width of the amount field in pence
+const AMOUNT_WIDTH: usize = 11;
+/// Width of value totals in the UTL1 label
+const TOTAL_WIDTH: usize = 13;
+/// Width of item counts in the UTL1 label
+const COUNT_WIDTH: usize = 7;
+/// Characters BACS accepts in text fields besides `A-Z` and `0-9`
+const ALLOWED_PUNCTUATION: &str = " .&/-";
+
+/// Payment (detail or contra) record, 100 characters
+const PAYMENT_RECORD: RecordLayout = RecordLayout {
+    name: "Standard 18 payment",
+    fields: &[
+        FieldSpec::numeric("destination_sort_code", 6),
+        FieldSpec::numeric("destination_account", 8),
+        FieldSpec::numeric("account_type", 1),
+        FieldSpec::numeric("transaction_code", 2),
+        FieldSpec::numeric("originating_sort_code", 6),
+        FieldSpec::numeric("originating_account", 8),
+        FieldSpec::alpha("free_format", 4),
+        FieldSpec::numeric("amount", AMOUNT_WIDTH),
+        FieldSpec::alpha("service_user_name", 18),
+        FieldSpec::alpha("reference", 18),
+        FieldSpec::alpha("destination_name", 18),
+    ],
+};
+
+/// Writer profile for UK BACS Standard 18 payment files.
+///
+/// Legacy UK systems take bank movements as a Standard 18 file: 80-character
+/// tape labels (`VOL1`, `HDR1`, `HDR2`, `UHL1`, `EOF1`, `EOF2`, `UTL1`) around
+/// fixed-width 100-character payment records. Money paid out of the statement
+/// account becomes a bank giro credit (`99`) to the counterparty, money received
+/// a direct debit (`17`) from it, and each side is balanced by a contra record
+/// on the statement account.
+///
+/// Sort codes and account numbers are taken from UK IBANs (`GBkkBANKssssssaaaaaaaa`)
+/// or 14-digit sort code + account strings, for the statement account and every
+/// counterparty. Text is upper-cased and limited to the BACS character set.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, Standard18Profile};
+///
+/// let data = ":20:REF\n:25:GB29NWBK60161331926819\n:28C:1/1\n:60F:C250101GBP100,00\n\
+///             :61:250107D25,00NTRFINV7\n:86:Invoice 7\n:62F:C250107GBP75,00\n";
+/// let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// statement.transactions[0].counterparty_account = Some("40051512345678".into());
+///
+/// let profile = Standard18Profile {
+///     service_user_number: "123456".into(),
+///     ..Standard18Profile::default()
+/// };
+/// let mut output = Vec::new();
+/// profile.write_to(statement.as_view(), &mut output).unwrap();
+/// assert!(output.starts_with(b"VOL1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Standard18Profile {
+    /// BACS service user number (SUN), six digits
+    pub service_user_number: String,
+    /// Service user name written on every record (up to 18 characters)
+    pub service_user_name: String,
+    /// Processing day of the file; defaults to the statement's closing date
+    pub processing_date: Option<NaiveDate>,
+    /// Volume/file serial number
+    pub file_serial_number: u32,
+    /// Creation date written to the labels; `None` uses the current date
+    pub created_at: Option<NaiveDate>,
+}
+
+impl Default for Standard18Profile {
+    fn default() -> Self {
+        Standard18Profile {
+            service_user_number: "000000".into(),
+            service_user_name: "LEDGER BRIDGE".into(),
+            processing_date: None,
+            file_serial_number: 1,
+            created_at: None,
+        }
+    }
+}
+
+/// UK sort code and account number
+struct UkAccount {
+    sort_code: String,
+    account_number: String,
+}
+
+impl Standard18Profile {
+    /// Profile name used by [`ExportPreset`](crate::ExportPreset)
+    pub fn name(&self) -> &'static str {
+        PRESET_STANDARD18
+    }
+
+    /// Write the statement as a Standard 18 file.
+    ///
+    /// # Errors
+    /// Returns `ParseError::InvalidFieldValue` if the statement account or a
+    /// counterparty account has no UK sort code/account number, the service user
+    /// number is not six digits, or an amount does not fit the record; returns
+    /// `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(
+        &self,
+        statement: StatementRef<'_>,
+        writer: &mut W,
+    ) -> Result<(), ParseError> {
+        if self.service_user_number.len() != 6
+            || !self.service_user_number.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(ParseError::InvalidFieldValue {
+                field: "service_user_number".into(),
+                value: self.service_user_number.clone(),
+            });
+        }
+        let origin =
+            uk_account(statement.account_number).ok_or_else(|| ParseError::InvalidFieldValue {
+                field: "account_number".into(),
+                value: statement.account_number.to_string(),
+            })?;
+
+        let mut records = Vec::new();
+        let mut credit_total: u64 = 0; // pence paid out via 99 records
+        let mut debit_total: u64 = 0; // pence collected via 17 records
+        for transaction in statement.transactions {
+            let pence = pence(transaction.amount)?;
+            match transaction.transaction_type {
+                TransactionType::Debit => credit_total += pence,
+                TransactionType::Credit => debit_total += pence,
+            }
+            records.push(self.payment_record(&origin, transaction, pence)?);
+        }
+
+        let mut contra_count = (0, 0);
+        if credit_total > 0 {
+            records.push(self.contra_record(&origin, CODE_DEBIT, credit_total)?);
+            contra_count.0 += 1;
+        }
+        if debit_total > 0 {
+            records.push(self.contra_record(&origin, CODE_CREDIT, debit_total)?);
+            contra_count.1 += 1;
+        }
+
+        // UTL1 totals include the contras, so both sides balance
+        let debit_count = count(statement.transactions, TransactionType::Credit) + contra_count.0;
+        let credit_count = count(statement.transactions, TransactionType::Debit) + contra_count.1;
+        let totals = (
+            debit_total + credit_total,
+            credit_total + debit_total,
+            debit_count,
+            credit_count,
+        );
+
+        let processing_date = self
+            .processing_date
+            .unwrap_or_else(|| statement.closing_date.date_naive());
+        let created_at = self.created_at.unwrap_or_else(|| Utc::now().date_naive());
+
+        let mut content = String::new();
+        for label in self.header_labels(created_at, processing_date) {
+            push_record(&mut content, &label);
+        }
+        for record in &records {
+            push_record(&mut content, record);
+        }
+        for label in self.trailer_labels(created_at, totals) {
+            push_record(&mut content, &label);
+        }
+
+        writer.write_all(content.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// VOL1, HDR1, HDR2 and UHL1
+    fn header_labels(&self, created_at: NaiveDate, processing_date: NaiveDate) -> Vec<String> {
+        vec![
+            [
+                "VOL1",
+                &numeric(self.file_serial_number.into(), 6),
+                "0",
+                &alpha("", 30),
+                &alpha("", 6),
+                &alpha(&self.service_user_number, 10),
+                &alpha("", 22),
+                "1",
+            ]
+            .concat(),
+            self.file_label("HDR1", created_at),
+            file_format_label("HDR2"),
+            [
+                "UHL1",
+                &julian(processing_date),
+                "999999",
+                &alpha("", 4),
+                "00",
+                "000000",
+                "1 DAILY  ",
+                "001",
+                &alpha("", 40),
+            ]
+            .concat(),
+        ]
+    }
+
+    /// EOF1, EOF2 and UTL1 with value totals and item counts
+    fn trailer_labels(&self, created_at: NaiveDate, totals: (u64, u64, u64, u64)) -> Vec<String> {
+        let (debit_total, credit_total, debit_count, credit_count) = totals;
+        vec![
+            self.file_label("EOF1", created_at),
+            file_format_label("EOF2"),
+            [
+                "UTL1",
+                &numeric(debit_total, TOTAL_WIDTH),
+                &numeric(credit_total, TOTAL_WIDTH),
+                &numeric(debit_count, COUNT_WIDTH),
+                &numeric(credit_count, COUNT_WIDTH),
+                &alpha("", 36),
+            ]
+            .concat(),
+        ]
+    }
+
+    /// HDR1/EOF1: file identifier, serial, sections and dates
+    fn file_label(&self, kind: &str, created_at: NaiveDate) -> String {
+        let file_id = format!(
+            "A{}S  {}",
+            self.service_user_number, self.service_user_number
+        );
+        [
+            kind,
+            &alpha(&file_id, 17),
+            &numeric(self.file_serial_number.into(), 6),
+            "0001",
+            "0001",
+            &alpha("", 6),
+            &julian(created_at),
+            &julian(created_at),
+            "0",
+            "000000",
+            &alpha("", 13),
+            &alpha("", 7),
+        ]
+        .concat()
+    }
+
+    /// Payment record between the statement account and the counterparty
+    fn payment_record(
+        &self,
+        origin: &UkAccount,
+        transaction: &Transaction,
+        pence: u64,
+    ) -> Result<String, ParseError> {
+        let account = transaction.counterparty_account.as_deref().unwrap_or("");
+        let destination = uk_account(account).ok_or_else(|| ParseError::InvalidFieldValue {
+            field: "counterparty_account".into(),
+            value: account.to_string(),
+        })?;
+        let code = match transaction.transaction_type {
+            TransactionType::Debit => CODE_CREDIT,
+            TransactionType::Credit => CODE_DEBIT,
+        };
+        let reference = transaction
+            .reference
+            .as_deref()
+            .unwrap_or(&transaction.description);
+
+        self.record(
+            &destination,
+            code,
+            origin,
+            pence,
+            reference,
+            transaction.counterparty_name.as_deref().unwrap_or(""),
+        )
+    }
+
+    /// Contra record balancing one side of the file on the statement account
+    fn contra_record(
+        &self,
+        origin: &UkAccount,
+        code: &str,
+        pence: u64,
+    ) -> Result<String, ParseError> {
+        self.record(
+            origin,
+            code,
+            origin,
+            pence,
+            CONTRA_REFERENCE,
+            &self.service_user_name,
+        )
+    }
+
+    /// One 100-character payment record
+    fn record(
+        &self,
+        destination: &UkAccount,
+        code: &str,
+        origin: &UkAccount,
+        pence: u64,
+        reference: &str,
+        destination_name: &str,
+    ) -> Result<String, ParseError> {
+        PAYMENT_RECORD.render(&[
+            &destination.sort_code,
+            &destination.account_number,
+            ACCOUNT_TYPE,
+            code,
+            &origin.sort_code,
+            &origin.account_number,
+            "",
+            &pence.to_string(),
+            &bacs_text(&self.service_user_name),
+            &bacs_text(reference),
+            &bacs_text(destination_name),
+        ])
+    }
+}
+
+/// HDR2/EOF2: fixed record format, block length 2000 and record length 100
+fn file_format_label(kind: &str) -> String {
+    [
+        kind,
+        "F",
+        &numeric(2000, 5),
+        &numeric(PAYMENT_RECORD.length() as u64, 5),
+        &alpha("", 35),
+        "00",
+        &alpha("", 28),
+    ]
+    .concat()
+}
+
+/// Append a record terminated by CRLF
+fn push_record(content: &mut String, record: &str) {
+    content.push_str(record);
+    content.push_str("\r\n");
+}
+
+/// Number of transactions of the given type
+fn count(transactions: &[Transaction], transaction_type: TransactionType) -> u64 {
+    transactions
+        .iter()
+        .filter(|tx| tx.transaction_type == transaction_type)
+        .count() as u64
+}
+
+/// Amount in pence, rejecting values that do not fit the 11-digit field
+fn pence(amount: f64) -> Result<u64, ParseError> {
+    let pence = (amount.abs() * 100.0).round();
+    if pence >= 10f64.powi(AMOUNT_WIDTH as i32) {
+        return Err(ParseError::InvalidFieldValue {
+            field: "amount".into(),
+            value: amount.to_string(),
+        });
+    }
+    Ok(pence as u64)
+}
+
+/// Sort code and account number from a UK IBAN or a 14-digit sort code + account string
+fn uk_account(account: &str) -> Option<UkAccount> {
+    let compact: String = account
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    let digits = match compact.strip_prefix("GB") {
+        // GBkk + 4-letter bank code + 6-digit sort code + 8-digit account
+        Some(_) if compact.len() == 22 => compact.get(8..)?.to_string(),
+        _ => compact,
+    };
+    if digits.len() != 14 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(UkAccount {
+        sort_code: digits[..6].to_string(),
+        account_number: digits[6..].to_string(),
+    })
+}
+
+/// BACS date: a space followed by the two-digit year and day of the year
+fn julian(date: NaiveDate) -> String {
+    date.format(" %y%j").to_string()
+}
+
+/// Upper-case `text` and replace characters outside the BACS set with spaces
+fn bacs_text(text: &str) -> String {
+    restrict_charset(text, ALLOWED_PUNCTUATION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::fixtures;
+    use crate::Camt053Statement;
+
+    /// Length of the VOL/HDR/UHL/EOF/UTL label records
+    const LABEL_LENGTH: usize = 80;
+
+    fn statement() -> Camt053Statement {
+        let tx = |amount, transaction_type, account: &str| Transaction {
+            reference: Some("inv-7/2025".into()),
+            counterparty_name: Some("Acme Ltd".into()),
+            counterparty_account: Some(account.into()),
+            ..fixtures::transaction(amount, transaction_type, "Invoice 7")
+        };
+        Camt053Statement {
+            account_number: "GB29 NWBK 6016 1331 9268 19".into(),
+            currency: "GBP".into(),
+            ..fixtures::statement(vec![
+                tx(1100.0, TransactionType::Credit, "40-05-15 12345678"),
+                tx(25.5, TransactionType::Debit, "GB33BUKB20201555555555"),
+            ])
+        }
+    }
+
+    fn profile() -> Standard18Profile {
+        Standard18Profile {
+            service_user_number: "123456".into(),
+            service_user_name: "Ledger Bridge Ltd".into(),
+            created_at: NaiveDate::from_ymd_opt(2025, 4, 1),
+            ..Standard18Profile::default()
+        }
+    }
+
+    #[test]
+    fn test_write_standard18() {
+        let mut output = Vec::new();
+        profile()
+            .write_to(statement().as_view(), &mut output)
+            .unwrap();
+        let content = String::from_utf8(output).unwrap();
+        let records: Vec<&str> = content.split_terminator("\r\n").collect();
+
+        let kinds: Vec<&str> = records.iter().map(|record| &record[..4]).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "VOL1", "HDR1", "HDR2", "UHL1", "4005", "2020", "6016", "6016", "EOF1", "EOF2",
+                "UTL1"
+            ]
+        );
+        for record in &records {
+            let expected = if record.starts_with(char::is_numeric) {
+                PAYMENT_RECORD.length()
+            } else {
+                LABEL_LENGTH
+            };
+            assert_eq!(record.len(), expected, "{:?}", record);
+        }
+
+        assert_eq!(
+            records[4],
+            [
+                "400515",
+                "12345678",
+                "0",
+                "17",
+                "601613",
+                "31926819",
+                "    ",
+                "00000110000",
+                "LEDGER BRIDGE LTD ",
+                "INV-7/2025        ",
+                "ACME LTD          ",
+            ]
+            .concat()
+        );
+        assert_eq!(&records[5][..17], "20201555555555099");
+        // Contras: the 99 credit to Barclays is balanced by a 17 on our account, and vice versa
+        assert_eq!(&records[6][..17], "60161331926819017");
+        assert_eq!(&records[6][35..46], "00000002550");
+        assert_eq!(&records[6][64..82], "CONTRA            ");
+        assert_eq!(&records[7][..17], "60161331926819099");
+        assert_eq!(&records[7][35..46], "00000110000");
+        assert_eq!(&records[3][4..10], " 25090");
+        assert_eq!(
+            &records[10][4..44],
+            ["0000000112550", "0000000112550", "0000002", "0000002"].concat()
+        );
+    }
+
+    #[test]
+    fn test_non_uk_account_is_an_error() {
+        let mut statement = statement();
+        statement.transactions[0].counterparty_account = Some("DE89370400440532013000".into());
+        let result = profile().write_to(statement.as_view(), &mut Vec::new());
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidFieldValue { field, .. }) if field == "counterparty_account"
+        ));
+
+        let invalid_sun = Standard18Profile {
+            service_user_number: "12AB".into(),
+            ..profile()
+        };
+        let result = invalid_sun.write_to(self::statement().as_view(), &mut Vec::new());
+        assert!(matches!(result, Err(ParseError::InvalidFieldValue { .. })));
+    }
+}
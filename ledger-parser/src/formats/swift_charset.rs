@@ -0,0 +1,261 @@
+//! The SWIFT `X` character set of MT940 output
+//!
+//! SWIFT gateways only accept the `X` set in MT messages: Latin letters,
+//! digits, space and `/ - ? : ( ) . , ' +`. Descriptions of Russian or German
+//! statements rarely fit in it; [`Mt940Charset`] selects what the writer does
+//! with the characters that do not.
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use crate::error::ParseError;
+use crate::model::{RemittanceInfo, Transaction};
+
+/// Punctuation of the SWIFT `X` character set, besides letters, digits and space
+const X_PUNCTUATION: &str = "/-?:().,'+";
+
+/// Stand-in for a character that has no `X` transliteration
+const REPLACEMENT: char = '.';
+
+/// Cyrillic letters with their Latin transliteration, as Russian banks write
+/// them in SWIFT payments
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "e"),
+    ('ж', "j"),
+    ('з', "z"),
+    ('и', "i"),
+    ('й', "i"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "h"),
+    ('ц', "c"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', "'"),
+    ('ы', "y"),
+    ('ь', "x"),
+    ('э', "e'"),
+    ('ю', "iu"),
+    ('я', "ia"),
+];
+
+/// Latin letters with diacritics, as German and other European banks write
+/// them without
+const LATIN: &[(char, &str)] = &[
+    ('ä', "ae"),
+    ('ö', "oe"),
+    ('ü', "ue"),
+    ('ß', "ss"),
+    ('à', "a"),
+    ('á', "a"),
+    ('â', "a"),
+    ('ã', "a"),
+    ('å', "a"),
+    ('æ', "ae"),
+    ('ç', "c"),
+    ('è', "e"),
+    ('é', "e"),
+    ('ê', "e"),
+    ('ë', "e"),
+    ('ì', "i"),
+    ('í', "i"),
+    ('î', "i"),
+    ('ï', "i"),
+    ('ñ', "n"),
+    ('ò', "o"),
+    ('ó', "o"),
+    ('ô', "o"),
+    ('õ', "o"),
+    ('ø', "o"),
+    ('œ', "oe"),
+    ('ù', "u"),
+    ('ú', "u"),
+    ('û', "u"),
+    ('ý', "y"),
+    ('ÿ', "y"),
+];
+
+/// What [`Mt940Statement::write_to_with`](crate::Mt940Statement::write_to_with)
+/// does with characters outside the SWIFT `X` set.
+///
+/// Applies to the `:20:`, `:25:`, `:61:` and `:86:` text. Parses from and
+/// displays as its lowercase name (`keep`, `translit`, `strip`, `error`).
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Charset, Mt940Statement, Mt940WriteOptions};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+/// let mut statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// statement.transactions[0].description = "Оплата за обед & кофе".into();
+///
+/// let options = Mt940WriteOptions {
+///     charset: Mt940Charset::Transliterate,
+///     ..Default::default()
+/// };
+/// let mut output = Vec::new();
+/// statement.write_to_with(&mut output, &options).unwrap();
+/// assert!(String::from_utf8(output).unwrap().contains(":86:Oplata za obed . kofe\n"));
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Mt940Charset {
+    /// Write the text unchanged (the historical output)
+    #[default]
+    Keep,
+    /// Transliterate Cyrillic and accented Latin letters (`Ж` to `J`, `ü` to
+    /// `ue`) and replace other characters with `.`
+    #[strum(to_string = "translit", serialize = "transliterate")]
+    Transliterate,
+    /// Leave out every character outside the set
+    Strip,
+    /// Fail with `ParseError::UnexpectedValue` on the first such character
+    Error,
+}
+
+impl Mt940Charset {
+    /// `text` of the `:tag:` field made to fit the `X` set
+    pub(crate) fn apply<'a>(self, text: &'a str, tag: &str) -> Result<Cow<'a, str>, ParseError> {
+        if self == Mt940Charset::Keep || text.chars().all(is_x_char) {
+            return Ok(Cow::Borrowed(text));
+        }
+        match self {
+            Mt940Charset::Keep => Ok(Cow::Borrowed(text)),
+            Mt940Charset::Transliterate => Ok(Cow::Owned(transliterate(text))),
+            Mt940Charset::Strip => Ok(Cow::Owned(text.chars().filter(|&c| is_x_char(c)).collect())),
+            Mt940Charset::Error => Err(ParseError::UnexpectedValue {
+                field: format!(":{}: text", tag),
+                expected: "characters of the SWIFT X set".into(),
+                found: text.into(),
+            }),
+        }
+    }
+
+    /// `transaction` with the text fields the `:86:` layouts write made to fit
+    /// the `X` set, so a layout wraps the text as it will be written
+    pub(crate) fn apply_to_transaction<'a>(
+        self,
+        transaction: &'a Transaction,
+    ) -> Result<Cow<'a, Transaction>, ParseError> {
+        if self == Mt940Charset::Keep {
+            return Ok(Cow::Borrowed(transaction));
+        }
+        let apply = |text: &str| self.apply(text, "86").map(Cow::into_owned);
+        let apply_option = |text: &Option<String>| text.as_deref().map(apply).transpose();
+
+        let mut tx = transaction.clone();
+        tx.description = apply(&tx.description)?;
+        tx.reference = apply_option(&tx.reference)?;
+        tx.end_to_end_id = apply_option(&tx.end_to_end_id)?;
+        tx.mandate_id = apply_option(&tx.mandate_id)?;
+        tx.counterparty_name = apply_option(&tx.counterparty_name)?;
+        tx.counterparty_account = apply_option(&tx.counterparty_account)?;
+        tx.counterparty_bic = apply_option(&tx.counterparty_bic)?;
+        if let Some(address) = &mut tx.counterparty_address {
+            address.town = apply_option(&address.town)?;
+        }
+        match &mut tx.remittance {
+            Some(RemittanceInfo::Unstructured(text)) => *text = apply(text)?,
+            Some(RemittanceInfo::Structured {
+                creditor_ref,
+                additional,
+                ..
+            }) => {
+                *creditor_ref = apply_option(creditor_ref)?;
+                for line in additional {
+                    *line = apply(line)?;
+                }
+            }
+            None => {}
+        }
+        Ok(Cow::Owned(tx))
+    }
+}
+
+/// Whether `c` belongs to the SWIFT `X` character set
+fn is_x_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == ' ' || X_PUNCTUATION.contains(c)
+}
+
+/// `text` with letters transliterated and other characters outside the set replaced
+fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_x_char(c) {
+            result.push(c);
+            continue;
+        }
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let latin = CYRILLIC
+            .iter()
+            .chain(LATIN)
+            .find(|(letter, _)| *letter == lower)
+            .map(|(_, latin)| *latin);
+        match latin {
+            // Capitals stay capitals: the whole replacement in upper-case
+            // words, its first letter before a lower-case one
+            Some(latin) if lower != c => {
+                if chars.peek().is_some_and(|next| next.is_lowercase()) {
+                    let mut letters = latin.chars();
+                    result.extend(letters.next().map(|first| first.to_ascii_uppercase()));
+                    result.push_str(letters.as_str());
+                } else {
+                    result.push_str(&latin.to_ascii_uppercase());
+                }
+            }
+            Some(latin) => result.push_str(latin),
+            None if c.is_whitespace() => result.push(' '),
+            None => result.push(REPLACEMENT),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate_keeps_case() {
+        assert_eq!(transliterate("Щукин ЖКХ ёлка"), "Shchukin JKH elka");
+        assert_eq!(transliterate("Müller & Söhne"), "Mueller . Soehne");
+        assert_eq!(transliterate("Tab\there"), "Tab here");
+    }
+
+    #[test]
+    fn test_policies() {
+        let text = "Счёт 42/A";
+        assert_eq!(Mt940Charset::Keep.apply(text, "86").unwrap(), text);
+        assert_eq!(Mt940Charset::Strip.apply(text, "86").unwrap(), " 42/A");
+        assert!(matches!(
+            Mt940Charset::Error.apply(text, "86"),
+            Err(ParseError::UnexpectedValue { field, .. }) if field == ":86: text"
+        ));
+        assert!(matches!(
+            Mt940Charset::Error.apply("Plain text", "86"),
+            Ok(Cow::Borrowed(_))
+        ));
+    }
+}
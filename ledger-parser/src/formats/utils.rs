@@ -1,45 +1,175 @@
-use chrono::{DateTime, FixedOffset, NaiveDate, Offset, Utc};
+use encoding_rs::{Encoding, UTF_8};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io;
+use std::path::Path;
 
 use crate::{formats::formats_const::*, ParseError};
 
-pub(crate) fn parse_date(date_str: &str) -> Result<DateTime<FixedOffset>, ParseError> {
-    let formats = vec![
-        "%d.%m.%Y",          // e.g., "26.10.2023"
-        "%Y-%m-%d",          // e.g., "2023-10-26"
-        "%Y-%m-%dT%H:%M:%S", // e.g., "2023-10-26T12:00:00"
-    ];
-
-    if let Ok(date) = DateTime::parse_from_rfc3339(date_str) {
-        return Ok(date);
+/// Cell text made safe for spreadsheets: text starting with a formula trigger
+/// (`=`, `+`, `-`, `@`) gets a leading `'`, so it is shown instead of evaluated
+pub(crate) fn escape_formula(text: String) -> String {
+    if text.starts_with(FORMULA_TRIGGERS) {
+        format!("{}{}", FORMULA_ESCAPE, text)
+    } else {
+        text
     }
-    for format in formats {
-        if let Ok(date) = NaiveDate::parse_from_str(date_str, format) {
-            // Construct datetime at midnight UTC+0 (you can change offset)
-            let ndt = date
-                .and_hms_opt(0, 0, 0)
-                .ok_or(ParseError::InvalidFormat("Invalid date".into()))?;
-            return Ok(DateTime::<FixedOffset>::from_naive_utc_and_offset(
-                ndt,
-                Utc.fix(),
-            ));
-        }
+}
+
+/// Whether `text` has the shape of a BIC (ISO 9362): four letters of bank code,
+/// two of country, two alphanumerics of location and an optional branch of three
+pub(crate) fn is_bic(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    matches!(bytes.len(), 8 | 11)
+        && bytes[..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6..]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Whether `account` has the shape of an IBAN as the ISO 20022 schemas define
+/// it (`IBAN2007Identifier`): two letters of country code, two check digits and
+/// up to 30 alphanumerics. Check digits are not verified, so the schema-valid
+/// test IBANs of bank samples count.
+pub(crate) fn is_iban(account: &str) -> bool {
+    let bytes = account.as_bytes();
+    (IBAN_MIN_LENGTH..=IBAN_MAX_LENGTH).contains(&bytes.len())
+        && bytes[..2].iter().all(u8::is_ascii_uppercase)
+        && bytes[2..4].iter().all(u8::is_ascii_digit)
+        && bytes[4..].iter().all(u8::is_ascii_alphanumeric)
+}
+
+/// Run `parse` over the UTF-8 contents of the file at `path`.
+///
+/// With the `mmap` feature the file is memory-mapped and parsed straight from the
+/// mapped pages, avoiding a full copy of multi-hundred-MB statements into a heap
+/// `String`. Without the feature the file is read into memory as usual.
+pub(crate) fn with_file_contents<T, P, F>(path: P, parse: F) -> Result<T, ParseError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&str) -> Result<T, ParseError>,
+{
+    with_file_bytes(path, |bytes| {
+        let content = std::str::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        parse(content)
+    })
+}
+
+/// Run `parse` over the raw contents of the file at `path`, memory-mapped with
+/// the `mmap` feature as in [`with_file_contents`]
+pub(crate) fn with_file_bytes<T, P, F>(path: P, parse: F) -> Result<T, ParseError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&[u8]) -> Result<T, ParseError>,
+{
+    let mut file = File::open(path)?;
+    read_file_bytes(&mut file, parse)
+}
+
+#[cfg(feature = "mmap")]
+fn read_file_bytes<T, F>(file: &mut File, parse: F) -> Result<T, ParseError>
+where
+    F: FnOnce(&[u8]) -> Result<T, ParseError>,
+{
+    // Mapping an empty file fails on some platforms; there is nothing to map anyway
+    if file.metadata()?.len() == 0 {
+        return parse(&[]);
     }
 
-    Err(ParseError::InvalidFormat("Invalid date".into()))
+    // SAFETY: the mapping is read-only and dropped before returning. Concurrent
+    // truncation of the file by another process is outside what we can guard
+    // against, the same caveat every memmap2 user accepts.
+    let mmap = unsafe { memmap2::Mmap::map(&*file)? };
+    parse(&mmap)
 }
 
-pub(crate) fn parse_amount(amount_str: &str) -> Result<f64, ParseError> {
-    let trimmed = amount_str.trim();
-    if trimmed.is_empty() {
-        return Ok(ZERO_AMOUNT);
+#[cfg(not(feature = "mmap"))]
+fn read_file_bytes<T, F>(file: &mut File, parse: F) -> Result<T, ParseError>
+where
+    F: FnOnce(&[u8]) -> Result<T, ParseError>,
+{
+    use std::io::Read;
+
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    parse(&content)
+}
+
+/// Text of `bytes` in the encoding named by `label` (a WHATWG label such as
+/// `windows-1251`); without a label, UTF-8 when the bytes are valid UTF-8 and
+/// `fallback` otherwise. A byte order mark overrides both and is removed.
+///
+/// # Errors
+/// `ParseError::UnexpectedValue` for an unknown label and `ParseError::IoError`
+/// if the bytes are malformed in the chosen encoding.
+#[cfg_attr(not(feature = "sberbank"), allow(dead_code))]
+pub(crate) fn decode<'a>(
+    bytes: &'a [u8],
+    label: Option<&str>,
+    fallback: &'static Encoding,
+) -> Result<Cow<'a, str>, ParseError> {
+    let encoding = match label {
+        Some(label) => Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+            ParseError::UnexpectedValue {
+                field: "encoding".into(),
+                expected: "an encoding label such as utf-8 or windows-1251".into(),
+                found: label.into(),
+            }
+        })?,
+        None if Encoding::for_bom(bytes).is_none() && std::str::from_utf8(bytes).is_err() => {
+            fallback
+        }
+        None => UTF_8,
+    };
+
+    let (content, used, malformed) = encoding.decode(bytes);
+    if malformed {
+        let message = format!("input is not valid {}", used.name());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message).into());
     }
+    Ok(content)
+}
 
-    // Replace comma with dot and remove spaces
-    let normalized = trimmed
-        .replace(DECIMAL_SEPARATOR_COMMA, DECIMAL_SEPARATOR_DOT)
-        .replace(' ', "");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::WINDOWS_1251;
 
-    normalized
-        .parse::<f64>()
-        .map_err(|_| ParseError::CsvError(format!("Invalid amount: {}", amount_str)))
+    #[test]
+    fn test_is_iban_checks_shape() {
+        assert!(is_iban("DE89370400440532013000"));
+        assert!(is_iban("NO9386011117947"));
+        assert!(is_iban("GB29NWBK60161331926819"));
+        // A 20-digit Russian account, lowercase and overlong identifiers
+        assert!(!is_iban("40702810900000012345"));
+        assert!(!is_iban("de89370400440532013000"));
+        assert!(!is_iban("DE8937040044053201300012345678901234"));
+    }
+
+    #[test]
+    fn test_decode_sniffs_and_falls_back() {
+        let (cp1251, _, _) = WINDOWS_1251.encode("Входящий остаток");
+        assert_eq!(
+            decode(&cp1251, None, WINDOWS_1251).unwrap(),
+            "Входящий остаток"
+        );
+        assert_eq!(
+            decode(
+                "\u{feff}Дата".as_bytes(),
+                Some("windows-1251"),
+                WINDOWS_1251
+            )
+            .unwrap(),
+            "Дата"
+        );
+        assert!(matches!(
+            decode(&cp1251, Some("utf-8"), WINDOWS_1251),
+            Err(ParseError::IoError(_))
+        ));
+        assert!(matches!(
+            decode(b"", Some("klingon"), WINDOWS_1251),
+            Err(ParseError::UnexpectedValue { .. })
+        ));
+    }
 }
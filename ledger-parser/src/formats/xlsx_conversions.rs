@@ -0,0 +1,41 @@
+//! Type conversions from Xlsx to other formats
+//!
+//! Implements the `From` trait so XLSX statements can be written out in any
+//! writable format (CAMT.053, CAMT.054, CSV, JSON, MT940, MT942, QIF). XLSX is
+//! read-only, so there are no conversions into XLSX.
+
+use crate::{
+    Camt053Statement, Camt054Statement, CsvStatement, JsonStatement, Mt940Statement, QifStatement,
+    StatementInfo, XlsxStatement,
+};
+
+/// Implements `From<XlsxStatement>` as a direct field-by-field conversion,
+/// since all structures share the same data model.
+/// Fields only the target has follow its name with their value.
+macro_rules! impl_from_xlsx {
+    ($target:ident $(, $field:ident: $value:expr)*) => {
+        impl From<XlsxStatement> for $target {
+            fn from(xlsx: XlsxStatement) -> Self {
+                $target {
+                    account_number: xlsx.account_number,
+                    currency: xlsx.currency,
+                    opening_balance: xlsx.opening_balance,
+                    opening_date: xlsx.opening_date,
+                    opening_indicator: xlsx.opening_indicator,
+                    closing_balance: xlsx.closing_balance,
+                    closing_date: xlsx.closing_date,
+                    closing_indicator: xlsx.closing_indicator,
+                    transactions: xlsx.transactions,
+                    $($field: $value,)*
+                }
+            }
+        }
+    };
+}
+
+impl_from_xlsx!(Camt053Statement, supplementary_data: Vec::new(), balances: Vec::new(), info: StatementInfo::default());
+impl_from_xlsx!(Camt054Statement);
+impl_from_xlsx!(CsvStatement, info: StatementInfo::default());
+impl_from_xlsx!(JsonStatement);
+impl_from_xlsx!(Mt940Statement, balances: Vec::new(), info: StatementInfo::default());
+impl_from_xlsx!(QifStatement);
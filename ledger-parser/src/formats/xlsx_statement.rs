@@ -0,0 +1,182 @@
+use calamine::{Data, Reader, Xlsx};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::path::Path;
+
+use crate::diagnostics::Diagnostics;
+use crate::error::ParseError;
+use crate::formats::csv_statement::CsvStatement;
+use crate::model::{BalanceType, Transaction};
+use crate::quirks::{ParseOptions, Quirk};
+
+/// Date format of Excel date cells handed to the CSV row parser
+const CELL_DATE_FORMAT: &str = "%d.%m.%Y";
+
+/// Excel (XLSX) export of the Sberbank statement layout (requires the `xlsx` cargo feature).
+///
+/// The first worksheet must hold the same rows as
+/// [`CsvStatement`](crate::CsvStatement) expects: header section, transaction
+/// table with separate debit/credit columns and balance footer. Cells are
+/// turned into text (Excel dates as `DD.MM.YYYY`, numbers in plain notation)
+/// and parsed by the CSV row logic, so both exports yield the same statement.
+///
+/// XLSX is read-only; convert into another format to write it out.
+/// Fields are identical to Mt940/Camt053/CsvStatement for seamless conversions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XlsxStatement {
+    /// Account number (IBAN or local format) from the bank statement
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code (e.g., USD, EUR, RUB)
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+    /// List of transactions in chronological order
+    pub transactions: Vec<Transaction>,
+}
+
+impl XlsxStatement {
+    /// Parse an XLSX workbook from any Read source (file, stdin, buffer).
+    ///
+    /// The workbook is buffered in memory since XLSX is a zip archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::XlsxError` if the input is not a readable workbook
+    /// or has no worksheet, otherwise the same errors as
+    /// [`CsvStatement::from_read`](crate::CsvStatement::from_read).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ledger_parser::XlsxStatement;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("statement.xlsx").unwrap();
+    /// let statement = XlsxStatement::from_read(&mut file).unwrap();
+    /// ```
+    pub fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        Self::from_read_with(reader, &ParseOptions::default())
+    }
+
+    /// Parse an XLSX workbook with the bank quirks selected by `options`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`XlsxStatement::from_read`].
+    pub fn from_read_with<R: Read>(
+        reader: &mut R,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+
+        let workbook = Xlsx::new(Cursor::new(content))?;
+        Self::from_workbook(workbook, options)
+    }
+
+    /// Parse an XLSX workbook from a file path.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if the file cannot be opened, otherwise the
+    /// same errors as [`XlsxStatement::from_read`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let workbook = Xlsx::new(BufReader::new(File::open(path)?))?;
+        Self::from_workbook(workbook, &ParseOptions::default())
+    }
+
+    /// Parse the first worksheet of an opened workbook
+    fn from_workbook<RS: Read + std::io::Seek>(
+        mut workbook: Xlsx<RS>,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let range = workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| ParseError::XlsxError("Workbook has no worksheet".into()))??;
+
+        // Leading empty rows and columns are not part of the range; restore them so
+        // cells sit at the positions the CSV row parser expects
+        let (first_row, first_column) = range.start().unwrap_or_default();
+        let padding = vec![String::new(); first_column as usize];
+        let mut records = vec![csv::StringRecord::new(); first_row as usize];
+        for row in range.rows() {
+            let cells = row.iter().map(cell_text);
+            records.push(padding.iter().cloned().chain(cells).collect());
+        }
+
+        // Bank markers are searched in the text of the rows, as in a CSV export
+        let text = records
+            .iter()
+            .map(|record| record.iter().collect::<Vec<_>>().join(","))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let skip_zero_rows = options
+            .active_quirks(&text)
+            .contains(&Quirk::SberbankZeroAmountRows);
+
+        let statement = CsvStatement::from_records(
+            &records,
+            skip_zero_rows,
+            options.missing_balance,
+            &mut Diagnostics::new(options.strict),
+        )?;
+        Ok(XlsxStatement {
+            account_number: statement.account_number,
+            currency: statement.currency,
+            opening_balance: statement.opening_balance,
+            opening_date: statement.opening_date,
+            opening_indicator: statement.opening_indicator,
+            closing_balance: statement.closing_balance,
+            closing_date: statement.closing_date,
+            closing_indicator: statement.closing_indicator,
+            transactions: statement.transactions,
+        })
+    }
+}
+
+/// Text of a cell as it would appear in the CSV export
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::String(text) | Data::DateTimeIso(text) | Data::DurationIso(text) => text.clone(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) => value.to_string(),
+        Data::Bool(value) => value.to_string(),
+        Data::DateTime(date) => date
+            .as_datetime()
+            .map(|date| date.format(CELL_DATE_FORMAT).to_string())
+            .unwrap_or_else(|| date.as_f64().to_string()),
+        Data::Error(_) | Data::Empty => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::{ExcelDateTime, ExcelDateTimeType};
+
+    #[test]
+    fn test_cell_text() {
+        let date = ExcelDateTime::new(45342.0, ExcelDateTimeType::DateTime, false);
+        assert_eq!(cell_text(&Data::DateTime(date)), "20.02.2024");
+        assert_eq!(cell_text(&Data::Float(1540.5)), "1540.5");
+        assert_eq!(cell_text(&Data::Empty), "");
+    }
+
+    #[test]
+    fn test_not_a_workbook() {
+        let result = XlsxStatement::from_read(&mut "Date,Amount\n".as_bytes());
+        assert!(matches!(result, Err(ParseError::XlsxError(_))));
+    }
+}
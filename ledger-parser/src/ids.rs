@@ -0,0 +1,201 @@
+//! Identifiers for generated documents.
+//!
+//! Writers need identifiers the input statement does not carry: the CAMT
+//! group header `MsgId` and statement `Id`, and the MT940 `:20:` transaction
+//! reference. By default every writer keeps its historical identifiers; set
+//! `id_generator` in [`Camt053WriteOptions`](crate::Camt053WriteOptions) or
+//! [`Mt940WriteOptions`](crate::Mt940WriteOptions) to follow an
+//! organization's own numbering instead.
+//!
+//! Built-in strategies are [`SequentialIds`], [`TimestampIds`] and (with the
+//! `uuid` cargo feature) [`UuidIds`]; any `Fn(&IdContext) -> String` closure
+//! is a generator as well. Writers cut identifiers to the length their format
+//! allows (35 characters for CAMT, 16 for MT940 `:20:`).
+
+use chrono::{DateTime, FixedOffset, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Digits of the counter in [`SequentialIds`] identifiers
+const SEQUENCE_WIDTH: usize = 6;
+/// Digits of the per-second counter appended by [`TimestampIds`]
+const TIMESTAMP_COUNTER_WIDTH: usize = 3;
+
+/// Which identifier a writer asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    /// Message identifier: CAMT `GrpHdr/MsgId`, MT940 `:20:`
+    Message,
+    /// Identifier of one statement page: CAMT `Stmt/Id`
+    Statement,
+}
+
+/// What a generator knows about the identifier being requested
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdContext<'a> {
+    /// Requested identifier
+    pub kind: IdKind,
+    /// 1-based page (CAMT `<Stmt>` or MT940 message) the identifier is for
+    pub page: usize,
+    /// Account of the statement being written
+    pub account_number: &'a str,
+    /// Closing date of the statement being written
+    pub closing_date: DateTime<FixedOffset>,
+}
+
+/// Source of identifiers for generated documents.
+///
+/// Generators are shared between writers and threads, so stateful ones keep
+/// their state behind atomics or locks.
+pub trait IdGenerator: Send + Sync {
+    /// Identifier for `context`; every call should return a new identifier
+    fn generate(&self, context: &IdContext<'_>) -> String;
+}
+
+impl<F> IdGenerator for F
+where
+    F: Fn(&IdContext<'_>) -> String + Send + Sync,
+{
+    fn generate(&self, context: &IdContext<'_>) -> String {
+        self(context)
+    }
+}
+
+/// Cheaply clonable handle to an [`IdGenerator`] for use in writer options.
+///
+/// Two handles are equal when they share the same generator. The handle is not
+/// serialized; options read from configuration start without a generator.
+#[derive(Clone)]
+pub struct SharedIdGenerator(Arc<dyn IdGenerator>);
+
+impl SharedIdGenerator {
+    /// Wrap a generator
+    pub fn new<G: IdGenerator + 'static>(generator: G) -> Self {
+        SharedIdGenerator(Arc::new(generator))
+    }
+
+    /// Identifier for `context`
+    pub fn generate(&self, context: &IdContext<'_>) -> String {
+        self.0.generate(context)
+    }
+}
+
+impl fmt::Debug for SharedIdGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedIdGenerator(..)")
+    }
+}
+
+impl PartialEq for SharedIdGenerator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// `prefix` followed by a zero-padded counter (`STMT000001`, `STMT000002`, ...)
+#[derive(Debug)]
+pub struct SequentialIds {
+    prefix: String,
+    next: AtomicU64,
+}
+
+impl SequentialIds {
+    /// Count up from `start`
+    pub fn new(prefix: impl Into<String>, start: u64) -> Self {
+        SequentialIds {
+            prefix: prefix.into(),
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIds {
+    fn generate(&self, _context: &IdContext<'_>) -> String {
+        let number = self.next.fetch_add(1, Ordering::Relaxed);
+        format!("{}{:0width$}", self.prefix, number, width = SEQUENCE_WIDTH)
+    }
+}
+
+/// `prefix` followed by the UTC generation time and a counter
+/// (`BR20250314153000001`), so identifiers stay unique within one second
+#[derive(Debug)]
+pub struct TimestampIds {
+    prefix: String,
+    counter: AtomicU64,
+}
+
+impl TimestampIds {
+    /// Timestamp identifiers starting with `prefix`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        TimestampIds {
+            prefix: prefix.into(),
+            counter: AtomicU64::new(1),
+        }
+    }
+}
+
+impl IdGenerator for TimestampIds {
+    fn generate(&self, _context: &IdContext<'_>) -> String {
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed)
+            % 10u64.pow(TIMESTAMP_COUNTER_WIDTH as u32);
+        format!(
+            "{}{}{:0width$}",
+            self.prefix,
+            Utc::now().format("%Y%m%d%H%M%S"),
+            counter,
+            width = TIMESTAMP_COUNTER_WIDTH
+        )
+    }
+}
+
+/// Random (version 4) UUIDs without hyphens, so they fit the 35-character CAMT limit
+#[cfg(feature = "uuid")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidIds;
+
+#[cfg(feature = "uuid")]
+impl IdGenerator for UuidIds {
+    fn generate(&self, _context: &IdContext<'_>) -> String {
+        uuid::Uuid::new_v4().simple().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn context() -> IdContext<'static> {
+        IdContext {
+            kind: IdKind::Message,
+            page: 1,
+            account_number: "DE89370400440532013000",
+            closing_date: text::parse_date("2025-03-14").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sequential_and_closure_generators() {
+        let sequential = SequentialIds::new("STMT", 41);
+        assert_eq!(sequential.generate(&context()), "STMT000041");
+        assert_eq!(sequential.generate(&context()), "STMT000042");
+
+        let custom = SharedIdGenerator::new(|context: &IdContext<'_>| {
+            format!("{}-{}", &context.account_number[18..], context.page)
+        });
+        assert_eq!(custom.generate(&context()), "3000-1");
+        assert_eq!(custom.clone(), custom);
+        assert_ne!(custom, SharedIdGenerator::new(SequentialIds::new("", 1)));
+    }
+
+    #[test]
+    fn test_timestamp_ids_are_unique() {
+        let timestamps = TimestampIds::new("BR");
+        let first = timestamps.generate(&context());
+        let second = timestamps.generate(&context());
+        assert!(first.starts_with("BR"));
+        assert_eq!(first.len(), 2 + 14 + TIMESTAMP_COUNTER_WIDTH);
+        assert_ne!(first, second);
+    }
+}
@@ -1,13 +1,19 @@
 //! Ledger Bridge Parser Library
 //!
-//! A library for parsing and converting financial data between CSV, MT940, and CAMT.053 formats.
+//! A library for parsing and converting financial data between CSV, MT940, CAMT.053, and QIF formats.
 //!
 //! # Overview
 //!
-//! This library provides parsing and formatting capabilities for three common bank statement formats:
+//! This library provides parsing and formatting capabilities for common bank statement formats:
 //! - **CSV**: Comma-separated values format (e.g., Sberbank export format)
 //! - **MT940**: SWIFT MT940 message format (international banking standard)
+//! - **MT942**: SWIFT MT942 interim transaction report
 //! - **CAMT.053**: ISO 20022 XML format (modern banking standard)
+//! - **CAMT.052**: ISO 20022 intra-day account report (read-only)
+//! - **CAMT.054**: ISO 20022 debit/credit notification
+//! - **QIF**: Quicken Interchange Format (personal finance exports)
+//! - **JSON**: Canonical, versioned JSON serialisation of the unified model
+//! - **BAI2**: Cash management balance reports from US treasury systems (read-only)
 //!
 //! All formats can be converted bidirectionally using the Rust `From` trait.
 //!
@@ -18,6 +24,8 @@
 //! - **Unified data model**: Shared `Transaction` and balance types across all formats
 //! - **Error handling**: Comprehensive `ParseError` type with descriptive messages
 //! - **Serde integration**: All types support serialization/deserialization
+//! - **Secrets-safe tracing**: With the `tracing` feature, parse/write events are
+//!   logged with account numbers masked (see [`MaskedAccount`])
 //!
 //! # Quick Start
 //!
@@ -57,11 +65,11 @@
 //! ## Working with Transactions
 //!
 //! ```no_run
-//! use ledger_parser::{CsvStatement, TransactionType};
+//! use ledger_parser::{Mt940Statement, TransactionType};
 //! use std::fs::File;
 //!
-//! let mut file = File::open("statement.csv").unwrap();
-//! let statement = CsvStatement::from_read(&mut file).unwrap();
+//! let mut file = File::open("statement.mt940").unwrap();
+//! let statement = Mt940Statement::from_read(&mut file).unwrap();
 //!
 //! // Filter credit transactions
 //! let credits: Vec<_> = statement.transactions.iter()
@@ -76,12 +84,23 @@
 //! Each format is represented by its own struct type:
 //! - [`CsvStatement`] - CSV bank statement format
 //! - [`Mt940Statement`] - SWIFT MT940 message format
+//! - [`Mt942Statement`] - SWIFT MT942 interim transaction report
 //! - [`Camt053Statement`] - ISO 20022 CAMT.053 XML format
+//! - [`Camt052Statement`] - ISO 20022 CAMT.052 intra-day report (parse and convert only)
+//! - [`Camt054Statement`] - ISO 20022 CAMT.054 debit/credit notification
+//! - [`JsonStatement`] - Canonical JSON statement format
+//! - [`Bai2Statement`] - BAI2 cash management balance report (parse and convert only)
+//! - `XlsxStatement` - Excel export of the CSV layout (parse and convert only, `xlsx` feature)
 //!
 //! All format structs implement:
 //! - `from_read<R: Read>(&mut R) -> Result<Self, ParseError>` - Parse from any reader
 //! - `write_to<W: Write>(&mut W) -> Result<(), ParseError>` - Write to any writer
 //! - `From<OtherFormat>` - Convert between formats
+//! - `as_view(&self) -> StatementRef<'_>` - Borrow a format-independent read-only view
+//!
+//! [`Statement`] holds any of the writable formats, for code that picks the
+//! input and output format by name at run time; the [`StatementFormat`] trait
+//! covers the same ground for code generic over the format type.
 //!
 //! # Error Handling
 //!
@@ -103,25 +122,157 @@
 
 #![warn(missing_docs)]
 
+mod annotations;
+mod cancel;
+#[cfg(feature = "compression")]
+mod compress;
+mod currency;
+pub mod debug;
+mod detect;
+mod diagnostics;
+mod enrich;
 mod error;
+mod ids;
 mod model;
+mod portfolio;
+mod quirks;
+#[macro_use]
+mod redact;
+mod report;
+mod search;
+mod statement;
+#[cfg(feature = "async")]
+mod stream;
+mod summary;
+pub mod text;
+mod view;
 mod formats {
+    pub(crate) mod bai2_statement;
+    pub(crate) mod beancount;
+    pub(crate) mod camt052_statement;
     pub(crate) mod camt053_statement;
+    pub(crate) mod camt054_statement;
+    pub(crate) mod csv_mapping;
+    pub(crate) mod csv_profile;
     pub(crate) mod csv_statement;
+    #[cfg(feature = "sberbank")]
     pub(crate) mod cvs_const;
+    pub(crate) mod datev;
+    pub(crate) mod fixedwidth;
+    #[cfg(test)]
+    pub(crate) mod fixtures;
     pub(crate) mod formats_const;
+    pub(crate) mod json_statement;
+    pub(crate) mod ledger_journal;
+    pub(crate) mod mt940_dialect;
+    pub(crate) mod mt940_reader;
     pub(crate) mod mt940_statement;
+    pub(crate) mod mt940_tags;
+    pub(crate) mod mt942_statement;
+    pub(crate) mod paging;
+    pub(crate) mod pain001;
+    pub(crate) mod presets;
+    pub(crate) mod qif_statement;
+    pub(crate) mod standard18;
+    pub(crate) mod swift_charset;
     pub(crate) mod utils;
+    #[cfg(feature = "xlsx")]
+    pub(crate) mod xlsx_statement;
 
     // Format conversion modules
+    mod bai2_conversions;
+    mod camt052_conversions;
     mod camt053_conversions;
+    mod camt054_conversions;
+    mod checked_conversions;
     mod csv_conversions;
+    mod json_conversions;
     mod mt940_conversions;
+    mod mt942_conversions;
+    mod qif_conversions;
+    #[cfg(feature = "xlsx")]
+    mod xlsx_conversions;
 }
 
 // Re-export shared types for convenience
-pub use error::ParseError;
-pub use formats::camt053_statement::Camt053Statement;
-pub use formats::csv_statement::CsvStatement;
-pub use formats::mt940_statement::Mt940Statement;
-pub use model::{BalanceType, Transaction, TransactionType};
+pub use annotations::{fingerprints, Annotation, AnnotationStore, ANNOTATION_SCHEMA_VERSION};
+pub use cancel::{Cancellable, CancellationToken};
+#[cfg(feature = "compression")]
+pub use compress::{CompressedWriter, Compression};
+pub use currency::Currency;
+pub use detect::detect_format;
+pub use diagnostics::{ParseStats, ParseWarning};
+pub use enrich::{CounterpartyDirectory, Enricher};
+pub use error::{ErrorLocation, ParseError};
+pub use formats::bai2_statement::Bai2Statement;
+pub use formats::beancount::BeancountProfile;
+pub use formats::camt052_statement::Camt052Statement;
+pub use formats::camt053_statement::{
+    Camt053Profile, Camt053Reader, Camt053Statement, Camt053Version, Camt053WriteOptions,
+};
+pub use formats::camt054_statement::Camt054Statement;
+pub use formats::csv_mapping::{
+    CsvDialect, CsvField, CsvMapping, CSV_PROFILE_ING, CSV_PROFILE_N26, CSV_PROFILE_NAMES,
+    CSV_PROFILE_NORDEA, CSV_PROFILE_REVOLUT, CSV_PROFILE_TINKOFF, CSV_PROFILE_WISE,
+};
+pub use formats::csv_profile::{CsvColumn, CsvProfile};
+pub use formats::csv_statement::{CsvStatement, CsvWriteOptions};
+pub use formats::datev::DatevProfile;
+pub use formats::json_statement::{JsonStatement, JSON_SCHEMA_VERSION};
+pub use formats::ledger_journal::LedgerProfile;
+pub use formats::mt940_dialect::Mt940Dialect;
+pub use formats::mt940_reader::Mt940Reader;
+pub use formats::mt940_statement::{Mt940Statement, Mt940WriteOptions};
+pub use formats::mt940_tags::{Mt940Tag, Mt940Tags};
+pub use formats::mt942_statement::Mt942Statement;
+pub use formats::pain001::Pain001Document;
+pub use formats::presets::{
+    ExportPreset, PRESET_BEANCOUNT, PRESET_DATEV, PRESET_LEDGER, PRESET_NAMES,
+    PRESET_QUICKBOOKS_CSV, PRESET_STANDARD18, PRESET_XERO,
+};
+pub use formats::qif_statement::{QifStatement, QIF_UNKNOWN_CURRENCY};
+pub use formats::standard18::Standard18Profile;
+pub use formats::swift_charset::Mt940Charset;
+#[cfg(feature = "xlsx")]
+pub use formats::xlsx_statement::XlsxStatement;
+#[cfg(feature = "uuid")]
+pub use ids::UuidIds;
+pub use ids::{IdContext, IdGenerator, IdKind, SequentialIds, SharedIdGenerator, TimestampIds};
+pub use model::{
+    Balance, BalanceCode, BalanceType, Charge, EntryStatus, Money, PostalAddress, RemittanceInfo,
+    StatementHeader, StatementInfo, SwiftEntry, Transaction, TransactionDetail, TransactionType,
+};
+pub use portfolio::{Portfolio, PortfolioAccount, Transfer, TRANSFER_MAX_DAYS};
+pub use quirks::{Bank, MissingBalancePolicy, ParseOptions, Quirk};
+pub use redact::{
+    account_masking_enabled, set_account_masking, MaskedAccount, VISIBLE_ACCOUNT_CHARS,
+};
+pub use report::{ConversionReport, FieldLoss, LossKind};
+pub use search::{SearchHit, SearchIndex};
+pub use statement::{Statement, StatementFormat};
+#[cfg(feature = "async")]
+pub use stream::{convert_stream, mt940_entry_encoder, mt940_transactions, DEFAULT_STREAM_BUFFER};
+pub use summary::{
+    AggregateReport, BucketPeriod, Snapshots, StreamSummarizer, SummarySnapshot, Totals,
+    UNCATEGORIZED,
+};
+pub use view::StatementRef;
+
+/// Version of this library, as published
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Cargo features this build of the library was compiled with
+pub fn enabled_features() -> Vec<&'static str> {
+    [
+        ("sberbank", cfg!(feature = "sberbank")),
+        ("mmap", cfg!(feature = "mmap")),
+        ("tracing", cfg!(feature = "tracing")),
+        ("uuid", cfg!(feature = "uuid")),
+        ("async", cfg!(feature = "async")),
+        ("compression", cfg!(feature = "compression")),
+        ("xlsx", cfg!(feature = "xlsx")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
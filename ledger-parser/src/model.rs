@@ -1,5 +1,11 @@
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum_macros::{Display, EnumString};
+
+use crate::currency::Currency;
+use crate::error::ParseError;
+use crate::formats::paging::round_cents;
 
 /// Balance type indicator representing credit or debit position.
 ///
@@ -18,6 +24,52 @@ pub enum BalanceType {
     Debit,
 }
 
+/// Kind of a balance reported beside the opening and closing booked balances.
+///
+/// Serializes, parses from and displays as its ISO 20022 balance type code.
+///
+/// # Format Mappings
+/// - **CAMT.053/052**: `<Bal><Tp><CdOrPrtry><Cd>` of the statement
+/// - **MT940**: `:64:` (closing available) and `:65:` (forward available)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum BalanceCode {
+    /// Funds available at the start of the period (`OPAV`)
+    #[serde(rename = "OPAV")]
+    #[strum(serialize = "OPAV")]
+    OpeningAvailable,
+    /// Funds available at the end of the period (`CLAV`, MT940 `:64:`)
+    #[serde(rename = "CLAV")]
+    #[strum(serialize = "CLAV")]
+    ClosingAvailable,
+    /// Funds available on a future date (`FWAV`, MT940 `:65:`)
+    #[serde(rename = "FWAV")]
+    #[strum(serialize = "FWAV")]
+    ForwardAvailable,
+    /// Booked balance during the period (`ITBD`)
+    #[serde(rename = "ITBD")]
+    #[strum(serialize = "ITBD")]
+    InterimBooked,
+    /// Available balance during the period (`ITAV`)
+    #[serde(rename = "ITAV")]
+    #[strum(serialize = "ITAV")]
+    InterimAvailable,
+}
+
+/// Balance reported beside the opening and closing booked balances, such as
+/// the available or forward available funds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Balance {
+    /// Kind of balance
+    pub code: BalanceCode,
+    /// Balance amount (always positive; see `indicator`)
+    pub amount: f64,
+    /// Credit or debit position
+    pub indicator: BalanceType,
+    /// Date the balance applies to
+    pub date: DateTime<FixedOffset>,
+}
+
 /// Transaction type indicating whether money was received or paid out.
 ///
 /// Used to classify individual transactions as incoming (credit) or outgoing (debit).
@@ -35,6 +87,129 @@ pub enum TransactionType {
     Debit,
 }
 
+/// Booking status of a transaction.
+///
+/// Only booked entries have moved the balance; pending and informational ones
+/// are reported ahead of or beside the booking and must not be reconciled.
+///
+/// # Format Mappings
+/// - **CAMT.053/052/054**: `BOOK`, `PDNG` (or `FUTR`) and `INFO` in the entry's `<Sts>`
+/// - **MT940/CSV and others**: always booked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryStatus {
+    /// Posted to the account (the default)
+    #[default]
+    Booked,
+    /// Announced but not yet posted
+    Pending,
+    /// For information only; never posted
+    Info,
+}
+
+impl EntryStatus {
+    /// Whether this is the default `Booked` status
+    pub fn is_booked(&self) -> bool {
+        *self == EntryStatus::Booked
+    }
+}
+
+/// Amount of money in a currency.
+///
+/// `Money` is a helper for arithmetic, not how statements store amounts:
+/// balances and transaction amounts stay plain unsigned numbers with their
+/// [`BalanceType`] or [`TransactionType`], next to the statement currency and,
+/// for entries in another currency such as card payments abroad, the entry's
+/// own [`Transaction::currency`]. `Money` pairs an amount with its currency so
+/// they cannot be combined across currencies by accident. The amount is signed: credits (incoming money,
+/// credit balances) are positive, debits negative. The currency code is
+/// normalised on construction (see [`Currency::normalize`]), so `eur` and
+/// `EUR` amounts add up.
+///
+/// # Example
+/// ```
+/// use ledger_parser::Money;
+///
+/// let balance = Money::new(100.0, "EUR");
+/// let total = balance.checked_add(&Money::new(-30.25, "EUR")).unwrap();
+/// assert_eq!(total.to_string(), "69.75 EUR");
+/// assert!(balance.checked_add(&Money::new(1.0, "USD")).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Money {
+    /// Signed amount: positive for credits, negative for debits
+    pub amount: f64,
+    /// Three-letter ISO 4217 currency code
+    pub currency: String,
+}
+
+impl Money {
+    /// Amount in `currency`
+    pub fn new(amount: f64, currency: impl AsRef<str>) -> Self {
+        Money {
+            amount,
+            currency: Currency::normalize(currency.as_ref()),
+        }
+    }
+
+    /// Balance of `amount` on the side given by `indicator`
+    pub fn from_balance(amount: f64, indicator: &BalanceType, currency: impl AsRef<str>) -> Self {
+        let amount = match indicator {
+            BalanceType::Credit => amount,
+            BalanceType::Debit => -amount,
+        };
+        Money::new(amount, currency)
+    }
+
+    /// The currency as a known ISO 4217 code.
+    ///
+    /// # Errors
+    /// Returns `ParseError::UnexpectedValue` if the code is not in ISO 4217.
+    pub fn iso_currency(&self) -> Result<Currency, ParseError> {
+        Currency::parse(&self.currency)
+    }
+
+    /// Balance indicator of the amount (zero counts as credit)
+    pub fn indicator(&self) -> BalanceType {
+        if self.amount < 0.0 {
+            BalanceType::Debit
+        } else {
+            BalanceType::Credit
+        }
+    }
+
+    /// Sum of both amounts, rounded to cents.
+    ///
+    /// # Errors
+    /// Returns `ParseError::CurrencyMismatch` if the currencies differ.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, ParseError> {
+        if self.currency != other.currency {
+            return Err(ParseError::CurrencyMismatch {
+                expected: self.currency.clone(),
+                found: other.currency.clone(),
+            });
+        }
+        Ok(Money::new(
+            round_cents(self.amount + other.amount),
+            self.currency.clone(),
+        ))
+    }
+
+    /// Difference of both amounts, rounded to cents.
+    ///
+    /// # Errors
+    /// Returns `ParseError::CurrencyMismatch` if the currencies differ.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, ParseError> {
+        self.checked_add(&Money::new(-other.amount, other.currency.clone()))
+    }
+}
+
+/// Formats as amount with two decimals and currency code, e.g. `-12.50 EUR`
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}", self.amount, self.currency)
+    }
+}
+
 /// Individual transaction entry shared across all statement formats.
 ///
 /// Represents a single financial transaction with all relevant details.
@@ -42,28 +217,41 @@ pub enum TransactionType {
 ///
 /// # Field Details
 /// - **booking_date**: When the transaction was posted to the account
-/// - **value_date**: Optional value date (when funds become available)
+/// - **value_date**: Optional value date (when funds become available); a calendar date,
+///   serialized as `YYYY-MM-DD`
 /// - **amount**: Transaction amount (always positive; see `transaction_type` for direction)
 /// - **transaction_type**: Whether this is incoming (Credit) or outgoing (Debit)
 /// - **description**: Human-readable transaction description
 /// - **reference**: Optional transaction reference or ID
 /// - **counterparty_name**: Optional name of the other party (debtor/creditor)
 /// - **counterparty_account**: Optional account number/IBAN of the other party
+/// - **currency**: Currency of `amount`, only set when it differs from the statement currency
+/// - **original_amount** / **original_currency**: Amount as instructed before conversion,
+///   e.g. the USD price of a card payment booked on a EUR account
+/// - **exchange_rate**: Rate applied to the conversion, as reported by the bank
+/// - **remittance**: Optional structured remittance information (see [`RemittanceInfo`])
+/// - **counterparty_bic** / **counterparty_address**: Optional bank identifier and postal
+///   address of the other party, as needed for sanctions screening
+/// - **end_to_end_id** / **mandate_id**: Optional SEPA end-to-end identification and
+///   direct debit mandate reference, which travel with the payment from the payer
+/// - **status**: Whether the entry is booked, pending or informational (see [`EntryStatus`])
+/// - **is_reversal**: Whether the entry reverses an earlier one
+/// - **supplementary_data**: Raw XML of CAMT `<SplmtryData>` blocks, written back unchanged
 ///
 /// # Example
 /// ```
 /// use ledger_parser::{Transaction, TransactionType};
-/// use chrono::{DateTime, FixedOffset, TimeZone};
+/// use chrono::{FixedOffset, NaiveDate, TimeZone};
 ///
+/// let booking_date = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
 /// let transaction = Transaction {
-///     booking_date: FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap(),
-///     value_date: Some("2025-01-15".to_string()),
-///     amount: 100.50,
-///     transaction_type: TransactionType::Credit,
-///     description: "Payment received".to_string(),
+///     value_date: NaiveDate::from_ymd_opt(2025, 1, 15),
 ///     reference: Some("REF123".to_string()),
 ///     counterparty_name: Some("John Doe".to_string()),
 ///     counterparty_account: Some("GB29NWBK60161331926819".to_string()),
+///     counterparty_bic: Some("NWBKGB2L".to_string()),
+///     end_to_end_id: Some("E2E-2025-03-001".to_string()),
+///     ..Transaction::new(booking_date, 100.50, TransactionType::Credit, "Payment received")
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -71,7 +259,7 @@ pub struct Transaction {
     /// Date when the transaction was posted to the account
     pub booking_date: DateTime<FixedOffset>,
     /// Optional value date (when funds become available)
-    pub value_date: Option<String>,
+    pub value_date: Option<NaiveDate>,
     /// Transaction amount (always positive number)
     pub amount: f64,
     /// Whether this is a credit (incoming) or debit (outgoing) transaction
@@ -84,25 +272,347 @@ pub struct Transaction {
     pub counterparty_name: Option<String>,
     /// Optional account number/IBAN of the other party
     pub counterparty_account: Option<String>,
+    /// Currency of `amount` when it differs from the statement currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Amount as instructed before currency conversion (always positive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_amount: Option<f64>,
+    /// Three-letter ISO 4217 currency code of `original_amount`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_currency: Option<String>,
+    /// Exchange rate applied to convert `original_amount` into `amount`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_rate: Option<f64>,
+    /// Remittance information beyond the free-text `description`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remittance: Option<RemittanceInfo>,
+    /// Optional BIC of the other party's bank
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterparty_bic: Option<String>,
+    /// Optional postal address of the other party
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counterparty_address: Option<PostalAddress>,
+    /// SEPA end-to-end identification assigned by the initiating party
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_to_end_id: Option<String>,
+    /// SEPA direct debit mandate reference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mandate_id: Option<String>,
+    /// Whether the entry is booked, pending or informational
+    #[serde(default, skip_serializing_if = "EntryStatus::is_booked")]
+    pub status: EntryStatus,
+    /// Whether the entry reverses an earlier one, e.g. a returned transfer
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_reversal: bool,
+    /// Bank-proprietary XML of the entry's CAMT `<SplmtryData>` blocks, kept verbatim
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supplementary_data: Vec<String>,
+    /// Payments of a batch booked as this one entry; empty for a single payment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<TransactionDetail>,
+    /// Fees and taxes charged on the transaction, apart from its amount
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub charges: Vec<Charge>,
+    /// Codes and bank references of an MT940 `:61:` line (see [`SwiftEntry`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swift_entry: Option<SwiftEntry>,
+}
+
+impl Transaction {
+    /// Booked transaction with the given date, amount, direction and description
+    /// and no other details; set the rest with struct update syntax
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::{EntryStatus, Transaction, TransactionType};
+    /// use chrono::{FixedOffset, TimeZone};
+    ///
+    /// let date = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+    /// let transaction = Transaction::new(date, 100.50, TransactionType::Credit, "Payment received");
+    /// assert_eq!(transaction.status, EntryStatus::Booked);
+    /// assert_eq!(transaction.counterparty_name, None);
+    /// ```
+    pub fn new(
+        booking_date: DateTime<FixedOffset>,
+        amount: f64,
+        transaction_type: TransactionType,
+        description: impl Into<String>,
+    ) -> Self {
+        Transaction {
+            booking_date,
+            value_date: None,
+            amount,
+            transaction_type,
+            description: description.into(),
+            reference: None,
+            counterparty_name: None,
+            counterparty_account: None,
+            currency: None,
+            original_amount: None,
+            original_currency: None,
+            exchange_rate: None,
+            remittance: None,
+            counterparty_bic: None,
+            counterparty_address: None,
+            end_to_end_id: None,
+            mandate_id: None,
+            status: EntryStatus::Booked,
+            is_reversal: false,
+            supplementary_data: Vec::new(),
+            details: Vec::new(),
+            charges: Vec::new(),
+            swift_entry: None,
+        }
+    }
+
+    /// Signed amount (debits negative) in the transaction's own currency, or
+    /// in the statement currency `currency` when it has none
+    pub fn money(&self, currency: &str) -> Money {
+        let amount = match self.transaction_type {
+            TransactionType::Credit => self.amount,
+            TransactionType::Debit => -self.amount,
+        };
+        Money::new(amount, self.currency.as_deref().unwrap_or(currency))
+    }
+}
+
+/// One payment of a batch booked as a single entry.
+///
+/// The transaction itself carries the entry amount and the details read from
+/// all payments together; `details` keeps each payment apart.
+///
+/// # Format Mappings
+/// - **CAMT.053/052/054**: each `<TxDtls>` of an `<NtryDtls>` with several
+/// - **Other formats**: none; every transaction is a single payment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionDetail {
+    /// Amount of this payment (`AmtDtls/TxAmt/Amt`, or `Amt` in newer versions)
+    pub amount: Option<f64>,
+    /// Transaction reference (`Refs/TxId`)
+    pub reference: Option<String>,
+    /// SEPA end-to-end identification (`Refs/EndToEndId`)
+    pub end_to_end_id: Option<String>,
+    /// SEPA direct debit mandate reference (`Refs/MndtId`)
+    pub mandate_id: Option<String>,
+    /// Name of the other party (debtor for credits, creditor for debits)
+    pub counterparty_name: Option<String>,
+    /// Account number/IBAN of the other party
+    pub counterparty_account: Option<String>,
+    /// Unstructured remittance text (`RmtInf/Ustrd`)
+    pub description: String,
+}
+
+/// Fee or tax a bank charged on a transaction.
+///
+/// Charges are reported beside the booked amount, so the gross (instructed)
+/// and net (booked) amounts can be told apart after conversion.
+///
+/// # Format Mappings
+/// - **CAMT.053/052/054**: each `<Chrgs>` of the entry (`<Chrgs><Rcrd>` in
+///   newer versions), or of its `<TxDtls>` when the entry has none: `Amt`,
+///   `CdtDbtInd`, `Tp/Cd` and `Br`
+/// - **MT940**: each Dutch `/CHGS/` keyword of `:86:`, e.g. `/CHGS/EUR1,50/`
+/// - **Other formats**: none
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Charge {
+    /// Charge amount (always positive)
+    pub amount: f64,
+    /// Three-letter ISO 4217 currency code of `amount`, if given
+    pub currency: Option<String>,
+    /// Debit for a charge paid by the account holder, credit for a refund
+    pub charge_type: TransactionType,
+    /// ISO 20022 charge type code, e.g. `COMM` (commission) or `BRKF` (brokerage fee)
+    pub code: Option<String>,
+    /// Party bearing the charge: `DEBT`, `CRED`, `SHAR` or `SLEV`
+    pub bearer: Option<String>,
+}
+
+/// Parts of an MT940/MT942 `:61:` line beyond the dates, amount and the
+/// owner's reference (which is [`Transaction::reference`]).
+///
+/// # Format Mappings
+/// - **MT940/MT942**: `:61:` as `YYMMDD[MMDD]` `C|D|RC|RD` funds code, amount,
+///   transaction type code, owner's reference, `//` bank reference and, on the
+///   next line, supplementary details
+/// - **Other formats**: none
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwiftEntry {
+    /// Funds code, the third letter of the currency code (e.g. `R` for `CHR`)
+    pub funds_code: Option<char>,
+    /// Transaction type identification code: `N`, `F` or `S` and three
+    /// characters, e.g. `NTRF` (transfer) or `NCHK` (cheque)
+    pub transaction_code: Option<String>,
+    /// Reference of the account servicing institution, after `//`
+    pub bank_reference: Option<String>,
+    /// Supplementary details from the second line of `:61:`
+    pub supplementary_details: Option<String>,
+}
+
+/// Remittance information of a transaction: what the payment settles.
+///
+/// Parsers set `Structured` when the source carries a structured reference
+/// (CAMT `<Strd>`); free text always lands in `Transaction::description` as
+/// well, so consumers that ignore this field lose nothing. `Unstructured`
+/// lets a caller supply remittance text that differs from the description;
+/// the CAMT.053 writer then uses it in place of the description.
+///
+/// # Format Mappings
+/// - **CAMT.053**: `<RmtInf><Ustrd>` and `<RmtInf><Strd>` (`CdtrRefInf/Ref`,
+///   `CdtrRefInf/Tp/CdOrPrtry`, `AddtlRmtInf`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RemittanceInfo {
+    /// Free-text remittance information
+    Unstructured(String),
+    /// Structured creditor reference, e.g. an ISO 11649 `RF` reference
+    Structured {
+        /// Creditor reference (`CdtrRefInf/Ref`)
+        creditor_ref: Option<String>,
+        /// Reference type code, e.g. `SCOR` for a structured communication reference
+        ref_type: Option<String>,
+        /// Additional remittance lines such as invoice numbers (`AddtlRmtInf`)
+        additional: Vec<String>,
+    },
+}
+
+/// Postal address of a party, in the ISO 20022 `PostalAddress` structure.
+///
+/// Banks send either structured parts or free-form `lines`, sometimes both.
+///
+/// # Format Mappings
+/// - **CAMT.053**: `<PstlAdr>` of `<Dbtr>`/`<Cdtr>` (`StrtNm`, `BldgNb`, `PstCd`,
+///   `TwnNm`, `Ctry`, `AdrLine`)
+/// - **MT940**: town only, as the city of the Dutch `/CNTP/` sub-field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostalAddress {
+    /// Street name
+    pub street: Option<String>,
+    /// Building number
+    pub building_number: Option<String>,
+    /// Postal code
+    pub postal_code: Option<String>,
+    /// Town or city
+    pub town: Option<String>,
+    /// Two-letter ISO 3166 country code
+    pub country: Option<String>,
+    /// Free-form address lines
+    #[serde(default)]
+    pub lines: Vec<String>,
+}
+
+impl PostalAddress {
+    /// Whether no part of the address is known
+    pub fn is_empty(&self) -> bool {
+        self.street.is_none()
+            && self.building_number.is_none()
+            && self.postal_code.is_none()
+            && self.town.is_none()
+            && self.country.is_none()
+            && self.lines.is_empty()
+    }
+}
+
+/// Identification and period of a statement, kept for deduplication downstream.
+///
+/// Every field is optional; formats fill in what they carry.
+///
+/// # Format Mappings
+/// - **CAMT.053**: `<Stmt>` `Id`, `StmtPgntn/PgNb`, `ElctrncSeqNb`, `LglSeqNb`, `CreDtTm`,
+///   `FrToDt`, and `Acct/Ownr/Nm` and `Acct/Svcr/FinInstnId/BIC`
+/// - **MT940**: `:20:` as the statement id and `:28C:` as sequence and page number
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatementInfo {
+    /// Statement identification (`Stmt/Id`, MT940 `:20:`)
+    pub statement_id: Option<String>,
+    /// Electronic sequence number (`ElctrncSeqNb`, MT940 `:28C:` statement number)
+    pub sequence_number: Option<u32>,
+    /// Legal sequence number (`LglSeqNb`)
+    pub legal_sequence_number: Option<u32>,
+    /// Page of a statement sent in several parts (`StmtPgntn/PgNb`, MT940 `:28C:` sequence number)
+    pub page_number: Option<u32>,
+    /// When the bank created the statement (`CreDtTm`)
+    pub created_at: Option<DateTime<FixedOffset>>,
+    /// Start of the reporting period (`FrToDt/FrDtTm`)
+    pub from_date: Option<DateTime<FixedOffset>>,
+    /// End of the reporting period (`FrToDt/ToDtTm`)
+    pub to_date: Option<DateTime<FixedOffset>>,
+    /// Name of the account owner (`Acct/Ownr/Nm`)
+    pub account_owner: Option<String>,
+    /// BIC of the bank servicing the account (`Acct/Svcr`)
+    pub servicer_bic: Option<String>,
+}
+
+impl StatementInfo {
+    /// Whether nothing about the statement is known
+    pub fn is_empty(&self) -> bool {
+        *self == StatementInfo::default()
+    }
+}
+
+/// Account, currency and balances of a statement without its transactions.
+///
+/// Returned by `peek_header` (e.g. [`Mt940Statement::peek_header`](crate::Mt940Statement::peek_header)),
+/// which stops reading once the balances are known, for balance monitoring
+/// that does not need the entries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementHeader {
+    /// Account number (IBAN or local format)
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code
+    pub currency: String,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: BalanceType,
+}
+
+impl StatementHeader {
+    /// Signed opening balance in the statement currency
+    pub fn opening_money(&self) -> Money {
+        Money::from_balance(
+            self.opening_balance,
+            &self.opening_indicator,
+            &self.currency,
+        )
+    }
+
+    /// Signed closing balance in the statement currency
+    pub fn closing_money(&self) -> Money {
+        Money::from_balance(
+            self.closing_balance,
+            &self.closing_indicator,
+            &self.currency,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::formats::utils;
+    use crate::text;
 
     use super::*;
 
     #[test]
     fn test_transaction_creation() {
         let tx = Transaction {
-            booking_date: utils::parse_date("2025-01-15").unwrap(),
-            value_date: Some("2025-01-15".into()),
-            amount: 100.50,
-            transaction_type: TransactionType::Credit,
-            description: "Payment received".into(),
+            value_date: NaiveDate::from_ymd_opt(2025, 1, 15),
             reference: Some("REF123".into()),
             counterparty_name: Some("John Doe".into()),
             counterparty_account: Some("IBAN123".into()),
+            ..Transaction::new(
+                text::parse_date("2025-01-15").unwrap(),
+                100.50,
+                TransactionType::Credit,
+                "Payment received",
+            )
         };
         assert_eq!(tx.amount, 100.50);
         assert_eq!(tx.transaction_type, TransactionType::Credit);
@@ -129,19 +639,38 @@ mod tests {
     #[test]
     fn test_transaction_serialization() {
         let tx = Transaction {
-            booking_date: utils::parse_date("2025-01-15").unwrap(),
-            value_date: None,
-            amount: 250.75,
-            transaction_type: TransactionType::Debit,
-            description: "Purchase".into(),
-            reference: None,
-            counterparty_name: None,
-            counterparty_account: None,
+            value_date: NaiveDate::from_ymd_opt(2025, 1, 17),
+            ..Transaction::new(
+                text::parse_date("2025-01-15").unwrap(),
+                250.75,
+                TransactionType::Debit,
+                "Purchase",
+            )
         };
 
         // Test that it can be serialized and deserialized
         let serialized = serde_json::to_string(&tx).unwrap();
+        assert!(serialized.contains(r#""value_date":"2025-01-17""#));
         let deserialized: Transaction = serde_json::from_str(&serialized).unwrap();
         assert_eq!(tx, deserialized);
     }
+
+    #[test]
+    fn test_money_signs_and_currency_checks() {
+        let tx = Transaction::new(
+            text::parse_date("2025-01-15").unwrap(),
+            40.5,
+            TransactionType::Debit,
+            "Card",
+        );
+        let opening = Money::from_balance(10.0, &BalanceType::Credit, "EUR");
+        let closing = opening.checked_add(&tx.money("EUR")).unwrap();
+        assert_eq!(closing, Money::new(-30.5, "EUR"));
+        assert_eq!(closing.indicator(), BalanceType::Debit);
+        assert_eq!(closing.to_string(), "-30.50 EUR");
+        assert!(matches!(
+            opening.checked_sub(&tx.money("USD")),
+            Err(ParseError::CurrencyMismatch { .. })
+        ));
+    }
 }
@@ -0,0 +1,443 @@
+//! Consolidated view over the statements of several accounts.
+//!
+//! Households and companies rarely hold a single account. A [`Portfolio`]
+//! combines statements into per-currency balance timelines, pairs transfers
+//! between the accounts (a debit in one matched by the same credit in another)
+//! and totals income and spending with those transfers left out, since money
+//! moved between one's own accounts is neither.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
+
+use crate::error::ParseError;
+use crate::formats::paging::{booked_amount, round_cents, signed_amount};
+use crate::model::{Transaction, TransactionType};
+use crate::summary::Totals;
+use crate::view::StatementRef;
+
+/// Maximum number of days between the debit and the credit of a transfer
+pub const TRANSFER_MAX_DAYS: i64 = 3;
+
+/// One account of a [`Portfolio`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioAccount {
+    /// Account number (IBAN or local format)
+    pub account_number: String,
+    /// Three-letter ISO 4217 currency code
+    pub currency: String,
+    /// Opening balance (signed: credit positive, debit negative)
+    pub opening_balance: f64,
+    /// Closing balance (signed: credit positive, debit negative)
+    pub closing_balance: f64,
+    /// Totals over the account's transactions, transfers excluded
+    pub totals: Totals,
+    /// Totals over the account's legs of transfers to or from other accounts
+    pub transfers: Totals,
+}
+
+/// Money moved between two accounts of a [`Portfolio`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transfer {
+    /// Account the money left
+    pub from_account: String,
+    /// Account the money arrived in
+    pub to_account: String,
+    /// Three-letter ISO 4217 currency code
+    pub currency: String,
+    /// Amount moved
+    pub amount: f64,
+    /// Booking date of the debit
+    pub debit_date: NaiveDate,
+    /// Booking date of the credit
+    pub credit_date: NaiveDate,
+    /// Index of the debit leg: statement, then transaction within it
+    pub debit: (usize, usize),
+    /// Index of the credit leg: statement, then transaction within it
+    pub credit: (usize, usize),
+    /// Whether the counterparty account of either leg names the other account,
+    /// rather than the pair matching on amount and date alone
+    pub confirmed: bool,
+}
+
+/// Statements of several accounts combined into one view.
+///
+/// - `timeline`: per currency, the combined end-of-day balance of all accounts
+///   in that currency on every day with a balance or booking. An account
+///   contributes its opening balance before its first booking.
+/// - `transfers`: debits paired with a credit of the same amount and currency
+///   in another account, booked at most [`TRANSFER_MAX_DAYS`] apart. A leg whose
+///   counterparty account is one of the portfolio's accounts only pairs with
+///   that account; such confirmed pairs win over closer dates, then the closest
+///   date wins. Every transaction is used once.
+/// - `totals`: per currency, credits and debits of all accounts except transfers,
+///   so money moved between the accounts is not counted as income and expense.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, Portfolio};
+///
+/// let checking = ":20:A\n:25:CHECKING\n:60F:C250301EUR500,00\n\
+///                 :61:250303D200,00NTRFNONREF\n:86:To savings\n\
+///                 :61:250304D50,00NTRFNONREF\n:86:Groceries\n:62F:C250304EUR250,00\n";
+/// let savings = ":20:B\n:25:SAVINGS\n:60F:C250301EUR1000,00\n\
+///                :61:250304C200,00NTRFNONREF\n:86:From checking\n:62F:C250304EUR1200,00\n";
+/// let statements = [
+///     Mt940Statement::from_read(&mut checking.as_bytes()).unwrap(),
+///     Mt940Statement::from_read(&mut savings.as_bytes()).unwrap(),
+/// ];
+///
+/// let portfolio = Portfolio::from_statements(statements.iter().map(|s| s.as_view()));
+/// assert_eq!(portfolio.transfers.len(), 1);
+/// assert_eq!(portfolio.transfers[0].to_account, "SAVINGS");
+/// assert_eq!(portfolio.totals["EUR"].net(), -50.0);
+/// assert_eq!(portfolio.timeline["EUR"].values().last(), Some(&1450.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Portfolio {
+    /// Accounts in input order
+    pub accounts: Vec<PortfolioAccount>,
+    /// Combined end-of-day balance per currency and day
+    pub timeline: BTreeMap<String, BTreeMap<NaiveDate, f64>>,
+    /// Transfers detected between the accounts
+    pub transfers: Vec<Transfer>,
+    /// Totals per currency without the transfers
+    pub totals: BTreeMap<String, Totals>,
+}
+
+/// Transaction of one account, addressed within the portfolio
+struct Booking<'a> {
+    account: usize,
+    index: usize,
+    transaction: &'a Transaction,
+}
+
+impl Portfolio {
+    /// Combine the statements of several accounts
+    pub fn from_statements<'a, I>(statements: I) -> Self
+    where
+        I: IntoIterator<Item = StatementRef<'a>>,
+    {
+        let statements: Vec<StatementRef<'a>> = statements.into_iter().collect();
+
+        let bookings: Vec<Booking<'a>> = statements
+            .iter()
+            .enumerate()
+            .flat_map(|(account, statement)| {
+                statement
+                    .transactions
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, transaction)| Booking {
+                        account,
+                        index,
+                        transaction,
+                    })
+            })
+            .collect();
+        let (transfers, in_transfer) = match_transfers(&statements, &bookings);
+
+        let mut accounts: Vec<PortfolioAccount> = statements
+            .iter()
+            .map(|statement| PortfolioAccount {
+                account_number: statement.account_number.to_string(),
+                currency: statement.currency.to_string(),
+                opening_balance: signed_amount(
+                    statement.opening_balance,
+                    statement.opening_indicator,
+                ),
+                closing_balance: signed_amount(
+                    statement.closing_balance,
+                    statement.closing_indicator,
+                ),
+                totals: Totals::default(),
+                transfers: Totals::default(),
+            })
+            .collect();
+        let mut totals: BTreeMap<String, Totals> = BTreeMap::new();
+        for (booking, in_transfer) in bookings.iter().zip(&in_transfer) {
+            let account = &mut accounts[booking.account];
+            if *in_transfer {
+                account.transfers.add(booking.transaction);
+            } else {
+                account.totals.add(booking.transaction);
+                totals
+                    .entry(account.currency.clone())
+                    .or_default()
+                    .add(booking.transaction);
+            }
+        }
+
+        Portfolio {
+            accounts,
+            timeline: timeline(&statements),
+            transfers,
+            totals,
+        }
+    }
+
+    /// Whether transaction `transaction` of statement `statement` is a leg of a transfer
+    pub fn is_transfer(&self, statement: usize, transaction: usize) -> bool {
+        let leg = (statement, transaction);
+        self.transfers
+            .iter()
+            .any(|transfer| transfer.debit == leg || transfer.credit == leg)
+    }
+
+    /// Write the portfolio as a pretty-printed JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        let mut writer = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| {
+            if e.is_io() {
+                ParseError::from(std::io::Error::from(e))
+            } else {
+                ParseError::JsonError(format!("Failed to write portfolio: {}", e))
+            }
+        })?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Pair debits with credits of another account; returns the transfers and,
+/// per booking, whether it is part of one
+fn match_transfers(
+    statements: &[StatementRef<'_>],
+    bookings: &[Booking<'_>],
+) -> (Vec<Transfer>, Vec<bool>) {
+    let accounts: Vec<String> = statements
+        .iter()
+        .map(|statement| normalize_account(statement.account_number))
+        .collect();
+    // Counterparty account of a booking if it is one of the portfolio's accounts
+    let counterpart = |booking: &Booking<'_>| {
+        booking
+            .transaction
+            .counterparty_account
+            .as_deref()
+            .map(normalize_account)
+            .filter(|account| accounts.contains(account))
+    };
+
+    let mut in_transfer = vec![false; bookings.len()];
+    let mut transfers = Vec::new();
+
+    for (debit_index, debit) in bookings.iter().enumerate() {
+        if debit.transaction.transaction_type != TransactionType::Debit {
+            continue;
+        }
+        let debit_account = &accounts[debit.account];
+        let debit_counterpart = counterpart(debit);
+        let debit_date = debit.transaction.booking_date.date_naive();
+        let currency = statements[debit.account].currency;
+
+        let credit = bookings
+            .iter()
+            .enumerate()
+            .filter(|(index, credit)| {
+                !in_transfer[*index]
+                    && accounts[credit.account] != *debit_account
+                    && credit.transaction.transaction_type == TransactionType::Credit
+                    && statements[credit.account].currency == currency
+                    && round_cents(credit.transaction.amount)
+                        == round_cents(debit.transaction.amount)
+            })
+            .filter_map(|(index, credit)| {
+                let credit_account = &accounts[credit.account];
+                let credit_counterpart = counterpart(credit);
+                // A leg naming a portfolio account only pairs with that account
+                let debit_names = debit_counterpart.as_ref().map(|c| c == credit_account);
+                let credit_names = credit_counterpart.as_ref().map(|c| c == debit_account);
+                if debit_names == Some(false) || credit_names == Some(false) {
+                    return None;
+                }
+                let confirmed = debit_names == Some(true) || credit_names == Some(true);
+                let days = (credit.transaction.booking_date.date_naive() - debit_date)
+                    .num_days()
+                    .abs();
+                (days <= TRANSFER_MAX_DAYS).then_some((!confirmed, days, index))
+            })
+            .min();
+
+        if let Some((unconfirmed, _, credit_index)) = credit {
+            let credit = &bookings[credit_index];
+            in_transfer[debit_index] = true;
+            in_transfer[credit_index] = true;
+            transfers.push(Transfer {
+                from_account: statements[debit.account].account_number.to_string(),
+                to_account: statements[credit.account].account_number.to_string(),
+                currency: currency.to_string(),
+                amount: round_cents(debit.transaction.amount),
+                debit_date,
+                credit_date: credit.transaction.booking_date.date_naive(),
+                debit: (debit.account, debit.index),
+                credit: (credit.account, credit.index),
+                confirmed: !unconfirmed,
+            });
+        }
+    }
+
+    (transfers, in_transfer)
+}
+
+/// Account number without spaces and in upper case, as IBANs are printed in groups
+fn normalize_account(account: &str) -> String {
+    account
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Combined end-of-day balances per currency
+fn timeline(statements: &[StatementRef<'_>]) -> BTreeMap<String, BTreeMap<NaiveDate, f64>> {
+    // End-of-day balance of every account on each of its own days
+    let daily: Vec<BTreeMap<NaiveDate, f64>> = statements
+        .iter()
+        .map(|statement| {
+            let mut balance = signed_amount(statement.opening_balance, statement.opening_indicator);
+            let mut days = BTreeMap::new();
+            days.insert(statement.opening_date.date_naive(), balance);
+            for transaction in statement.transactions {
+                balance = round_cents(balance + booked_amount(transaction));
+                days.insert(transaction.booking_date.date_naive(), balance);
+            }
+            days
+        })
+        .collect();
+
+    let mut timeline: BTreeMap<String, BTreeMap<NaiveDate, f64>> = BTreeMap::new();
+    for (statement, days) in statements.iter().zip(&daily) {
+        let dates = timeline.entry(statement.currency.to_string()).or_default();
+        dates.extend(days.keys().map(|date| (*date, 0.0)));
+    }
+    for (currency, dates) in timeline.iter_mut() {
+        for (date, total) in dates.iter_mut() {
+            let balances = statements
+                .iter()
+                .zip(&daily)
+                .filter(|(statement, _)| statement.currency == currency)
+                .map(|(_, days)| {
+                    // Latest balance on or before `date`, the opening balance before the first day
+                    days.range(..=*date)
+                        .next_back()
+                        .or_else(|| days.iter().next())
+                        .map(|(_, balance)| *balance)
+                        .unwrap_or_default()
+                });
+            *total = round_cents(balances.sum());
+        }
+    }
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EntryStatus, Mt940Statement};
+
+    fn statement(data: &str) -> Mt940Statement {
+        Mt940Statement::from_read(&mut data.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_transfers_match_closest_date_within_window() {
+        let checking = statement(
+            ":20:A\n:25:CHECKING\n:60F:C250301EUR500,00\n\
+             :61:250302D100,00NTRFNONREF\n:86:To savings\n\
+             :61:250320D100,00NTRFNONREF\n:86:Rent\n:62F:C250320EUR300,00\n",
+        );
+        let savings = statement(
+            ":20:B\n:25:SAVINGS\n:60F:C250301EUR0,00\n\
+             :61:250301C100,00NTRFNONREF\n:86:Interest and bonus\n\
+             :61:250303C100,00NTRFNONREF\n:86:From checking\n:62F:C250303EUR200,00\n",
+        );
+        let usd = statement(
+            ":20:C\n:25:BROKER\n:60F:C250301USD0,00\n\
+             :61:250302C100,00NTRFNONREF\n:86:Dividend\n:62F:C250302USD100,00\n",
+        );
+        let portfolio =
+            Portfolio::from_statements([checking.as_view(), savings.as_view(), usd.as_view()]);
+
+        // Both savings credits are a day away from the debit; the first one is taken
+        assert_eq!(portfolio.transfers.len(), 1);
+        let transfer = &portfolio.transfers[0];
+        assert_eq!(transfer.from_account, "CHECKING");
+        assert_eq!(transfer.to_account, "SAVINGS");
+        assert_eq!(
+            transfer.credit_date,
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+
+        assert_eq!(portfolio.totals["EUR"].credit_total, 100.0);
+        assert_eq!(portfolio.totals["EUR"].debit_total, 100.0);
+        assert_eq!(portfolio.totals["USD"].credit_count, 1);
+        assert_eq!(portfolio.accounts[0].totals.debit_count, 1);
+        assert_eq!(portfolio.accounts[0].transfers.debit_total, 100.0);
+        assert!(!transfer.confirmed);
+    }
+
+    #[test]
+    fn test_counterparty_account_confirms_or_rules_out_transfer() {
+        let mut checking = statement(
+            ":20:A\n:25:CHECKING\n:60F:C250301EUR500,00\n\
+             :61:250302D100,00NTRFNONREF\n:86:To savings\n:62F:C250302EUR400,00\n",
+        );
+        let mut savings = statement(
+            ":20:B\n:25:SAVINGS\n:60F:C250301EUR0,00\n\
+             :61:250301C100,00NTRFNONREF\n:86:Refund\n\
+             :61:250303C100,00NTRFNONREF\n:86:From checking\n:62F:C250303EUR200,00\n",
+        );
+        savings.transactions[0].counterparty_account = Some("DE02 1203 0000 0000 2020 51".into());
+        savings.transactions[1].counterparty_account = Some("checking".into());
+
+        // The credit naming CHECKING wins over the equally close refund
+        let portfolio = Portfolio::from_statements([checking.as_view(), savings.as_view()]);
+        assert_eq!(portfolio.transfers.len(), 1);
+        assert_eq!(portfolio.transfers[0].credit, (1, 1));
+        assert!(portfolio.transfers[0].confirmed);
+        assert!(portfolio.is_transfer(0, 0));
+        assert!(portfolio.is_transfer(1, 1));
+        assert!(!portfolio.is_transfer(1, 0));
+        assert_eq!(portfolio.totals["EUR"].credit_total, 100.0);
+
+        // A debit naming another portfolio account never pairs with SAVINGS
+        let broker = statement(":20:C\n:25:BROKER\n:60F:C250301EUR0,00\n:62F:C250301EUR0,00\n");
+        checking.transactions[0].counterparty_account = Some("BROKER".into());
+        let portfolio =
+            Portfolio::from_statements([checking.as_view(), savings.as_view(), broker.as_view()]);
+        assert!(portfolio.transfers.is_empty());
+    }
+
+    #[test]
+    fn test_timeline_combines_balances_per_currency() {
+        let checking = statement(
+            ":20:A\n:25:CHECKING\n:60F:C250301EUR500,00\n\
+             :61:250303D200,00NTRFNONREF\n:86:Card\n:62F:C250303EUR300,00\n",
+        );
+        let credit_card = statement(
+            ":20:B\n:25:CARD\n:60F:D250302EUR50,00\n\
+             :61:250304D25,00NTRFNONREF\n:86:Books\n:62F:D250304EUR75,00\n",
+        );
+        let portfolio = Portfolio::from_statements([checking.as_view(), credit_card.as_view()]);
+
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let timeline = &portfolio.timeline["EUR"];
+        assert_eq!(timeline[&day(1)], 450.0);
+        assert_eq!(timeline[&day(2)], 450.0);
+        assert_eq!(timeline[&day(3)], 250.0);
+        assert_eq!(timeline[&day(4)], 225.0);
+        assert_eq!(portfolio.accounts[1].closing_balance, -75.0);
+
+        let mut pending = credit_card.clone();
+        pending.transactions[0].status = EntryStatus::Pending;
+        let portfolio = Portfolio::from_statements([checking.as_view(), pending.as_view()]);
+        assert_eq!(portfolio.timeline["EUR"][&day(4)], 250.0);
+    }
+}
@@ -0,0 +1,229 @@
+//! Registry of bank-specific workarounds.
+//!
+//! Real statement files deviate from their format specification in small,
+//! bank-specific ways. Instead of burying such hacks in the parsers, each one
+//! is a documented [`Quirk`] owned by the [`Bank`] that needs it. Parsers ask
+//! [`ParseOptions`] which quirks are active for the input: by default those of
+//! the bank detected from the file header (BIC in the SWIFT header blocks or
+//! the bank name in a CSV header), adjusted by explicit enable/disable lists.
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// Bytes at the start of a file searched for bank markers
+const HEADER_SCAN_BYTES: usize = 2048;
+/// Start of the SWIFT text block; markers after it belong to transactions, not the sender
+const SWIFT_TEXT_BLOCK: &str = "{4:";
+
+/// Bank with registered quirks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bank {
+    /// Sberbank (СберБизнес CSV exports; requires the `sberbank` cargo feature)
+    #[cfg(feature = "sberbank")]
+    Sberbank,
+    /// Goldman Sachs Bank USA (MT940)
+    GoldmanSachs,
+}
+
+impl Bank {
+    /// Header markers (BICs, bank names) identifying each bank
+    const MARKERS: &'static [(Bank, &'static [&'static str])] = &[
+        #[cfg(feature = "sberbank")]
+        (Bank::Sberbank, &["СБЕРБАНК", "СберБизнес", "SABRRUMM"]),
+        (Bank::GoldmanSachs, &["GSCRUS3"]),
+    ];
+
+    /// Detect the issuing bank from the header of a statement file.
+    ///
+    /// Only the beginning of the file is searched, and for SWIFT messages only
+    /// the header blocks before `{4:`, so counterparty BICs inside transactions
+    /// never count as the issuer.
+    pub fn detect(content: &str) -> Option<Bank> {
+        let mut end = content.len().min(HEADER_SCAN_BYTES);
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        let header = &content[..end];
+        let header = header
+            .find(SWIFT_TEXT_BLOCK)
+            .map_or(header, |block| &header[..block]);
+
+        Self::MARKERS
+            .iter()
+            .find(|(_, markers)| markers.iter().any(|marker| header.contains(marker)))
+            .map(|(bank, _)| *bank)
+    }
+
+    /// Quirks applied to files of this bank by default
+    pub fn quirks(self) -> &'static [Quirk] {
+        match self {
+            #[cfg(feature = "sberbank")]
+            Bank::Sberbank => &[Quirk::SberbankZeroAmountRows],
+            Bank::GoldmanSachs => &[Quirk::GoldmanSachsEntryDate],
+        }
+    }
+}
+
+/// A documented, bank-specific parsing workaround
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quirk {
+    /// Sberbank CSV exports contain service rows with a date but neither a debit
+    /// nor a credit amount; they are skipped. Without the quirk such rows become
+    /// zero-amount credits.
+    #[cfg(feature = "sberbank")]
+    SberbankZeroAmountRows,
+    /// Goldman Sachs puts the actual posting date in the optional entry date
+    /// (`MMDD`) of `:61:`; it becomes the booking date and the leading `YYMMDD`
+    /// the value date. Without the quirk the leading date is the booking date.
+    GoldmanSachsEntryDate,
+}
+
+impl Quirk {
+    /// Bank the quirk belongs to
+    pub fn bank(self) -> Bank {
+        match self {
+            #[cfg(feature = "sberbank")]
+            Quirk::SberbankZeroAmountRows => Bank::Sberbank,
+            Quirk::GoldmanSachsEntryDate => Bank::GoldmanSachs,
+        }
+    }
+}
+
+/// Options for `from_read_with` parsers.
+///
+/// `Default` detects the bank and applies its quirks, which is what `from_read` does.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseOptions {
+    /// Bank whose quirks apply; `None` detects it from the file header
+    pub bank: Option<Bank>,
+    /// Apply the quirks of the detected (or given) bank
+    pub bank_quirks: bool,
+    /// Quirks applied regardless of the bank
+    pub enable_quirks: Vec<Quirk>,
+    /// Quirks never applied, even if the bank needs them
+    pub disable_quirks: Vec<Quirk>,
+    /// What the Sberbank CSV and XLSX parsers do when the footer lacks the
+    /// opening or closing balance row
+    #[serde(default)]
+    pub missing_balance: MissingBalancePolicy,
+    /// Fail on malformed transaction records instead of skipping them with a
+    /// [`ParseWarning`](crate::ParseWarning)
+    #[serde(default)]
+    pub strict: bool,
+    /// Character encoding of Sberbank CSV input, as a WHATWG label such as
+    /// `windows-1251` or `utf-8`; `None` honours a byte order mark and reads
+    /// anything that is not valid UTF-8 as Windows-1251
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+/// Handling of a statement whose opening or closing balance is missing.
+///
+/// Some Sberbank exports leave out a balance row of the footer. The balance is
+/// dated at the first (opening) or last (closing) transaction, or at the other
+/// balance if there are no transactions. Parses from and displays as `error`,
+/// `derive` or `zero`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum MissingBalancePolicy {
+    /// Fail to parse the statement (the historical behaviour)
+    #[default]
+    #[strum(serialize = "error")]
+    Error,
+    /// Compute the missing balance from the other one and the transactions;
+    /// with both missing, the opening balance is zero
+    #[strum(serialize = "derive")]
+    DeriveFromTransactions,
+    /// Take the missing balance as zero
+    #[strum(serialize = "zero")]
+    AssumeZero,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            bank: None,
+            bank_quirks: true,
+            enable_quirks: Vec::new(),
+            disable_quirks: Vec::new(),
+            missing_balance: MissingBalancePolicy::Error,
+            strict: false,
+            encoding: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options applying no quirks at all and failing on malformed records, for
+    /// strict spec-conformant parsing
+    pub fn strict() -> Self {
+        ParseOptions {
+            bank_quirks: false,
+            strict: true,
+            ..ParseOptions::default()
+        }
+    }
+
+    /// Quirks active for a file with the given `content`
+    pub(crate) fn active_quirks(&self, content: &str) -> Vec<Quirk> {
+        let bank = if self.bank_quirks {
+            self.bank.or_else(|| Bank::detect(content))
+        } else {
+            None
+        };
+
+        bank.map(Bank::quirks)
+            .unwrap_or_default()
+            .iter()
+            .chain(&self.enable_quirks)
+            .filter(|quirk| !self.disable_quirks.contains(quirk))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_bank_from_header_only() {
+        assert_eq!(
+            Bank::detect("{1:F01GSCRUS30XXXX3614000002}{2:I940GSCRUS30XXXXN}{4:\n:20:1\n"),
+            Some(Bank::GoldmanSachs)
+        );
+        // A Goldman Sachs counterparty inside block 4 says nothing about the issuer
+        assert_eq!(
+            Bank::detect("{1:F01INGBNL2AXXXX}{4:\n:86:/CBIC/GSCRUS30XXX\n"),
+            None
+        );
+        #[cfg(feature = "sberbank")]
+        assert_eq!(Bank::detect(",ПАО СБЕРБАНК,,\n"), Some(Bank::Sberbank));
+    }
+
+    #[test]
+    fn test_active_quirks_toggles() {
+        let header = "{1:F01GSCRUS30XXXX}{4:";
+        assert_eq!(
+            ParseOptions::default().active_quirks(header),
+            [Quirk::GoldmanSachsEntryDate]
+        );
+        assert!(ParseOptions::strict().active_quirks(header).is_empty());
+
+        let disabled = ParseOptions {
+            disable_quirks: vec![Quirk::GoldmanSachsEntryDate],
+            ..ParseOptions::default()
+        };
+        assert!(disabled.active_quirks(header).is_empty());
+        let enabled = ParseOptions {
+            enable_quirks: vec![Quirk::GoldmanSachsEntryDate],
+            ..ParseOptions::strict()
+        };
+        assert_eq!(
+            enabled.active_quirks("{1:F01INGBNL2AXXXX}{4:"),
+            [Quirk::GoldmanSachsEntryDate]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Masking of account numbers in diagnostic output.
+//!
+//! # Logging policy
+//!
+//! Statements carry account numbers and counterparty accounts, which finance
+//! teams treat as secrets. The library's `tracing` instrumentation (enabled with
+//! the `tracing` feature) never records them in full:
+//!
+//! - account fields are always emitted through [`MaskedAccount`], which keeps
+//!   only the last [`VISIBLE_ACCOUNT_CHARS`] characters;
+//! - statements and transactions are never logged via their `Debug` output;
+//! - masking is on by default and can only be turned off explicitly with
+//!   [`set_account_masking`]`(false)`, e.g. in a local debugging session.
+//!
+//! Only instrumentation is affected: parsed values and written outputs always
+//! contain the full account numbers.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Number of trailing account characters left visible by [`MaskedAccount`].
+pub const VISIBLE_ACCOUNT_CHARS: usize = 4;
+
+/// Character replacing hidden account characters
+const MASK_CHAR: char = '*';
+
+/// Process-wide masking switch; masking is enabled unless explicitly opted out
+static ACCOUNT_MASKING: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable masking of account numbers in log output.
+///
+/// Masking is enabled by default. Disabling it makes [`MaskedAccount`] print
+/// accounts in full for the whole process, so only do this where logs never
+/// leave the machine.
+pub fn set_account_masking(enabled: bool) {
+    ACCOUNT_MASKING.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether account numbers are currently masked in log output.
+pub fn account_masking_enabled() -> bool {
+    ACCOUNT_MASKING.load(Ordering::Relaxed)
+}
+
+/// Display wrapper that masks an account number for logging.
+///
+/// # Example
+/// ```
+/// use ledger_parser::MaskedAccount;
+///
+/// let masked = MaskedAccount::new("NL81ASNB9999991234");
+/// assert_eq!(masked.to_string(), "**************1234");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaskedAccount<'a>(&'a str);
+
+impl<'a> MaskedAccount<'a> {
+    /// Wrap an account number for masked display.
+    pub fn new(account: &'a str) -> Self {
+        MaskedAccount(account)
+    }
+}
+
+impl fmt::Display for MaskedAccount<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !account_masking_enabled() {
+            return f.write_str(self.0);
+        }
+
+        let total = self.0.chars().count();
+        let hidden = total.saturating_sub(VISIBLE_ACCOUNT_CHARS);
+        // Short values are hidden completely; showing 4 of 5 characters masks nothing
+        let hidden = if hidden <= VISIBLE_ACCOUNT_CHARS {
+            total
+        } else {
+            hidden
+        };
+
+        let visible_start = self
+            .0
+            .char_indices()
+            .nth(hidden)
+            .map_or(self.0.len(), |(index, _)| index);
+        for _ in 0..hidden {
+            write!(f, "{}", MASK_CHAR)?;
+        }
+        f.write_str(&self.0[visible_start..])
+    }
+}
+
+/// Emit a `tracing` event describing a parsed or written statement.
+///
+/// Compiles to nothing without the `tracing` feature. Account numbers only ever
+/// reach the subscriber through [`MaskedAccount`].
+macro_rules! trace_statement {
+    ($action:literal, $format:literal, $statement:expr) => {
+        #[cfg(feature = "tracing")]
+        {
+            let statement = &$statement;
+            tracing::debug!(
+                format = $format,
+                account = %$crate::redact::MaskedAccount::new(&statement.account_number),
+                currency = %statement.currency,
+                transactions = statement.transactions.len(),
+                $action
+            );
+            for transaction in &statement.transactions {
+                tracing::trace!(
+                    format = $format,
+                    booking_date = %transaction.booking_date.format("%Y-%m-%d"),
+                    amount = transaction.amount,
+                    counterparty_account = transaction
+                        .counterparty_account
+                        .as_deref()
+                        .map(|account| $crate::redact::MaskedAccount::new(account).to_string()),
+                    "{} transaction",
+                    $action
+                );
+            }
+        }
+    };
+}
+
+/// Serializes tests touching or relying on the process-wide masking switch
+#[cfg(test)]
+pub(crate) static MASKING_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_account_display_and_opt_out() {
+        let _guard = MASKING_LOCK.lock().unwrap();
+
+        assert_eq!(
+            MaskedAccount::new("NL81ASNB9999991234").to_string(),
+            "**************1234"
+        );
+        assert_eq!(MaskedAccount::new("12345678").to_string(), "********");
+        assert_eq!(MaskedAccount::new("").to_string(), "");
+        assert_eq!(
+            MaskedAccount::new("40702810ёж0000001234").to_string(),
+            "****************1234"
+        );
+
+        set_account_masking(false);
+        let unmasked = MaskedAccount::new("NL81ASNB9999991234").to_string();
+        set_account_masking(true);
+        assert_eq!(unmasked, "NL81ASNB9999991234");
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_never_logs_full_accounts() {
+        use crate::{Camt053Statement, CsvStatement, Mt940Statement};
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0
+                    .lock()
+                    .map_err(|_| io::Error::other("poisoned"))?
+                    .extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let _guard = MASKING_LOCK.lock().unwrap();
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let account = "NL81ASNB9999999999";
+        let counterparty = "DE89370400440532013000";
+        let input = format!(
+            ":20:REF\n:25:{account}\n:28C:1/1\n:60F:C200101EUR100,00\n\
+             :61:200102C50,00NTRFNONREF\n:86:Payment\n:62F:C200102EUR150,00\n"
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut mt940 = Mt940Statement::from_read(&mut input.as_bytes()).unwrap();
+            mt940.transactions[0].counterparty_account = Some(counterparty.into());
+            let camt: Camt053Statement = mt940.clone().into();
+            let csv: CsvStatement = mt940.clone().into();
+            mt940.write_to(&mut Vec::new()).unwrap();
+            camt.write_to(&mut Vec::new()).unwrap();
+            #[cfg(feature = "sberbank")]
+            csv.write_to(&mut Vec::new()).unwrap();
+        });
+
+        let logs = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("**************9999"), "{logs}");
+        assert!(!logs.contains(account), "{logs}");
+        assert!(!logs.contains(counterparty), "{logs}");
+    }
+}
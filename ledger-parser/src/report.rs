@@ -0,0 +1,285 @@
+//! What a conversion lost.
+//!
+//! Every format keeps a different subset of the unified model: CSV has no
+//! counterparty columns, MT940 one date per entry, QIF no currency. Writing a
+//! statement in such a format silently drops what it cannot express.
+//! [`ConversionReport`] compares a statement with its converted form, as the
+//! target format reads it back, and lists each field that went missing or
+//! came back cut short, with the number of transactions affected.
+
+use std::fmt;
+
+use serde::Serialize;
+use strum_macros::Display;
+
+use crate::model::Transaction;
+use crate::view::StatementRef;
+use crate::QIF_UNKNOWN_CURRENCY;
+
+/// Field name of transactions missing from the converted statement
+const TRANSACTIONS_FIELD: &str = "transactions";
+
+/// Tells how the converted transaction lost a field the source one has
+type FieldCheck = fn(&Transaction, &Transaction) -> Option<LossKind>;
+
+/// Transaction fields compared, by name, in [`Transaction`] order; amounts,
+/// directions and booking dates every format keeps
+const TRANSACTION_FIELDS: &[(&str, FieldCheck)] = &[
+    ("value_date", |source, target| {
+        presence(source.value_date.is_some(), target.value_date.is_some())
+    }),
+    ("description", |source, target| {
+        text(Some(&source.description), Some(&target.description))
+    }),
+    ("reference", |source, target| {
+        text(source.reference.as_deref(), target.reference.as_deref())
+    }),
+    ("counterparty_name", |source, target| {
+        text(
+            source.counterparty_name.as_deref(),
+            target.counterparty_name.as_deref(),
+        )
+    }),
+    ("counterparty_account", |source, target| {
+        text(
+            source.counterparty_account.as_deref(),
+            target.counterparty_account.as_deref(),
+        )
+    }),
+    ("currency", |source, target| {
+        text(source.currency.as_deref(), target.currency.as_deref())
+    }),
+    ("original_amount", |source, target| {
+        presence(
+            source.original_amount.is_some(),
+            target.original_amount.is_some(),
+        )
+    }),
+    ("exchange_rate", |source, target| {
+        presence(
+            source.exchange_rate.is_some(),
+            target.exchange_rate.is_some(),
+        )
+    }),
+    ("remittance", |source, target| {
+        presence(source.remittance.is_some(), target.remittance.is_some())
+    }),
+    ("counterparty_bic", |source, target| {
+        text(
+            source.counterparty_bic.as_deref(),
+            target.counterparty_bic.as_deref(),
+        )
+    }),
+    ("counterparty_address", |source, target| {
+        presence(
+            source.counterparty_address.is_some(),
+            target.counterparty_address.is_some(),
+        )
+    }),
+    ("end_to_end_id", |source, target| {
+        text(
+            source.end_to_end_id.as_deref(),
+            target.end_to_end_id.as_deref(),
+        )
+    }),
+    ("mandate_id", |source, target| {
+        text(source.mandate_id.as_deref(), target.mandate_id.as_deref())
+    }),
+    ("status", |source, target| {
+        presence(!source.status.is_booked(), !target.status.is_booked())
+    }),
+    ("is_reversal", |source, target| {
+        presence(source.is_reversal, target.is_reversal)
+    }),
+    ("supplementary_data", |source, target| {
+        presence(
+            !source.supplementary_data.is_empty(),
+            !target.supplementary_data.is_empty(),
+        )
+    }),
+    ("details", |source, target| {
+        presence(!source.details.is_empty(), !target.details.is_empty())
+    }),
+    ("charges", |source, target| {
+        presence(!source.charges.is_empty(), !target.charges.is_empty())
+    }),
+];
+
+/// How a field was lost. Displays as `dropped` or `truncated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LossKind {
+    /// The converted statement has no value where the source has one
+    Dropped,
+    /// The converted value is the start of the source value only
+    Truncated,
+}
+
+/// One field the conversion lost.
+///
+/// Displays as e.g. `counterparty_name dropped from 3 transactions`, or
+/// `account_number dropped` for a statement field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldLoss {
+    /// Name of the [`Transaction`] or statement field, e.g. `value_date`;
+    /// `transactions` for entries missing altogether
+    pub field: &'static str,
+    /// Whether the field was dropped or truncated
+    pub kind: LossKind,
+    /// Transactions that lost the field; zero for a statement field
+    pub transactions: usize,
+}
+
+impl fmt::Display for FieldLoss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.transactions;
+        match (self.field, count) {
+            (TRANSACTIONS_FIELD, _) => write!(f, "{} {} {}", count, noun(count), self.kind),
+            (field, 0) => write!(f, "{} {}", field, self.kind),
+            (field, _) => write!(f, "{} {} from {} {}", field, self.kind, count, noun(count)),
+        }
+    }
+}
+
+/// `transaction` or `transactions`, to go with `count`
+fn noun(count: usize) -> &'static str {
+    if count == 1 {
+        "transaction"
+    } else {
+        "transactions"
+    }
+}
+
+/// Fields a conversion dropped or truncated.
+///
+/// Only information loss is reported: values the target format adds (such as
+/// the `:61:` codes MT940 fills in) or normalises (whitespace, case) are not.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{ConversionReport, Mt940Statement};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102D25,00NTRFNONREF\n:86:Lunch at the office\n:62F:C200102EUR75,00\n";
+/// let source = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+/// let mut target = source.clone();
+/// target.account_number.clear();
+/// target.transactions[0].description = "Lunch".into();
+///
+/// let report = ConversionReport::compare(source.as_view(), target.as_view());
+/// let losses: Vec<_> = report.losses.iter().map(ToString::to_string).collect();
+/// assert_eq!(losses, ["account_number dropped", "description truncated from 1 transaction"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConversionReport {
+    /// Statement fields first, then transaction fields in [`Transaction`] order
+    pub losses: Vec<FieldLoss>,
+}
+
+impl ConversionReport {
+    /// Losses of `target` against `source`, matching transactions by position
+    pub fn compare(source: StatementRef<'_>, target: StatementRef<'_>) -> Self {
+        let mut report = ConversionReport::default();
+        if let Some(kind) = text(Some(source.account_number), Some(target.account_number)) {
+            report.add("account_number", kind, 0);
+        }
+        let currency = Some(target.currency).filter(|currency| *currency != QIF_UNKNOWN_CURRENCY);
+        if let Some(kind) = text(Some(source.currency), currency) {
+            report.add("currency", kind, 0);
+        }
+        if let Some(missing) = source.len().checked_sub(target.len()).filter(|n| *n > 0) {
+            report.add(TRANSACTIONS_FIELD, LossKind::Dropped, missing);
+        }
+
+        for (field, check) in TRANSACTION_FIELDS {
+            for kind in [LossKind::Dropped, LossKind::Truncated] {
+                let count = source
+                    .transactions
+                    .iter()
+                    .zip(target.transactions)
+                    .filter(|(source, target)| check(source, target) == Some(kind))
+                    .count();
+                if count > 0 {
+                    report.add(field, kind, count);
+                }
+            }
+        }
+        report
+    }
+
+    /// Whether the conversion kept every field
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+
+    fn add(&mut self, field: &'static str, kind: LossKind, transactions: usize) {
+        self.losses.push(FieldLoss {
+            field,
+            kind,
+            transactions,
+        });
+    }
+}
+
+/// Loss of a field that is either there or not
+fn presence(source: bool, target: bool) -> Option<LossKind> {
+    (source && !target).then_some(LossKind::Dropped)
+}
+
+/// Loss of a text field, ignoring differences in whitespace
+fn text(source: Option<&str>, target: Option<&str>) -> Option<LossKind> {
+    let source = normalize(source?);
+    let target = normalize(target.unwrap_or_default());
+    if source.is_empty() || target == source {
+        None
+    } else if target.is_empty() {
+        Some(LossKind::Dropped)
+    } else if source.starts_with(&target) {
+        Some(LossKind::Truncated)
+    } else {
+        None
+    }
+}
+
+/// Text with runs of whitespace collapsed to one space
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Statement;
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:2001020102D25,00NTRFINV-1\n:86:Lunch\n:62F:C200102EUR75,00\n";
+
+    #[test]
+    fn test_qif_loses_currency_and_counterparty() {
+        let mut statement = Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+        statement.transactions_mut()[0].counterparty_account =
+            Some("DE89370400440532013000".into());
+        statement.transactions_mut()[0].end_to_end_id = Some("E2E-1".into());
+
+        let (converted, report) = statement.convert_with_report("qif").unwrap();
+        assert_eq!(converted.transactions().len(), 1);
+        let fields: Vec<_> = report.losses.iter().map(|loss| loss.field).collect();
+        assert!(fields.contains(&"currency"));
+        assert!(fields.contains(&"counterparty_account"));
+        assert!(fields.contains(&"end_to_end_id"));
+        assert!(!fields.contains(&"description"));
+    }
+
+    #[test]
+    fn test_lossless_conversion_and_missing_transactions() {
+        let statement = Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+        let (_, report) = statement.convert_with_report("camt053").unwrap();
+        assert!(report.is_lossless(), "{:?}", report);
+
+        let mut target = statement.clone();
+        target.transactions_mut().clear();
+        let report = ConversionReport::compare(statement.as_view(), target.as_view());
+        assert_eq!(report.losses[0].to_string(), "1 transaction dropped");
+    }
+}
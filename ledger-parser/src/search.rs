@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use crate::model::Transaction;
+use crate::view::StatementRef;
+
+/// Weight of a query token that only matches the beginning of an indexed token
+const PREFIX_MATCH_WEIGHT: f64 = 0.5;
+
+/// A transaction matching a search query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchHit<'a> {
+    /// Position of the transaction in the statement
+    pub index: usize,
+    /// Relevance score; higher is better
+    pub score: f64,
+    /// The matching transaction
+    pub transaction: &'a Transaction,
+}
+
+/// In-memory inverted index over transaction descriptions.
+///
+/// Indexes the description, reference and counterparty name of every
+/// transaction. Text is tokenized by splitting on anything that is not a letter
+/// or digit and lowercasing, so `"INV-123"` matches the query `"inv 123"`.
+///
+/// Hits are ranked by TF-IDF: rare tokens (an invoice number) weigh more than
+/// common ones (`"payment"`). A query token that is only a prefix of an indexed
+/// token counts at half weight, which makes incremental search-as-you-type work.
+/// Build the index once with [`SearchIndex::new`] to run many queries; for a
+/// single query `statement.search(..)` is a shortcut.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, SearchIndex};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C200101EUR100,00\n\
+///             :61:200102C50,00NTRFNONREF\n:86:Invoice 123 ACME\n\
+///             :61:200103D10,00NTRFNONREF\n:86:Card payment\n\
+///             :62F:C200103EUR140,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let index = SearchIndex::new(statement.as_view());
+/// let hits = index.search("invoice 123");
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].transaction.amount, 50.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchIndex<'a> {
+    transactions: &'a [Transaction],
+    /// Token -> (transaction index, occurrences of the token in that transaction)
+    postings: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl<'a> SearchIndex<'a> {
+    /// Build an index over the transactions of `statement`.
+    pub fn new(statement: StatementRef<'a>) -> Self {
+        let mut postings: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+
+        for (index, transaction) in statement.transactions.iter().enumerate() {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            let fields = [
+                Some(transaction.description.as_str()),
+                transaction.reference.as_deref(),
+                transaction.counterparty_name.as_deref(),
+            ];
+            for text in fields.into_iter().flatten() {
+                for token in tokenize(text) {
+                    *counts.entry(token).or_insert(0) += 1;
+                }
+            }
+            for (token, count) in counts {
+                postings.entry(token).or_default().push((index, count));
+            }
+        }
+
+        SearchIndex {
+            transactions: statement.transactions,
+            postings,
+        }
+    }
+
+    /// Find transactions matching any token of `query`, best match first.
+    ///
+    /// Ties are broken by statement order. An empty or punctuation-only query
+    /// returns no hits.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'a>> {
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for query_token in tokenize(query) {
+            for (token, postings) in &self.postings {
+                let weight = if *token == query_token {
+                    1.0
+                } else if token.starts_with(&query_token) {
+                    PREFIX_MATCH_WEIGHT
+                } else {
+                    continue;
+                };
+
+                let idf = self.inverse_document_frequency(postings.len());
+                for &(index, count) in postings {
+                    *scores.entry(index).or_insert(0.0) += weight * count as f64 * idf;
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit<'a>> = scores
+            .into_iter()
+            .filter_map(|(index, score)| {
+                self.transactions.get(index).map(|transaction| SearchHit {
+                    index,
+                    score,
+                    transaction,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+        hits
+    }
+
+    /// Smoothed IDF so tokens present in every transaction still score above zero
+    fn inverse_document_frequency(&self, document_frequency: usize) -> f64 {
+        let total = self.transactions.len() as f64;
+        ((total + 1.0) / (document_frequency as f64 + 1.0)).ln() + 1.0
+    }
+}
+
+impl<'a> StatementRef<'a> {
+    /// Search transaction descriptions, building a throwaway [`SearchIndex`].
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'a>> {
+        SearchIndex::new(*self).search(query)
+    }
+}
+
+/// Lowercase alphanumeric tokens of `text`
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+    use crate::{BalanceType, Camt053Statement, StatementInfo, TransactionType};
+
+    fn statement(descriptions: &[&str]) -> Camt053Statement {
+        Camt053Statement {
+            account_number: "DK8030000001234567".into(),
+            currency: "DKK".into(),
+            opening_balance: 0.0,
+            opening_date: text::parse_date("2025-01-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 0.0,
+            closing_date: text::parse_date("2025-01-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: descriptions
+                .iter()
+                .map(|description| {
+                    Transaction::new(
+                        text::parse_date("2025-01-15").unwrap(),
+                        1.0,
+                        TransactionType::Credit,
+                        *description,
+                    )
+                })
+                .collect(),
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize_splits_and_lowercases() {
+        let tokens: Vec<String> = tokenize("INV-123, Оплата/ACME").collect();
+        assert_eq!(tokens, vec!["inv", "123", "оплата", "acme"]);
+    }
+
+    #[test]
+    fn test_search_ranks_rare_tokens_higher() {
+        let statement = statement(&["Payment invoice 123", "Payment invoice 456", "Payment rent"]);
+
+        let hits = statement.search("invoice 123");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].index, 0);
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn test_search_prefix_match_and_empty_query() {
+        let statement = statement(&["Invoice 123", "Rent"]);
+
+        let hits = statement.search("inv");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].transaction.description, "Invoice 123");
+        assert!(statement.search(" -- ").is_empty());
+    }
+}
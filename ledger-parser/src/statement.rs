@@ -0,0 +1,927 @@
+//! Statements whose format is only known at run time.
+//!
+//! Tools that take the input and output format from a command line or a
+//! request all need the same dispatch: parse by format name, keep whichever
+//! statement came out, convert it to the requested output. [`Statement`] does
+//! that once, holding one of the writable format structs; read-only formats
+//! (MT942, CAMT.052, JSON, BAI2, XLSX) are parsed into the writable format
+//! that carries them without loss.
+//!
+//! Code that knows the format at compile time but not which one, e.g. a
+//! function generic over its input, uses the [`StatementFormat`] trait instead.
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use std::io::{Read, Write};
+
+use crate::detect::detect_format;
+use crate::diagnostics::ParseWarning;
+use crate::error::ParseError;
+use crate::formats::paging::{booked_amount, round_cents, signed_amount, unsigned_balance};
+use crate::model::{Money, StatementInfo, Transaction};
+use crate::report::ConversionReport;
+use crate::view::StatementRef;
+#[cfg(feature = "xlsx")]
+use crate::XlsxStatement;
+use crate::{
+    Bai2Statement, Camt052Statement, Camt053Statement, Camt054Statement, CsvDialect, CsvStatement,
+    Currency, JsonStatement, Mt940Statement, Mt942Statement, ParseOptions, QifStatement,
+};
+
+/// Input formats [`Statement::from_read`] understands in this build, for error messages
+const INPUT_FORMATS: &[&str] = &[
+    "csv",
+    "mt940",
+    "mt942",
+    "camt053",
+    "camt052",
+    "camt054",
+    "bai2",
+    "json",
+    "qif",
+    #[cfg(feature = "xlsx")]
+    "xlsx",
+    AUTO_FORMAT,
+];
+/// Input format name that detects the format from the content
+const AUTO_FORMAT: &str = "auto";
+/// Output formats [`Statement::write_to`] understands in this build, for error messages
+const OUTPUT_FORMATS: &[&str] = &[
+    #[cfg(feature = "sberbank")]
+    "csv",
+    "mt940",
+    "mt942",
+    "camt053",
+    "camt054",
+    "json",
+    "qif",
+];
+
+/// A statement in any of the writable formats.
+///
+/// Format names are case-insensitive: `csv`, `mt940`, `mt942`, `camt053`,
+/// `camt052`, `camt054`, `bai2`, `json`, `qif` and, with the `xlsx` feature,
+/// `xlsx` for input, as well as `auto`, which reads the whole input and picks
+/// the format [`detect_format`] finds in it; `csv`, `mt940`, `mt942`,
+/// `camt053`, `camt054`, `json` and `qif` for output.
+///
+/// # Example
+/// ```
+/// use ledger_parser::Statement;
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+/// let statement = Statement::from_read(&mut data.as_bytes(), "mt940").unwrap();
+/// assert_eq!(statement.account_number(), "NL81ASNB9999999999");
+/// assert_eq!(statement.transactions().len(), 1);
+///
+/// let mut xml = Vec::new();
+/// statement.write_to(&mut xml, "camt053").unwrap();
+/// assert!(String::from_utf8(xml).unwrap().contains("<Ntry>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// CSV statement; also holds XLSX input
+    Csv(CsvStatement),
+    /// MT940 statement; also holds MT942 input
+    Mt940(Mt940Statement),
+    /// CAMT.053 statement; also holds CAMT.052, JSON and BAI2 input
+    Camt053(Camt053Statement),
+    /// CAMT.054 notification
+    Camt054(Camt054Statement),
+    /// QIF statement
+    Qif(QifStatement),
+}
+
+impl Statement {
+    /// Parse input in the format named `format` with the default
+    /// [`ParseOptions`]; CSV input is read in the Sberbank layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidFormat` for an unknown format name, for
+    /// `auto` input whose format cannot be detected (and for CSV in builds
+    /// without the `sberbank` feature, which need
+    /// [`Statement::from_read_with_options`] and a [`CsvDialect`]), otherwise
+    /// the errors of the format's parser.
+    pub fn from_read<R: Read>(reader: &mut R, format: &str) -> Result<Self, ParseError> {
+        Self::from_read_with_options(reader, format, None, &ParseOptions::default())
+            .map(|(statement, _)| statement)
+    }
+
+    /// Parse input in the format named `format`, returning a [`ParseWarning`]
+    /// for every record skipped.
+    ///
+    /// `csv_dialect` selects the CSV layout; `None` reads the Sberbank layout
+    /// with `options`. `options` apply to the MT940, CAMT.053 and XLSX readers
+    /// as well; the other formats fail on malformed records in either mode.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Statement::from_read`], and in strict mode the error of the
+    /// first malformed record.
+    pub fn from_read_with_options<R: Read>(
+        reader: &mut R,
+        format: &str,
+        csv_dialect: Option<&CsvDialect>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        if format.eq_ignore_ascii_case(AUTO_FORMAT) {
+            let (content, format) = detect_input(reader)?;
+            return Self::from_read_with_options(
+                &mut content.as_slice(),
+                format,
+                csv_dialect,
+                options,
+            );
+        }
+        let no_warnings = |statement| (statement, Vec::new());
+        match format.to_lowercase().as_str() {
+            "csv" => Ok(match csv_dialect {
+                #[cfg(feature = "sberbank")]
+                Some(CsvDialect::Sberbank(options)) => {
+                    let (csv, warnings) = CsvStatement::from_read_with_options(reader, options)?;
+                    (Statement::Csv(csv), warnings)
+                }
+                Some(dialect) => no_warnings(Statement::Csv(CsvStatement::from_read_with(
+                    reader, dialect,
+                )?)),
+                #[cfg(feature = "sberbank")]
+                None => {
+                    let (csv, warnings) = CsvStatement::from_read_with_options(reader, options)?;
+                    (Statement::Csv(csv), warnings)
+                }
+                #[cfg(not(feature = "sberbank"))]
+                None => {
+                    return Err(ParseError::InvalidFormat(
+                        "CSV input needs a CsvDialect in builds without the sberbank feature"
+                            .into(),
+                    ))
+                }
+            }),
+            "mt940" => {
+                let (mt940, warnings) = Mt940Statement::from_read_with_options(reader, options)?;
+                Ok((Statement::Mt940(mt940), warnings))
+            }
+            // Interim reports travel as MT940; floor limits only matter when writing MT942
+            "mt942" => Ok(no_warnings(Statement::Mt940(
+                Mt942Statement::from_read(reader)?.into(),
+            ))),
+            "camt053" => {
+                let (camt053, warnings) =
+                    Camt053Statement::from_read_with_options(reader, options)?;
+                Ok((Statement::Camt053(camt053), warnings))
+            }
+            // Intra-day reports have no writer of their own; carry them as CAMT.053
+            "camt052" => Ok(no_warnings(Statement::Camt053(
+                Camt052Statement::from_read(reader)?.into(),
+            ))),
+            "camt054" => Ok(no_warnings(Statement::Camt054(
+                Camt054Statement::from_read(reader)?,
+            ))),
+            // JSON carries the unified model, which every variant holds without loss
+            "json" => Ok(no_warnings(Statement::Camt053(
+                JsonStatement::from_read(reader)?.into(),
+            ))),
+            // BAI2 is read-only as well; the (single) account travels as CAMT.053
+            "bai2" => Ok(no_warnings(Statement::Camt053(
+                Bai2Statement::from_read(reader)?.into(),
+            ))),
+            "qif" => Ok(no_warnings(Statement::Qif(QifStatement::from_read(
+                reader,
+            )?))),
+            // Excel exports of the Sberbank layout become CSV statements
+            #[cfg(feature = "xlsx")]
+            "xlsx" => Ok(no_warnings(Statement::Csv(
+                XlsxStatement::from_read_with(reader, options)?.into(),
+            ))),
+            _ => Err(ParseError::InvalidFormat(format!(
+                "Unknown input format: {}. Supported: {}",
+                format,
+                INPUT_FORMATS.join(", ")
+            ))),
+        }
+    }
+
+    /// Parse every statement of the input, as [`Statement::from_read_with_options`];
+    /// only MT940 and CAMT.053 files carry more than one.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Statement::from_read_with_options`].
+    pub fn parse_all_with_options<R: Read>(
+        reader: &mut R,
+        format: &str,
+        csv_dialect: Option<&CsvDialect>,
+        options: &ParseOptions,
+    ) -> Result<(Vec<Self>, Vec<ParseWarning>), ParseError> {
+        if format.eq_ignore_ascii_case(AUTO_FORMAT) {
+            let (content, format) = detect_input(reader)?;
+            return Self::parse_all_with_options(
+                &mut content.as_slice(),
+                format,
+                csv_dialect,
+                options,
+            );
+        }
+        if format.eq_ignore_ascii_case("mt940") {
+            let (statements, warnings) = Mt940Statement::parse_all_with_options(reader, options)?;
+            return Ok((
+                statements.into_iter().map(Statement::Mt940).collect(),
+                warnings,
+            ));
+        }
+        if format.eq_ignore_ascii_case("camt053") {
+            let (statements, warnings) = Camt053Statement::parse_all_with_options(reader, options)?;
+            return Ok((
+                statements.into_iter().map(Statement::Camt053).collect(),
+                warnings,
+            ));
+        }
+        Self::from_read_with_options(reader, format, csv_dialect, options)
+            .map(|(statement, warnings)| (vec![statement], warnings))
+    }
+
+    /// Write the statement in the format named `format` with the writer's
+    /// default options, converting a copy first if it is held in another one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidFormat` for an unknown format name (CSV
+    /// output needs the `sberbank` feature), otherwise the errors of the
+    /// format's writer.
+    pub fn write_to<W: Write>(&self, writer: &mut W, format: &str) -> Result<(), ParseError> {
+        match (self, format.to_lowercase().as_str()) {
+            // Already in the requested format: write it as it is
+            #[cfg(feature = "sberbank")]
+            (Statement::Csv(csv), "csv") => csv.write_to(writer),
+            (Statement::Mt940(mt940), "mt940") => mt940.write_to(writer),
+            (Statement::Camt053(camt053), "camt053") => camt053.write_to(writer),
+            (Statement::Camt054(camt054), "camt054") => camt054.write_to(writer),
+            (Statement::Qif(qif), "qif") => qif.write_to(writer),
+            #[cfg(feature = "sberbank")]
+            (_, "csv") => CsvStatement::from(self.clone()).write_to(writer),
+            (_, "mt940") => Mt940Statement::from(self.clone()).write_to(writer),
+            (_, "mt942") => Mt942Statement::from(self.clone()).write_to(writer),
+            (_, "camt053") => Camt053Statement::from(self.clone()).write_to(writer),
+            (_, "camt054") => Camt054Statement::from(self.clone()).write_to(writer),
+            (_, "json") => JsonStatement::from(self.clone()).write_to(writer),
+            (_, "qif") => QifStatement::from(self.clone()).write_to(writer),
+            _ => Err(ParseError::InvalidFormat(format!(
+                "Unknown output format: {}. Supported: {}",
+                format,
+                OUTPUT_FORMATS.join(", ")
+            ))),
+        }
+    }
+
+    /// Convert the statement to the format named `format`, returning it as
+    /// that format reads it back, with the fields the conversion lost.
+    ///
+    /// The statement is written with the writer's default options and parsed
+    /// back leniently with the default [`ParseOptions`], so the report names
+    /// what the format itself cannot hold rather than what the in-memory
+    /// `From` conversion keeps.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`Statement::write_to`], and those of the `format` parser
+    /// if its own output cannot be read back.
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::Statement;
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:2001020102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+    /// let statement = Statement::from_read(&mut data.as_bytes(), "mt940").unwrap();
+    /// let (_, report) = statement.convert_with_report("qif").unwrap();
+    /// assert_eq!(report.losses[0].to_string(), "currency dropped");
+    /// ```
+    pub fn convert_with_report(
+        &self,
+        format: &str,
+    ) -> Result<(Statement, ConversionReport), ParseError> {
+        let mut output = Vec::new();
+        self.write_to(&mut output, format)?;
+        let converted = Statement::from_read(&mut output.as_slice(), format)?;
+        let report = ConversionReport::compare(self.as_view(), converted.as_view());
+        Ok((converted, report))
+    }
+
+    /// Join consecutive statements of one account into one, e.g. the daily
+    /// statements of a month.
+    ///
+    /// The statements are taken in the order of their opening dates, and each
+    /// must open with the closing balance of the one before and not before its
+    /// closing date, so a statement given twice is refused. The result opens
+    /// with the first balance, closes with the last and holds every
+    /// transaction in that order. It is held as CAMT.053, which carries any
+    /// input without loss; statement ids and sequence numbers are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::MissingField` without statements,
+    /// `ParseError::CurrencyMismatch` for a statement in another currency and
+    /// `ParseError::UnexpectedValue` for one of another account, one whose
+    /// period overlaps the one before or one whose opening balance differs
+    /// from the closing balance before it. Errors number the statements from 1
+    /// in the order they were given.
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::Statement;
+    ///
+    /// let march = ":20:A\n:25:NL81ASNB9999999999\n:60F:C250301EUR100,00\n\
+    ///              :61:250303D25,00NTRFNONREF\n:86:Lunch\n:62F:C250331EUR75,00\n";
+    /// let april = ":20:B\n:25:NL81ASNB9999999999\n:60F:C250331EUR75,00\n\
+    ///              :61:250402C50,00NTRFNONREF\n:86:Refund\n:62F:C250430EUR125,00\n";
+    /// let statements = [april, march]
+    ///     .map(|data| Statement::from_read(&mut data.as_bytes(), "mt940").unwrap());
+    ///
+    /// let merged = Statement::merge(statements).unwrap();
+    /// assert_eq!(merged.transactions().len(), 2);
+    /// assert_eq!(merged.as_view().opening_balance, 100.0);
+    /// assert_eq!(merged.as_view().closing_balance, 125.0);
+    /// ```
+    pub fn merge(statements: impl IntoIterator<Item = Statement>) -> Result<Self, ParseError> {
+        // Statements are numbered from 1 in the order they were given
+        let mut parts: Vec<(usize, Camt053Statement)> = statements
+            .into_iter()
+            .map(Camt053Statement::from)
+            .enumerate()
+            .map(|(index, part)| (index + 1, part))
+            .collect();
+        parts.sort_by_key(|(_, part)| part.opening_date);
+        let mut parts = parts.into_iter();
+        let (mut previous, mut merged) = parts
+            .next()
+            .ok_or_else(|| ParseError::MissingField("statements to merge".into()))?;
+        let mut previous_opening = merged.opening_date;
+        merged.info = StatementInfo {
+            from_date: merged.info.from_date,
+            to_date: merged.info.to_date,
+            account_owner: merged.info.account_owner.take(),
+            servicer_bic: merged.info.servicer_bic.take(),
+            ..StatementInfo::default()
+        };
+
+        for (number, part) in parts {
+            if part.account_number != merged.account_number {
+                return Err(ParseError::UnexpectedValue {
+                    field: format!("account number of statement {}", number),
+                    expected: merged.account_number,
+                    found: part.account_number,
+                });
+            }
+            if Currency::normalize(&part.currency) != Currency::normalize(&merged.currency) {
+                return Err(ParseError::CurrencyMismatch {
+                    expected: merged.currency,
+                    found: part.currency,
+                });
+            }
+            if part.opening_date < merged.closing_date || part.opening_date == previous_opening {
+                return Err(ParseError::UnexpectedValue {
+                    field: format!("opening date of statement {}", number),
+                    expected: format!(
+                        "after the period of statement {}, {} to {}",
+                        previous,
+                        previous_opening.date_naive(),
+                        merged.closing_date.date_naive()
+                    ),
+                    found: part.opening_date.date_naive().to_string(),
+                });
+            }
+            let closing = merged.as_view().closing_money();
+            let opening = part.as_view().opening_money();
+            if round_cents(closing.amount) != round_cents(opening.amount) {
+                return Err(ParseError::UnexpectedValue {
+                    field: format!("opening balance of statement {}", number),
+                    expected: format!("{}, the closing balance before it", closing),
+                    found: opening.to_string(),
+                });
+            }
+
+            merged.closing_balance = part.closing_balance;
+            merged.closing_date = part.closing_date;
+            merged.closing_indicator = part.closing_indicator;
+            merged.transactions.extend(part.transactions);
+            // Available and forward balances only hold for the end of the period
+            merged.balances = part.balances;
+            merged.supplementary_data.extend(part.supplementary_data);
+            merged.info.to_date = part.info.to_date;
+            previous = number;
+            previous_opening = part.opening_date;
+        }
+        Ok(Statement::Camt053(merged))
+    }
+
+    /// Account number (IBAN or local format)
+    pub fn account_number(&self) -> &str {
+        self.as_view().account_number
+    }
+
+    /// Three-letter ISO 4217 currency code
+    pub fn currency(&self) -> &str {
+        self.as_view().currency
+    }
+
+    /// Transactions in statement order
+    pub fn transactions(&self) -> &[Transaction] {
+        self.as_view().transactions
+    }
+
+    /// Transactions of the statement, for in-place changes or filtering
+    pub fn transactions_mut(&mut self) -> &mut Vec<Transaction> {
+        match self {
+            Statement::Csv(s) => &mut s.transactions,
+            Statement::Mt940(s) => &mut s.transactions,
+            Statement::Camt053(s) => &mut s.transactions,
+            Statement::Camt054(s) => &mut s.transactions,
+            Statement::Qif(s) => &mut s.transactions,
+        }
+    }
+
+    /// Set the closing balance to the opening balance plus the booked
+    /// transactions, as after leaving some of them out through
+    /// [`transactions_mut`](Self::transactions_mut); pending and informational
+    /// entries have not moved the balance. The closing date stays.
+    ///
+    /// # Example
+    /// ```
+    /// use ledger_parser::Statement;
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n\
+    ///             :61:200103D150,00NTRFNONREF\n:86:Rent\n:62F:D200103EUR75,00\n";
+    /// let mut statement = Statement::from_read(&mut data.as_bytes(), "mt940").unwrap();
+    /// statement.transactions_mut().retain(|tx| tx.description == "Rent");
+    /// statement.recompute_closing_balance();
+    /// assert_eq!(statement.as_view().closing_money().amount, -50.0);
+    /// ```
+    pub fn recompute_closing_balance(&mut self) {
+        let view = self.as_view();
+        let movement: f64 = view.transactions.iter().map(booked_amount).sum();
+        let closing = signed_amount(view.opening_balance, view.opening_indicator) + movement;
+        let (balance, indicator) = unsigned_balance(round_cents(closing));
+        match self {
+            Statement::Csv(s) => (s.closing_balance, s.closing_indicator) = (balance, indicator),
+            Statement::Mt940(s) => (s.closing_balance, s.closing_indicator) = (balance, indicator),
+            Statement::Camt053(s) => {
+                (s.closing_balance, s.closing_indicator) = (balance, indicator)
+            }
+            Statement::Camt054(s) => {
+                (s.closing_balance, s.closing_indicator) = (balance, indicator)
+            }
+            Statement::Qif(s) => (s.closing_balance, s.closing_indicator) = (balance, indicator),
+        }
+    }
+
+    /// Keep only the transactions booked from `from` through `to`, either bound
+    /// left open, with the balances of the account at the new period's ends.
+    ///
+    /// The entries dropped before `from` move the opening balance forward, and
+    /// the opening date to the last of them; the closing balance is recomputed,
+    /// and when entries after `to` were dropped the closing date moves back to the
+    /// last entry kept.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use ledger_parser::Statement;
+    ///
+    /// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+    ///             :61:200102D25,00NTRFNONREF\n:86:Lunch\n\
+    ///             :61:200103D150,00NTRFNONREF\n:86:Rent\n:62F:D200103EUR75,00\n";
+    /// let mut statement = Statement::from_read(&mut data.as_bytes(), "mt940").unwrap();
+    /// statement.retain_booked_between(NaiveDate::from_ymd_opt(2020, 1, 3), None);
+    /// let view = statement.as_view();
+    /// assert_eq!(view.opening_money().amount, 75.0);
+    /// assert_eq!(view.opening_date.date_naive().to_string(), "2020-01-02");
+    /// assert_eq!(view.closing_money().amount, -75.0);
+    /// ```
+    pub fn retain_booked_between(&mut self, from: Option<NaiveDate>, to: Option<NaiveDate>) {
+        let view = self.as_view();
+        let mut opening = signed_amount(view.opening_balance, view.opening_indicator);
+        let mut opening_date = view.opening_date;
+        let mut closing_date = view.closing_date;
+        let mut dropped_after = false;
+        let mut last_kept: Option<DateTime<FixedOffset>> = None;
+        for transaction in view.transactions {
+            let date = transaction.booking_date.date_naive();
+            if from.is_some_and(|from| date < from) {
+                opening += booked_amount(transaction);
+                opening_date = opening_date.max(transaction.booking_date);
+            } else if to.is_some_and(|to| date > to) {
+                dropped_after = true;
+            } else {
+                last_kept = last_kept.max(Some(transaction.booking_date));
+            }
+        }
+        if dropped_after {
+            closing_date = last_kept.unwrap_or(opening_date);
+        }
+
+        self.transactions_mut().retain(|transaction| {
+            let date = transaction.booking_date.date_naive();
+            from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to)
+        });
+        self.set_period(round_cents(opening), opening_date, closing_date);
+        self.recompute_closing_balance();
+    }
+
+    /// Set the signed opening balance and the opening and closing dates
+    fn set_period(
+        &mut self,
+        opening: f64,
+        opening_date: DateTime<FixedOffset>,
+        closing_date: DateTime<FixedOffset>,
+    ) {
+        let (balance, indicator) = unsigned_balance(opening);
+        let period = (balance, indicator, opening_date, closing_date);
+        match self {
+            Statement::Csv(s) => {
+                (
+                    s.opening_balance,
+                    s.opening_indicator,
+                    s.opening_date,
+                    s.closing_date,
+                ) = period
+            }
+            Statement::Mt940(s) => {
+                (
+                    s.opening_balance,
+                    s.opening_indicator,
+                    s.opening_date,
+                    s.closing_date,
+                ) = period
+            }
+            Statement::Camt053(s) => {
+                (
+                    s.opening_balance,
+                    s.opening_indicator,
+                    s.opening_date,
+                    s.closing_date,
+                ) = period
+            }
+            Statement::Camt054(s) => {
+                (
+                    s.opening_balance,
+                    s.opening_indicator,
+                    s.opening_date,
+                    s.closing_date,
+                ) = period
+            }
+            Statement::Qif(s) => {
+                (
+                    s.opening_balance,
+                    s.opening_indicator,
+                    s.opening_date,
+                    s.closing_date,
+                ) = period
+            }
+        }
+    }
+
+    /// Borrow the statement as a format-independent view
+    pub fn as_view(&self) -> StatementRef<'_> {
+        match self {
+            Statement::Csv(s) => s.as_view(),
+            Statement::Mt940(s) => s.as_view(),
+            Statement::Camt053(s) => s.as_view(),
+            Statement::Camt054(s) => s.as_view(),
+            Statement::Qif(s) => s.as_view(),
+        }
+    }
+}
+
+/// Whole input of the `auto` format with the format detected in it
+fn detect_input<R: Read>(reader: &mut R) -> Result<(Vec<u8>, &'static str), ParseError> {
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content)?;
+    let format = detect_format(&content)
+        .ok_or_else(|| ParseError::InvalidFormat("Could not detect the input format".into()))?;
+    Ok((content, format))
+}
+
+/// Operations every statement format shares.
+///
+/// Implemented by every format struct, so generic code can read, inspect and
+/// write statements without naming a concrete type. Formats the library only
+/// reads (CAMT.052, BAI2, XLSX) fail in [`StatementFormat::write_to`]; CSV
+/// implements the trait with the `sberbank` feature, as it reads and writes
+/// the Sberbank layout.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Camt053Statement, Mt940Statement, StatementFormat};
+///
+/// fn net_change<S: StatementFormat>(data: &str) -> f64 {
+///     let statement = S::from_read(&mut data.as_bytes()).unwrap();
+///     statement.closing().amount - statement.opening().amount
+/// }
+///
+/// let mt940 = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///              :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+/// assert_eq!(net_change::<Mt940Statement>(mt940), -25.0);
+///
+/// let mut xml = Vec::new();
+/// let statement: Camt053Statement = Mt940Statement::from_read(&mut mt940.as_bytes())
+///     .unwrap()
+///     .into();
+/// StatementFormat::write_to(&statement, &mut xml).unwrap();
+/// assert_eq!(net_change::<Camt053Statement>(std::str::from_utf8(&xml).unwrap()), -25.0);
+/// ```
+pub trait StatementFormat: Sized {
+    /// Parse a statement of this format from any reader
+    ///
+    /// # Errors
+    /// The errors of the format's `from_read`.
+    fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError>;
+
+    /// Write the statement in this format to any writer
+    ///
+    /// # Errors
+    /// The errors of the format's `write_to`, and `ParseError::InvalidFormat`
+    /// for formats the library only reads.
+    fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError>;
+
+    /// Borrow the statement as a format-independent view
+    fn as_view(&self) -> StatementRef<'_>;
+
+    /// Transactions in statement order
+    fn transactions(&self) -> &[Transaction] {
+        self.as_view().transactions
+    }
+
+    /// Account number (IBAN or local format)
+    fn account_number(&self) -> &str {
+        self.as_view().account_number
+    }
+
+    /// Three-letter ISO 4217 currency code
+    fn currency(&self) -> &str {
+        self.as_view().currency
+    }
+
+    /// Signed opening balance in the statement currency
+    fn opening(&self) -> Money {
+        self.as_view().opening_money()
+    }
+
+    /// Signed closing balance in the statement currency
+    fn closing(&self) -> Money {
+        self.as_view().closing_money()
+    }
+}
+
+/// [`StatementFormat`] impls delegating to the inherent methods of each
+/// format struct; read-only formats name themselves in the `write_to` error
+macro_rules! impl_statement_format {
+    ($format:ty) => {
+        impl StatementFormat for $format {
+            fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+                <$format>::from_read(reader)
+            }
+
+            fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+                <$format>::write_to(self, writer)
+            }
+
+            fn as_view(&self) -> StatementRef<'_> {
+                <$format>::as_view(self)
+            }
+        }
+    };
+    ($format:ty, read_only $name:literal) => {
+        impl StatementFormat for $format {
+            fn from_read<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+                <$format>::from_read(reader)
+            }
+
+            fn write_to<W: Write>(&self, _writer: &mut W) -> Result<(), ParseError> {
+                Err(ParseError::InvalidFormat(format!(
+                    "{} is read-only; convert the statement to a writable format first",
+                    $name
+                )))
+            }
+
+            fn as_view(&self) -> StatementRef<'_> {
+                <$format>::as_view(self)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sberbank")]
+impl_statement_format!(CsvStatement);
+impl_statement_format!(Mt940Statement);
+impl_statement_format!(Mt942Statement);
+impl_statement_format!(Camt053Statement);
+impl_statement_format!(Camt054Statement);
+impl_statement_format!(JsonStatement);
+impl_statement_format!(QifStatement);
+impl_statement_format!(Camt052Statement, read_only "CAMT.052");
+impl_statement_format!(Bai2Statement, read_only "BAI2");
+#[cfg(feature = "xlsx")]
+impl_statement_format!(XlsxStatement, read_only "XLSX");
+
+/// `From` impls wrapping each format struct in its variant
+macro_rules! impl_into_statement {
+    ($($variant:ident($format:ty)),* $(,)?) => {
+        $(
+            impl From<$format> for Statement {
+                fn from(statement: $format) -> Self {
+                    Statement::$variant(statement)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_statement!(
+    Csv(CsvStatement),
+    Mt940(Mt940Statement),
+    Camt053(Camt053Statement),
+    Camt054(Camt054Statement),
+    Qif(QifStatement),
+);
+
+/// `From` impls converting whichever statement is held into each format
+macro_rules! impl_from_statement {
+    ($($format:ty),* $(,)?) => {
+        $(
+            impl From<Statement> for $format {
+                fn from(statement: Statement) -> Self {
+                    match statement {
+                        Statement::Csv(s) => s.into(),
+                        Statement::Mt940(s) => s.into(),
+                        Statement::Camt053(s) => s.into(),
+                        Statement::Camt054(s) => s.into(),
+                        Statement::Qif(s) => s.into(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_statement!(
+    CsvStatement,
+    Mt940Statement,
+    Mt942Statement,
+    Camt053Statement,
+    Camt054Statement,
+    JsonStatement,
+    QifStatement,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MT940: &str = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+                         :61:200102D25,00NTRFNONREF\n:86:Lunch\n:62F:C200102EUR75,00\n";
+
+    #[test]
+    fn test_round_trip_through_every_output_format() {
+        let statement = Statement::from_read(&mut MT940.as_bytes(), "MT940").unwrap();
+        for (format, back) in [
+            ("mt940", "mt940"),
+            ("camt053", "camt053"),
+            ("camt054", "camt054"),
+            ("json", "json"),
+            ("qif", "qif"),
+            ("mt942", "mt942"),
+        ] {
+            let mut output = Vec::new();
+            statement.write_to(&mut output, format).unwrap();
+            let read = Statement::from_read(&mut output.as_slice(), back).unwrap();
+            assert_eq!(read.transactions().len(), 1, "{}", format);
+            let detected = Statement::from_read(&mut output.as_slice(), "auto").unwrap();
+            assert_eq!(detected, read, "{}", format);
+            assert_eq!(read.transactions()[0].amount, 25.0, "{}", format);
+        }
+
+        assert!(matches!(
+            Statement::from_read(&mut MT940.as_bytes(), "swift"),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        let Err(ParseError::InvalidFormat(message)) = statement.write_to(&mut Vec::new(), "bai2")
+        else {
+            panic!("bai2 output accepted");
+        };
+        assert!(message.contains("Supported: "), "{}", message);
+        assert_eq!(
+            message.contains("csv"),
+            cfg!(feature = "sberbank"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_parse_all_with_options_reports_warnings() {
+        let data = format!(
+            "{}{}",
+            MT940,
+            ":20:REF2\n:25:NL81ASNB9999999999\n:60F:C200102EUR75,00\n\
+             :61:200132C5,00NTRFNONREF\n:62F:C200103EUR75,00\n"
+        );
+        let (mut statements, warnings) = Statement::parse_all_with_options(
+            &mut data.as_bytes(),
+            "mt940",
+            None,
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(statements[1].transactions().is_empty());
+
+        statements[0].transactions_mut().clear();
+        let camt053 = Camt053Statement::from(statements.remove(0));
+        assert!(camt053.transactions.is_empty());
+        assert_eq!(camt053.currency, "EUR");
+    }
+
+    #[test]
+    fn test_merge_checks_account_currency_and_continuity() {
+        let next = |account: &str, currency: &str, opening: &str| {
+            let data = format!(
+                ":20:REF2\n:25:{}\n:60F:C200102{}{}\n:61:200103C5,00NTRFNONREF\n\
+                 :62F:C200103EUR80,00\n",
+                account, currency, opening
+            );
+            Statement::from_read(&mut data.as_bytes(), "mt940").unwrap()
+        };
+        let first = || Statement::from_read(&mut MT940.as_bytes(), "mt940").unwrap();
+
+        let merged =
+            Statement::merge([first(), next("NL81ASNB9999999999", "EUR", "75,00")]).unwrap();
+        assert_eq!(merged.transactions().len(), 2);
+        assert_eq!(merged.as_view().closing_balance, 80.0);
+
+        let gap = Statement::merge([first(), next("NL81ASNB9999999999", "EUR", "70,00")]);
+        assert_eq!(
+            gap.unwrap_err().to_string(),
+            "Invalid opening balance of statement 2: expected 75.00 EUR, \
+             the closing balance before it, found '70.00 EUR'"
+        );
+        // Numbered in the order given, not in the order of their dates
+        let gap = Statement::merge([next("NL81ASNB9999999999", "EUR", "70,00"), first()]);
+        assert!(gap.unwrap_err().to_string().contains("statement 1"));
+        assert_eq!(
+            Statement::merge([first(), first()])
+                .unwrap_err()
+                .to_string(),
+            "Invalid opening date of statement 2: expected after the period of statement 1, \
+             2020-01-01 to 2020-01-02, found '2020-01-01'"
+        );
+        assert!(matches!(
+            Statement::merge([first(), next("DE89370400440532013000", "EUR", "75,00")]),
+            Err(ParseError::UnexpectedValue { .. })
+        ));
+        assert!(matches!(
+            Statement::merge([first(), next("NL81ASNB9999999999", "USD", "75,00")]),
+            Err(ParseError::CurrencyMismatch { .. })
+        ));
+        assert!(matches!(
+            Statement::merge([]),
+            Err(ParseError::MissingField(_))
+        ));
+    }
+
+    #[test]
+    fn test_statement_format_is_generic_over_formats() {
+        fn describe<S: StatementFormat>(statement: &S) -> (&str, &str, usize, f64) {
+            (
+                statement.account_number(),
+                statement.currency(),
+                statement.transactions().len(),
+                statement.closing().amount,
+            )
+        }
+
+        let mt940 = <Mt940Statement as StatementFormat>::from_read(&mut MT940.as_bytes()).unwrap();
+        let expected = ("NL81ASNB9999999999", "EUR", 1, 75.0);
+        assert_eq!(describe(&mt940), expected);
+        assert_eq!(describe(&QifStatement::from(mt940.clone())).2, 1);
+
+        let camt052 = Camt052Statement {
+            account_number: mt940.account_number.clone(),
+            currency: mt940.currency.clone(),
+            opening_balance: mt940.opening_balance,
+            opening_date: mt940.opening_date,
+            opening_indicator: mt940.opening_indicator.clone(),
+            closing_balance: mt940.closing_balance,
+            closing_date: mt940.closing_date,
+            closing_indicator: mt940.closing_indicator.clone(),
+            transactions: mt940.transactions.clone(),
+        };
+        assert_eq!(describe(&camt052), expected);
+        assert!(matches!(
+            StatementFormat::write_to(&camt052, &mut Vec::new()),
+            Err(ParseError::InvalidFormat(message)) if message.starts_with("CAMT.052")
+        ));
+    }
+}
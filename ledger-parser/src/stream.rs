@@ -0,0 +1,338 @@
+//! Async transaction streams (requires the `async` cargo feature).
+//!
+//! [`mt940_transactions`] turns any [`AsyncBufRead`] into a
+//! [`Stream`] of transactions that reads the input one line at a time, and
+//! [`convert_stream`] drains such a stream into an [`AsyncWrite`] through a
+//! bounded buffer. Both are runtime-agnostic: they only rely on the `futures`
+//! IO traits, so tokio readers are used through a compatibility layer such as
+//! `tokio-util`'s `compat`.
+//!
+//! For whole statements, every writable statement type also gets
+//! `from_async_read` and `write_to_async`, so services fetching statements
+//! over SFTP or HTTP do not block the runtime on IO. The statement is read
+//! into memory before it is parsed and encoded in memory before it is
+//! written; only the IO is asynchronous.
+//!
+//! Back-pressure comes from the pull model: the next line is only read once the
+//! consumer asks for the next transaction, and [`convert_stream`] stops pulling
+//! while its output writer is busy. Dropping the stream or the conversion
+//! future cancels the work without reading any further input.
+
+use futures_util::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::pin::pin;
+
+use crate::error::ParseError;
+use crate::formats::mt940_dialect::Mt940Dialect;
+use crate::formats::mt940_statement::Mt940Statement;
+use crate::formats::swift_charset::Mt940Charset;
+use crate::model::Transaction;
+#[cfg(feature = "sberbank")]
+use crate::CsvStatement;
+use crate::{Camt053Statement, Camt054Statement, JsonStatement, Mt942Statement, QifStatement};
+
+/// Encoded bytes [`convert_stream`] collects before writing them out by default
+pub const DEFAULT_STREAM_BUFFER: usize = 8 * 1024;
+
+/// Stream the `:61:`/`:86:` entries of MT940 input as transactions.
+///
+/// Lines are read as the stream is polled, so memory stays bounded by the
+/// longest entry rather than the file size. Entries of every message in the
+/// input are yielded in order; header tags and balances are skipped. Like
+/// [`Mt940Statement::from_read`], malformed `:61:` lines are skipped; bank
+/// quirks are not applied since the header is not buffered for detection. The
+/// stream ends after the first IO error.
+///
+/// # Example
+/// ```
+/// use futures_util::StreamExt;
+/// use ledger_parser::mt940_transactions;
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102C50,00NTRFNONREF\n:86:Invoice 123\n:62F:C200102EUR150,00\n";
+/// let transactions: Vec<_> =
+///     futures_executor::block_on(mt940_transactions(data.as_bytes()).collect());
+/// assert_eq!(transactions.len(), 1);
+/// assert_eq!(transactions[0].as_ref().unwrap().description, "Invoice 123");
+/// ```
+pub fn mt940_transactions<R>(reader: R) -> impl Stream<Item = Result<Transaction, ParseError>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    stream::unfold(Some(Mt940EntryReader::new(reader)), |state| async move {
+        let mut entries = state?;
+        match entries.next_transaction().await {
+            Ok(Some(transaction)) => Some((Ok(transaction), Some(entries))),
+            Ok(None) => None,
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// Drain `transactions` into `writer`, encoding each one with `encode`.
+///
+/// Encoded output is collected in a buffer of about `buffer_size` bytes and
+/// written whenever it fills up; the stream is not polled while a write is
+/// pending. Returns the number of converted transactions. The first error from
+/// the stream, the encoder or the writer aborts the conversion; output written
+/// up to that point is not rolled back.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{convert_stream, mt940_entry_encoder, mt940_transactions, Mt940Dialect};
+///
+/// let data = ":61:200102C50,00NTRFNONREF\n:86:Invoice 123\n";
+/// let mut output = Vec::new();
+/// let converted = futures_executor::block_on(convert_stream(
+///     mt940_transactions(data.as_bytes()),
+///     &mut output,
+///     ledger_parser::DEFAULT_STREAM_BUFFER,
+///     mt940_entry_encoder(Mt940Dialect::Standard),
+/// ))
+/// .unwrap();
+/// assert_eq!(converted, 1);
+/// assert!(String::from_utf8(output).unwrap().starts_with(":61:200102C50,00"));
+/// ```
+pub async fn convert_stream<S, W, E>(
+    transactions: S,
+    writer: &mut W,
+    buffer_size: usize,
+    mut encode: E,
+) -> Result<usize, ParseError>
+where
+    S: Stream<Item = Result<Transaction, ParseError>>,
+    W: AsyncWrite + Unpin,
+    E: FnMut(&Transaction, &mut Vec<u8>) -> Result<(), ParseError>,
+{
+    let mut transactions = pin!(transactions);
+    let mut buffer = Vec::with_capacity(buffer_size);
+    let mut converted = 0;
+
+    while let Some(transaction) = transactions.next().await {
+        encode(&transaction?, &mut buffer)?;
+        converted += 1;
+        if buffer.len() >= buffer_size {
+            writer.write_all(&buffer).await?;
+            buffer.clear();
+        }
+    }
+
+    writer.write_all(&buffer).await?;
+    writer.flush().await?;
+    Ok(converted)
+}
+
+/// Encoder for [`convert_stream`] writing MT940 `:61:`/`:86:` entries in `dialect`
+pub fn mt940_entry_encoder(
+    dialect: Mt940Dialect,
+) -> impl FnMut(&Transaction, &mut Vec<u8>) -> Result<(), ParseError> {
+    move |transaction, buffer| {
+        Mt940Statement::write_entries(
+            buffer,
+            std::slice::from_ref(transaction),
+            dialect,
+            Mt940Charset::default(),
+        )
+    }
+}
+
+macro_rules! impl_async_io {
+    ($statement:ty) => {
+        impl $statement {
+            /// Parse the statement like `from_read`, reading `reader` asynchronously.
+            ///
+            /// # Errors
+            /// Same as `from_read`.
+            pub async fn from_async_read<R: AsyncRead + Unpin>(
+                reader: &mut R,
+            ) -> Result<Self, ParseError> {
+                let mut content = Vec::new();
+                reader.read_to_end(&mut content).await?;
+                Self::from_read(&mut content.as_slice())
+            }
+
+            /// Write the statement like `write_to`, writing `writer` asynchronously.
+            ///
+            /// # Errors
+            /// Same as `write_to`.
+            pub async fn write_to_async<W: AsyncWrite + Unpin>(
+                &self,
+                writer: &mut W,
+            ) -> Result<(), ParseError> {
+                let mut content = Vec::new();
+                self.write_to(&mut content)?;
+                writer.write_all(&content).await?;
+                writer.flush().await?;
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sberbank")]
+impl_async_io!(CsvStatement);
+impl_async_io!(Mt940Statement);
+impl_async_io!(Mt942Statement);
+impl_async_io!(Camt053Statement);
+impl_async_io!(Camt054Statement);
+impl_async_io!(JsonStatement);
+impl_async_io!(QifStatement);
+
+/// Line-by-line MT940 reader producing one entry at a time
+struct Mt940EntryReader<R> {
+    reader: R,
+    line: String,
+    /// Tag whose value may still continue on the next line
+    current: Option<(String, String)>,
+    /// Complete tag read ahead while looking for a `:86:`
+    lookahead: Option<(String, String)>,
+}
+
+impl<R: AsyncBufRead + Unpin> Mt940EntryReader<R> {
+    fn new(reader: R) -> Self {
+        Mt940EntryReader {
+            reader,
+            line: String::new(),
+            current: None,
+            lookahead: None,
+        }
+    }
+
+    /// Next complete `:61:` entry with the description of a directly following `:86:`
+    async fn next_transaction(&mut self) -> Result<Option<Transaction>, ParseError> {
+        loop {
+            let next = match self.lookahead.take() {
+                Some(tag) => Some(tag),
+                None => self.next_tag().await?,
+            };
+            let Some((tag, value)) = next else {
+                return Ok(None);
+            };
+            if tag != "61" {
+                continue;
+            }
+
+            let description = match self.next_tag().await? {
+                Some((tag, description)) if tag == "86" => description.trim().to_string(),
+                other => {
+                    self.lookahead = other;
+                    String::new()
+                }
+            };
+            if let Ok(transaction) =
+                Mt940Statement::parse_transaction_line(&value, &description, &[])
+            {
+                return Ok(Some(transaction));
+            }
+        }
+    }
+
+    /// Next `(tag, value)` pair, with continuation lines joined by `\n`
+    async fn next_tag(&mut self) -> Result<Option<(String, String)>, ParseError> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line).await? == 0 {
+                return Ok(self.current.take());
+            }
+            let line = self.line.trim_end_matches(['\r', '\n']);
+            let trimmed = line.trim();
+
+            if let Some((tag, value)) = trimmed.strip_prefix(':').and_then(|s| s.split_once(':')) {
+                let tag = (tag.to_string(), value.to_string());
+                if let Some(complete) = self.current.replace(tag) {
+                    return Ok(Some(complete));
+                }
+            } else if trimmed.starts_with('{') || trimmed.starts_with("-}") {
+                // Block boundaries end the current tag; their content is not entry data
+                if let Some(complete) = self.current.take() {
+                    return Ok(Some(complete));
+                }
+            } else if let Some((_, value)) = &mut self.current {
+                value.push('\n');
+                value.push_str(line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_executor::block_on;
+    use futures_util::io::Cursor;
+
+    const TWO_MESSAGES: &str =
+        "{1:F01BANK}{2:I940BANK}{4:\r\n:20:REF\r\n:25:NL81ASNB9999999999\r\n\
+        :60F:C200101EUR100,00\r\n:61:200102C50,00NTRFINV-1\r\n:86:Invoice 1\r\nACME\r\n\
+        :61:200103D10,00NTRFNONREF\r\n:62F:C200103EUR140,00\r\n-}\r\n\
+        {1:F01BANK}{2:I940BANK}{4:\r\n:20:REF\r\n:61:200104D5,00NTRFNONREF\r\n:86:Fee\r\n-}\r\n";
+
+    #[test]
+    fn test_mt940_transactions_across_messages() {
+        let transactions: Vec<_> = block_on(
+            mt940_transactions(Cursor::new(TWO_MESSAGES.as_bytes()))
+                .map(Result::unwrap)
+                .collect(),
+        );
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[0].description, "Invoice 1\nACME");
+        assert_eq!(transactions[0].reference.as_deref(), Some("INV-1"));
+        assert_eq!(transactions[1].description, "");
+        assert_eq!(transactions[2].description, "Fee");
+    }
+
+    #[test]
+    fn test_async_read_and_write_match_sync() {
+        let input = "{1:F01BANK}{2:I940BANK}{4:\n:20:REF\n:25:NL81ASNB9999999999\n\
+                     :60F:C200101EUR100,00\n:61:200102C50,00NTRFNONREF\n:86:Invoice\n\
+                     :62F:C200102EUR150,00\n-}";
+        let statement = block_on(Mt940Statement::from_async_read(&mut Cursor::new(input))).unwrap();
+        assert_eq!(
+            statement,
+            Mt940Statement::from_read(&mut input.as_bytes()).unwrap()
+        );
+
+        let camt053 = Camt053Statement::from(statement);
+        let mut written = Cursor::new(Vec::new());
+        block_on(camt053.write_to_async(&mut written)).unwrap();
+        let mut expected = Vec::new();
+        camt053.write_to(&mut expected).unwrap();
+        assert_eq!(written.into_inner(), expected);
+    }
+
+    #[test]
+    fn test_convert_stream_flushes_small_buffer() {
+        let mut output = Vec::new();
+        let converted = block_on(convert_stream(
+            mt940_transactions(TWO_MESSAGES.as_bytes()),
+            &mut output,
+            1,
+            mt940_entry_encoder(Mt940Dialect::Standard),
+        ))
+        .unwrap();
+        assert_eq!(converted, 3);
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches(":61:").count(), 3);
+        assert!(output.contains(":86:Fee"));
+    }
+
+    #[test]
+    fn test_convert_stream_stops_at_first_error() {
+        let failing = stream::iter(vec![
+            Err(ParseError::Mt940Error("broken feed".into())),
+            Ok(Mt940Statement::parse_transaction_line("200102C1,00", "", &[]).unwrap()),
+        ]);
+        let mut output = Vec::new();
+        let result = block_on(convert_stream(
+            failing,
+            &mut output,
+            DEFAULT_STREAM_BUFFER,
+            mt940_entry_encoder(Mt940Dialect::Standard),
+        ));
+        assert!(matches!(result, Err(ParseError::Mt940Error(_))));
+        assert!(output.is_empty());
+    }
+}
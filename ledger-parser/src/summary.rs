@@ -0,0 +1,413 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
+use strum_macros::{Display, EnumString};
+
+use crate::annotations::{fingerprints, AnnotationStore};
+use crate::error::ParseError;
+use crate::formats::paging::round_cents;
+use crate::model::{Transaction, TransactionType};
+use crate::view::StatementRef;
+
+/// Category of transactions without an annotated category in an [`AggregateReport`]
+pub const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Calendar period transactions are grouped into by [`StreamSummarizer`].
+/// Parses from and displays as `day` or `month`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum BucketPeriod {
+    /// One bucket per booking day
+    Day,
+    /// One bucket per calendar month, keyed by its first day
+    Month,
+}
+
+impl BucketPeriod {
+    /// First day of the bucket containing `date`
+    fn bucket_start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BucketPeriod::Day => date,
+            BucketPeriod::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+}
+
+/// Running totals over a set of transactions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Totals {
+    /// Number of credit transactions
+    pub credit_count: usize,
+    /// Number of debit transactions
+    pub debit_count: usize,
+    /// Sum of credit amounts
+    pub credit_total: f64,
+    /// Sum of debit amounts
+    pub debit_total: f64,
+}
+
+impl Totals {
+    /// Number of transactions of either direction
+    pub fn count(&self) -> usize {
+        self.credit_count + self.debit_count
+    }
+
+    /// Credits minus debits
+    pub fn net(&self) -> f64 {
+        round_cents(self.credit_total - self.debit_total)
+    }
+
+    pub(crate) fn add(&mut self, transaction: &Transaction) {
+        match transaction.transaction_type {
+            TransactionType::Credit => {
+                self.credit_count += 1;
+                self.credit_total = round_cents(self.credit_total + transaction.amount);
+            }
+            TransactionType::Debit => {
+                self.debit_count += 1;
+                self.debit_total = round_cents(self.debit_total + transaction.amount);
+            }
+        }
+    }
+}
+
+/// State of a [`StreamSummarizer`] at one point of the stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummarySnapshot {
+    /// Transactions consumed so far
+    pub entries_seen: usize,
+    /// Totals over all consumed transactions
+    pub totals: Totals,
+    /// Totals per bucket, keyed by the first day of the bucket
+    pub buckets: BTreeMap<NaiveDate, Totals>,
+}
+
+/// Incremental aggregation over a transaction stream.
+///
+/// Keeps overall totals and per-period buckets (by booking date) while
+/// transactions are fed in one at a time, and hands out a [`SummarySnapshot`]
+/// every `snapshot_every` entries. Memory grows with the number of buckets,
+/// not the number of transactions, so large feeds can drive a live dashboard
+/// without being collected first.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{BucketPeriod, Mt940Statement, StreamSummarizer};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C200101EUR100,00\n\
+///             :61:200102C50,00NTRFNONREF\n:86:Invoice 123\n\
+///             :61:200203D10,00NTRFNONREF\n:86:Card payment\n\
+///             :62F:C200203EUR140,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let summarizer = StreamSummarizer::new(BucketPeriod::Month, 1);
+/// let snapshots: Vec<_> = summarizer
+///     .snapshots(statement.transactions.into_iter().map(Ok))
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(snapshots.len(), 2);
+/// assert_eq!(snapshots[1].totals.net(), 40.0);
+/// assert_eq!(snapshots[1].buckets.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamSummarizer {
+    period: BucketPeriod,
+    snapshot_every: usize,
+    entries_seen: usize,
+    totals: Totals,
+    buckets: BTreeMap<NaiveDate, Totals>,
+}
+
+impl StreamSummarizer {
+    /// Summarizer bucketing by `period` that emits a snapshot every `snapshot_every` entries.
+    ///
+    /// A `snapshot_every` of 0 is treated as 1.
+    pub fn new(period: BucketPeriod, snapshot_every: usize) -> Self {
+        StreamSummarizer {
+            period,
+            snapshot_every: snapshot_every.max(1),
+            entries_seen: 0,
+            totals: Totals::default(),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    /// Add one transaction; returns a snapshot when it completes a batch of `snapshot_every`.
+    pub fn push(&mut self, transaction: &Transaction) -> Option<SummarySnapshot> {
+        self.entries_seen += 1;
+        self.totals.add(transaction);
+        let bucket = self
+            .period
+            .bucket_start(transaction.booking_date.date_naive());
+        self.buckets.entry(bucket).or_default().add(transaction);
+
+        if self.entries_seen.is_multiple_of(self.snapshot_every) {
+            Some(self.snapshot())
+        } else {
+            None
+        }
+    }
+
+    /// Current state, regardless of the snapshot interval
+    pub fn snapshot(&self) -> SummarySnapshot {
+        SummarySnapshot {
+            entries_seen: self.entries_seen,
+            totals: self.totals.clone(),
+            buckets: self.buckets.clone(),
+        }
+    }
+
+    /// Consume `transactions` and yield a snapshot every `snapshot_every` entries.
+    ///
+    /// When the stream ends, a final snapshot covering the trailing entries is
+    /// yielded unless the last batch already ended exactly there (an empty
+    /// stream still yields one empty snapshot). The first error from the
+    /// stream is passed through and ends the iteration.
+    pub fn snapshots<I>(self, transactions: I) -> Snapshots<I::IntoIter>
+    where
+        I: IntoIterator<Item = Result<Transaction, ParseError>>,
+    {
+        Snapshots {
+            summarizer: self,
+            transactions: transactions.into_iter(),
+            finished: false,
+        }
+    }
+}
+
+/// Iterator returned by [`StreamSummarizer::snapshots`]
+#[derive(Debug)]
+pub struct Snapshots<I> {
+    summarizer: StreamSummarizer,
+    transactions: I,
+    finished: bool,
+}
+
+impl<I> Iterator for Snapshots<I>
+where
+    I: Iterator<Item = Result<Transaction, ParseError>>,
+{
+    type Item = Result<SummarySnapshot, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        for transaction in self.transactions.by_ref() {
+            match transaction {
+                Ok(transaction) => {
+                    if let Some(snapshot) = self.summarizer.push(&transaction) {
+                        return Some(Ok(snapshot));
+                    }
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        self.finished = true;
+        let seen = self.summarizer.entries_seen;
+        if seen == 0 || !seen.is_multiple_of(self.summarizer.snapshot_every) {
+            Some(Ok(self.summarizer.snapshot()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Cashflow aggregates of a statement without any individual transaction.
+///
+/// Meant for sharing cashflow data with third parties (accountants, lenders,
+/// budgeting services): the report holds counts and sums per period bucket and
+/// per category, nothing else. No field can carry a description, reference,
+/// counterparty or account number, so raw transaction text never reaches the
+/// output; category names are the user's own labels from an [`AnnotationStore`].
+///
+/// # Example
+/// ```
+/// use ledger_parser::{AggregateReport, BucketPeriod, Mt940Statement, UNCATEGORIZED};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:60F:C200101EUR100,00\n\
+///             :61:200102C50,00NTRFNONREF\n:86:Salary ACME\n:62F:C200102EUR150,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let report = AggregateReport::new(statement.as_view(), BucketPeriod::Day, None);
+/// assert_eq!(report.totals.credit_total, 50.0);
+/// assert_eq!(report.categories[UNCATEGORIZED].credit_count, 1);
+///
+/// let mut output = Vec::new();
+/// report.write_to(&mut output).unwrap();
+/// assert!(!String::from_utf8(output).unwrap().contains("ACME"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateReport {
+    /// Three-letter ISO 4217 currency code of all amounts
+    pub currency: String,
+    /// Period of the buckets
+    pub period: BucketPeriod,
+    /// Totals over the whole statement
+    pub totals: Totals,
+    /// Totals per bucket, keyed by the first day of the bucket
+    pub buckets: BTreeMap<NaiveDate, Totals>,
+    /// Totals per annotated category; unannotated transactions count as [`UNCATEGORIZED`]
+    pub categories: BTreeMap<String, Totals>,
+}
+
+impl AggregateReport {
+    /// Aggregate `statement` into `period` buckets and the categories of `annotations`
+    pub fn new(
+        statement: StatementRef<'_>,
+        period: BucketPeriod,
+        annotations: Option<&AnnotationStore>,
+    ) -> Self {
+        let mut summarizer = StreamSummarizer::new(period, usize::MAX);
+        let mut categories: BTreeMap<String, Totals> = BTreeMap::new();
+        let keys = fingerprints(statement.transactions);
+        for (transaction, key) in statement.transactions.iter().zip(&keys) {
+            summarizer.push(transaction);
+            let category = annotations
+                .and_then(|store| store.get(key))
+                .and_then(|annotation| annotation.category.as_deref())
+                .unwrap_or(UNCATEGORIZED);
+            categories
+                .entry(category.to_string())
+                .or_default()
+                .add(transaction);
+        }
+
+        let snapshot = summarizer.snapshot();
+        AggregateReport {
+            currency: statement.currency.to_string(),
+            period,
+            totals: snapshot.totals,
+            buckets: snapshot.buckets,
+            categories,
+        }
+    }
+
+    /// Write the report as a pretty-printed JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::IoError` if writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), ParseError> {
+        let mut writer = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| {
+            if e.is_io() {
+                ParseError::from(std::io::Error::from(e))
+            } else {
+                ParseError::JsonError(format!("Failed to write aggregates: {}", e))
+            }
+        })?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    fn transaction(date: &str, amount: f64, transaction_type: TransactionType) -> Transaction {
+        Transaction::new(
+            text::parse_date(date).unwrap(),
+            amount,
+            transaction_type,
+            "Payment",
+        )
+    }
+
+    #[test]
+    fn test_push_buckets_and_snapshot_interval() {
+        let mut summarizer = StreamSummarizer::new(BucketPeriod::Day, 2);
+        assert!(summarizer
+            .push(&transaction("2025-03-01", 0.1, TransactionType::Credit))
+            .is_none());
+        let snapshot = summarizer
+            .push(&transaction("2025-03-01", 0.2, TransactionType::Credit))
+            .unwrap();
+        assert_eq!(snapshot.entries_seen, 2);
+        assert_eq!(snapshot.totals.credit_total, 0.3);
+
+        summarizer.push(&transaction("2025-03-02", 5.0, TransactionType::Debit));
+        let snapshot = summarizer.snapshot();
+        assert_eq!(snapshot.totals.count(), 3);
+        assert_eq!(snapshot.totals.net(), -4.7);
+        let day = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        assert_eq!(snapshot.buckets[&day].debit_count, 1);
+    }
+
+    #[test]
+    fn test_snapshots_final_batch_and_errors() {
+        let stream = vec![
+            Ok(transaction("2025-03-01", 1.0, TransactionType::Credit)),
+            Ok(transaction("2025-03-20", 2.0, TransactionType::Credit)),
+            Ok(transaction("2025-04-02", 3.0, TransactionType::Credit)),
+        ];
+        let snapshots: Vec<_> = StreamSummarizer::new(BucketPeriod::Month, 2)
+            .snapshots(stream)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].entries_seen, 3);
+        assert_eq!(snapshots[1].buckets.len(), 2);
+
+        let failing = vec![
+            Ok(transaction("2025-03-01", 1.0, TransactionType::Credit)),
+            Err(ParseError::Mt940Error("truncated".into())),
+        ];
+        let mut snapshots = StreamSummarizer::new(BucketPeriod::Day, 10).snapshots(failing);
+        assert!(matches!(
+            snapshots.next(),
+            Some(Err(ParseError::Mt940Error(_)))
+        ));
+        assert!(snapshots.next().is_none());
+    }
+
+    #[test]
+    fn test_aggregate_report_categories_without_raw_text() {
+        let transactions = vec![
+            transaction("2025-03-01", 12.5, TransactionType::Debit),
+            transaction("2025-03-01", 7.5, TransactionType::Debit),
+            transaction("2025-03-02", 100.0, TransactionType::Credit),
+        ];
+        let mut store = AnnotationStore::new();
+        let keys = fingerprints(&transactions);
+        store.set(
+            &keys[1],
+            crate::Annotation {
+                note: Some("Dinner with Alice".into()),
+                category: Some("Meals".into()),
+            },
+        );
+
+        let statement = crate::Mt940Statement {
+            transactions,
+            ..crate::Mt940Statement::from_read(
+                &mut ":20:R\n:25:ACC-123\n:60F:C250301EUR0,00\n:62F:C250302EUR80,00\n".as_bytes(),
+            )
+            .unwrap()
+        };
+        let report = AggregateReport::new(statement.as_view(), BucketPeriod::Day, Some(&store));
+        assert_eq!(report.totals.net(), 80.0);
+        assert_eq!(report.buckets.len(), 2);
+        assert_eq!(report.categories["Meals"].debit_total, 7.5);
+        assert_eq!(report.categories[UNCATEGORIZED].count(), 2);
+
+        let mut output = Vec::new();
+        report.write_to(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        for raw in ["Payment", "Alice", "ACC-123"] {
+            assert!(!output.contains(raw), "{} leaked", raw);
+        }
+        assert_eq!(
+            "month".parse::<BucketPeriod>().unwrap(),
+            BucketPeriod::Month
+        );
+    }
+}
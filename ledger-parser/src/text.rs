@@ -0,0 +1,225 @@
+//! Amounts and dates as banks write them.
+//!
+//! The format parsers read numbers and dates through these functions, so code
+//! that picks values out of bank data itself (an extra CSV column, a date in a
+//! `:86:` narrative) gets the same leniency: decimal commas, spaces between
+//! thousands, two-digit SWIFT years and dates with or without a time.
+//!
+//! Dates come back as [`DateTime<FixedOffset>`] like every date of the model;
+//! values without a time are midnight UTC.
+//!
+//! # Example
+//! ```
+//! use ledger_parser::text;
+//!
+//! assert_eq!(text::parse_amount("1 540,00").unwrap(), 1540.0);
+//! assert_eq!(text::parse_amount_with("1.234,56", ',').unwrap(), 1234.56);
+//! assert_eq!(
+//!     text::parse_yymmdd("250307").unwrap(),
+//!     text::parse_date("07.03.2025").unwrap()
+//! );
+//! ```
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Offset, Utc};
+
+use crate::formats::formats_const::{DECIMAL_SEPARATOR_COMMA, DECIMAL_SEPARATOR_DOT, ZERO_AMOUNT};
+use crate::ParseError;
+
+/// Day-first date of Russian and most European exports, e.g. `26.10.2023`
+const DMY_FORMAT: &str = "%d.%m.%Y";
+/// ISO 8601 calendar date, e.g. `2023-10-26`
+const ISO_DATE_FORMAT: &str = "%Y-%m-%d";
+/// ISO 8601 date and time without offset, e.g. `2023-10-26T12:00:00.250`
+const ISO_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+/// Expected forms named by the errors
+const EXPECTED_AMOUNT: &str = "a number such as 1540,00 or -12.5";
+const EXPECTED_YYMMDD: &str = "a YYMMDD date";
+const EXPECTED_DMY: &str = "a DD.MM.YYYY date";
+const EXPECTED_RFC3339: &str = "an RFC 3339 timestamp";
+const EXPECTED_DATE: &str = "an RFC 3339, YYYY-MM-DD or DD.MM.YYYY date";
+/// Two-digit years below this belong to the 2000s, the others to the 1900s
+const YY_CENTURY_PIVOT: i32 = 50;
+
+/// Amount with a decimal comma or point, e.g. `1540,00`, `-12.5` or `1 540,00`.
+///
+/// Spaces (including the no-break spaces of Russian and French exports) are
+/// dropped; empty text is zero, as banks leave the unused one of a debit and
+/// credit column empty. Thousands separators other than spaces are ambiguous
+/// here; use [`parse_amount_with`] when the layout is known.
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` if the text is not a number.
+pub fn parse_amount(text: &str) -> Result<f64, ParseError> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Ok(ZERO_AMOUNT);
+    }
+
+    let normalized: String = trimmed
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace(DECIMAL_SEPARATOR_COMMA, DECIMAL_SEPARATOR_DOT);
+    normalized
+        .parse()
+        .map_err(|_| invalid("amount", EXPECTED_AMOUNT, text))
+}
+
+/// Amount written with `decimal_separator`, the other of `,` and `.` being a
+/// thousands separator, e.g. `1.234,56` with `','` or `1,234.56` with `'.'`.
+///
+/// Spaces are dropped and empty text is zero, as with [`parse_amount`].
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` if the text is not a number.
+pub fn parse_amount_with(text: &str, decimal_separator: char) -> Result<f64, ParseError> {
+    let thousands = if decimal_separator == ',' { '.' } else { ',' };
+    let normalized: String = text
+        .chars()
+        .filter(|c| *c != thousands && !c.is_whitespace())
+        .map(|c| if c == decimal_separator { '.' } else { c })
+        .collect();
+    if normalized.is_empty() {
+        return Ok(ZERO_AMOUNT);
+    }
+    normalized
+        .parse()
+        .map_err(|_| invalid("amount", EXPECTED_AMOUNT, text))
+}
+
+/// SWIFT `YYMMDD` date of MT940/MT942 fields, e.g. `250307`.
+///
+/// Years `00`-`49` are 2000-2049 and `50`-`99` are 1950-1999.
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` unless the text is six digits
+/// forming a calendar date.
+pub fn parse_yymmdd(text: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    if text.len() != 6 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("date", EXPECTED_YYMMDD, text));
+    }
+    let number = |range: std::ops::Range<usize>| text[range].parse::<u32>().ok();
+    let (Some(yy), Some(month), Some(day)) = (number(0..2), number(2..4), number(4..6)) else {
+        return Err(invalid("date", EXPECTED_YYMMDD, text));
+    };
+
+    let yy = yy as i32;
+    let year = if yy < YY_CENTURY_PIVOT {
+        2000 + yy
+    } else {
+        1900 + yy
+    };
+    NaiveDate::from_ymd_opt(year, month, day)
+        .map(midnight_utc)
+        .ok_or_else(|| invalid("date", EXPECTED_YYMMDD, text))
+}
+
+/// Day-first `DD.MM.YYYY` date, e.g. `26.10.2023`.
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` if the text is not such a date.
+pub fn parse_dmy(text: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    NaiveDate::parse_from_str(text.trim(), DMY_FORMAT)
+        .map(midnight_utc)
+        .map_err(|_| invalid("date", EXPECTED_DMY, text))
+}
+
+/// RFC 3339 timestamp with offset, e.g. `2023-10-26T12:00:00+02:00`.
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` if the text is not such a timestamp.
+pub fn parse_rfc3339(text: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    DateTime::parse_from_rfc3339(text.trim()).map_err(|_| invalid("date", EXPECTED_RFC3339, text))
+}
+
+/// Date in any layout the statement formats use: RFC 3339, an ISO date and
+/// time without offset (read as UTC, fractional seconds kept), `DD.MM.YYYY`
+/// or `YYYY-MM-DD`.
+///
+/// Times are kept so the order of bookings within a day survives.
+///
+/// # Errors
+/// Returns `ParseError::UnexpectedValue` if the text matches none of them.
+pub fn parse_date(text: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Ok(date);
+    }
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(text, ISO_DATE_TIME_FORMAT) {
+        return Ok(DateTime::from_naive_utc_and_offset(date_time, Utc.fix()));
+    }
+    [DMY_FORMAT, ISO_DATE_FORMAT]
+        .into_iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .map(midnight_utc)
+        .ok_or_else(|| invalid("date", EXPECTED_DATE, text))
+}
+
+fn midnight_utc(date: NaiveDate) -> DateTime<FixedOffset> {
+    DateTime::from_naive_utc_and_offset(date.and_time(chrono::NaiveTime::MIN), Utc.fix())
+}
+
+fn invalid(field: &str, expected: &str, text: &str) -> ParseError {
+    ParseError::UnexpectedValue {
+        field: field.into(),
+        expected: expected.into(),
+        found: text.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    #[test]
+    fn test_amounts() {
+        assert_eq!(parse_amount("1540,00").unwrap(), 1540.0);
+        assert_eq!(parse_amount(" -12.5 ").unwrap(), -12.5);
+        assert_eq!(parse_amount("1 540,00").unwrap(), 1540.0);
+        assert_eq!(parse_amount("1\u{a0}540,00").unwrap(), 1540.0);
+        assert_eq!(parse_amount("").unwrap(), 0.0);
+        assert!(parse_amount("12,5 EUR").is_err());
+        assert!(matches!(
+            parse_amount("n/a"),
+            Err(ParseError::UnexpectedValue { field, found, .. }) if field == "amount" && found == "n/a"
+        ));
+
+        assert_eq!(parse_amount_with("1.234,56", ',').unwrap(), 1234.56);
+        assert_eq!(parse_amount_with("1,234.56", '.').unwrap(), 1234.56);
+        assert_eq!(parse_amount_with("-1 234,56", ',').unwrap(), -1234.56);
+        assert_eq!(parse_amount_with(" ", ',').unwrap(), 0.0);
+        assert!(parse_amount_with("1,2,3", ',').is_err());
+    }
+
+    #[test]
+    fn test_swift_and_day_first_dates() {
+        let date = parse_yymmdd("250307").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2025, 3, 7));
+        assert_eq!(parse_yymmdd("991231").unwrap().year(), 1999);
+        assert_eq!(parse_yymmdd("490101").unwrap().year(), 2049);
+        for bad in ["250230", "2503071", "25-3-7", "ab0307", ""] {
+            assert!(parse_yymmdd(bad).is_err(), "{}", bad);
+        }
+
+        assert_eq!(parse_dmy("07.03.2025").unwrap(), date);
+        assert!(parse_dmy("2025-03-07").is_err());
+        assert!(parse_dmy("31.02.2025").is_err());
+    }
+
+    #[test]
+    fn test_timestamps_and_lenient_dates() {
+        let stamp = parse_rfc3339("2025-03-07T09:15:00+02:00").unwrap();
+        assert_eq!(stamp.offset().local_minus_utc(), 2 * 3600);
+        assert!(parse_rfc3339("2025-03-07").is_err());
+
+        assert_eq!(parse_date("2025-03-07T09:15:00+02:00").unwrap(), stamp);
+        let naive = parse_date("2025-03-07T09:15:00.250").unwrap();
+        assert_eq!((naive.hour(), naive.nanosecond()), (9, 250_000_000));
+        assert_eq!(
+            parse_date("07.03.2025").unwrap(),
+            parse_date("2025-03-07").unwrap()
+        );
+        assert_eq!(parse_date("2025-03-07").unwrap().hour(), 0);
+        assert!(parse_date("03/07/2025").is_err());
+    }
+}
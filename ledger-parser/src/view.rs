@@ -0,0 +1,262 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::ops::Range;
+use std::slice::Chunks;
+
+use crate::model::{BalanceType, Money, Transaction, TransactionType};
+use crate::search::SearchHit;
+#[cfg(feature = "xlsx")]
+use crate::XlsxStatement;
+use crate::{
+    Bai2Statement, Camt052Statement, Camt053Statement, Camt054Statement, CsvStatement,
+    JsonStatement, Mt940Statement, Mt942Statement, QifStatement,
+};
+
+/// Read-only, format-independent view over a parsed statement.
+///
+/// All three format structs carry the same fields, but converting between them
+/// with `From` moves or clones every `String` and transaction. Services that only
+/// inspect and route statements can borrow a `StatementRef` instead: it is
+/// `Copy`, costs no allocation to create, and exposes the data as slices.
+///
+/// # Example
+/// ```
+/// use ledger_parser::{Mt940Statement, StatementRef};
+///
+/// let data = ":20:REF\n:25:NL81ASNB9999999999\n:28C:1/1\n:60F:C200101EUR100,00\n:62F:C200101EUR100,00\n";
+/// let statement = Mt940Statement::from_read(&mut data.as_bytes()).unwrap();
+///
+/// let view: StatementRef<'_> = statement.as_view();
+/// assert_eq!(view.account_number, "NL81ASNB9999999999");
+/// assert_eq!(view.currency, "EUR");
+/// assert!(view.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct StatementRef<'a> {
+    /// Account number (IBAN or local format)
+    pub account_number: &'a str,
+    /// Three-letter ISO 4217 currency code
+    pub currency: &'a str,
+    /// Opening balance amount at the start of the statement period
+    pub opening_balance: f64,
+    /// Date and time of the opening balance
+    pub opening_date: DateTime<FixedOffset>,
+    /// Opening balance type (Credit or Debit indicator)
+    pub opening_indicator: &'a BalanceType,
+    /// Closing balance amount at the end of the statement period
+    pub closing_balance: f64,
+    /// Date and time of the closing balance
+    pub closing_date: DateTime<FixedOffset>,
+    /// Closing balance type (Credit or Debit indicator)
+    pub closing_indicator: &'a BalanceType,
+    /// Transactions in statement order
+    pub transactions: &'a [Transaction],
+}
+
+impl<'a> StatementRef<'a> {
+    /// Number of transactions in the statement
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Whether the statement has no transactions
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Signed opening balance in the statement currency
+    pub fn opening_money(&self) -> Money {
+        Money::from_balance(self.opening_balance, self.opening_indicator, self.currency)
+    }
+
+    /// Signed closing balance in the statement currency
+    pub fn closing_money(&self) -> Money {
+        Money::from_balance(self.closing_balance, self.closing_indicator, self.currency)
+    }
+
+    /// Signed amounts of the transactions in the statement currency
+    pub fn transaction_money(&self) -> impl Iterator<Item = Money> + 'a {
+        let currency = self.currency;
+        self.transactions
+            .iter()
+            .map(move |transaction| transaction.money(currency))
+    }
+
+    /// Iterate over incoming (credit) transactions
+    pub fn credits(&self) -> impl Iterator<Item = &'a Transaction> {
+        self.transactions
+            .iter()
+            .filter(|t| t.transaction_type == TransactionType::Credit)
+    }
+
+    /// Iterate over outgoing (debit) transactions
+    pub fn debits(&self) -> impl Iterator<Item = &'a Transaction> {
+        self.transactions
+            .iter()
+            .filter(|t| t.transaction_type == TransactionType::Debit)
+    }
+
+    /// Iterate over the transactions in pages of `size` entries (the last page may be shorter).
+    ///
+    /// A `size` of 0 is treated as 1 rather than panicking like `slice::chunks`.
+    pub fn transactions_chunks(&self, size: usize) -> Chunks<'a, Transaction> {
+        self.transactions.chunks(size.max(1))
+    }
+
+    /// View restricted to the transactions in `range`.
+    ///
+    /// The range is clamped to the available transactions, so out-of-bounds
+    /// requests from a UI yield a shorter or empty page instead of panicking.
+    /// Balances are left untouched and still describe the whole statement.
+    pub fn take_range(&self, range: Range<usize>) -> StatementRef<'a> {
+        let end = range.end.min(self.transactions.len());
+        let start = range.start.min(end);
+        StatementRef {
+            transactions: &self.transactions[start..end],
+            ..*self
+        }
+    }
+}
+
+/// Implements `as_view` and `From<&Statement>` for a format struct; all three
+/// formats share the same field layout.
+macro_rules! impl_statement_view {
+    ($statement:ty) => {
+        impl $statement {
+            /// Borrow the statement as a read-only [`StatementRef`] without cloning.
+            pub fn as_view(&self) -> StatementRef<'_> {
+                StatementRef::from(self)
+            }
+
+            /// Iterate over the transactions in pages of `size` entries without cloning.
+            ///
+            /// See [`StatementRef::transactions_chunks`].
+            pub fn transactions_chunks(&self, size: usize) -> Chunks<'_, Transaction> {
+                self.as_view().transactions_chunks(size)
+            }
+
+            /// Borrow a view restricted to the transactions in `range`.
+            ///
+            /// See [`StatementRef::take_range`].
+            pub fn take_range(&self, range: Range<usize>) -> StatementRef<'_> {
+                self.as_view().take_range(range)
+            }
+
+            /// Search transaction descriptions, best match first.
+            ///
+            /// Builds a throwaway [`SearchIndex`](crate::SearchIndex); keep an index around for repeated queries.
+            pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+                self.as_view().search(query)
+            }
+        }
+
+        impl<'a> From<&'a $statement> for StatementRef<'a> {
+            fn from(statement: &'a $statement) -> Self {
+                StatementRef {
+                    account_number: &statement.account_number,
+                    currency: &statement.currency,
+                    opening_balance: statement.opening_balance,
+                    opening_date: statement.opening_date,
+                    opening_indicator: &statement.opening_indicator,
+                    closing_balance: statement.closing_balance,
+                    closing_date: statement.closing_date,
+                    closing_indicator: &statement.closing_indicator,
+                    transactions: &statement.transactions,
+                }
+            }
+        }
+    };
+}
+
+impl_statement_view!(CsvStatement);
+impl_statement_view!(Mt940Statement);
+impl_statement_view!(Mt942Statement);
+impl_statement_view!(Camt053Statement);
+impl_statement_view!(Camt052Statement);
+impl_statement_view!(Camt054Statement);
+impl_statement_view!(Bai2Statement);
+impl_statement_view!(JsonStatement);
+impl_statement_view!(QifStatement);
+#[cfg(feature = "xlsx")]
+impl_statement_view!(XlsxStatement);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+    use crate::StatementInfo;
+
+    fn statement() -> Camt053Statement {
+        let tx = |amount: f64, transaction_type: TransactionType| {
+            Transaction::new(
+                text::parse_date("2025-01-15").unwrap(),
+                amount,
+                transaction_type,
+                "Payment",
+            )
+        };
+        Camt053Statement {
+            account_number: "DK8030000001234567".into(),
+            currency: "DKK".into(),
+            opening_balance: 100.0,
+            opening_date: text::parse_date("2025-01-01").unwrap(),
+            opening_indicator: BalanceType::Credit,
+            closing_balance: 130.0,
+            closing_date: text::parse_date("2025-01-31").unwrap(),
+            closing_indicator: BalanceType::Credit,
+            transactions: vec![
+                tx(50.0, TransactionType::Credit),
+                tx(20.0, TransactionType::Debit),
+            ],
+            supplementary_data: Vec::new(),
+            info: StatementInfo::default(),
+            balances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_view_borrows_statement_fields() {
+        let statement = statement();
+        let view = statement.as_view();
+
+        assert_eq!(view.account_number, "DK8030000001234567");
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.credits().count(), 1);
+        assert_eq!(view.debits().next().map(|t| t.amount), Some(20.0));
+        assert!(std::ptr::eq(
+            view.transactions.as_ptr(),
+            statement.transactions.as_ptr()
+        ));
+    }
+
+    #[test]
+    fn test_transactions_chunks() {
+        let statement = statement();
+
+        let pages: Vec<usize> = statement.transactions_chunks(1).map(<[_]>::len).collect();
+        assert_eq!(pages, vec![1, 1]);
+        assert_eq!(statement.transactions_chunks(0).count(), 2);
+        assert_eq!(statement.transactions_chunks(10).count(), 1);
+    }
+
+    #[test]
+    fn test_take_range_clamps_bounds() {
+        let statement = statement();
+
+        let page = statement.take_range(1..2);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.transactions[0].amount, 20.0);
+        assert_eq!(page.closing_balance, 130.0);
+
+        assert_eq!(statement.take_range(1..100).len(), 1);
+        assert!(statement.take_range(5..9).is_empty());
+    }
+
+    #[test]
+    fn test_views_agree_across_formats() {
+        let camt = statement();
+        let mt940: Mt940Statement = camt.clone().into();
+
+        assert_eq!(camt.as_view(), mt940.as_view());
+    }
+}
@@ -0,0 +1,423 @@
+//! Writer/parser differential tests
+//!
+//! For every format with both a writer and a parser, random statements are
+//! written, parsed back and compared with the original. Fields a format cannot
+//! carry are masked out through its [`Capabilities`]; everything else must
+//! survive the round trip unchanged.
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone};
+use ledger_parser::*;
+
+/// Random statements generated per format
+const CASES: u64 = 200;
+/// Maximum number of transactions in a generated statement
+const MAX_TRANSACTIONS: u64 = 12;
+/// Characters outside the token alphabet put into free text: XML markup,
+/// quotes and non-ASCII letters
+const SPECIAL_CHARACTERS: &[char] = &['&', '<', '>', '"', '\'', 'é', 'ü', 'ß', 'Ж', 'я', '€'];
+
+/// Fields a format preserves through write + parse
+#[derive(Debug, Clone, Copy)]
+struct Capabilities {
+    account_number: bool,
+    currency: bool,
+    opening_balance: bool,
+    balance_dates: bool,
+    value_date: bool,
+    reference: bool,
+    counterparty_name: bool,
+    counterparty_account: bool,
+    statement_info: bool,
+    /// Description and counterparty name keep XML-special and non-ASCII characters
+    special_characters: bool,
+}
+
+/// Small deterministic xorshift generator, so failures reproduce from the seed
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn chance(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Upper-case alphanumeric token, safe in every format's character set
+    fn token(&mut self, min: u64, max: u64) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let length = min + self.below(max - min + 1);
+        (0..length)
+            .map(|_| ALPHABET[self.below(ALPHABET.len() as u64) as usize] as char)
+            .collect()
+    }
+
+    /// String of decimal digits
+    fn digits(&mut self, length: usize) -> String {
+        (0..length)
+            .map(|_| char::from(b'0' + self.below(10) as u8))
+            .collect()
+    }
+
+    /// A few space-separated words
+    fn words(&mut self) -> String {
+        let count = 1 + self.below(4);
+        (0..count)
+            .map(|_| self.token(2, 8))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Free text: [`Rng::words`], with some words holding [`SPECIAL_CHARACTERS`]
+    /// when `special`
+    fn text(&mut self, special: bool) -> String {
+        let words = self.words();
+        if !special {
+            return words;
+        }
+        words
+            .split(' ')
+            .map(|word| {
+                let mut word = word.to_string();
+                if self.chance() {
+                    let index = self.below(word.len() as u64 + 1) as usize;
+                    let special =
+                        SPECIAL_CHARACTERS[self.below(SPECIAL_CHARACTERS.len() as u64) as usize];
+                    word.insert(index, special);
+                }
+                word
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Amount in cents, up to one million
+    fn amount(&mut self) -> f64 {
+        (1 + self.below(100_000_000)) as f64 / 100.0
+    }
+}
+
+fn date(days: i64) -> DateTime<FixedOffset> {
+    let start = FixedOffset::east_opt(0)
+        .and_then(|utc| utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single())
+        .expect("valid start date");
+    start + Duration::days(days)
+}
+
+fn signed(amount: f64, indicator: &BalanceType) -> f64 {
+    match indicator {
+        BalanceType::Credit => amount,
+        BalanceType::Debit => -amount,
+    }
+}
+
+fn round(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Random statement whose closing balance matches its transactions; its free
+/// text only holds special characters when `caps` allows them
+fn random_statement(rng: &mut Rng, caps: Capabilities) -> Camt053Statement {
+    let start = rng.below(365) as i64;
+    let count = 1 + rng.below(MAX_TRANSACTIONS);
+    let mut day = start;
+    let transactions: Vec<Transaction> = (0..count)
+        .map(|_| {
+            day += rng.below(3) as i64;
+            let booking_date = date(day);
+            Transaction {
+                value_date: rng.chance().then(|| booking_date.date_naive()),
+                reference: rng.chance().then(|| rng.token(4, 16)),
+                counterparty_name: rng.chance().then(|| rng.text(caps.special_characters)),
+                counterparty_account: rng.chance().then(|| format!("DE{}", rng.token(12, 20))),
+                ..Transaction::new(
+                    booking_date,
+                    rng.amount(),
+                    if rng.chance() {
+                        TransactionType::Credit
+                    } else {
+                        TransactionType::Debit
+                    },
+                    rng.text(caps.special_characters),
+                )
+            }
+        })
+        .collect();
+
+    let opening_indicator = if rng.below(4) == 0 {
+        BalanceType::Debit
+    } else {
+        BalanceType::Credit
+    };
+    let opening_balance = rng.amount();
+    let net: f64 = transactions
+        .iter()
+        .map(|tx| match tx.transaction_type {
+            TransactionType::Credit => tx.amount,
+            TransactionType::Debit => -tx.amount,
+        })
+        .sum();
+    let closing = round(signed(opening_balance, &opening_indicator) + net);
+
+    Camt053Statement {
+        // 20 digits: the Sberbank CSV layout only recognises domestic account numbers
+        account_number: rng.digits(20),
+        currency: rng.pick(&["EUR", "USD", "RUB"]).to_string(),
+        opening_balance,
+        opening_date: date(start),
+        opening_indicator,
+        closing_balance: closing.abs(),
+        closing_date: date(day),
+        closing_indicator: if closing < 0.0 {
+            BalanceType::Debit
+        } else {
+            BalanceType::Credit
+        },
+        transactions,
+        supplementary_data: Vec::new(),
+        info: StatementInfo::default(),
+        balances: Vec::new(),
+    }
+}
+
+/// Reduce a statement to the fields `caps` says survive, with amounts in cents
+fn project(statement: &Camt053Statement, caps: Capabilities) -> Camt053Statement {
+    let mut projected = statement.clone();
+    if !caps.account_number {
+        projected.account_number.clear();
+    }
+    if !caps.currency {
+        projected.currency.clear();
+    }
+    projected.opening_balance = round(statement.opening_balance);
+    projected.closing_balance = round(statement.closing_balance);
+    if !caps.opening_balance {
+        projected.opening_balance = 0.0;
+        projected.opening_indicator = BalanceType::Credit;
+        projected.closing_balance = 0.0;
+        projected.closing_indicator = BalanceType::Credit;
+    }
+    if !caps.balance_dates {
+        projected.opening_date = date(0);
+        projected.closing_date = date(0);
+    }
+    if !caps.statement_info {
+        projected.info = StatementInfo::default();
+    }
+    for tx in &mut projected.transactions {
+        tx.amount = round(tx.amount);
+        // Random statements carry no `:61:` codes; MT940 reads back the default `NTRF`
+        tx.swift_entry = None;
+        if !caps.value_date {
+            tx.value_date = None;
+        }
+        if !caps.reference {
+            tx.reference = None;
+        }
+        if !caps.counterparty_name {
+            tx.counterparty_name = None;
+        }
+        if !caps.counterparty_account {
+            tx.counterparty_account = None;
+        }
+    }
+    projected
+}
+
+/// Every field survives
+const ALL: Capabilities = Capabilities {
+    account_number: true,
+    currency: true,
+    opening_balance: true,
+    balance_dates: true,
+    value_date: true,
+    reference: true,
+    counterparty_name: true,
+    counterparty_account: true,
+    statement_info: true,
+    special_characters: true,
+};
+
+/// Write `statement` in a format and parse it back
+type RoundTrip = fn(&Camt053Statement) -> Result<Camt053Statement, ParseError>;
+
+#[cfg(feature = "sberbank")]
+fn round_trip_csv(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let csv: CsvStatement = statement.clone().into();
+    let mut output = Vec::new();
+    csv.write_to(&mut output)?;
+    Ok(CsvStatement::from_read(&mut output.as_slice())?.into())
+}
+
+fn round_trip_mt940(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let mt940: Mt940Statement = statement.clone().into();
+    let mut output = Vec::new();
+    mt940.write_to(&mut output)?;
+    Ok(Mt940Statement::from_read(&mut output.as_slice())?.into())
+}
+
+fn round_trip_mt942(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let mt942: Mt942Statement = statement.clone().into();
+    let mut output = Vec::new();
+    mt942.write_to(&mut output)?;
+    Ok(Mt942Statement::from_read(&mut output.as_slice())?.into())
+}
+
+fn round_trip_camt053(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    // Generated statement ids and creation times would not read back as written
+    let options = Camt053WriteOptions {
+        group_header: Some(false),
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    statement.write_to_with(&mut output, &options)?;
+    Camt053Statement::from_read(&mut output.as_slice())
+}
+
+fn round_trip_camt054(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let camt054: Camt054Statement = statement.clone().into();
+    let mut output = Vec::new();
+    camt054.write_to(&mut output)?;
+    Ok(Camt054Statement::from_read(&mut output.as_slice())?.into())
+}
+
+fn round_trip_qif(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let qif: QifStatement = statement.clone().into();
+    let mut output = Vec::new();
+    qif.write_to(&mut output)?;
+    Ok(QifStatement::from_read(&mut output.as_slice())?.into())
+}
+
+fn round_trip_json(statement: &Camt053Statement) -> Result<Camt053Statement, ParseError> {
+    let json: JsonStatement = statement.clone().into();
+    let mut output = Vec::new();
+    json.write_to(&mut output)?;
+    Ok(JsonStatement::from_read(&mut output.as_slice())?.into())
+}
+
+/// Generate [`CASES`] statements and check each survives `round_trip` under `caps`
+fn check_format(name: &str, caps: Capabilities, round_trip: RoundTrip) {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let original = random_statement(&mut rng, caps);
+        let parsed = round_trip(&original)
+            .unwrap_or_else(|err| panic!("{name}: seed {seed} failed to round-trip: {err}"));
+        let (mut parsed, mut original) = (project(&parsed, caps), project(&original, caps));
+        assert_eq!(
+            parsed.transactions.len(),
+            original.transactions.len(),
+            "{name}: seed {seed} changed the number of transactions"
+        );
+        for (index, (parsed_tx, original_tx)) in parsed
+            .transactions
+            .iter()
+            .zip(&original.transactions)
+            .enumerate()
+        {
+            assert_eq!(
+                parsed_tx, original_tx,
+                "{name}: seed {seed} changed transaction {index}"
+            );
+        }
+        parsed.transactions.clear();
+        original.transactions.clear();
+        assert_eq!(
+            parsed, original,
+            "{name}: seed {seed} changed the statement header"
+        );
+    }
+}
+
+#[cfg(feature = "sberbank")]
+#[test]
+fn test_differential_csv() {
+    // The Sberbank layout has no value date or counterparty columns
+    let caps = Capabilities {
+        value_date: false,
+        counterparty_name: false,
+        counterparty_account: false,
+        ..ALL
+    };
+    check_format("csv", caps, round_trip_csv);
+}
+
+#[test]
+fn test_differential_mt940() {
+    // `:61:` carries a single date; the standard `:86:` holds only the description.
+    // `:20:` and `:28C:` are mandatory, so statements without them read back placeholders
+    let caps = Capabilities {
+        value_date: false,
+        counterparty_name: false,
+        counterparty_account: false,
+        statement_info: false,
+        ..ALL
+    };
+    check_format("mt940", caps, round_trip_mt940);
+}
+
+#[test]
+fn test_differential_camt053() {
+    check_format("camt053", ALL, round_trip_camt053);
+}
+
+#[test]
+fn test_differential_camt054() {
+    // Notifications carry no balances; they are rebuilt from the entries
+    let caps = Capabilities {
+        opening_balance: false,
+        balance_dates: false,
+        ..ALL
+    };
+    check_format("camt054", caps, round_trip_camt054);
+}
+
+#[test]
+fn test_differential_qif() {
+    // QIF has no account number, currency, value date or counterparty account,
+    // and dates its opening balance at the first transaction
+    let caps = Capabilities {
+        account_number: false,
+        currency: false,
+        balance_dates: false,
+        value_date: false,
+        counterparty_account: false,
+        ..ALL
+    };
+    check_format("qif", caps, round_trip_qif);
+}
+
+#[test]
+fn test_differential_mt942() {
+    // Interim reports carry no balances, a single date per `:61:` and only the
+    // description in `:86:`
+    let caps = Capabilities {
+        opening_balance: false,
+        balance_dates: false,
+        value_date: false,
+        counterparty_name: false,
+        counterparty_account: false,
+        ..ALL
+    };
+    check_format("mt942", caps, round_trip_mt942);
+}
+
+#[test]
+fn test_differential_json() {
+    check_format("json", ALL, round_trip_json);
+}
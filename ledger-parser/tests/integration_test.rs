@@ -3,7 +3,7 @@
 //! Tests all `From` trait implementations between format pairs and verifies
 //! data integrity through conversions and round-trip operations.
 
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate};
 use ledger_parser::*;
 
 /// Helper function to create a test MT940 statement
@@ -18,15 +18,19 @@ fn create_test_mt940() -> Mt940Statement {
         closing_date: DateTime::parse_from_rfc3339("2025-01-31T00:00:00Z").unwrap(),
         closing_indicator: BalanceType::Credit,
         transactions: vec![Transaction {
-            booking_date: DateTime::parse_from_rfc3339("2025-01-15T00:00:00Z").unwrap(),
-            value_date: Some("2025-01-15".to_string()),
-            amount: 500.25,
-            transaction_type: TransactionType::Credit,
-            description: "Payment received".to_string(),
+            value_date: NaiveDate::from_ymd_opt(2025, 1, 15),
             reference: Some("REF001".to_string()),
             counterparty_name: Some("John Doe".to_string()),
             counterparty_account: Some("DE89370400440532013111".to_string()),
+            ..Transaction::new(
+                DateTime::parse_from_rfc3339("2025-01-15T00:00:00Z").unwrap(),
+                500.25,
+                TransactionType::Credit,
+                "Payment received",
+            )
         }],
+        info: StatementInfo::default(),
+        balances: Vec::new(),
     }
 }
 
@@ -42,15 +46,20 @@ fn create_test_camt053() -> Camt053Statement {
         closing_date: DateTime::parse_from_rfc3339("2025-02-28T00:00:00Z").unwrap(),
         closing_indicator: BalanceType::Credit,
         transactions: vec![Transaction {
-            booking_date: DateTime::parse_from_rfc3339("2025-02-10T00:00:00Z").unwrap(),
-            value_date: Some("2025-02-10".to_string()),
-            amount: 750.00,
-            transaction_type: TransactionType::Debit,
-            description: "Payment sent".to_string(),
+            value_date: NaiveDate::from_ymd_opt(2025, 2, 10),
             reference: Some("CAMTREF123".to_string()),
             counterparty_name: Some("Jane Smith".to_string()),
             counterparty_account: Some("DK9876543210987654".to_string()),
+            ..Transaction::new(
+                DateTime::parse_from_rfc3339("2025-02-10T00:00:00Z").unwrap(),
+                750.00,
+                TransactionType::Debit,
+                "Payment sent",
+            )
         }],
+        supplementary_data: Vec::new(),
+        info: StatementInfo::default(),
+        balances: Vec::new(),
     }
 }
 
@@ -66,15 +75,18 @@ fn create_test_csv() -> CsvStatement {
         closing_date: DateTime::parse_from_rfc3339("2025-03-31T00:00:00Z").unwrap(),
         closing_indicator: BalanceType::Credit,
         transactions: vec![Transaction {
-            booking_date: DateTime::parse_from_rfc3339("2025-03-15T00:00:00Z").unwrap(),
-            value_date: Some("2025-03-15".to_string()),
-            amount: 500.00,
-            transaction_type: TransactionType::Debit,
-            description: "Purchase".to_string(),
+            value_date: NaiveDate::from_ymd_opt(2025, 3, 15),
             reference: Some("CSV001".to_string()),
             counterparty_name: Some("Store ABC".to_string()),
             counterparty_account: Some("40817810099910004444".to_string()),
+            ..Transaction::new(
+                DateTime::parse_from_rfc3339("2025-03-15T00:00:00Z").unwrap(),
+                500.00,
+                TransactionType::Debit,
+                "Purchase",
+            )
         }],
+        info: StatementInfo::default(),
     }
 }
 
@@ -382,6 +394,8 @@ fn test_conversion_with_empty_transactions() {
         closing_date: DateTime::parse_from_rfc3339("2025-01-31T00:00:00Z").unwrap(),
         closing_indicator: BalanceType::Credit,
         transactions: vec![],
+        info: StatementInfo::default(),
+        balances: Vec::new(),
     };
 
     let camt053: Camt053Statement = mt940.clone().into();
@@ -390,3 +404,33 @@ fn test_conversion_with_empty_transactions() {
     assert_eq!(camt053.transactions.len(), 0);
     assert_eq!(csv.transactions.len(), 0);
 }
+
+#[test]
+fn test_round_trip_qif_mt940_qif() {
+    let data = "!Type:Bank\nD01/05/2025\nT-379.00\nPCITY OF SPRINGFIELD\nMWater bill\n^\n\
+                D01/10/2025\nT250.50\nPACME Corp\nMInvoice 17\nN17\n^\n";
+    let original = QifStatement::from_read(&mut data.as_bytes()).unwrap();
+
+    let mut mt940: Mt940Statement = original.clone().into();
+    let mut output = Vec::new();
+    mt940.write_to(&mut output).unwrap();
+    mt940 = Mt940Statement::from_read(&mut output.as_slice()).unwrap();
+    let back: QifStatement = mt940.into();
+
+    assert_eq!(back.closing_balance, original.closing_balance);
+    assert_eq!(back.closing_indicator, original.closing_indicator);
+    assert_eq!(back.transactions.len(), 2);
+    assert_eq!(back.transactions[1].amount, 250.5);
+    assert_eq!(back.transactions[1].description, "Invoice 17");
+}
+
+#[cfg(feature = "xlsx")]
+#[test]
+fn test_xlsx_export_matches_csv_export() {
+    let example = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../example_files");
+    let csv = CsvStatement::from_path(example.join("example_of_account_statement.csv")).unwrap();
+    let xlsx = XlsxStatement::from_path(example.join("example_of_account_statement.xlsx")).unwrap();
+
+    let xlsx: CsvStatement = xlsx.into();
+    assert_eq!(xlsx, csv);
+}
@@ -0,0 +1,103 @@
+//! CAMT.053 test vectors in the layout of the ISO 20022 sample messages
+//!
+//! Every `<name>.xml` in `tests/fixtures/iso20022` is parsed and compared with
+//! `<name>.expected.json`: the values the sample states, written as the
+//! canonical JSON of [`JsonStatement`]. Only the keys present in the expected
+//! document are compared, so a vector pins what the standard defines without
+//! tying the test to fields the sample leaves out.
+//!
+//! Set `ISO20022_SAMPLES_DIR` to a directory of further `.xml`/`.expected.json`
+//! pairs (e.g. the sample messages downloaded from the ISO 20022 message
+//! catalogue) to check them as well.
+
+use ledger_parser::*;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming an extra directory of test vectors
+const SAMPLES_DIR_VAR: &str = "ISO20022_SAMPLES_DIR";
+/// Suffix of the expectation file next to each sample
+const EXPECTED_SUFFIX: &str = ".expected.json";
+
+/// Sample messages with their expectation files, in name order
+fn vectors(dir: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let mut samples: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+        .collect();
+    samples.sort();
+    samples
+        .into_iter()
+        .map(|sample| {
+            let stem = sample.file_stem().unwrap().to_string_lossy().into_owned();
+            let expected = sample.with_file_name(format!("{}{}", stem, EXPECTED_SUFFIX));
+            (sample, expected)
+        })
+        .collect()
+}
+
+/// Differences between `expected` and the same keys of `actual`, by JSON path
+fn mismatches(path: &str, expected: &Value, actual: &Value, found: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let child = format!("{}.{}", path, key);
+                match actual.get(key) {
+                    Some(actual) => mismatches(&child, value, actual, found),
+                    None => found.push(format!("{}: missing, expected {}", child, value)),
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                mismatches(&format!("{}[{}]", path, index), expected, actual, found);
+            }
+        }
+        (Value::Number(expected), Value::Number(actual))
+            if expected.as_f64() == actual.as_f64() => {}
+        _ if expected == actual => {}
+        _ => found.push(format!("{}: expected {}, found {}", path, expected, actual)),
+    }
+}
+
+fn check_vectors(dir: &Path) -> usize {
+    let vectors = vectors(dir);
+    for (sample, expected) in &vectors {
+        let statement = Camt053Statement::from_path(sample)
+            .unwrap_or_else(|e| panic!("{}: {}", sample.display(), e));
+        let mut json = Vec::new();
+        JsonStatement::from(statement).write_to(&mut json).unwrap();
+        let actual: Value = serde_json::from_slice(&json).unwrap();
+        let expected: Value = serde_json::from_str(
+            &fs::read_to_string(expected)
+                .unwrap_or_else(|e| panic!("{}: {}", expected.display(), e)),
+        )
+        .unwrap();
+
+        let mut found = Vec::new();
+        mismatches("$", &expected, &actual, &mut found);
+        assert!(
+            found.is_empty(),
+            "{}:\n{}",
+            sample.display(),
+            found.join("\n")
+        );
+    }
+    vectors.len()
+}
+
+#[test]
+fn test_bundled_iso20022_vectors() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/iso20022");
+    assert!(check_vectors(&dir) >= 2);
+}
+
+#[test]
+fn test_downloaded_iso20022_vectors() {
+    // Opt-in: the published samples are not redistributed with the crate
+    if let Some(dir) = std::env::var_os(SAMPLES_DIR_VAR) {
+        check_vectors(Path::new(&dir));
+    }
+}